@@ -48,6 +48,8 @@ pub enum InterfaceType {
     Resources,
     /// prompts interface
     Prompts,
+    /// custom interface (experimental capabilities and x-* methods)
+    Custom,
     /// server-io interface
     ServerIo,
     /// server-auth interface (JWT validation)
@@ -66,6 +68,7 @@ impl InterfaceType {
             Self::Tools => "tools",
             Self::Resources => "resources",
             Self::Prompts => "prompts",
+            Self::Custom => "custom",
             Self::ServerIo => "server-io",
             Self::ServerAuth => "server-auth",
             Self::Sessions => "sessions",
@@ -135,6 +138,8 @@ pub enum ComponentType {
     ResourcesMiddleware,
     /// Prompts capability middleware
     PromptsMiddleware,
+    /// Custom method middleware
+    CustomMiddleware,
     /// Server I/O component
     ServerIo,
     /// Session store component
@@ -156,6 +161,7 @@ impl ComponentType {
             Self::ToolsMiddleware => "tools-middleware",
             Self::ResourcesMiddleware => "resources-middleware",
             Self::PromptsMiddleware => "prompts-middleware",
+            Self::CustomMiddleware => "custom-middleware",
             Self::ServerIo => "server-io",
             Self::SessionStore => "session-store",
             Self::Authorization => "authorization",
@@ -267,6 +273,7 @@ mod tests {
         assert_eq!(InterfaceType::Tools.name(), "tools");
         assert_eq!(InterfaceType::Resources.name(), "resources");
         assert_eq!(InterfaceType::Prompts.name(), "prompts");
+        assert_eq!(InterfaceType::Custom.name(), "custom");
         assert_eq!(InterfaceType::ServerIo.name(), "server-io");
         assert_eq!(InterfaceType::Sessions.name(), "sessions");
         assert_eq!(InterfaceType::SessionManager.name(), "session-manager");
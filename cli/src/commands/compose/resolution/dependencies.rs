@@ -57,6 +57,7 @@ fn map_interface_to_component(interface: &str) -> Option<&'static str> {
         "tools" => Some(ComponentType::ToolsMiddleware.name()),
         "resources" => Some(ComponentType::ResourcesMiddleware.name()),
         "prompts" => Some(ComponentType::PromptsMiddleware.name()),
+        "custom" => Some(ComponentType::CustomMiddleware.name()),
         "store" if interface.contains("keyvalue") => Some(ComponentType::KvStore.name()),
         "sessions" | "session-manager" => Some(ComponentType::SessionStore.name()),
         _ => None,
@@ -422,6 +423,10 @@ mod tests {
             map_interface_to_component("wasmcp:mcp-v20251125/prompts@0.1.7"),
             Some("prompts-middleware")
         );
+        assert_eq!(
+            map_interface_to_component("wasmcp:mcp-v20251125/custom@0.1.7"),
+            Some("custom-middleware")
+        );
 
         // Test keyvalue interface
         assert_eq!(
@@ -0,0 +1,119 @@
+//! JWKS for the test keypair
+//!
+//! Publishes the test public key as a JSON Web Key Set so the JWKS path
+//! (fetch, key selection by `kid`, signature check) can be tested offline:
+//! the transport serves the set from `WASMCP_DEV_JWKS` at
+//! /.well-known/jwks.json, and the authorization component fetches it from
+//! there through `JWT_JWKS_URI`.
+
+use anyhow::{Context, Result};
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
+use rsa::RsaPublicKey;
+use rsa::pkcs8::DecodePublicKey;
+use rsa::traits::PublicKeyParts;
+use serde_json::{Value, json};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::PathBuf;
+
+use super::get_jwt_test_dir;
+
+/// Base64url-encoded modulus and exponent of an RSA public key
+fn rsa_components(public_key: &RsaPublicKey) -> (String, String) {
+    (
+        URL_SAFE_NO_PAD.encode(public_key.n().to_bytes_be()),
+        URL_SAFE_NO_PAD.encode(public_key.e().to_bytes_be()),
+    )
+}
+
+/// RFC 7638 thumbprint of an RSA key from its base64url components
+fn thumbprint(n: &str, e: &str) -> String {
+    // Required members only, in lexicographic order, without whitespace
+    let canonical = format!(r#"{{"e":"{e}","kty":"RSA","n":"{n}"}}"#);
+    URL_SAFE_NO_PAD.encode(Sha256::digest(canonical.as_bytes()))
+}
+
+/// Key ID for a public key: its RFC 7638 thumbprint
+///
+/// Derived from the key itself, so a regenerated keypair never reuses the
+/// `kid` of a key set still cached by a server.
+pub(crate) fn key_id(public_key: &RsaPublicKey) -> String {
+    let (n, e) = rsa_components(public_key);
+    thumbprint(&n, &e)
+}
+
+/// JWKS holding one RS256 signing key
+fn build_jwks(public_key: &RsaPublicKey) -> Value {
+    let (n, e) = rsa_components(public_key);
+    json!({
+        "keys": [{
+            "kty": "RSA",
+            "use": "sig",
+            "alg": "RS256",
+            "kid": thumbprint(&n, &e),
+            "n": n,
+            "e": e,
+        }]
+    })
+}
+
+/// Print the JWKS for the test public key
+///
+/// Only the key set goes to stdout, so it can be captured directly:
+/// `export WASMCP_DEV_JWKS="$(wasmcp jwt jwks)"`.
+pub fn print_jwks(public_key_path: Option<PathBuf>) -> Result<()> {
+    let key_path = match public_key_path {
+        Some(path) => path,
+        None => get_jwt_test_dir()?.join("public.pem"),
+    };
+
+    if !key_path.exists() {
+        anyhow::bail!(
+            "Public key not found at {}.\nRun 'wasmcp jwt generate-keypair' first.",
+            key_path.display()
+        );
+    }
+
+    let public_key_pem = fs::read_to_string(&key_path)
+        .with_context(|| format!("Failed to read public key from {}", key_path.display()))?;
+    let public_key =
+        RsaPublicKey::from_public_key_pem(&public_key_pem).context("Failed to parse public key")?;
+
+    eprintln!("⚠️  WARNING: FOR LOCAL TESTING ONLY - DO NOT USE IN PRODUCTION");
+    println!("{}", build_jwks(&public_key));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_thumbprint_rfc7638_example() {
+        // RFC 7638 section 3.1
+        let n = "0vx7agoebGcQSuuPiLJXZptN9nndrQmbXEps2aiAFbWhM78LhWx4cbbfAAtVT86zwu1RK7aPFFxuhDR1L6tSoc_BJECPebWKRXjBZCiFV4n3oknjhMstn64tZ_2W-5JsGY4Hc5n9yBXArwl93lqt7_RN5w6Cf0h4QyQ5v-65YGjQR0_FDW2QvzqY368QQMicAtaSqzs8KJZgnYb9c7d0zgdAZHzu6qMQvRL5hajrn1n91CbOpbISD08qNLyrdkt-bFTWhAI4vMQFh6WeZu0fM4lFd2NcRwr3XPksINHaQ-G_xBniIqbw0Ls1jF44-csFCur-kEgU8awapJzKnqDKgw";
+        assert_eq!(
+            thumbprint(n, "AQAB"),
+            "NzbLsXh8uDCcd-6MNwXF4W_7noWXFZAfHkxZsRGC9Xs"
+        );
+    }
+
+    #[test]
+    fn test_jwks_round_trip() {
+        let n = URL_SAFE_NO_PAD
+            .decode("0vx7agoebGcQSuuPiLJXZptN9nndrQmbXEps2aiAFbWhM78LhWx4cbbfAAtVT86zwu1RK7aPFFxuhDR1L6tSoc_BJECPebWKRXjBZCiFV4n3oknjhMstn64tZ_2W-5JsGY4Hc5n9yBXArwl93lqt7_RN5w6Cf0h4QyQ5v-65YGjQR0_FDW2QvzqY368QQMicAtaSqzs8KJZgnYb9c7d0zgdAZHzu6qMQvRL5hajrn1n91CbOpbISD08qNLyrdkt-bFTWhAI4vMQFh6WeZu0fM4lFd2NcRwr3XPksINHaQ-G_xBniIqbw0Ls1jF44-csFCur-kEgU8awapJzKnqDKgw")
+            .unwrap();
+        let public_key = RsaPublicKey::new(
+            rsa::BigUint::from_bytes_be(&n),
+            rsa::BigUint::from(65537u32),
+        )
+        .unwrap();
+
+        let jwks = build_jwks(&public_key);
+        let key = &jwks["keys"][0];
+        assert_eq!(key["e"], "AQAB");
+        assert_eq!(key["kid"], "NzbLsXh8uDCcd-6MNwXF4W_7noWXFZAfHkxZsRGC9Xs");
+        assert_eq!(key["kid"], key_id(&public_key).as_str());
+    }
+}
@@ -87,6 +87,11 @@ pub fn generate_keypair(force: bool) -> Result<()> {
     );
     println!("  export JWT_ISSUER=\"wasmcp-local-test\"");
     println!("  export WASMCP_AUTH_MODE=\"oauth\"");
+    println!();
+    println!("Or, to test JWKS validation, serve the key set from the server:");
+    println!("  export WASMCP_DEV_JWKS=\"$(wasmcp jwt jwks)\"");
+    println!("  export JWT_JWKS_URI=\"http://localhost:8080/.well-known/jwks.json\"");
+    println!("  export JWT_ISSUER=\"wasmcp-local-test\"");
 
     Ok(())
 }
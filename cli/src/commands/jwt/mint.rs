@@ -2,6 +2,9 @@
 
 use anyhow::{Context, Result};
 use jsonwebtoken::{Algorithm, EncodingKey, Header, encode};
+use rsa::pkcs1::DecodeRsaPrivateKey;
+use rsa::pkcs8::DecodePrivateKey;
+use rsa::{RsaPrivateKey, RsaPublicKey};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
@@ -9,6 +12,7 @@ use std::fs;
 use std::path::PathBuf;
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use super::jwks::key_id;
 use super::{format_timestamp, get_jwt_test_dir};
 
 /// JWT signing algorithm (RS256 for RSA-2048 keys)
@@ -77,6 +81,14 @@ fn parse_custom_claims(claim_strings: Vec<String>) -> Result<HashMap<String, Val
     Ok(claims)
 }
 
+/// Key ID of a PEM private key's public half (PKCS#8 or PKCS#1)
+fn signing_key_id(private_key_pem: &str) -> Option<String> {
+    let private_key = RsaPrivateKey::from_pkcs8_pem(private_key_pem)
+        .or_else(|_| RsaPrivateKey::from_pkcs1_pem(private_key_pem))
+        .ok()?;
+    Some(key_id(&RsaPublicKey::from(&private_key)))
+}
+
 /// Calculate duration until expiration
 fn time_until_expiration(exp: u64) -> String {
     let now = match SystemTime::now().duration_since(UNIX_EPOCH) {
@@ -175,8 +187,9 @@ pub fn mint_test_token(config: MintTokenConfig) -> Result<()> {
     let encoding_key = EncodingKey::from_rsa_pem(private_key_pem.as_bytes())
         .context("Failed to parse private key")?;
 
-    // Create header
-    let header = Header::new(JWT_ALGORITHM);
+    // Create header, naming the key so it can be found in `wasmcp jwt jwks`
+    let mut header = Header::new(JWT_ALGORITHM);
+    header.kid = signing_key_id(&private_key_pem);
 
     // Encode JWT
     let token = encode(&header, &claims, &encoding_key).context("Failed to encode JWT")?;
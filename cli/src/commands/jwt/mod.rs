@@ -4,6 +4,7 @@
 //! with custom claims for testing authentication and authorization patterns.
 
 mod decode;
+mod jwks;
 mod keygen;
 mod mint;
 mod storage;
@@ -87,6 +88,23 @@ pub enum JwtCommand {
         private_key: Option<PathBuf>,
     },
 
+    /// Print the test public key as a JWKS
+    ///
+    /// Outputs a JSON Web Key Set for the test keypair, keyed by the same `kid`
+    /// that `mint` puts in token headers. Serve it from the transport to test
+    /// JWKS validation end-to-end without an identity provider.
+    ///
+    /// Example:
+    ///   export WASMCP_DEV_JWKS="$(wasmcp jwt jwks)"
+    ///   export JWT_JWKS_URI=http://localhost:8080/.well-known/jwks.json
+    ///
+    /// ⚠️  WARNING: FOR LOCAL TESTING ONLY - DO NOT USE IN PRODUCTION
+    Jwks {
+        /// Path to public key (defaults to ~/.wasmcp/jwt-test/public.pem)
+        #[arg(long)]
+        public_key: Option<PathBuf>,
+    },
+
     /// Load a saved token
     ///
     /// Outputs the raw JWT token for use with export or curl.
@@ -139,6 +157,8 @@ pub async fn handle_jwt_command(command: JwtCommand) -> Result<()> {
             private_key_path: private_key,
         }),
 
+        JwtCommand::Jwks { public_key } => jwks::print_jwks(public_key),
+
         JwtCommand::LoadToken { name } => storage::load_token(&name),
 
         JwtCommand::ListTokens => storage::list_tokens(),
@@ -223,7 +223,8 @@ enum ComposeCommand {
         ///
         /// Format: --override <component>=<value>
         /// Valid components: transport, server-io, authorization, kv-store, session-store,
-        /// method-not-found, tools-middleware, resources-middleware, prompts-middleware
+        /// method-not-found, tools-middleware, resources-middleware, prompts-middleware,
+        /// custom-middleware
         ///
         /// Value types:
         ///   - Path ending in .wasm: Use custom component (local or URL)
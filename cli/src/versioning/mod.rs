@@ -315,6 +315,7 @@ mod tests {
         assert!(middleware.contains(&"tools-middleware"));
         assert!(middleware.contains(&"resources-middleware"));
         assert!(middleware.contains(&"prompts-middleware"));
+        assert!(middleware.contains(&"custom-middleware"));
 
         // Should not include non-middleware
         assert!(!middleware.contains(&"transport"));
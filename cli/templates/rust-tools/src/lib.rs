@@ -9,6 +9,8 @@ mod bindings {
     });
 }
 
+mod panics;
+
 use bindings::exports::wasmcp::mcp_v20251125::tools::Guest;
 use bindings::wasmcp::mcp_v20251125::mcp::*;
 use bindings::wasmcp::mcp_v20251125::server_handler::MessageContext;
@@ -62,14 +64,16 @@ impl Guest for Calculator {
     }
 
     fn call_tool(
-        _ctx: MessageContext,
+        ctx: MessageContext,
         request: CallToolRequest,
     ) -> Result<Option<CallToolResult>, ErrorCode> {
-        match request.name.as_str() {
-            "add" => Ok(Some(execute_operation(&request.arguments, |a, b| a + b))),
-            "subtract" => Ok(Some(execute_operation(&request.arguments, |a, b| a - b))),
-            _ => Ok(None), // We don't handle this tool
-        }
+        // A panic in a tool is reported to the client before the component traps
+        let result = panics::guard(&ctx, || match request.name.as_str() {
+            "add" => Some(execute_operation(&request.arguments, |a, b| a + b)),
+            "subtract" => Some(execute_operation(&request.arguments, |a, b| a - b)),
+            _ => None, // We don't handle this tool
+        });
+        Ok(result)
     }
 }
 
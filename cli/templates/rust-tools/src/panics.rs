@@ -0,0 +1,85 @@
+//! Panic reporting for tool calls
+//!
+//! wasm32-wasip2 has no unwinding, so a panic traps the component and the
+//! client sees the transport's error for the failed call, not a tool result.
+//! Tool bodies run inside `guard(&ctx, ..)`, whose panic hook runs before
+//! the trap: it sends the client an error log with a hash of the panic's
+//! message and location, and writes the details to stderr under the same
+//! hash, so a report can be matched without the client seeing them.
+
+use crate::bindings::wasmcp::mcp_v20251125::mcp::{
+    LogLevel, LoggingMessageNotification, ServerMessage, ServerNotification,
+};
+use crate::bindings::wasmcp::mcp_v20251125::server_handler::MessageContext;
+use crate::bindings::wasmcp::mcp_v20251125::server_io;
+use std::cell::Cell;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::panic::{self, PanicHookInfo};
+use std::sync::Once;
+
+static INIT: Once = Once::new();
+
+thread_local! {
+    /// Context of the call being handled, for the panic hook
+    static CONTEXT: Cell<Option<*const MessageContext<'static>>> = const { Cell::new(None) };
+}
+
+/// Run a tool body, reporting a panic in it to the client
+pub fn guard<T>(ctx: &MessageContext, f: impl FnOnce() -> T) -> T {
+    INIT.call_once(|| panic::set_hook(Box::new(report_panic)));
+
+    // Without unwinding, `f` either returns or traps, so the pointer never
+    // outlives `ctx`
+    let ctx = std::ptr::from_ref(ctx).cast::<MessageContext<'static>>();
+    let previous = CONTEXT.with(|c| c.replace(Some(ctx)));
+    let result = f();
+    CONTEXT.with(|c| c.set(previous));
+    result
+}
+
+fn report_panic(info: &PanicHookInfo<'_>) {
+    let message = match info.payload().downcast_ref::<&str>() {
+        Some(s) => s.to_string(),
+        None => match info.payload().downcast_ref::<String>() {
+            Some(s) => s.clone(),
+            None => "Box<dyn Any>".to_string(),
+        },
+    };
+    let location = info
+        .location()
+        .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()))
+        .unwrap_or_default();
+
+    let mut hasher = DefaultHasher::new();
+    message.hash(&mut hasher);
+    location.hash(&mut hasher);
+    let hash = hasher.finish();
+    eprintln!("[{{project_name}}] panic {:016x} at {}: {}", hash, location, message);
+
+    let Some(ctx) = CONTEXT.with(Cell::get) else {
+        return;
+    };
+    // SAFETY: set only while `guard` runs, and the panicking code runs inside it
+    let ctx = unsafe { &*ctx };
+
+    // Plain JSON responses carry only the result, and the client may have
+    // asked for less than errors
+    let framed = !ctx.frame.prefix.is_empty() || !ctx.frame.suffix.is_empty();
+    let muted = matches!(
+        ctx.log_level,
+        Some(LogLevel::Critical | LogLevel::Alert | LogLevel::Emergency)
+    );
+    let Some(stream) = ctx.client_stream.filter(|_| framed && !muted) else {
+        return;
+    };
+    let _ = server_io::send_message(
+        stream,
+        ServerMessage::Notification(ServerNotification::Log(LoggingMessageNotification {
+            data: format!("Tool call failed unexpectedly (panic {:016x})", hash),
+            level: LogLevel::Error,
+            logger: Some("panic".to_string()),
+        })),
+        &ctx.frame,
+    );
+}
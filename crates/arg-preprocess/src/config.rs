@@ -0,0 +1,129 @@
+//! Configuration for the argument preprocessing middleware
+//!
+//! Environment variables:
+//! - `WASMCP_ARG_PREPROCESS`: Operations per tool argument (required)
+//!   - JSON object keyed by tool name, then argument name:
+//!     `{"search": {"query": "trim, lowercase, max_len=200", "limit": "coerce"}}`
+//!   - Operations: `trim`, `lowercase`, `uppercase`, `collapse_whitespace`,
+//!     `max_len=N`, `coerce` and `coerce=integer|number|boolean|string`
+
+use crate::bindings::wasi::cli::environment::get_environment;
+use crate::preprocess::{self, Op};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+/// Operations on each argument of a tool
+pub type ToolRules = Vec<(String, Vec<Op>)>;
+
+/// Argument preprocessing configuration from environment variables
+#[derive(Debug, Clone)]
+pub struct PreprocessConfig {
+    pub tools: HashMap<String, ToolRules>,
+}
+
+impl PreprocessConfig {
+    /// Load configuration from environment variables
+    pub fn from_env() -> Self {
+        let env_map: HashMap<String, String> = get_environment().into_iter().collect();
+
+        let tools = match env_map.get("WASMCP_ARG_PREPROCESS") {
+            Some(v) => {
+                let (tools, warnings) = parse_rules(v);
+                for warning in warnings {
+                    eprintln!("[arg-preprocess] WARNING: {}", warning);
+                }
+                tools
+            }
+            None => HashMap::new(),
+        };
+
+        PreprocessConfig { tools }
+    }
+
+    /// The argument operations of a tool, if it has any
+    pub fn rules(&self, tool: &str) -> Option<&ToolRules> {
+        self.tools.get(tool)
+    }
+}
+
+/// Parse the rules object into operations per tool
+///
+/// An argument with an invalid operation list is reported and skipped, so
+/// one typo doesn't disable the other rules.
+pub fn parse_rules(value: &str) -> (HashMap<String, ToolRules>, Vec<String>) {
+    let mut warnings = Vec::new();
+    let object: Map<String, Value> = match serde_json::from_str(value.trim()) {
+        Ok(object) => object,
+        Err(e) => {
+            warnings.push(format!(
+                "Invalid WASMCP_ARG_PREPROCESS JSON: {}. No arguments preprocessed.",
+                e
+            ));
+            return (HashMap::new(), warnings);
+        }
+    };
+
+    let mut tools = HashMap::new();
+    for (tool, arguments) in object {
+        let Value::Object(arguments) = arguments else {
+            warnings.push(format!("Rules for {} must be an object", tool));
+            continue;
+        };
+        let mut rules = Vec::new();
+        for (argument, spec) in arguments {
+            let parsed = spec
+                .as_str()
+                .ok_or_else(|| "operations must be a string".to_string())
+                .and_then(preprocess::parse_ops);
+            match parsed {
+                Ok(ops) if !ops.is_empty() => rules.push((argument, ops)),
+                Ok(_) => {}
+                Err(e) => warnings.push(format!("Skipping {}.{}: {}", tool, argument, e)),
+            }
+        }
+        if !rules.is_empty() {
+            tools.insert(tool, rules);
+        }
+    }
+
+    (tools, warnings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rules() {
+        let (tools, warnings) = parse_rules(
+            r#"{
+                "search": {"query": "trim, max_len=200", "limit": "coerce=integer", "page": 1},
+                "lookup": {"id": "slugify"},
+                "broken": "trim"
+            }"#,
+        );
+        assert_eq!(tools.len(), 1);
+        assert_eq!(
+            tools["search"],
+            [
+                (
+                    "limit".to_string(),
+                    vec![Op::Coerce(Some(preprocess::Target::Integer))]
+                ),
+                ("query".to_string(), vec![Op::Trim, Op::MaxLen(200)]),
+            ]
+        );
+        assert_eq!(
+            warnings,
+            [
+                "Rules for broken must be an object",
+                "Skipping lookup.id: Unknown operation 'slugify'",
+                "Skipping search.page: operations must be a string",
+            ]
+        );
+
+        let (tools, warnings) = parse_rules("{not json");
+        assert!(tools.is_empty());
+        assert_eq!(warnings.len(), 1);
+    }
+}
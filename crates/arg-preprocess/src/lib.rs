@@ -0,0 +1,92 @@
+//! Argument Preprocessing Middleware Component
+//!
+//! Cleans up tool call arguments before handlers see them, so common
+//! sanitization doesn't clutter tool code. For every tool argument listed in
+//! `WASMCP_ARG_PREPROCESS`, this component:
+//! - Trims, collapses whitespace, lowercases or uppercases string values
+//! - Truncates strings to a maximum number of characters
+//! - Coerces values sent with the wrong JSON type, such as numbers sent as
+//!   strings, to the type the handler expects
+//! - Forwards the call with the rewritten arguments
+//! - Delegates everything else downstream unchanged
+
+#![allow(warnings)]
+
+mod bindings {
+    wit_bindgen::generate!({
+        world: "arg-preprocess",
+        generate_all,
+    });
+}
+
+mod config;
+mod preprocess;
+
+use bindings::exports::wasmcp::mcp_v20251125::server_handler::{Guest, MessageContext};
+use bindings::wasmcp::mcp_v20251125::mcp::*;
+use bindings::wasmcp::mcp_v20251125::server_handler as downstream;
+use config::PreprocessConfig;
+use serde_json::{Map, Value};
+
+struct ArgPreprocess;
+
+// Convert exported MessageContext to imported MessageContext
+fn to_downstream_ctx<'a>(ctx: &'a MessageContext<'a>) -> downstream::MessageContext<'a> {
+    downstream::MessageContext {
+        client_stream: ctx.client_stream,
+        protocol_version: ctx.protocol_version.clone(),
+        session: ctx.session.as_ref().map(|s| downstream::Session {
+            session_id: s.session_id.clone(),
+            store_id: s.store_id.clone(),
+        }),
+        identity: ctx.identity.as_ref().map(|i| downstream::Identity {
+            jwt: i.jwt.clone(),
+            claims: i.claims.clone(),
+        }),
+        frame: ctx.frame.clone(),
+        http_context: ctx.http_context.clone(),
+        client_capabilities: ctx.client_capabilities.clone(),
+        request_id: ctx.request_id.clone(),
+        deadline: ctx.deadline,
+        log_level: ctx.log_level,
+        client_info: ctx.client_info.clone(),
+        locale: ctx.locale.clone(),
+        trace_context: ctx.trace_context.clone(),
+    }
+}
+
+impl Guest for ArgPreprocess {
+    fn handle(
+        ctx: MessageContext,
+        message: ClientMessage,
+    ) -> Option<Result<ServerResult, ErrorCode>> {
+        let message = match message {
+            ClientMessage::Request((request_id, ClientRequest::ToolsCall(mut call))) => {
+                let config = PreprocessConfig::from_env();
+                if let Some(rules) = config.rules(&call.name) {
+                    call.arguments = preprocess_arguments(call.arguments, rules);
+                }
+                ClientMessage::Request((request_id, ClientRequest::ToolsCall(call)))
+            }
+            other => other,
+        };
+        downstream::handle(&to_downstream_ctx(&ctx), message)
+    }
+}
+
+/// Apply a tool's rules to its JSON-encoded arguments
+///
+/// Arguments that aren't a JSON object are forwarded unchanged, for the
+/// handler to reject.
+fn preprocess_arguments(arguments: Option<String>, rules: &config::ToolRules) -> Option<String> {
+    let json = arguments?;
+    match serde_json::from_str::<Map<String, Value>>(&json) {
+        Ok(mut object) => {
+            preprocess::apply(&mut object, rules);
+            Some(Value::Object(object).to_string())
+        }
+        Err(_) => Some(json),
+    }
+}
+
+bindings::export!(ArgPreprocess with_types_in bindings);
@@ -0,0 +1,255 @@
+//! Argument operations
+//!
+//! Each argument has a list of operations written like an attribute,
+//! e.g. `trim, lowercase, max_len=200` or `coerce=integer`, applied in order.
+//! String operations also apply to each string in an array.
+
+use serde_json::{Map, Number, Value};
+
+/// Type a value is coerced to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Target {
+    Integer,
+    Number,
+    Boolean,
+    String,
+}
+
+/// An operation on one argument
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    /// Remove leading and trailing whitespace
+    Trim,
+    Lowercase,
+    Uppercase,
+    /// Replace runs of whitespace with a single space
+    CollapseWhitespace,
+    /// Truncate to at most this many characters
+    MaxLen(usize),
+    /// Convert to a type; without one, strings that read as a number or
+    /// boolean become one
+    Coerce(Option<Target>),
+}
+
+/// Parse an operation list such as `trim, lowercase, max_len=200`
+pub fn parse_ops(spec: &str) -> Result<Vec<Op>, String> {
+    spec.split(',')
+        .map(str::trim)
+        .filter(|op| !op.is_empty())
+        .map(parse_op)
+        .collect()
+}
+
+fn parse_op(op: &str) -> Result<Op, String> {
+    let (name, value) = match op.split_once('=') {
+        Some((name, value)) => (name.trim(), Some(value.trim())),
+        None => (op, None),
+    };
+    match (name, value) {
+        ("trim", None) => Ok(Op::Trim),
+        ("lowercase", None) => Ok(Op::Lowercase),
+        ("uppercase", None) => Ok(Op::Uppercase),
+        ("collapse_whitespace", None) => Ok(Op::CollapseWhitespace),
+        ("max_len", Some(n)) => n
+            .parse::<usize>()
+            .map(Op::MaxLen)
+            .map_err(|_| format!("Invalid max_len '{}'", n)),
+        ("coerce", None) => Ok(Op::Coerce(None)),
+        ("coerce", Some(target)) => match target {
+            "integer" => Ok(Op::Coerce(Some(Target::Integer))),
+            "number" => Ok(Op::Coerce(Some(Target::Number))),
+            "boolean" => Ok(Op::Coerce(Some(Target::Boolean))),
+            "string" => Ok(Op::Coerce(Some(Target::String))),
+            _ => Err(format!("Unknown coerce type '{}'", target)),
+        },
+        _ => Err(format!("Unknown operation '{}'", op)),
+    }
+}
+
+/// Apply each argument's operations to an arguments object
+///
+/// Arguments that are absent are left absent, and values an operation
+/// doesn't apply to (e.g. `trim` on a number, `coerce=integer` on "abc")
+/// are left for the handler to validate.
+pub fn apply(arguments: &mut Map<String, Value>, rules: &[(String, Vec<Op>)]) {
+    for (name, ops) in rules {
+        if let Some(value) = arguments.get_mut(name) {
+            for op in ops {
+                apply_op(value, *op);
+            }
+        }
+    }
+}
+
+fn apply_op(value: &mut Value, op: Op) {
+    if let Value::Array(items) = value {
+        for item in items {
+            apply_op(item, op);
+        }
+        return;
+    }
+    match op {
+        Op::Coerce(target) => {
+            if let Some(coerced) = coerce(value, target) {
+                *value = coerced;
+            }
+        }
+        _ => {
+            if let Value::String(s) = value {
+                *s = apply_str(s, op);
+            }
+        }
+    }
+}
+
+fn apply_str(s: &str, op: Op) -> String {
+    match op {
+        Op::Trim => s.trim().to_string(),
+        Op::Lowercase => s.to_lowercase(),
+        Op::Uppercase => s.to_uppercase(),
+        Op::CollapseWhitespace => s.split_whitespace().collect::<Vec<_>>().join(" "),
+        Op::MaxLen(max) => s.chars().take(max).collect(),
+        Op::Coerce(_) => s.to_string(),
+    }
+}
+
+/// The value converted to a type, if it reads as one
+fn coerce(value: &Value, target: Option<Target>) -> Option<Value> {
+    match (value, target) {
+        (Value::String(s), None) => {
+            let s = s.trim();
+            parse_bool(s)
+                .map(Value::Bool)
+                .or_else(|| parse_integer(s))
+                .or_else(|| parse_number(s))
+        }
+        (Value::String(s), Some(Target::Integer)) => parse_integer(s.trim()),
+        (Value::String(s), Some(Target::Number)) => {
+            parse_integer(s.trim()).or_else(|| parse_number(s.trim()))
+        }
+        (Value::String(s), Some(Target::Boolean)) => parse_bool(s.trim()).map(Value::Bool),
+        // Whole floats such as 3.0 are integers to a schema
+        (Value::Number(n), Some(Target::Integer)) => n
+            .as_f64()
+            .filter(|f| f.fract() == 0.0 && f.abs() < i64::MAX as f64)
+            .map(|f| Value::from(f as i64)),
+        (Value::Number(n), Some(Target::String)) => Some(Value::String(n.to_string())),
+        (Value::Bool(b), Some(Target::String)) => Some(Value::String(b.to_string())),
+        _ => None,
+    }
+}
+
+fn parse_bool(s: &str) -> Option<bool> {
+    match s {
+        "true" => Some(true),
+        "false" => Some(false),
+        _ => None,
+    }
+}
+
+fn parse_integer(s: &str) -> Option<Value> {
+    s.parse::<i64>()
+        .map(Value::from)
+        .or_else(|_| s.parse::<u64>().map(Value::from))
+        .ok()
+}
+
+fn parse_number(s: &str) -> Option<Value> {
+    s.parse::<f64>()
+        .ok()
+        .and_then(Number::from_f64)
+        .map(Value::Number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn run(arguments: Value, rules: &[(&str, &str)]) -> Value {
+        let rules: Vec<(String, Vec<Op>)> = rules
+            .iter()
+            .map(|(name, spec)| (name.to_string(), parse_ops(spec).unwrap()))
+            .collect();
+        let mut arguments = arguments.as_object().unwrap().clone();
+        apply(&mut arguments, &rules);
+        Value::Object(arguments)
+    }
+
+    #[test]
+    fn test_parse_ops() {
+        assert_eq!(
+            parse_ops("trim, lowercase, max_len = 200, coerce").unwrap(),
+            [Op::Trim, Op::Lowercase, Op::MaxLen(200), Op::Coerce(None)]
+        );
+        assert_eq!(
+            parse_ops("coerce=integer").unwrap(),
+            [Op::Coerce(Some(Target::Integer))]
+        );
+        assert!(parse_ops("max_len=many").is_err());
+        assert!(parse_ops("coerce=date").is_err());
+        assert!(parse_ops("trim=yes").is_err());
+        assert!(parse_ops("slugify").is_err());
+    }
+
+    #[test]
+    fn test_string_ops() {
+        let result = run(
+            json!({ "q": "  Hello   World  ", "tags": [" A ", "b"], "n": 5 }),
+            &[
+                ("q", "trim, collapse_whitespace, lowercase, max_len=8"),
+                ("tags", "trim, uppercase"),
+                ("n", "trim"),
+                ("missing", "trim"),
+            ],
+        );
+        assert_eq!(
+            result,
+            json!({ "q": "hello wo", "tags": ["A", "B"], "n": 5 })
+        );
+    }
+
+    #[test]
+    fn test_max_len_counts_chars() {
+        let result = run(json!({ "q": "héllo" }), &[("q", "max_len=2")]);
+        assert_eq!(result["q"], "hé");
+    }
+
+    #[test]
+    fn test_coerce_inferred() {
+        let result = run(
+            json!({ "a": " 42 ", "b": "-2.5", "c": "true", "d": "abc", "e": 7, "f": ["1", "x"] }),
+            &[
+                ("a", "coerce"),
+                ("b", "coerce"),
+                ("c", "coerce"),
+                ("d", "coerce"),
+                ("e", "coerce"),
+                ("f", "coerce"),
+            ],
+        );
+        assert_eq!(
+            result,
+            json!({ "a": 42, "b": -2.5, "c": true, "d": "abc", "e": 7, "f": [1, "x"] })
+        );
+    }
+
+    #[test]
+    fn test_coerce_typed() {
+        let result = run(
+            json!({ "a": "3", "b": 3.0, "c": 3.5, "d": 12, "e": "1", "f": "yes" }),
+            &[
+                ("a", "coerce=number"),
+                ("b", "coerce=integer"),
+                ("c", "coerce=integer"),
+                ("d", "coerce=string"),
+                ("e", "coerce=boolean"),
+                ("f", "coerce=boolean"),
+            ],
+        );
+        assert_eq!(
+            result,
+            json!({ "a": 3, "b": 3, "c": 3.5, "d": "12", "e": "1", "f": "yes" })
+        );
+    }
+}
@@ -3,11 +3,17 @@
 use crate::error::{AuthError, Result};
 use serde::{Deserialize, Serialize};
 
+/// Default clock skew tolerance in seconds
+const DEFAULT_CLOCK_SKEW_SECS: u64 = 60;
+
 /// Main configuration structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
-    /// JWT provider configuration
-    pub provider: JwtProvider,
+    /// Trusted JWT providers: the `JWT_*` provider first, then `JWT_TRUSTED_ISSUERS`
+    pub providers: Vec<JwtProvider>,
+
+    /// Time claim checks applied to every provider's tokens
+    pub time: TimeValidation,
 
     /// Optional policy for fine-grained authorization
     pub policy: Option<String>,
@@ -20,6 +26,7 @@ pub struct Config {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JwtProvider {
     /// JWT issuer URL (optional - empty means no issuer validation)
+    #[serde(default)]
     pub issuer: String,
 
     /// Static public key (PEM format) - mutually exclusive with jwks_uri
@@ -29,6 +36,7 @@ pub struct JwtProvider {
     pub jwks_uri: Option<String>,
 
     /// Expected audience(s) (required for security)
+    #[serde(default)]
     pub audience: Vec<String>,
 
     /// JWT signing algorithm (defaults to RS256)
@@ -36,6 +44,69 @@ pub struct JwtProvider {
 
     /// Required scopes for all requests
     pub required_scopes: Option<Vec<String>>,
+
+    /// Claims copied into custom claims under stable names
+    #[serde(default)]
+    pub claim_mappings: Vec<ClaimMapping>,
+}
+
+/// How `exp`, `nbf` and `iat` are checked
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimeValidation {
+    /// Clock skew tolerated by every time check, in seconds
+    pub leeway: u64,
+
+    /// Reject tokens used before their `nbf`
+    pub validate_nbf: bool,
+
+    /// Reject tokens whose `iat` is in the future
+    pub validate_iat: bool,
+
+    /// Reject tokens issued longer ago than this many seconds, whatever their `exp`
+    pub max_token_age: Option<u64>,
+}
+
+impl Default for TimeValidation {
+    fn default() -> Self {
+        Self {
+            leeway: DEFAULT_CLOCK_SKEW_SECS,
+            validate_nbf: true,
+            validate_iat: false,
+            max_token_age: None,
+        }
+    }
+}
+
+/// Copy of one JWT claim into the custom claims handlers see
+///
+/// Lets handlers read e.g. `tenant` whether the identity provider calls it
+/// `org_id`, `tid` or `https://example.com/org`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClaimMapping {
+    /// Claim to read: an exact claim name, or a dotted path into nested objects
+    pub from: String,
+
+    /// Custom claim name to store it under
+    pub to: String,
+
+    /// How to coerce the value
+    #[serde(default, rename = "type")]
+    pub claim_type: ClaimType,
+}
+
+/// Target type of a mapped claim (values are stored as strings)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ClaimType {
+    /// Strings as-is, anything else as JSON
+    #[default]
+    String,
+    /// A number, from a JSON number or a numeric string
+    Number,
+    /// "true"/"false", from a boolean, "true"/"false"/"1"/"0" or 0/1
+    Bool,
+    /// Comma-separated, from an array or a space- or comma-separated string
+    List,
 }
 
 impl Config {
@@ -54,13 +125,6 @@ impl Config {
         let public_key = get_env("JWT_PUBLIC_KEY");
         let jwks_uri = get_env("JWT_JWKS_URI");
 
-        // Require at least one
-        if public_key.is_none() && jwks_uri.is_none() {
-            return Err(AuthError::Configuration(
-                "Either JWT_PUBLIC_KEY or JWT_JWKS_URI is required".to_string(),
-            ));
-        }
-
         // Don't allow both
         if public_key.is_some() && jwks_uri.is_some() {
             return Err(AuthError::Configuration(
@@ -80,23 +144,152 @@ impl Config {
         // Load required scopes (optional)
         let required_scopes = get_env("JWT_REQUIRED_SCOPES").map(|s| parse_comma_separated(&s));
 
+        // Load claim mappings (optional)
+        let claim_mappings = match get_env("JWT_CLAIM_MAPPINGS") {
+            Some(json) => serde_json::from_str(&json).map_err(|e| {
+                AuthError::Configuration(format!("Invalid JWT_CLAIM_MAPPINGS: {e}"))
+            })?,
+            None => Vec::new(),
+        };
+
+        let primary = JwtProvider {
+            issuer,
+            public_key,
+            jwks_uri,
+            audience,
+            algorithm,
+            required_scopes,
+            claim_mappings,
+        };
+
+        // Additional identity providers (e.g. staging and prod, partner orgs)
+        let trusted_issuers = match get_env("JWT_TRUSTED_ISSUERS") {
+            Some(json) => parse_trusted_issuers(&json, &primary)?,
+            None => Vec::new(),
+        };
+
+        let mut providers = Vec::new();
+        if primary.public_key.is_some() || primary.jwks_uri.is_some() {
+            providers.push(primary);
+        }
+        providers.extend(trusted_issuers);
+
+        // Require at least one
+        if providers.is_empty() {
+            return Err(AuthError::Configuration(
+                "Either JWT_PUBLIC_KEY, JWT_JWKS_URI or JWT_TRUSTED_ISSUERS is required"
+                    .to_string(),
+            ));
+        }
+
+        let time = load_time_validation()?;
+
         // Load optional policy configuration
         let policy = get_env("POLICY");
         let policy_data = get_env("POLICY_DATA");
 
         Ok(Self {
-            provider: JwtProvider {
-                issuer,
-                public_key,
-                jwks_uri,
-                audience,
-                algorithm,
-                required_scopes,
-            },
+            providers,
+            time,
             policy,
             policy_data,
         })
     }
+
+    /// Provider that verifies tokens from `issuer`
+    ///
+    /// A provider configured for that exact issuer wins; otherwise the first
+    /// provider without an issuer (which accepts any) is used.
+    pub fn provider_for(&self, issuer: Option<&str>) -> Option<&JwtProvider> {
+        self.providers
+            .iter()
+            .find(|p| !p.issuer.is_empty() && Some(p.issuer.as_str()) == issuer)
+            .or_else(|| self.providers.iter().find(|p| p.issuer.is_empty()))
+    }
+}
+
+/// Parse `JWT_TRUSTED_ISSUERS`, a JSON array of providers
+///
+/// Each entry needs an `issuer` and exactly one of `jwks_uri` or
+/// `public_key`. `audience`, `algorithm`, `required_scopes` and
+/// `claim_mappings` are optional; the last three default to those of the
+/// `JWT_*` provider (`JWT_ALGORITHM`, `JWT_REQUIRED_SCOPES`,
+/// `JWT_CLAIM_MAPPINGS`).
+fn parse_trusted_issuers(json: &str, defaults: &JwtProvider) -> Result<Vec<JwtProvider>> {
+    let providers: Vec<JwtProvider> = serde_json::from_str(json)
+        .map_err(|e| AuthError::Configuration(format!("Invalid JWT_TRUSTED_ISSUERS: {e}")))?;
+
+    providers
+        .into_iter()
+        .map(|mut provider| {
+            if provider.issuer.is_empty() {
+                return Err(AuthError::Configuration(
+                    "Every JWT_TRUSTED_ISSUERS entry needs an issuer".to_string(),
+                ));
+            }
+            provider.issuer = normalize_issuer(provider.issuer)?;
+
+            if provider.public_key.is_some() == provider.jwks_uri.is_some() {
+                return Err(AuthError::Configuration(format!(
+                    "JWT_TRUSTED_ISSUERS entry '{}' needs exactly one of jwks_uri or public_key",
+                    provider.issuer
+                )));
+            }
+
+            if provider.algorithm.is_none() {
+                provider.algorithm = defaults.algorithm.clone();
+            }
+            if provider.required_scopes.is_none() {
+                provider.required_scopes = defaults.required_scopes.clone();
+            }
+            if provider.claim_mappings.is_empty() {
+                provider.claim_mappings = defaults.claim_mappings.clone();
+            }
+            Ok(provider)
+        })
+        .collect()
+}
+
+/// Load time claim checks
+///
+/// - `JWT_CLOCK_SKEW_SECS`: Tolerated clock skew (default: 60)
+/// - `JWT_VALIDATE_NBF`: "true"/"false" (default: true)
+/// - `JWT_VALIDATE_IAT`: "true"/"false" (default: false)
+/// - `JWT_MAX_TOKEN_AGE_SECS`: Positive integer (optional, default: no limit)
+fn load_time_validation() -> Result<TimeValidation> {
+    let defaults = TimeValidation::default();
+
+    let leeway = match get_env("JWT_CLOCK_SKEW_SECS") {
+        Some(v) => v
+            .trim()
+            .parse::<u64>()
+            .map_err(|_| AuthError::Configuration(format!("Invalid JWT_CLOCK_SKEW_SECS: {v}")))?,
+        None => defaults.leeway,
+    };
+
+    let max_token_age = match get_env("JWT_MAX_TOKEN_AGE_SECS") {
+        Some(v) => Some(
+            v.trim()
+                .parse::<u64>()
+                .ok()
+                .filter(|age| *age > 0)
+                .ok_or_else(|| {
+                    AuthError::Configuration(format!("Invalid JWT_MAX_TOKEN_AGE_SECS: {v}"))
+                })?,
+        ),
+        None => None,
+    };
+
+    Ok(TimeValidation {
+        leeway,
+        validate_nbf: get_env("JWT_VALIDATE_NBF")
+            .map(|v| v.to_lowercase() != "false")
+            .unwrap_or(defaults.validate_nbf),
+        validate_iat: get_env("JWT_VALIDATE_IAT")
+            .map(|v| v.to_lowercase() == "true")
+            .unwrap_or(defaults.validate_iat),
+        max_token_age,
+    })
 }
 
 /// Get environment variable (using WIT bindings)
@@ -157,4 +350,126 @@ mod tests {
         let result = normalize_issuer("http://example.com".to_string());
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_parse_trusted_issuers() {
+        let json = r#"[
+            {"issuer": "https://staging.example.com/", "jwks_uri": "https://staging.example.com/jwks"},
+            {"issuer": "partner", "public_key": "PEM", "audience": ["mcp"], "required_scopes": []}
+        ]"#;
+        let scopes = vec!["mcp:read".to_string()];
+        let defaults = JwtProvider {
+            algorithm: Some("ES256".to_string()),
+            required_scopes: Some(scopes.clone()),
+            ..provider("")
+        };
+        let providers = parse_trusted_issuers(json, &defaults).unwrap();
+
+        assert_eq!(providers[0].issuer, "https://staging.example.com");
+        assert_eq!(providers[0].algorithm.as_deref(), Some("ES256"));
+        assert_eq!(providers[0].required_scopes, Some(scopes));
+        assert!(providers[0].audience.is_empty());
+        assert_eq!(providers[1].audience, vec!["mcp"]);
+        assert_eq!(providers[1].required_scopes, Some(vec![]));
+
+        // Issuer and exactly one key source are required
+        let parse = |json| parse_trusted_issuers(json, &defaults);
+        assert!(parse(r#"[{"jwks_uri": "https://a/jwks"}]"#).is_err());
+        assert!(parse(r#"[{"issuer": "a"}]"#).is_err());
+        assert!(parse("not json").is_err());
+    }
+
+    #[test]
+    fn test_parse_claim_mappings() {
+        let json = r#"[
+            {"issuer": "a", "public_key": "PEM", "claim_mappings": [
+                {"from": "org.id", "to": "tenant", "type": "number"},
+                {"from": "email", "to": "user_email"}
+            ]},
+            {"issuer": "b", "public_key": "PEM"}
+        ]"#;
+        let defaults = JwtProvider {
+            claim_mappings: vec![ClaimMapping {
+                from: "tid".to_string(),
+                to: "tenant".to_string(),
+                claim_type: ClaimType::String,
+            }],
+            ..provider("")
+        };
+        let providers = parse_trusted_issuers(json, &defaults).unwrap();
+
+        assert_eq!(providers[0].claim_mappings.len(), 2);
+        assert_eq!(providers[0].claim_mappings[0].claim_type, ClaimType::Number);
+        assert_eq!(providers[0].claim_mappings[1].claim_type, ClaimType::String);
+        assert_eq!(providers[1].claim_mappings, defaults.claim_mappings);
+
+        let bad_type = r#"[{"issuer": "a", "public_key": "PEM",
+            "claim_mappings": [{"from": "x", "to": "y", "type": "date"}]}]"#;
+        assert!(parse_trusted_issuers(bad_type, &defaults).is_err());
+    }
+
+    fn provider(issuer: &str) -> JwtProvider {
+        JwtProvider {
+            issuer: issuer.to_string(),
+            public_key: Some("PEM".to_string()),
+            jwks_uri: None,
+            audience: Vec::new(),
+            algorithm: None,
+            required_scopes: None,
+            claim_mappings: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_provider_for_issuer() {
+        let config = Config {
+            providers: vec![
+                provider("https://a.example.com"),
+                provider("https://b.example.com"),
+            ],
+            time: TimeValidation::default(),
+            policy: None,
+            policy_data: None,
+        };
+        let issuer = |iss| config.provider_for(iss).map(|p| p.issuer.as_str());
+        assert_eq!(
+            issuer(Some("https://b.example.com")),
+            Some("https://b.example.com")
+        );
+        assert_eq!(issuer(Some("https://evil.example.com")), None);
+        assert_eq!(issuer(None), None);
+
+        // A provider without an issuer accepts the rest
+        let config = Config {
+            providers: vec![provider("https://a.example.com"), provider("")],
+            ..config
+        };
+        assert_eq!(
+            config
+                .provider_for(Some("https://c.example.com"))
+                .map(|p| p.issuer.as_str()),
+            Some("")
+        );
+    }
+
+    #[test]
+    fn test_trusted_issuer_scopes_use_normalized_issuer() {
+        let providers = parse_trusted_issuers(
+            r#"[{"issuer": "https://idp.example.com/", "public_key": "key", "required_scopes": ["mcp:admin"]}]"#,
+            &provider(""),
+        )
+        .unwrap();
+        let config = Config {
+            providers,
+            time: TimeValidation::default(),
+            policy: None,
+            policy_data: None,
+        };
+        assert_eq!(
+            config
+                .provider_for(Some("https://idp.example.com"))
+                .and_then(|p| p.required_scopes.clone()),
+            Some(vec!["mcp:admin".to_string()])
+        );
+    }
 }
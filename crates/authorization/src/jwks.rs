@@ -59,7 +59,8 @@ struct CachedJwks {
 
 /// Fetch JWKS from URI with caching
 pub fn fetch_jwks(jwks_uri: &str) -> Result<Jwks> {
-    let cache_key = "oauth-jwks";
+    // One entry per key set when several issuers are trusted
+    let cache_key = format!("oauth-jwks:{}", jwks_uri);
 
     // Get bucket name from environment (must match MCP_SESSION_BUCKET)
     let bucket_name = std::env::var("MCP_SESSION_BUCKET")
@@ -72,7 +73,7 @@ pub fn fetch_jwks(jwks_uri: &str) -> Result<Jwks> {
     })?;
 
     // Check cache first
-    if let Ok(Some(cached_value)) = bucket.get(cache_key) {
+    if let Ok(Some(cached_value)) = bucket.get(&cache_key) {
         // Extract string from typed-value
         let cached_str = match cached_value {
             kv::TypedValue::AsJson(s) | kv::TypedValue::AsString(s) => s,
@@ -101,7 +102,7 @@ pub fn fetch_jwks(jwks_uri: &str) -> Result<Jwks> {
     };
 
     if let Ok(cached_json) = serde_json::to_string(&cached) {
-        let _ = bucket.set(cache_key, &kv::TypedValue::AsJson(cached_json));
+        let _ = bucket.set(&cache_key, &kv::TypedValue::AsJson(cached_json));
     }
 
     Ok(jwks)
@@ -1,10 +1,13 @@
 //! JWT token verification
 
-use crate::bindings::wasmcp::auth::types::JwtClaims;
-use crate::config::JwtProvider;
+use crate::bindings::wasmcp::auth::types::{JwtClaims, TokenConfirmation};
+use crate::config::{ClaimMapping, ClaimType, JwtProvider, TimeValidation};
 use crate::error::{AuthError, Result};
-use jsonwebtoken::{Algorithm, DecodingKey, Validation, decode, decode_header};
+use jsonwebtoken::{
+    Algorithm, DecodingKey, Validation, decode, decode_header, get_current_timestamp,
+};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 /// JWT Claims structure
 #[derive(Debug, Serialize, Deserialize)]
@@ -60,7 +63,7 @@ enum ScopeValue {
 
 /// Verify a JWT token using the provided configuration
 /// Returns structured JwtClaims directly (WIT type)
-pub fn verify(token: &str, provider: &JwtProvider) -> Result<JwtClaims> {
+pub fn verify(token: &str, provider: &JwtProvider, time: &TimeValidation) -> Result<JwtClaims> {
     // Decode header to get algorithm and key ID
     let header = decode_header(token)?;
 
@@ -102,11 +105,9 @@ pub fn verify(token: &str, provider: &JwtProvider) -> Result<JwtClaims> {
         validation.validate_aud = false;
     }
 
-    // Enable nbf (not before) validation if present in token
-    validation.validate_nbf = true;
-
-    // Add leeway for clock skew tolerance (60 seconds)
-    validation.leeway = 60;
+    // exp is always checked; nbf unless disabled, both with the configured leeway
+    validation.validate_nbf = time.validate_nbf;
+    validation.leeway = time.leeway;
 
     // Set required claims
     validation.set_required_spec_claims(&["exp", "sub", "iss"]);
@@ -114,25 +115,17 @@ pub fn verify(token: &str, provider: &JwtProvider) -> Result<JwtClaims> {
     // Decode and validate token (error conversion via From trait in error.rs)
     let token_data = decode::<Claims>(token, &decoding_key, &validation)?;
     let claims = token_data.claims;
+    check_issued_at(claims.iat, get_current_timestamp(), time)?;
+
+    // Everything the token carries, for claim mappings
+    let raw_claims = serde_json::to_value(&claims).unwrap_or_default();
 
     // Extract scopes
     let scopes = extract_scopes(&claims);
 
-    // Check required scopes
-    if let Some(required_scopes) = &provider.required_scopes {
-        use std::collections::HashSet;
-
-        let token_scopes: HashSet<String> = scopes.iter().cloned().collect();
-        let required_set: HashSet<String> = required_scopes.iter().cloned().collect();
-
-        if !required_set.is_subset(&token_scopes) {
-            let missing_scopes: Vec<String> =
-                required_set.difference(&token_scopes).cloned().collect();
-            return Err(AuthError::Unauthorized(format!(
-                "Token missing required scopes: {missing_scopes:?}"
-            )));
-        }
-    }
+    // Required scopes are not checked here: a valid token without them is
+    // an authorization failure (403 insufficient_scope), not an invalid token.
+    // The transport and `authorize` enforce them.
 
     // Extract audience
     let audience = match claims.aud {
@@ -146,6 +139,9 @@ pub fn verify(token: &str, provider: &JwtProvider) -> Result<JwtClaims> {
     let issued_at = Some(claims.iat as u64);
     let not_before = claims.nbf.map(|nbf| nbf as u64);
 
+    // Key or certificate the token is bound to (RFC 8705, RFC 9449)
+    let confirmation = claims.additional.get("cnf").and_then(extract_confirmation);
+
     // Extract JWT ID if present
     let jwt_id = claims
         .additional
@@ -156,13 +152,19 @@ pub fn verify(token: &str, provider: &JwtProvider) -> Result<JwtClaims> {
     let standard_fields = [
         "sub", "iss", "aud", "exp", "iat", "nbf", "jti", "scope", "scp", "cnf",
     ];
-    let custom_claims: Vec<(String, String)> = claims
+    let mut custom_claims: Vec<(String, String)> = claims
         .additional
         .into_iter()
         .filter(|(k, _)| !standard_fields.contains(&k.as_str()))
         .map(|(k, v)| (k, crate::utils::json_value_to_string(v)))
         .collect();
 
+    // Add mapped claims under their configured names
+    for (name, value) in map_claims(&raw_claims, &provider.claim_mappings) {
+        custom_claims.retain(|(k, _)| *k != name);
+        custom_claims.push((name, value));
+    }
+
     // Build JwtClaims directly - NO intermediate type!
     Ok(JwtClaims {
         subject: claims.sub,
@@ -173,11 +175,60 @@ pub fn verify(token: &str, provider: &JwtProvider) -> Result<JwtClaims> {
         not_before,
         jwt_id,
         scopes,
-        confirmation: None, // TODO: Extract cnf claim if present
+        confirmation,
         custom_claims,
     })
 }
 
+/// Read the `cnf` claim's certificate and DPoP key thumbprints
+///
+/// Returns None when neither is present, so unbound tokens stay unbound.
+fn extract_confirmation(cnf: &Value) -> Option<TokenConfirmation> {
+    let member = |name: &str| cnf.get(name).and_then(Value::as_str).map(String::from);
+    let confirmation = TokenConfirmation {
+        x5t_s256: member("x5t#S256"),
+        jkt: member("jkt"),
+    };
+    (confirmation.x5t_s256.is_some() || confirmation.jkt.is_some()).then_some(confirmation)
+}
+
+/// Check `iat` against the configured time validation
+///
+/// jsonwebtoken only checks `exp` and `nbf`, so tokens from the future and
+/// tokens older than the maximum age are rejected here.
+fn check_issued_at(iat: i64, now: u64, time: &TimeValidation) -> Result<()> {
+    // A negative iat is in the distant past
+    let iat = u64::try_from(iat).unwrap_or(0);
+
+    if time.validate_iat && iat > now.saturating_add(time.leeway) {
+        return Err(AuthError::InvalidToken(
+            "Token issued in the future".to_string(),
+        ));
+    }
+
+    if let Some(max_age) = time.max_token_age
+        && now.saturating_sub(iat) > max_age.saturating_add(time.leeway)
+    {
+        return Err(AuthError::ExpiredToken);
+    }
+
+    Ok(())
+}
+
+/// Read a token's `iss` claim without verifying it
+///
+/// Only used to choose which trusted provider verifies the token; that
+/// provider then checks the signature and the issuer itself.
+pub fn peek_issuer(token: &str) -> Option<String> {
+    use base64::Engine as _;
+    use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+
+    let payload = token.split('.').nth(1)?;
+    let bytes = URL_SAFE_NO_PAD.decode(payload.trim_end_matches('=')).ok()?;
+    let claims: serde_json::Value = serde_json::from_slice(&bytes).ok()?;
+    claims.get("iss")?.as_str().map(String::from)
+}
+
 /// Extract scopes from claims
 fn extract_scopes(claims: &Claims) -> Vec<String> {
     // OAuth2 'scope' claim takes precedence
@@ -196,3 +247,196 @@ fn extract_scopes(claims: &Claims) -> Vec<String> {
     // No scopes
     Vec::new()
 }
+
+/// Apply claim mappings to a token's claims
+///
+/// Claims that are missing, null or can't be coerced are left out.
+fn map_claims(claims: &Value, mappings: &[ClaimMapping]) -> Vec<(String, String)> {
+    mappings
+        .iter()
+        .filter_map(|mapping| {
+            let value = lookup_claim(claims, &mapping.from)?;
+            let coerced = coerce_claim(value, mapping.claim_type);
+            if coerced.is_none() {
+                eprintln!(
+                    "[authorization] Claim '{}' is not a {:?}; not mapping it to '{}'",
+                    mapping.from, mapping.claim_type, mapping.to
+                );
+            }
+            Some((mapping.to.clone(), coerced?))
+        })
+        .collect()
+}
+
+/// Find a claim by exact name, then as a dotted path into nested objects
+fn lookup_claim<'a>(claims: &'a Value, name: &str) -> Option<&'a Value> {
+    claims
+        .get(name)
+        .or_else(|| {
+            name.split('.')
+                .try_fold(claims, |value, key| value.get(key))
+        })
+        .filter(|value| !value.is_null())
+}
+
+/// Coerce a claim value to the string form of `claim_type`
+fn coerce_claim(value: &Value, claim_type: ClaimType) -> Option<String> {
+    match (claim_type, value) {
+        (ClaimType::String, value) => Some(crate::utils::json_value_to_string(value.clone())),
+        (ClaimType::Number, Value::Number(n)) => Some(n.to_string()),
+        (ClaimType::Number, Value::String(s)) => {
+            let s = s.trim();
+            match s.parse::<i64>() {
+                Ok(n) => Some(n.to_string()),
+                Err(_) => s
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|n| n.is_finite())
+                    .map(|n| n.to_string()),
+            }
+        }
+        (ClaimType::Bool, Value::Bool(b)) => Some(b.to_string()),
+        (ClaimType::Bool, Value::String(s)) => match s.trim().to_ascii_lowercase().as_str() {
+            "true" | "1" => Some("true".to_string()),
+            "false" | "0" => Some("false".to_string()),
+            _ => None,
+        },
+        (ClaimType::Bool, Value::Number(n)) => match n.as_u64() {
+            Some(1) => Some("true".to_string()),
+            Some(0) => Some("false".to_string()),
+            _ => None,
+        },
+        (ClaimType::List, Value::Array(items)) => Some(
+            items
+                .iter()
+                .map(|item| crate::utils::json_value_to_string(item.clone()))
+                .collect::<Vec<_>>()
+                .join(","),
+        ),
+        (ClaimType::List, Value::String(s)) => Some(
+            s.split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|item| !item.is_empty())
+                .collect::<Vec<_>>()
+                .join(","),
+        ),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn mapping(from: &str, to: &str, claim_type: ClaimType) -> ClaimMapping {
+        ClaimMapping {
+            from: from.to_string(),
+            to: to.to_string(),
+            claim_type,
+        }
+    }
+
+    #[test]
+    fn test_map_claims() {
+        let claims = json!({
+            "sub": "user-1",
+            "https://example.com/org": "acme",
+            "org": {"id": "42", "admin": 1},
+            "groups": ["eng", "ops"],
+            "email": null,
+        });
+        let mapped = map_claims(
+            &claims,
+            &[
+                mapping("sub", "user_id", ClaimType::String),
+                mapping("https://example.com/org", "tenant", ClaimType::String),
+                mapping("org.id", "org_id", ClaimType::Number),
+                mapping("org.admin", "is_admin", ClaimType::Bool),
+                mapping("groups", "roles", ClaimType::List),
+                mapping("email", "user_email", ClaimType::String),
+                mapping("sub", "sub_number", ClaimType::Number),
+            ],
+        );
+        let expected = [
+            ("user_id", "user-1"),
+            ("tenant", "acme"),
+            ("org_id", "42"),
+            ("is_admin", "true"),
+            ("roles", "eng,ops"),
+        ];
+        assert_eq!(
+            mapped,
+            expected.map(|(k, v)| (k.to_string(), v.to_string()))
+        );
+    }
+
+    #[test]
+    fn test_coerce_claim() {
+        assert_eq!(
+            coerce_claim(&json!(" 2.5 "), ClaimType::Number).as_deref(),
+            Some("2.5")
+        );
+        assert_eq!(coerce_claim(&json!("NaN"), ClaimType::Number), None);
+        assert_eq!(
+            coerce_claim(&json!("FALSE"), ClaimType::Bool).as_deref(),
+            Some("false")
+        );
+        assert_eq!(coerce_claim(&json!(2), ClaimType::Bool), None);
+        assert_eq!(
+            coerce_claim(&json!("read write,admin"), ClaimType::List).as_deref(),
+            Some("read,write,admin")
+        );
+        assert_eq!(
+            coerce_claim(&json!({"a": 1}), ClaimType::String).as_deref(),
+            Some(r#"{"a":1}"#)
+        );
+    }
+
+    #[test]
+    fn test_check_issued_at() {
+        let now = 10_000;
+        let defaults = TimeValidation::default();
+        // Off by default
+        assert!(check_issued_at(now as i64 + 3_600, now, &defaults).is_ok());
+        assert!(check_issued_at(0, now, &defaults).is_ok());
+
+        let future = TimeValidation {
+            validate_iat: true,
+            ..defaults.clone()
+        };
+        assert!(check_issued_at(now as i64 + 60, now, &future).is_ok());
+        assert!(check_issued_at(now as i64 + 61, now, &future).is_err());
+
+        let max_age = TimeValidation {
+            max_token_age: Some(300),
+            ..defaults
+        };
+        assert!(check_issued_at(now as i64 - 360, now, &max_age).is_ok());
+        assert!(matches!(
+            check_issued_at(now as i64 - 361, now, &max_age),
+            Err(AuthError::ExpiredToken)
+        ));
+        assert!(check_issued_at(-1, now, &max_age).is_err());
+    }
+
+    #[test]
+    fn test_extract_confirmation() {
+        let cnf =
+            extract_confirmation(&json!({"jkt": "0ZcOCORZNYy-DWpqq30jZyJGHTN0d2HglBV3uiguA4I"}))
+                .unwrap();
+        assert_eq!(
+            cnf.jkt.as_deref(),
+            Some("0ZcOCORZNYy-DWpqq30jZyJGHTN0d2HglBV3uiguA4I")
+        );
+        assert_eq!(cnf.x5t_s256, None);
+
+        let cnf = extract_confirmation(
+            &json!({"x5t#S256": "bwcK0esc3ACC3DB2Y5_lESsXE8o9ltc05O89jdN-dg2"}),
+        )
+        .unwrap();
+        assert!(cnf.x5t_s256.is_some() && cnf.jkt.is_none());
+
+        assert!(extract_confirmation(&json!({"kid": "key-1"})).is_none());
+        assert!(extract_confirmation(&json!("jkt")).is_none());
+    }
+}
@@ -46,8 +46,11 @@ impl Guest for Component {
         // Get configuration
         let config = get_config();
 
-        // Verify JWT - returns JwtClaims directly!
-        let jwt_claims = jwt::verify(token_str, &config.provider).map_err(|_| ())?;
+        // Pick the provider trusted for the token's issuer, then verify with it
+        let provider = config
+            .provider_for(jwt::peek_issuer(token_str).as_deref())
+            .ok_or(())?;
+        let jwt_claims = jwt::verify(token_str, provider, &config.time).map_err(|_| ())?;
 
         // Return JwtClaims directly - NO conversion needed!
         Ok(jwt_claims)
@@ -82,7 +85,10 @@ impl Guest for Component {
         }
 
         // Fallback: Simple scope-based authorization
-        let Some(ref required_scopes) = config.provider.required_scopes else {
+        let Some(required_scopes) = config
+            .provider_for(claims.issuer.as_deref())
+            .and_then(|provider| provider.required_scopes.as_ref())
+        else {
             return true;
         };
 
@@ -95,6 +101,17 @@ impl Guest for Component {
 
         true
     }
+
+    /// Scopes of the provider that verifies tokens from `issuer`
+    ///
+    /// Providers are matched as `decode` matches them, on the normalized
+    /// issuer, so the transport enforces the same scopes.
+    fn required_scopes(issuer: Option<String>) -> Vec<String> {
+        get_config()
+            .provider_for(issuer.as_deref())
+            .and_then(|provider| provider.required_scopes.clone())
+            .unwrap_or_default()
+    }
 }
 
 // === OAuth Interface Implementations ===
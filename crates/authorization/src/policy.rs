@@ -221,6 +221,15 @@ fn extract_mcp_context(message: &ClientMessage) -> serde_json::Value {
                         "ref": format!("{:?}", complete.ref_)
                     })
                 }
+                crate::bindings::wasmcp::mcp_v20251125::mcp::ClientRequest::Custom(custom) => {
+                    json!({
+                        "method": custom.method,
+                        "params": parse_json_string(&custom.params)
+                    })
+                }
+                crate::bindings::wasmcp::mcp_v20251125::mcp::ClientRequest::Unknown(method) => {
+                    json!({ "method": method })
+                }
             }
         }
         ClientMessage::Result((_, _)) => {
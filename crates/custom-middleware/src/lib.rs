@@ -0,0 +1,173 @@
+//! Custom Middleware Component
+//!
+//! A reusable middleware that bridges the MCP protocol (server-handler)
+//! with the custom interface for protocol extensions. This component:
+//! - Routes custom `x-*` requests to the imported handle-custom function
+//! - Adds declared experimental capabilities to the initialize result
+//! - Delegates all other requests downstream
+//!
+//! Experimental capabilities reach the transport through the initialize
+//! request it forwards down the chain. When no downstream handler answers
+//! initialize, this component answers with an empty server-info name, which
+//! tells the transport to keep its configured server metadata and only merge
+//! the capabilities.
+
+#![allow(warnings)]
+
+mod bindings {
+    wit_bindgen::generate!({
+        world: "custom-middleware",
+        generate_all,
+    });
+}
+
+use bindings::exports::wasmcp::mcp_v20251125::server_handler::{Guest, MessageContext};
+use bindings::wasmcp::mcp_v20251125::custom;
+use bindings::wasmcp::mcp_v20251125::mcp::*;
+use bindings::wasmcp::mcp_v20251125::server_handler as downstream;
+
+struct CustomMiddleware;
+
+// Convert exported MessageContext to imported MessageContext
+fn to_downstream_ctx<'a>(ctx: &'a MessageContext<'a>) -> downstream::MessageContext<'a> {
+    downstream::MessageContext {
+        client_stream: ctx.client_stream,
+        protocol_version: ctx.protocol_version.clone(),
+        session: ctx.session.as_ref().map(|s| downstream::Session {
+            session_id: s.session_id.clone(),
+            store_id: s.store_id.clone(),
+        }),
+        identity: ctx.identity.as_ref().map(|i| downstream::Identity {
+            jwt: i.jwt.clone(),
+            claims: i.claims.clone(),
+        }),
+        frame: ctx.frame.clone(),
+        http_context: ctx.http_context.clone(),
+        client_capabilities: ctx.client_capabilities.clone(),
+        request_id: ctx.request_id.clone(),
+        deadline: ctx.deadline,
+        log_level: ctx.log_level,
+        client_info: ctx.client_info.clone(),
+        locale: ctx.locale.clone(),
+        trace_context: ctx.trace_context.clone(),
+    }
+}
+
+impl Guest for CustomMiddleware {
+    fn handle(
+        ctx: MessageContext,
+        message: ClientMessage,
+    ) -> Option<Result<ServerResult, ErrorCode>> {
+        match message {
+            ClientMessage::Request((request_id, request)) => {
+                // Handle requests - match on request type
+                let result = match &request {
+                    ClientRequest::Custom(custom_req) => {
+                        handle_custom(request_id.clone(), custom_req.clone(), &ctx)
+                    }
+                    ClientRequest::Initialize(init_req) => {
+                        return handle_initialize(request_id.clone(), init_req.clone(), &ctx);
+                    }
+                    _ => {
+                        // Delegate all other requests to downstream handler
+                        let downstream_msg = ClientMessage::Request((request_id.clone(), request));
+                        return downstream::handle(&to_downstream_ctx(&ctx), downstream_msg);
+                    }
+                };
+                Some(result)
+            }
+            _ => {
+                // Forward notifications, results, errors to downstream
+                downstream::handle(&to_downstream_ctx(&ctx), message)
+            }
+        }
+    }
+}
+
+fn handle_custom(
+    request_id: RequestId,
+    req: CustomRequest,
+    ctx: &MessageContext,
+) -> Result<ServerResult, ErrorCode> {
+    // Try calling imported custom interface first
+    match custom::handle_custom(&to_downstream_ctx(ctx), &req.method, req.params.as_deref()) {
+        Ok(Some(result)) => {
+            // Imported interface handled it - return the result
+            Ok(ServerResult::Custom(result))
+        }
+        Ok(None) => {
+            // Imported interface doesn't handle this method - try downstream
+            // Preserve the original request ID
+            let downstream_req = ClientRequest::Custom(req.clone());
+            let downstream_msg = ClientMessage::Request((request_id, downstream_req));
+            match downstream::handle(&to_downstream_ctx(ctx), downstream_msg) {
+                Some(Ok(response)) => Ok(response),
+                Some(Err(ErrorCode::MethodNotFound(_))) | None => {
+                    Err(ErrorCode::MethodNotFound(Error {
+                        code: -32601,
+                        message: format!("Method not found: {}", req.method),
+                        data: None,
+                    }))
+                }
+                Some(Err(e)) => Err(e),
+            }
+        }
+        Err(e) => {
+            // Imported interface returned an error - propagate it
+            Err(e)
+        }
+    }
+}
+
+fn handle_initialize(
+    request_id: RequestId,
+    req: InitializeRequest,
+    ctx: &MessageContext,
+) -> Option<Result<ServerResult, ErrorCode>> {
+    let ours = custom::experimental_capabilities(&to_downstream_ctx(ctx));
+
+    let downstream_req = ClientRequest::Initialize(req.clone());
+    let downstream_msg = ClientMessage::Request((request_id, downstream_req));
+    let downstream_result = downstream::handle(&to_downstream_ctx(ctx), downstream_msg);
+
+    if ours.is_empty() {
+        return downstream_result;
+    }
+
+    match downstream_result {
+        Some(Ok(ServerResult::Initialize(mut result))) => {
+            // Downstream supplied an initialize result - add our capabilities to it
+            result
+                .capabilities
+                .experimental
+                .get_or_insert_with(Vec::new)
+                .extend(ours);
+            Some(Ok(ServerResult::Initialize(result)))
+        }
+        _ => {
+            // Nobody else answered - contribute capabilities only
+            Some(Ok(ServerResult::Initialize(InitializeResult {
+                meta: None,
+                server_info: Implementation {
+                    name: String::new(),
+                    title: None,
+                    version: String::new(),
+                    description: None,
+                    icons: None,
+                },
+                capabilities: ServerCapabilities {
+                    completions: None,
+                    experimental: Some(ours),
+                    logging: None,
+                    list_changed: None,
+                    subscriptions: None,
+                    lists: None,
+                },
+                protocol_version: req.protocol_version,
+                options: None,
+            })))
+        }
+    }
+}
+
+bindings::export!(CustomMiddleware with_types_in bindings);
@@ -0,0 +1,48 @@
+//! Configuration for static identities
+//!
+//! Environment variables:
+//! - `WASMCP_FAKE_AUTH_TOKENS`: JSON object of token to identity (see `identities`)
+//! - `WASMCP_FAKE_AUTH_REQUIRED_SCOPES`: Scopes every request needs, space or
+//!   comma separated (default: none)
+//!
+//! A missing or invalid token table is logged and no token is accepted.
+
+use crate::bindings::wasi::cli::environment::get_environment;
+use crate::identities;
+use std::collections::HashMap;
+use wasmcp_claims::Claims;
+
+/// Static identity configuration from environment variables
+pub struct FakeAuthConfig {
+    /// Identity per bearer token
+    pub tokens: HashMap<String, Claims>,
+    pub required_scopes: Vec<String>,
+}
+
+impl FakeAuthConfig {
+    /// Load configuration from environment variables
+    pub fn from_env() -> Self {
+        let env_map: HashMap<String, String> = get_environment().into_iter().collect();
+
+        let tokens = match env_map.get("WASMCP_FAKE_AUTH_TOKENS") {
+            Some(json) => identities::parse(json).unwrap_or_else(|e| {
+                eprintln!("[fake-auth] Invalid WASMCP_FAKE_AUTH_TOKENS: {e}; rejecting all tokens");
+                HashMap::new()
+            }),
+            None => {
+                eprintln!("[fake-auth] WASMCP_FAKE_AUTH_TOKENS is not set; rejecting all tokens");
+                HashMap::new()
+            }
+        };
+
+        let required_scopes = env_map
+            .get("WASMCP_FAKE_AUTH_REQUIRED_SCOPES")
+            .map(|v| wasmcp_claims::parse_list(v))
+            .unwrap_or_default();
+
+        FakeAuthConfig {
+            tokens,
+            required_scopes,
+        }
+    }
+}
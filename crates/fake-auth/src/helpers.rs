@@ -0,0 +1,101 @@
+//! Claim helpers
+//!
+//! The transport imports `wasmcp:auth/helpers` alongside server-auth, so a
+//! drop-in for the authorization component exports both. These behave the
+//! same as the authorization component's helpers.
+
+use crate::bindings::exports::wasmcp::auth::helpers::Guest;
+use crate::bindings::wasmcp::auth::types::JwtClaims;
+use crate::{Component, now};
+
+impl Guest for Component {
+    fn flatten_claims(claims: JwtClaims) -> Vec<(String, String)> {
+        let mut flat = vec![("sub".to_string(), claims.subject.clone())];
+        for key in ["iss", "aud", "exp", "iat", "nbf", "jti", "scope"] {
+            if let Some(value) = standard_claim(&claims, key) {
+                flat.push((key.to_string(), value));
+            }
+        }
+        flat.extend(claims.custom_claims);
+        flat
+    }
+
+    fn has_scope(claims: JwtClaims, scope: String) -> bool {
+        claims.scopes.contains(&scope)
+    }
+
+    fn has_any_scope(claims: JwtClaims, scopes: Vec<String>) -> bool {
+        scopes.iter().any(|scope| claims.scopes.contains(scope))
+    }
+
+    fn has_all_scopes(claims: JwtClaims, scopes: Vec<String>) -> bool {
+        scopes.iter().all(|scope| claims.scopes.contains(scope))
+    }
+
+    fn get_claim(claims: JwtClaims, key: String) -> Option<String> {
+        if key == "sub" {
+            return Some(claims.subject);
+        }
+        if let Some(value) = standard_claim(&claims, &key) {
+            return Some(value);
+        }
+        claims
+            .custom_claims
+            .into_iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, v)| v)
+    }
+
+    fn has_audience(claims: JwtClaims, audience: String) -> bool {
+        claims.audience.contains(&audience)
+    }
+
+    fn is_expired(claims: JwtClaims, clock_skew_seconds: Option<u64>) -> bool {
+        let skew = clock_skew_seconds.unwrap_or(0);
+        claims
+            .expiration
+            .is_some_and(|exp| now() >= exp.saturating_sub(skew))
+    }
+
+    fn is_valid_time(claims: JwtClaims, clock_skew_seconds: Option<u64>) -> bool {
+        let now = now();
+        let skew = clock_skew_seconds.unwrap_or(0);
+        let started = claims
+            .not_before
+            .is_none_or(|nbf| now >= nbf.saturating_sub(skew));
+        let expired = claims
+            .expiration
+            .is_some_and(|exp| now >= exp.saturating_sub(skew));
+        started && !expired
+    }
+
+    fn get_subject(claims: JwtClaims) -> String {
+        claims.subject
+    }
+
+    fn get_issuer(claims: JwtClaims) -> Option<String> {
+        claims.issuer
+    }
+
+    fn get_scopes(claims: JwtClaims) -> Vec<String> {
+        claims.scopes
+    }
+
+    fn get_audiences(claims: JwtClaims) -> Vec<String> {
+        claims.audience
+    }
+}
+
+/// A registered claim other than `sub`, in its token form
+fn standard_claim(claims: &JwtClaims, key: &str) -> Option<String> {
+    match key {
+        "iss" => claims.issuer.clone(),
+        "aud" => Some(claims.audience.join(",")).filter(|aud| !aud.is_empty()),
+        "exp" => claims.expiration.map(|e| e.to_string()),
+        "iat" => claims.issued_at.map(|i| i.to_string()),
+        "nbf" => claims.not_before.map(|n| n.to_string()),
+        "jti" => claims.jwt_id.clone(),
+        "scope" | "scp" => Some(claims.scopes.join(" ")).filter(|scope| !scope.is_empty()),
+        _ => None,
+    }
+}
@@ -0,0 +1,211 @@
+//! Static token table
+//!
+//! `WASMCP_FAKE_AUTH_TOKENS` is a JSON object from bearer token to the
+//! identity it stands for. An identity is either a subject string or an
+//! object of JWT claims, written as they would appear in a token payload:
+//!
+//! ```json
+//! {
+//!   "alice-token": {"sub": "alice", "scope": "mcp:read mcp:write", "roles": ["admin"]},
+//!   "bob-token": "bob"
+//! }
+//! ```
+//!
+//! `sub` is required; `iss`, `aud`, `exp`, `iat`, `nbf`, `jti` and `scope`
+//! (or `scp`) fill the registered claims, and every other claim becomes a
+//! custom claim the same way the authorization component stores them:
+//! strings as-is, anything else as JSON.
+
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use wasmcp_claims::Claims;
+
+/// Clock skew tolerated for `exp` and `nbf`, as in the authorization component
+const LEEWAY_SECS: u64 = 60;
+
+/// Parse the token table
+pub fn parse(json: &str) -> Result<HashMap<String, Claims>, String> {
+    let Value::Object(tokens) =
+        serde_json::from_str(json).map_err(|e| format!("invalid JSON: {e}"))?
+    else {
+        return Err("expected an object of token to identity".to_string());
+    };
+
+    tokens
+        .into_iter()
+        .map(|(token, identity)| {
+            if token.is_empty() {
+                return Err("tokens must not be empty".to_string());
+            }
+            let claims = match identity {
+                Value::String(subject) => Claims {
+                    subject,
+                    ..Claims::default()
+                },
+                Value::Object(claims) => to_claims(claims)?,
+                _ => return Err("an identity must be a subject or an object".to_string()),
+            };
+            if claims.subject.is_empty() {
+                return Err("an identity has an empty subject".to_string());
+            }
+            Ok((token, claims))
+        })
+        .collect()
+}
+
+/// Whether claims are within their validity window at `now`
+pub fn is_current(claims: &Claims, now: u64) -> bool {
+    let started = claims
+        .not_before
+        .is_none_or(|nbf| nbf <= now.saturating_add(LEEWAY_SECS));
+    let expired = claims
+        .expiration
+        .is_some_and(|exp| exp.saturating_add(LEEWAY_SECS) <= now);
+    started && !expired
+}
+
+fn to_claims(mut payload: Map<String, Value>) -> Result<Claims, String> {
+    let subject = match payload.remove("sub") {
+        Some(Value::String(sub)) => sub,
+        Some(_) => return Err("\"sub\" must be a string".to_string()),
+        None => return Err("an identity is missing \"sub\"".to_string()),
+    };
+    let scopes = match payload.remove("scope") {
+        Some(scope) => string_list("scope", scope)?,
+        None => payload
+            .remove("scp")
+            .map(|scp| string_list("scp", scp))
+            .transpose()?
+            .unwrap_or_default(),
+    };
+    // Only honored when `scope` is present; dropped so it isn't a custom claim
+    payload.remove("scp");
+
+    Ok(Claims {
+        subject,
+        issuer: payload
+            .remove("iss")
+            .map(|v| string("iss", v))
+            .transpose()?,
+        audience: payload
+            .remove("aud")
+            .map(|v| string_list("aud", v))
+            .transpose()?
+            .unwrap_or_default(),
+        expiration: payload.remove("exp").map(|v| time("exp", v)).transpose()?,
+        issued_at: payload.remove("iat").map(|v| time("iat", v)).transpose()?,
+        not_before: payload.remove("nbf").map(|v| time("nbf", v)).transpose()?,
+        jwt_id: payload
+            .remove("jti")
+            .map(|v| string("jti", v))
+            .transpose()?,
+        scopes,
+        custom_claims: payload
+            .into_iter()
+            .map(|(name, value)| match value {
+                Value::String(s) => (name, s),
+                other => (name, other.to_string()),
+            })
+            .collect(),
+    })
+}
+
+fn string(name: &str, value: Value) -> Result<String, String> {
+    match value {
+        Value::String(s) => Ok(s),
+        _ => Err(format!("\"{name}\" must be a string")),
+    }
+}
+
+/// A space-separated string or an array of strings
+fn string_list(name: &str, value: Value) -> Result<Vec<String>, String> {
+    match value {
+        Value::String(s) => Ok(s.split_whitespace().map(String::from).collect()),
+        Value::Array(items) => items.into_iter().map(|item| string(name, item)).collect(),
+        _ => Err(format!(
+            "\"{name}\" must be a string or an array of strings"
+        )),
+    }
+}
+
+/// Unix epoch seconds
+fn time(name: &str, value: Value) -> Result<u64, String> {
+    value
+        .as_u64()
+        .ok_or_else(|| format!("\"{name}\" must be a non-negative integer"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let tokens = parse(
+            r#"{
+                "alice-token": {
+                    "sub": "alice",
+                    "iss": "https://idp.test",
+                    "aud": "https://mcp.test",
+                    "exp": 2000,
+                    "scope": "mcp:read mcp:write",
+                    "roles": ["admin"],
+                    "tenant": "acme"
+                },
+                "bob-token": "bob",
+                "carol-token": {"sub": "carol", "aud": ["a", "b"], "scp": ["mcp:read"]}
+            }"#,
+        )
+        .unwrap();
+
+        let alice = &tokens["alice-token"];
+        assert_eq!(alice.subject, "alice");
+        assert_eq!(alice.issuer.as_deref(), Some("https://idp.test"));
+        assert_eq!(alice.audience, ["https://mcp.test"]);
+        assert_eq!(alice.expiration, Some(2000));
+        assert_eq!(alice.scopes, ["mcp:read", "mcp:write"]);
+        assert_eq!(alice.roles(), ["admin"]);
+        assert_eq!(alice.claim("tenant"), Some("acme"));
+
+        assert_eq!(
+            tokens["bob-token"],
+            Claims {
+                subject: "bob".to_string(),
+                ..Claims::default()
+            }
+        );
+
+        let carol = &tokens["carol-token"];
+        assert_eq!(carol.audience, ["a", "b"]);
+        assert_eq!(carol.scopes, ["mcp:read"]);
+        assert!(carol.custom_claims.is_empty());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse("not json").is_err());
+        assert!(parse(r#"["alice"]"#).is_err());
+        assert!(parse(r#"{"": "alice"}"#).is_err());
+        assert!(parse(r#"{"t": ""}"#).is_err());
+        assert!(parse(r#"{"t": 7}"#).is_err());
+        assert!(parse(r#"{"t": {"scope": "mcp:read"}}"#).is_err());
+        assert!(parse(r#"{"t": {"sub": "alice", "exp": "soon"}}"#).is_err());
+        assert!(parse(r#"{"t": {"sub": "alice", "aud": [1]}}"#).is_err());
+        assert!(parse("{}").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_is_current() {
+        let claims = Claims {
+            subject: "alice".to_string(),
+            not_before: Some(1_000),
+            expiration: Some(2_000),
+            ..Claims::default()
+        };
+        assert!(!is_current(&claims, 1_000 - LEEWAY_SECS - 1));
+        assert!(is_current(&claims, 1_000 - LEEWAY_SECS));
+        assert!(is_current(&claims, 2_000 + LEEWAY_SECS - 1));
+        assert!(!is_current(&claims, 2_000 + LEEWAY_SECS));
+        assert!(is_current(&Claims::default(), u64::MAX));
+    }
+}
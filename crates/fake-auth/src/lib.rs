@@ -0,0 +1,96 @@
+//! Fake Auth Component
+//!
+//! A test double for the authorization component. Instead of validating
+//! JWTs against an identity provider, bearer tokens are looked up in a
+//! static table from configuration, so integration tests and local
+//! development can exercise authorization-aware handlers with fixed
+//! identities. This component:
+//! - Decodes known tokens to their configured claims, honoring `exp`/`nbf`
+//! - Rejects unknown tokens like invalid JWTs
+//! - Authorizes every request, or those carrying the required scopes
+//! - Exports the claim helpers the transport imports
+//!
+//! Anyone who knows a configured token is that identity. Never deploy it.
+
+#![allow(warnings)]
+
+mod bindings {
+    wit_bindgen::generate!({
+        world: "fake-auth",
+        generate_all,
+    });
+}
+
+mod config;
+mod helpers;
+mod identities;
+
+use bindings::exports::wasmcp::mcp_v20251125::server_auth::{Guest, HttpContext};
+use bindings::wasmcp::auth::types::{Jwt, JwtClaims};
+use bindings::wasmcp::mcp_v20251125::mcp::{ClientMessage, Session};
+use config::FakeAuthConfig;
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+use wasmcp_claims::Claims;
+
+static CONFIG: OnceLock<FakeAuthConfig> = OnceLock::new();
+
+fn get_config() -> &'static FakeAuthConfig {
+    CONFIG.get_or_init(FakeAuthConfig::from_env)
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn to_jwt_claims(claims: &Claims) -> JwtClaims {
+    JwtClaims {
+        subject: claims.subject.clone(),
+        issuer: claims.issuer.clone(),
+        audience: claims.audience.clone(),
+        expiration: claims.expiration,
+        issued_at: claims.issued_at,
+        not_before: claims.not_before,
+        jwt_id: claims.jwt_id.clone(),
+        scopes: claims.scopes.clone(),
+        confirmation: None,
+        custom_claims: claims.custom_claims.clone(),
+    }
+}
+
+struct Component;
+
+impl Guest for Component {
+    /// Look up the token's configured identity
+    fn decode(jwt: Jwt) -> Result<JwtClaims, ()> {
+        let token = std::str::from_utf8(&jwt).map_err(|_| ())?;
+        let claims = get_config().tokens.get(token).ok_or(())?;
+        if !identities::is_current(claims, now()) {
+            return Err(());
+        }
+        Ok(to_jwt_claims(claims))
+    }
+
+    /// Allow requests carrying every required scope
+    fn authorize(
+        _request: ClientMessage,
+        claims: JwtClaims,
+        _session: Option<Session>,
+        _http_context: Option<HttpContext>,
+    ) -> bool {
+        get_config()
+            .required_scopes
+            .iter()
+            .all(|scope| claims.scopes.contains(scope))
+    }
+
+    /// The configured required scopes, whatever the issuer
+    fn required_scopes(_issuer: Option<String>) -> Vec<String> {
+        get_config().required_scopes.clone()
+    }
+}
+
+bindings::export!(Component with_types_in bindings);
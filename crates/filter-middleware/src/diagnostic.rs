@@ -55,7 +55,10 @@ pub fn handle_inspect_routing(ctx: &MessageContext) -> Result<ServerResult, Erro
     let all_tools = fetch_tools_from_downstream(
         ctx,
         RequestId::Number(INTERNAL_REQUEST_ID_VALUE),
-        ListToolsRequest { cursor: None },
+        ListToolsRequest {
+            cursor: None,
+            meta: None,
+        },
     )
     .unwrap_or_default(); // If we can't get tools, proceed without conflict detection
 
@@ -17,6 +17,13 @@ pub fn to_downstream_ctx<'a>(ctx: &'a MessageContext<'a>) -> downstream::Message
         }),
         frame: ctx.frame.clone(),
         http_context: ctx.http_context.clone(),
+        client_capabilities: ctx.client_capabilities.clone(),
+        request_id: ctx.request_id.clone(),
+        deadline: ctx.deadline,
+        log_level: ctx.log_level,
+        client_info: ctx.client_info.clone(),
+        locale: ctx.locale.clone(),
+        trace_context: ctx.trace_context.clone(),
     }
 }
 
@@ -168,7 +168,10 @@ fn handle_tools_call(
             let all_tools = fetch_tools_from_downstream(
                 ctx,
                 RequestId::Number(INTERNAL_REQUEST_ID_VALUE),
-                ListToolsRequest { cursor: None },
+                ListToolsRequest {
+                    cursor: None,
+                    meta: None,
+                },
             )?;
 
             let config = load_and_aggregate_configs(ctx).map_err(|e| {
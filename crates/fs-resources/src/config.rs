@@ -0,0 +1,62 @@
+//! Configuration for the filesystem resource provider
+//!
+//! Environment variables:
+//! - `WASMCP_FS_WRITABLE`: "true"/"false" (default: "false") - Expose the `write_file` tool
+//! - `WASMCP_FS_PAGE_SIZE`: Resources per resources/list page (default: "100")
+//! - `WASMCP_FS_MAX_READ_BYTES`: Largest file resources/read will return (default: 10MB)
+//! - `WASMCP_FS_INCLUDE_HIDDEN`: "true"/"false" (default: "false") - Serve dotfiles
+//! - `WASMCP_CURSOR_KEY`: Key signing resources/list cursors (default: drawn per
+//!   session, see `cursor.rs`; required to page without a session over HTTP)
+
+use crate::bindings::wasi::cli::environment::get_environment;
+use std::collections::HashMap;
+
+const DEFAULT_PAGE_SIZE: usize = 100;
+const DEFAULT_MAX_READ_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Filesystem provider configuration from environment variables
+#[derive(Debug, Clone)]
+pub struct FsConfig {
+    pub writable: bool,
+    pub page_size: usize,
+    pub max_read_bytes: u64,
+    pub include_hidden: bool,
+    pub cursor_key: Option<String>,
+}
+
+impl FsConfig {
+    /// Load configuration from environment variables
+    pub fn from_env() -> Self {
+        let env_map: HashMap<String, String> = get_environment().into_iter().collect();
+
+        let flag = |key: &str| {
+            env_map
+                .get(key)
+                .map(|v| v.to_lowercase() == "true")
+                .unwrap_or(false)
+        };
+
+        let page_size = env_map
+            .get("WASMCP_FS_PAGE_SIZE")
+            .and_then(|v| v.parse::<usize>().ok())
+            .filter(|n| *n > 0)
+            .unwrap_or(DEFAULT_PAGE_SIZE);
+
+        let max_read_bytes = env_map
+            .get("WASMCP_FS_MAX_READ_BYTES")
+            .and_then(|v| v.parse::<u64>().ok())
+            .filter(|n| *n > 0)
+            .unwrap_or(DEFAULT_MAX_READ_BYTES);
+
+        FsConfig {
+            writable: flag("WASMCP_FS_WRITABLE"),
+            page_size,
+            max_read_bytes,
+            include_hidden: flag("WASMCP_FS_INCLUDE_HIDDEN"),
+            cursor_key: env_map
+                .get("WASMCP_CURSOR_KEY")
+                .filter(|k| !k.is_empty())
+                .cloned(),
+        }
+    }
+}
@@ -0,0 +1,74 @@
+//! Keys signing resources/list cursors
+//!
+//! A cursor must decode on whichever instance serves the next page. With
+//! `WASMCP_CURSOR_KEY` set, every instance signs with that key. Otherwise the
+//! key is drawn once per session and kept in it (`fs:cursor-key`), so cursors
+//! survive the HTTP transport's instance per request, and are only valid in
+//! the session that got them.
+//!
+//! Without a session, a key drawn per instance serves stdio, where one
+//! instance lives as long as the connection. Over HTTP it would be gone with
+//! the request, so listing past the first page fails with an error naming
+//! `WASMCP_CURSOR_KEY` instead of handing out cursors no instance accepts.
+
+use crate::bindings::exports::wasmcp::mcp_v20251125::server_handler::MessageContext;
+use crate::bindings::wasi::random::random::get_random_bytes;
+use crate::bindings::wasmcp::keyvalue::store::TypedValue;
+use crate::bindings::wasmcp::mcp_v20251125::sessions;
+use crate::config::FsConfig;
+use std::sync::OnceLock;
+use wasmcp_cursor::CursorCodec;
+
+/// Cursor scope for pages of this component's files
+pub const SCOPE: &str = "fs";
+
+/// Session key holding the session's cursor key
+const SESSION_KEY: &str = "fs:cursor-key";
+
+/// Size of drawn keys, in bytes
+const KEY_BYTES: u64 = 32;
+
+/// Key for connections without a session, drawn once per instance
+static INSTANCE_KEY: OnceLock<Vec<u8>> = OnceLock::new();
+
+/// Whether a cursor carries this component's scope (it may still be invalid)
+pub fn is_own(cursor: &str) -> bool {
+    CursorCodec::new(SCOPE, &[]).owns(cursor)
+}
+
+/// Codec for this request's cursors
+pub fn codec(ctx: &MessageContext, config: &FsConfig) -> Result<CursorCodec, String> {
+    if let Some(key) = &config.cursor_key {
+        return Ok(CursorCodec::new(SCOPE, key.as_bytes()));
+    }
+    if let Some(session) = &ctx.session {
+        let session = sessions::Session::open(&session.session_id, &session.store_id)
+            .map_err(|e| format!("Failed to open session for the cursor key: {:?}", e))?;
+        return session_key(&session).map(|key| CursorCodec::new(SCOPE, &key));
+    }
+    if ctx.http_context.is_some() {
+        return Err(
+            "Paging resources/list without a session needs WASMCP_CURSOR_KEY: \
+             each request runs in a new instance"
+                .to_string(),
+        );
+    }
+    Ok(CursorCodec::new(
+        SCOPE,
+        INSTANCE_KEY.get_or_init(|| get_random_bytes(KEY_BYTES)),
+    ))
+}
+
+/// The session's cursor key, drawn and stored on first use
+fn session_key(session: &sessions::Session) -> Result<Vec<u8>, String> {
+    match session.get(SESSION_KEY) {
+        Ok(Some(TypedValue::AsBytes(key))) if !key.is_empty() => return Ok(key),
+        Ok(_) => {}
+        Err(e) => return Err(format!("Failed to read the cursor key: {:?}", e)),
+    }
+    let key = get_random_bytes(KEY_BYTES);
+    session
+        .set(SESSION_KEY, &TypedValue::AsBytes(key.clone()))
+        .map_err(|e| format!("Failed to store the cursor key: {:?}", e))?;
+    Ok(key)
+}
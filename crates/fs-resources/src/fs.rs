@@ -0,0 +1,335 @@
+//! Filesystem access over wasi:filesystem preopens
+//!
+//! Every preopened directory is exposed under `file://<guest path>/...`. A
+//! preopen mounted at `/data` serves `/data/notes/a.md` as
+//! `file:///data/notes/a.md`; a preopen mounted at `.` is served from `file:///`.
+//!
+//! Paths are always resolved relative to a preopen descriptor, and `..`
+//! components are rejected before any filesystem call, so resources can never
+//! escape the directories the host granted. Unless hidden files are included,
+//! dotfiles and dot-directories can't be read or written either, not only
+//! left out of listings.
+
+use crate::bindings::wasi::filesystem::preopens::get_directories;
+use crate::bindings::wasi::filesystem::types::{
+    Descriptor, DescriptorFlags, DescriptorType, OpenFlags, PathFlags,
+};
+
+/// Upper bound on files collected per listing walk
+const MAX_WALK_FILES: usize = 10_000;
+
+/// Bytes requested per read call
+const READ_CHUNK_SIZE: u64 = 64 * 1024;
+
+/// A preopened directory and the guest path it is mounted at
+pub struct Preopen {
+    descriptor: Descriptor,
+    /// Normalized mount path: "" for the root, otherwise "/segment[/segment...]"
+    base: String,
+}
+
+/// A file discovered while listing
+pub struct FileEntry {
+    pub uri: String,
+    /// Path relative to the preopen, '/'-separated
+    pub path: String,
+    pub size: u64,
+}
+
+/// All preopened directories granted by the host
+pub fn preopens() -> Vec<Preopen> {
+    get_directories()
+        .into_iter()
+        .map(|(descriptor, name)| Preopen {
+            descriptor,
+            base: normalize_base(&name),
+        })
+        .collect()
+}
+
+fn normalize_base(name: &str) -> String {
+    let trimmed = name.trim_start_matches("./").trim_end_matches('/');
+    if trimmed.is_empty() || trimmed == "." {
+        String::new()
+    } else if trimmed.starts_with('/') {
+        trimmed.to_string()
+    } else {
+        format!("/{}", trimmed)
+    }
+}
+
+impl Preopen {
+    fn uri(&self, path: &str) -> String {
+        format!("file://{}/{}", self.base, path)
+    }
+}
+
+/// Walk all preopens and collect regular files in a stable order
+pub fn list_files(preopens: &[Preopen], include_hidden: bool) -> Vec<FileEntry> {
+    let mut files = Vec::new();
+    for preopen in preopens {
+        walk(preopen, &preopen.descriptor, "", include_hidden, &mut files);
+    }
+    files
+}
+
+fn walk(
+    preopen: &Preopen,
+    dir: &Descriptor,
+    prefix: &str,
+    include_hidden: bool,
+    files: &mut Vec<FileEntry>,
+) {
+    let Ok(stream) = dir.read_directory() else {
+        return;
+    };
+
+    let mut entries = Vec::new();
+    while let Ok(Some(entry)) = stream.read_directory_entry() {
+        if is_visible(&entry.name, include_hidden) {
+            entries.push(entry);
+        }
+    }
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+
+    for entry in entries {
+        if files.len() >= MAX_WALK_FILES {
+            return;
+        }
+
+        let path = if prefix.is_empty() {
+            entry.name.clone()
+        } else {
+            format!("{}/{}", prefix, entry.name)
+        };
+
+        match entry.type_ {
+            DescriptorType::RegularFile => {
+                let size = dir
+                    .stat_at(PathFlags::empty(), &entry.name)
+                    .map(|stat| stat.size)
+                    .unwrap_or(0);
+                files.push(FileEntry {
+                    uri: preopen.uri(&path),
+                    path,
+                    size,
+                });
+            }
+            DescriptorType::Directory => {
+                if let Ok(child) = dir.open_at(
+                    PathFlags::empty(),
+                    &entry.name,
+                    OpenFlags::DIRECTORY,
+                    DescriptorFlags::READ,
+                ) {
+                    walk(preopen, &child, &path, include_hidden, files);
+                }
+            }
+            // Symlinks and special files are never followed or exposed
+            _ => {}
+        }
+    }
+}
+
+/// Whether a directory entry is served
+fn is_visible(name: &str, include_hidden: bool) -> bool {
+    include_hidden || !name.starts_with('.')
+}
+
+/// Resolve a file:// URI to a preopen and a path relative to it
+///
+/// Returns Ok(None) when the URI is not under any preopen (another provider
+/// may own it) and Err when it is under a preopen but the path is unsafe or
+/// hidden.
+pub fn resolve<'a>(
+    preopens: &'a [Preopen],
+    uri: &str,
+    include_hidden: bool,
+) -> Result<Option<(&'a Preopen, String)>, String> {
+    let bases: Vec<&str> = preopens.iter().map(|p| p.base.as_str()).collect();
+    let resolved = resolve_path(&bases, uri, include_hidden)?;
+    Ok(resolved.map(|(index, path)| (&preopens[index], path)))
+}
+
+/// `resolve` over mount paths: the index of the preopen and the path
+fn resolve_path(
+    bases: &[&str],
+    uri: &str,
+    include_hidden: bool,
+) -> Result<Option<(usize, String)>, String> {
+    let Some(full_path) = uri.strip_prefix("file://") else {
+        return Ok(None);
+    };
+
+    // Longest mount path wins so nested preopens resolve to the innermost one
+    let Some((index, relative)) = bases
+        .iter()
+        .enumerate()
+        .filter_map(|(i, base)| {
+            full_path
+                .strip_prefix(&format!("{}/", base))
+                .map(|rest| (i, rest))
+        })
+        .max_by_key(|(i, _)| bases[*i].len())
+    else {
+        return Ok(None);
+    };
+
+    if relative
+        .split('/')
+        .any(|segment| segment.is_empty() || segment == "." || segment == "..")
+    {
+        return Err(format!("Invalid file path in URI: {}", uri));
+    }
+    if !relative
+        .split('/')
+        .all(|segment| is_visible(segment, include_hidden))
+    {
+        return Err(format!("Hidden files are not served: {}", uri));
+    }
+
+    Ok(Some((index, relative.to_string())))
+}
+
+/// Read a whole file, refusing files larger than `max_bytes`
+pub fn read_file(preopen: &Preopen, path: &str, max_bytes: u64) -> Result<Vec<u8>, String> {
+    let file = preopen
+        .descriptor
+        .open_at(
+            PathFlags::empty(),
+            path,
+            OpenFlags::empty(),
+            DescriptorFlags::READ,
+        )
+        .map_err(|e| format!("Failed to open {}: {:?}", path, e))?;
+
+    let stat = file
+        .stat()
+        .map_err(|e| format!("Failed to stat {}: {:?}", path, e))?;
+    if stat.type_ != DescriptorType::RegularFile {
+        return Err(format!("Not a regular file: {}", path));
+    }
+    if stat.size > max_bytes {
+        return Err(format!(
+            "File {} is {} bytes, exceeding the {} byte read limit",
+            path, stat.size, max_bytes
+        ));
+    }
+
+    let mut contents = Vec::with_capacity(stat.size as usize);
+    loop {
+        let (chunk, end_of_file) = file
+            .read(READ_CHUNK_SIZE, contents.len() as u64)
+            .map_err(|e| format!("Failed to read {}: {:?}", path, e))?;
+        contents.extend_from_slice(&chunk);
+        if end_of_file || chunk.is_empty() {
+            break;
+        }
+        if contents.len() as u64 > max_bytes {
+            return Err(format!("File {} grew past the read limit", path));
+        }
+    }
+
+    Ok(contents)
+}
+
+/// Create or replace a file with the given contents
+pub fn write_file(preopen: &Preopen, path: &str, contents: &[u8]) -> Result<(), String> {
+    let file = preopen
+        .descriptor
+        .open_at(
+            PathFlags::empty(),
+            path,
+            OpenFlags::CREATE | OpenFlags::TRUNCATE,
+            DescriptorFlags::WRITE,
+        )
+        .map_err(|e| format!("Failed to open {} for writing: {:?}", path, e))?;
+
+    let mut written = 0usize;
+    while written < contents.len() {
+        let n = file
+            .write(&contents[written..], written as u64)
+            .map_err(|e| format!("Failed to write {}: {:?}", path, e))?;
+        if n == 0 {
+            return Err(format!("Failed to write {}: no progress", path));
+        }
+        written += n as usize;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_base() {
+        assert_eq!(normalize_base("."), "");
+        assert_eq!(normalize_base("./"), "");
+        assert_eq!(normalize_base("/"), "");
+        assert_eq!(normalize_base("/data/"), "/data");
+        assert_eq!(normalize_base("./data"), "/data");
+        assert_eq!(normalize_base("data/notes"), "/data/notes");
+    }
+
+    #[test]
+    fn test_resolve_path() {
+        let bases = ["", "/data", "/data/notes"];
+        assert_eq!(
+            resolve_path(&bases, "file:///readme.md", false),
+            Ok(Some((0, "readme.md".to_string())))
+        );
+        // The innermost mount wins
+        assert_eq!(
+            resolve_path(&bases, "file:///data/notes/a.md", false),
+            Ok(Some((2, "a.md".to_string())))
+        );
+        assert_eq!(
+            resolve_path(&bases, "file:///data/b/c.md", false),
+            Ok(Some((1, "b/c.md".to_string())))
+        );
+        assert_eq!(
+            resolve_path(&bases, "https://example.com/a", false),
+            Ok(None)
+        );
+        assert_eq!(
+            resolve_path(&["/data"], "file:///etc/passwd", false),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn test_resolve_path_rejects_escapes() {
+        for uri in [
+            "file:///data/../etc/passwd",
+            "file:///data/./a.md",
+            "file:///data//a.md",
+            "file:///data/a/",
+            "file:///data/a/..",
+        ] {
+            assert!(resolve_path(&["/data"], uri, true).is_err(), "{}", uri);
+        }
+    }
+
+    #[test]
+    fn test_hidden_files() {
+        for uri in ["file:///data/.env", "file:///data/.git/config"] {
+            assert!(resolve_path(&["/data"], uri, false).is_err(), "{}", uri);
+            assert!(
+                resolve_path(&["/data"], uri, true).unwrap().is_some(),
+                "{}",
+                uri
+            );
+        }
+        // Mount paths may be hidden; only the path below them is checked
+        assert_eq!(
+            resolve_path(&["/.config"], "file:///.config/app.toml", false),
+            Ok(Some((0, "app.toml".to_string())))
+        );
+
+        assert!(is_visible("notes.md", false));
+        assert!(!is_visible(".env", false));
+        assert!(is_visible(".env", true));
+    }
+}
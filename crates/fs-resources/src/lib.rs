@@ -0,0 +1,398 @@
+//! Filesystem Resources Middleware Component
+//!
+//! A reusable middleware that exposes the host's preopened directories as MCP
+//! resources with no custom handler code. This component:
+//! - Lists files under every preopen as `file://` resources (paginated)
+//! - Reads text and binary files with MIME type detection
+//! - Optionally exposes a `write_file` tool (`WASMCP_FS_WRITABLE=true`)
+//! - Honors client roots, recorded from roots/list results
+//! - Delegates all other requests downstream
+//!
+//! Resource listing pages through this component's files first, then continues
+//! with the downstream handler's resources.
+
+#![allow(warnings)]
+
+mod bindings {
+    wit_bindgen::generate!({
+        world: "fs-resources",
+        generate_all,
+    });
+}
+
+mod config;
+mod cursor;
+mod fs;
+mod mime;
+mod roots;
+
+use bindings::exports::wasmcp::mcp_v20251125::server_handler::{Guest, MessageContext};
+use bindings::wasmcp::mcp_v20251125::mcp::*;
+use bindings::wasmcp::mcp_v20251125::server_handler as downstream;
+use config::FsConfig;
+use wasmcp_cursor::Cursor;
+
+/// Name of the optional write tool
+const WRITE_TOOL_NAME: &str = "write_file";
+
+struct FsResources;
+
+// Convert exported MessageContext to imported MessageContext
+fn to_downstream_ctx<'a>(ctx: &'a MessageContext<'a>) -> downstream::MessageContext<'a> {
+    downstream::MessageContext {
+        client_stream: ctx.client_stream,
+        protocol_version: ctx.protocol_version.clone(),
+        session: ctx.session.as_ref().map(|s| downstream::Session {
+            session_id: s.session_id.clone(),
+            store_id: s.store_id.clone(),
+        }),
+        identity: ctx.identity.as_ref().map(|i| downstream::Identity {
+            jwt: i.jwt.clone(),
+            claims: i.claims.clone(),
+        }),
+        frame: ctx.frame.clone(),
+        http_context: ctx.http_context.clone(),
+        client_capabilities: ctx.client_capabilities.clone(),
+        request_id: ctx.request_id.clone(),
+        deadline: ctx.deadline,
+        log_level: ctx.log_level,
+        client_info: ctx.client_info.clone(),
+        locale: ctx.locale.clone(),
+        trace_context: ctx.trace_context.clone(),
+    }
+}
+
+impl Guest for FsResources {
+    fn handle(
+        ctx: MessageContext,
+        message: ClientMessage,
+    ) -> Option<Result<ServerResult, ErrorCode>> {
+        let config = FsConfig::from_env();
+
+        match message {
+            ClientMessage::Request((request_id, request)) => {
+                let result = match &request {
+                    ClientRequest::ResourcesList(list_req)
+                        if list_req.cursor.as_deref().is_none_or(cursor::is_own) =>
+                    {
+                        handle_resources_list(request_id.clone(), list_req, &ctx, &config)
+                    }
+                    ClientRequest::ResourcesRead(read_req) => {
+                        match handle_resources_read(read_req, &ctx, &config) {
+                            Some(result) => result,
+                            None => return delegate(&ctx, request_id, request),
+                        }
+                    }
+                    ClientRequest::ToolsList(list_req) if config.writable => {
+                        handle_tools_list(request_id.clone(), list_req.clone(), &ctx)
+                    }
+                    ClientRequest::ToolsCall(call_req)
+                        if config.writable && call_req.name == WRITE_TOOL_NAME =>
+                    {
+                        Ok(handle_write_file(call_req, &ctx, &config))
+                    }
+                    _ => return delegate(&ctx, request_id, request),
+                };
+                Some(result)
+            }
+            ClientMessage::Result((result_id, ClientResult::RootsList(result))) => {
+                roots::store_roots(&ctx, &result.roots);
+
+                // Our own roots request ends here; others continue downstream
+                if matches!(&result_id, RequestId::String(id) if id == roots::ROOTS_REQUEST_ID) {
+                    return None;
+                }
+                let message = ClientMessage::Result((result_id, ClientResult::RootsList(result)));
+                downstream::handle(&to_downstream_ctx(&ctx), message)
+            }
+            ClientMessage::Notification(ClientNotification::RootsListChanged(options)) => {
+                roots::clear_roots(&ctx);
+                roots::request_roots(&ctx);
+                let message =
+                    ClientMessage::Notification(ClientNotification::RootsListChanged(options));
+                downstream::handle(&to_downstream_ctx(&ctx), message)
+            }
+            _ => {
+                // Forward notifications, results, errors to downstream
+                downstream::handle(&to_downstream_ctx(&ctx), message)
+            }
+        }
+    }
+}
+
+fn delegate(
+    ctx: &MessageContext,
+    request_id: RequestId,
+    request: ClientRequest,
+) -> Option<Result<ServerResult, ErrorCode>> {
+    let downstream_msg = ClientMessage::Request((request_id, request));
+    downstream::handle(&to_downstream_ctx(ctx), downstream_msg)
+}
+
+fn handle_resources_list(
+    request_id: RequestId,
+    req: &ListResourcesRequest,
+    ctx: &MessageContext,
+    config: &FsConfig,
+) -> Result<ServerResult, ErrorCode> {
+    let offset = match req.cursor.as_deref() {
+        Some(c) => {
+            cursor::codec(ctx, config)
+                .map_err(|e| internal_error(&e))?
+                .decode(c)
+                .map_err(|e| invalid_params(&e.to_string()))?
+                .offset as usize
+        }
+        None => 0,
+    };
+
+    let client_roots = roots::load_roots(ctx);
+    if client_roots.is_none() {
+        roots::request_roots(ctx);
+    }
+
+    let files: Vec<fs::FileEntry> = fs::list_files(&fs::preopens(), config.include_hidden)
+        .into_iter()
+        .filter(|f| roots::is_allowed(client_roots.as_deref(), &f.uri))
+        .collect();
+
+    let end = offset.saturating_add(config.page_size).min(files.len());
+    let mut resources: Vec<McpResource> = files
+        .get(offset..end)
+        .unwrap_or_default()
+        .iter()
+        .map(to_resource)
+        .collect();
+
+    if end < files.len() {
+        return Ok(ServerResult::ResourcesList(ListResourcesResult {
+            resources,
+            next_cursor: Some(
+                cursor::codec(ctx, config)
+                    .map_err(|e| internal_error(&e))?
+                    .encode(&Cursor::at(end as u64)),
+            ),
+            meta: None,
+        }));
+    }
+
+    // Our files are exhausted - continue with the downstream handler's first page
+    let downstream_req = ClientRequest::ResourcesList(ListResourcesRequest { cursor: None });
+    let downstream_msg = ClientMessage::Request((request_id, downstream_req));
+    let next_cursor = match downstream::handle(&to_downstream_ctx(ctx), downstream_msg) {
+        Some(Ok(ServerResult::ResourcesList(downstream_result))) => {
+            resources.extend(downstream_result.resources);
+            downstream_result.next_cursor
+        }
+        Some(Err(ErrorCode::MethodNotFound(_))) | None => None,
+        Some(Err(e)) => return Err(e),
+        Some(Ok(_)) => None,
+    };
+
+    Ok(ServerResult::ResourcesList(ListResourcesResult {
+        resources,
+        next_cursor,
+        meta: None,
+    }))
+}
+
+/// Returns None for URIs this component doesn't own
+fn handle_resources_read(
+    req: &ReadResourceRequest,
+    ctx: &MessageContext,
+    config: &FsConfig,
+) -> Option<Result<ServerResult, ErrorCode>> {
+    let preopens = fs::preopens();
+    let (preopen, path) = match fs::resolve(&preopens, &req.uri, config.include_hidden) {
+        Ok(Some(resolved)) => resolved,
+        Ok(None) => return None,
+        Err(e) => return Some(Err(invalid_params(&e))),
+    };
+
+    if !roots::is_allowed(roots::load_roots(ctx).as_deref(), &req.uri) {
+        return Some(Err(invalid_params(&format!(
+            "Resource is outside the client's roots: {}",
+            req.uri
+        ))));
+    }
+
+    let contents = match fs::read_file(preopen, &path, config.max_read_bytes) {
+        Ok(contents) => contents,
+        Err(e) => return Some(Err(invalid_params(&e))),
+    };
+
+    let mime_type = mime::mime_type(&path);
+    let options = Some(EmbeddedResourceOptions {
+        mime_type: Some(mime_type.to_string()),
+        meta: None,
+    });
+
+    let resource_contents = match String::from_utf8(contents) {
+        Ok(text) if mime::is_text(mime_type) => ResourceContents::Text(TextResourceContents {
+            uri: req.uri.clone(),
+            text: TextData::Text(text),
+            options,
+        }),
+        Ok(text) => ResourceContents::Blob(BlobResourceContents {
+            uri: req.uri.clone(),
+            blob: BlobData::Blob(text.into_bytes()),
+            options,
+        }),
+        Err(e) => ResourceContents::Blob(BlobResourceContents {
+            uri: req.uri.clone(),
+            blob: BlobData::Blob(e.into_bytes()),
+            options,
+        }),
+    };
+
+    Some(Ok(ServerResult::ResourcesRead(ReadResourceResult {
+        contents: vec![resource_contents],
+        meta: None,
+    })))
+}
+
+fn handle_tools_list(
+    request_id: RequestId,
+    req: ListToolsRequest,
+    ctx: &MessageContext,
+) -> Result<ServerResult, ErrorCode> {
+    let downstream_msg = ClientMessage::Request((request_id, ClientRequest::ToolsList(req)));
+    match downstream::handle(&to_downstream_ctx(ctx), downstream_msg) {
+        Some(Ok(ServerResult::ToolsList(mut downstream_result))) => {
+            downstream_result.tools.insert(0, write_tool());
+            Ok(ServerResult::ToolsList(downstream_result))
+        }
+        Some(Err(ErrorCode::MethodNotFound(_))) | None | Some(Ok(_)) => {
+            Ok(ServerResult::ToolsList(ListToolsResult {
+                tools: vec![write_tool()],
+                next_cursor: None,
+                meta: None,
+            }))
+        }
+        Some(Err(e)) => Err(e),
+    }
+}
+
+fn write_tool() -> Tool {
+    Tool {
+        name: WRITE_TOOL_NAME.to_string(),
+        input_schema: r#"{
+            "type": "object",
+            "properties": {
+                "uri": {"type": "string", "description": "file:// URI of the file to create or replace"},
+                "content": {"type": "string", "description": "UTF-8 text to write"}
+            },
+            "required": ["uri", "content"]
+        }"#
+        .to_string(),
+        options: Some(ToolOptions {
+            meta: None,
+            annotations: Some(ToolAnnotations {
+                destructive_hint: Some(true),
+                idempotent_hint: Some(true),
+                open_world_hint: Some(false),
+                read_only_hint: Some(false),
+                title: None,
+            }),
+            description: Some(
+                "Create or replace a file in a directory exposed as file:// resources".to_string(),
+            ),
+            output_schema: None,
+            title: Some("Write File".to_string()),
+            icons: None,
+        }),
+    }
+}
+
+fn handle_write_file(
+    req: &CallToolRequest,
+    ctx: &MessageContext,
+    config: &FsConfig,
+) -> ServerResult {
+    match write_file(req, ctx, config) {
+        Ok(uri) => tool_result(format!("Wrote {}", uri), false),
+        Err(e) => tool_result(e, true),
+    }
+}
+
+fn write_file(
+    req: &CallToolRequest,
+    ctx: &MessageContext,
+    config: &FsConfig,
+) -> Result<String, String> {
+    let args: serde_json::Value = req
+        .arguments
+        .as_deref()
+        .map(serde_json::from_str)
+        .transpose()
+        .map_err(|e| format!("Invalid arguments: {}", e))?
+        .unwrap_or_default();
+
+    let uri = args
+        .get("uri")
+        .and_then(|v| v.as_str())
+        .ok_or("Missing 'uri' argument")?;
+    let content = args
+        .get("content")
+        .and_then(|v| v.as_str())
+        .ok_or("Missing 'content' argument")?;
+
+    let preopens = fs::preopens();
+    let (preopen, path) = fs::resolve(&preopens, uri, config.include_hidden)?
+        .ok_or_else(|| format!("Not a writable location: {}", uri))?;
+
+    if !roots::is_allowed(roots::load_roots(ctx).as_deref(), uri) {
+        return Err(format!("Location is outside the client's roots: {}", uri));
+    }
+
+    fs::write_file(preopen, &path, content.as_bytes())?;
+    Ok(uri.to_string())
+}
+
+fn to_resource(file: &fs::FileEntry) -> McpResource {
+    let title = file.path.rsplit('/').next().map(|name| name.to_string());
+
+    McpResource {
+        uri: file.uri.clone(),
+        name: file.path.clone(),
+        options: Some(ResourceOptions {
+            size: Some(file.size),
+            title,
+            description: None,
+            mime_type: Some(mime::mime_type(&file.path).to_string()),
+            annotations: None,
+            meta: None,
+            icons: None,
+        }),
+    }
+}
+
+fn tool_result(text: String, is_error: bool) -> ServerResult {
+    ServerResult::ToolsCall(CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text: TextData::Text(text),
+            options: None,
+        })],
+        is_error: is_error.then_some(true),
+        meta: None,
+        structured_content: None,
+    })
+}
+
+fn invalid_params(message: &str) -> ErrorCode {
+    ErrorCode::InvalidParams(Error {
+        code: -32602,
+        message: message.to_string(),
+        data: None,
+    })
+}
+
+fn internal_error(message: &str) -> ErrorCode {
+    ErrorCode::InternalError(Error {
+        code: -32603,
+        message: message.to_string(),
+        data: None,
+    })
+}
+
+bindings::export!(FsResources with_types_in bindings);
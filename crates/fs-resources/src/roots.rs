@@ -0,0 +1,115 @@
+//! Client roots tracking
+//!
+//! Clients that support roots tell the server which directories it may work
+//! with. This component records every roots/list result that flows through the
+//! chain and only exposes files inside those roots.
+//!
+//! Roots are persisted in the session (HTTP creates a fresh instance per
+//! request) and cached in-process for stdio, where there is no session bucket.
+//! Until a client has reported roots, no roots filtering is applied.
+
+use crate::bindings::exports::wasmcp::mcp_v20251125::server_handler::MessageContext;
+use crate::bindings::wasmcp::keyvalue::store::TypedValue;
+use crate::bindings::wasmcp::mcp_v20251125::mcp::{
+    ClientLists, ListRootsRequest, RequestId, Root, ServerMessage, ServerRequest,
+};
+use crate::bindings::wasmcp::mcp_v20251125::{server_io, sessions};
+use std::cell::RefCell;
+
+/// Session key holding the client's root URIs as a JSON array
+const ROOTS_KEY: &str = "fs:roots";
+
+/// Request ID used for roots/list requests issued by this component
+pub const ROOTS_REQUEST_ID: &str = "fs-resources:roots";
+
+thread_local! {
+    /// Roots for connections without a session (stdio)
+    static CACHED_ROOTS: RefCell<Option<Vec<String>>> = const { RefCell::new(None) };
+}
+
+/// Root URIs reported by the client, if known
+pub fn load_roots(ctx: &MessageContext) -> Option<Vec<String>> {
+    if let Some(session) = open_session(ctx) {
+        return match session.get(ROOTS_KEY) {
+            Ok(Some(TypedValue::AsJson(json))) => serde_json::from_str(&json).ok(),
+            _ => None,
+        };
+    }
+    CACHED_ROOTS.with(|c| c.borrow().clone())
+}
+
+/// Record the roots from a roots/list result
+pub fn store_roots(ctx: &MessageContext, roots: &[Root]) {
+    let uris: Vec<String> = roots.iter().map(|r| r.uri.clone()).collect();
+
+    if let Some(session) = open_session(ctx) {
+        let json = serde_json::Value::from(uris).to_string();
+        if let Err(e) = session.set(ROOTS_KEY, &TypedValue::AsJson(json)) {
+            eprintln!("[fs-resources] Failed to store client roots: {:?}", e);
+        }
+        return;
+    }
+    CACHED_ROOTS.with(|c| *c.borrow_mut() = Some(uris));
+}
+
+/// Forget recorded roots (the client said they changed)
+pub fn clear_roots(ctx: &MessageContext) {
+    if let Some(session) = open_session(ctx) {
+        // Sessions have no delete; JSON null loads back as "unknown"
+        if let Err(e) = session.set(ROOTS_KEY, &TypedValue::AsJson("null".to_string())) {
+            eprintln!("[fs-resources] Failed to clear client roots: {:?}", e);
+        }
+        return;
+    }
+    CACHED_ROOTS.with(|c| *c.borrow_mut() = None);
+}
+
+/// Whether a file URI falls inside the client's roots
+pub fn is_allowed(roots: Option<&[String]>, uri: &str) -> bool {
+    let Some(roots) = roots else {
+        return true;
+    };
+    roots.iter().any(|root| {
+        let root = root.trim_end_matches('/');
+        uri == root || uri.starts_with(&format!("{}/", root))
+    })
+}
+
+/// Ask the client for its roots, if it supports them and a stream is available
+///
+/// The response arrives later as a separate client result and is recorded by
+/// `store_roots`. Plain JSON responses carry exactly one message, so the
+/// request is only sent on framed streams (SSE or stdio).
+pub fn request_roots(ctx: &MessageContext) {
+    // Roots are offered with or without listChanged
+    let supports_roots = ctx.client_capabilities.as_ref().is_some_and(|c| {
+        [c.lists, c.list_changed]
+            .into_iter()
+            .flatten()
+            .any(|lists| lists.contains(ClientLists::ROOTS))
+    });
+    let framed = !ctx.frame.prefix.is_empty() || !ctx.frame.suffix.is_empty();
+
+    let Some(stream) = ctx.client_stream else {
+        return;
+    };
+    if !supports_roots || !framed {
+        return;
+    }
+
+    let request = ServerMessage::Request((
+        RequestId::String(ROOTS_REQUEST_ID.to_string()),
+        ServerRequest::RootsList(ListRootsRequest {
+            meta: None,
+            progress_token: None,
+        }),
+    ));
+    if let Err(e) = server_io::send_message(stream, request, &ctx.frame) {
+        eprintln!("[fs-resources] Failed to request client roots: {:?}", e);
+    }
+}
+
+fn open_session(ctx: &MessageContext) -> Option<sessions::Session> {
+    let session = ctx.session.as_ref()?;
+    sessions::Session::open(&session.session_id, &session.store_id).ok()
+}
@@ -0,0 +1,173 @@
+//! Glob Filter Middleware Component
+//!
+//! Restricts what a composed handler exposes without recompiling it, e.g. to
+//! hide administrative tools of a third-party tool pack. This component:
+//! - Removes denied tools, resources, resource templates and prompts from
+//!   list results, and denied resources from `resources/search` results
+//! - Blocks calls, reads, subscriptions, prompt gets and completions that
+//!   target them, answering as if they did not exist
+//! - Delegates everything else downstream unchanged
+//!
+//! Rules come from `WASMCP_FILTER_ALLOW` and `WASMCP_FILTER_DENY`; see
+//! `rules.rs` for the pattern syntax.
+
+#![allow(warnings)]
+
+mod bindings {
+    wit_bindgen::generate!({
+        world: "glob-filter",
+        generate_all,
+    });
+}
+
+mod rules;
+mod search;
+
+use bindings::exports::wasmcp::mcp_v20251125::server_handler::{Guest, MessageContext};
+use bindings::wasmcp::mcp_v20251125::mcp;
+use bindings::wasmcp::mcp_v20251125::mcp::*;
+use bindings::wasmcp::mcp_v20251125::server_handler as downstream;
+use rules::{Kind, Rules};
+use std::sync::OnceLock;
+use wasmcp_errors::McpError;
+
+wasmcp_errors::impl_error_code!(mcp::ErrorCode, mcp::Error);
+
+/// Completion references to tool arguments (`ref/tool`, see server-io)
+const TOOL_REF_URI_PREFIX: &str = "wasmcp://tool/";
+
+/// Rules, compiled once per instance
+static RULES: OnceLock<Rules> = OnceLock::new();
+
+struct GlobFilter;
+
+// Convert exported MessageContext to imported MessageContext
+fn to_downstream_ctx<'a>(ctx: &'a MessageContext<'a>) -> downstream::MessageContext<'a> {
+    downstream::MessageContext {
+        client_stream: ctx.client_stream,
+        protocol_version: ctx.protocol_version.clone(),
+        session: ctx.session.as_ref().map(|s| downstream::Session {
+            session_id: s.session_id.clone(),
+            store_id: s.store_id.clone(),
+        }),
+        identity: ctx.identity.as_ref().map(|i| downstream::Identity {
+            jwt: i.jwt.clone(),
+            claims: i.claims.clone(),
+        }),
+        frame: ctx.frame.clone(),
+        http_context: ctx.http_context.clone(),
+        client_capabilities: ctx.client_capabilities.clone(),
+        request_id: ctx.request_id.clone(),
+        deadline: ctx.deadline,
+        log_level: ctx.log_level,
+        client_info: ctx.client_info.clone(),
+        locale: ctx.locale.clone(),
+        trace_context: ctx.trace_context.clone(),
+    }
+}
+
+impl Guest for GlobFilter {
+    fn handle(
+        ctx: MessageContext,
+        message: ClientMessage,
+    ) -> Option<Result<ServerResult, ErrorCode>> {
+        let ClientMessage::Request((request_id, request)) = message else {
+            // Forward notifications, results, errors to downstream
+            return downstream::handle(&to_downstream_ctx(&ctx), message);
+        };
+
+        let rules = RULES.get_or_init(Rules::from_env);
+        if let Some(error) = blocked(rules, &request) {
+            return Some(Err(error.into()));
+        }
+
+        let is_list = matches!(
+            request,
+            ClientRequest::ToolsList(_)
+                | ClientRequest::ResourcesList(_)
+                | ClientRequest::ResourcesTemplatesList(_)
+                | ClientRequest::PromptsList(_)
+        ) || matches!(&request, ClientRequest::Custom(req) if req.method == search::METHOD);
+        let downstream_msg = ClientMessage::Request((request_id, request));
+        let response = downstream::handle(&to_downstream_ctx(&ctx), downstream_msg);
+
+        if is_list {
+            response.map(|result| result.map(|r| filter_list(rules, r)))
+        } else {
+            response
+        }
+    }
+}
+
+/// The error for a request that targets a hidden item
+///
+/// Errors match what a handler returns for an item that does not exist, so
+/// clients cannot tell hidden items from missing ones.
+fn blocked(rules: &Rules, request: &ClientRequest) -> Option<McpError> {
+    match request {
+        ClientRequest::ToolsCall(req) if !rules.allows(Kind::Tool, &req.name) => {
+            Some(McpError::tool_not_found(&req.name))
+        }
+        ClientRequest::ResourcesRead(req) if !rules.allows(Kind::Resource, &req.uri) => {
+            Some(McpError::resource_not_found(&req.uri))
+        }
+        ClientRequest::ResourcesSubscribe(uri) | ClientRequest::ResourcesUnsubscribe(uri)
+            if !rules.allows(Kind::Resource, uri) =>
+        {
+            Some(McpError::resource_not_found(uri))
+        }
+        ClientRequest::PromptsGet(req) if !rules.allows(Kind::Prompt, &req.name) => Some(
+            McpError::invalid_params(format!("Unknown prompt: {}", req.name)),
+        ),
+        ClientRequest::CompletionComplete(req) => match &req.ref_ {
+            CompletionReference::Prompt(prompt) if !rules.allows(Kind::Prompt, &prompt.name) => {
+                Some(McpError::invalid_params(format!(
+                    "Unknown prompt: {}",
+                    prompt.name
+                )))
+            }
+            CompletionReference::ResourceTemplate(uri) => {
+                match uri.strip_prefix(TOOL_REF_URI_PREFIX) {
+                    Some(tool) if !rules.allows(Kind::Tool, tool) => {
+                        Some(McpError::tool_not_found(tool))
+                    }
+                    Some(_) => None,
+                    None if !rules.allows(Kind::Resource, uri) => {
+                        Some(McpError::resource_not_found(uri))
+                    }
+                    None => None,
+                }
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Drop hidden items from a list result, keeping its cursor
+fn filter_list(rules: &Rules, result: ServerResult) -> ServerResult {
+    match result {
+        ServerResult::ToolsList(mut list) => {
+            list.tools.retain(|t| rules.allows(Kind::Tool, &t.name));
+            ServerResult::ToolsList(list)
+        }
+        ServerResult::ResourcesList(mut list) => {
+            list.resources
+                .retain(|r| rules.allows(Kind::Resource, &r.uri));
+            ServerResult::ResourcesList(list)
+        }
+        ServerResult::ResourcesTemplatesList(mut list) => {
+            list.resource_templates
+                .retain(|t| rules.allows(Kind::Resource, &t.uri_template));
+            ServerResult::ResourcesTemplatesList(list)
+        }
+        ServerResult::PromptsList(mut list) => {
+            list.prompts.retain(|p| rules.allows(Kind::Prompt, &p.name));
+            ServerResult::PromptsList(list)
+        }
+        ServerResult::Custom(result) => ServerResult::Custom(search::filter(rules, result)),
+        other => other,
+    }
+}
+
+bindings::export!(GlobFilter with_types_in bindings);
@@ -0,0 +1,312 @@
+//! Allow/deny rules with glob patterns
+//!
+//! Environment variables:
+//! - `WASMCP_FILTER_ALLOW`: Comma-separated patterns; when any apply to a
+//!   kind, only matching items of that kind are exposed
+//! - `WASMCP_FILTER_DENY`: Comma-separated patterns; matching items are
+//!   always hidden ("Deny Trumps Allow")
+//!
+//! A pattern is `[kind:]glob`, where `kind` is `tool`, `resource` or
+//! `prompt` and scopes the pattern to that kind. Without a kind the pattern
+//! applies to all three. Tools and prompts match by name, resources and
+//! resource templates by URI (template). In globs, `*` matches any run of
+//! characters and `?` exactly one.
+//!
+//! ```text
+//! WASMCP_FILTER_DENY="admin_*,resource:file:///etc/*"
+//! WASMCP_FILTER_ALLOW="tool:search_*,tool:fetch"
+//! ```
+//!
+//! Patterns are compiled once into a trie keyed by their literal prefix (the
+//! characters before the first wildcard). A lookup walks the name through
+//! the trie and only tries the globs whose prefix it passes, so thousands of
+//! URI patterns cost about as much as the few that share the name's prefix.
+
+use crate::bindings::wasi::cli::environment::get_environment;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Tool,
+    Resource,
+    Prompt,
+}
+
+impl Kind {
+    fn from_prefix(prefix: &str) -> Option<Self> {
+        match prefix {
+            "tool" => Some(Kind::Tool),
+            "resource" => Some(Kind::Resource),
+            "prompt" => Some(Kind::Prompt),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Pattern {
+    /// None applies to every kind
+    kind: Option<Kind>,
+    glob: String,
+}
+
+impl Pattern {
+    fn parse(spec: &str) -> Self {
+        match spec.split_once(':') {
+            Some((prefix, glob)) if Kind::from_prefix(prefix).is_some() => Pattern {
+                kind: Kind::from_prefix(prefix),
+                glob: glob.to_string(),
+            },
+            // Includes URIs such as "file:///etc/*", whose scheme is not a kind
+            _ => Pattern {
+                kind: None,
+                glob: spec.to_string(),
+            },
+        }
+    }
+}
+
+/// Patterns compiled into a trie over their literal prefixes
+#[derive(Debug, Default)]
+struct PatternSet {
+    /// Trie nodes, the root first; empty when there are no patterns
+    nodes: Vec<Node>,
+    /// Kind scopes present in the set
+    kinds: Vec<Option<Kind>>,
+}
+
+#[derive(Debug, Default)]
+struct Node {
+    children: HashMap<char, usize>,
+    /// Patterns whose literal prefix ends at this node: kind scope and the
+    /// rest of the glob, starting at its first wildcard
+    globs: Vec<(Option<Kind>, Vec<char>)>,
+}
+
+impl PatternSet {
+    fn new(patterns: impl IntoIterator<Item = Pattern>) -> Self {
+        let mut set = PatternSet::default();
+        for pattern in patterns {
+            if set.nodes.is_empty() {
+                set.nodes.push(Node::default());
+            }
+            if !set.kinds.contains(&pattern.kind) {
+                set.kinds.push(pattern.kind);
+            }
+
+            let glob: Vec<char> = pattern.glob.chars().collect();
+            let split = glob
+                .iter()
+                .position(|&c| c == '*' || c == '?')
+                .unwrap_or(glob.len());
+            let mut node = 0;
+            for &c in &glob[..split] {
+                node = match set.nodes[node].children.get(&c) {
+                    Some(&child) => child,
+                    None => {
+                        set.nodes.push(Node::default());
+                        let child = set.nodes.len() - 1;
+                        set.nodes[node].children.insert(c, child);
+                        child
+                    }
+                };
+            }
+            set.nodes[node]
+                .globs
+                .push((pattern.kind, glob[split..].to_vec()));
+        }
+        set
+    }
+
+    /// Whether any pattern is scoped to this kind or unscoped
+    fn applies_to(&self, kind: Kind) -> bool {
+        self.kinds.iter().any(|k| k.is_none_or(|k| k == kind))
+    }
+
+    /// Whether a pattern that applies to this kind matches the name
+    fn matches(&self, kind: Kind, name: &str) -> bool {
+        let Some(mut node) = self.nodes.first() else {
+            return false;
+        };
+        let text: Vec<char> = name.chars().collect();
+        for t in 0..=text.len() {
+            let rest = &text[t..];
+            let matched = node
+                .globs
+                .iter()
+                .any(|(k, glob)| k.is_none_or(|k| k == kind) && glob_match(glob, rest));
+            if matched {
+                return true;
+            }
+            match text.get(t).and_then(|c| node.children.get(c)) {
+                Some(&child) => node = &self.nodes[child],
+                None => return false,
+            }
+        }
+        false
+    }
+}
+
+/// Filter rules from configuration
+#[derive(Debug, Default)]
+pub struct Rules {
+    allow: PatternSet,
+    deny: PatternSet,
+}
+
+impl Rules {
+    /// Load rules from environment variables
+    pub fn from_env() -> Self {
+        let env_map: HashMap<String, String> = get_environment().into_iter().collect();
+        let get = |key: &str| env_map.get(key).map(String::as_str).unwrap_or_default();
+        Self::parse(get("WASMCP_FILTER_ALLOW"), get("WASMCP_FILTER_DENY"))
+    }
+
+    pub(crate) fn parse(allow: &str, deny: &str) -> Self {
+        let patterns = |spec: &str| {
+            PatternSet::new(
+                spec.split(',')
+                    .map(str::trim)
+                    .filter(|p| !p.is_empty())
+                    .map(Pattern::parse),
+            )
+        };
+        Rules {
+            allow: patterns(allow),
+            deny: patterns(deny),
+        }
+    }
+
+    /// Whether an item of this kind is exposed
+    pub fn allows(&self, kind: Kind, name: &str) -> bool {
+        if self.deny.matches(kind, name) {
+            return false;
+        }
+        !self.allow.applies_to(kind) || self.allow.matches(kind, name)
+    }
+}
+
+/// Match `text` against a glob where `*` is any run and `?` any one character
+fn glob_match(pattern: &[char], text: &[char]) -> bool {
+    let (mut p, mut t) = (0, 0);
+    // Position of the last `*` and the text position it is matched up to
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                // Let the last `*` absorb one more character
+                Some((star, star_t)) => {
+                    backtrack = Some((star, star_t + 1));
+                    p = star + 1;
+                    t = star_t + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn glob(pattern: &str, text: &str) -> bool {
+        let pattern: Vec<char> = pattern.chars().collect();
+        let text: Vec<char> = text.chars().collect();
+        glob_match(&pattern, &text)
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob("admin_*", "admin_reset"));
+        assert!(glob("admin_*", "admin_"));
+        assert!(!glob("admin_*", "user_admin_reset"));
+        assert!(glob("*_delete", "user_delete"));
+        assert!(glob("get_?", "get_x"));
+        assert!(!glob("get_?", "get_xy"));
+        assert!(glob("a*b*c", "a-b-b-c"));
+        assert!(!glob("a*b*c", "a-b-b-d"));
+        assert!(glob("*", ""));
+        assert!(glob("exact", "exact"));
+        assert!(!glob("exact", "exactly"));
+    }
+
+    #[test]
+    fn test_pattern_kinds() {
+        assert_eq!(Pattern::parse("tool:admin_*").kind, Some(Kind::Tool));
+        assert_eq!(Pattern::parse("prompt:x").kind, Some(Kind::Prompt));
+        let uri = Pattern::parse("file:///etc/*");
+        assert_eq!(uri.kind, None);
+        assert_eq!(uri.glob, "file:///etc/*");
+        let scoped = Pattern::parse("resource:file:///etc/*");
+        assert_eq!(scoped.kind, Some(Kind::Resource));
+        assert_eq!(scoped.glob, "file:///etc/*");
+    }
+
+    #[test]
+    fn test_deny_trumps_allow() {
+        let rules = Rules::parse("tool:*", "admin_*");
+        assert!(rules.allows(Kind::Tool, "search"));
+        assert!(!rules.allows(Kind::Tool, "admin_reset"));
+        assert!(!rules.allows(Kind::Prompt, "admin_help"));
+    }
+
+    #[test]
+    fn test_allow_is_per_kind() {
+        let rules = Rules::parse("tool:search_*", "");
+        assert!(rules.allows(Kind::Tool, "search_web"));
+        assert!(!rules.allows(Kind::Tool, "delete_all"));
+        // No allow patterns for resources or prompts, so they are unrestricted
+        assert!(rules.allows(Kind::Resource, "file:///data.txt"));
+        assert!(rules.allows(Kind::Prompt, "summarize"));
+    }
+
+    #[test]
+    fn test_empty_rules_allow_everything() {
+        let rules = Rules::parse("", " , ");
+        assert!(rules.allows(Kind::Tool, "anything"));
+        assert!(rules.allows(Kind::Resource, "file:///etc/passwd"));
+    }
+
+    #[test]
+    fn test_trie_shares_prefixes() {
+        let rules = Rules::parse(
+            "",
+            "resource:file:///etc/*,resource:file:///etc/passwd,file:///e?c/shadow,*.key",
+        );
+        assert!(!rules.allows(Kind::Resource, "file:///etc/hosts"));
+        assert!(!rules.allows(Kind::Resource, "file:///etc/"));
+        assert!(!rules.allows(Kind::Resource, "file:///ebc/shadow"));
+        assert!(!rules.allows(Kind::Resource, "file:///home/id.key"));
+        assert!(rules.allows(Kind::Resource, "file:///et"));
+        assert!(rules.allows(Kind::Resource, "file:///home/id.pub"));
+        // Scoped patterns only apply to their kind, even on a shared prefix
+        assert!(rules.allows(Kind::Tool, "file:///etc/hosts"));
+        assert!(!rules.allows(Kind::Tool, "file:///ebc/shadow"));
+    }
+
+    #[test]
+    fn test_many_patterns() {
+        let allow: Vec<String> = (0..5000)
+            .map(|i| format!("resource:db://tenant-{}/*", i))
+            .collect();
+        let rules = Rules::parse(&allow.join(","), "resource:db://tenant-42/secrets/*");
+        assert!(rules.allows(Kind::Resource, "db://tenant-4999/orders/1"));
+        assert!(rules.allows(Kind::Resource, "db://tenant-42/orders/1"));
+        assert!(!rules.allows(Kind::Resource, "db://tenant-42/secrets/key"));
+        assert!(!rules.allows(Kind::Resource, "db://tenant-5000/orders/1"));
+        assert!(!rules.allows(Kind::Resource, "db://tenant-/orders/1"));
+        assert!(rules.allows(Kind::Tool, "anything"));
+    }
+}
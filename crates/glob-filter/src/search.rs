@@ -0,0 +1,55 @@
+//! Filtering `resources/search` answers
+//!
+//! resources-middleware answers the experimental `resources/search`, which
+//! reaches the chain as a custom request with `{"resources": [...]}` as its
+//! result. Hidden resources are dropped from it as from `resources/list`;
+//! entries without a URI can't be checked, so they are dropped too.
+
+use crate::rules::{Kind, Rules};
+use serde_json::Value;
+
+/// Method name of the search request
+pub const METHOD: &str = "resources/search";
+
+/// Drop hidden resources from a search result
+///
+/// A result that isn't a resource list is returned unchanged.
+pub fn filter(rules: &Rules, result: String) -> String {
+    let Ok(Value::Object(mut object)) = serde_json::from_str::<Value>(&result) else {
+        return result;
+    };
+    let Some(Value::Array(resources)) = object.get_mut("resources") else {
+        return result;
+    };
+    resources.retain(|resource| {
+        resource["uri"]
+            .as_str()
+            .is_some_and(|uri| rules.allows(Kind::Resource, uri))
+    });
+    Value::Object(object).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_filter() {
+        let rules = Rules::parse("", "resource:file:///etc/*");
+        let result = json!({
+            "resources": [
+                { "uri": "file:///etc/passwd", "name": "passwd" },
+                { "uri": "file:///notes.md", "name": "notes" },
+                { "name": "no-uri" }
+            ]
+        });
+        let filtered: Value = serde_json::from_str(&filter(&rules, result.to_string())).unwrap();
+        assert_eq!(
+            filtered,
+            json!({ "resources": [{ "uri": "file:///notes.md", "name": "notes" }] })
+        );
+
+        assert_eq!(filter(&rules, "not json".to_string()), "not json");
+    }
+}
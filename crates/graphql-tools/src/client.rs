@@ -0,0 +1,166 @@
+//! Requests to the GraphQL endpoint
+//!
+//! The schema comes from `WASMCP_GRAPHQL_SCHEMA` when it was captured at
+//! build time. Otherwise the endpoint is introspected and the result cached
+//! in wasmcp:keyvalue under `graphql-tools:schema:{endpoint}` for
+//! `WASMCP_GRAPHQL_SCHEMA_TTL` seconds, so introspection runs once at
+//! startup rather than on every request.
+
+use crate::bindings::wasmcp::keyvalue::store as kv;
+use crate::config::GraphqlConfig;
+use crate::schema::{INTROSPECTION_QUERY, Schema};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::time::{SystemTime, UNIX_EPOCH};
+use wasmcp_http_client::Request;
+
+const SCHEMA_PREFIX: &str = "graphql-tools:schema:";
+
+/// Cached introspection result
+#[derive(Serialize, Deserialize)]
+struct CachedSchema {
+    stored_at: u64,
+    introspection: String,
+}
+
+/// The endpoint's schema, from configuration, the cache or introspection
+pub fn load_schema(config: &GraphqlConfig, deadline: Option<u64>) -> Result<Schema, String> {
+    if let Some(introspection) = &config.schema {
+        return Schema::parse(introspection);
+    }
+    let endpoint = endpoint(config)?;
+
+    let bucket = kv::open(&config.bucket)
+        .map_err(|e| {
+            eprintln!(
+                "[graphql-tools] Failed to open schema cache bucket '{}': {}. Introspecting uncached.",
+                config.bucket, e
+            )
+        })
+        .ok();
+    let key = format!("{}{}", SCHEMA_PREFIX, endpoint);
+    let now = now_secs();
+
+    if let Some(cached) = bucket.as_ref().and_then(|b| load_cached(b, &key))
+        && now < cached.stored_at.saturating_add(config.schema_ttl_secs)
+        && let Ok(schema) = Schema::parse(&cached.introspection)
+    {
+        return Ok(schema);
+    }
+
+    let response = execute(config, INTROSPECTION_QUERY, &json!({}), deadline)?;
+    if response.get("data").is_none_or(Value::is_null) {
+        return Err(format!(
+            "Introspection failed: {}",
+            error_messages(&response).unwrap_or_else(|| "no data".to_string())
+        ));
+    }
+    let introspection = response.to_string();
+    let schema = Schema::parse(&introspection)?;
+
+    if let Some(bucket) = &bucket
+        && config.schema_ttl_secs > 0
+    {
+        store_cached(
+            bucket,
+            &key,
+            &CachedSchema {
+                stored_at: now,
+                introspection,
+            },
+        );
+    }
+    Ok(schema)
+}
+
+/// POST an operation and return the GraphQL response (`data`, `errors`)
+///
+/// Transport failures and non-JSON responses are errors; GraphQL errors
+/// are part of the returned response.
+pub fn execute(
+    config: &GraphqlConfig,
+    document: &str,
+    variables: &Value,
+    deadline: Option<u64>,
+) -> Result<Value, String> {
+    let endpoint = endpoint(config)?;
+    let body = json!({ "query": document, "variables": variables }).to_string();
+
+    let request = Request::new("POST", endpoint)
+        .with_header("content-type", "application/json")
+        .with_header(
+            "accept",
+            "application/graphql-response+json, application/json",
+        )
+        .with_body(body);
+    let request = config
+        .headers
+        .iter()
+        .fold(request, |request, (name, value)| {
+            request.with_header(name.as_str(), value.as_str())
+        });
+
+    let response = crate::wasi_client("graphql-tools", deadline)
+        .with_max_response_bytes(config.max_bytes)
+        .send(request)
+        .map_err(|e| e.to_string())?;
+
+    // GraphQL over HTTP may answer 4xx with a GraphQL error response
+    let parsed: Option<Value> = serde_json::from_slice(&response.body).ok();
+    match parsed {
+        Some(value) if value.get("data").is_some() || value.get("errors").is_some() => Ok(value),
+        _ => Err(format!(
+            "Upstream {} returned HTTP {} without a GraphQL response",
+            endpoint, response.status
+        )),
+    }
+}
+
+/// The messages of a response's `errors`, joined
+pub fn error_messages(response: &Value) -> Option<String> {
+    let errors = response.get("errors")?.as_array()?;
+    if errors.is_empty() {
+        return None;
+    }
+    Some(
+        errors
+            .iter()
+            .map(|e| {
+                e.get("message")
+                    .and_then(Value::as_str)
+                    .map(str::to_string)
+                    .unwrap_or_else(|| e.to_string())
+            })
+            .collect::<Vec<_>>()
+            .join("; "),
+    )
+}
+
+fn endpoint(config: &GraphqlConfig) -> Result<&str, String> {
+    config
+        .endpoint
+        .as_deref()
+        .ok_or_else(|| "WASMCP_GRAPHQL_ENDPOINT is not set".to_string())
+}
+
+fn load_cached(bucket: &kv::Bucket, key: &str) -> Option<CachedSchema> {
+    match bucket.get(key) {
+        Ok(Some(kv::TypedValue::AsJson(json))) => serde_json::from_str(&json).ok(),
+        _ => None,
+    }
+}
+
+fn store_cached(bucket: &kv::Bucket, key: &str, cached: &CachedSchema) {
+    if let Ok(json) = serde_json::to_string(cached) {
+        if let Err(e) = bucket.set(key, &kv::TypedValue::AsJson(json)) {
+            eprintln!("[graphql-tools] Failed to cache schema: {}", e);
+        }
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
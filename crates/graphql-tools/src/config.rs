@@ -0,0 +1,230 @@
+//! Configuration for the GraphQL tool provider
+//!
+//! Environment variables:
+//! - `WASMCP_GRAPHQL_ENDPOINT`: GraphQL endpoint URL (required)
+//! - `WASMCP_GRAPHQL_OPERATIONS`: Root fields to expose as tools (required)
+//!   - JSON array of objects: `[{"field": "user", "name": "get_user", "selection": "{ id name }"}]`
+//!   - Or a comma-separated list of fields, mutations prefixed with `mutation.`
+//! - `WASMCP_GRAPHQL_HEADERS`: JSON object of headers sent with every request,
+//!   e.g. `{"Authorization": "Bearer ..."}`
+//! - `WASMCP_GRAPHQL_SCHEMA`: Introspection result captured at build time;
+//!   when unset the endpoint is introspected at startup
+//! - `WASMCP_GRAPHQL_BUCKET`: KV bucket caching the introspected schema (default: "")
+//! - `WASMCP_GRAPHQL_SCHEMA_TTL`: Seconds a cached schema is used (default: "300")
+//! - `WASMCP_GRAPHQL_MAX_DEPTH`: Deepest selection set and input object
+//!   nesting (default: "3")
+//! - `WASMCP_GRAPHQL_ALLOW_MUTATIONS`: "true"/"false" (default: "false") -
+//!   Expose mutation operations
+//! - `WASMCP_GRAPHQL_MAX_BYTES`: Largest upstream response (default: 10MB)
+
+use crate::bindings::wasi::cli::environment::get_environment;
+use crate::schema;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+const DEFAULT_SCHEMA_TTL_SECS: u64 = 300;
+const DEFAULT_MAX_DEPTH: usize = 3;
+const DEFAULT_MAX_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Root operation type of an exposed field
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum OperationKind {
+    #[default]
+    Query,
+    Mutation,
+}
+
+impl OperationKind {
+    pub fn keyword(self) -> &'static str {
+        match self {
+            OperationKind::Query => "query",
+            OperationKind::Mutation => "mutation",
+        }
+    }
+}
+
+/// A root query or mutation field exposed as a tool
+#[derive(Debug, Clone, Deserialize)]
+pub struct OperationDef {
+    /// Root field on the query or mutation type
+    pub field: String,
+    #[serde(default)]
+    pub operation: OperationKind,
+    /// Tool name (default: the field name)
+    pub name: Option<String>,
+    pub title: Option<String>,
+    /// Tool description (default: the field's schema description)
+    pub description: Option<String>,
+    /// Selection set for the result, e.g. `{ id name }` (default: generated
+    /// from the return type up to the depth limit)
+    pub selection: Option<String>,
+}
+
+impl OperationDef {
+    pub fn tool_name(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.field)
+    }
+}
+
+/// GraphQL provider configuration from environment variables
+#[derive(Debug, Clone)]
+pub struct GraphqlConfig {
+    pub endpoint: Option<String>,
+    pub operations: Vec<OperationDef>,
+    /// Tool name to position in `operations`
+    index: HashMap<String, usize>,
+    pub headers: Vec<(String, String)>,
+    pub schema: Option<String>,
+    pub bucket: String,
+    pub schema_ttl_secs: u64,
+    pub max_depth: usize,
+    pub max_bytes: u64,
+}
+
+impl GraphqlConfig {
+    /// Load configuration from environment variables
+    ///
+    /// Operations that fail validation are dropped with a warning.
+    pub fn from_env() -> Self {
+        let env_map: HashMap<String, String> = get_environment().into_iter().collect();
+
+        let allow_mutations = env_map
+            .get("WASMCP_GRAPHQL_ALLOW_MUTATIONS")
+            .map(|v| v.to_lowercase() == "true")
+            .unwrap_or(false);
+
+        let max_depth = env_map
+            .get("WASMCP_GRAPHQL_MAX_DEPTH")
+            .and_then(|v| v.parse::<usize>().ok())
+            .filter(|n| *n > 0)
+            .unwrap_or(DEFAULT_MAX_DEPTH);
+
+        let operations: Vec<OperationDef> = env_map
+            .get("WASMCP_GRAPHQL_OPERATIONS")
+            .map(|v| parse_operations(v))
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|op| match validate(op, allow_mutations, max_depth) {
+                Ok(()) => true,
+                Err(e) => {
+                    eprintln!(
+                        "[graphql-tools] WARNING: Skipping operation '{}': {}",
+                        op.tool_name(),
+                        e
+                    );
+                    false
+                }
+            })
+            .collect();
+
+        let headers = env_map
+            .get("WASMCP_GRAPHQL_HEADERS")
+            .map(|v| {
+                serde_json::from_str::<HashMap<String, String>>(v).unwrap_or_else(|e| {
+                    eprintln!(
+                        "[graphql-tools] WARNING: Invalid WASMCP_GRAPHQL_HEADERS JSON: {}",
+                        e
+                    );
+                    HashMap::new()
+                })
+            })
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+
+        let schema_ttl_secs = env_map
+            .get("WASMCP_GRAPHQL_SCHEMA_TTL")
+            .and_then(|v| v.parse::<u64>().ok())
+            .unwrap_or(DEFAULT_SCHEMA_TTL_SECS);
+
+        let max_bytes = env_map
+            .get("WASMCP_GRAPHQL_MAX_BYTES")
+            .and_then(|v| v.parse::<u64>().ok())
+            .filter(|n| *n > 0)
+            .unwrap_or(DEFAULT_MAX_BYTES);
+
+        let mut index = HashMap::with_capacity(operations.len());
+        for (i, op) in operations.iter().enumerate() {
+            index.entry(op.tool_name().to_string()).or_insert(i);
+        }
+
+        GraphqlConfig {
+            endpoint: env_map
+                .get("WASMCP_GRAPHQL_ENDPOINT")
+                .filter(|v| !v.is_empty())
+                .cloned(),
+            operations,
+            index,
+            headers,
+            schema: env_map
+                .get("WASMCP_GRAPHQL_SCHEMA")
+                .filter(|v| !v.trim().is_empty())
+                .cloned(),
+            bucket: env_map
+                .get("WASMCP_GRAPHQL_BUCKET")
+                .cloned()
+                .unwrap_or_default(),
+            schema_ttl_secs,
+            max_depth,
+            max_bytes,
+        }
+    }
+
+    pub fn operation(&self, tool: &str) -> Option<&OperationDef> {
+        self.index.get(tool).map(|&i| &self.operations[i])
+    }
+}
+
+fn parse_operations(value: &str) -> Vec<OperationDef> {
+    let trimmed = value.trim();
+    if trimmed.starts_with('[') {
+        return serde_json::from_str(trimmed).unwrap_or_else(|e| {
+            eprintln!(
+                "[graphql-tools] WARNING: Invalid WASMCP_GRAPHQL_OPERATIONS JSON: {}. No operations exposed.",
+                e
+            );
+            Vec::new()
+        });
+    }
+
+    trimmed
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let (operation, field) = match entry.split_once('.') {
+                Some(("mutation", field)) => (OperationKind::Mutation, field),
+                Some(("query", field)) => (OperationKind::Query, field),
+                _ => (OperationKind::Query, entry),
+            };
+            OperationDef {
+                field: field.to_string(),
+                operation,
+                name: None,
+                title: None,
+                description: None,
+                selection: None,
+            }
+        })
+        .collect()
+}
+
+fn validate(op: &OperationDef, allow_mutations: bool, max_depth: usize) -> Result<(), String> {
+    if op.field.is_empty() {
+        return Err("field is empty".to_string());
+    }
+    if op.operation == OperationKind::Mutation && !allow_mutations {
+        return Err("mutations require WASMCP_GRAPHQL_ALLOW_MUTATIONS=true".to_string());
+    }
+    if let Some(selection) = &op.selection {
+        let depth = schema::selection_depth(selection)?;
+        if depth > max_depth {
+            return Err(format!(
+                "selection is {} levels deep, above WASMCP_GRAPHQL_MAX_DEPTH ({})",
+                depth, max_depth
+            ));
+        }
+    }
+    Ok(())
+}
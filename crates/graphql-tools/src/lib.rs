@@ -0,0 +1,258 @@
+//! GraphQL Tools Middleware Component
+//!
+//! A gateway that exposes selected fields of a GraphQL API as MCP tools with
+//! no custom handler code. This component:
+//! - Introspects the endpoint at startup (or uses a schema captured at build
+//!   time) and lists one tool per query or mutation field declared in
+//!   `WASMCP_GRAPHQL_OPERATIONS`
+//! - Derives each tool's input schema from the field's argument types
+//! - Validates tool arguments against those types before calling upstream
+//! - Sends arguments as GraphQL variables, with a selection set bounded by
+//!   `WASMCP_GRAPHQL_MAX_DEPTH`
+//! - Delegates all other requests downstream
+//!
+//! Only declared fields can be called, and mutations stay hidden unless
+//! `WASMCP_GRAPHQL_ALLOW_MUTATIONS=true`.
+
+#![allow(warnings)]
+
+mod bindings {
+    wit_bindgen::generate!({
+        world: "graphql-tools",
+        generate_all,
+    });
+}
+
+mod client;
+mod config;
+mod schema;
+mod variables;
+
+use bindings::exports::wasmcp::mcp_v20251125::server_handler::{Guest, MessageContext};
+use bindings::wasmcp::mcp_v20251125::mcp::*;
+use bindings::wasmcp::mcp_v20251125::server_handler as downstream;
+use config::{GraphqlConfig, OperationDef, OperationKind};
+use schema::Schema;
+use std::sync::OnceLock;
+use wasmcp_errors::{ErrorKind, McpError};
+
+wasmcp_http_client::impl_wasi_transport!(crate::bindings::wasi);
+wasmcp_errors::impl_error_code!(ErrorCode, Error);
+
+struct GraphqlTools;
+
+/// Configuration, read once per instance
+static CONFIG: OnceLock<GraphqlConfig> = OnceLock::new();
+
+// Convert exported MessageContext to imported MessageContext
+fn to_downstream_ctx<'a>(ctx: &'a MessageContext<'a>) -> downstream::MessageContext<'a> {
+    downstream::MessageContext {
+        client_stream: ctx.client_stream,
+        protocol_version: ctx.protocol_version.clone(),
+        session: ctx.session.as_ref().map(|s| downstream::Session {
+            session_id: s.session_id.clone(),
+            store_id: s.store_id.clone(),
+        }),
+        identity: ctx.identity.as_ref().map(|i| downstream::Identity {
+            jwt: i.jwt.clone(),
+            claims: i.claims.clone(),
+        }),
+        frame: ctx.frame.clone(),
+        http_context: ctx.http_context.clone(),
+        client_capabilities: ctx.client_capabilities.clone(),
+        request_id: ctx.request_id.clone(),
+        deadline: ctx.deadline,
+        log_level: ctx.log_level,
+        client_info: ctx.client_info.clone(),
+        locale: ctx.locale.clone(),
+        trace_context: ctx.trace_context.clone(),
+    }
+}
+
+impl Guest for GraphqlTools {
+    fn handle(
+        ctx: MessageContext,
+        message: ClientMessage,
+    ) -> Option<Result<ServerResult, ErrorCode>> {
+        let config = CONFIG.get_or_init(GraphqlConfig::from_env);
+
+        match message {
+            ClientMessage::Request((request_id, request)) => {
+                let result = match &request {
+                    // Our tools are on the first page
+                    ClientRequest::ToolsList(list_req)
+                        if list_req.cursor.is_none() && !config.operations.is_empty() =>
+                    {
+                        handle_tools_list(request_id.clone(), &ctx, config)
+                    }
+                    ClientRequest::ToolsCall(call_req) => match config.operation(&call_req.name) {
+                        Some(op) => Ok(handle_tools_call(call_req, op, &ctx, config)),
+                        None => return delegate(&ctx, request_id, request),
+                    },
+                    _ => return delegate(&ctx, request_id, request),
+                };
+                Some(result)
+            }
+            _ => {
+                // Forward notifications, results, errors to downstream
+                downstream::handle(&to_downstream_ctx(&ctx), message)
+            }
+        }
+    }
+}
+
+fn delegate(
+    ctx: &MessageContext,
+    request_id: RequestId,
+    request: ClientRequest,
+) -> Option<Result<ServerResult, ErrorCode>> {
+    let downstream_msg = ClientMessage::Request((request_id, request));
+    downstream::handle(&to_downstream_ctx(ctx), downstream_msg)
+}
+
+/// List our tools ahead of downstream's first page
+///
+/// An unreachable endpoint hides our tools rather than failing the list.
+fn handle_tools_list(
+    request_id: RequestId,
+    ctx: &MessageContext,
+    config: &GraphqlConfig,
+) -> Result<ServerResult, ErrorCode> {
+    let mut tools = match client::load_schema(config, ctx.deadline) {
+        Ok(schema) => config
+            .operations
+            .iter()
+            .filter_map(|op| to_tool(&schema, op, config))
+            .collect(),
+        Err(e) => {
+            eprintln!("[graphql-tools] Failed to load schema: {}", e);
+            Vec::new()
+        }
+    };
+
+    let downstream_req = ClientRequest::ToolsList(ListToolsRequest {
+        cursor: None,
+        meta: None,
+    });
+    let downstream_msg = ClientMessage::Request((request_id, downstream_req));
+    let next_cursor = match downstream::handle(&to_downstream_ctx(ctx), downstream_msg) {
+        Some(Ok(ServerResult::ToolsList(downstream_result))) => {
+            tools.extend(downstream_result.tools);
+            downstream_result.next_cursor
+        }
+        // A downstream without tools/list still lets ours through
+        Some(Err(e)) if McpError::from(&e).kind == ErrorKind::MethodNotFound => None,
+        None | Some(Ok(_)) => None,
+        Some(Err(e)) => return Err(e),
+    };
+
+    Ok(ServerResult::ToolsList(ListToolsResult {
+        tools,
+        next_cursor,
+        meta: None,
+    }))
+}
+
+fn to_tool(schema: &Schema, op: &OperationDef, config: &GraphqlConfig) -> Option<Tool> {
+    let mutation = op.operation == OperationKind::Mutation;
+    let Some(field) = schema.root_field(mutation, &op.field) else {
+        eprintln!(
+            "[graphql-tools] WARNING: No {} field '{}' in the schema",
+            op.operation.keyword(),
+            op.field
+        );
+        return None;
+    };
+
+    Some(Tool {
+        name: op.tool_name().to_string(),
+        input_schema: schema.input_schema(field, config.max_depth).to_string(),
+        options: Some(ToolOptions {
+            meta: None,
+            annotations: Some(ToolAnnotations {
+                destructive_hint: Some(mutation),
+                idempotent_hint: Some(!mutation),
+                open_world_hint: Some(true),
+                read_only_hint: Some(!mutation),
+                title: None,
+            }),
+            description: op.description.clone().or_else(|| field.description.clone()),
+            output_schema: None,
+            title: op.title.clone(),
+            icons: None,
+        }),
+    })
+}
+
+fn handle_tools_call(
+    req: &CallToolRequest,
+    op: &OperationDef,
+    ctx: &MessageContext,
+    config: &GraphqlConfig,
+) -> ServerResult {
+    match call(req, op, ctx, config) {
+        Ok((text, structured)) => tool_result(text, Some(structured), false),
+        Err(e) => tool_result(e, None, true),
+    }
+}
+
+fn call(
+    req: &CallToolRequest,
+    op: &OperationDef,
+    ctx: &MessageContext,
+    config: &GraphqlConfig,
+) -> Result<(String, serde_json::Value), String> {
+    let args = match req.arguments.as_deref().map(serde_json::from_str) {
+        Some(Ok(serde_json::Value::Object(args))) => args,
+        Some(Ok(serde_json::Value::Null)) | None => serde_json::Map::new(),
+        Some(Ok(_)) => return Err("Arguments must be a JSON object".to_string()),
+        Some(Err(e)) => return Err(format!("Invalid arguments: {}", e)),
+    };
+
+    let schema = client::load_schema(config, ctx.deadline)?;
+    let field = schema
+        .root_field(op.operation == OperationKind::Mutation, &op.field)
+        .ok_or_else(|| format!("No {} field '{}'", op.operation.keyword(), op.field))?;
+    variables::validate(&schema, field, &args, config.max_depth)?;
+
+    let selection = op
+        .selection
+        .clone()
+        .or_else(|| schema.selection(&field.type_ref, config.max_depth));
+    let document = schema::document(op.operation.keyword(), field, selection.as_deref());
+
+    let response = client::execute(
+        config,
+        &document,
+        &serde_json::Value::Object(args),
+        ctx.deadline,
+    )?;
+    if let Some(errors) = client::error_messages(&response) {
+        return Err(format!("GraphQL error: {}", errors));
+    }
+
+    let data = response
+        .get("data")
+        .cloned()
+        .unwrap_or(serde_json::Value::Null);
+    let text = serde_json::to_string_pretty(&data[op.field.as_str()]).unwrap_or_default();
+    Ok((text, data))
+}
+
+fn tool_result(
+    text: String,
+    structured_content: Option<serde_json::Value>,
+    is_error: bool,
+) -> ServerResult {
+    ServerResult::ToolsCall(CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text: TextData::Text(text),
+            options: None,
+        })],
+        is_error: is_error.then_some(true),
+        meta: None,
+        structured_content: structured_content.map(|v| v.to_string()),
+    })
+}
+
+bindings::export!(GraphqlTools with_types_in bindings);
@@ -0,0 +1,457 @@
+//! GraphQL schema from introspection, and what tools derive from it
+//!
+//! From a root field this module builds:
+//! - The tool's input schema, mapping arguments to JSON Schema
+//! - A selection set for the return type, bounded by the depth limit
+//! - The operation document sent upstream, with one variable per argument
+
+use serde::Deserialize;
+use serde_json::{Map, Value, json};
+
+/// Standard introspection query, with type references nested 7 deep
+pub const INTROSPECTION_QUERY: &str = r#"query IntrospectionQuery {
+  __schema {
+    queryType { name }
+    mutationType { name }
+    types {
+      kind name description
+      fields(includeDeprecated: false) { name description args { ...InputValue } type { ...TypeRef } }
+      inputFields { ...InputValue }
+      enumValues(includeDeprecated: false) { name }
+    }
+  }
+}
+fragment InputValue on __InputValue { name description type { ...TypeRef } defaultValue }
+fragment TypeRef on __Type {
+  kind name
+  ofType { kind name ofType { kind name ofType { kind name ofType { kind name
+    ofType { kind name ofType { kind name ofType { kind name } } } } } } }
+}"#;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Schema {
+    pub query_type: Option<NamedRef>,
+    pub mutation_type: Option<NamedRef>,
+    pub types: Vec<FullType>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NamedRef {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FullType {
+    pub kind: String,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub fields: Option<Vec<Field>>,
+    pub input_fields: Option<Vec<InputValue>>,
+    pub enum_values: Option<Vec<EnumValue>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Field {
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub args: Vec<InputValue>,
+    #[serde(rename = "type")]
+    pub type_ref: TypeRef,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InputValue {
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(rename = "type")]
+    pub type_ref: TypeRef,
+    pub default_value: Option<String>,
+}
+
+impl InputValue {
+    /// Non-null without a default, so callers must supply it
+    pub fn is_required(&self) -> bool {
+        self.type_ref.kind == "NON_NULL" && self.default_value.is_none()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct EnumValue {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypeRef {
+    pub kind: String,
+    pub name: Option<String>,
+    pub of_type: Option<Box<TypeRef>>,
+}
+
+impl TypeRef {
+    /// The named type under any NON_NULL and LIST wrappers
+    pub fn named(&self) -> &str {
+        match (&self.name, &self.of_type) {
+            (Some(name), _) => name,
+            (None, Some(inner)) => inner.named(),
+            (None, None) => "",
+        }
+    }
+
+    /// GraphQL type syntax for variable declarations, e.g. `[ID!]!`
+    pub fn to_graphql(&self) -> String {
+        let inner = || {
+            self.of_type
+                .as_ref()
+                .map(|t| t.to_graphql())
+                .unwrap_or_default()
+        };
+        match self.kind.as_str() {
+            "NON_NULL" => format!("{}!", inner()),
+            "LIST" => format!("[{}]", inner()),
+            _ => self.name.clone().unwrap_or_default(),
+        }
+    }
+}
+
+impl Schema {
+    /// Parse an introspection response (`{"data": {"__schema": ...}}`, or
+    /// the `data` object alone)
+    pub fn parse(introspection: &str) -> Result<Schema, String> {
+        let value: Value = serde_json::from_str(introspection)
+            .map_err(|e| format!("Invalid introspection JSON: {}", e))?;
+        let schema = value
+            .get("data")
+            .unwrap_or(&value)
+            .get("__schema")
+            .ok_or_else(|| "Introspection result has no __schema".to_string())?;
+        serde_json::from_value(schema.clone())
+            .map_err(|e| format!("Invalid introspection schema: {}", e))
+    }
+
+    pub fn get_type(&self, name: &str) -> Option<&FullType> {
+        self.types.iter().find(|t| t.name.as_deref() == Some(name))
+    }
+
+    /// A field of the query or mutation root type
+    pub fn root_field(&self, mutation: bool, field: &str) -> Option<&Field> {
+        let root = if mutation {
+            self.mutation_type.as_ref()
+        } else {
+            self.query_type.as_ref()
+        }?;
+        self.get_type(&root.name)?
+            .fields
+            .as_ref()?
+            .iter()
+            .find(|f| f.name == field)
+    }
+
+    /// JSON Schema for a field's arguments
+    pub fn input_schema(&self, field: &Field, max_depth: usize) -> Value {
+        let properties: Map<String, Value> = field
+            .args
+            .iter()
+            .map(|arg| {
+                let mut schema = self.type_schema(&arg.type_ref, max_depth);
+                if let Some(description) = &arg.description {
+                    schema["description"] = json!(description);
+                }
+                (arg.name.clone(), schema)
+            })
+            .collect();
+        let required: Vec<&str> = field
+            .args
+            .iter()
+            .filter(|a| a.is_required())
+            .map(|a| a.name.as_str())
+            .collect();
+
+        json!({
+            "type": "object",
+            "properties": properties,
+            "required": required,
+            "additionalProperties": false,
+        })
+    }
+
+    fn type_schema(&self, type_ref: &TypeRef, depth: usize) -> Value {
+        match type_ref.kind.as_str() {
+            "NON_NULL" => match &type_ref.of_type {
+                Some(inner) => self.type_schema(inner, depth),
+                None => json!({}),
+            },
+            "LIST" => match &type_ref.of_type {
+                Some(inner) => json!({ "type": "array", "items": self.type_schema(inner, depth) }),
+                None => json!({ "type": "array" }),
+            },
+            _ => self.named_schema(type_ref.named(), depth),
+        }
+    }
+
+    fn named_schema(&self, name: &str, depth: usize) -> Value {
+        match name {
+            "Int" => return json!({ "type": "integer" }),
+            "Float" => return json!({ "type": "number" }),
+            "String" => return json!({ "type": "string" }),
+            "Boolean" => return json!({ "type": "boolean" }),
+            "ID" => return json!({ "type": ["string", "integer"] }),
+            _ => {}
+        }
+
+        let Some(full) = self.get_type(name) else {
+            return json!({});
+        };
+        match full.kind.as_str() {
+            "ENUM" => {
+                let values: Vec<&str> = full
+                    .enum_values
+                    .iter()
+                    .flatten()
+                    .map(|v| v.name.as_str())
+                    .collect();
+                json!({ "type": "string", "enum": values })
+            }
+            // Nested past the depth limit, which argument validation rejects
+            "INPUT_OBJECT" if depth == 0 => json!({ "type": "object" }),
+            "INPUT_OBJECT" => {
+                let fields = full.input_fields.as_deref().unwrap_or_default();
+                let properties: Map<String, Value> = fields
+                    .iter()
+                    .map(|f| (f.name.clone(), self.type_schema(&f.type_ref, depth - 1)))
+                    .collect();
+                let required: Vec<&str> = fields
+                    .iter()
+                    .filter(|f| f.is_required())
+                    .map(|f| f.name.as_str())
+                    .collect();
+                json!({
+                    "type": "object",
+                    "properties": properties,
+                    "required": required,
+                    "additionalProperties": false,
+                })
+            }
+            // Custom scalars: any JSON value
+            _ => json!({}),
+        }
+    }
+
+    /// Selection set for a return type, or None for scalars and enums
+    ///
+    /// Includes every field that takes no required arguments, descending at
+    /// most `max_depth` levels. Object fields past the limit are left out.
+    pub fn selection(&self, type_ref: &TypeRef, max_depth: usize) -> Option<String> {
+        let full = self.get_type(type_ref.named())?;
+        match full.kind.as_str() {
+            "OBJECT" | "INTERFACE" => {
+                let mut parts = Vec::new();
+                for field in full.fields.iter().flatten() {
+                    if field.args.iter().any(InputValue::is_required) {
+                        continue;
+                    }
+                    if self.is_leaf(&field.type_ref) {
+                        parts.push(field.name.clone());
+                    } else if max_depth > 1
+                        && let Some(nested) = self.selection(&field.type_ref, max_depth - 1)
+                    {
+                        parts.push(format!("{} {}", field.name, nested));
+                    }
+                }
+                if parts.is_empty() {
+                    parts.push("__typename".to_string());
+                }
+                Some(format!("{{ {} }}", parts.join(" ")))
+            }
+            "UNION" => Some("{ __typename }".to_string()),
+            _ => None,
+        }
+    }
+
+    fn is_leaf(&self, type_ref: &TypeRef) -> bool {
+        self.get_type(type_ref.named())
+            .is_none_or(|t| matches!(t.kind.as_str(), "SCALAR" | "ENUM"))
+    }
+}
+
+/// The operation document for one root field
+///
+/// Every argument becomes a variable of the same name, so tool arguments are
+/// sent as variables and never spliced into the document.
+pub fn document(keyword: &str, field: &Field, selection: Option<&str>) -> String {
+    let variables: Vec<String> = field
+        .args
+        .iter()
+        .map(|a| format!("${}: {}", a.name, a.type_ref.to_graphql()))
+        .collect();
+    let arguments: Vec<String> = field
+        .args
+        .iter()
+        .map(|a| format!("{}: ${}", a.name, a.name))
+        .collect();
+
+    let mut doc = format!("{} {}", keyword, field.name);
+    if !variables.is_empty() {
+        doc.push_str(&format!("({})", variables.join(", ")));
+    }
+    doc.push_str(&format!(" {{ {}", field.name));
+    if !arguments.is_empty() {
+        doc.push_str(&format!("({})", arguments.join(", ")));
+    }
+    if let Some(selection) = selection {
+        doc.push(' ');
+        doc.push_str(selection);
+    }
+    doc.push_str(" }");
+    doc
+}
+
+/// Nesting depth of a selection set such as `{ id author { name } }`
+pub fn selection_depth(selection: &str) -> Result<usize, String> {
+    let mut depth = 0usize;
+    let mut max = 0usize;
+    let mut in_string = false;
+    for c in selection.chars() {
+        match c {
+            '"' => in_string = !in_string,
+            '{' if !in_string => {
+                depth += 1;
+                max = max.max(depth);
+            }
+            '}' if !in_string => {
+                depth = depth
+                    .checked_sub(1)
+                    .ok_or_else(|| "selection has an unmatched '}'".to_string())?;
+            }
+            _ => {}
+        }
+    }
+    if depth != 0 || in_string {
+        return Err("selection is not closed".to_string());
+    }
+    if max == 0 {
+        return Err("selection must be wrapped in braces".to_string());
+    }
+    Ok(max)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// A small blog schema in introspection form
+    pub(crate) fn blog_schema() -> Schema {
+        let named = |kind: &str, name: &str| json!({ "kind": kind, "name": name });
+        let non_null = |inner: Value| json!({ "kind": "NON_NULL", "name": null, "ofType": inner });
+        let list = |inner: Value| json!({ "kind": "LIST", "name": null, "ofType": inner });
+        let arg = |name: &str, type_ref: Value| json!({ "name": name, "description": null, "type": type_ref, "defaultValue": null });
+        let field = |name: &str, args: Vec<Value>, type_ref: Value| json!({ "name": name, "description": null, "args": args, "type": type_ref });
+
+        let introspection = json!({ "data": { "__schema": {
+            "queryType": { "name": "Query" },
+            "mutationType": { "name": "Mutation" },
+            "types": [
+                { "kind": "OBJECT", "name": "Query", "fields": [
+                    field("post", vec![arg("id", non_null(named("SCALAR", "ID")))], named("OBJECT", "Post")),
+                    field("posts", vec![
+                        arg("first", named("SCALAR", "Int")),
+                        arg("status", named("ENUM", "Status")),
+                    ], non_null(list(non_null(named("OBJECT", "Post"))))),
+                ]},
+                { "kind": "OBJECT", "name": "Mutation", "fields": [
+                    field("createPost", vec![arg("input", non_null(named("INPUT_OBJECT", "PostInput")))], named("OBJECT", "Post")),
+                ]},
+                { "kind": "OBJECT", "name": "Post", "fields": [
+                    field("id", vec![], non_null(named("SCALAR", "ID"))),
+                    field("title", vec![], named("SCALAR", "String")),
+                    field("status", vec![], named("ENUM", "Status")),
+                    field("author", vec![], named("OBJECT", "User")),
+                    field("related", vec![arg("limit", non_null(named("SCALAR", "Int")))], list(named("OBJECT", "Post"))),
+                ]},
+                { "kind": "OBJECT", "name": "User", "fields": [
+                    field("name", vec![], named("SCALAR", "String")),
+                    field("posts", vec![], list(named("OBJECT", "Post"))),
+                ]},
+                { "kind": "ENUM", "name": "Status", "enumValues": [{ "name": "DRAFT" }, { "name": "PUBLISHED" }] },
+                { "kind": "INPUT_OBJECT", "name": "PostInput", "inputFields": [
+                    arg("title", non_null(named("SCALAR", "String"))),
+                    arg("tags", list(non_null(named("SCALAR", "String")))),
+                    arg("status", named("ENUM", "Status")),
+                ]},
+                { "kind": "SCALAR", "name": "ID" },
+                { "kind": "SCALAR", "name": "Int" },
+                { "kind": "SCALAR", "name": "String" },
+            ],
+        }}});
+        Schema::parse(&introspection.to_string()).unwrap()
+    }
+
+    #[test]
+    fn test_input_schema() {
+        let schema = blog_schema();
+        let create = schema.root_field(true, "createPost").unwrap();
+        let input = schema.input_schema(create, 3);
+
+        assert_eq!(input["required"], json!(["input"]));
+        let post_input = &input["properties"]["input"];
+        assert_eq!(post_input["required"], json!(["title"]));
+        assert_eq!(
+            post_input["properties"]["tags"],
+            json!({ "type": "array", "items": { "type": "string" } })
+        );
+        assert_eq!(
+            post_input["properties"]["status"]["enum"],
+            json!(["DRAFT", "PUBLISHED"])
+        );
+
+        let shallow = schema.input_schema(create, 0);
+        assert_eq!(shallow["properties"]["input"], json!({ "type": "object" }));
+    }
+
+    #[test]
+    fn test_selection() {
+        let schema = blog_schema();
+        let post = schema.root_field(false, "post").unwrap();
+
+        assert_eq!(
+            schema.selection(&post.type_ref, 1).unwrap(),
+            "{ id title status }"
+        );
+        assert_eq!(
+            schema.selection(&post.type_ref, 2).unwrap(),
+            "{ id title status author { name } }"
+        );
+        let deep = schema.selection(&post.type_ref, 3).unwrap();
+        assert_eq!(selection_depth(&deep).unwrap(), 3);
+        assert!(!deep.contains("related"));
+    }
+
+    #[test]
+    fn test_document() {
+        let schema = blog_schema();
+        let post = schema.root_field(false, "post").unwrap();
+        assert_eq!(
+            document("query", post, Some("{ id }")),
+            "query post($id: ID!) { post(id: $id) { id } }"
+        );
+
+        let posts = schema.root_field(false, "posts").unwrap();
+        assert_eq!(posts.type_ref.to_graphql(), "[Post!]!");
+    }
+
+    #[test]
+    fn test_selection_depth() {
+        assert_eq!(selection_depth("{ id }").unwrap(), 1);
+        assert_eq!(
+            selection_depth("{ id author { name posts { id } } }").unwrap(),
+            3
+        );
+        assert_eq!(selection_depth(r#"{ a(x: "{{") { b } }"#).unwrap(), 2);
+        assert!(selection_depth("{ id").is_err());
+        assert!(selection_depth("id").is_err());
+    }
+}
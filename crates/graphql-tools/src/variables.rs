@@ -0,0 +1,216 @@
+//! Validation of tool arguments as GraphQL variables
+//!
+//! Arguments are checked against the field's argument types before anything
+//! is sent upstream, so clients get a precise error instead of a remote one
+//! and input objects cannot nest deeper than the configured limit.
+
+use crate::schema::{Field, InputValue, Schema, TypeRef};
+use serde_json::{Map, Value};
+
+/// Check tool arguments against a field's arguments
+///
+/// Rejects unknown arguments, missing required ones and values of the wrong
+/// type, naming the offending path (e.g. `input.tags[2]`).
+pub fn validate(
+    schema: &Schema,
+    field: &Field,
+    arguments: &Map<String, Value>,
+    max_depth: usize,
+) -> Result<(), String> {
+    check_object(schema, &field.args, arguments, "", max_depth)
+}
+
+fn check_object(
+    schema: &Schema,
+    fields: &[InputValue],
+    values: &Map<String, Value>,
+    path: &str,
+    depth: usize,
+) -> Result<(), String> {
+    let join = |name: &str| {
+        if path.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}.{}", path, name)
+        }
+    };
+
+    if let Some(unknown) = values
+        .keys()
+        .find(|k| !fields.iter().any(|f| &f.name == *k))
+    {
+        return Err(format!("Unknown argument '{}'", join(unknown)));
+    }
+    for field in fields {
+        match values.get(&field.name) {
+            Some(value) => check_value(schema, &field.type_ref, value, &join(&field.name), depth)?,
+            None if field.is_required() => {
+                return Err(format!("Missing required argument '{}'", join(&field.name)));
+            }
+            None => {}
+        }
+    }
+    Ok(())
+}
+
+fn check_value(
+    schema: &Schema,
+    type_ref: &TypeRef,
+    value: &Value,
+    path: &str,
+    depth: usize,
+) -> Result<(), String> {
+    let inner = type_ref.of_type.as_deref();
+    match (type_ref.kind.as_str(), inner) {
+        ("NON_NULL", _) if value.is_null() => Err(format!("'{}' must not be null", path)),
+        ("NON_NULL", Some(inner)) => check_value(schema, inner, value, path, depth),
+        (_, _) if value.is_null() => Ok(()),
+        ("LIST", Some(inner)) => match value {
+            Value::Array(items) => items.iter().enumerate().try_for_each(|(i, item)| {
+                check_value(schema, inner, item, &format!("{}[{}]", path, i), depth)
+            }),
+            // GraphQL coerces a single value into a one-item list
+            single => check_value(schema, inner, single, path, depth),
+        },
+        _ => check_named(schema, type_ref.named(), value, path, depth),
+    }
+}
+
+fn check_named(
+    schema: &Schema,
+    name: &str,
+    value: &Value,
+    path: &str,
+    depth: usize,
+) -> Result<(), String> {
+    let expected = match name {
+        "Int" => value
+            .as_i64()
+            .is_some_and(|n| i32::try_from(n).is_ok())
+            .then_some(())
+            .ok_or("a 32-bit integer"),
+        "Float" => value.is_number().then_some(()).ok_or("a number"),
+        "String" => value.is_string().then_some(()).ok_or("a string"),
+        "Boolean" => value.is_boolean().then_some(()).ok_or("a boolean"),
+        "ID" => (value.is_string() || value.is_i64() || value.is_u64())
+            .then_some(())
+            .ok_or("a string or integer ID"),
+        _ => Ok(()),
+    };
+    if let Err(expected) = expected {
+        return Err(format!("'{}' must be {}", path, expected));
+    }
+
+    let Some(full) = schema.get_type(name) else {
+        return Ok(());
+    };
+    match full.kind.as_str() {
+        "ENUM" => {
+            let allowed = full.enum_values.iter().flatten().map(|v| v.name.as_str());
+            match value.as_str() {
+                Some(s) if allowed.clone().any(|a| a == s) => Ok(()),
+                _ => Err(format!(
+                    "'{}' must be one of: {}",
+                    path,
+                    allowed.collect::<Vec<_>>().join(", ")
+                )),
+            }
+        }
+        "INPUT_OBJECT" => {
+            if depth == 0 {
+                return Err(format!(
+                    "'{}' nests input objects deeper than the depth limit",
+                    path
+                ));
+            }
+            let Value::Object(values) = value else {
+                return Err(format!("'{}' must be an object", path));
+            };
+            let fields = full.input_fields.as_deref().unwrap_or_default();
+            check_object(schema, fields, values, path, depth - 1)
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::tests::blog_schema;
+    use serde_json::json;
+
+    fn check(field: &str, mutation: bool, arguments: Value, depth: usize) -> Result<(), String> {
+        let schema = blog_schema();
+        let field = schema.root_field(mutation, field).unwrap();
+        let Value::Object(arguments) = arguments else {
+            panic!("arguments must be an object");
+        };
+        validate(&schema, field, &arguments, depth)
+    }
+
+    #[test]
+    fn test_scalars_and_enums() {
+        assert!(check("post", false, json!({ "id": "p1" }), 3).is_ok());
+        assert!(check("post", false, json!({ "id": 7 }), 3).is_ok());
+        assert_eq!(
+            check("post", false, json!({}), 3).unwrap_err(),
+            "Missing required argument 'id'"
+        );
+        assert_eq!(
+            check("post", false, json!({ "id": null }), 3).unwrap_err(),
+            "'id' must not be null"
+        );
+        assert_eq!(
+            check("post", false, json!({ "id": "p1", "x": 1 }), 3).unwrap_err(),
+            "Unknown argument 'x'"
+        );
+        assert_eq!(
+            check("posts", false, json!({ "first": 5_000_000_000u64 }), 3).unwrap_err(),
+            "'first' must be a 32-bit integer"
+        );
+        assert_eq!(
+            check("posts", false, json!({ "status": "GONE" }), 3).unwrap_err(),
+            "'status' must be one of: DRAFT, PUBLISHED"
+        );
+    }
+
+    #[test]
+    fn test_input_objects() {
+        let input = json!({ "input": { "title": "Hi", "tags": ["a", "b"], "status": "DRAFT" } });
+        assert!(check("createPost", true, input.clone(), 3).is_ok());
+        assert_eq!(
+            check("createPost", true, input, 0).unwrap_err(),
+            "'input' nests input objects deeper than the depth limit"
+        );
+        assert_eq!(
+            check(
+                "createPost",
+                true,
+                json!({ "input": { "tags": ["a", 1] } }),
+                3
+            )
+            .unwrap_err(),
+            "Missing required argument 'input.title'"
+        );
+        assert_eq!(
+            check(
+                "createPost",
+                true,
+                json!({ "input": { "title": "Hi", "tags": ["a", 1] } }),
+                3
+            )
+            .unwrap_err(),
+            "'input.tags[1]' must be a string"
+        );
+        // A single value is coerced into a list
+        assert!(
+            check(
+                "createPost",
+                true,
+                json!({ "input": { "title": "Hi", "tags": "a" } }),
+                3
+            )
+            .is_ok()
+        );
+    }
+}
@@ -0,0 +1,60 @@
+//! Configuration for HMAC request verification
+//!
+//! Environment variables:
+//! - `WASMCP_HMAC_SECRET`: Shared secret clients sign with (required)
+//! - `WASMCP_HMAC_MAX_SKEW_SECS`: Largest accepted clock difference (default: 300)
+//! - `WASMCP_HMAC_BUCKET`: KV bucket for seen nonces (default: "")
+//! - `WASMCP_HMAC_REPLAY_PROTECTION`: "true"/"false" (default: "true") - Reject reused nonces
+//!
+//! The secret is read from the environment, which is where hosts inject
+//! secrets (e.g. `wasmtime --env`, Spin variables); it is never logged.
+
+use crate::bindings::wasi::cli::environment::get_environment;
+use std::collections::HashMap;
+
+const DEFAULT_MAX_SKEW_SECS: u64 = 300;
+
+/// HMAC verification configuration from environment variables
+#[derive(Clone)]
+pub struct HmacConfig {
+    /// None when `WASMCP_HMAC_SECRET` is unset or empty
+    pub secret: Option<Vec<u8>>,
+    pub max_skew_secs: u64,
+    pub bucket: String,
+    pub replay_protection: bool,
+}
+
+impl HmacConfig {
+    /// Load configuration from environment variables
+    pub fn from_env() -> Self {
+        let env_map: HashMap<String, String> = get_environment().into_iter().collect();
+
+        let secret = env_map
+            .get("WASMCP_HMAC_SECRET")
+            .filter(|v| !v.is_empty())
+            .map(|v| v.as_bytes().to_vec());
+
+        let max_skew_secs = env_map
+            .get("WASMCP_HMAC_MAX_SKEW_SECS")
+            .and_then(|v| v.parse::<u64>().ok())
+            .filter(|n| *n > 0)
+            .unwrap_or(DEFAULT_MAX_SKEW_SECS);
+
+        let bucket = env_map
+            .get("WASMCP_HMAC_BUCKET")
+            .cloned()
+            .unwrap_or_default();
+
+        let replay_protection = env_map
+            .get("WASMCP_HMAC_REPLAY_PROTECTION")
+            .map(|v| v.to_lowercase() != "false")
+            .unwrap_or(true);
+
+        HmacConfig {
+            secret,
+            max_skew_secs,
+            bucket,
+            replay_protection,
+        }
+    }
+}
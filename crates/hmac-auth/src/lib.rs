@@ -0,0 +1,122 @@
+//! HMAC Auth Middleware Component
+//!
+//! Lightweight authentication for deployments that do not run OAuth. Every
+//! message arriving over HTTP must carry an HMAC-SHA256 signature made with a
+//! shared secret. This component:
+//! - Verifies the signature over the timestamp, nonce and body digest
+//! - Rejects timestamps outside the allowed clock skew
+//! - Rejects reused nonces, tracked in wasmcp:keyvalue until their
+//!   timestamp expires
+//! - Delegates verified messages downstream unchanged
+//!
+//! The transport only exposes a fixed set of headers by default, so the
+//! signature headers must be listed in `WASMCP_FORWARD_HEADERS`. Messages
+//! without an HTTP context (stdio) come from the local process and are not
+//! checked.
+
+#![allow(warnings)]
+
+mod bindings {
+    wit_bindgen::generate!({
+        world: "hmac-auth",
+        generate_all,
+    });
+}
+
+mod config;
+mod nonce;
+mod verify;
+
+use bindings::exports::wasmcp::mcp_v20251125::server_handler::{Guest, MessageContext};
+use bindings::wasmcp::mcp_v20251125::mcp;
+use bindings::wasmcp::mcp_v20251125::mcp::*;
+use bindings::wasmcp::mcp_v20251125::server_handler as downstream;
+use config::HmacConfig;
+use std::time::{SystemTime, UNIX_EPOCH};
+use wasmcp_errors::McpError;
+
+wasmcp_errors::impl_error_code!(mcp::ErrorCode, mcp::Error);
+
+struct HmacAuth;
+
+// Convert exported MessageContext to imported MessageContext
+fn to_downstream_ctx<'a>(ctx: &'a MessageContext<'a>) -> downstream::MessageContext<'a> {
+    downstream::MessageContext {
+        client_stream: ctx.client_stream,
+        protocol_version: ctx.protocol_version.clone(),
+        session: ctx.session.as_ref().map(|s| downstream::Session {
+            session_id: s.session_id.clone(),
+            store_id: s.store_id.clone(),
+        }),
+        identity: ctx.identity.as_ref().map(|i| downstream::Identity {
+            jwt: i.jwt.clone(),
+            claims: i.claims.clone(),
+        }),
+        frame: ctx.frame.clone(),
+        http_context: ctx.http_context.clone(),
+        client_capabilities: ctx.client_capabilities.clone(),
+        request_id: ctx.request_id.clone(),
+        deadline: ctx.deadline,
+        log_level: ctx.log_level,
+        client_info: ctx.client_info.clone(),
+        locale: ctx.locale.clone(),
+        trace_context: ctx.trace_context.clone(),
+    }
+}
+
+impl Guest for HmacAuth {
+    fn handle(
+        ctx: MessageContext,
+        message: ClientMessage,
+    ) -> Option<Result<ServerResult, ErrorCode>> {
+        if let Some(http_context) = &ctx.http_context {
+            let config = HmacConfig::from_env();
+            if let Err(e) = authenticate(&http_context.headers, &config) {
+                return Some(Err(e.into()));
+            }
+        }
+
+        downstream::handle(&to_downstream_ctx(&ctx), message)
+    }
+}
+
+/// Check the signature, then the nonce
+///
+/// The nonce is only recorded once the signature is valid, so unsigned
+/// traffic cannot fill the nonce store.
+fn authenticate(headers: &[(String, String)], config: &HmacConfig) -> Result<(), McpError> {
+    let Some(secret) = &config.secret else {
+        eprintln!("[hmac-auth] WASMCP_HMAC_SECRET is not set; rejecting request");
+        return Err(McpError::internal("HMAC authentication is not configured"));
+    };
+
+    let signed = verify::verify(headers, secret, now_secs(), config.max_skew_secs).map_err(|e| {
+        if headers.iter().all(|(name, _)| name != verify::SIGNATURE_HEADER) {
+            eprintln!(
+                "[hmac-auth] No signature header in http-context; make sure WASMCP_FORWARD_HEADERS includes {}",
+                verify::REQUIRED_HEADERS.join(",")
+            );
+        }
+        McpError::unauthorized(e)
+    })?;
+
+    // Instance clocks may differ by up to the skew too, so the nonce is kept
+    // for twice the window
+    if config.replay_protection {
+        let expires_at = signed
+            .timestamp
+            .saturating_add(config.max_skew_secs.saturating_mul(2));
+        nonce::claim(&config.bucket, signed.nonce, expires_at)?;
+    }
+
+    Ok(())
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+bindings::export!(HmacAuth with_types_in bindings);
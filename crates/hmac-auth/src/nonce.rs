@@ -0,0 +1,63 @@
+//! Replay protection backed by wasmcp:keyvalue
+//!
+//! Each accepted nonce is claimed under `hmac-auth:nonce:{nonce}` with an
+//! atomic increment, so two concurrent requests carrying the same nonce
+//! cannot both be accepted. The claimed counter is then replaced by a JSON
+//! record with an `expires_at`: nonces only need to be remembered while their
+//! timestamp is accepted, and the maintenance component's `kv-ttl` job
+//! deletes records past it (add `hmac-auth:nonce:` to its rules). A later
+//! increment on the record fails, since it is no longer a counter, and the
+//! record is what tells that failure apart from a storage error.
+
+use crate::bindings::wasmcp::keyvalue::store::{self as kv, Bucket, TypedValue};
+use serde_json::json;
+use wasmcp_errors::McpError;
+
+const NONCE_PREFIX: &str = "hmac-auth:nonce:";
+
+/// Record a nonce, failing if it was seen before
+///
+/// `expires_at` is when the nonce's timestamp stops being accepted, in Unix
+/// seconds. Storage errors reject the request: without the store a replay
+/// cannot be told apart from a fresh request.
+pub fn claim(bucket_name: &str, nonce: &str, expires_at: u64) -> Result<(), McpError> {
+    let bucket = kv::open(bucket_name).map_err(|e| {
+        eprintln!(
+            "[hmac-auth] Failed to open nonce bucket '{}': {}",
+            bucket_name, e
+        );
+        McpError::unavailable("Replay protection unavailable")
+    })?;
+
+    let key = format!("{}{}", NONCE_PREFIX, nonce);
+    match bucket.increment(&key, 1) {
+        Ok(1) => {
+            record_expiry(&bucket, &key, expires_at);
+            Ok(())
+        }
+        Ok(_) => Err(McpError::unauthorized("Nonce has already been used")),
+        Err(_) if is_recorded(&bucket, &key) => {
+            Err(McpError::unauthorized("Nonce has already been used"))
+        }
+        Err(e) => {
+            eprintln!("[hmac-auth] Failed to record nonce: {}", e);
+            Err(McpError::unavailable("Replay protection unavailable"))
+        }
+    }
+}
+
+/// Replace a claimed counter with a record the `kv-ttl` job can expire
+///
+/// Best effort: a counter left in place still rejects replays, it just
+/// isn't swept.
+fn record_expiry(bucket: &Bucket, key: &str, expires_at: u64) {
+    let record = json!({ "expires_at": expires_at }).to_string();
+    if let Err(e) = bucket.set_json(key, &record) {
+        eprintln!("[hmac-auth] WARNING: Failed to set nonce expiry: {}", e);
+    }
+}
+
+/// Whether a nonce was claimed and its record written
+fn is_recorded(bucket: &Bucket, key: &str) -> bool {
+    matches!(bucket.get(key), Ok(Some(TypedValue::AsJson(_))))
+}
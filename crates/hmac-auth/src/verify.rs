@@ -0,0 +1,218 @@
+//! Request signature verification
+//!
+//! Clients sign every HTTP request with four headers:
+//! - `x-wasmcp-timestamp`: Unix time in seconds
+//! - `x-wasmcp-nonce`: a value never reused with this server (1-128 of
+//!   `A-Z a-z 0-9 - _`, e.g. a UUID)
+//! - `content-digest`: RFC 9530 digest of the request body, e.g.
+//!   `sha-256=:<base64>:`
+//! - `x-wasmcp-signature`: `sha256=<hex>`, the HMAC-SHA256 of
+//!   `"{timestamp}.{nonce}.{content-digest}"` under the shared secret
+//!
+//! Middleware receives the request already parsed, so the body is covered
+//! through its digest rather than byte-for-byte. The transport hashes the
+//! body of every POST carrying `content-digest` and rejects a mismatch
+//! before any handler runs, so a verified digest stands for the body.
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+pub const TIMESTAMP_HEADER: &str = "x-wasmcp-timestamp";
+pub const NONCE_HEADER: &str = "x-wasmcp-nonce";
+pub const DIGEST_HEADER: &str = "content-digest";
+pub const SIGNATURE_HEADER: &str = "x-wasmcp-signature";
+
+/// Headers the transport must forward (`WASMCP_FORWARD_HEADERS`)
+pub const REQUIRED_HEADERS: &[&str] = &[
+    TIMESTAMP_HEADER,
+    NONCE_HEADER,
+    DIGEST_HEADER,
+    SIGNATURE_HEADER,
+];
+
+const SIGNATURE_PREFIX: &str = "sha256=";
+const MAX_NONCE_LEN: usize = 128;
+
+/// A request whose signature checked out
+#[derive(Debug, PartialEq, Eq)]
+pub struct SignedRequest<'a> {
+    pub timestamp: u64,
+    pub nonce: &'a str,
+}
+
+/// Verify the signature headers of a request
+///
+/// `now` is the current Unix time; timestamps further than `max_skew_secs`
+/// from it in either direction are rejected.
+pub fn verify<'a>(
+    headers: &'a [(String, String)],
+    secret: &[u8],
+    now: u64,
+    max_skew_secs: u64,
+) -> Result<SignedRequest<'a>, String> {
+    let header = |name: &str| {
+        headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.trim())
+            .ok_or_else(|| format!("Missing {} header", name))
+    };
+
+    let timestamp_str = header(TIMESTAMP_HEADER)?;
+    let nonce = header(NONCE_HEADER)?;
+    let digest = header(DIGEST_HEADER)?;
+    let signature = header(SIGNATURE_HEADER)?;
+
+    let timestamp = timestamp_str
+        .parse::<u64>()
+        .map_err(|_| format!("Invalid {} header", TIMESTAMP_HEADER))?;
+    if now.abs_diff(timestamp) > max_skew_secs {
+        return Err("Request timestamp is outside the allowed window".to_string());
+    }
+
+    if nonce.is_empty()
+        || nonce.len() > MAX_NONCE_LEN
+        || !nonce
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+    {
+        return Err(format!("Invalid {} header", NONCE_HEADER));
+    }
+
+    let expected = signature
+        .strip_prefix(SIGNATURE_PREFIX)
+        .and_then(|hex_sig| hex::decode(hex_sig).ok())
+        .ok_or_else(|| {
+            format!(
+                "Invalid {} header, expected {}<hex>",
+                SIGNATURE_HEADER, SIGNATURE_PREFIX
+            )
+        })?;
+
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret).map_err(|_| "Invalid HMAC secret".to_string())?;
+    mac.update(signing_input(timestamp_str, nonce, digest).as_bytes());
+    // Constant-time comparison
+    mac.verify_slice(&expected)
+        .map_err(|_| "Signature mismatch".to_string())?;
+
+    Ok(SignedRequest { timestamp, nonce })
+}
+
+/// The string a client signs
+pub fn signing_input(timestamp: &str, nonce: &str, digest: &str) -> String {
+    format!("{}.{}.{}", timestamp, nonce, digest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &[u8] = b"shared-secret";
+    const NOW: u64 = 1_700_000_000;
+    const DIGEST: &str = "sha-256=:47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU=:";
+
+    fn sign(timestamp: &str, nonce: &str, digest: &str) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(SECRET).unwrap();
+        mac.update(signing_input(timestamp, nonce, digest).as_bytes());
+        format!(
+            "{}{}",
+            SIGNATURE_PREFIX,
+            hex::encode(mac.finalize().into_bytes())
+        )
+    }
+
+    fn headers(timestamp: u64, nonce: &str) -> Vec<(String, String)> {
+        let timestamp = timestamp.to_string();
+        vec![
+            (TIMESTAMP_HEADER.to_string(), timestamp.clone()),
+            (NONCE_HEADER.to_string(), nonce.to_string()),
+            (DIGEST_HEADER.to_string(), DIGEST.to_string()),
+            (
+                SIGNATURE_HEADER.to_string(),
+                sign(&timestamp, nonce, DIGEST),
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_valid_signature() {
+        let headers = headers(NOW, "3f0c-a1_b2");
+        assert_eq!(
+            verify(&headers, SECRET, NOW, 300),
+            Ok(SignedRequest {
+                timestamp: NOW,
+                nonce: "3f0c-a1_b2"
+            })
+        );
+
+        // Header names are case-insensitive
+        let upper: Vec<_> = headers
+            .iter()
+            .map(|(name, value)| (name.to_ascii_uppercase(), value.clone()))
+            .collect();
+        assert!(verify(&upper, SECRET, NOW, 300).is_ok());
+    }
+
+    #[test]
+    fn test_bad_signature() {
+        let mut headers = headers(NOW, "n1");
+        assert_eq!(
+            verify(&headers, b"other-secret", NOW, 300),
+            Err("Signature mismatch".to_string())
+        );
+
+        // Any signed value changed after signing
+        headers[2].1 = "sha-256=:AAAA:".to_string();
+        assert_eq!(
+            verify(&headers, SECRET, NOW, 300),
+            Err("Signature mismatch".to_string())
+        );
+
+        headers[3].1 = "md5=abcd".to_string();
+        assert!(verify(&headers, SECRET, NOW, 300).is_err());
+        headers[3].1 = "sha256=not-hex".to_string();
+        assert!(verify(&headers, SECRET, NOW, 300).is_err());
+    }
+
+    #[test]
+    fn test_clock_skew() {
+        assert!(verify(&headers(NOW - 300, "n1"), SECRET, NOW, 300).is_ok());
+        assert!(verify(&headers(NOW + 300, "n1"), SECRET, NOW, 300).is_ok());
+        assert_eq!(
+            verify(&headers(NOW - 301, "n1"), SECRET, NOW, 300),
+            Err("Request timestamp is outside the allowed window".to_string())
+        );
+        assert!(verify(&headers(NOW + 301, "n1"), SECRET, NOW, 300).is_err());
+
+        let mut headers = headers(NOW, "n1");
+        headers[0].1 = "yesterday".to_string();
+        assert_eq!(
+            verify(&headers, SECRET, NOW, 300),
+            Err(format!("Invalid {} header", TIMESTAMP_HEADER))
+        );
+    }
+
+    #[test]
+    fn test_nonce_charset() {
+        let invalid = Err(format!("Invalid {} header", NONCE_HEADER));
+        for nonce in ["a.b", "a/b", "a b", "ä", &"n".repeat(MAX_NONCE_LEN + 1)] {
+            assert_eq!(verify(&headers(NOW, nonce), SECRET, NOW, 300), invalid);
+        }
+        assert!(verify(&headers(NOW, &"n".repeat(MAX_NONCE_LEN)), SECRET, NOW, 300).is_ok());
+    }
+
+    #[test]
+    fn test_missing_header() {
+        for missing in REQUIRED_HEADERS {
+            let headers: Vec<_> = headers(NOW, "n1")
+                .into_iter()
+                .filter(|(name, _)| name != missing)
+                .collect();
+            assert_eq!(
+                verify(&headers, SECRET, NOW, 300),
+                Err(format!("Missing {} header", missing))
+            );
+        }
+    }
+}
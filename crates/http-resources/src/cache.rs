@@ -0,0 +1,211 @@
+//! HTTP response cache backed by wasmcp:keyvalue
+//!
+//! Responses are cached per URL under two keys:
+//! - `http-resources:meta:{url}` - freshness and validators as JSON
+//! - `http-resources:body:{url}` - the raw response body
+//!
+//! Caching follows the upstream `Cache-Control` header:
+//! - `no-store`: the response is never cached
+//! - `no-cache`: cached, but always revalidated before use
+//! - `max-age` / `s-maxage`: fresh for that many seconds
+//!
+//! Stale entries with an `ETag` or `Last-Modified` validator are revalidated
+//! with a conditional request; a 304 reuses the cached body.
+
+use crate::bindings::wasmcp::keyvalue::store as kv;
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+use wasmcp_http_client::{Request, Response};
+
+const META_PREFIX: &str = "http-resources:meta:";
+const BODY_PREFIX: &str = "http-resources:body:";
+
+/// Cached response metadata
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheMeta {
+    stored_at: u64,
+    /// Seconds the entry is fresh for (0 = always revalidate)
+    max_age: u64,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    content_type: Option<String>,
+}
+
+impl CacheMeta {
+    fn is_fresh(&self, now: u64) -> bool {
+        self.max_age > 0 && now < self.stored_at.saturating_add(self.max_age)
+    }
+}
+
+/// A successfully fetched (or cached) resource body
+pub struct Fetched {
+    pub body: Vec<u8>,
+    pub content_type: Option<String>,
+}
+
+/// Fetch a URL, serving from and populating the cache when a bucket is given
+///
+/// Cache hits are served even past `deadline`; only upstream fetches are bounded.
+pub fn fetch(
+    url: &str,
+    bucket: Option<&kv::Bucket>,
+    max_bytes: u64,
+    deadline: Option<u64>,
+) -> Result<Fetched, String> {
+    let Some(bucket) = bucket else {
+        let response = get(url, &[], max_bytes, deadline)?;
+        return into_fetched(url, response);
+    };
+
+    let meta_key = format!("{}{}", META_PREFIX, url);
+    let body_key = format!("{}{}", BODY_PREFIX, url);
+    let now = now_secs();
+
+    let cached = load_meta(bucket, &meta_key).and_then(|meta| {
+        bucket
+            .get_bytes(&body_key)
+            .ok()
+            .flatten()
+            .map(|body| (meta, body))
+    });
+
+    let mut headers = Vec::new();
+    if let Some((meta, body)) = &cached {
+        if meta.is_fresh(now) {
+            return Ok(Fetched {
+                body: body.clone(),
+                content_type: meta.content_type.clone(),
+            });
+        }
+        if let Some(etag) = &meta.etag {
+            headers.push(("If-None-Match", etag.clone()));
+        }
+        if let Some(last_modified) = &meta.last_modified {
+            headers.push(("If-Modified-Since", last_modified.clone()));
+        }
+    }
+
+    let response = get(url, &headers, max_bytes, deadline)?;
+
+    if response.status == 304 {
+        if let Some((mut meta, body)) = cached {
+            meta.stored_at = now;
+            if let Some(max_age) = parse_max_age(response.header("cache-control")) {
+                meta.max_age = max_age;
+            }
+            store_meta(bucket, &meta_key, &meta);
+            return Ok(Fetched {
+                body,
+                content_type: meta.content_type,
+            });
+        }
+        return Err(format!("Unexpected 304 Not Modified from {}", url));
+    }
+
+    let cache_control = response.header("cache-control").unwrap_or_default();
+    let cacheable =
+        (200..300).contains(&response.status) && !has_directive(cache_control, "no-store");
+
+    if cacheable {
+        let max_age = if has_directive(cache_control, "no-cache") {
+            0
+        } else {
+            parse_max_age(Some(cache_control)).unwrap_or(0)
+        };
+        let meta = CacheMeta {
+            stored_at: now,
+            max_age,
+            etag: response.header("etag").map(str::to_string),
+            last_modified: response.header("last-modified").map(str::to_string),
+            content_type: response.header("content-type").map(str::to_string),
+        };
+
+        // Entries that can be neither served fresh nor revalidated are useless
+        if meta.max_age > 0 || meta.etag.is_some() || meta.last_modified.is_some() {
+            if let Err(e) = bucket.set_bytes(&body_key, &response.body) {
+                eprintln!("[http-resources] Failed to cache body for {}: {}", url, e);
+            } else {
+                store_meta(bucket, &meta_key, &meta);
+            }
+        }
+    }
+
+    into_fetched(url, response)
+}
+
+/// GET a URL upstream, bounded by `deadline` and the host allow-list
+///
+/// Bodies larger than `max_bytes` are rejected rather than truncated.
+fn get(
+    url: &str,
+    headers: &[(&str, String)],
+    max_bytes: u64,
+    deadline: Option<u64>,
+) -> Result<Response, String> {
+    let request = headers
+        .iter()
+        .fold(Request::new("GET", url), |request, (name, value)| {
+            request.with_header(*name, value.clone())
+        });
+    crate::wasi_client("http-resources", deadline)
+        .with_max_response_bytes(max_bytes)
+        .send(request)
+        .map_err(|e| e.to_string())
+}
+
+fn into_fetched(url: &str, response: Response) -> Result<Fetched, String> {
+    if !(200..300).contains(&response.status) {
+        return Err(format!(
+            "Upstream {} returned HTTP {}",
+            url, response.status
+        ));
+    }
+    let content_type = response.header("content-type").map(str::to_string);
+    Ok(Fetched {
+        body: response.body,
+        content_type,
+    })
+}
+
+fn load_meta(bucket: &kv::Bucket, key: &str) -> Option<CacheMeta> {
+    match bucket.get(key) {
+        Ok(Some(kv::TypedValue::AsJson(json))) => serde_json::from_str(&json).ok(),
+        _ => None,
+    }
+}
+
+fn store_meta(bucket: &kv::Bucket, key: &str, meta: &CacheMeta) {
+    if let Ok(json) = serde_json::to_string(meta) {
+        if let Err(e) = bucket.set(key, &kv::TypedValue::AsJson(json)) {
+            eprintln!("[http-resources] Failed to cache metadata: {}", e);
+        }
+    }
+}
+
+fn has_directive(cache_control: &str, directive: &str) -> bool {
+    cache_control
+        .split(',')
+        .any(|d| d.trim().eq_ignore_ascii_case(directive))
+}
+
+/// `s-maxage` takes precedence over `max-age`
+fn parse_max_age(cache_control: Option<&str>) -> Option<u64> {
+    let cache_control = cache_control?;
+    let directive = |name: &str| {
+        cache_control.split(',').find_map(|d| {
+            let (key, value) = d.trim().split_once('=')?;
+            key.trim()
+                .eq_ignore_ascii_case(name)
+                .then(|| value.trim().trim_matches('"').parse::<u64>().ok())
+                .flatten()
+        })
+    };
+    directive("s-maxage").or_else(|| directive("max-age"))
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
@@ -0,0 +1,116 @@
+//! Configuration for the HTTP resource provider
+//!
+//! Environment variables:
+//! - `WASMCP_HTTP_RESOURCES`: Upstream endpoints to expose (required)
+//!   - JSON array of objects: `[{"url": "https://...", "name": "...", "description": "..."}]`
+//!   - Or a comma-separated list of URLs
+//! - `WASMCP_HTTP_RESOURCES_BUCKET`: KV bucket for the response cache (default: "")
+//! - `WASMCP_HTTP_RESOURCES_CACHE`: "true"/"false" (default: "true") - Enable response caching
+//! - `WASMCP_HTTP_RESOURCES_MAX_BYTES`: Largest upstream body returned (default: 10MB)
+
+use crate::bindings::wasi::cli::environment::get_environment;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+const DEFAULT_MAX_BYTES: u64 = 10 * 1024 * 1024;
+
+/// An upstream endpoint exposed as a resource
+#[derive(Debug, Clone, Deserialize)]
+pub struct Endpoint {
+    /// Upstream URL, also used as the resource URI
+    pub url: String,
+    /// Resource name (default: the URL)
+    pub name: Option<String>,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    /// MIME type for listings and reads (default: inferred from the URL
+    /// extension in listings, the response Content-Type in reads)
+    #[serde(alias = "mimeType")]
+    pub mime_type: Option<String>,
+}
+
+/// HTTP provider configuration from environment variables
+#[derive(Debug, Clone)]
+pub struct HttpResourcesConfig {
+    pub endpoints: Vec<Endpoint>,
+    /// Endpoint URL to position in `endpoints`
+    index: HashMap<String, usize>,
+    pub bucket: String,
+    pub cache_enabled: bool,
+    pub max_bytes: u64,
+}
+
+impl HttpResourcesConfig {
+    /// Load configuration from environment variables
+    pub fn from_env() -> Self {
+        let env_map: HashMap<String, String> = get_environment().into_iter().collect();
+
+        let endpoints: Vec<Endpoint> = env_map
+            .get("WASMCP_HTTP_RESOURCES")
+            .map(|v| parse_endpoints(v))
+            .unwrap_or_default();
+
+        let bucket = env_map
+            .get("WASMCP_HTTP_RESOURCES_BUCKET")
+            .cloned()
+            .unwrap_or_default();
+
+        let cache_enabled = env_map
+            .get("WASMCP_HTTP_RESOURCES_CACHE")
+            .map(|v| v.to_lowercase() != "false")
+            .unwrap_or(true);
+
+        let max_bytes = env_map
+            .get("WASMCP_HTTP_RESOURCES_MAX_BYTES")
+            .and_then(|v| v.parse::<u64>().ok())
+            .filter(|n| *n > 0)
+            .unwrap_or(DEFAULT_MAX_BYTES);
+
+        let mut index = HashMap::with_capacity(endpoints.len());
+        for (i, endpoint) in endpoints.iter().enumerate() {
+            index.entry(endpoint.url.clone()).or_insert(i);
+        }
+
+        HttpResourcesConfig {
+            endpoints,
+            index,
+            bucket,
+            cache_enabled,
+            max_bytes,
+        }
+    }
+
+    /// Find the endpoint configured for a resource URI
+    ///
+    /// Only configured URLs are ever fetched, so clients cannot use this
+    /// component to reach arbitrary hosts.
+    pub fn endpoint(&self, uri: &str) -> Option<&Endpoint> {
+        self.index.get(uri).map(|&i| &self.endpoints[i])
+    }
+}
+
+fn parse_endpoints(value: &str) -> Vec<Endpoint> {
+    let trimmed = value.trim();
+    if trimmed.starts_with('[') {
+        return serde_json::from_str(trimmed).unwrap_or_else(|e| {
+            eprintln!(
+                "[http-resources] WARNING: Invalid WASMCP_HTTP_RESOURCES JSON: {}. No endpoints configured.",
+                e
+            );
+            Vec::new()
+        });
+    }
+
+    trimmed
+        .split(',')
+        .map(str::trim)
+        .filter(|url| !url.is_empty())
+        .map(|url| Endpoint {
+            url: url.to_string(),
+            name: None,
+            title: None,
+            description: None,
+            mime_type: None,
+        })
+        .collect()
+}
@@ -0,0 +1,213 @@
+//! HTTP Resources Middleware Component
+//!
+//! A reusable middleware that exposes upstream HTTP endpoints as MCP resources
+//! with no custom handler code. This component:
+//! - Lists every configured endpoint as a resource (URI = upstream URL)
+//! - Fetches endpoints on resources/read via wasi:http
+//! - Caches responses in wasmcp:keyvalue, honoring Cache-Control, ETag and
+//!   Last-Modified
+//! - Delegates all other requests downstream
+//!
+//! Only URLs listed in `WASMCP_HTTP_RESOURCES` are ever fetched. Reads for any
+//! other URI are passed downstream untouched, so clients cannot turn this
+//! component into an open proxy.
+
+#![allow(warnings)]
+
+mod bindings {
+    wit_bindgen::generate!({
+        world: "http-resources",
+        generate_all,
+    });
+}
+
+mod cache;
+mod config;
+mod mime;
+
+use bindings::exports::wasmcp::mcp_v20251125::server_handler::{Guest, MessageContext};
+use bindings::wasmcp::keyvalue::store as kv;
+use bindings::wasmcp::mcp_v20251125::mcp::*;
+use bindings::wasmcp::mcp_v20251125::server_handler as downstream;
+use config::{Endpoint, HttpResourcesConfig};
+use std::sync::OnceLock;
+
+wasmcp_http_client::impl_wasi_transport!(crate::bindings::wasi);
+
+struct HttpResources;
+
+/// Configuration, read once per instance
+static CONFIG: OnceLock<HttpResourcesConfig> = OnceLock::new();
+
+// Convert exported MessageContext to imported MessageContext
+fn to_downstream_ctx<'a>(ctx: &'a MessageContext<'a>) -> downstream::MessageContext<'a> {
+    downstream::MessageContext {
+        client_stream: ctx.client_stream,
+        protocol_version: ctx.protocol_version.clone(),
+        session: ctx.session.as_ref().map(|s| downstream::Session {
+            session_id: s.session_id.clone(),
+            store_id: s.store_id.clone(),
+        }),
+        identity: ctx.identity.as_ref().map(|i| downstream::Identity {
+            jwt: i.jwt.clone(),
+            claims: i.claims.clone(),
+        }),
+        frame: ctx.frame.clone(),
+        http_context: ctx.http_context.clone(),
+        client_capabilities: ctx.client_capabilities.clone(),
+        request_id: ctx.request_id.clone(),
+        deadline: ctx.deadline,
+        log_level: ctx.log_level,
+        client_info: ctx.client_info.clone(),
+        locale: ctx.locale.clone(),
+        trace_context: ctx.trace_context.clone(),
+    }
+}
+
+impl Guest for HttpResources {
+    fn handle(
+        ctx: MessageContext,
+        message: ClientMessage,
+    ) -> Option<Result<ServerResult, ErrorCode>> {
+        let config = CONFIG.get_or_init(HttpResourcesConfig::from_env);
+
+        match message {
+            ClientMessage::Request((request_id, request)) => {
+                let result = match &request {
+                    // Our resources are all on the first page
+                    ClientRequest::ResourcesList(list_req) if list_req.cursor.is_none() => {
+                        handle_resources_list(request_id.clone(), &ctx, config)
+                    }
+                    ClientRequest::ResourcesRead(read_req) => {
+                        match config.endpoint(&read_req.uri) {
+                            Some(endpoint) => handle_resources_read(endpoint, config, ctx.deadline),
+                            None => return delegate(&ctx, request_id, request),
+                        }
+                    }
+                    _ => return delegate(&ctx, request_id, request),
+                };
+                Some(result)
+            }
+            _ => {
+                // Forward notifications, results, errors to downstream
+                downstream::handle(&to_downstream_ctx(&ctx), message)
+            }
+        }
+    }
+}
+
+fn delegate(
+    ctx: &MessageContext,
+    request_id: RequestId,
+    request: ClientRequest,
+) -> Option<Result<ServerResult, ErrorCode>> {
+    let downstream_msg = ClientMessage::Request((request_id, request));
+    downstream::handle(&to_downstream_ctx(ctx), downstream_msg)
+}
+
+fn handle_resources_list(
+    request_id: RequestId,
+    ctx: &MessageContext,
+    config: &HttpResourcesConfig,
+) -> Result<ServerResult, ErrorCode> {
+    let mut resources: Vec<McpResource> = config.endpoints.iter().map(to_resource).collect();
+
+    let downstream_req = ClientRequest::ResourcesList(ListResourcesRequest { cursor: None });
+    let downstream_msg = ClientMessage::Request((request_id, downstream_req));
+    let next_cursor = match downstream::handle(&to_downstream_ctx(ctx), downstream_msg) {
+        Some(Ok(ServerResult::ResourcesList(downstream_result))) => {
+            resources.extend(downstream_result.resources);
+            downstream_result.next_cursor
+        }
+        Some(Err(ErrorCode::MethodNotFound(_))) | None => None,
+        Some(Err(e)) => return Err(e),
+        Some(Ok(_)) => None,
+    };
+
+    Ok(ServerResult::ResourcesList(ListResourcesResult {
+        resources,
+        next_cursor,
+        meta: None,
+    }))
+}
+
+fn handle_resources_read(
+    endpoint: &Endpoint,
+    config: &HttpResourcesConfig,
+    deadline: Option<u64>,
+) -> Result<ServerResult, ErrorCode> {
+    let bucket = if config.cache_enabled {
+        kv::open(&config.bucket)
+            .map_err(|e| {
+                eprintln!(
+                    "[http-resources] Failed to open cache bucket '{}': {}. Fetching uncached.",
+                    config.bucket, e
+                )
+            })
+            .ok()
+    } else {
+        None
+    };
+
+    let fetched = cache::fetch(&endpoint.url, bucket.as_ref(), config.max_bytes, deadline)
+        .map_err(|e| internal_error(&e))?;
+
+    let mime_type = mime::of_response(endpoint, fetched.content_type.as_deref());
+    let is_text = mime::is_text(&mime_type);
+    let options = Some(EmbeddedResourceOptions {
+        mime_type: Some(mime_type),
+        meta: None,
+    });
+
+    let resource_contents = match String::from_utf8(fetched.body) {
+        Ok(text) if is_text => ResourceContents::Text(TextResourceContents {
+            uri: endpoint.url.clone(),
+            text: TextData::Text(text),
+            options,
+        }),
+        Ok(text) => ResourceContents::Blob(BlobResourceContents {
+            uri: endpoint.url.clone(),
+            blob: BlobData::Blob(text.into_bytes()),
+            options,
+        }),
+        Err(e) => ResourceContents::Blob(BlobResourceContents {
+            uri: endpoint.url.clone(),
+            blob: BlobData::Blob(e.into_bytes()),
+            options,
+        }),
+    };
+
+    Ok(ServerResult::ResourcesRead(ReadResourceResult {
+        contents: vec![resource_contents],
+        meta: None,
+    }))
+}
+
+fn to_resource(endpoint: &Endpoint) -> McpResource {
+    McpResource {
+        uri: endpoint.url.clone(),
+        name: endpoint
+            .name
+            .clone()
+            .unwrap_or_else(|| endpoint.url.clone()),
+        options: Some(ResourceOptions {
+            size: None,
+            title: endpoint.title.clone(),
+            description: endpoint.description.clone(),
+            mime_type: mime::advertised(endpoint),
+            annotations: None,
+            meta: None,
+            icons: None,
+        }),
+    }
+}
+
+fn internal_error(message: &str) -> ErrorCode {
+    ErrorCode::InternalError(Error {
+        code: -32603,
+        message: message.to_string(),
+        data: None,
+    })
+}
+
+bindings::export!(HttpResources with_types_in bindings);
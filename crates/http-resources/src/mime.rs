@@ -0,0 +1,78 @@
+//! MIME types for HTTP resources
+
+use crate::config::Endpoint;
+
+/// Fallback when neither the configuration, the response nor the URL says
+pub const DEFAULT_MIME_TYPE: &str = "application/octet-stream";
+
+/// MIME type an endpoint is advertised with in listings
+///
+/// A configured `mimeType` wins; otherwise it's inferred from the URL path's
+/// extension, and left out when the extension is unknown.
+pub fn advertised(endpoint: &Endpoint) -> Option<String> {
+    endpoint
+        .mime_type
+        .clone()
+        .or_else(|| from_extension(&endpoint.url).map(String::from))
+}
+
+/// MIME type of fetched contents
+///
+/// A configured `mimeType` overrides the response `Content-Type` (upstreams
+/// often serve e.g. Markdown as text/plain), so reads match the listing.
+pub fn of_response(endpoint: &Endpoint, content_type: Option<&str>) -> String {
+    // Strip parameters such as "; charset=utf-8"
+    let content_type = content_type
+        .and_then(|ct| ct.split(';').next())
+        .map(str::trim)
+        .filter(|ct| !ct.is_empty());
+
+    endpoint
+        .mime_type
+        .as_deref()
+        .or(content_type)
+        .or_else(|| from_extension(&endpoint.url))
+        .unwrap_or(DEFAULT_MIME_TYPE)
+        .to_string()
+}
+
+/// Infer a MIME type from the extension of a URL's path
+fn from_extension(url: &str) -> Option<&'static str> {
+    let url = url::Url::parse(url).ok()?;
+    let file = url.path_segments()?.next_back()?;
+    let (_, extension) = file.rsplit_once('.')?;
+    match extension.to_ascii_lowercase().as_str() {
+        "md" | "markdown" => Some("text/markdown"),
+        "txt" => Some("text/plain"),
+        "html" | "htm" => Some("text/html"),
+        "css" => Some("text/css"),
+        "csv" => Some("text/csv"),
+        "js" => Some("application/javascript"),
+        "json" => Some("application/json"),
+        "yaml" | "yml" => Some("application/yaml"),
+        "toml" => Some("application/toml"),
+        "xml" => Some("application/xml"),
+        "svg" => Some("image/svg+xml"),
+        "png" => Some("image/png"),
+        "jpg" | "jpeg" => Some("image/jpeg"),
+        "gif" => Some("image/gif"),
+        "webp" => Some("image/webp"),
+        "pdf" => Some("application/pdf"),
+        _ => None,
+    }
+}
+
+/// Whether a MIME type is returned as text contents
+pub fn is_text(mime_type: &str) -> bool {
+    mime_type.starts_with("text/")
+        || mime_type.ends_with("+json")
+        || mime_type.ends_with("+xml")
+        || matches!(
+            mime_type,
+            "application/json"
+                | "application/xml"
+                | "application/javascript"
+                | "application/yaml"
+                | "application/toml"
+        )
+}
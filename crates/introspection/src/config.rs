@@ -0,0 +1,43 @@
+//! Configuration for the introspection middleware
+//!
+//! Environment variables:
+//! - `WASMCP_SERVER_NAME`, `WASMCP_SERVER_TITLE`, `WASMCP_SERVER_VERSION`:
+//!   Server metadata, shared with the transport's initialize response
+//! - `WASMCP_INTROSPECTION_TOOL`: "true"/"false" (default: "true") - Also
+//!   expose the description as the `mcp-describe` tool
+
+use crate::bindings::wasi::cli::environment::get_environment;
+use std::collections::HashMap;
+
+/// Matches the transport's default server name
+const DEFAULT_SERVER_NAME: &str = "wasmcp-server";
+
+/// Introspection configuration from environment variables
+#[derive(Debug, Clone)]
+pub struct IntrospectionConfig {
+    pub server_name: String,
+    pub server_title: Option<String>,
+    pub server_version: Option<String>,
+    pub tool_enabled: bool,
+}
+
+impl IntrospectionConfig {
+    /// Load configuration from environment variables
+    pub fn from_env() -> Self {
+        let env_map: HashMap<String, String> = get_environment().into_iter().collect();
+        let get = |name: &str| env_map.get(name).filter(|v| !v.is_empty()).cloned();
+
+        let tool_enabled = env_map
+            .get("WASMCP_INTROSPECTION_TOOL")
+            .map(|v| v.to_lowercase() != "false")
+            .unwrap_or(true);
+
+        IntrospectionConfig {
+            server_name: get("WASMCP_SERVER_NAME")
+                .unwrap_or_else(|| DEFAULT_SERVER_NAME.to_string()),
+            server_title: get("WASMCP_SERVER_TITLE"),
+            server_version: get("WASMCP_SERVER_VERSION"),
+            tool_enabled,
+        }
+    }
+}
@@ -0,0 +1,244 @@
+//! The server description document
+//!
+//! Built by listing every page of tools, resources, resource templates and
+//! prompts from the handlers composed downstream, so it always matches what
+//! clients see in the individual list results.
+
+use crate::bindings::exports::wasmcp::mcp_v20251125::server_handler::MessageContext;
+use crate::bindings::wasmcp::mcp_v20251125::mcp::*;
+use crate::bindings::wasmcp::mcp_v20251125::server_handler as downstream;
+use crate::config::IntrospectionConfig;
+use crate::to_downstream_ctx;
+use serde_json::{Map, Value, json};
+
+/// Upper bound on pages fetched per list, in case a handler never ends one
+const MAX_PAGES: usize = 100;
+
+/// Describe the server and everything it exposes
+///
+/// `tools` and `resources` are this component's own entries, which are
+/// listed ahead of the downstream ones.
+pub fn describe(
+    ctx: &MessageContext,
+    request_id: &RequestId,
+    config: &IntrospectionConfig,
+    tools: Vec<Tool>,
+    resources: Vec<McpResource>,
+) -> Result<Value, ErrorCode> {
+    let mut all_tools = tools;
+    all_tools.extend(collect(
+        ctx,
+        request_id,
+        |cursor| ClientRequest::ToolsList(ListToolsRequest { cursor, meta: None }),
+        |result| match result {
+            ServerResult::ToolsList(list) => Some((list.tools, list.next_cursor)),
+            _ => None,
+        },
+    )?);
+
+    let mut all_resources = resources;
+    all_resources.extend(collect(
+        ctx,
+        request_id,
+        |cursor| ClientRequest::ResourcesList(ListResourcesRequest { cursor }),
+        |result| match result {
+            ServerResult::ResourcesList(list) => Some((list.resources, list.next_cursor)),
+            _ => None,
+        },
+    )?);
+
+    let templates = collect(
+        ctx,
+        request_id,
+        |cursor| ClientRequest::ResourcesTemplatesList(ListResourceTemplatesRequest { cursor }),
+        |result| match result {
+            ServerResult::ResourcesTemplatesList(list) => {
+                Some((list.resource_templates, list.next_cursor))
+            }
+            _ => None,
+        },
+    )?;
+
+    let prompts = collect(
+        ctx,
+        request_id,
+        |cursor| ClientRequest::PromptsList(ListPromptsRequest { cursor }),
+        |result| match result {
+            ServerResult::PromptsList(list) => Some((list.prompts, list.next_cursor)),
+            _ => None,
+        },
+    )?;
+
+    Ok(json!({
+        "server": {
+            "name": config.server_name,
+            "title": config.server_title,
+            "version": config.server_version,
+        },
+        "protocolVersion": ctx.protocol_version,
+        "tools": all_tools.iter().map(tool_json).collect::<Vec<_>>(),
+        "resources": all_resources.iter().map(resource_json).collect::<Vec<_>>(),
+        "resourceTemplates": templates.iter().map(template_json).collect::<Vec<_>>(),
+        "prompts": prompts.iter().map(prompt_json).collect::<Vec<_>>(),
+    }))
+}
+
+/// Every item of a paginated list from downstream
+///
+/// A list downstream doesn't implement is empty.
+fn collect<T>(
+    ctx: &MessageContext,
+    request_id: &RequestId,
+    request: impl Fn(Option<String>) -> ClientRequest,
+    page: impl Fn(ServerResult) -> Option<(Vec<T>, Option<String>)>,
+) -> Result<Vec<T>, ErrorCode> {
+    let mut items = Vec::new();
+    let mut cursor = None;
+    for _ in 0..MAX_PAGES {
+        let message = ClientMessage::Request((request_id.clone(), request(cursor.take())));
+        let (page_items, next) = match downstream::handle(&to_downstream_ctx(ctx), message) {
+            Some(Ok(result)) => page(result).unwrap_or_default(),
+            Some(Err(ErrorCode::MethodNotFound(_))) | None => break,
+            Some(Err(e)) => return Err(e),
+        };
+        items.extend(page_items);
+        match next {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+    Ok(items)
+}
+
+fn tool_json(tool: &Tool) -> Value {
+    let mut entry = Map::new();
+    entry.insert("name".into(), json!(tool.name));
+    entry.insert("inputSchema".into(), parse(&tool.input_schema));
+    if let Some(options) = &tool.options {
+        insert(
+            &mut entry,
+            "title",
+            options.title.as_deref().map(Value::from),
+        );
+        insert(
+            &mut entry,
+            "description",
+            options.description.as_deref().map(Value::from),
+        );
+        insert(
+            &mut entry,
+            "outputSchema",
+            options.output_schema.as_deref().map(parse),
+        );
+        insert(
+            &mut entry,
+            "annotations",
+            options.annotations.as_ref().map(|a| {
+                json!({
+                    "title": a.title,
+                    "readOnlyHint": a.read_only_hint,
+                    "destructiveHint": a.destructive_hint,
+                    "idempotentHint": a.idempotent_hint,
+                    "openWorldHint": a.open_world_hint,
+                })
+            }),
+        );
+        insert(&mut entry, "_meta", options.meta.as_deref().map(parse));
+    }
+    Value::Object(entry)
+}
+
+fn resource_json(resource: &McpResource) -> Value {
+    let mut entry = Map::new();
+    entry.insert("uri".into(), json!(resource.uri));
+    entry.insert("name".into(), json!(resource.name));
+    if let Some(options) = &resource.options {
+        insert(
+            &mut entry,
+            "title",
+            options.title.as_deref().map(Value::from),
+        );
+        insert(
+            &mut entry,
+            "description",
+            options.description.as_deref().map(Value::from),
+        );
+        insert(
+            &mut entry,
+            "mimeType",
+            options.mime_type.as_deref().map(Value::from),
+        );
+        insert(&mut entry, "size", options.size.map(Value::from));
+    }
+    Value::Object(entry)
+}
+
+fn template_json(template: &ResourceTemplate) -> Value {
+    let mut entry = Map::new();
+    entry.insert("uriTemplate".into(), json!(template.uri_template));
+    entry.insert("name".into(), json!(template.name));
+    if let Some(options) = &template.options {
+        insert(
+            &mut entry,
+            "title",
+            options.title.as_deref().map(Value::from),
+        );
+        insert(
+            &mut entry,
+            "description",
+            options.description.as_deref().map(Value::from),
+        );
+        insert(
+            &mut entry,
+            "mimeType",
+            options.mime_type.as_deref().map(Value::from),
+        );
+    }
+    Value::Object(entry)
+}
+
+fn prompt_json(prompt: &Prompt) -> Value {
+    let mut entry = Map::new();
+    entry.insert("name".into(), json!(prompt.name));
+    if let Some(options) = &prompt.options {
+        insert(
+            &mut entry,
+            "title",
+            options.title.as_deref().map(Value::from),
+        );
+        insert(
+            &mut entry,
+            "description",
+            options.description.as_deref().map(Value::from),
+        );
+        insert(
+            &mut entry,
+            "arguments",
+            options.arguments.as_ref().map(|arguments| {
+                arguments
+                    .iter()
+                    .map(|a| {
+                        json!({
+                            "name": a.name,
+                            "title": a.title,
+                            "description": a.description,
+                            "required": a.required.unwrap_or(false),
+                        })
+                    })
+                    .collect::<Value>()
+            }),
+        );
+    }
+    Value::Object(entry)
+}
+
+/// Embedded JSON (schemas, _meta), or the raw string if it doesn't parse
+fn parse(json: &str) -> Value {
+    serde_json::from_str(json).unwrap_or_else(|_| Value::String(json.to_string()))
+}
+
+fn insert(entry: &mut Map<String, Value>, key: &str, value: Option<Value>) {
+    if let Some(value) = value {
+        entry.insert(key.to_string(), value);
+    }
+}
@@ -0,0 +1,261 @@
+//! Introspection Middleware Component
+//!
+//! Describes the composed server in one JSON document, for agent ecosystems
+//! and registries that index MCP servers. This component:
+//! - Serves the description from the `wasmcp://server-info` resource
+//! - Optionally exposes it as the `mcp-describe` tool as well
+//! - Delegates all other requests downstream
+//!
+//! The description holds the server metadata, the negotiated protocol
+//! version, and every tool (with schemas), resource, resource template and
+//! prompt listed by the handlers composed behind this component.
+
+#![allow(warnings)]
+
+mod bindings {
+    wit_bindgen::generate!({
+        world: "introspection",
+        generate_all,
+    });
+}
+
+mod config;
+mod describe;
+
+use bindings::exports::wasmcp::mcp_v20251125::server_handler::{Guest, MessageContext};
+use bindings::wasmcp::mcp_v20251125::mcp::*;
+use bindings::wasmcp::mcp_v20251125::server_handler as downstream;
+use config::IntrospectionConfig;
+use wasmcp_errors::McpError;
+
+wasmcp_errors::impl_error_code!(ErrorCode, Error);
+
+const SERVER_INFO_URI: &str = "wasmcp://server-info";
+const TOOL_NAME: &str = "mcp-describe";
+
+struct Introspection;
+
+// Convert exported MessageContext to imported MessageContext
+fn to_downstream_ctx<'a>(ctx: &'a MessageContext<'a>) -> downstream::MessageContext<'a> {
+    downstream::MessageContext {
+        client_stream: ctx.client_stream,
+        protocol_version: ctx.protocol_version.clone(),
+        session: ctx.session.as_ref().map(|s| downstream::Session {
+            session_id: s.session_id.clone(),
+            store_id: s.store_id.clone(),
+        }),
+        identity: ctx.identity.as_ref().map(|i| downstream::Identity {
+            jwt: i.jwt.clone(),
+            claims: i.claims.clone(),
+        }),
+        frame: ctx.frame.clone(),
+        http_context: ctx.http_context.clone(),
+        client_capabilities: ctx.client_capabilities.clone(),
+        request_id: ctx.request_id.clone(),
+        deadline: ctx.deadline,
+        log_level: ctx.log_level,
+        client_info: ctx.client_info.clone(),
+        locale: ctx.locale.clone(),
+        trace_context: ctx.trace_context.clone(),
+    }
+}
+
+impl Guest for Introspection {
+    fn handle(
+        ctx: MessageContext,
+        message: ClientMessage,
+    ) -> Option<Result<ServerResult, ErrorCode>> {
+        let config = IntrospectionConfig::from_env();
+
+        match message {
+            ClientMessage::Request((request_id, request)) => {
+                let result = match &request {
+                    // Our entries are on the first page
+                    ClientRequest::ToolsList(list_req)
+                        if config.tool_enabled && list_req.cursor.is_none() =>
+                    {
+                        handle_tools_list(request_id.clone(), &ctx)
+                    }
+                    ClientRequest::ToolsCall(call)
+                        if config.tool_enabled && call.name == TOOL_NAME =>
+                    {
+                        handle_describe_call(&request_id, &ctx, &config)
+                    }
+                    ClientRequest::ResourcesList(list_req) if list_req.cursor.is_none() => {
+                        handle_resources_list(request_id.clone(), &ctx)
+                    }
+                    ClientRequest::ResourcesRead(read_req) if read_req.uri == SERVER_INFO_URI => {
+                        handle_server_info_read(&request_id, &ctx, &config)
+                    }
+                    _ => return delegate(&ctx, request_id, request),
+                };
+                Some(result)
+            }
+            _ => {
+                // Forward notifications, results, errors to downstream
+                downstream::handle(&to_downstream_ctx(&ctx), message)
+            }
+        }
+    }
+}
+
+fn delegate(
+    ctx: &MessageContext,
+    request_id: RequestId,
+    request: ClientRequest,
+) -> Option<Result<ServerResult, ErrorCode>> {
+    let downstream_msg = ClientMessage::Request((request_id, request));
+    downstream::handle(&to_downstream_ctx(ctx), downstream_msg)
+}
+
+fn handle_tools_list(
+    request_id: RequestId,
+    ctx: &MessageContext,
+) -> Result<ServerResult, ErrorCode> {
+    let mut tools = vec![describe_tool()];
+
+    let downstream_req = ClientRequest::ToolsList(ListToolsRequest {
+        cursor: None,
+        meta: None,
+    });
+    let downstream_msg = ClientMessage::Request((request_id, downstream_req));
+    let next_cursor = match downstream::handle(&to_downstream_ctx(ctx), downstream_msg) {
+        Some(Ok(ServerResult::ToolsList(downstream_result))) => {
+            tools.extend(downstream_result.tools);
+            downstream_result.next_cursor
+        }
+        Some(Err(ErrorCode::MethodNotFound(_))) | None => None,
+        Some(Err(e)) => return Err(e),
+        Some(Ok(_)) => None,
+    };
+
+    Ok(ServerResult::ToolsList(ListToolsResult {
+        tools,
+        next_cursor,
+        meta: None,
+    }))
+}
+
+fn handle_resources_list(
+    request_id: RequestId,
+    ctx: &MessageContext,
+) -> Result<ServerResult, ErrorCode> {
+    let mut resources = vec![server_info_resource()];
+
+    let downstream_req = ClientRequest::ResourcesList(ListResourcesRequest { cursor: None });
+    let downstream_msg = ClientMessage::Request((request_id, downstream_req));
+    let next_cursor = match downstream::handle(&to_downstream_ctx(ctx), downstream_msg) {
+        Some(Ok(ServerResult::ResourcesList(downstream_result))) => {
+            resources.extend(downstream_result.resources);
+            downstream_result.next_cursor
+        }
+        Some(Err(ErrorCode::MethodNotFound(_))) | None => None,
+        Some(Err(e)) => return Err(e),
+        Some(Ok(_)) => None,
+    };
+
+    Ok(ServerResult::ResourcesList(ListResourcesResult {
+        resources,
+        next_cursor,
+        meta: None,
+    }))
+}
+
+fn handle_server_info_read(
+    request_id: &RequestId,
+    ctx: &MessageContext,
+    config: &IntrospectionConfig,
+) -> Result<ServerResult, ErrorCode> {
+    let description = describe(request_id, ctx, config)?;
+    let text = serde_json::to_string_pretty(&description)
+        .map_err(|e| McpError::internal(e.to_string()))?;
+
+    Ok(ServerResult::ResourcesRead(ReadResourceResult {
+        contents: vec![ResourceContents::Text(TextResourceContents {
+            uri: SERVER_INFO_URI.to_string(),
+            text: TextData::Text(text),
+            options: Some(EmbeddedResourceOptions {
+                mime_type: Some("application/json".to_string()),
+                meta: None,
+            }),
+        })],
+        meta: None,
+    }))
+}
+
+fn handle_describe_call(
+    request_id: &RequestId,
+    ctx: &MessageContext,
+    config: &IntrospectionConfig,
+) -> Result<ServerResult, ErrorCode> {
+    let description = describe(request_id, ctx, config)?;
+
+    Ok(ServerResult::ToolsCall(CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text: TextData::Text(description.to_string()),
+            options: None,
+        })],
+        is_error: None,
+        meta: None,
+        structured_content: Some(description.to_string()),
+    }))
+}
+
+/// The description, including this component's own tool and resource
+fn describe(
+    request_id: &RequestId,
+    ctx: &MessageContext,
+    config: &IntrospectionConfig,
+) -> Result<serde_json::Value, ErrorCode> {
+    let tools = if config.tool_enabled {
+        vec![describe_tool()]
+    } else {
+        Vec::new()
+    };
+    describe::describe(ctx, request_id, config, tools, vec![server_info_resource()])
+}
+
+fn describe_tool() -> Tool {
+    Tool {
+        name: TOOL_NAME.to_string(),
+        input_schema: r#"{"type": "object", "properties": {}}"#.to_string(),
+        options: Some(ToolOptions {
+            meta: None,
+            annotations: Some(ToolAnnotations {
+                destructive_hint: Some(false),
+                idempotent_hint: Some(true),
+                open_world_hint: Some(false),
+                read_only_hint: Some(true),
+                title: None,
+            }),
+            description: Some(
+                "Describe this server: metadata, protocol version, and every tool (with schemas), resource, resource template and prompt it exposes."
+                    .to_string(),
+            ),
+            output_schema: None,
+            title: Some("Describe server".to_string()),
+            icons: None,
+        }),
+    }
+}
+
+fn server_info_resource() -> McpResource {
+    McpResource {
+        uri: SERVER_INFO_URI.to_string(),
+        name: "server-info".to_string(),
+        options: Some(ResourceOptions {
+            size: None,
+            title: Some("Server description".to_string()),
+            description: Some(
+                "Server metadata and every tool, resource, resource template and prompt"
+                    .to_string(),
+            ),
+            mime_type: Some("application/json".to_string()),
+            annotations: None,
+            meta: None,
+            icons: None,
+        }),
+    }
+}
+
+bindings::export!(Introspection with_types_in bindings);
@@ -0,0 +1,49 @@
+//! Configuration for the key-value admin tools
+//!
+//! Environment variables:
+//! - `WASMCP_KV_ADMIN_SCOPE`: OAuth scope required to use the tools (default: "kv:admin")
+//! - `WASMCP_KV_ADMIN_BUCKET`: Default bucket (default: the caller's session store)
+//! - `WASMCP_KV_ADMIN_READ_ONLY`: "true"/"false" (default: "false") - Hide `kv_set` and `kv_delete`
+//! - `WASMCP_KV_ADMIN_ALLOW_ANONYMOUS`: "true"/"false" (default: "false") - Allow
+//!   requests without an identity (e.g. local stdio debugging)
+
+use crate::bindings::wasi::cli::environment::get_environment;
+use std::collections::HashMap;
+
+const DEFAULT_SCOPE: &str = "kv:admin";
+
+/// Admin tools configuration from environment variables
+#[derive(Debug, Clone)]
+pub struct KvAdminConfig {
+    pub scope: String,
+    pub bucket: Option<String>,
+    pub read_only: bool,
+    pub allow_anonymous: bool,
+}
+
+impl KvAdminConfig {
+    /// Load configuration from environment variables
+    pub fn from_env() -> Self {
+        let env_map: HashMap<String, String> = get_environment().into_iter().collect();
+
+        let flag = |key: &str| {
+            env_map
+                .get(key)
+                .map(|v| v.to_lowercase() == "true")
+                .unwrap_or(false)
+        };
+
+        let scope = env_map
+            .get("WASMCP_KV_ADMIN_SCOPE")
+            .filter(|s| !s.is_empty())
+            .cloned()
+            .unwrap_or_else(|| DEFAULT_SCOPE.to_string());
+
+        KvAdminConfig {
+            scope,
+            bucket: env_map.get("WASMCP_KV_ADMIN_BUCKET").cloned(),
+            read_only: flag("WASMCP_KV_ADMIN_READ_ONLY"),
+            allow_anonymous: flag("WASMCP_KV_ADMIN_ALLOW_ANONYMOUS"),
+        }
+    }
+}
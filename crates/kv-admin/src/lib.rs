@@ -0,0 +1,215 @@
+//! Key-Value Admin Tools Middleware Component
+//!
+//! A reusable middleware that exposes wasmcp:keyvalue buckets as MCP tools for
+//! debugging deployed servers (e.g. inspecting session state). This component:
+//! - Adds `kv_get`, `kv_set`, `kv_delete` and `kv_list` tools
+//! - Reports and accepts the TypedValue type of every value
+//! - Only serves callers whose token carries the admin scope
+//! - Delegates all other requests downstream
+//!
+//! Callers without the scope don't see the tools in tools/list, and calls to
+//! them are refused.
+
+#![allow(warnings)]
+
+mod bindings {
+    wit_bindgen::generate!({
+        world: "kv-admin",
+        generate_all,
+    });
+}
+
+mod config;
+mod tools;
+
+use bindings::exports::wasmcp::mcp_v20251125::server_handler::{Guest, MessageContext};
+use bindings::wasmcp::keyvalue::store as kv;
+use bindings::wasmcp::mcp_v20251125::mcp::*;
+use bindings::wasmcp::mcp_v20251125::server_handler as downstream;
+use config::KvAdminConfig;
+
+struct KvAdmin;
+
+// Convert exported MessageContext to imported MessageContext
+fn to_downstream_ctx<'a>(ctx: &'a MessageContext<'a>) -> downstream::MessageContext<'a> {
+    downstream::MessageContext {
+        client_stream: ctx.client_stream,
+        protocol_version: ctx.protocol_version.clone(),
+        session: ctx.session.as_ref().map(|s| downstream::Session {
+            session_id: s.session_id.clone(),
+            store_id: s.store_id.clone(),
+        }),
+        identity: ctx.identity.as_ref().map(|i| downstream::Identity {
+            jwt: i.jwt.clone(),
+            claims: i.claims.clone(),
+        }),
+        frame: ctx.frame.clone(),
+        http_context: ctx.http_context.clone(),
+        client_capabilities: ctx.client_capabilities.clone(),
+        request_id: ctx.request_id.clone(),
+        deadline: ctx.deadline,
+        log_level: ctx.log_level,
+        client_info: ctx.client_info.clone(),
+        locale: ctx.locale.clone(),
+        trace_context: ctx.trace_context.clone(),
+    }
+}
+
+impl Guest for KvAdmin {
+    fn handle(
+        ctx: MessageContext,
+        message: ClientMessage,
+    ) -> Option<Result<ServerResult, ErrorCode>> {
+        let config = KvAdminConfig::from_env();
+
+        match message {
+            ClientMessage::Request((request_id, request)) => {
+                let names = tools::names(config.read_only);
+                let result = match &request {
+                    ClientRequest::ToolsList(list_req) if is_admin(&ctx, &config) => {
+                        handle_tools_list(request_id.clone(), list_req.clone(), &ctx, names)
+                    }
+                    ClientRequest::ToolsCall(call_req)
+                        if names.contains(&call_req.name.as_str()) =>
+                    {
+                        if !is_admin(&ctx, &config) {
+                            return Some(Ok(tool_result(
+                                format!("Permission denied: requires scope '{}'", config.scope),
+                                None,
+                                true,
+                            )));
+                        }
+                        Ok(handle_tools_call(call_req, &ctx, &config))
+                    }
+                    _ => return delegate(&ctx, request_id, request),
+                };
+                Some(result)
+            }
+            _ => {
+                // Forward notifications, results, errors to downstream
+                downstream::handle(&to_downstream_ctx(&ctx), message)
+            }
+        }
+    }
+}
+
+fn delegate(
+    ctx: &MessageContext,
+    request_id: RequestId,
+    request: ClientRequest,
+) -> Option<Result<ServerResult, ErrorCode>> {
+    let downstream_msg = ClientMessage::Request((request_id, request));
+    downstream::handle(&to_downstream_ctx(ctx), downstream_msg)
+}
+
+/// Whether the caller's token carries the admin scope
+fn is_admin(ctx: &MessageContext, config: &KvAdminConfig) -> bool {
+    match &ctx.identity {
+        Some(identity) => identity.claims.scopes.iter().any(|s| s == &config.scope),
+        None => config.allow_anonymous,
+    }
+}
+
+fn handle_tools_list(
+    request_id: RequestId,
+    req: ListToolsRequest,
+    ctx: &MessageContext,
+    names: &[&str],
+) -> Result<ServerResult, ErrorCode> {
+    let mut tools: Vec<Tool> = names.iter().map(|name| to_tool(name)).collect();
+
+    let downstream_msg = ClientMessage::Request((request_id, ClientRequest::ToolsList(req)));
+    let next_cursor = match downstream::handle(&to_downstream_ctx(ctx), downstream_msg) {
+        Some(Ok(ServerResult::ToolsList(downstream_result))) => {
+            tools.extend(downstream_result.tools);
+            downstream_result.next_cursor
+        }
+        Some(Err(ErrorCode::MethodNotFound(_))) | None | Some(Ok(_)) => None,
+        Some(Err(e)) => return Err(e),
+    };
+
+    Ok(ServerResult::ToolsList(ListToolsResult {
+        tools,
+        next_cursor,
+        meta: None,
+    }))
+}
+
+fn to_tool(name: &str) -> Tool {
+    let (description, input_schema, read_only) = tools::definition(name);
+
+    Tool {
+        name: name.to_string(),
+        input_schema,
+        options: Some(ToolOptions {
+            meta: None,
+            annotations: Some(ToolAnnotations {
+                destructive_hint: Some(!read_only),
+                idempotent_hint: Some(true),
+                open_world_hint: Some(false),
+                read_only_hint: Some(read_only),
+                title: None,
+            }),
+            description: Some(description.to_string()),
+            output_schema: None,
+            title: None,
+            icons: None,
+        }),
+    }
+}
+
+fn handle_tools_call(
+    req: &CallToolRequest,
+    ctx: &MessageContext,
+    config: &KvAdminConfig,
+) -> ServerResult {
+    match call(req, ctx, config) {
+        Ok(output) => tool_result(output.to_string(), Some(output), false),
+        Err(e) => tool_result(e, None, true),
+    }
+}
+
+fn call(
+    req: &CallToolRequest,
+    ctx: &MessageContext,
+    config: &KvAdminConfig,
+) -> Result<serde_json::Value, String> {
+    let args = match req.arguments.as_deref().map(serde_json::from_str) {
+        Some(Ok(serde_json::Value::Object(args))) => args,
+        Some(Ok(serde_json::Value::Null)) | None => serde_json::Map::new(),
+        Some(Ok(_)) => return Err("Arguments must be a JSON object".to_string()),
+        Some(Err(e)) => return Err(format!("Invalid arguments: {}", e)),
+    };
+
+    // Explicit argument, then configured bucket, then the caller's session store
+    let bucket_name = args
+        .get("bucket")
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+        .or_else(|| config.bucket.clone())
+        .or_else(|| ctx.session.as_ref().map(|s| s.store_id.clone()))
+        .unwrap_or_default();
+
+    let bucket = kv::open(&bucket_name)
+        .map_err(|e| format!("Failed to open bucket '{}': {:?}", bucket_name, e))?;
+
+    tools::call(&req.name, &bucket, &args)
+}
+
+fn tool_result(
+    text: String,
+    structured_content: Option<serde_json::Value>,
+    is_error: bool,
+) -> ServerResult {
+    ServerResult::ToolsCall(CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text: TextData::Text(text),
+            options: None,
+        })],
+        is_error: is_error.then_some(true),
+        meta: None,
+        structured_content: structured_content.map(|v| v.to_string()),
+    })
+}
+
+bindings::export!(KvAdmin with_types_in bindings);
@@ -0,0 +1,155 @@
+//! Tool definitions and bucket operations
+//!
+//! Values are exchanged as `{"type": ..., "value": ...}` so the stored
+//! TypedValue tag round-trips:
+//! - `string`, `json`, `u64`, `s64`, `bool` map onto the matching JSON value
+//! - `bytes` is base64-encoded
+
+use crate::bindings::wasmcp::keyvalue::store::{self as kv, TypedValue};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use serde_json::{Map, Value, json};
+
+pub const GET: &str = "kv_get";
+pub const SET: &str = "kv_set";
+pub const DELETE: &str = "kv_delete";
+pub const LIST: &str = "kv_list";
+
+/// Names of the tools exposed in the given mode
+pub fn names(read_only: bool) -> &'static [&'static str] {
+    if read_only {
+        &[GET, LIST]
+    } else {
+        &[GET, SET, DELETE, LIST]
+    }
+}
+
+const BUCKET_PROPERTY: &str = r#""bucket": {"type": "string", "description": "Bucket to open (default: the configured bucket or the caller's session store)"}"#;
+
+/// (description, input schema, read-only) for a tool name
+pub fn definition(name: &str) -> (&'static str, String, bool) {
+    match name {
+        GET => (
+            "Read a key and its stored type",
+            format!(
+                r#"{{"type": "object", "properties": {{"key": {{"type": "string"}}, {}}}, "required": ["key"]}}"#,
+                BUCKET_PROPERTY
+            ),
+            true,
+        ),
+        SET => (
+            "Write a key with an explicit type (string, json, u64, s64, bool, bytes as base64)",
+            format!(
+                r#"{{"type": "object", "properties": {{"key": {{"type": "string"}}, "type": {{"type": "string", "enum": ["string", "json", "u64", "s64", "bool", "bytes"]}}, "value": {{}}, {}}}, "required": ["key", "type", "value"]}}"#,
+                BUCKET_PROPERTY
+            ),
+            false,
+        ),
+        DELETE => (
+            "Delete a key (no error if it does not exist)",
+            format!(
+                r#"{{"type": "object", "properties": {{"key": {{"type": "string"}}, {}}}, "required": ["key"]}}"#,
+                BUCKET_PROPERTY
+            ),
+            false,
+        ),
+        _ => (
+            "List keys, optionally filtered by prefix. Pass the returned cursor to continue.",
+            format!(
+                r#"{{"type": "object", "properties": {{"prefix": {{"type": "string"}}, "cursor": {{"type": "string"}}, {}}}}}"#,
+                BUCKET_PROPERTY
+            ),
+            true,
+        ),
+    }
+}
+
+/// Run a tool against an open bucket, returning structured output
+pub fn call(name: &str, bucket: &kv::Bucket, args: &Map<String, Value>) -> Result<Value, String> {
+    match name {
+        GET => {
+            let key = string_arg(args, "key")?;
+            match bucket.get(key).map_err(kv_error)? {
+                Some(value) => {
+                    let (kind, value) = to_json(value);
+                    Ok(json!({ "key": key, "exists": true, "type": kind, "value": value }))
+                }
+                None => Ok(json!({ "key": key, "exists": false })),
+            }
+        }
+        SET => {
+            let key = string_arg(args, "key")?;
+            let kind = string_arg(args, "type")?;
+            let value = args.get("value").ok_or("Missing 'value' argument")?;
+            bucket
+                .set(key, &from_json(kind, value)?)
+                .map_err(kv_error)?;
+            Ok(json!({ "key": key, "type": kind }))
+        }
+        DELETE => {
+            let key = string_arg(args, "key")?;
+            bucket.delete(key).map_err(kv_error)?;
+            Ok(json!({ "key": key, "deleted": true }))
+        }
+        _ => {
+            let prefix = args.get("prefix").and_then(Value::as_str).unwrap_or("");
+            let cursor = args
+                .get("cursor")
+                .and_then(Value::as_str)
+                .map(str::to_string);
+            let page = bucket.list_keys(cursor.as_deref()).map_err(kv_error)?;
+            let keys: Vec<String> = page
+                .keys
+                .into_iter()
+                .filter(|k| k.starts_with(prefix))
+                .collect();
+            Ok(json!({ "keys": keys, "cursor": page.cursor }))
+        }
+    }
+}
+
+fn string_arg<'a>(args: &'a Map<String, Value>, name: &str) -> Result<&'a str, String> {
+    args.get(name)
+        .and_then(Value::as_str)
+        .ok_or_else(|| format!("Missing '{}' argument", name))
+}
+
+fn to_json(value: TypedValue) -> (&'static str, Value) {
+    match value {
+        TypedValue::AsString(s) => ("string", Value::String(s)),
+        // Invalid JSON can't be written through the store, but show it raw if present
+        TypedValue::AsJson(s) => ("json", serde_json::from_str(&s).unwrap_or(Value::String(s))),
+        TypedValue::AsU64(n) => ("u64", json!(n)),
+        TypedValue::AsS64(n) => ("s64", json!(n)),
+        TypedValue::AsBool(b) => ("bool", json!(b)),
+        TypedValue::AsBytes(bytes) => ("bytes", Value::String(STANDARD.encode(bytes))),
+    }
+}
+
+fn from_json(kind: &str, value: &Value) -> Result<TypedValue, String> {
+    let mismatch = || format!("Value does not match type '{}'", kind);
+    match kind {
+        "string" => value
+            .as_str()
+            .map(|s| TypedValue::AsString(s.to_string()))
+            .ok_or_else(mismatch),
+        "json" => Ok(TypedValue::AsJson(value.to_string())),
+        "u64" => value.as_u64().map(TypedValue::AsU64).ok_or_else(mismatch),
+        "s64" => value.as_i64().map(TypedValue::AsS64).ok_or_else(mismatch),
+        "bool" => value.as_bool().map(TypedValue::AsBool).ok_or_else(mismatch),
+        "bytes" => value
+            .as_str()
+            .and_then(|s| STANDARD.decode(s).ok())
+            .map(TypedValue::AsBytes)
+            .ok_or_else(|| "Value for type 'bytes' must be a base64 string".to_string()),
+        _ => Err(format!("Unknown type '{}'", kind)),
+    }
+}
+
+fn kv_error(e: kv::Error) -> String {
+    match e {
+        kv::Error::NoSuchStore => "No such bucket".to_string(),
+        kv::Error::AccessDenied => "Access to bucket denied".to_string(),
+        kv::Error::Other(msg) => format!("Store error: {}", msg),
+    }
+}
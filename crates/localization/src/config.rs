@@ -0,0 +1,98 @@
+//! Configuration for the localization middleware
+//!
+//! Environment variables:
+//! - `WASMCP_LOCALIZATION`: Translations as a JSON catalog (required), e.g.
+//!   `{"tools": {"add": {"description": {"en": "Add numbers", "de": "Zahlen addieren"}}}}`
+//! - `WASMCP_DEFAULT_LOCALE`: Locale used when the client's has no
+//!   translation (default: "en")
+//!
+//! The catalog has four sections, each keyed by the item's identifier:
+//! `tools` and `prompts` by name, `resources` by URI and `resourceTemplates`
+//! by URI template. Every entry may localize `title` and `description`.
+//! Prompt entries may also localize their `arguments` (by argument name) and
+//! the text of their `messages`.
+
+use crate::bindings::wasi::cli::environment::get_environment;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+const DEFAULT_LOCALE: &str = "en";
+
+/// One text in several locales, keyed by BCP 47 tag
+pub type Localized = HashMap<String, String>;
+
+/// Localized title and description of a tool, resource or argument
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Strings {
+    #[serde(default)]
+    pub title: Option<Localized>,
+    #[serde(default)]
+    pub description: Option<Localized>,
+}
+
+/// Localized strings and content of a prompt
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PromptStrings {
+    #[serde(flatten)]
+    pub strings: Strings,
+    #[serde(default)]
+    pub arguments: HashMap<String, Strings>,
+    /// Locale -> text of each text message, in order
+    ///
+    /// `{argument}` placeholders are filled from the prompts/get arguments.
+    #[serde(default)]
+    pub messages: HashMap<String, Vec<String>>,
+}
+
+/// Translations for everything the composed handlers list
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Catalog {
+    #[serde(default)]
+    pub tools: HashMap<String, Strings>,
+    #[serde(default)]
+    pub prompts: HashMap<String, PromptStrings>,
+    #[serde(default)]
+    pub resources: HashMap<String, Strings>,
+    #[serde(default)]
+    pub resource_templates: HashMap<String, Strings>,
+}
+
+/// Localization configuration from environment variables
+#[derive(Debug, Clone)]
+pub struct LocalizationConfig {
+    pub catalog: Catalog,
+    pub default_locale: String,
+}
+
+impl LocalizationConfig {
+    /// Load configuration from environment variables
+    pub fn from_env() -> Self {
+        let env_map: HashMap<String, String> = get_environment().into_iter().collect();
+
+        let catalog = env_map
+            .get("WASMCP_LOCALIZATION")
+            .filter(|v| !v.trim().is_empty())
+            .map(|v| {
+                serde_json::from_str(v).unwrap_or_else(|e| {
+                    eprintln!(
+                        "[localization] WARNING: Invalid WASMCP_LOCALIZATION JSON: {}. Nothing is localized.",
+                        e
+                    );
+                    Catalog::default()
+                })
+            })
+            .unwrap_or_default();
+
+        let default_locale = env_map
+            .get("WASMCP_DEFAULT_LOCALE")
+            .filter(|v| !v.trim().is_empty())
+            .cloned()
+            .unwrap_or_else(|| DEFAULT_LOCALE.to_string());
+
+        LocalizationConfig {
+            catalog,
+            default_locale,
+        }
+    }
+}
@@ -0,0 +1,280 @@
+//! Localization Middleware Component
+//!
+//! Serves titles, descriptions and prompt content in the client's language.
+//! This component:
+//! - Replaces the title and description of listed tools, prompts (and their
+//!   arguments), resources and resource templates with the best translation
+//!   for the client's locale
+//! - Replaces the description and message text of prompts/get results
+//! - Keeps the downstream text when nothing matches the client's locale or
+//!   the default locale
+//! - Delegates all other requests downstream
+//!
+//! The client's locale is `MessageContext.locale`, which the transport takes
+//! from initialize `_meta.locale` or the Accept-Language header.
+//! Translations come from `WASMCP_LOCALIZATION`; see `config.rs`.
+
+#![allow(warnings)]
+
+mod bindings {
+    wit_bindgen::generate!({
+        world: "localization",
+        generate_all,
+    });
+}
+
+mod config;
+mod negotiate;
+
+use bindings::exports::wasmcp::mcp_v20251125::server_handler::{Guest, MessageContext};
+use bindings::wasmcp::mcp_v20251125::mcp::*;
+use bindings::wasmcp::mcp_v20251125::server_handler as downstream;
+use config::{LocalizationConfig, Localized, Strings};
+use std::collections::HashMap;
+
+struct Localization;
+
+// Convert exported MessageContext to imported MessageContext
+fn to_downstream_ctx<'a>(ctx: &'a MessageContext<'a>) -> downstream::MessageContext<'a> {
+    downstream::MessageContext {
+        client_stream: ctx.client_stream,
+        protocol_version: ctx.protocol_version.clone(),
+        session: ctx.session.as_ref().map(|s| downstream::Session {
+            session_id: s.session_id.clone(),
+            store_id: s.store_id.clone(),
+        }),
+        identity: ctx.identity.as_ref().map(|i| downstream::Identity {
+            jwt: i.jwt.clone(),
+            claims: i.claims.clone(),
+        }),
+        frame: ctx.frame.clone(),
+        http_context: ctx.http_context.clone(),
+        client_capabilities: ctx.client_capabilities.clone(),
+        request_id: ctx.request_id.clone(),
+        deadline: ctx.deadline,
+        log_level: ctx.log_level,
+        client_info: ctx.client_info.clone(),
+        locale: ctx.locale.clone(),
+        trace_context: ctx.trace_context.clone(),
+    }
+}
+
+impl Guest for Localization {
+    fn handle(
+        ctx: MessageContext,
+        message: ClientMessage,
+    ) -> Option<Result<ServerResult, ErrorCode>> {
+        let ClientMessage::Request((request_id, request)) = message else {
+            // Forward notifications, results, errors to downstream
+            return downstream::handle(&to_downstream_ctx(&ctx), message);
+        };
+
+        let prompt_arguments = match &request {
+            ClientRequest::PromptsGet(req) => req.arguments.clone(),
+            _ => None,
+        };
+        let prompt_name = match &request {
+            ClientRequest::PromptsGet(req) => Some(req.name.clone()),
+            _ => None,
+        };
+
+        let downstream_msg = ClientMessage::Request((request_id, request));
+        let response = downstream::handle(&to_downstream_ctx(&ctx), downstream_msg);
+        let Some(Ok(mut result)) = response else {
+            return response;
+        };
+
+        let config = LocalizationConfig::from_env();
+        let translator = Translator {
+            locale: ctx.locale.as_deref(),
+            default_locale: &config.default_locale,
+        };
+        let catalog = &config.catalog;
+
+        match &mut result {
+            ServerResult::ToolsList(list) => {
+                for tool in &mut list.tools {
+                    localize_tool(tool, catalog.tools.get(&tool.name), &translator);
+                }
+            }
+            ServerResult::PromptsList(list) => {
+                for prompt in &mut list.prompts {
+                    localize_prompt(prompt, &config, &translator);
+                }
+            }
+            ServerResult::PromptsGet(get) => {
+                if let Some(strings) = prompt_name.and_then(|name| catalog.prompts.get(&name)) {
+                    localize_prompt_content(get, strings, prompt_arguments.as_deref(), &translator);
+                }
+            }
+            ServerResult::ResourcesList(list) => {
+                for resource in &mut list.resources {
+                    localize_resource(resource, catalog.resources.get(&resource.uri), &translator);
+                }
+            }
+            ServerResult::ResourcesTemplatesList(list) => {
+                for template in &mut list.resource_templates {
+                    localize_template(
+                        template,
+                        catalog.resource_templates.get(&template.uri_template),
+                        &translator,
+                    );
+                }
+            }
+            _ => {}
+        }
+
+        Some(Ok(result))
+    }
+}
+
+/// Picks translations for the client's locale
+struct Translator<'a> {
+    locale: Option<&'a str>,
+    default_locale: &'a str,
+}
+
+impl Translator<'_> {
+    fn pick<'t, T>(&self, entries: &'t HashMap<String, T>) -> Option<&'t T> {
+        negotiate::best_match(entries, self.locale, self.default_locale)
+    }
+
+    fn text(&self, texts: Option<&Localized>) -> Option<String> {
+        texts.and_then(|t| self.pick(t)).cloned()
+    }
+
+    /// Translated (title, description), if either has a translation
+    fn strings(&self, strings: Option<&Strings>) -> Option<(Option<String>, Option<String>)> {
+        let strings = strings?;
+        let title = self.text(strings.title.as_ref());
+        let description = self.text(strings.description.as_ref());
+        (title.is_some() || description.is_some()).then_some((title, description))
+    }
+}
+
+fn localize_tool(tool: &mut Tool, strings: Option<&Strings>, translator: &Translator) {
+    let Some((title, description)) = translator.strings(strings) else {
+        return;
+    };
+    let options = tool.options.get_or_insert_with(|| ToolOptions {
+        meta: None,
+        annotations: None,
+        description: None,
+        output_schema: None,
+        title: None,
+        icons: None,
+    });
+    replace(&mut options.title, title);
+    replace(&mut options.description, description);
+}
+
+fn localize_prompt(prompt: &mut Prompt, config: &LocalizationConfig, translator: &Translator) {
+    let Some(strings) = config.catalog.prompts.get(&prompt.name) else {
+        return;
+    };
+    let options = prompt.options.get_or_insert_with(|| PromptOptions {
+        meta: None,
+        arguments: None,
+        description: None,
+        title: None,
+        icons: None,
+    });
+
+    if let Some((title, description)) = translator.strings(Some(&strings.strings)) {
+        replace(&mut options.title, title);
+        replace(&mut options.description, description);
+    }
+    for argument in options.arguments.iter_mut().flatten() {
+        if let Some((title, description)) =
+            translator.strings(strings.arguments.get(&argument.name))
+        {
+            replace(&mut argument.title, title);
+            replace(&mut argument.description, description);
+        }
+    }
+}
+
+/// Localize a prompts/get result
+///
+/// Translated messages replace the text of the result's text messages in
+/// order, so roles and non-text content stay as downstream produced them.
+fn localize_prompt_content(
+    result: &mut GetPromptResult,
+    strings: &config::PromptStrings,
+    arguments: Option<&str>,
+    translator: &Translator,
+) {
+    replace(
+        &mut result.description,
+        translator.text(strings.strings.description.as_ref()),
+    );
+
+    let Some(templates) = translator.pick(&strings.messages) else {
+        return;
+    };
+    let texts = result
+        .messages
+        .iter_mut()
+        .filter_map(|message| match &mut message.content {
+            ContentBlock::Text(TextContent {
+                text: TextData::Text(text),
+                ..
+            }) => Some(text),
+            _ => None,
+        });
+    for (text, template) in texts.zip(templates) {
+        *text = negotiate::fill(template, arguments);
+    }
+}
+
+fn localize_resource(
+    resource: &mut McpResource,
+    strings: Option<&Strings>,
+    translator: &Translator,
+) {
+    let Some((title, description)) = translator.strings(strings) else {
+        return;
+    };
+    let options = resource.options.get_or_insert_with(|| ResourceOptions {
+        size: None,
+        title: None,
+        description: None,
+        mime_type: None,
+        annotations: None,
+        meta: None,
+        icons: None,
+    });
+    replace(&mut options.title, title);
+    replace(&mut options.description, description);
+}
+
+fn localize_template(
+    template: &mut ResourceTemplate,
+    strings: Option<&Strings>,
+    translator: &Translator,
+) {
+    let Some((title, description)) = translator.strings(strings) else {
+        return;
+    };
+    let options = template
+        .options
+        .get_or_insert_with(|| ResourceTemplateOptions {
+            description: None,
+            title: None,
+            mime_type: None,
+            annotations: None,
+            meta: None,
+            icons: None,
+        });
+    replace(&mut options.title, title);
+    replace(&mut options.description, description);
+}
+
+/// Keep the downstream text unless there is a translation
+fn replace(field: &mut Option<String>, translation: Option<String>) {
+    if translation.is_some() {
+        *field = translation;
+    }
+}
+
+bindings::export!(Localization with_types_in bindings);
@@ -0,0 +1,114 @@
+//! Locale negotiation and prompt message templates
+
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// The entry for the best locale match, with fallback
+///
+/// Tries the client's locale, then the default locale. Each is matched
+/// exactly (case-insensitive), then with subtags removed from the end
+/// ("zh-Hant-TW", "zh-Hant", "zh"), then against any entry of the same
+/// language ("de" matches "de-DE").
+pub fn best_match<'a, T>(
+    entries: &'a HashMap<String, T>,
+    locale: Option<&str>,
+    default_locale: &str,
+) -> Option<&'a T> {
+    locale
+        .and_then(|locale| lookup(entries, locale))
+        .or_else(|| lookup(entries, default_locale))
+}
+
+fn lookup<'a, T>(entries: &'a HashMap<String, T>, locale: &str) -> Option<&'a T> {
+    let find = |tag: &str| {
+        entries
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(tag))
+            .map(|(_, value)| value)
+    };
+
+    let mut tag = locale.trim();
+    loop {
+        if let Some(found) = find(tag) {
+            return Some(found);
+        }
+        match tag.rfind('-') {
+            Some(i) => tag = &tag[..i],
+            None => break,
+        }
+    }
+
+    // Same language, different region or script (pick deterministically)
+    let language = tag.to_ascii_lowercase();
+    entries
+        .iter()
+        .filter(|(key, _)| {
+            key.split('-')
+                .next()
+                .is_some_and(|l| l.eq_ignore_ascii_case(&language))
+        })
+        .min_by(|(a, _), (b, _)| a.cmp(b))
+        .map(|(_, value)| value)
+}
+
+/// Fill `{name}` placeholders from JSON-encoded prompt arguments
+///
+/// Unknown placeholders are left as they are.
+pub fn fill(template: &str, arguments: Option<&str>) -> String {
+    let Some(Value::Object(arguments)) = arguments.and_then(|a| serde_json::from_str(a).ok())
+    else {
+        return template.to_string();
+    };
+
+    let mut text = template.to_string();
+    for (name, value) in &arguments {
+        let value = match value {
+            Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+        text = text.replace(&format!("{{{}}}", name), &value);
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries(tags: &[&str]) -> HashMap<String, String> {
+        tags.iter()
+            .map(|t| (t.to_string(), t.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_best_match() {
+        let e = entries(&["en", "de-DE", "de-AT", "zh-Hant", "pt"]);
+        let best = |locale: Option<&str>| best_match(&e, locale, "en").map(String::as_str);
+
+        assert_eq!(best(Some("de-AT")), Some("de-AT"));
+        assert_eq!(best(Some("DE-at")), Some("de-AT"));
+        assert_eq!(best(Some("de-CH")), Some("de-AT"));
+        assert_eq!(best(Some("de")), Some("de-AT"));
+        assert_eq!(best(Some("zh-Hant-TW")), Some("zh-Hant"));
+        assert_eq!(best(Some("pt-BR")), Some("pt"));
+        assert_eq!(best(Some("fr")), Some("en"));
+        assert_eq!(best(None), Some("en"));
+
+        let no_default = entries(&["de"]);
+        assert_eq!(best_match(&no_default, Some("fr"), "en"), None);
+    }
+
+    #[test]
+    fn test_fill() {
+        assert_eq!(
+            fill(
+                "Hallo {name}, du bist {age}!",
+                Some(r#"{"name": "Ada", "age": 36}"#)
+            ),
+            "Hallo Ada, du bist 36!"
+        );
+        assert_eq!(fill("Hallo {name}", None), "Hallo {name}");
+        assert_eq!(fill("Hallo {name}", Some("{}")), "Hallo {name}");
+    }
+}
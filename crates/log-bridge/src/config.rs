@@ -0,0 +1,84 @@
+//! Configuration for the logging bridge
+//!
+//! Environment variables:
+//! - `WASMCP_LOG_TARGET`: "stdio" or "stderr" (default: "stderr") - Where records go
+//! - `WASMCP_LOG_LEVEL`: Least severe level forwarded (default: "info")
+
+use crate::bindings::exports::wasi::logging::logging::Level;
+use crate::bindings::wasi::cli::environment::get_environment;
+use std::collections::HashMap;
+
+/// Where log records are written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Target {
+    /// Plain lines on stderr (default)
+    #[default]
+    Stderr,
+    /// notifications/message on stdout, the message stream of the stdio transport
+    Stdio,
+}
+
+/// Logging bridge configuration from environment variables
+#[derive(Debug, Clone)]
+pub struct LogBridgeConfig {
+    pub target: Target,
+    /// Records below this severity are dropped
+    pub min_level: u8,
+}
+
+impl LogBridgeConfig {
+    /// Load configuration from environment variables
+    pub fn from_env() -> Self {
+        let env_map: HashMap<String, String> = get_environment().into_iter().collect();
+
+        let target = match env_map
+            .get("WASMCP_LOG_TARGET")
+            .map(|v| v.to_lowercase())
+            .as_deref()
+        {
+            None | Some("stderr") => Target::Stderr,
+            Some("stdio") => Target::Stdio,
+            Some(other) => {
+                eprintln!(
+                    "[log-bridge] WARNING: Invalid WASMCP_LOG_TARGET='{}', defaulting to 'stderr'. \
+                     Valid values: 'stdio', 'stderr'",
+                    other
+                );
+                Target::Stderr
+            }
+        };
+
+        let min_level = env_map
+            .get("WASMCP_LOG_LEVEL")
+            .and_then(|v| parse_level(v))
+            .map(|level| severity(&level))
+            .unwrap_or_else(|| severity(&Level::Info));
+
+        LogBridgeConfig { target, min_level }
+    }
+}
+
+/// Parse a wasi:logging level name (case-insensitive)
+fn parse_level(name: &str) -> Option<Level> {
+    match name.to_lowercase().as_str() {
+        "trace" => Some(Level::Trace),
+        "debug" => Some(Level::Debug),
+        "info" => Some(Level::Info),
+        "warn" | "warning" => Some(Level::Warn),
+        "error" => Some(Level::Error),
+        "critical" => Some(Level::Critical),
+        _ => None,
+    }
+}
+
+/// Numeric severity, least severe first
+pub fn severity(level: &Level) -> u8 {
+    match level {
+        Level::Trace => 0,
+        Level::Debug => 1,
+        Level::Info => 2,
+        Level::Warn => 3,
+        Level::Error => 4,
+        Level::Critical => 5,
+    }
+}
@@ -0,0 +1,110 @@
+//! Logging Bridge Component
+//!
+//! Implements `wasi:logging/logging` so tool components can log through the
+//! standard `log`/`tracing` facades and have the records reach the MCP client.
+//! This component:
+//! - Forwards records as `notifications/message` when the message stream is
+//!   reachable (stdio transport, where the stream is stdout)
+//! - Writes plain lines to stderr otherwise
+//! - Drops records below `WASMCP_LOG_LEVEL`
+//!
+//! The bridge is not a middleware. A tool calling back into a component that
+//! is already on the call stack would trap (components can't be re-entered),
+//! so the bridge can't borrow the request's stream from the handler chain.
+//! Over HTTP, handlers that need logs on the SSE stream should send
+//! notifications through `ctx.client_stream` instead.
+
+#![allow(warnings)]
+
+mod bindings {
+    wit_bindgen::generate!({
+        world: "log-bridge",
+        generate_all,
+    });
+}
+
+mod config;
+
+use bindings::exports::wasi::logging::logging::{Guest, Level};
+use bindings::wasi::cli::{stderr, stdout};
+use bindings::wasmcp::mcp_v20251125::mcp::{
+    LogLevel, LoggingMessageNotification, ServerMessage, ServerNotification,
+};
+use bindings::wasmcp::mcp_v20251125::server_io::{self, MessageFrame};
+use config::{LogBridgeConfig, Target};
+
+struct LogBridge;
+
+impl Guest for LogBridge {
+    fn log(level: Level, context: String, message: String) {
+        let config = LogBridgeConfig::from_env();
+        if config::severity(&level) < config.min_level {
+            return;
+        }
+
+        match config.target {
+            Target::Stdio => {
+                let notification = ServerNotification::Log(LoggingMessageNotification {
+                    data: message.clone(),
+                    level: to_mcp_level(&level),
+                    logger: (!context.is_empty()).then(|| context.clone()),
+                });
+                let stream = stdout::get_stdout();
+                // Fall back to stderr rather than lose the record
+                if server_io::send_message(
+                    &stream,
+                    ServerMessage::Notification(notification),
+                    &stdio_frame(),
+                )
+                .is_err()
+                {
+                    write_stderr(&level, &context, &message);
+                }
+            }
+            Target::Stderr => write_stderr(&level, &context, &message),
+        }
+    }
+}
+
+/// Newline-delimited JSON, matching the stdio transport
+fn stdio_frame() -> MessageFrame {
+    MessageFrame {
+        prefix: vec![],
+        suffix: b"\n".to_vec(),
+    }
+}
+
+fn write_stderr(level: &Level, context: &str, message: &str) {
+    let line = if context.is_empty() {
+        format!("[{}] {}\n", level_name(level), message)
+    } else {
+        format!("[{}] {}: {}\n", level_name(level), context, message)
+    };
+    let _ = stderr::get_stderr().blocking_write_and_flush(line.as_bytes());
+}
+
+/// Map wasi:logging levels onto MCP (RFC 5424) levels
+///
+/// MCP has no trace level, so trace records are sent as debug.
+fn to_mcp_level(level: &Level) -> LogLevel {
+    match level {
+        Level::Trace | Level::Debug => LogLevel::Debug,
+        Level::Info => LogLevel::Info,
+        Level::Warn => LogLevel::Warning,
+        Level::Error => LogLevel::Error,
+        Level::Critical => LogLevel::Critical,
+    }
+}
+
+fn level_name(level: &Level) -> &'static str {
+    match level {
+        Level::Trace => "TRACE",
+        Level::Debug => "DEBUG",
+        Level::Info => "INFO",
+        Level::Warn => "WARN",
+        Level::Error => "ERROR",
+        Level::Critical => "CRITICAL",
+    }
+}
+
+bindings::export!(LogBridge with_types_in bindings);
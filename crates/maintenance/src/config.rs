@@ -0,0 +1,204 @@
+//! Configuration for the maintenance runner
+//!
+//! Environment variables:
+//! - `WASMCP_MAINTENANCE_SCHEDULE`: Jobs and intervals, e.g.
+//!   `session-gc=1h,kv-ttl=1h,jwks-refresh=4m` (default: all three at those intervals)
+//! - `WASMCP_MAINTENANCE_BUCKET`: Bucket holding sessions, caches and the
+//!   time each job last ran (default: "", the session store)
+//! - `WASMCP_MAINTENANCE_TTL_RULES`: Cache key prefixes swept by `kv-ttl`,
+//!   each optionally with a maximum age, e.g. `oauth-jwks:,graphql-tools:schema:=1d`
+//!   (default: "oauth-jwks:")
+//! - `WASMCP_MAINTENANCE_TIMEOUT_MS`: Time allowed for each JWKS fetch (default: "10000")
+//! - `JWT_JWKS_URI`, `JWT_TRUSTED_ISSUERS`: Key sets refreshed by `jwks-refresh`,
+//!   read as the authorization component reads them
+//! - `JWT_JWKS_TTL`: Seconds a refreshed key set stays cached (default: "300")
+//! - `MCP_SESSION_BUCKET`, `MCP_KV_BUCKET`: Bucket the authorization
+//!   component caches key sets in (default: "default")
+
+use crate::bindings::wasi::cli::environment::get_environment;
+use crate::schedule::{self, Job};
+use serde_json::Value;
+use std::collections::HashMap;
+
+const DEFAULT_TTL_RULES: &str = "oauth-jwks:";
+const DEFAULT_TIMEOUT_MS: u64 = 10_000;
+const DEFAULT_JWKS_TTL_SECS: u64 = 300;
+const DEFAULT_JWKS_BUCKET: &str = "default";
+
+/// Cache entries swept by `kv-ttl`
+#[derive(Debug, Clone, PartialEq)]
+pub struct TtlRule {
+    pub prefix: String,
+    /// Entries older than this (by `stored_at`) are deleted, in seconds
+    pub max_age: Option<u64>,
+}
+
+/// Maintenance configuration from environment variables
+#[derive(Debug, Clone)]
+pub struct MaintenanceConfig {
+    pub schedule: Vec<(Job, u64)>,
+    pub bucket: String,
+    pub ttl_rules: Vec<TtlRule>,
+    pub timeout_ms: u64,
+    pub jwks_uris: Vec<String>,
+    pub jwks_ttl_secs: u64,
+    pub jwks_bucket: String,
+}
+
+impl MaintenanceConfig {
+    /// Load configuration from environment variables
+    pub fn from_env() -> Self {
+        let env_map: HashMap<String, String> = get_environment().into_iter().collect();
+
+        let table = env_map
+            .get("WASMCP_MAINTENANCE_SCHEDULE")
+            .map(String::as_str)
+            .unwrap_or(schedule::DEFAULT_SCHEDULE);
+        let (schedule, warnings) = schedule::parse_schedule(table);
+        for warning in warnings {
+            eprintln!(
+                "[maintenance] WARNING: WASMCP_MAINTENANCE_SCHEDULE: {}. Ignoring.",
+                warning
+            );
+        }
+
+        let ttl_rules = parse_ttl_rules(
+            env_map
+                .get("WASMCP_MAINTENANCE_TTL_RULES")
+                .map(String::as_str)
+                .unwrap_or(DEFAULT_TTL_RULES),
+        );
+
+        let number = |key: &str, default: u64| match env_map.get(key) {
+            Some(v) => v.trim().parse().unwrap_or_else(|_| {
+                eprintln!(
+                    "[maintenance] WARNING: Invalid {}: {}. Using {}.",
+                    key, v, default
+                );
+                default
+            }),
+            None => default,
+        };
+
+        let jwks_bucket = env_map
+            .get("MCP_SESSION_BUCKET")
+            .or_else(|| env_map.get("MCP_KV_BUCKET"))
+            .cloned()
+            .unwrap_or_else(|| DEFAULT_JWKS_BUCKET.to_string());
+
+        MaintenanceConfig {
+            schedule,
+            bucket: env_map
+                .get("WASMCP_MAINTENANCE_BUCKET")
+                .cloned()
+                .unwrap_or_default(),
+            ttl_rules,
+            timeout_ms: number("WASMCP_MAINTENANCE_TIMEOUT_MS", DEFAULT_TIMEOUT_MS),
+            jwks_uris: jwks_uris(
+                env_map.get("JWT_JWKS_URI").map(String::as_str),
+                env_map.get("JWT_TRUSTED_ISSUERS").map(String::as_str),
+            ),
+            jwks_ttl_secs: number("JWT_JWKS_TTL", DEFAULT_JWKS_TTL_SECS),
+            jwks_bucket,
+        }
+    }
+}
+
+/// Parse `prefix[=max-age]` rules
+pub fn parse_ttl_rules(rules: &str) -> Vec<TtlRule> {
+    rules
+        .split(',')
+        .map(str::trim)
+        .filter(|r| !r.is_empty())
+        .filter_map(|rule| match rule.rsplit_once('=') {
+            Some((prefix, age)) => match schedule::parse_interval(age.trim()) {
+                Some(max_age) => Some(TtlRule {
+                    prefix: prefix.trim().to_string(),
+                    max_age: Some(max_age),
+                }),
+                None => {
+                    eprintln!(
+                        "[maintenance] WARNING: Invalid max age in WASMCP_MAINTENANCE_TTL_RULES: {}. Ignoring.",
+                        rule
+                    );
+                    None
+                }
+            },
+            None => Some(TtlRule {
+                prefix: rule.to_string(),
+                max_age: None,
+            }),
+        })
+        .collect()
+}
+
+/// Key set URIs the authorization component fetches
+///
+/// `JWT_TRUSTED_ISSUERS` is a JSON array of providers; those with a
+/// `jwks_uri` are included.
+pub fn jwks_uris(primary: Option<&str>, trusted_issuers: Option<&str>) -> Vec<String> {
+    let mut uris: Vec<String> = primary
+        .filter(|u| !u.is_empty())
+        .map(str::to_string)
+        .into_iter()
+        .collect();
+
+    if let Some(json) = trusted_issuers {
+        match serde_json::from_str::<Vec<Value>>(json) {
+            Ok(issuers) => {
+                for uri in issuers
+                    .iter()
+                    .filter_map(|issuer| issuer.get("jwks_uri")?.as_str())
+                {
+                    if !uris.iter().any(|u| u == uri) {
+                        uris.push(uri.to_string());
+                    }
+                }
+            }
+            Err(e) => eprintln!(
+                "[maintenance] WARNING: Invalid JWT_TRUSTED_ISSUERS: {}. Ignoring.",
+                e
+            ),
+        }
+    }
+
+    uris
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ttl_rules() {
+        assert_eq!(
+            parse_ttl_rules("oauth-jwks:, graphql-tools:schema:=1d,bad:=soon"),
+            [
+                TtlRule {
+                    prefix: "oauth-jwks:".to_string(),
+                    max_age: None
+                },
+                TtlRule {
+                    prefix: "graphql-tools:schema:".to_string(),
+                    max_age: Some(86400)
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_jwks_uris() {
+        let trusted = r#"[
+            {"issuer": "https://staging.example.com", "jwks_uri": "https://staging.example.com/jwks"},
+            {"issuer": "https://partner.example.com", "public_key": "-----BEGIN PUBLIC KEY-----"}
+        ]"#;
+        assert_eq!(
+            jwks_uris(Some("https://auth.example.com/jwks"), Some(trusted)),
+            [
+                "https://auth.example.com/jwks",
+                "https://staging.example.com/jwks"
+            ]
+        );
+        assert!(jwks_uris(Some(""), Some("not json")).is_empty());
+    }
+}
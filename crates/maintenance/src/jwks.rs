@@ -0,0 +1,80 @@
+//! JWKS refresh
+//!
+//! The authorization component fetches a key set when its cached copy has
+//! expired, so the first request after each expiry pays for the fetch and
+//! fails if the identity provider is briefly down. Refreshing on a schedule
+//! shorter than `JWT_JWKS_TTL` keeps the cache warm: this job writes the
+//! same entry authorization reads, `oauth-jwks:{uri}` holding the key set
+//! and its `expires_at`.
+
+use crate::bindings::wasi::clocks::monotonic_clock;
+use crate::bindings::wasmcp::keyvalue::store::{self as kv, TypedValue};
+use crate::config::MaintenanceConfig;
+use serde_json::{Value, json};
+use wasmcp_http_client::Request;
+
+/// Largest key set accepted (1MB)
+const MAX_JWKS_BYTES: u64 = 1024 * 1024;
+
+/// Refresh every configured key set, returning how many were refreshed
+///
+/// Every URI is tried; the first failure is returned after the rest.
+pub fn refresh(config: &MaintenanceConfig, now: u64) -> Result<usize, String> {
+    if config.jwks_uris.is_empty() {
+        return Ok(0);
+    }
+    let bucket = kv::open(&config.jwks_bucket)
+        .map_err(|e| format!("Failed to open bucket '{}': {}", config.jwks_bucket, e))?;
+
+    let mut refreshed = 0;
+    let mut first_error = None;
+    for uri in &config.jwks_uris {
+        let result = fetch_jwks(uri, config.timeout_ms).and_then(|jwks| {
+            let cached = cache_entry(jwks, now, config.jwks_ttl_secs);
+            bucket
+                .set(
+                    &format!("oauth-jwks:{}", uri),
+                    &TypedValue::AsJson(cached.to_string()),
+                )
+                .map_err(|e| format!("Failed to cache {}: {}", uri, e))
+        });
+        match result {
+            Ok(()) => refreshed += 1,
+            Err(e) => {
+                eprintln!("[maintenance] WARNING: {}", e);
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+    match first_error {
+        Some(e) => Err(e),
+        None => Ok(refreshed),
+    }
+}
+
+/// Fetch and check a key set
+fn fetch_jwks(uri: &str, timeout_ms: u64) -> Result<Value, String> {
+    let deadline = monotonic_clock::now().saturating_add(timeout_ms.saturating_mul(1_000_000));
+    let response = crate::wasi_client("maintenance", Some(deadline))
+        .with_max_response_bytes(MAX_JWKS_BYTES)
+        .send(Request::new("GET", uri).with_header("accept", "application/json"))
+        .map_err(|e| e.to_string())?;
+    if response.status != 200 {
+        return Err(format!(
+            "JWKS fetch from {} failed with HTTP {}",
+            uri, response.status
+        ));
+    }
+    let jwks: Value = serde_json::from_slice(&response.body)
+        .map_err(|e| format!("Invalid JWKS from {}: {}", uri, e))?;
+    // Never replace a working cached key set with one authorization can't use
+    match jwks["keys"].as_array() {
+        Some(keys) if !keys.is_empty() => Ok(jwks),
+        _ => Err(format!("JWKS from {} has no keys", uri)),
+    }
+}
+
+/// Cache entry in the shape authorization reads
+fn cache_entry(jwks: Value, now: u64, ttl_secs: u64) -> Value {
+    json!({ "jwks": jwks, "expires_at": now.saturating_add(ttl_secs) })
+}
@@ -0,0 +1,151 @@
+//! Maintenance Component
+//!
+//! Runs the housekeeping jobs a deployed server needs, as a `wasi:cli/run`
+//! command started by the host's scheduler (cron, a Kubernetes CronJob,
+//! `wasmtime run` in a loop). This component:
+//! - Reads the schedule table from `WASMCP_MAINTENANCE_SCHEDULE`
+//! - Runs each job whose interval has elapsed since it last succeeded,
+//!   recording the time in key-value storage so runs can be frequent and
+//!   stateless
+//! - Runs the jobs named on the command line immediately, whatever the
+//!   schedule
+//!
+//! Jobs:
+//! - `session-gc`: Delete terminated, expired and orphaned sessions
+//! - `kv-ttl`: Delete expired cache entries under configured prefixes
+//! - `jwks-refresh`: Fetch JWKS ahead of their cache expiry
+//!
+//! The run fails if any job fails; failed jobs are retried on the next run.
+
+#![allow(warnings)]
+
+mod bindings {
+    wit_bindgen::generate!({
+        world: "maintenance",
+        generate_all,
+    });
+}
+
+mod config;
+mod jwks;
+mod schedule;
+mod sessions;
+mod store;
+mod ttl;
+
+use bindings::exports::wasi::cli::run::Guest;
+use bindings::wasi::cli::environment::get_arguments;
+use bindings::wasmcp::keyvalue::store::{self as kv, Bucket};
+use config::MaintenanceConfig;
+use schedule::Job;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+wasmcp_http_client::impl_wasi_transport!(crate::bindings::wasi);
+
+/// KV key prefix for the time each job last succeeded (Unix seconds)
+const LAST_RUN_PREFIX: &str = "wasmcp:maintenance:last-run:";
+
+struct Maintenance;
+
+impl Guest for Maintenance {
+    fn run() -> Result<(), ()> {
+        let config = MaintenanceConfig::from_env();
+
+        // Job names on the command line run now, whatever the schedule
+        let mut forced = Vec::new();
+        for name in get_arguments().into_iter().skip(1) {
+            match Job::from_name(&name) {
+                Some(job) => forced.push(job),
+                None => {
+                    eprintln!(
+                        "[maintenance] ERROR: Unknown job '{}'. Jobs: session-gc, kv-ttl, jwks-refresh",
+                        name
+                    );
+                    return Err(());
+                }
+            }
+        }
+
+        let bucket = match kv::open(&config.bucket) {
+            Ok(bucket) => bucket,
+            Err(e) => {
+                eprintln!(
+                    "[maintenance] ERROR: Failed to open bucket '{}': {}",
+                    config.bucket, e
+                );
+                return Err(());
+            }
+        };
+
+        let now = now_secs();
+        let mut failed = false;
+        for job in Job::ALL {
+            let last_run_key = format!("{}{}", LAST_RUN_PREFIX, job.name());
+            let due = if forced.is_empty() {
+                config
+                    .schedule
+                    .iter()
+                    .find(|(j, _)| *j == job)
+                    .is_some_and(|&(_, interval)| {
+                        let last_run = bucket.get_u64(&last_run_key).ok().flatten();
+                        schedule::is_due(last_run, interval, now)
+                    })
+            } else {
+                forced.contains(&job)
+            };
+            if !due {
+                continue;
+            }
+
+            match run_job(job, &config, &bucket, now) {
+                Ok(summary) => {
+                    eprintln!("[maintenance] {}: {}", job.name(), summary);
+                    if let Err(e) = bucket.set_u64(&last_run_key, now) {
+                        eprintln!(
+                            "[maintenance] WARNING: Failed to record {} run: {}",
+                            job.name(),
+                            e
+                        );
+                    }
+                }
+                Err(e) => {
+                    eprintln!("[maintenance] ERROR: {}: {}", job.name(), e);
+                    failed = true;
+                }
+            }
+        }
+
+        if failed { Err(()) } else { Ok(()) }
+    }
+}
+
+/// Run one job, returning a summary of what it did
+fn run_job(
+    job: Job,
+    config: &MaintenanceConfig,
+    bucket: &Bucket,
+    now: u64,
+) -> Result<String, String> {
+    match job {
+        Job::SessionGc => sessions::collect(bucket, now).map(|collected| {
+            format!(
+                "deleted {} sessions ({} keys)",
+                collected.sessions, collected.keys
+            )
+        }),
+        Job::KvTtl => ttl::sweep(bucket, &config.ttl_rules, now)
+            .map(|deleted| format!("deleted {} expired entries", deleted)),
+        Job::JwksRefresh => {
+            jwks::refresh(config, now).map(|refreshed| format!("refreshed {} key sets", refreshed))
+        }
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+bindings::export!(Maintenance with_types_in bindings);
@@ -0,0 +1,140 @@
+//! Schedule table
+//!
+//! `WASMCP_MAINTENANCE_SCHEDULE` lists jobs with the interval between runs,
+//! e.g. `session-gc=1h,kv-ttl=30m,jwks-refresh=4m`. The host runs the
+//! component more often than the shortest interval; each run executes the
+//! jobs whose interval has elapsed since they last succeeded.
+
+/// A housekeeping job
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Job {
+    /// Delete terminated and expired sessions
+    SessionGc,
+    /// Delete cache entries past their expiry
+    KvTtl,
+    /// Fetch JWKS ahead of their cache expiry
+    JwksRefresh,
+}
+
+impl Job {
+    pub const ALL: [Job; 3] = [Job::SessionGc, Job::KvTtl, Job::JwksRefresh];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Job::SessionGc => "session-gc",
+            Job::KvTtl => "kv-ttl",
+            Job::JwksRefresh => "jwks-refresh",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Job> {
+        Job::ALL.into_iter().find(|job| job.name() == name)
+    }
+}
+
+/// Schedule used when `WASMCP_MAINTENANCE_SCHEDULE` is unset
+pub const DEFAULT_SCHEDULE: &str = "session-gc=1h,kv-ttl=1h,jwks-refresh=4m";
+
+/// Parse a schedule table into jobs and their intervals in seconds
+///
+/// Unknown jobs and invalid intervals are reported and skipped, so one typo
+/// doesn't stop the other jobs.
+pub fn parse_schedule(table: &str) -> (Vec<(Job, u64)>, Vec<String>) {
+    let mut schedule: Vec<(Job, u64)> = Vec::new();
+    let mut warnings = Vec::new();
+
+    for entry in table.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+        let Some((name, interval)) = entry.split_once('=') else {
+            warnings.push(format!("Missing interval in '{}'", entry));
+            continue;
+        };
+        let Some(job) = Job::from_name(name.trim()) else {
+            warnings.push(format!("Unknown job '{}'", name.trim()));
+            continue;
+        };
+        let Some(secs) = parse_interval(interval.trim()) else {
+            warnings.push(format!(
+                "Invalid interval '{}' for {}",
+                interval.trim(),
+                name
+            ));
+            continue;
+        };
+        schedule.retain(|(j, _)| *j != job);
+        schedule.push((job, secs));
+    }
+
+    (schedule, warnings)
+}
+
+/// Parse an interval: seconds, or a number with an `s`, `m`, `h` or `d` suffix
+pub fn parse_interval(value: &str) -> Option<u64> {
+    let (digits, unit) = match value.char_indices().last()? {
+        (i, c) if c.is_ascii_alphabetic() => (&value[..i], c),
+        _ => (value, 's'),
+    };
+    let scale = match unit {
+        's' => 1,
+        'm' => 60,
+        'h' => 60 * 60,
+        'd' => 24 * 60 * 60,
+        _ => return None,
+    };
+    digits
+        .parse::<u64>()
+        .ok()
+        .filter(|&n| n > 0)
+        .and_then(|n| n.checked_mul(scale))
+}
+
+/// Whether a job that last succeeded at `last_run` is due at `now`
+///
+/// Times are Unix seconds; a job that never ran is always due.
+pub fn is_due(last_run: Option<u64>, interval: u64, now: u64) -> bool {
+    last_run.is_none_or(|last| now >= last.saturating_add(interval))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_interval() {
+        assert_eq!(parse_interval("90"), Some(90));
+        assert_eq!(parse_interval("45s"), Some(45));
+        assert_eq!(parse_interval("10m"), Some(600));
+        assert_eq!(parse_interval("2h"), Some(7200));
+        assert_eq!(parse_interval("1d"), Some(86400));
+        assert_eq!(parse_interval("0"), None);
+        assert_eq!(parse_interval("5w"), None);
+        assert_eq!(parse_interval("m"), None);
+        assert_eq!(parse_interval(""), None);
+    }
+
+    #[test]
+    fn test_parse_schedule() {
+        let (schedule, warnings) =
+            parse_schedule("session-gc=1h, kv-ttl=30m,otel-flush=1m,jwks-refresh,kv-ttl=10m");
+        assert_eq!(schedule, [(Job::SessionGc, 3600), (Job::KvTtl, 600)]);
+        assert_eq!(
+            warnings,
+            [
+                "Unknown job 'otel-flush'",
+                "Missing interval in 'jwks-refresh'"
+            ]
+        );
+
+        let (schedule, warnings) = parse_schedule(DEFAULT_SCHEDULE);
+        assert_eq!(schedule.len(), Job::ALL.len());
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_is_due() {
+        assert!(is_due(None, 60, 1_000));
+        assert!(!is_due(Some(950), 60, 1_000));
+        assert!(is_due(Some(940), 60, 1_000));
+        // A clock that went backwards waits rather than running early
+        assert!(!is_due(Some(2_000), 60, 1_000));
+    }
+}
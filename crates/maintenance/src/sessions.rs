@@ -0,0 +1,108 @@
+//! Session garbage collection
+//!
+//! session-store keeps every session under `{session-id}:{key}`, with its
+//! metadata at `{session-id}:__meta__`. Terminated sessions are only marked,
+//! and expired ones are only rejected on use, so their keys stay in the
+//! bucket until this job deletes them. Keys left behind by a session whose
+//! metadata is already gone are deleted too.
+
+use crate::bindings::wasmcp::keyvalue::store::Bucket;
+use crate::store;
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Field session-store keeps its metadata under
+const META_FIELD: &str = "__meta__";
+
+/// Outcome of a session GC run
+#[derive(Debug, Default)]
+pub struct Collected {
+    pub sessions: usize,
+    pub keys: usize,
+}
+
+/// Delete the keys of terminated, expired and orphaned sessions
+pub fn collect(bucket: &Bucket, now: u64) -> Result<Collected, String> {
+    // Keys of each session, and whether it has metadata
+    let mut sessions: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for key in store::all_keys(bucket)? {
+        if let Some(id) = session_id(&key) {
+            sessions.entry(id.to_string()).or_default().push(key);
+        }
+    }
+
+    let mut collected = Collected::default();
+    for (id, keys) in sessions {
+        let meta = bucket
+            .get_json(&format!("{}:{}", id, META_FIELD))
+            .map_err(|e| format!("Failed to read metadata of session {}: {}", id, e))?
+            .and_then(|json| serde_json::from_str::<Value>(&json).ok());
+        if meta.as_ref().is_some_and(|meta| !is_dead(meta, now)) {
+            continue;
+        }
+        store::delete_all(bucket, &keys)?;
+        collected.sessions += 1;
+        collected.keys += keys.len();
+    }
+    Ok(collected)
+}
+
+/// The session a key belongs to, if it is a session key
+///
+/// Session IDs are UUIDs, which keeps other entries sharing the bucket
+/// (metrics, concurrency counters, caches) out of the sweep.
+pub fn session_id(key: &str) -> Option<&str> {
+    let (id, _) = key.split_once(':')?;
+    let is_uuid = id.len() == 36
+        && id.char_indices().all(|(i, c)| match i {
+            8 | 13 | 18 | 23 => c == '-',
+            _ => c.is_ascii_hexdigit(),
+        });
+    is_uuid.then_some(id)
+}
+
+/// Whether session metadata marks the session terminated or expired
+///
+/// `expires_at` is in Unix seconds, as session-store writes it.
+pub fn is_dead(meta: &Value, now: u64) -> bool {
+    let terminated = meta["terminated"].as_bool().unwrap_or(false);
+    let expired = meta["expires_at"]
+        .as_u64()
+        .is_some_and(|expires_at| now >= expires_at);
+    terminated || expired
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_session_id() {
+        let id = "550e8400-e29b-41d4-a716-446655440000";
+        assert_eq!(session_id(&format!("{}:__meta__", id)), Some(id));
+        assert_eq!(session_id(&format!("{}:concurrency:tools", id)), Some(id));
+        assert_eq!(session_id("wasmcp:metrics:requests:POST:2xx"), None);
+        assert_eq!(session_id("oauth-jwks:https://auth.example.com/jwks"), None);
+        assert_eq!(session_id("550e8400-e29b-41d4-a716-44665544000g:key"), None);
+        assert_eq!(session_id(id), None);
+    }
+
+    #[test]
+    fn test_is_dead() {
+        let now = 1_700_000_000;
+        assert!(!is_dead(
+            &json!({ "terminated": false, "created_at": 0, "expires_at": now + 1 }),
+            now
+        ));
+        assert!(!is_dead(&json!({ "terminated": false }), now));
+        assert!(is_dead(
+            &json!({ "terminated": true, "expires_at": now + 1 }),
+            now
+        ));
+        assert!(is_dead(
+            &json!({ "terminated": false, "expires_at": now }),
+            now
+        ));
+    }
+}
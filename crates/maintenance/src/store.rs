@@ -0,0 +1,33 @@
+//! Key-value helpers shared by the jobs
+
+use crate::bindings::wasmcp::keyvalue::store::Bucket;
+
+/// Keys deleted per delete-many call
+const DELETE_BATCH: usize = 100;
+
+/// Every key in the bucket, following list cursors
+pub fn all_keys(bucket: &Bucket) -> Result<Vec<String>, String> {
+    let mut keys = Vec::new();
+    let mut cursor: Option<String> = None;
+    loop {
+        let page = bucket
+            .list_keys(cursor.as_deref())
+            .map_err(|e| format!("Failed to list keys: {}", e))?;
+        keys.extend(page.keys);
+        match page.cursor {
+            // A cursor that doesn't advance would loop forever
+            Some(next) if cursor.as_deref() != Some(next.as_str()) => cursor = Some(next),
+            _ => return Ok(keys),
+        }
+    }
+}
+
+/// Delete keys in batches
+pub fn delete_all(bucket: &Bucket, keys: &[String]) -> Result<(), String> {
+    for batch in keys.chunks(DELETE_BATCH) {
+        bucket
+            .delete_many(batch)
+            .map_err(|e| format!("Failed to delete keys: {}", e))?;
+    }
+    Ok(())
+}
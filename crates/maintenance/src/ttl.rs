@@ -0,0 +1,82 @@
+//! Expired cache entry sweeps
+//!
+//! Components cache JSON entries in key-value storage with their own expiry:
+//! authorization stores key sets with an `expires_at`, and graphql-tools
+//! stores a `stored_at` it checks against its TTL on read.
+//! Stale entries are skipped but never removed, so keys for upstreams that
+//! are no longer called accumulate. This job deletes entries under the
+//! configured prefixes once they are past `expires_at`, or older than the
+//! rule's maximum age by `stored_at`.
+
+use crate::bindings::wasmcp::keyvalue::store::{Bucket, TypedValue};
+use crate::config::TtlRule;
+use crate::store;
+use serde_json::Value;
+
+/// Delete expired entries matching the rules, returning how many were deleted
+pub fn sweep(bucket: &Bucket, rules: &[TtlRule], now: u64) -> Result<usize, String> {
+    let mut expired = Vec::new();
+    for key in store::all_keys(bucket)? {
+        let Some(rule) = rules.iter().find(|r| key.starts_with(&r.prefix)) else {
+            continue;
+        };
+        // Entries that aren't JSON carry no expiry to check
+        let value = match bucket.get(&key) {
+            Ok(Some(TypedValue::AsJson(json))) => serde_json::from_str::<Value>(&json).ok(),
+            Ok(_) => None,
+            Err(e) => {
+                eprintln!("[maintenance] WARNING: Failed to read {}: {}", key, e);
+                None
+            }
+        };
+        if value.is_some_and(|value| is_expired(&value, rule, now)) {
+            expired.push(key);
+        }
+    }
+    store::delete_all(bucket, &expired)?;
+    Ok(expired.len())
+}
+
+/// Whether a cached entry is past its expiry
+///
+/// Times are Unix seconds.
+pub fn is_expired(value: &Value, rule: &TtlRule, now: u64) -> bool {
+    if let Some(expires_at) = value["expires_at"].as_u64() {
+        return now >= expires_at;
+    }
+    match (value["stored_at"].as_u64(), rule.max_age) {
+        (Some(stored_at), Some(max_age)) => now >= stored_at.saturating_add(max_age),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn rule(max_age: Option<u64>) -> TtlRule {
+        TtlRule {
+            prefix: "cache:".to_string(),
+            max_age,
+        }
+    }
+
+    #[test]
+    fn test_expires_at() {
+        let entry = json!({ "jwks": { "keys": [] }, "expires_at": 1_000 });
+        assert!(!is_expired(&entry, &rule(None), 999));
+        assert!(is_expired(&entry, &rule(None), 1_000));
+        // An explicit expiry wins over the rule's maximum age
+        assert!(!is_expired(&entry, &rule(Some(1)), 999));
+    }
+
+    #[test]
+    fn test_stored_at() {
+        let entry = json!({ "stored_at": 1_000, "introspection": {} });
+        assert!(!is_expired(&entry, &rule(None), 1_000_000));
+        assert!(!is_expired(&entry, &rule(Some(60)), 1_059));
+        assert!(is_expired(&entry, &rule(Some(60)), 1_060));
+        assert!(!is_expired(&json!({ "value": 1 }), &rule(Some(60)), 1_060));
+    }
+}
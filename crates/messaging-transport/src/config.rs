@@ -0,0 +1,41 @@
+//! Configuration from environment variables
+
+use crate::bindings::wasi::cli::environment::get_environment;
+use std::collections::HashMap;
+
+/// Messaging client used when no name is configured
+const DEFAULT_CLIENT: &str = "default";
+
+/// Default server name when `WASMCP_SERVER_NAME` is not set
+const DEFAULT_SERVER_NAME: &str = "wasmcp-server";
+
+/// Default server title when `WASMCP_SERVER_TITLE` is not set
+const DEFAULT_SERVER_TITLE: &str = "wasmcp Messaging Transport Server";
+
+#[derive(Debug, Clone)]
+pub struct Config {
+    // Messaging client for publishing to reply-to topics
+    pub client: String,
+    pub server_name: String,
+    pub server_title: String,
+    pub server_version: String,
+    pub instructions: Option<String>,
+}
+
+impl Config {
+    pub fn from_env() -> Self {
+        let env_map: HashMap<String, String> = get_environment().into_iter().collect();
+        let get = |key: &str| env_map.get(key).filter(|v| !v.is_empty()).cloned();
+
+        Config {
+            client: get("WASMCP_MESSAGING_CLIENT").unwrap_or_else(|| DEFAULT_CLIENT.to_string()),
+            server_name: get("WASMCP_SERVER_NAME")
+                .unwrap_or_else(|| DEFAULT_SERVER_NAME.to_string()),
+            server_title: get("WASMCP_SERVER_TITLE")
+                .unwrap_or_else(|| DEFAULT_SERVER_TITLE.to_string()),
+            server_version: get("WASMCP_SERVER_VERSION")
+                .unwrap_or_else(|| env!("CARGO_PKG_VERSION").to_string()),
+            instructions: get("WASMCP_SERVER_INSTRUCTIONS"),
+        }
+    }
+}
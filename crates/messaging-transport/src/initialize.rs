@@ -0,0 +1,110 @@
+//! Initialize results
+//!
+//! As with the HTTP and stdio transport, the initialize request is first
+//! passed down the handler chain. A handler's result overrides server
+//! metadata (when its name is non-empty) and instructions, adds its
+//! experimental capabilities, and may declare the lists. Otherwise metadata
+//! comes from the `WASMCP_SERVER_*` variables and the lists from probing.
+//! Messages are independent, so every initialize probes again; handlers that
+//! list expensively should declare their lists.
+//!
+//! Logging, list-changed and subscriptions are not advertised: a message
+//! broker client has no stream to receive notifications on.
+
+use crate::bindings::wasmcp::mcp_v20251125::mcp::{
+    ClientMessage, ClientRequest, Implementation, InitializeRequest, InitializeResult,
+    InitializeResultOptions, ListPromptsRequest, ListResourcesRequest, ListToolsRequest, RequestId,
+    ServerCapabilities, ServerLists, ServerResult,
+};
+use crate::bindings::wasmcp::mcp_v20251125::server_handler::handle;
+use crate::config::Config;
+use crate::message_context;
+
+/// Request ID for probes; real client request IDs are typically positive
+const PROBE_REQUEST_ID: i64 = -1;
+
+pub fn result(init_req: &InitializeRequest, config: &Config) -> InitializeResult {
+    let handler_result = match probe(init_req, ClientRequest::Initialize(init_req.clone())) {
+        Some(ServerResult::Initialize(result)) => Some(result),
+        _ => None,
+    };
+    let handler_capabilities = handler_result.as_ref().map(|r| &r.capabilities);
+
+    let lists = match handler_capabilities.and_then(|c| c.lists) {
+        Some(lists) => lists,
+        None => probe_lists(init_req),
+    };
+    let capabilities = ServerCapabilities {
+        completions: handler_capabilities.and_then(|c| c.completions.clone()),
+        experimental: handler_capabilities.and_then(|c| c.experimental.clone()),
+        logging: None,
+        list_changed: None,
+        subscriptions: None,
+        lists: if lists.is_empty() { None } else { Some(lists) },
+    };
+
+    let (server_info, options) = match handler_result {
+        Some(result) if !result.server_info.name.is_empty() => (result.server_info, result.options),
+        Some(result) if result.options.is_some() => (configured_info(config), result.options),
+        _ => (configured_info(config), configured_options(config)),
+    };
+
+    InitializeResult {
+        meta: None,
+        server_info,
+        capabilities,
+        protocol_version: init_req.protocol_version,
+        options,
+    }
+}
+
+/// Lists the handler chain answers
+fn probe_lists(init_req: &InitializeRequest) -> ServerLists {
+    let mut lists = ServerLists::empty();
+    let tools = ClientRequest::ToolsList(ListToolsRequest {
+        cursor: None,
+        meta: None,
+    });
+    if probe(init_req, tools).is_some() {
+        lists |= ServerLists::TOOLS;
+    }
+    let resources = ClientRequest::ResourcesList(ListResourcesRequest { cursor: None });
+    if probe(init_req, resources).is_some() {
+        lists |= ServerLists::RESOURCES;
+    }
+    let prompts = ClientRequest::PromptsList(ListPromptsRequest { cursor: None });
+    if probe(init_req, prompts).is_some() {
+        lists |= ServerLists::PROMPTS;
+    }
+    lists
+}
+
+fn configured_info(config: &Config) -> Implementation {
+    Implementation {
+        name: config.server_name.clone(),
+        title: Some(config.server_title.clone()),
+        version: config.server_version.clone(),
+        description: None,
+        icons: None,
+    }
+}
+
+fn configured_options(config: &Config) -> Option<InitializeResultOptions> {
+    config
+        .instructions
+        .clone()
+        .map(|instructions| InitializeResultOptions {
+            instructions: Some(instructions),
+            meta: None,
+        })
+}
+
+/// A successful result from the handler chain, if any
+fn probe(init_req: &InitializeRequest, request: ClientRequest) -> Option<ServerResult> {
+    let ctx = message_context(init_req.protocol_version, None);
+    let message = ClientMessage::Request((RequestId::Number(PROBE_REQUEST_ID), request));
+    match handle(&ctx, message) {
+        Some(Ok(result)) => Some(result),
+        _ => None,
+    }
+}
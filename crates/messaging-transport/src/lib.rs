@@ -0,0 +1,170 @@
+//! Messaging transport
+//!
+//! Serves MCP over wasi:messaging, so event-driven systems (NATS, Kafka)
+//! can call an MCP server without HTTP. The host subscribes the component
+//! to its request topics; every message carries one JSON-RPC message:
+//! - Requests are handled like any other transport's and answered with one
+//!   response message (see `reply.rs` for where it goes)
+//! - Notifications are passed down the handler chain and not answered
+//! - Responses from the client are dropped: this transport never sends
+//!   server requests, so nothing waits for them
+//!
+//! Messages are independent: there are no sessions, and nothing negotiated
+//! in an initialize carries over to later messages. A request states its
+//! protocol version in `mcp-protocol-version` metadata, as HTTP clients do
+//! with the header; without it the latest version is assumed. Handlers get
+//! no client stream, so progress and log notifications are not delivered.
+//!
+//! Who may publish to the request topics is up to the broker; messages
+//! carry no identity.
+//!
+//! # Environment Variables
+//!
+//! - **`WASMCP_MESSAGING_CLIENT`** - Name of the messaging client to publish
+//!   `reply-to` responses with, as configured in the host
+//!   - Default: `default`
+//!
+//! - **`WASMCP_SERVER_NAME`**, **`WASMCP_SERVER_TITLE`**,
+//!   **`WASMCP_SERVER_VERSION`**, **`WASMCP_SERVER_INSTRUCTIONS`** - Server
+//!   metadata for initialize, as for the HTTP and stdio transport
+
+mod bindings {
+    wit_bindgen::generate!({
+        world: "messaging-transport",
+        generate_all,
+    });
+}
+
+mod config;
+mod initialize;
+mod reply;
+
+use bindings::exports::wasi::messaging::incoming_handler::Guest;
+use bindings::wasi::messaging::types::{Error, Message};
+use bindings::wasmcp::mcp_v20251125::mcp::{
+    self, ClientMessage, ClientRequest, ErrorCode, ProtocolVersion, RequestId, ServerMessage,
+    ServerResult,
+};
+use bindings::wasmcp::mcp_v20251125::server_handler::{MessageContext, handle};
+use bindings::wasmcp::mcp_v20251125::server_io::{self, MessageFrame};
+use config::Config;
+use wasmcp_errors::McpError;
+
+wasmcp_errors::impl_error_code!(ErrorCode, mcp::Error);
+
+/// Metadata key carrying the request's protocol version
+const PROTOCOL_VERSION_KEY: &str = "mcp-protocol-version";
+
+struct MessagingTransport;
+
+impl Guest for MessagingTransport {
+    fn handle(message: Message) -> Result<(), Error> {
+        let config = Config::from_env();
+        let protocol_version = reply::metadata(&message, PROTOCOL_VERSION_KEY)
+            .and_then(|v| parse_protocol_version(&v))
+            .unwrap_or(ProtocolVersion::V20251125);
+        server_io::set_protocol_version(protocol_version);
+
+        let response = match server_io::decode_message(&message.data()) {
+            Ok(ClientMessage::Request((request_id, request))) => {
+                match handle_request(request_id.clone(), request, protocol_version, &config) {
+                    Ok(result) => ServerMessage::Result((request_id, result)),
+                    Err(e) => ServerMessage::Error((Some(request_id), e)),
+                }
+            }
+            Ok(ClientMessage::Notification(notification)) => {
+                let ctx = message_context(protocol_version, None);
+                handle(&ctx, ClientMessage::Notification(notification));
+                return Ok(());
+            }
+            Ok(ClientMessage::Result((request_id, _)))
+            | Ok(ClientMessage::Error((Some(request_id), _))) => {
+                eprintln!(
+                    "[messaging-transport] Dropping response to unknown request {:?}",
+                    request_id
+                );
+                return Ok(());
+            }
+            Ok(ClientMessage::Error((None, error))) => {
+                eprintln!("[messaging-transport] Client reported error: {:?}", error);
+                return Ok(());
+            }
+            Err(e) => ServerMessage::Error((
+                None,
+                McpError::parse_error(format!("Failed to parse message: {:?}", e)).into(),
+            )),
+        };
+
+        let bytes = server_io::encode_message(response)
+            .map_err(|e| Error::Other(format!("Failed to serialize response: {:?}", e)))?;
+        reply::send(&message, bytes, &config)
+    }
+}
+
+/// Answer a request: ping and initialize here, everything else downstream
+fn handle_request(
+    request_id: RequestId,
+    request: ClientRequest,
+    protocol_version: ProtocolVersion,
+    config: &Config,
+) -> Result<ServerResult, ErrorCode> {
+    match request {
+        ClientRequest::Ping(_) => Ok(ServerResult::Ping),
+        ClientRequest::Initialize(init_req) => Ok(ServerResult::Initialize(initialize::result(
+            &init_req, config,
+        ))),
+        request => {
+            let ctx = message_context(protocol_version, Some(request_id.clone()));
+            match handle(&ctx, ClientMessage::Request((request_id, request))) {
+                Some(result) => result,
+                None => Err(McpError::internal("Handler returned None for request").into()),
+            }
+        }
+    }
+}
+
+/// Context for a handler: no stream, session or identity
+fn message_context<'a>(
+    protocol_version: ProtocolVersion,
+    request_id: Option<RequestId>,
+) -> MessageContext<'a> {
+    MessageContext {
+        client_stream: None,
+        protocol_version: protocol_version_to_string(protocol_version).to_string(),
+        session: None,
+        identity: None,
+        frame: MessageFrame {
+            prefix: vec![],
+            suffix: vec![],
+        },
+        http_context: None,
+        client_capabilities: None,
+        request_id,
+        deadline: None,
+        log_level: None,
+        client_info: None,
+        locale: None,
+        trace_context: None,
+    }
+}
+
+fn parse_protocol_version(version: &str) -> Option<ProtocolVersion> {
+    match version.trim() {
+        "2025-11-25" => Some(ProtocolVersion::V20251125),
+        "2025-06-18" => Some(ProtocolVersion::V20250618),
+        "2025-03-26" => Some(ProtocolVersion::V20250326),
+        "2024-11-05" => Some(ProtocolVersion::V20241105),
+        _ => None,
+    }
+}
+
+fn protocol_version_to_string(version: ProtocolVersion) -> &'static str {
+    match version {
+        ProtocolVersion::V20251125 => "2025-11-25",
+        ProtocolVersion::V20250618 => "2025-06-18",
+        ProtocolVersion::V20250326 => "2025-03-26",
+        ProtocolVersion::V20241105 => "2024-11-05",
+    }
+}
+
+bindings::export!(MessagingTransport with_types_in bindings);
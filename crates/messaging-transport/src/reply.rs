@@ -0,0 +1,47 @@
+//! Delivering responses
+//!
+//! Brokers with request-reply (NATS) give each request a reply subject, and
+//! the host answers on it with `request-reply.reply`. Brokers without one
+//! (Kafka) rely on metadata instead: a request with `reply-to` gets its
+//! response published to that topic with the producer. Either way, the
+//! request's `correlation-id` is copied to the response so the caller can
+//! match them.
+
+use crate::bindings::wasi::messaging::producer;
+use crate::bindings::wasi::messaging::request_reply;
+use crate::bindings::wasi::messaging::types::{Client, Error, Message};
+use crate::config::Config;
+
+/// Metadata key naming the topic to publish the response to
+const REPLY_TO_KEY: &str = "reply-to";
+
+/// Metadata key copied from the request to the response
+const CORRELATION_ID_KEY: &str = "correlation-id";
+
+/// Send a serialized response for `request`
+pub fn send(request: &Message, bytes: Vec<u8>, config: &Config) -> Result<(), Error> {
+    let response = Message::new(&bytes);
+    response.set_content_type("application/json");
+    if let Some(correlation_id) = metadata(request, CORRELATION_ID_KEY) {
+        response.add_metadata(CORRELATION_ID_KEY, &correlation_id);
+    }
+
+    match metadata(request, REPLY_TO_KEY) {
+        Some(topic) => {
+            let client = Client::connect(&config.client)?;
+            producer::send(&client, &topic, response)
+        }
+        None => request_reply::reply(request, response),
+    }
+    .inspect_err(|e| eprintln!("[messaging-transport] Failed to send response: {:?}", e))
+}
+
+/// A metadata value of a message (keys are matched case-insensitively, as
+/// brokers differ in how they case headers)
+pub fn metadata(message: &Message, key: &str) -> Option<String> {
+    message
+        .metadata()?
+        .into_iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case(key))
+        .map(|(_, v)| v)
+}
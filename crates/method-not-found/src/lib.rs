@@ -14,6 +14,9 @@ mod bindings {
 
 use bindings::exports::wasmcp::mcp_v20251125::server_handler::{Guest, MessageContext};
 use bindings::wasmcp::mcp_v20251125::mcp;
+use wasmcp_errors::McpError;
+
+wasmcp_errors::impl_error_code!(mcp::ErrorCode, mcp::Error);
 
 struct MethodNotFoundHandler;
 
@@ -43,14 +46,12 @@ impl Guest for MethodNotFoundHandler {
             mcp::ClientRequest::Ping(_) => "ping",
             mcp::ClientRequest::ResourcesSubscribe(_) => "resources/subscribe",
             mcp::ClientRequest::ResourcesUnsubscribe(_) => "resources/unsubscribe",
+            mcp::ClientRequest::Custom(custom) => custom.method.as_str(),
+            mcp::ClientRequest::Unknown(method) => method.as_str(),
         };
 
         // Return MethodNotFound for all requests
-        Some(Err(mcp::ErrorCode::MethodNotFound(mcp::Error {
-            code: -32601,
-            message: format!("Method not found: {}", method),
-            data: None,
-        })))
+        Some(Err(McpError::method_not_found(method).into()))
     }
 }
 
@@ -0,0 +1,162 @@
+//! Credentials for an operation's security requirements
+//!
+//! The secret for the scheme `petstore_auth` comes from
+//! `WASMCP_OPENAPI_SECRET_PETSTORE_AUTH` and is applied the way the scheme
+//! declares:
+//! - `apiKey`: the secret in the named header, query parameter or cookie
+//! - `http` bearer, `oauth2`, `openIdConnect`: `Authorization: Bearer {secret}`
+//!   (a token obtained ahead of time)
+//! - `http` basic: the secret as `user:password`, base64-encoded
+//!
+//! The first alternative whose schemes all have secrets is used.
+
+use crate::config::secret_var;
+use crate::request::Prepared;
+use crate::spec::{Operation, Spec};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Add credentials for the operation's security requirements
+pub fn apply(
+    spec: &Spec,
+    op: &Operation,
+    secrets: &HashMap<String, String>,
+    request: &mut Prepared,
+) -> Result<(), String> {
+    if op.security.is_empty() {
+        return Ok(());
+    }
+
+    for alternative in &op.security {
+        let credentials: Option<Vec<(&Value, &String)>> = alternative
+            .iter()
+            .map(|name| Some((spec.security_scheme(name)?, secrets.get(&secret_var(name))?)))
+            .collect();
+        if let Some(credentials) = credentials {
+            for (scheme, secret) in credentials {
+                add_credential(scheme, secret, request)?;
+            }
+            return Ok(());
+        }
+    }
+
+    let needed: Vec<String> = op
+        .security
+        .iter()
+        .flatten()
+        .map(|name| secret_var(name))
+        .collect();
+    Err(format!(
+        "No credentials for this operation; set {}",
+        needed.join(" or ")
+    ))
+}
+
+fn add_credential(scheme: &Value, secret: &str, request: &mut Prepared) -> Result<(), String> {
+    let field = |key: &str| scheme.get(key).and_then(Value::as_str).unwrap_or("");
+    match field("type") {
+        "apiKey" => {
+            let name = field("name");
+            match field("in") {
+                "header" => request.headers.push((name.to_string(), secret.to_string())),
+                "query" => {
+                    request.url.query_pairs_mut().append_pair(name, secret);
+                }
+                "cookie" => request.add_cookie(name, secret),
+                other => return Err(format!("Unsupported apiKey location '{}'", other)),
+            }
+        }
+        "http" if field("scheme").eq_ignore_ascii_case("basic") => request.headers.push((
+            "authorization".to_string(),
+            format!("Basic {}", STANDARD.encode(secret)),
+        )),
+        "http" if field("scheme").eq_ignore_ascii_case("bearer") => request
+            .headers
+            .push(("authorization".to_string(), format!("Bearer {}", secret))),
+        "oauth2" | "openIdConnect" => request
+            .headers
+            .push(("authorization".to_string(), format!("Bearer {}", secret))),
+        other => return Err(format!("Unsupported security scheme type '{}'", other)),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::request::prepare;
+    use crate::spec::tests::{operation, petstore};
+    use serde_json::json;
+
+    fn authorize(tool: &str, secrets: &[(&str, &str)]) -> Result<Prepared, String> {
+        let spec = petstore();
+        let op = operation(&spec, tool);
+        let args = match tool {
+            "get_pets_petId" => json!({ "petId": "1", "X-Trace": "t" }),
+            "deletePet" => json!({ "petId": "1" }),
+            _ => json!({}),
+        };
+        let Value::Object(args) = args else {
+            unreachable!()
+        };
+        let mut request = prepare(&op, "https://pets.example.com", &args)?;
+        let secrets = secrets
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        apply(&spec, &op, &secrets, &mut request)?;
+        Ok(request)
+    }
+
+    #[test]
+    fn test_bearer() {
+        let request =
+            authorize("listPets", &[("WASMCP_OPENAPI_SECRET_BEARERAUTH", "tok")]).unwrap();
+        assert!(
+            request
+                .headers
+                .contains(&("authorization".to_string(), "Bearer tok".to_string()))
+        );
+        assert_eq!(
+            authorize("listPets", &[]).unwrap_err(),
+            "No credentials for this operation; set WASMCP_OPENAPI_SECRET_BEARERAUTH"
+        );
+    }
+
+    #[test]
+    fn test_alternatives() {
+        // apiKey in the query when its secret is set
+        let request =
+            authorize("get_pets_petId", &[("WASMCP_OPENAPI_SECRET_APIKEY", "k 1")]).unwrap();
+        assert_eq!(request.url.query(), Some("api_key=k+1"));
+
+        // Otherwise the empty alternative: no credentials
+        let request = authorize("get_pets_petId", &[]).unwrap();
+        assert_eq!(request.url.query(), None);
+        assert!(!request.headers.iter().any(|(n, _)| n == "authorization"));
+
+        // Security turned off for the operation
+        assert!(authorize("deletePet", &[]).is_ok());
+    }
+
+    #[test]
+    fn test_basic() {
+        let scheme = json!({ "type": "http", "scheme": "Basic" });
+        let mut request = Prepared {
+            method: "GET".to_string(),
+            url: url::Url::parse("https://example.com").unwrap(),
+            headers: Vec::new(),
+            body: None,
+        };
+        add_credential(&scheme, "user:pass", &mut request).unwrap();
+        assert_eq!(
+            request.headers,
+            [(
+                "authorization".to_string(),
+                "Basic dXNlcjpwYXNz".to_string()
+            )]
+        );
+    }
+}
@@ -0,0 +1,116 @@
+//! Configuration for the OpenAPI tool provider
+//!
+//! Environment variables:
+//! - `WASMCP_OPENAPI_SPEC`: OpenAPI 3 document as JSON, captured at build
+//!   time (required)
+//! - `WASMCP_OPENAPI_BASE_URL`: Server URL operations are called on
+//!   (default: the document's first server)
+//! - `WASMCP_OPENAPI_OPERATIONS`: Comma-separated tool names (operationIds)
+//!   to expose (default: all)
+//! - `WASMCP_OPENAPI_ALLOW_WRITES`: "true"/"false" (default: "false") -
+//!   Expose operations other than GET, HEAD and OPTIONS
+//! - `WASMCP_OPENAPI_SECRET_{SCHEME}`: Credential for a security scheme, with
+//!   the scheme name uppercased and other characters replaced by `_`
+//! - `WASMCP_OPENAPI_MAX_BYTES`: Largest upstream response (default: 10MB)
+//!
+//! Secrets are read from the environment, which is where hosts inject
+//! secrets (e.g. `wasmtime --env`, Spin variables); they are never logged.
+
+use crate::bindings::wasi::cli::environment::get_environment;
+use std::collections::HashMap;
+
+const DEFAULT_MAX_BYTES: u64 = 10 * 1024 * 1024;
+const SECRET_PREFIX: &str = "WASMCP_OPENAPI_SECRET_";
+
+/// OpenAPI provider configuration from environment variables
+#[derive(Clone)]
+pub struct OpenapiConfig {
+    pub spec: Option<String>,
+    pub base_url: Option<String>,
+    /// None exposes every operation
+    pub operations: Option<Vec<String>>,
+    pub allow_writes: bool,
+    /// Keyed by full variable name
+    pub secrets: HashMap<String, String>,
+    pub max_bytes: u64,
+}
+
+impl OpenapiConfig {
+    /// Load configuration from environment variables
+    pub fn from_env() -> Self {
+        let env_map: HashMap<String, String> = get_environment().into_iter().collect();
+
+        let operations = env_map
+            .get("WASMCP_OPENAPI_OPERATIONS")
+            .map(|v| {
+                v.split(',')
+                    .map(str::trim)
+                    .filter(|name| !name.is_empty())
+                    .map(String::from)
+                    .collect::<Vec<_>>()
+            })
+            .filter(|names| !names.is_empty());
+
+        let allow_writes = env_map
+            .get("WASMCP_OPENAPI_ALLOW_WRITES")
+            .map(|v| v.to_lowercase() == "true")
+            .unwrap_or(false);
+
+        let max_bytes = env_map
+            .get("WASMCP_OPENAPI_MAX_BYTES")
+            .and_then(|v| v.parse::<u64>().ok())
+            .filter(|n| *n > 0)
+            .unwrap_or(DEFAULT_MAX_BYTES);
+
+        let secrets = env_map
+            .iter()
+            .filter(|(name, value)| name.starts_with(SECRET_PREFIX) && !value.is_empty())
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+
+        OpenapiConfig {
+            spec: env_map
+                .get("WASMCP_OPENAPI_SPEC")
+                .filter(|v| !v.trim().is_empty())
+                .cloned(),
+            base_url: env_map
+                .get("WASMCP_OPENAPI_BASE_URL")
+                .filter(|v| !v.is_empty())
+                .cloned(),
+            operations,
+            allow_writes,
+            secrets,
+            max_bytes,
+        }
+    }
+
+    /// Whether a tool is exposed by `WASMCP_OPENAPI_OPERATIONS`
+    pub fn lists(&self, tool_name: &str) -> bool {
+        self.operations
+            .as_ref()
+            .is_none_or(|names| names.iter().any(|n| n == tool_name))
+    }
+}
+
+/// Methods that do not change server state
+pub fn is_safe_method(method: &str) -> bool {
+    matches!(method, "GET" | "HEAD" | "OPTIONS")
+}
+
+/// Environment variable holding the secret for a security scheme
+///
+/// `petstore_auth` becomes `WASMCP_OPENAPI_SECRET_PETSTORE_AUTH` and
+/// `bearerAuth` becomes `WASMCP_OPENAPI_SECRET_BEARERAUTH`.
+pub fn secret_var(scheme: &str) -> String {
+    let suffix: String = scheme
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{}{}", SECRET_PREFIX, suffix)
+}
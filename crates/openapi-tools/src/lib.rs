@@ -0,0 +1,258 @@
+//! OpenAPI Tools Middleware Component
+//!
+//! Generates MCP tools from an OpenAPI 3 document with no custom handler
+//! code. This component:
+//! - Lists one tool per operation in `WASMCP_OPENAPI_SPEC`, named by its
+//!   operationId, with an input schema built from its parameters and JSON
+//!   request body
+//! - Calls the operation over wasi:http with the tool arguments in the path,
+//!   query, headers, cookies and body as the document declares
+//! - Adds credentials for the operation's security schemes from
+//!   `WASMCP_OPENAPI_SECRET_*`
+//! - Returns JSON responses as structuredContent matching the output schema
+//!   derived from the 2xx response
+//! - Delegates all other requests downstream
+//!
+//! Only GET, HEAD and OPTIONS operations are exposed unless
+//! `WASMCP_OPENAPI_ALLOW_WRITES=true`.
+
+#![allow(warnings)]
+
+mod bindings {
+    wit_bindgen::generate!({
+        world: "openapi-tools",
+        generate_all,
+    });
+}
+
+mod auth;
+mod config;
+mod registry;
+mod request;
+mod spec;
+
+use bindings::exports::wasmcp::mcp_v20251125::server_handler::{Guest, MessageContext};
+use bindings::wasmcp::mcp_v20251125::mcp::*;
+use bindings::wasmcp::mcp_v20251125::server_handler as downstream;
+use config::OpenapiConfig;
+use registry::Registry;
+use spec::{Operation, Spec};
+use wasmcp_errors::{ErrorKind, McpError};
+use wasmcp_http_client::Request;
+
+wasmcp_http_client::impl_wasi_transport!(crate::bindings::wasi);
+wasmcp_errors::impl_error_code!(ErrorCode, Error);
+
+/// Longest upstream error body quoted in a tool error
+const MAX_ERROR_TEXT: usize = 1024;
+
+struct OpenapiTools;
+
+// Convert exported MessageContext to imported MessageContext
+fn to_downstream_ctx<'a>(ctx: &'a MessageContext<'a>) -> downstream::MessageContext<'a> {
+    downstream::MessageContext {
+        client_stream: ctx.client_stream,
+        protocol_version: ctx.protocol_version.clone(),
+        session: ctx.session.as_ref().map(|s| downstream::Session {
+            session_id: s.session_id.clone(),
+            store_id: s.store_id.clone(),
+        }),
+        identity: ctx.identity.as_ref().map(|i| downstream::Identity {
+            jwt: i.jwt.clone(),
+            claims: i.claims.clone(),
+        }),
+        frame: ctx.frame.clone(),
+        http_context: ctx.http_context.clone(),
+        client_capabilities: ctx.client_capabilities.clone(),
+        request_id: ctx.request_id.clone(),
+        deadline: ctx.deadline,
+        log_level: ctx.log_level,
+        client_info: ctx.client_info.clone(),
+        locale: ctx.locale.clone(),
+        trace_context: ctx.trace_context.clone(),
+    }
+}
+
+impl Guest for OpenapiTools {
+    fn handle(
+        ctx: MessageContext,
+        message: ClientMessage,
+    ) -> Option<Result<ServerResult, ErrorCode>> {
+        let Some(registry) = registry::get() else {
+            return downstream::handle(&to_downstream_ctx(&ctx), message);
+        };
+        let (spec, config) = (&registry.spec, &registry.config);
+
+        match message {
+            ClientMessage::Request((request_id, request)) => {
+                let result = match &request {
+                    // Our tools are on the first page
+                    ClientRequest::ToolsList(list_req) if list_req.cursor.is_none() => {
+                        handle_tools_list(request_id.clone(), &ctx, registry)
+                    }
+                    ClientRequest::ToolsCall(call_req) => {
+                        match registry.operation(&call_req.name) {
+                            Some(op) => Ok(handle_tools_call(call_req, op, &ctx, spec, config)),
+                            None => return delegate(&ctx, request_id, request),
+                        }
+                    }
+                    _ => return delegate(&ctx, request_id, request),
+                };
+                Some(result)
+            }
+            _ => {
+                // Forward notifications, results, errors to downstream
+                downstream::handle(&to_downstream_ctx(&ctx), message)
+            }
+        }
+    }
+}
+
+fn delegate(
+    ctx: &MessageContext,
+    request_id: RequestId,
+    request: ClientRequest,
+) -> Option<Result<ServerResult, ErrorCode>> {
+    let downstream_msg = ClientMessage::Request((request_id, request));
+    downstream::handle(&to_downstream_ctx(ctx), downstream_msg)
+}
+
+fn handle_tools_list(
+    request_id: RequestId,
+    ctx: &MessageContext,
+    registry: &Registry,
+) -> Result<ServerResult, ErrorCode> {
+    let mut tools: Vec<Tool> = registry.operations().iter().map(to_tool).collect();
+
+    let downstream_req = ClientRequest::ToolsList(ListToolsRequest {
+        cursor: None,
+        meta: None,
+    });
+    let downstream_msg = ClientMessage::Request((request_id, downstream_req));
+    let next_cursor = match downstream::handle(&to_downstream_ctx(ctx), downstream_msg) {
+        Some(Ok(ServerResult::ToolsList(downstream_result))) => {
+            tools.extend(downstream_result.tools);
+            downstream_result.next_cursor
+        }
+        // A downstream without tools/list still lets ours through
+        Some(Err(e)) if McpError::from(&e).kind == ErrorKind::MethodNotFound => None,
+        None | Some(Ok(_)) => None,
+        Some(Err(e)) => return Err(e),
+    };
+
+    Ok(ServerResult::ToolsList(ListToolsResult {
+        tools,
+        next_cursor,
+        meta: None,
+    }))
+}
+
+fn to_tool(op: &Operation) -> Tool {
+    let read_only = config::is_safe_method(&op.method);
+    Tool {
+        name: op.tool_name.clone(),
+        input_schema: op.input_schema().to_string(),
+        options: Some(ToolOptions {
+            meta: None,
+            annotations: Some(ToolAnnotations {
+                destructive_hint: Some(matches!(op.method.as_str(), "DELETE" | "PUT" | "PATCH")),
+                idempotent_hint: Some(read_only || matches!(op.method.as_str(), "PUT" | "DELETE")),
+                open_world_hint: Some(true),
+                read_only_hint: Some(read_only),
+                title: None,
+            }),
+            description: op.description.clone().or_else(|| op.summary.clone()),
+            output_schema: op.output_schema().map(|s| s.to_string()),
+            title: op.summary.clone(),
+            icons: None,
+        }),
+    }
+}
+
+fn handle_tools_call(
+    req: &CallToolRequest,
+    op: &Operation,
+    ctx: &MessageContext,
+    spec: &Spec,
+    config: &OpenapiConfig,
+) -> ServerResult {
+    match call(req, op, ctx, spec, config) {
+        Ok((text, structured)) => tool_result(text, structured, false),
+        Err(e) => tool_result(e, None, true),
+    }
+}
+
+fn call(
+    req: &CallToolRequest,
+    op: &Operation,
+    ctx: &MessageContext,
+    spec: &Spec,
+    config: &OpenapiConfig,
+) -> Result<(String, Option<serde_json::Value>), String> {
+    let args = match req.arguments.as_deref().map(serde_json::from_str) {
+        Some(Ok(serde_json::Value::Object(args))) => args,
+        Some(Ok(serde_json::Value::Null)) | None => serde_json::Map::new(),
+        Some(Ok(_)) => return Err("Arguments must be a JSON object".to_string()),
+        Some(Err(e)) => return Err(format!("Invalid arguments: {}", e)),
+    };
+
+    let base_url = config
+        .base_url
+        .clone()
+        .or_else(|| spec.server_url())
+        .filter(|url| url.starts_with("http://") || url.starts_with("https://"))
+        .ok_or_else(|| {
+            "The document has no absolute server URL; set WASMCP_OPENAPI_BASE_URL".to_string()
+        })?;
+
+    let mut prepared = request::prepare(op, &base_url, &args)?;
+    auth::apply(spec, op, &config.secrets, &mut prepared)?;
+
+    let outgoing = prepared.headers.iter().fold(
+        Request::new(prepared.method.as_str(), prepared.url.as_str()),
+        |outgoing, (name, value)| outgoing.with_header(name.as_str(), value.as_str()),
+    );
+    let outgoing = match prepared.body {
+        Some(body) => outgoing.with_body(body),
+        None => outgoing,
+    };
+    let response = wasi_client("openapi-tools", ctx.deadline)
+        .with_max_response_bytes(config.max_bytes)
+        .send(outgoing)
+        .map_err(|e| e.to_string())?;
+
+    let text = String::from_utf8_lossy(&response.body).to_string();
+    if !(200..300).contains(&response.status) {
+        let excerpt: String = text.chars().take(MAX_ERROR_TEXT).collect();
+        return Err(format!("HTTP {}: {}", response.status, excerpt));
+    }
+
+    let is_json = response
+        .header("content-type")
+        .is_some_and(|ct| ct.contains("json"));
+    match serde_json::from_slice::<serde_json::Value>(&response.body) {
+        Ok(value) if is_json => {
+            let text = serde_json::to_string_pretty(&value).unwrap_or(text);
+            Ok((text, Some(op.structured(value))))
+        }
+        _ => Ok((text, None)),
+    }
+}
+
+fn tool_result(
+    text: String,
+    structured_content: Option<serde_json::Value>,
+    is_error: bool,
+) -> ServerResult {
+    ServerResult::ToolsCall(CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text: TextData::Text(text),
+            options: None,
+        })],
+        is_error: is_error.then_some(true),
+        meta: None,
+        structured_content: structured_content.map(|v| v.to_string()),
+    })
+}
+
+bindings::export!(OpenapiTools with_types_in bindings);
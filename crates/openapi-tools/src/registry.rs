@@ -0,0 +1,134 @@
+//! Tool registry
+//!
+//! Parsing the document and resolving every operation is the expensive part
+//! of a request, and a document can define hundreds of operations. The
+//! registry does both once per component instance, on first use, and indexes
+//! the operations by tool name so a call finds its operation without a scan.
+
+use crate::config::{self, OpenapiConfig};
+use crate::spec::{Operation, Spec};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+static REGISTRY: OnceLock<Option<Registry>> = OnceLock::new();
+
+/// The configured document and the operations it exposes as tools
+pub struct Registry {
+    pub config: OpenapiConfig,
+    pub spec: Spec,
+    /// In document order, as listed
+    operations: Vec<Operation>,
+    /// Tool name to position in `operations`
+    index: HashMap<String, usize>,
+}
+
+/// The registry, or None (passing everything downstream) when the document
+/// is unset or invalid
+pub fn get() -> Option<&'static Registry> {
+    REGISTRY
+        .get_or_init(|| Registry::load(OpenapiConfig::from_env()))
+        .as_ref()
+}
+
+impl Registry {
+    fn load(config: OpenapiConfig) -> Option<Registry> {
+        let document = config.spec.as_ref()?;
+        let spec = Spec::parse(document)
+            .map_err(|e| eprintln!("[openapi-tools] WARNING: WASMCP_OPENAPI_SPEC: {}", e))
+            .ok()?;
+        Some(Registry::new(config, spec))
+    }
+
+    /// Resolve the operations exposed as tools
+    ///
+    /// Operations that cannot be called, and writes without
+    /// `WASMCP_OPENAPI_ALLOW_WRITES`, are left out with a warning. When two
+    /// operations share a tool name, the first one is called.
+    pub fn new(config: OpenapiConfig, spec: Spec) -> Registry {
+        let operations: Vec<Operation> = spec
+            .operations()
+            .into_iter()
+            .filter_map(|op| {
+                op.map_err(|e| eprintln!("[openapi-tools] WARNING: Skipping {}", e))
+                    .ok()
+            })
+            .filter(|op| config.lists(&op.tool_name))
+            .filter(|op| {
+                let allowed = config.allow_writes || config::is_safe_method(&op.method);
+                if !allowed {
+                    eprintln!(
+                        "[openapi-tools] WARNING: Skipping '{}': {} operations require WASMCP_OPENAPI_ALLOW_WRITES=true",
+                        op.tool_name, op.method
+                    );
+                }
+                allowed
+            })
+            .collect();
+
+        let mut index = HashMap::with_capacity(operations.len());
+        for (i, op) in operations.iter().enumerate() {
+            index.entry(op.tool_name.clone()).or_insert(i);
+        }
+
+        Registry {
+            config,
+            spec,
+            operations,
+            index,
+        }
+    }
+
+    /// Operations exposed as tools, in document order
+    pub fn operations(&self) -> &[Operation] {
+        &self.operations
+    }
+
+    /// The operation behind a tool
+    pub fn operation(&self, tool_name: &str) -> Option<&Operation> {
+        self.index.get(tool_name).map(|&i| &self.operations[i])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::tests::petstore;
+
+    fn config(allow_writes: bool) -> OpenapiConfig {
+        OpenapiConfig {
+            spec: None,
+            base_url: None,
+            operations: None,
+            allow_writes,
+            secrets: HashMap::new(),
+            max_bytes: 1024,
+        }
+    }
+
+    #[test]
+    fn test_read_only_registry() {
+        let registry = Registry::new(config(false), petstore());
+        let names: Vec<&str> = registry
+            .operations()
+            .iter()
+            .map(|op| op.tool_name.as_str())
+            .collect();
+        assert_eq!(names, ["listPets", "get_pets_petId"]);
+        assert_eq!(
+            registry.operation("get_pets_petId").unwrap().path,
+            "/pets/{petId}"
+        );
+        assert!(registry.operation("deletePet").is_none());
+        assert!(registry.operation("upload").is_none());
+    }
+
+    #[test]
+    fn test_registry_with_writes() {
+        let mut config = config(true);
+        config.operations = Some(vec!["listPets".to_string(), "deletePet".to_string()]);
+        let registry = Registry::new(config, petstore());
+        assert_eq!(registry.operations().len(), 2);
+        assert_eq!(registry.operation("deletePet").unwrap().method, "DELETE");
+        assert!(registry.operation("createPet").is_none());
+    }
+}
@@ -0,0 +1,290 @@
+//! HTTP requests built from tool arguments
+//!
+//! Arguments are serialized the OpenAPI default way for their location:
+//! path parameters are percent-encoded into the path, query arrays repeat
+//! their key, header and cookie arrays are comma-separated, and the body is
+//! sent as JSON.
+
+use crate::spec::{Location, Operation};
+use serde_json::{Map, Value};
+
+/// A request ready to send
+#[derive(Debug)]
+pub struct Prepared {
+    pub method: String,
+    pub url: url::Url,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<Vec<u8>>,
+}
+
+impl Prepared {
+    /// Add a cookie to the request's Cookie header
+    pub fn add_cookie(&mut self, name: &str, value: &str) {
+        let cookie = format!("{}={}", name, value);
+        match self.headers.iter_mut().find(|(n, _)| n == "cookie") {
+            Some((_, existing)) => {
+                existing.push_str("; ");
+                existing.push_str(&cookie);
+            }
+            None => self.headers.push(("cookie".to_string(), cookie)),
+        }
+    }
+}
+
+/// Build the request for an operation from tool arguments
+pub fn prepare(
+    op: &Operation,
+    base_url: &str,
+    args: &Map<String, Value>,
+) -> Result<Prepared, String> {
+    let known = |arg: &str| {
+        op.parameters.iter().any(|p| p.arg == arg) || op.body.as_ref().is_some_and(|b| b.arg == arg)
+    };
+    if let Some(unknown) = args.keys().find(|k| !known(k)) {
+        return Err(format!("Unknown argument '{}'", unknown));
+    }
+
+    let mut path = op.path.clone();
+    let mut query: Vec<(String, String)> = Vec::new();
+    let mut headers: Vec<(String, String)> = Vec::new();
+    let mut cookies: Vec<(String, String)> = Vec::new();
+
+    for param in &op.parameters {
+        let value = match args.get(&param.arg) {
+            None | Some(Value::Null) if param.required => {
+                return Err(format!("Missing required argument '{}'", param.arg));
+            }
+            None | Some(Value::Null) => continue,
+            Some(value) => value,
+        };
+        match param.location {
+            Location::Path => {
+                let encoded = encode_segment(&simple(value, &param.arg)?);
+                path = path.replace(&format!("{{{}}}", param.name), &encoded);
+            }
+            Location::Query if param.explode => match value {
+                Value::Array(items) => {
+                    for item in items {
+                        query.push((param.name.clone(), scalar(item, &param.arg)?));
+                    }
+                }
+                Value::Object(fields) => {
+                    for (key, item) in fields {
+                        query.push((key.clone(), scalar(item, &param.arg)?));
+                    }
+                }
+                value => query.push((param.name.clone(), scalar(value, &param.arg)?)),
+            },
+            Location::Query => query.push((param.name.clone(), simple(value, &param.arg)?)),
+            Location::Header => {
+                let value = simple(value, &param.arg)?;
+                if value.contains(['\r', '\n']) {
+                    return Err(format!("'{}' must not contain line breaks", param.arg));
+                }
+                headers.push((param.name.clone(), value));
+            }
+            Location::Cookie => {
+                let value = simple(value, &param.arg)?;
+                if value.contains([';', '\r', '\n']) {
+                    return Err(format!(
+                        "'{}' must not contain ';' or line breaks",
+                        param.arg
+                    ));
+                }
+                cookies.push((param.name.clone(), value));
+            }
+        }
+    }
+
+    // Values are encoded, so any brace left is an undeclared parameter
+    if let Some(start) = path.find('{') {
+        let end = path[start..]
+            .find('}')
+            .map_or(path.len(), |i| start + i + 1);
+        return Err(format!(
+            "Path parameter '{}' is not declared",
+            &path[start..end]
+        ));
+    }
+
+    let mut url = url::Url::parse(&format!("{}{}", base_url.trim_end_matches('/'), path))
+        .map_err(|e| format!("Invalid URL for {}: {}", op.path, e))?;
+    if !query.is_empty() {
+        url.query_pairs_mut().extend_pairs(&query);
+    }
+
+    let mut body = None;
+    if let Some(request_body) = &op.body {
+        match args.get(&request_body.arg) {
+            None | Some(Value::Null) if request_body.required => {
+                return Err(format!("Missing required argument '{}'", request_body.arg));
+            }
+            None | Some(Value::Null) => {}
+            Some(value) => {
+                headers.push((
+                    "content-type".to_string(),
+                    request_body.content_type.clone(),
+                ));
+                body = Some(value.to_string().into_bytes());
+            }
+        }
+    }
+    headers.push((
+        "accept".to_string(),
+        "application/json, */*;q=0.5".to_string(),
+    ));
+
+    let mut prepared = Prepared {
+        method: op.method.clone(),
+        url,
+        headers,
+        body,
+    };
+    for (name, value) in cookies {
+        prepared.add_cookie(&name, &value);
+    }
+    Ok(prepared)
+}
+
+/// A string, number or boolean as text
+fn scalar(value: &Value, arg: &str) -> Result<String, String> {
+    match value {
+        Value::String(s) => Ok(s.clone()),
+        Value::Number(n) => Ok(n.to_string()),
+        Value::Bool(b) => Ok(b.to_string()),
+        _ => Err(format!("'{}' must be a string, number or boolean", arg)),
+    }
+}
+
+/// Simple style: arrays as `a,b`, objects as `key,value,key,value`
+fn simple(value: &Value, arg: &str) -> Result<String, String> {
+    let parts: Vec<String> = match value {
+        Value::Array(items) => items
+            .iter()
+            .map(|item| scalar(item, arg))
+            .collect::<Result<_, _>>()?,
+        Value::Object(fields) => fields
+            .iter()
+            .map(|(key, item)| Ok(format!("{},{}", key, scalar(item, arg)?)))
+            .collect::<Result<_, String>>()?,
+        value => return scalar(value, arg),
+    };
+    Ok(parts.join(","))
+}
+
+/// Percent-encode everything but unreserved characters, so values cannot
+/// add path segments or a query
+fn encode_segment(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::tests::{operation, petstore};
+    use serde_json::json;
+
+    fn build(tool: &str, args: Value) -> Result<Prepared, String> {
+        let spec = petstore();
+        let op = operation(&spec, tool);
+        let Value::Object(args) = args else {
+            panic!("arguments must be an object");
+        };
+        prepare(&op, "https://pets.example.com/v1/", &args)
+    }
+
+    #[test]
+    fn test_path_and_headers() {
+        let request = build(
+            "get_pets_petId",
+            json!({ "petId": "a/b c", "X-Trace": "t1" }),
+        )
+        .unwrap();
+        assert_eq!(request.method, "GET");
+        assert_eq!(
+            request.url.as_str(),
+            "https://pets.example.com/v1/pets/a%2Fb%20c"
+        );
+        assert!(
+            request
+                .headers
+                .contains(&("X-Trace".to_string(), "t1".to_string()))
+        );
+
+        assert_eq!(
+            build("get_pets_petId", json!({ "petId": "1" })).unwrap_err(),
+            "Missing required argument 'X-Trace'"
+        );
+        assert_eq!(
+            build(
+                "get_pets_petId",
+                json!({ "petId": "1", "X-Trace": "a\r\nb" })
+            )
+            .unwrap_err(),
+            "'X-Trace' must not contain line breaks"
+        );
+        assert_eq!(
+            build(
+                "get_pets_petId",
+                json!({ "petId": "1", "X-Trace": "t", "x": 1 })
+            )
+            .unwrap_err(),
+            "Unknown argument 'x'"
+        );
+    }
+
+    #[test]
+    fn test_query() {
+        let request = build("listPets", json!({ "limit": 5, "tags": ["a&b", "c"] })).unwrap();
+        assert_eq!(
+            request.url.as_str(),
+            "https://pets.example.com/v1/pets?limit=5&tags=a%26b&tags=c"
+        );
+        assert!(request.body.is_none());
+
+        let request = build("listPets", json!({ "limit": null })).unwrap();
+        assert_eq!(request.url.query(), None);
+        assert_eq!(
+            build("listPets", json!({ "tags": [["nested"]] })).unwrap_err(),
+            "'tags' must be a string, number or boolean"
+        );
+    }
+
+    #[test]
+    fn test_body() {
+        let request = build("createPet", json!({ "body": { "name": "Rex" } })).unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.body.unwrap(), br#"{"name":"Rex"}"#);
+        assert!(
+            request
+                .headers
+                .contains(&("content-type".to_string(), "application/json".to_string()))
+        );
+        assert_eq!(
+            build("createPet", json!({})).unwrap_err(),
+            "Missing required argument 'body'"
+        );
+    }
+
+    #[test]
+    fn test_cookies() {
+        let spec = petstore();
+        let op = operation(&spec, "listPets");
+        let mut request = prepare(&op, "https://pets.example.com", &Map::new()).unwrap();
+        request.add_cookie("a", "1");
+        request.add_cookie("b", "2");
+        assert!(
+            request
+                .headers
+                .contains(&("cookie".to_string(), "a=1; b=2".to_string()))
+        );
+    }
+}
@@ -0,0 +1,592 @@
+//! OpenAPI 3 documents, and the tools derived from their operations
+//!
+//! Each operation becomes one tool:
+//! - One argument per parameter, plus `body` for a JSON request body
+//! - Local `$ref`s are inlined, so input and output schemas are self-contained
+//! - The 2xx JSON response schema becomes the output schema, wrapped as
+//!   `{"result": ...}` when it is not an object
+
+use serde_json::{Map, Value, json};
+
+const METHODS: [&str; 8] = [
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// Longest chain of `$ref`s inlined; recursive schemas end in `{}`
+const MAX_REF_DEPTH: usize = 8;
+
+/// Longest tool name clients accept
+const MAX_TOOL_NAME: usize = 128;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Location {
+    Path,
+    Query,
+    Header,
+    Cookie,
+}
+
+impl Location {
+    fn parse(value: &str) -> Option<Location> {
+        match value {
+            "path" => Some(Location::Path),
+            "query" => Some(Location::Query),
+            "header" => Some(Location::Header),
+            "cookie" => Some(Location::Cookie),
+            _ => None,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Location::Path => "path",
+            Location::Query => "query",
+            Location::Header => "header",
+            Location::Cookie => "cookie",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Parameter {
+    /// Tool argument carrying the parameter: its name, or `{in}_{name}`
+    /// when another parameter already took the name
+    pub arg: String,
+    pub name: String,
+    pub location: Location,
+    pub required: bool,
+    pub schema: Value,
+    pub description: Option<String>,
+    /// Query arrays and objects as repeated keys (the OpenAPI default)
+    pub explode: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct RequestBody {
+    /// `body`, or `requestBody` when a parameter is named `body`
+    pub arg: String,
+    pub required: bool,
+    pub content_type: String,
+    pub schema: Value,
+    pub description: Option<String>,
+}
+
+/// An operation exposed as a tool
+#[derive(Debug, Clone)]
+pub struct Operation {
+    pub tool_name: String,
+    /// Uppercase, e.g. `GET`
+    pub method: String,
+    /// Path template, e.g. `/pets/{petId}`
+    pub path: String,
+    pub summary: Option<String>,
+    pub description: Option<String>,
+    pub parameters: Vec<Parameter>,
+    pub body: Option<RequestBody>,
+    /// Alternative security requirements, each naming the schemes it needs;
+    /// an empty alternative means credentials are optional
+    pub security: Vec<Vec<String>>,
+    pub response_schema: Option<Value>,
+}
+
+impl Operation {
+    /// JSON Schema for the tool's arguments
+    pub fn input_schema(&self) -> Value {
+        let mut properties = Map::new();
+        let mut required = Vec::new();
+        for param in &self.parameters {
+            let mut schema = param.schema.clone();
+            if let (Some(description), Some(obj)) = (&param.description, schema.as_object_mut()) {
+                obj.entry("description")
+                    .or_insert_with(|| json!(description));
+            }
+            properties.insert(param.arg.clone(), schema);
+            if param.required {
+                required.push(param.arg.clone());
+            }
+        }
+        if let Some(body) = &self.body {
+            let mut schema = body.schema.clone();
+            if let (Some(description), Some(obj)) = (&body.description, schema.as_object_mut()) {
+                obj.entry("description")
+                    .or_insert_with(|| json!(description));
+            }
+            properties.insert(body.arg.clone(), schema);
+            if body.required {
+                required.push(body.arg.clone());
+            }
+        }
+
+        json!({
+            "type": "object",
+            "properties": properties,
+            "required": required,
+            "additionalProperties": false,
+        })
+    }
+
+    /// Output schema for structuredContent, which must be an object
+    pub fn output_schema(&self) -> Option<Value> {
+        let schema = self.response_schema.as_ref()?;
+        if is_object_schema(schema) {
+            Some(schema.clone())
+        } else {
+            Some(json!({
+                "type": "object",
+                "properties": { "result": schema },
+                "required": ["result"],
+            }))
+        }
+    }
+
+    /// Shape a JSON response into structuredContent matching `output_schema`
+    pub fn structured(&self, value: Value) -> Value {
+        let object_schema = self.response_schema.as_ref().is_none_or(is_object_schema);
+        if value.is_object() && object_schema {
+            value
+        } else {
+            json!({ "result": value })
+        }
+    }
+}
+
+/// A parsed OpenAPI document
+pub struct Spec {
+    doc: Value,
+}
+
+impl Spec {
+    /// Parse an OpenAPI 3 document in JSON
+    pub fn parse(document: &str) -> Result<Spec, String> {
+        let doc: Value =
+            serde_json::from_str(document).map_err(|e| format!("Invalid OpenAPI JSON: {}", e))?;
+        match doc.get("openapi").and_then(Value::as_str) {
+            Some(version) if version.starts_with("3.") => Ok(Spec { doc }),
+            Some(version) => Err(format!("Unsupported OpenAPI version {}", version)),
+            None => Err("Not an OpenAPI 3 document (no 'openapi' field)".to_string()),
+        }
+    }
+
+    /// The first server's URL, with variables set to their defaults
+    pub fn server_url(&self) -> Option<String> {
+        let server = self.doc.get("servers")?.as_array()?.first()?;
+        let mut url = server.get("url")?.as_str()?.to_string();
+        if let Some(variables) = server.get("variables").and_then(Value::as_object) {
+            for (name, variable) in variables {
+                if let Some(default) = variable.get("default").and_then(Value::as_str) {
+                    url = url.replace(&format!("{{{}}}", name), default);
+                }
+            }
+        }
+        Some(url)
+    }
+
+    /// A security scheme from `components.securitySchemes`
+    pub fn security_scheme(&self, name: &str) -> Option<&Value> {
+        let scheme = self.doc.pointer("/components/securitySchemes")?.get(name)?;
+        self.resolve(scheme)
+    }
+
+    /// Every operation, in document order
+    ///
+    /// Operations that cannot be called as tools (such as a required body
+    /// that is not JSON) are errors naming the operation.
+    pub fn operations(&self) -> Vec<Result<Operation, String>> {
+        let Some(paths) = self.doc.get("paths").and_then(Value::as_object) else {
+            return Vec::new();
+        };
+        let mut operations = Vec::new();
+        for (path, item) in paths {
+            let Some(item) = self.resolve(item) else {
+                continue;
+            };
+            for method in METHODS {
+                if let Some(op) = item.get(method) {
+                    operations.push(
+                        self.operation(path, method, item, op)
+                            .map_err(|e| format!("{} {}: {}", method.to_uppercase(), path, e)),
+                    );
+                }
+            }
+        }
+        operations
+    }
+
+    fn operation(
+        &self,
+        path: &str,
+        method: &str,
+        item: &Value,
+        op: &Value,
+    ) -> Result<Operation, String> {
+        let tool_name = match op.get("operationId").and_then(Value::as_str) {
+            Some(id) => tool_name(id),
+            None => tool_name(&format!("{}_{}", method, path)),
+        };
+
+        let parameters = self.parameters(item, op)?;
+        let body = match op.get("requestBody").and_then(|b| self.resolve(b)) {
+            Some(body) => {
+                let arg = if parameters.iter().any(|p| p.arg == "body") {
+                    "requestBody"
+                } else {
+                    "body"
+                };
+                self.request_body(body, arg)?
+            }
+            None => None,
+        };
+
+        // Operation security replaces the document's, and `[]` turns it off
+        let requirements = op.get("security").or_else(|| self.doc.get("security"));
+        let security = requirements
+            .and_then(Value::as_array)
+            .map(|alternatives| {
+                alternatives
+                    .iter()
+                    .filter_map(Value::as_object)
+                    .map(|schemes| schemes.keys().cloned().collect())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(Operation {
+            tool_name,
+            method: method.to_uppercase(),
+            path: path.to_string(),
+            summary: string(op, "summary"),
+            description: string(op, "description"),
+            parameters,
+            body,
+            security,
+            response_schema: self.response_schema(op),
+        })
+    }
+
+    /// Path-level parameters merged with the operation's, which override
+    /// those with the same name and location
+    fn parameters(&self, item: &Value, op: &Value) -> Result<Vec<Parameter>, String> {
+        let declared = |value: &Value| -> Vec<Value> {
+            value
+                .get("parameters")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(|p| self.resolve(p).cloned())
+                .collect()
+        };
+        let mut merged: Vec<Value> = declared(item);
+        for param in declared(op) {
+            merged.retain(|p| p.get("name") != param.get("name") || p.get("in") != param.get("in"));
+            merged.push(param);
+        }
+
+        let mut parameters: Vec<Parameter> = Vec::new();
+        for param in merged {
+            let name = string(&param, "name").ok_or("parameter without a name")?;
+            let location = param
+                .get("in")
+                .and_then(Value::as_str)
+                .and_then(Location::parse)
+                .ok_or_else(|| format!("parameter '{}' has no valid 'in'", name))?;
+            let arg = if parameters.iter().any(|p| p.arg == name) {
+                format!("{}_{}", location.as_str(), name)
+            } else {
+                name.clone()
+            };
+            // Path parameters are always required
+            let required = location == Location::Path
+                || param.get("required").and_then(Value::as_bool) == Some(true);
+            let schema = param
+                .get("schema")
+                .map(|s| self.inline(s, MAX_REF_DEPTH))
+                .unwrap_or_else(|| json!({ "type": "string" }));
+            let explode = param
+                .get("explode")
+                .and_then(Value::as_bool)
+                .unwrap_or(true);
+
+            parameters.push(Parameter {
+                arg,
+                name,
+                location,
+                required,
+                schema,
+                description: string(&param, "description"),
+                explode,
+            });
+        }
+        Ok(parameters)
+    }
+
+    fn request_body(&self, body: &Value, arg: &str) -> Result<Option<RequestBody>, String> {
+        let required = body.get("required").and_then(Value::as_bool) == Some(true);
+        let content = body.get("content").and_then(Value::as_object);
+        let json_content = content
+            .into_iter()
+            .flatten()
+            .find(|(content_type, _)| is_json(content_type));
+
+        match json_content {
+            Some((content_type, media)) => Ok(Some(RequestBody {
+                arg: arg.to_string(),
+                required,
+                content_type: content_type.clone(),
+                schema: media
+                    .get("schema")
+                    .map(|s| self.inline(s, MAX_REF_DEPTH))
+                    .unwrap_or_else(|| json!({})),
+                description: string(body, "description"),
+            })),
+            // An optional body in another format is left out
+            None if !required => Ok(None),
+            None => Err("request body is not JSON".to_string()),
+        }
+    }
+
+    /// JSON schema of the first 2xx response
+    fn response_schema(&self, op: &Value) -> Option<Value> {
+        let responses = op.get("responses")?.as_object()?;
+        let response = ["200", "201", "202", "203", "206", "2XX"]
+            .iter()
+            .find_map(|status| responses.get(*status))?;
+        let response = self.resolve(response)?;
+        let (_, media) = response
+            .get("content")?
+            .as_object()?
+            .iter()
+            .find(|(content_type, _)| is_json(content_type))?;
+        media.get("schema").map(|s| self.inline(s, MAX_REF_DEPTH))
+    }
+
+    /// Follow a `$ref` to its target, or return the value itself
+    fn resolve<'a>(&'a self, value: &'a Value) -> Option<&'a Value> {
+        let mut current = value;
+        for _ in 0..MAX_REF_DEPTH {
+            match current.get("$ref").and_then(Value::as_str) {
+                Some(reference) => current = self.lookup(reference)?,
+                None => return Some(current),
+            }
+        }
+        None
+    }
+
+    fn lookup(&self, reference: &str) -> Option<&Value> {
+        // Only local references; `#/components/schemas/Pet` is a JSON pointer
+        self.doc.pointer(reference.strip_prefix('#')?)
+    }
+
+    /// Copy a schema with its `$ref`s replaced by their targets
+    fn inline(&self, value: &Value, depth: usize) -> Value {
+        match value {
+            Value::Object(map) => {
+                if let Some(reference) = map.get("$ref").and_then(Value::as_str) {
+                    return match self.lookup(reference) {
+                        Some(target) if depth > 0 => self.inline(target, depth - 1),
+                        _ => json!({}),
+                    };
+                }
+                Value::Object(
+                    map.iter()
+                        .map(|(k, v)| (k.clone(), self.inline(v, depth)))
+                        .collect(),
+                )
+            }
+            Value::Array(items) => {
+                Value::Array(items.iter().map(|v| self.inline(v, depth)).collect())
+            }
+            other => other.clone(),
+        }
+    }
+}
+
+/// Tool name from an operationId or `{method}_{path}`, keeping letters,
+/// digits, `_`, `-` and `.`
+pub fn tool_name(source: &str) -> String {
+    let mut name = String::new();
+    for c in source.chars() {
+        if c.is_ascii_alphanumeric() || matches!(c, '-' | '.') {
+            name.push(c);
+        } else if !name.is_empty() && !name.ends_with('_') {
+            name.push('_');
+        }
+    }
+    let name = name.trim_end_matches('_');
+    name.chars().take(MAX_TOOL_NAME).collect()
+}
+
+fn is_json(content_type: &str) -> bool {
+    let essence = content_type.split(';').next().unwrap_or("").trim();
+    essence == "application/json" || essence.ends_with("+json")
+}
+
+fn is_object_schema(schema: &Value) -> bool {
+    match schema.get("type") {
+        Some(kind) => kind == "object",
+        None => schema.get("properties").is_some(),
+    }
+}
+
+fn string(value: &Value, key: &str) -> Option<String> {
+    value.get(key).and_then(Value::as_str).map(str::to_string)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// A small pet store document
+    pub(crate) fn petstore() -> Spec {
+        let doc = json!({
+            "openapi": "3.0.3",
+            "servers": [{ "url": "https://{region}.pets.example.com/v1", "variables": { "region": { "default": "eu" } } }],
+            "security": [{ "bearerAuth": [] }],
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "operationId": "listPets",
+                        "summary": "List pets",
+                        "parameters": [
+                            { "name": "limit", "in": "query", "schema": { "type": "integer" } },
+                            { "name": "tags", "in": "query", "schema": { "type": "array", "items": { "type": "string" } } },
+                        ],
+                        "responses": { "200": { "description": "ok", "content": { "application/json": {
+                            "schema": { "type": "array", "items": { "$ref": "#/components/schemas/Pet" } }
+                        }}}},
+                    },
+                    "post": {
+                        "operationId": "createPet",
+                        "requestBody": { "required": true, "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Pet" } } } },
+                        "responses": { "201": { "$ref": "#/components/responses/PetResponse" } },
+                    },
+                },
+                "/pets/{petId}": {
+                    "parameters": [
+                        { "name": "petId", "in": "path", "schema": { "type": "string" } },
+                        { "name": "X-Trace", "in": "header", "schema": { "type": "string" } },
+                    ],
+                    "get": {
+                        "parameters": [{ "$ref": "#/components/parameters/Trace" }],
+                        "security": [{ "apiKey": [] }, {}],
+                        "responses": { "200": { "$ref": "#/components/responses/PetResponse" } },
+                    },
+                    "delete": { "operationId": "deletePet", "security": [], "responses": { "204": { "description": "gone" } } },
+                },
+                "/upload": {
+                    "put": {
+                        "operationId": "upload",
+                        "requestBody": { "required": true, "content": { "application/octet-stream": {} } },
+                        "responses": {},
+                    },
+                },
+            },
+            "components": {
+                "schemas": {
+                    "Pet": { "type": "object", "required": ["name"], "properties": {
+                        "name": { "type": "string" },
+                        "parent": { "$ref": "#/components/schemas/Pet" },
+                    }},
+                },
+                "parameters": {
+                    "Trace": { "name": "X-Trace", "in": "header", "required": true, "description": "Trace id", "schema": { "type": "string" } },
+                },
+                "responses": {
+                    "PetResponse": { "description": "a pet", "content": { "application/json; charset=utf-8": { "schema": { "$ref": "#/components/schemas/Pet" } } } },
+                },
+                "securitySchemes": {
+                    "bearerAuth": { "type": "http", "scheme": "bearer" },
+                    "apiKey": { "type": "apiKey", "in": "query", "name": "api_key" },
+                },
+            },
+        });
+        Spec::parse(&doc.to_string()).unwrap()
+    }
+
+    pub(crate) fn operation(spec: &Spec, tool: &str) -> Operation {
+        spec.operations()
+            .into_iter()
+            .flatten()
+            .find(|op| op.tool_name == tool)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_operations() {
+        let spec = petstore();
+        let names: Vec<String> = spec
+            .operations()
+            .into_iter()
+            .map(|op| op.map(|op| op.tool_name).unwrap_or_else(|e| e))
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "listPets",
+                "createPet",
+                "get_pets_petId",
+                "deletePet",
+                "PUT /upload: request body is not JSON"
+            ]
+        );
+        assert_eq!(spec.server_url().unwrap(), "https://eu.pets.example.com/v1");
+    }
+
+    #[test]
+    fn test_parameters_and_security() {
+        let spec = petstore();
+        let get = operation(&spec, "get_pets_petId");
+
+        // The operation's X-Trace replaces the path-level one
+        let args: Vec<(&str, bool)> = get
+            .parameters
+            .iter()
+            .map(|p| (p.arg.as_str(), p.required))
+            .collect();
+        assert_eq!(args, [("petId", true), ("X-Trace", true)]);
+        assert_eq!(
+            get.input_schema()["properties"]["X-Trace"]["description"],
+            "Trace id"
+        );
+
+        assert_eq!(get.security, [vec!["apiKey".to_string()], vec![]]);
+        assert!(operation(&spec, "deletePet").security.is_empty());
+        assert_eq!(
+            operation(&spec, "listPets").security,
+            [vec!["bearerAuth".to_string()]]
+        );
+    }
+
+    #[test]
+    fn test_schemas() {
+        let spec = petstore();
+        let create = operation(&spec, "createPet");
+        let input = create.input_schema();
+        assert_eq!(input["required"], json!(["body"]));
+        let body = &input["properties"]["body"];
+        assert_eq!(body["required"], json!(["name"]));
+        // Recursion ends in an empty schema
+        let mut nested = body;
+        for _ in 0..MAX_REF_DEPTH {
+            nested = &nested["properties"]["parent"];
+        }
+        assert_eq!(nested, &json!({}));
+
+        // Object responses are used as is, others wrapped
+        assert_eq!(create.output_schema().unwrap()["type"], "object");
+        let list = operation(&spec, "listPets");
+        assert_eq!(list.output_schema().unwrap()["required"], json!(["result"]));
+        assert_eq!(list.structured(json!([1])), json!({ "result": [1] }));
+        assert_eq!(
+            create.structured(json!({ "name": "Rex" })),
+            json!({ "name": "Rex" })
+        );
+        assert!(operation(&spec, "deletePet").output_schema().is_none());
+    }
+
+    #[test]
+    fn test_tool_name() {
+        assert_eq!(tool_name("get /users/{id}/posts"), "get_users_id_posts");
+        assert_eq!(tool_name("pets.list-all"), "pets.list-all");
+        assert_eq!(tool_name(&"a".repeat(200)).len(), MAX_TOOL_NAME);
+    }
+}
@@ -0,0 +1,299 @@
+//! Prompt includes
+//!
+//! A prompt can embed another registered prompt, so shared preambles are
+//! written once. A message whose content block carries
+//! `"_meta": {"wasmcp/include": "preamble"}` is replaced by the messages of
+//! the `preamble` prompt, fetched through the same path as prompts/get (this
+//! component's prompts, then downstream). The object form
+//! `{"name": "preamble", "arguments": {..}}` passes its own arguments;
+//! otherwise the including prompt's arguments are passed on.
+//!
+//! Includes resolve recursively. A prompt that includes itself, directly or
+//! through others, or a chain deeper than `MAX_DEPTH`, fails the request
+//! with an internal error naming the chain.
+
+use crate::bindings::wasmcp::mcp_v20251125::mcp::{
+    ContentBlock, ErrorCode, GetPromptRequest, GetPromptResult, PromptMessage,
+};
+use serde_json::Value;
+use wasmcp_errors::McpError;
+
+/// `_meta` field naming the prompt to include
+pub const META_FIELD: &str = "wasmcp/include";
+
+/// Longest chain of nested includes
+pub const MAX_DEPTH: usize = 8;
+
+/// A prompt to include in place of a message
+#[derive(Debug, PartialEq)]
+pub struct Include {
+    pub name: String,
+    /// JSON-encoded arguments; None passes the including prompt's arguments
+    pub arguments: Option<String>,
+}
+
+/// Include directive in a content block's `_meta`, if any
+pub fn directive(meta: &str) -> Result<Option<Include>, String> {
+    let Ok(Value::Object(meta)) = serde_json::from_str::<Value>(meta) else {
+        return Ok(None);
+    };
+    match meta.get(META_FIELD) {
+        None => Ok(None),
+        Some(Value::String(name)) if !name.is_empty() => Ok(Some(Include {
+            name: name.clone(),
+            arguments: None,
+        })),
+        Some(Value::Object(include)) => {
+            let name = match include.get("name") {
+                Some(Value::String(name)) if !name.is_empty() => name.clone(),
+                _ => return Err(format!("{} needs a prompt name", META_FIELD)),
+            };
+            let arguments = match include.get("arguments") {
+                None | Some(Value::Null) => None,
+                Some(arguments @ Value::Object(_)) => Some(arguments.to_string()),
+                Some(_) => return Err(format!("{} arguments must be an object", META_FIELD)),
+            };
+            Ok(Some(Include { name, arguments }))
+        }
+        Some(_) => Err(format!(
+            "{} must be a prompt name or {{\"name\", \"arguments\"}}",
+            META_FIELD
+        )),
+    }
+}
+
+/// Replace include messages in a prompt's result, recursively
+///
+/// `fetch` gets a prompt by name the way prompts/get does.
+pub fn expand(
+    request: &GetPromptRequest,
+    result: GetPromptResult,
+    fetch: &mut impl FnMut(&GetPromptRequest) -> Result<GetPromptResult, ErrorCode>,
+) -> Result<GetPromptResult, ErrorCode> {
+    // Most prompts include nothing; return them untouched
+    if !result
+        .messages
+        .iter()
+        .any(|m| content_meta(&m.content).is_some_and(|meta| meta.contains(META_FIELD)))
+    {
+        return Ok(result);
+    }
+
+    let mut chain = vec![request.name.clone()];
+    let messages = expand_messages(
+        result.messages,
+        request.arguments.as_deref(),
+        &mut chain,
+        fetch,
+    )?;
+    Ok(GetPromptResult { messages, ..result })
+}
+
+fn expand_messages(
+    messages: Vec<PromptMessage>,
+    arguments: Option<&str>,
+    chain: &mut Vec<String>,
+    fetch: &mut impl FnMut(&GetPromptRequest) -> Result<GetPromptResult, ErrorCode>,
+) -> Result<Vec<PromptMessage>, ErrorCode> {
+    let mut expanded = Vec::with_capacity(messages.len());
+    for message in messages {
+        let include = match content_meta(&message.content) {
+            Some(meta) => directive(meta)
+                .map_err(|e| McpError::internal(format!("Prompt '{}': {}", current(chain), e)))?,
+            None => None,
+        };
+        let Some(include) = include else {
+            expanded.push(message);
+            continue;
+        };
+
+        if chain.contains(&include.name) {
+            return Err(McpError::internal(format!(
+                "Prompt include cycle: {} -> {}",
+                chain.join(" -> "),
+                include.name
+            ))
+            .into());
+        }
+        if chain.len() >= MAX_DEPTH {
+            return Err(McpError::internal(format!(
+                "Prompt includes nested deeper than {}: {} -> {}",
+                MAX_DEPTH,
+                chain.join(" -> "),
+                include.name
+            ))
+            .into());
+        }
+
+        let request = GetPromptRequest {
+            name: include.name,
+            arguments: include.arguments.or_else(|| arguments.map(String::from)),
+        };
+        let included = fetch(&request).map_err(|e| match e {
+            // An unknown or misused include is the server's mistake, not the client's
+            ErrorCode::InvalidParams(e) => McpError::internal(format!(
+                "Prompt '{}' includes '{}': {}",
+                current(chain),
+                request.name,
+                e.message
+            ))
+            .into(),
+            other => other,
+        })?;
+
+        chain.push(request.name);
+        expanded.extend(expand_messages(
+            included.messages,
+            request.arguments.as_deref(),
+            chain,
+            fetch,
+        )?);
+        chain.pop();
+    }
+    Ok(expanded)
+}
+
+/// `_meta` of a content block
+fn content_meta(content: &ContentBlock) -> Option<&str> {
+    let meta = match content {
+        ContentBlock::Text(text) => text.options.as_ref()?.meta.as_ref(),
+        ContentBlock::Image(blob) | ContentBlock::Audio(blob) => {
+            blob.options.as_ref()?.meta.as_ref()
+        }
+        ContentBlock::ResourceLink(link) => link.options.as_ref()?.meta.as_ref(),
+        ContentBlock::EmbeddedResource(embedded) => embedded.options.as_ref()?.meta.as_ref(),
+    };
+    meta.map(String::as_str)
+}
+
+fn current(chain: &[String]) -> &str {
+    chain.last().map(String::as_str).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bindings::wasmcp::mcp_v20251125::mcp::{
+        ContentOptions, Role, TextContent, TextData,
+    };
+
+    fn text(text: &str, meta: Option<&str>) -> PromptMessage {
+        PromptMessage {
+            content: ContentBlock::Text(TextContent {
+                text: TextData::Text(text.to_string()),
+                options: meta.map(|meta| ContentOptions {
+                    annotations: None,
+                    meta: Some(meta.to_string()),
+                }),
+            }),
+            role: Role::User,
+        }
+    }
+
+    fn texts(result: &GetPromptResult) -> Vec<&str> {
+        result
+            .messages
+            .iter()
+            .map(|m| match &m.content {
+                ContentBlock::Text(TextContent {
+                    text: TextData::Text(text),
+                    ..
+                }) => text.as_str(),
+                _ => panic!("Expected text content"),
+            })
+            .collect()
+    }
+
+    fn prompt(messages: Vec<PromptMessage>) -> GetPromptResult {
+        GetPromptResult {
+            meta: None,
+            description: None,
+            messages,
+        }
+    }
+
+    #[test]
+    fn test_directive() {
+        assert_eq!(directive(r#"{"trace": "x"}"#), Ok(None));
+        assert_eq!(directive("not json"), Ok(None));
+        assert_eq!(
+            directive(r#"{"wasmcp/include": "preamble"}"#),
+            Ok(Some(Include {
+                name: "preamble".to_string(),
+                arguments: None
+            }))
+        );
+        assert_eq!(
+            directive(r#"{"wasmcp/include": {"name": "style", "arguments": {"tone": "formal"}}}"#),
+            Ok(Some(Include {
+                name: "style".to_string(),
+                arguments: Some(r#"{"tone":"formal"}"#.to_string())
+            }))
+        );
+        assert!(directive(r#"{"wasmcp/include": {"arguments": {}}}"#).is_err());
+        assert!(directive(r#"{"wasmcp/include": 3}"#).is_err());
+    }
+
+    #[test]
+    fn test_expand_nested_includes() {
+        let request = GetPromptRequest {
+            name: "review".to_string(),
+            arguments: Some(r#"{"lang":"rust"}"#.to_string()),
+        };
+        let result = prompt(vec![
+            text("", Some(r#"{"wasmcp/include": "preamble"}"#)),
+            text("Review this code", None),
+        ]);
+        let mut requested = Vec::new();
+        let mut fetch = |request: &GetPromptRequest| {
+            requested.push((request.name.clone(), request.arguments.clone()));
+            match request.name.as_str() {
+                "preamble" => Ok(prompt(vec![
+                    text("You are a reviewer", None),
+                    text(
+                        "",
+                        Some(r#"{"wasmcp/include": {"name": "style", "arguments": {}}}"#),
+                    ),
+                ])),
+                "style" => Ok(prompt(vec![text("Be brief", None)])),
+                _ => panic!("unexpected prompt"),
+            }
+        };
+
+        let expanded = expand(&request, result, &mut fetch).unwrap();
+        assert_eq!(
+            texts(&expanded),
+            ["You are a reviewer", "Be brief", "Review this code"]
+        );
+        assert_eq!(
+            requested,
+            [
+                (
+                    "preamble".to_string(),
+                    Some(r#"{"lang":"rust"}"#.to_string())
+                ),
+                ("style".to_string(), Some("{}".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_expand_detects_cycles() {
+        let request = GetPromptRequest {
+            name: "a".to_string(),
+            arguments: None,
+        };
+        let result = prompt(vec![text("", Some(r#"{"wasmcp/include": "b"}"#))]);
+        let mut fetch =
+            |_: &GetPromptRequest| Ok(prompt(vec![text("", Some(r#"{"wasmcp/include": "a"}"#))]));
+        match expand(&request, result, &mut fetch) {
+            Err(ErrorCode::InternalError(e)) => {
+                assert_eq!(e.message, "Prompt include cycle: a -> b -> a")
+            }
+            other => panic!(
+                "Expected a cycle error, got {:?}",
+                other.map(|r| r.messages.len())
+            ),
+        }
+    }
+}
@@ -5,6 +5,7 @@
 //! - Detects prompts/list and prompts/get requests
 //! - Calls the imported prompts interface functions
 //! - Merges results with downstream handlers
+//! - Expands prompts that include other prompts (see `includes`)
 //! - Delegates all other requests downstream
 
 #![allow(warnings)]
@@ -16,11 +17,15 @@ mod bindings {
     });
 }
 
+mod includes;
+
 use bindings::exports::wasmcp::mcp_v20251125::server_handler::{Guest, MessageContext};
 use bindings::wasmcp::mcp_v20251125::mcp::*;
 use bindings::wasmcp::mcp_v20251125::prompts;
 use bindings::wasmcp::mcp_v20251125::server_handler as downstream;
 
+wasmcp_errors::impl_error_code!(ErrorCode, Error);
+
 struct PromptsMiddleware;
 
 // Convert exported MessageContext to imported MessageContext
@@ -38,6 +43,13 @@ fn to_downstream_ctx<'a>(ctx: &'a MessageContext<'a>) -> downstream::MessageCont
         }),
         frame: ctx.frame.clone(),
         http_context: ctx.http_context.clone(),
+        client_capabilities: ctx.client_capabilities.clone(),
+        request_id: ctx.request_id.clone(),
+        deadline: ctx.deadline,
+        log_level: ctx.log_level,
+        client_info: ctx.client_info.clone(),
+        locale: ctx.locale.clone(),
+        trace_context: ctx.trace_context.clone(),
     }
 }
 
@@ -173,9 +185,33 @@ fn handle_prompt_get(
     request_id: RequestId,
     req: GetPromptRequest,
     ctx: &MessageContext,
+) -> Result<ServerResult, ErrorCode> {
+    match get_prompt(&request_id, &req, ctx)? {
+        ServerResult::PromptsGet(result) => {
+            // Included prompts are fetched the same way, from either source
+            let mut fetch =
+                |include: &GetPromptRequest| match get_prompt(&request_id, include, ctx)? {
+                    ServerResult::PromptsGet(result) => Ok(result),
+                    _ => Err(ErrorCode::InternalError(Error {
+                        code: -32603,
+                        message: "Unexpected response type from downstream handler".to_string(),
+                        data: None,
+                    })),
+                };
+            includes::expand(&req, result, &mut fetch).map(ServerResult::PromptsGet)
+        }
+        other => Ok(other),
+    }
+}
+
+/// Get a prompt from the imported prompts interface or downstream
+fn get_prompt(
+    request_id: &RequestId,
+    req: &GetPromptRequest,
+    ctx: &MessageContext,
 ) -> Result<ServerResult, ErrorCode> {
     // Try getting from imported prompts interface first
-    match prompts::get_prompt(&to_downstream_ctx(ctx), &req) {
+    match prompts::get_prompt(&to_downstream_ctx(ctx), req) {
         Ok(Some(result)) => {
             // Imported interface handled it - return the result
             Ok(ServerResult::PromptsGet(result))
@@ -183,7 +219,7 @@ fn handle_prompt_get(
         Ok(None) => {
             // Imported interface doesn't handle this prompt - try downstream
             let downstream_req = ClientRequest::PromptsGet(req.clone());
-            let downstream_msg = ClientMessage::Request((request_id, downstream_req));
+            let downstream_msg = ClientMessage::Request((request_id.clone(), downstream_req));
             match downstream::handle(&to_downstream_ctx(ctx), downstream_msg) {
                 Some(Ok(response)) => Ok(response),
                 Some(Err(ErrorCode::MethodNotFound(_))) | None => {
@@ -5,6 +5,7 @@
 //! - Detects resources/list, resources/read, and resources/templates/list requests
 //! - Calls the imported resources interface functions
 //! - Merges results with downstream handlers
+//! - Answers the experimental resources/search method (see `search`)
 //! - Delegates all other requests downstream
 
 #![allow(warnings)]
@@ -16,6 +17,8 @@ mod bindings {
     });
 }
 
+mod search;
+
 use bindings::exports::wasmcp::mcp_v20251125::server_handler::{Guest, MessageContext};
 use bindings::wasmcp::mcp_v20251125::mcp::*;
 use bindings::wasmcp::mcp_v20251125::resources;
@@ -38,6 +41,13 @@ fn to_downstream_ctx<'a>(ctx: &'a MessageContext<'a>) -> downstream::MessageCont
         }),
         frame: ctx.frame.clone(),
         http_context: ctx.http_context.clone(),
+        client_capabilities: ctx.client_capabilities.clone(),
+        request_id: ctx.request_id.clone(),
+        deadline: ctx.deadline,
+        log_level: ctx.log_level,
+        client_info: ctx.client_info.clone(),
+        locale: ctx.locale.clone(),
+        trace_context: ctx.trace_context.clone(),
     }
 }
 
@@ -59,6 +69,12 @@ impl Guest for ResourcesMiddleware {
                     ClientRequest::ResourcesTemplatesList(templates_req) => {
                         handle_templates_list(request_id.clone(), templates_req.clone(), &ctx)
                     }
+                    ClientRequest::Custom(custom_req) if custom_req.method == search::METHOD => {
+                        handle_resources_search(request_id.clone(), custom_req.clone(), &ctx)
+                    }
+                    ClientRequest::Initialize(init_req) => {
+                        return handle_initialize(request_id.clone(), init_req.clone(), &ctx);
+                    }
                     _ => {
                         // Delegate all other requests to downstream handler
                         let downstream_msg = ClientMessage::Request((request_id.clone(), request));
@@ -306,4 +322,137 @@ fn handle_templates_list(
     }
 }
 
+fn handle_resources_search(
+    request_id: RequestId,
+    req: CustomRequest,
+    ctx: &MessageContext,
+) -> Result<ServerResult, ErrorCode> {
+    let params = search::SearchParams::parse(req.params.as_deref()).map_err(|message| {
+        ErrorCode::InvalidParams(Error {
+            code: -32602,
+            message,
+            data: None,
+        })
+    })?;
+
+    // Our resources, every page
+    let ours = list_all(
+        |req| match resources::list_resources(&to_downstream_ctx(ctx), req) {
+            Ok(result) => Ok(result),
+            // Component doesn't implement resources interface - nothing to match
+            Err(ErrorCode::MethodNotFound(_)) => Ok(ListResourcesResult {
+                meta: None,
+                next_cursor: None,
+                resources: Vec::new(),
+            }),
+            Err(e) => Err(e),
+        },
+    )?;
+    let matches = search::rank(&params.query, &ours);
+
+    // A downstream resources-middleware or custom handler searches what's below us
+    let downstream_msg = ClientMessage::Request((request_id.clone(), ClientRequest::Custom(req)));
+    let downstream = match downstream::handle(&to_downstream_ctx(ctx), downstream_msg) {
+        Some(Ok(ServerResult::Custom(result))) => search::downstream_resources(&result),
+        Some(Err(ErrorCode::MethodNotFound(_))) | Some(Ok(_)) | None => {
+            // Nobody below searches - match downstream's listing ourselves
+            let listed = list_all(|req| {
+                let downstream_msg = ClientMessage::Request((
+                    request_id.clone(),
+                    ClientRequest::ResourcesList(req.clone()),
+                ));
+                match downstream::handle(&to_downstream_ctx(ctx), downstream_msg) {
+                    Some(Ok(ServerResult::ResourcesList(result))) => Ok(result),
+                    Some(Err(e)) if !matches!(e, ErrorCode::MethodNotFound(_)) => Err(e),
+                    _ => Ok(ListResourcesResult {
+                        meta: None,
+                        next_cursor: None,
+                        resources: Vec::new(),
+                    }),
+                }
+            })?;
+            let found = search::rank(&params.query, &listed);
+            return Ok(ServerResult::Custom(search::result(
+                &matches.into_iter().chain(found).collect::<Vec<_>>(),
+                Vec::new(),
+                params.limit,
+            )));
+        }
+        Some(Err(e)) => return Err(e),
+    };
+
+    Ok(ServerResult::Custom(search::result(
+        &matches,
+        downstream,
+        params.limit,
+    )))
+}
+
+/// Follow resources/list cursors to the last page
+fn list_all(
+    mut list: impl FnMut(&ListResourcesRequest) -> Result<ListResourcesResult, ErrorCode>,
+) -> Result<Vec<McpResource>, ErrorCode> {
+    // Guards against a handler that never stops returning cursors
+    const MAX_PAGES: usize = 100;
+
+    let mut resources = Vec::new();
+    let mut cursor = None;
+    for _ in 0..MAX_PAGES {
+        let page = list(&ListResourcesRequest { cursor })?;
+        resources.extend(page.resources);
+        cursor = page.next_cursor;
+        if cursor.is_none() {
+            break;
+        }
+    }
+    Ok(resources)
+}
+
+fn handle_initialize(
+    request_id: RequestId,
+    req: InitializeRequest,
+    ctx: &MessageContext,
+) -> Option<Result<ServerResult, ErrorCode>> {
+    let downstream_req = ClientRequest::Initialize(req.clone());
+    let downstream_msg = ClientMessage::Request((request_id, downstream_req));
+    let search_capability = (search::METHOD.to_string(), "{}".to_string());
+
+    match downstream::handle(&to_downstream_ctx(ctx), downstream_msg) {
+        Some(Ok(ServerResult::Initialize(mut result))) => {
+            // Advertise resources/search once, however many of us are composed
+            let experimental = result
+                .capabilities
+                .experimental
+                .get_or_insert_with(Vec::new);
+            if !experimental.iter().any(|(name, _)| name == search::METHOD) {
+                experimental.push(search_capability);
+            }
+            Some(Ok(ServerResult::Initialize(result)))
+        }
+        _ => {
+            // Nobody else answered - contribute capabilities only, as custom-middleware does
+            Some(Ok(ServerResult::Initialize(InitializeResult {
+                meta: None,
+                server_info: Implementation {
+                    name: String::new(),
+                    title: None,
+                    version: String::new(),
+                    description: None,
+                    icons: None,
+                },
+                capabilities: ServerCapabilities {
+                    completions: None,
+                    experimental: Some(vec![search_capability]),
+                    logging: None,
+                    list_changed: None,
+                    subscriptions: None,
+                    lists: None,
+                },
+                protocol_version: req.protocol_version,
+                options: None,
+            })))
+        }
+    }
+}
+
 bindings::export!(ResourcesMiddleware with_types_in bindings);
@@ -0,0 +1,230 @@
+//! Experimental resources/search
+//!
+//! `resources/search` takes `{"query": "...", "limit": 10}` and answers
+//! `{"resources": [...]}`, best matches first. It is advertised under
+//! `capabilities.experimental`, and reaches the chain as a custom request, so
+//! a handler exporting the `custom` interface can answer it with its own
+//! search (full text, embeddings) for the resources below it.
+//!
+//! Otherwise each resources-middleware matches its own resources by name,
+//! title, URI and description, case-insensitively, and ranks them:
+//! exact name, name prefix, name substring, then title, URI and description
+//! substrings. Downstream answers follow in the order they came, without
+//! resources already listed.
+
+use crate::bindings::wasmcp::mcp_v20251125::mcp::McpResource;
+use serde_json::{Map, Value, json};
+
+/// Method name, also the experimental capability key
+pub const METHOD: &str = "resources/search";
+
+/// Results returned when the request sets no limit
+pub const DEFAULT_LIMIT: usize = 50;
+
+/// A parsed resources/search request
+#[derive(Debug, PartialEq)]
+pub struct SearchParams {
+    pub query: String,
+    pub limit: usize,
+}
+
+impl SearchParams {
+    /// Parse the request's JSON params
+    pub fn parse(params: Option<&str>) -> Result<Self, String> {
+        let params: Value = match params {
+            Some(p) => serde_json::from_str(p).map_err(|e| format!("Invalid params: {}", e))?,
+            None => Value::Null,
+        };
+        let query = match params.get("query") {
+            Some(Value::String(q)) if !q.trim().is_empty() => q.trim().to_string(),
+            _ => return Err("resources/search needs a non-empty query string".to_string()),
+        };
+        let limit = match params.get("limit") {
+            None | Some(Value::Null) => DEFAULT_LIMIT,
+            Some(limit) => match limit.as_u64() {
+                Some(n) if n > 0 => n as usize,
+                _ => return Err("resources/search limit must be a positive integer".to_string()),
+            },
+        };
+        Ok(SearchParams { query, limit })
+    }
+}
+
+/// Rank of a resource for a query; None if it doesn't match
+pub fn score(query: &str, resource: &McpResource) -> Option<u8> {
+    let query = query.to_lowercase();
+    let name = resource.name.to_lowercase();
+    let options = resource.options.as_ref();
+    let contains =
+        |field: Option<&String>| field.is_some_and(|f| f.to_lowercase().contains(&query));
+
+    if name == query {
+        Some(0)
+    } else if name.starts_with(&query) {
+        Some(1)
+    } else if name.contains(&query) {
+        Some(2)
+    } else if contains(options.and_then(|o| o.title.as_ref())) {
+        Some(3)
+    } else if contains(Some(&resource.uri)) {
+        Some(4)
+    } else if contains(options.and_then(|o| o.description.as_ref())) {
+        Some(5)
+    } else {
+        None
+    }
+}
+
+/// Matching resources, best first; ties keep listing order
+pub fn rank<'a>(query: &str, resources: &'a [McpResource]) -> Vec<&'a McpResource> {
+    let mut matches: Vec<(u8, &McpResource)> = resources
+        .iter()
+        .filter_map(|r| score(query, r).map(|s| (s, r)))
+        .collect();
+    matches.sort_by_key(|(score, _)| *score);
+    matches.into_iter().map(|(_, r)| r).collect()
+}
+
+/// Search result: our matches, then downstream's, up to `limit`
+///
+/// A URI already in the result is not repeated.
+pub fn result(ours: &[&McpResource], downstream: Vec<Value>, limit: usize) -> String {
+    let mut resources: Vec<Value> = Vec::new();
+    for resource in ours.iter().map(|r| to_json(r)).chain(downstream) {
+        let uri = resource.get("uri");
+        if uri.is_none() || !resources.iter().any(|r| r.get("uri") == uri) {
+            resources.push(resource);
+        }
+    }
+    resources.truncate(limit);
+    json!({ "resources": resources }).to_string()
+}
+
+/// Resources in a downstream resources/search answer
+pub fn downstream_resources(result: &str) -> Vec<Value> {
+    match serde_json::from_str::<Value>(result) {
+        Ok(Value::Object(mut result)) => match result.remove("resources") {
+            Some(Value::Array(resources)) => resources,
+            _ => Vec::new(),
+        },
+        _ => Vec::new(),
+    }
+}
+
+/// A resource as it appears in resources/list
+fn to_json(resource: &McpResource) -> Value {
+    let mut json = Map::new();
+    json.insert("uri".to_string(), json!(resource.uri));
+    json.insert("name".to_string(), json!(resource.name));
+    if let Some(options) = &resource.options {
+        let fields = [
+            ("title", options.title.as_ref().map(|v| json!(v))),
+            (
+                "description",
+                options.description.as_ref().map(|v| json!(v)),
+            ),
+            ("mimeType", options.mime_type.as_ref().map(|v| json!(v))),
+            ("size", options.size.map(|v| json!(v))),
+            (
+                "_meta",
+                options
+                    .meta
+                    .as_ref()
+                    .and_then(|m| serde_json::from_str::<Value>(m).ok()),
+            ),
+        ];
+        for (key, value) in fields {
+            if let Some(value) = value {
+                json.insert(key.to_string(), value);
+            }
+        }
+    }
+    Value::Object(json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bindings::wasmcp::mcp_v20251125::mcp::ResourceOptions;
+
+    fn resource(
+        uri: &str,
+        name: &str,
+        title: Option<&str>,
+        description: Option<&str>,
+    ) -> McpResource {
+        McpResource {
+            uri: uri.to_string(),
+            name: name.to_string(),
+            options: Some(ResourceOptions {
+                size: None,
+                title: title.map(String::from),
+                description: description.map(String::from),
+                mime_type: None,
+                annotations: None,
+                meta: None,
+                icons: None,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            SearchParams::parse(Some(r#"{"query":" readme "}"#)),
+            Ok(SearchParams {
+                query: "readme".to_string(),
+                limit: DEFAULT_LIMIT
+            })
+        );
+        assert_eq!(
+            SearchParams::parse(Some(r#"{"query":"readme","limit":3}"#)).map(|p| p.limit),
+            Ok(3)
+        );
+        assert!(SearchParams::parse(None).is_err());
+        assert!(SearchParams::parse(Some(r#"{"query":""}"#)).is_err());
+        assert!(SearchParams::parse(Some(r#"{"query":"a","limit":0}"#)).is_err());
+    }
+
+    #[test]
+    fn test_rank() {
+        let resources = [
+            resource("file:///notes.md", "notes", None, Some("Config notes")),
+            resource("file:///config.toml", "app-config", None, None),
+            resource("file:///settings.json", "settings", Some("Config"), None),
+            resource("config://main", "main", None, None),
+            resource("file:///config", "Config", None, None),
+            resource("file:///cfg", "configuration", None, None),
+            resource("file:///other", "other", None, None),
+        ];
+        let names: Vec<&str> = rank("config", &resources)
+            .iter()
+            .map(|r| r.name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "Config",
+                "configuration",
+                "app-config",
+                "settings",
+                "main",
+                "notes"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_result_merges_downstream() {
+        let ours = [resource("file:///a", "a", None, None)];
+        let ours: Vec<&McpResource> = ours.iter().collect();
+        let downstream = downstream_resources(
+            r#"{"resources":[{"uri":"file:///a","name":"a"},{"uri":"file:///b","name":"b"},{"uri":"file:///c","name":"c"}]}"#,
+        );
+        let result: Value = serde_json::from_str(&result(&ours, downstream, 2)).unwrap();
+        assert_eq!(
+            result,
+            json!({"resources": [{"uri": "file:///a", "name": "a"}, {"uri": "file:///b", "name": "b"}]})
+        );
+    }
+}
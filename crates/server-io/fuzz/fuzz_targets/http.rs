@@ -0,0 +1,47 @@
+#![no_main]
+
+//! HTTP request bodies, and SSE events split across arbitrary read chunks
+//!
+//! The first byte picks the chunk size. The boundary-aware delimiter search
+//! must find the same event end as a search over the whole buffer.
+
+use libfuzzer_sys::fuzz_target;
+use server_io::fuzzing::{
+    MessageFrame, find_subsequence, parse_framed_message, search_with_boundary,
+};
+
+const SSE_DELIMITER: &[u8] = b"\n\n";
+
+fuzz_target!(|data: &[u8]| {
+    let Some((&chunk_size, body)) = data.split_first() else {
+        return;
+    };
+    let chunk_size = usize::from(chunk_size).max(1);
+
+    let plain = MessageFrame {
+        prefix: vec![],
+        suffix: vec![],
+    };
+    for strict in [false, true] {
+        let _ = parse_framed_message(body, &plain, strict);
+    }
+
+    let mut buffer = Vec::new();
+    let mut found = None;
+    for chunk in body.chunks(chunk_size) {
+        buffer.extend_from_slice(chunk);
+        if let Some((pos, _)) = search_with_boundary(&buffer, chunk.len(), SSE_DELIMITER) {
+            found = Some(pos);
+            break;
+        }
+    }
+    assert_eq!(found, find_subsequence(body, SSE_DELIMITER));
+
+    if let Some(pos) = found {
+        let sse = MessageFrame {
+            prefix: b"data: ".to_vec(),
+            suffix: SSE_DELIMITER.to_vec(),
+        };
+        let _ = parse_framed_message(&body[..pos + SSE_DELIMITER.len()], &sse, false);
+    }
+});
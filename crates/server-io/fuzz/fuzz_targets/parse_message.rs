@@ -0,0 +1,32 @@
+#![no_main]
+
+//! Arbitrary bytes through the full parse path, under each transport framing
+
+use libfuzzer_sys::fuzz_target;
+use server_io::fuzzing::{MessageFrame, parse_framed_message};
+
+fuzz_target!(|data: &[u8]| {
+    let frames = [
+        // HTTP request body
+        MessageFrame {
+            prefix: vec![],
+            suffix: vec![],
+        },
+        // stdio line
+        MessageFrame {
+            prefix: vec![],
+            suffix: b"\n".to_vec(),
+        },
+        // SSE event
+        MessageFrame {
+            prefix: b"data: ".to_vec(),
+            suffix: b"\n\n".to_vec(),
+        },
+    ];
+
+    for frame in &frames {
+        for strict in [false, true] {
+            let _ = parse_framed_message(data, frame, strict);
+        }
+    }
+});
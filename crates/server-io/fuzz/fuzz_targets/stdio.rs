@@ -0,0 +1,45 @@
+#![no_main]
+
+//! Newline-delimited stdio input, split into arbitrary read chunks
+//!
+//! The first byte picks the chunk size. Messages are split off the way
+//! `read_until_byte` does, checked against the input, then parsed.
+
+use libfuzzer_sys::fuzz_target;
+use server_io::fuzzing::{MessageFrame, parse_framed_message, take_delimited};
+
+fuzz_target!(|data: &[u8]| {
+    let Some((&chunk_size, input)) = data.split_first() else {
+        return;
+    };
+    let chunk_size = usize::from(chunk_size).max(1);
+    let frame = MessageFrame {
+        prefix: vec![],
+        suffix: b"\n".to_vec(),
+    };
+
+    let mut pending = Vec::new();
+    let mut messages = Vec::new();
+    for chunk in input.chunks(chunk_size) {
+        let mut scanned = pending.len();
+        pending.extend_from_slice(chunk);
+        while let Some(message) = take_delimited(&mut pending, scanned, b'\n') {
+            messages.push(message);
+            scanned = 0;
+        }
+    }
+
+    // Every message is one complete line, and nothing is lost or reordered
+    for message in &messages {
+        assert_eq!(
+            message.iter().position(|&b| b == b'\n'),
+            Some(message.len() - 1)
+        );
+    }
+    assert!(!pending.contains(&b'\n'));
+    assert_eq!([messages.concat(), pending].concat(), input);
+
+    for message in &messages {
+        let _ = parse_framed_message(message, &frame, false);
+    }
+});
@@ -0,0 +1,95 @@
+//! Per-content-type size limits for blob data
+//!
+//! Blob content (images, audio, blob resources) is base64-encoded into the
+//! response. Unless it is a blob stream written incrementally (see
+//! `stream_writer`), a large blob costs memory for the encoded copy on top
+//! of whatever the handler holds. Limits are configured with
+//! `WASMCP_BLOB_LIMITS`, a comma-separated list of `mime-type=bytes` rules:
+//!
+//! ```text
+//! WASMCP_BLOB_LIMITS="image/*=10485760,application/pdf=5242880,*=20971520"
+//! ```
+//!
+//! An exact MIME type wins over a `type/*` wildcard, which wins over `*`.
+//! Without a `*` rule the default limit (50MB) applies. Blobs over their
+//! limit are replaced by a text block explaining why, instead of failing
+//! the whole response. An incrementally written stream is only found to be
+//! over its limit mid-write, so it fails the message instead.
+
+use crate::stream_reader::StreamConfig;
+
+const BLOB_LIMITS_ENV: &str = "WASMCP_BLOB_LIMITS";
+
+thread_local! {
+    static LIMITS: BlobLimits = BlobLimits::from_env();
+}
+
+#[derive(Debug)]
+pub struct BlobLimits {
+    default: u64,
+    /// (pattern, max bytes); patterns are lowercase
+    rules: Vec<(String, u64)>,
+}
+
+impl BlobLimits {
+    fn from_env() -> Self {
+        let spec = std::env::var(BLOB_LIMITS_ENV).unwrap_or_default();
+        Self::parse(&spec)
+    }
+
+    pub(crate) fn parse(spec: &str) -> Self {
+        let mut limits = BlobLimits {
+            default: StreamConfig::default().max_size,
+            rules: Vec::new(),
+        };
+
+        for rule in spec.split(',').map(str::trim).filter(|r| !r.is_empty()) {
+            let parsed = rule
+                .split_once('=')
+                .and_then(|(pattern, bytes)| Some((pattern.trim(), bytes.trim().parse().ok()?)))
+                .filter(|(pattern, _)| !pattern.is_empty());
+            match parsed {
+                Some(("*", bytes)) => limits.default = bytes,
+                Some((pattern, bytes)) => limits.rules.push((pattern.to_ascii_lowercase(), bytes)),
+                None => eprintln!(
+                    "[server-io] WARNING: Invalid {} rule '{}', expected mime-type=bytes. Ignoring.",
+                    BLOB_LIMITS_ENV, rule
+                ),
+            }
+        }
+
+        limits
+    }
+
+    /// Maximum decoded size in bytes for a blob of this MIME type
+    pub fn max_bytes(&self, mime_type: Option<&str>) -> u64 {
+        let Some(mime_type) = mime_type else {
+            return self.default;
+        };
+        // Ignore parameters such as "; charset=utf-8"
+        let mime_type = mime_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+        let wildcard = mime_type
+            .split_once('/')
+            .map(|(top, _)| format!("{}/*", top));
+
+        let find = |pattern: &str| {
+            self.rules
+                .iter()
+                .find(|(p, _)| p == pattern)
+                .map(|(_, bytes)| *bytes)
+        };
+        find(&mime_type)
+            .or_else(|| wildcard.as_deref().and_then(find))
+            .unwrap_or(self.default)
+    }
+}
+
+/// Limit for a MIME type under the configured rules
+pub fn max_bytes(mime_type: Option<&str>) -> u64 {
+    LIMITS.with(|limits| limits.max_bytes(mime_type))
+}
@@ -21,18 +21,36 @@ mod bindings {
     });
 }
 
+mod blob_limits;
 mod framing;
 mod message_dispatch;
+mod outbox;
 mod parser;
+mod pending;
 mod reading;
+mod recording;
 mod serialization;
 mod serializer;
 mod stream_reader;
+mod stream_writer;
+mod strict;
 mod writing;
 
 #[cfg(test)]
 mod tests;
 
+/// Pure parse paths, exposed for the fuzz targets in `fuzz/`
+///
+/// Not part of the component interface; nothing here touches wasi, so it
+/// runs natively.
+#[doc(hidden)]
+pub mod fuzzing {
+    pub use crate::bindings::exports::wasmcp::mcp_v20251125::server_io::{IoError, MessageFrame};
+    pub use crate::bindings::wasmcp::mcp_v20251125::mcp::ClientMessage;
+    pub use crate::message_dispatch::parse_framed_message;
+    pub use crate::reading::{find_subsequence, search_with_boundary, take_delimited};
+}
+
 use bindings::exports::wasmcp::mcp_v20251125::server_io::{
     Guest, IoError, MessageFrame, ReadLimit,
 };
@@ -63,25 +81,15 @@ impl Guest for ServerIo {
             ReadLimit::MaxBytes(max) => reading::read_max_bytes(input, max)?,
         };
 
-        // Strip framing prefix and suffix
-        let json_bytes = framing::strip_framing(&raw_bytes, &frame)?;
-
-        // Convert to UTF-8 string
-        let json_str = String::from_utf8(json_bytes)
-            .map_err(|e| IoError::InvalidJsonrpc(format!("Invalid UTF-8: {}", e)))?;
-
-        // Parse JSON
-        let json: serde_json::Value = serde_json::from_str(&json_str)
-            .map_err(|e| IoError::InvalidJsonrpc(format!("Invalid JSON: {}", e)))?;
-
-        // Determine message type and parse
-        message_dispatch::parse_client_message(&json)
+        recording::record_request(&raw_bytes, &frame);
+        message_dispatch::parse_framed_message(&raw_bytes, &frame, strict::enabled())
     }
 
     /// Send a message to the client
     ///
     /// Serializes the server-message variant to JSON-RPC format, applies framing,
-    /// and writes to the output stream.
+    /// and writes to the output stream. Messages that fail to write on a
+    /// session's SSE stream are queued in the session outbox (see `outbox.rs`).
     fn send_message(
         output: &OutputStream,
         message: ServerMessage,
@@ -95,20 +103,54 @@ impl Guest for ServerIo {
             }
         }
 
+        // Each event on a session's stream gets its own id
+        let frame = outbox::number(frame);
+
+        // Responses to server requests are matched by the transport
+        pending::record(&message, &frame);
+
+        // Large lists and blob streams are written while they are serialized
+        if let Some(result) = stream_writer::try_send(output, &message, &frame) {
+            if result.is_err()
+                && stream_writer::can_retry(&message)
+                && let Ok(framed) = framing::serialize_message_to_bytes(message, &frame)
+                && outbox::store(&framed, &frame)
+            {
+                return Ok(());
+            }
+            return result;
+        }
+
         // Get framed bytes
         let framed = framing::serialize_message_to_bytes(message, &frame)?;
+        recording::record_response(&framed, &frame);
 
-        // Write to stream
-        writing::write_bytes(output, &framed, &frame)?;
-        Ok(())
+        // The transport writes held messages once it has chosen how to answer
+        if writing::hold(&framed, &frame) {
+            return Ok(());
+        }
+
+        // Write to stream; if the client went away, keep the message for
+        // when it reconnects
+        match writing::write_bytes(output, &framed, &frame) {
+            Err(_) if outbox::store(&framed, &frame) => Ok(()),
+            result => result,
+        }
     }
 
     /// Flush buffered data to stream (for buffered mode)
     ///
     /// In buffered mode (plain JSON framing), all writes accumulate in memory.
-    /// This function writes the entire buffer to the stream in one blocking operation.
-    /// For SSE/stdio (streaming modes), this is a no-op.
+    /// This function writes the entire buffer to the stream, waiting for
+    /// capacity as needed. For SSE/stdio (streaming modes), this is a no-op.
+    ///
+    /// Held messages are written and holding ends: a single message without
+    /// its frame, several with their frames.
     fn flush_buffer(output: &OutputStream) -> Result<(), IoError> {
+        if let Some(held) = writing::take_held() {
+            return writing::write_all(output, &held);
+        }
+
         // Check if there's anything buffered
         let has_data = writing::BUFFER.with(|buf| !buf.borrow().is_empty());
 
@@ -121,16 +163,64 @@ impl Guest for ServerIo {
             borrowed.clone()
         });
 
-        output.blocking_write_and_flush(&data).map_err(|e| {
-            eprintln!("[SERVER-IO] blocking_write_and_flush failed: {:?}", e);
-            IoError::Stream(e)
-        })?;
+        // blocking-write-and-flush takes at most 4096 bytes per call, so
+        // larger responses go through the streaming writer
+        writing::write_all(output, &data)?;
 
         // Clear buffer after successful write
         writing::BUFFER.with(|buf| buf.borrow_mut().clear());
 
         Ok(())
     }
+
+    /// Hold every message sent until the next flush
+    fn hold_messages() {
+        writing::start_holding();
+    }
+
+    /// Number of messages held since `hold_messages`
+    fn held_messages() -> u32 {
+        writing::held_count()
+    }
+
+    /// Parse a complete message from bytes, with no framing to strip
+    fn decode_message(bytes: Vec<u8>) -> Result<ClientMessage, IoError> {
+        let frame = unframed();
+        recording::record_request(&bytes, &frame);
+        message_dispatch::parse_framed_message(&bytes, &frame, strict::enabled())
+    }
+
+    /// Serialize a message to bytes for the transport to deliver
+    fn encode_message(message: ServerMessage) -> Result<Vec<u8>, IoError> {
+        let frame = unframed();
+        let bytes = framing::serialize_message_to_bytes(message, &frame)?;
+        recording::record_response(&bytes, &frame);
+        Ok(bytes)
+    }
+
+    /// Queue a message in a session's outbox, for its listening stream
+    fn queue_message(session_id: String, message: ServerMessage) -> Result<(), IoError> {
+        let frame = outbox::session_frame(&session_id)
+            .map_err(|e| IoError::Unexpected(format!("Cannot queue message: {}", e)))?;
+        let frame = outbox::number(frame);
+        let framed = framing::serialize_message_to_bytes(message, &frame)?;
+        outbox::queue(&session_id, &framed).map_err(IoError::Unexpected)
+    }
+
+    /// Set the protocol version errors are serialized for
+    fn set_protocol_version(version: ProtocolVersion) {
+        serialization::error_codes::set_protocol_version(version);
+    }
+}
+
+fn unframed() -> MessageFrame {
+    MessageFrame {
+        prefix: Vec::new(),
+        suffix: Vec::new(),
+    }
 }
 
+// Export names contain ':', which native linkers reject; only the component
+// needs them, and leaving them out lets the crate link natively for fuzzing
+#[cfg(target_family = "wasm")]
 bindings::export!(ServerIo with_types_in bindings);
@@ -6,9 +6,39 @@
 //! - Result (has "id" + "result")
 //! - Error (has "id" + "error")
 
-use crate::bindings::exports::wasmcp::mcp_v20251125::server_io::IoError;
+use crate::bindings::exports::wasmcp::mcp_v20251125::server_io::{IoError, MessageFrame};
 use crate::bindings::wasmcp::mcp_v20251125::mcp::ClientMessage;
+use crate::framing;
 use crate::parser;
+use crate::strict;
+
+/// Parse one framed message, as read from the stream, into a client-message
+///
+/// Pure: strict mode is passed in rather than read from the environment, so
+/// the whole parse path can run outside a component (see `fuzzing`).
+pub fn parse_framed_message(
+    raw: &[u8],
+    frame: &MessageFrame,
+    strict: bool,
+) -> Result<ClientMessage, IoError> {
+    // Strip framing prefix and suffix
+    let json_bytes = framing::strip_framing(raw, frame)?;
+
+    // Convert to UTF-8 string
+    let json_str = String::from_utf8(json_bytes)
+        .map_err(|e| IoError::InvalidJsonrpc(format!("Invalid UTF-8: {}", e)))?;
+
+    // Parse JSON
+    let json: serde_json::Value = serde_json::from_str(&json_str)
+        .map_err(|e| IoError::InvalidJsonrpc(format!("Invalid JSON: {}", e)))?;
+
+    if strict {
+        strict::validate(&json)?;
+    }
+
+    // Determine message type and parse
+    parse_client_message(&json)
+}
 
 /// Parse JSON into client-message variant
 ///
@@ -0,0 +1,299 @@
+//! Per-session outbox for messages the client did not receive
+//!
+//! When an SSE stream closes mid-request (the client went away), messages
+//! still being sent on it - notifications, the final response - fail to
+//! write. If the frame carries an SSE event id (`id: <session>\n` before
+//! `data: `, set by the transport for requests with a session), the framed
+//! event is queued in the session bucket instead of being lost. The
+//! transport replays and clears the queue when the client reconnects with
+//! `GET` and `Last-Event-ID`.
+//!
+//! Event ids must be unique within a session, so each event sent on such a
+//! frame is numbered: its id becomes `<session>:<n>`, with `n` taken from the
+//! session's event counter (`{session}:outbox:events`). A client resuming
+//! with `Last-Event-ID` gets only the queued events numbered after it.
+//!
+//! Environment variables:
+//! - `WASMCP_OUTBOX_MAX_EVENTS`: Events kept per session (default: 100, 0
+//!   disables the outbox)
+//! - `WASMCP_OUTBOX_MAX_BYTES`: Bytes kept per session (default: 1MB)
+//! - `WASMCP_SESSION_BUCKET`: Bucket holding the queue (default: "", as the
+//!   transport)
+//!
+//! Handlers also queue messages here on purpose, with `queue-message`, to
+//! reach a session's client from any instance: the transport instance
+//! holding the session's listening stream (GET) polls the queue, so
+//! delivery doesn't depend on which instance handled the triggering request.
+//!
+//! When a limit is exceeded the oldest events are evicted, so a client that
+//! comes back late still gets the most recent messages, including the
+//! response.
+//!
+//! Several instances may append to one session's queue at once, and one
+//! drains it meanwhile, so the queue is not a single value that each of them
+//! rewrites. Every event is stored under its own slot key,
+//! `{session}:outbox:{n}`, with `n` taken from an atomic counter
+//! (`{session}:outbox:seq`). `{session}:outbox:head` is the first slot that
+//! may still hold an event: eviction and the transport advance it past the
+//! slots they removed, and `{session}:outbox:bytes` counts what is queued.
+//! All of it is removed with the session.
+
+use crate::bindings::exports::wasmcp::mcp_v20251125::server_io::MessageFrame;
+use crate::bindings::wasi::cli::environment::get_environment;
+use crate::bindings::wasmcp::keyvalue::store;
+use std::collections::HashMap;
+
+const DEFAULT_MAX_EVENTS: usize = 100;
+const DEFAULT_MAX_BYTES: usize = 1024 * 1024;
+
+/// Session key prefix of the queue (see the transport's `session_keys::OUTBOX`)
+const OUTBOX_KEY: &str = "outbox";
+
+/// Last slot handed out (counter)
+const SEQ: &str = "seq";
+
+/// First slot that may still hold an event
+const HEAD: &str = "head";
+
+/// Bytes held by queued events (counter)
+const BYTES: &str = "bytes";
+
+/// Last event number handed out (counter)
+const EVENTS: &str = "events";
+
+thread_local! {
+    static CONFIG: OutboxConfig = OutboxConfig::from_env();
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct OutboxConfig {
+    max_events: usize,
+    max_bytes: usize,
+    bucket: String,
+}
+
+impl OutboxConfig {
+    fn from_env() -> Self {
+        let env_map: HashMap<String, String> = get_environment().into_iter().collect();
+        let limit = |key: &str, default: usize| {
+            env_map
+                .get(key)
+                .and_then(|v| v.trim().parse().ok())
+                .unwrap_or(default)
+        };
+        OutboxConfig {
+            max_events: limit("WASMCP_OUTBOX_MAX_EVENTS", DEFAULT_MAX_EVENTS),
+            max_bytes: limit("WASMCP_OUTBOX_MAX_BYTES", DEFAULT_MAX_BYTES),
+            bucket: env_map
+                .get("WASMCP_SESSION_BUCKET")
+                .cloned()
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// The SSE event id a frame assigns, if its prefix starts with one
+pub fn event_id(frame: &MessageFrame) -> Option<&str> {
+    let prefix = std::str::from_utf8(&frame.prefix).ok()?;
+    let (id, _) = prefix.strip_prefix("id: ")?.split_once('\n')?;
+    Some(id).filter(|id| !id.is_empty())
+}
+
+/// The session a frame's events belong to
+///
+/// The session is the event id, up to the event number if there is one.
+pub fn session_id(frame: &MessageFrame) -> Option<&str> {
+    let id = event_id(frame)?;
+    Some(id.split_once(':').map_or(id, |(session, _)| session))
+}
+
+/// Give a session's frame the next event id of the session
+///
+/// Frames without a session are returned unchanged. So is a frame whose
+/// number can't be taken because the store is unavailable: its event keeps
+/// the bare session id, and a client resuming from it gets every queued
+/// event.
+pub fn number(frame: MessageFrame) -> MessageFrame {
+    let Some(session_id) = session_id(&frame).map(str::to_string) else {
+        return frame;
+    };
+    let n = CONFIG.with(|config| {
+        store::open(&config.bucket)
+            .and_then(|bucket| bucket.increment(&key(&session_id, EVENTS), 1))
+            .map_err(|e| e.to_string())
+    });
+    match n {
+        Ok(n) => MessageFrame {
+            prefix: format!("id: {}:{}\ndata: ", session_id, n).into_bytes(),
+            suffix: frame.suffix,
+        },
+        Err(e) => {
+            eprintln!(
+                "[SERVER-IO] ⚠️  Failed to number event for session {}: {}",
+                session_id, e
+            );
+            frame
+        }
+    }
+}
+
+/// SSE frame for a message queued for a session, as the transport frames
+/// events on the session's streams
+pub fn session_frame(session_id: &str) -> Result<MessageFrame, String> {
+    // The id ends up in an SSE field and a storage key
+    if session_id.is_empty() || session_id.contains(['\n', '\r', ':']) {
+        return Err(format!(
+            "invalid session id '{}'",
+            session_id.escape_debug()
+        ));
+    }
+    Ok(MessageFrame {
+        prefix: format!("id: {}\ndata: ", session_id).into_bytes(),
+        suffix: b"\n\n".to_vec(),
+    })
+}
+
+/// Queue a framed message the client did not receive
+///
+/// Returns whether it was queued. Frames without an event id (stdio, plain
+/// JSON, requests without a session) are never queued.
+pub fn store(framed: &[u8], frame: &MessageFrame) -> bool {
+    let Some(session_id) = session_id(frame) else {
+        return false;
+    };
+    match queue(session_id, framed) {
+        Ok(()) => true,
+        Err(e) => {
+            eprintln!(
+                "[SERVER-IO] ⚠️  Failed to queue undelivered message for session {}: {}",
+                session_id, e
+            );
+            false
+        }
+    }
+}
+
+/// Append a framed event to a session's queue
+pub fn queue(session_id: &str, framed: &[u8]) -> Result<(), String> {
+    let event = String::from_utf8(framed.to_vec()).map_err(|e| e.to_string())?;
+    CONFIG.with(|config| {
+        if config.max_events == 0 {
+            return Err("the outbox is disabled (WASMCP_OUTBOX_MAX_EVENTS=0)".to_string());
+        }
+        append(config, session_id, event)
+    })
+}
+
+fn append(config: &OutboxConfig, session_id: &str, event: String) -> Result<(), String> {
+    let bucket = store::open(&config.bucket).map_err(|e| e.to_string())?;
+
+    // Each event gets its own slot, so concurrent appends never overwrite
+    // each other
+    let slot = bucket
+        .increment(&key(session_id, SEQ), 1)
+        .map_err(|e| e.to_string())? as u64;
+    bucket
+        .set_string(&key(session_id, &slot.to_string()), &event)
+        .map_err(|e| e.to_string())?;
+    let bytes = bucket
+        .increment(&key(session_id, BYTES), event.len() as i64)
+        .map_err(|e| e.to_string())?;
+
+    evict(&bucket, config, session_id, slot, bytes).map_err(|e| e.to_string())
+}
+
+/// Evict the oldest events beyond the limits, advancing the queue's head
+/// past them
+fn evict(
+    bucket: &store::Bucket,
+    config: &OutboxConfig,
+    session_id: &str,
+    newest: u64,
+    mut bytes: i64,
+) -> Result<(), store::Error> {
+    let head_key = key(session_id, HEAD);
+    let head = bucket.get_u64(&head_key)?.unwrap_or(1);
+
+    let mut slot = head;
+    while must_evict(slot, newest, bytes, config.max_events, config.max_bytes) {
+        let slot_key = key(session_id, &slot.to_string());
+        // Slots already taken by the transport are simply skipped
+        if let Some(event) = bucket.get_string(&slot_key)? {
+            bucket.delete(&slot_key)?;
+            bytes = bucket.increment(&key(session_id, BYTES), -(event.len() as i64))?;
+        }
+        slot += 1;
+    }
+    if slot > head {
+        bucket.set_u64(&head_key, slot)?;
+    }
+    Ok(())
+}
+
+/// Whether `slot` must go for the queue ending at `newest` to fit the limits
+///
+/// The newest event is always kept, even when it alone exceeds `max_bytes`.
+fn must_evict(slot: u64, newest: u64, bytes: i64, max_events: usize, max_bytes: usize) -> bool {
+    slot < newest && (newest - slot >= max_events as u64 || bytes > max_bytes as i64)
+}
+
+/// Storage key of one part of a session's queue
+fn key(session_id: &str, part: &str) -> String {
+    format!("{}:{}:{}", session_id, OUTBOX_KEY, part)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(prefix: &str) -> MessageFrame {
+        MessageFrame {
+            prefix: prefix.as_bytes().to_vec(),
+            suffix: b"\n\n".to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_event_id() {
+        assert_eq!(event_id(&frame("id: abc-123\ndata: ")), Some("abc-123"));
+        assert_eq!(event_id(&frame("data: ")), None);
+        assert_eq!(event_id(&frame("id: \ndata: ")), None);
+        assert_eq!(event_id(&frame("id: abc")), None);
+    }
+
+    #[test]
+    fn test_session_frame() {
+        let frame = session_frame("eu.550e8400-e29b-41d4-a716-446655440000").unwrap();
+        assert_eq!(
+            event_id(&frame),
+            Some("eu.550e8400-e29b-41d4-a716-446655440000")
+        );
+        assert!(session_frame("").is_err());
+        assert!(session_frame("abc\ndata: {}").is_err());
+        assert!(session_frame("abc:outbox").is_err());
+    }
+
+    #[test]
+    fn test_session_id() {
+        assert_eq!(session_id(&frame("id: abc-123\ndata: ")), Some("abc-123"));
+        assert_eq!(
+            session_id(&frame("id: abc-123:42\ndata: ")),
+            Some("abc-123")
+        );
+        assert_eq!(session_id(&frame("data: ")), None);
+    }
+
+    #[test]
+    fn test_must_evict_oldest() {
+        // Slots 3..=5 fit three events
+        assert!(must_evict(2, 5, 0, 3, 1024));
+        assert!(!must_evict(3, 5, 0, 3, 1024));
+
+        // Over the byte limit everything but the newest goes
+        assert!(must_evict(4, 5, 16, 3, 15));
+        assert!(!must_evict(4, 5, 15, 3, 15));
+
+        // An oversized event is still kept
+        assert!(!must_evict(5, 5, 100, 3, 15));
+    }
+}
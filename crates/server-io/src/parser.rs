@@ -8,8 +8,8 @@ use crate::bindings::wasmcp::mcp_v20251125::mcp::{
     Annotations, Blob, BlobData, CallToolRequest, CancelledNotification, ClientCapabilities,
     ClientNotification, ClientRequest, ClientResult, CompleteRequest, CompletionArgument,
     CompletionContext, CompletionPromptReference, CompletionReference, ContentBlock,
-    ContentOptions, ElicitResult, ElicitResultAction, ElicitResultContent, Error, ErrorCode,
-    GetPromptRequest, Implementation, InitializeRequest, ListPromptsRequest,
+    ContentOptions, CustomRequest, ElicitResult, ElicitResultAction, ElicitResultContent, Error,
+    ErrorCode, GetPromptRequest, Implementation, InitializeRequest, ListPromptsRequest,
     ListResourceTemplatesRequest, ListResourcesRequest, ListRootsResult, ListToolsRequest,
     LogLevel, NotificationOptions, PingRequest, ProgressNotification, ProgressToken,
     ProtocolVersion, ReadResourceRequest, RequestId, Role, Root, SamplingCreateMessageResult,
@@ -44,6 +44,8 @@ struct JsonInitializeRequestParams {
     protocol_version: String,
     capabilities: JsonClientCapabilities,
     client_info: JsonImplementation,
+    #[serde(default, rename = "_meta")]
+    meta: Option<Value>,
 }
 
 #[derive(Deserialize)]
@@ -104,6 +106,7 @@ fn convert_client_capabilities(caps: JsonClientCapabilities) -> ClientCapabiliti
                 .filter_map(|(k, v)| serde_json::to_string(&v).ok().map(|s| (k, s)))
                 .collect()
         }),
+        lists: caps.roots.as_ref().map(|_| ClientLists::ROOTS),
         list_changed: caps
             .roots
             .and_then(|r| r.list_changed)
@@ -162,10 +165,11 @@ pub fn parse_client_request(json: &Value) -> Result<ClientRequest, IoError> {
         "ping" => parse_ping_request(params),
         "resources/subscribe" => parse_resource_subscribe_request(params),
         "resources/unsubscribe" => parse_resource_unsubscribe_request(params),
-        _ => Err(IoError::Serialization(format!(
-            "Unsupported method: {}",
-            method
-        ))),
+        _ if method.starts_with("x-") => Ok(parse_custom_request(method, params)),
+        // Experimental methods, advertised under capabilities.experimental
+        "resources/search" => Ok(parse_custom_request(method, params)),
+        // Answered with MethodNotFound at the end of the handler chain
+        _ => Ok(ClientRequest::Unknown(method.to_string())),
     }
 }
 
@@ -185,6 +189,7 @@ fn parse_initialize_request(params: Option<&Value>) -> Result<ClientRequest, IoE
         protocol_version,
         capabilities,
         client_info,
+        meta: json_params.meta.map(|m| m.to_string()),
     }))
 }
 
@@ -193,8 +198,12 @@ fn parse_list_tools_request(params: Option<&Value>) -> Result<ClientRequest, IoE
         .and_then(|p| p.get("cursor"))
         .and_then(|c| c.as_str())
         .map(|s| s.to_string());
+    let meta = params
+        .and_then(|p| p.get("_meta"))
+        .filter(|m| m.is_object())
+        .map(|m| m.to_string());
 
-    Ok(ClientRequest::ToolsList(ListToolsRequest { cursor }))
+    Ok(ClientRequest::ToolsList(ListToolsRequest { cursor, meta }))
 }
 
 fn parse_call_tool_request(params: Option<&Value>) -> Result<ClientRequest, IoError> {
@@ -305,32 +314,7 @@ fn parse_complete_request(params: Option<&Value>) -> Result<ClientRequest, IoErr
         IoError::Serialization("Missing 'ref' field in completion/complete params".to_string())
     })?;
 
-    let completion_ref = if let Some(ref_obj_map) = ref_obj.as_object() {
-        // Check if it's a prompt reference (has "name" field)
-        if let Some(prompt_name) = ref_obj_map.get("name").and_then(|n| n.as_str()) {
-            CompletionReference::Prompt(CompletionPromptReference {
-                name: prompt_name.to_string(),
-                title: ref_obj_map
-                    .get("title")
-                    .and_then(|t| t.as_str())
-                    .map(|s| s.to_string()),
-            })
-        } else if let Some(uri) = ref_obj_map.get("uriTemplate").and_then(|u| u.as_str()) {
-            // It's a resource template reference - create inline struct
-            // Note: CompletionReference variant may need different handling
-            return Err(IoError::Serialization(
-                "Resource template references in completions not fully supported yet".to_string(),
-            ));
-        } else {
-            return Err(IoError::Serialization(
-                "Invalid 'ref' object: must have 'name' or 'uriTemplate'".to_string(),
-            ));
-        }
-    } else {
-        return Err(IoError::Serialization(
-            "Invalid 'ref' field: must be an object".to_string(),
-        ));
-    };
+    let completion_ref = parse_completion_reference(ref_obj)?;
 
     let argument_obj = params.get("argument").ok_or_else(|| {
         IoError::Serialization("Missing 'argument' field in completion/complete params".to_string())
@@ -370,6 +354,64 @@ fn parse_complete_request(params: Option<&Value>) -> Result<ClientRequest, IoErr
     }))
 }
 
+/// URI prefix that carries a `ref/tool` completion reference
+///
+/// `ref/tool` is a wasmcp extension for completing tool argument values. The
+/// MCP completion reference only has prompt and resource variants, so tool
+/// references travel as a resource template URI, `wasmcp://tool/{name}`.
+pub const TOOL_REF_URI_PREFIX: &str = "wasmcp://tool/";
+
+/// Parse a completion `ref` by its `type`
+///
+/// References without a type are told apart by their fields (`name` for
+/// prompts, `uri` or `uriTemplate` for resources).
+fn parse_completion_reference(ref_obj: &Value) -> Result<CompletionReference, IoError> {
+    let ref_obj_map = ref_obj.as_object().ok_or_else(|| {
+        IoError::Serialization("Invalid 'ref' field: must be an object".to_string())
+    })?;
+    let field = |name: &str| ref_obj_map.get(name).and_then(|v| v.as_str());
+    let missing = |name: &str, ref_type: &str| {
+        IoError::Serialization(format!("Missing '{}' in {} reference", name, ref_type))
+    };
+
+    let prompt = |name: &str| {
+        CompletionReference::Prompt(CompletionPromptReference {
+            name: name.to_string(),
+            title: field("title").map(|s| s.to_string()),
+        })
+    };
+
+    match field("type") {
+        Some("ref/prompt") => field("name")
+            .map(prompt)
+            .ok_or_else(|| missing("name", "ref/prompt")),
+        Some("ref/resource") => field("uri")
+            .or_else(|| field("uriTemplate"))
+            .map(|uri| CompletionReference::ResourceTemplate(uri.to_string()))
+            .ok_or_else(|| missing("uri", "ref/resource")),
+        Some("ref/tool") => field("name")
+            .map(|name| {
+                CompletionReference::ResourceTemplate(format!("{}{}", TOOL_REF_URI_PREFIX, name))
+            })
+            .ok_or_else(|| missing("name", "ref/tool")),
+        Some(other) => Err(IoError::Serialization(format!(
+            "Unknown completion reference type: {}",
+            other
+        ))),
+        None => {
+            if let Some(name) = field("name") {
+                Ok(prompt(name))
+            } else if let Some(uri) = field("uri").or_else(|| field("uriTemplate")) {
+                Ok(CompletionReference::ResourceTemplate(uri.to_string()))
+            } else {
+                Err(IoError::Serialization(
+                    "Invalid 'ref' object: must have 'name' or 'uri'".to_string(),
+                ))
+            }
+        }
+    }
+}
+
 fn parse_set_log_level_request(params: Option<&Value>) -> Result<ClientRequest, IoError> {
     let params = params.ok_or_else(|| {
         IoError::Serialization("Missing params for logging/setLevel request".to_string())
@@ -402,6 +444,16 @@ fn parse_set_log_level_request(params: Option<&Value>) -> Result<ClientRequest,
     Ok(ClientRequest::LoggingSetLevel(level))
 }
 
+/// Parse a custom `x-*` extension or experimental request
+///
+/// Params are passed through untouched; the handling component owns their schema.
+fn parse_custom_request(method: &str, params: Option<&Value>) -> ClientRequest {
+    ClientRequest::Custom(CustomRequest {
+        method: method.to_string(),
+        params: params.map(|p| p.to_string()),
+    })
+}
+
 fn parse_ping_request(params: Option<&Value>) -> Result<ClientRequest, IoError> {
     let ping_request = if let Some(p) = params {
         let progress_token = p.get("progressToken").and_then(|pt| {
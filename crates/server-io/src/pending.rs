@@ -0,0 +1,79 @@
+//! Outstanding server-initiated requests
+//!
+//! Requests the server sends to the client (sampling, elicitation, roots)
+//! are answered in a later POST, which may reach another instance. Each one
+//! sent on a session's stream (a frame whose SSE event id carries the session,
+//! see `outbox.rs`) is recorded in the session bucket, so the transport
+//! can tell whether a client response matches a request it is waiting for.
+//! The transport removes the entry when the response arrives; entries left
+//! unanswered are removed with the session.
+//!
+//! Environment variables:
+//! - `WASMCP_SESSION_BUCKET`: Bucket holding the entries (default: "", as
+//!   the transport)
+
+use crate::bindings::exports::wasmcp::mcp_v20251125::server_io::MessageFrame;
+use crate::bindings::wasi::cli::environment::get_environment;
+use crate::bindings::wasmcp::keyvalue::store;
+use crate::bindings::wasmcp::mcp_v20251125::mcp::{RequestId, ServerMessage};
+use crate::outbox;
+
+/// Session key prefix of entries (see the transport's `session_keys::PENDING_REQUEST`)
+const PENDING_REQUEST_KEY: &str = "pending-request";
+
+thread_local! {
+    static BUCKET: String = get_environment()
+        .into_iter()
+        .find(|(key, _)| key == "WASMCP_SESSION_BUCKET")
+        .map(|(_, value)| value)
+        .unwrap_or_default();
+}
+
+/// Session key suffix identifying a request ID
+///
+/// Numbers and strings are kept apart, since `1` and `"1"` are different IDs.
+pub fn request_key(id: &RequestId) -> String {
+    match id {
+        RequestId::Number(n) => format!("n:{}", n),
+        RequestId::String(s) => format!("s:{}", s),
+    }
+}
+
+/// Record a server request sent on a session's stream
+///
+/// Other messages, and frames without a session, are ignored.
+pub fn record(message: &ServerMessage, frame: &MessageFrame) {
+    let ServerMessage::Request((id, _)) = message else {
+        return;
+    };
+    let Some(session_id) = outbox::session_id(frame) else {
+        return;
+    };
+    let key = format!("{}:{}:{}", session_id, PENDING_REQUEST_KEY, request_key(id));
+    let result = BUCKET.with(|bucket| {
+        store::open(bucket)
+            .and_then(|bucket| bucket.set_string(&key, "1"))
+            .map_err(|e| e.to_string())
+    });
+    if let Err(e) = result {
+        eprintln!(
+            "[SERVER-IO] ⚠️  Failed to record request for session {}: {}",
+            session_id, e
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_key() {
+        assert_eq!(request_key(&RequestId::Number(7)), "n:7");
+        assert_eq!(request_key(&RequestId::String("7".to_string())), "s:7");
+        assert_eq!(
+            request_key(&RequestId::String("job:1:2".to_string())),
+            "s:job:1:2"
+        );
+    }
+}
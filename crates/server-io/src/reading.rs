@@ -36,32 +36,21 @@ pub fn read_until_delimiter(stream: &InputStream, delimiter: &[u8]) -> Result<Ve
 fn read_until_byte(stream: &InputStream, delimiter: u8) -> Result<Vec<u8>, IoError> {
     const MAX_SIZE: usize = 10 * 1024 * 1024; // 10MB max
     const CHUNK_SIZE: usize = 4096; // Read 4KB chunks
-    let mut buffer = Vec::new();
-
-    // FIRST: Check if we have buffered data from a previous read
+    // Start from data buffered by a previous read
     // This happens when multiple newline-delimited messages arrive in one chunk
-    READ_BUFFER.with(|rb| {
-        let mut read_buf = rb.borrow_mut();
-        if !read_buf.is_empty() {
-            buffer.extend_from_slice(&read_buf);
-            read_buf.clear();
-        }
-    });
+    let mut buffer = READ_BUFFER.with(|rb| std::mem::take(&mut *rb.borrow_mut()));
+    let mut scanned = 0;
 
-    // Check if buffered data already contains a complete message
-    if let Some(pos) = buffer.iter().position(|&b| b == delimiter) {
-        let message = buffer[..=pos].to_vec();
-        let remaining = buffer[pos + 1..].to_vec();
-
-        // Save remaining bytes for next call
-        if !remaining.is_empty() {
-            READ_BUFFER.with(|rb| *rb.borrow_mut() = remaining);
+    loop {
+        if let Some(message) = take_delimited(&mut buffer, scanned, delimiter) {
+            // Save remaining bytes for next call
+            if !buffer.is_empty() {
+                READ_BUFFER.with(|rb| *rb.borrow_mut() = buffer);
+            }
+            return Ok(message);
         }
+        scanned = buffer.len();
 
-        return Ok(message);
-    }
-
-    loop {
         if buffer.len() >= MAX_SIZE {
             return Err(IoError::Unexpected(format!(
                 "Message exceeds maximum size of {} bytes",
@@ -84,21 +73,26 @@ fn read_until_byte(stream: &InputStream, delimiter: u8) -> Result<Vec<u8>, IoErr
             return Err(IoError::Unexpected("Stream closed mid-message".to_string()));
         }
 
-        // Scan byte-by-byte for delimiter
-        for (idx, &byte) in chunk.iter().enumerate() {
-            buffer.push(byte);
-            if byte == delimiter {
-                // Found delimiter - save any remaining bytes from this chunk
-                let remaining = &chunk[idx + 1..];
-                if !remaining.is_empty() {
-                    READ_BUFFER.with(|rb| rb.borrow_mut().extend_from_slice(remaining));
-                }
-                return Ok(buffer);
-            }
-        }
+        buffer.extend_from_slice(&chunk);
     }
 }
 
+/// Split the first delimited message off the front of `pending`
+///
+/// Only `pending[scanned..]` is searched, so callers appending chunk by chunk
+/// don't rescan bytes already known to hold no delimiter. Returns the message
+/// including its delimiter and leaves the rest in `pending`, or None if no
+/// delimiter has arrived yet.
+pub fn take_delimited(pending: &mut Vec<u8>, scanned: usize, delimiter: u8) -> Option<Vec<u8>> {
+    let pos = scanned
+        + pending
+            .get(scanned..)?
+            .iter()
+            .position(|&b| b == delimiter)?;
+    let rest = pending.split_off(pos + 1);
+    Some(std::mem::replace(pending, rest))
+}
+
 /// Generic path for multi-byte delimiters with boundary handling
 ///
 /// Searches across chunk boundaries by checking overlapping regions.
@@ -164,7 +158,7 @@ pub fn read_max_bytes(stream: &InputStream, max_bytes: u64) -> Result<Vec<u8>, I
 }
 
 /// Find position of needle in haystack
-fn find_subsequence(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+pub fn find_subsequence(haystack: &[u8], needle: &[u8]) -> Option<usize> {
     if needle.is_empty() {
         return Some(0);
     }
@@ -196,7 +190,7 @@ fn find_subsequence(haystack: &[u8], needle: &[u8]) -> Option<usize> {
 /// let result = search_with_boundary(buffer, 10, b"\n\n");
 /// assert_eq!(result, Some((12, true))); // Found at position 12
 /// ```
-fn search_with_boundary(
+pub fn search_with_boundary(
     buffer: &[u8],
     new_chunk_len: usize,
     delimiter: &[u8],
@@ -343,4 +337,30 @@ mod boundary_tests {
         assert!(found);
         assert_eq!(pos, 5);
     }
+
+    #[test]
+    fn test_take_delimited_keeps_remainder() {
+        let mut pending = b"{\"a\":1}\n{\"b\":2}\n{\"c\"".to_vec();
+        assert_eq!(
+            take_delimited(&mut pending, 0, b'\n'),
+            Some(b"{\"a\":1}\n".to_vec())
+        );
+        assert_eq!(
+            take_delimited(&mut pending, 0, b'\n'),
+            Some(b"{\"b\":2}\n".to_vec())
+        );
+        assert_eq!(take_delimited(&mut pending, 0, b'\n'), None);
+        assert_eq!(pending, b"{\"c\"");
+    }
+
+    #[test]
+    fn test_take_delimited_skips_scanned_bytes() {
+        let mut pending = b"ab\ncd\n".to_vec();
+        assert_eq!(
+            take_delimited(&mut pending, 3, b'\n'),
+            Some(b"ab\ncd\n".to_vec())
+        );
+        assert!(pending.is_empty());
+        assert_eq!(take_delimited(&mut pending, 5, b'\n'), None);
+    }
 }
@@ -0,0 +1,182 @@
+//! Exchange recording for offline debugging
+//!
+//! With `WASMCP_RECORD_DIR` set, every message server-io parses or sends is
+//! appended to a JSON-lines file in that directory, so a client-reported bug
+//! can be replayed later (`dev-tools replay`). Each parsed message starts a
+//! new exchange file; everything sent until the next parse (notifications,
+//! server requests, the response) goes into the same file:
+//!
+//! ```text
+//! {"direction":"request","message":{"jsonrpc":"2.0","id":1,"method":"tools/call",...}}
+//! {"direction":"response","message":{"jsonrpc":"2.0","id":1,"result":{...}}}
+//! ```
+//!
+//! Tool and prompt arguments are redacted with the `WASMCP_REDACT` rules
+//! before they are written (see `wasmcp_redact`). Credentials never reach
+//! server-io, so there is nothing else to scrub.
+//!
+//! The directory must be preopened for the component (e.g. `wasmtime --dir`).
+//! Recording never fails a message: write errors are logged and ignored.
+
+use crate::bindings::exports::wasmcp::mcp_v20251125::server_io::MessageFrame;
+use crate::bindings::wasi::cli::environment::get_environment;
+use crate::framing;
+use serde_json::{Value, json};
+use std::cell::RefCell;
+use std::fs::OpenOptions;
+use std::hash::{BuildHasher, RandomState};
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+use wasmcp_redact::{REDACT_ENV, Redactor};
+
+const RECORD_DIR_ENV: &str = "WASMCP_RECORD_DIR";
+
+struct Recorder {
+    dir: PathBuf,
+    redactor: Redactor,
+    /// File for the current exchange (None before the first parse)
+    exchange: Option<PathBuf>,
+}
+
+thread_local! {
+    /// None when recording is off
+    static RECORDER: RefCell<Option<Recorder>> = RefCell::new(Recorder::from_env());
+}
+
+impl Recorder {
+    fn from_env() -> Option<Self> {
+        let env = get_environment();
+        let get = |name: &str| {
+            env.iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.clone())
+        };
+
+        let dir = get(RECORD_DIR_ENV).filter(|d| !d.is_empty())?;
+        let redactor = Redactor::parse(&get(REDACT_ENV).unwrap_or_default()).unwrap_or_else(|e| {
+            // Redact every argument rather than record secrets under a broken spec
+            eprintln!("[SERVER-IO] {}; recording arguments fully redacted", e);
+            Redactor::parse(r#"{"*": ["/arguments"]}"#).unwrap_or_default()
+        });
+
+        Some(Self {
+            dir: PathBuf::from(dir),
+            redactor,
+            exchange: None,
+        })
+    }
+}
+
+/// Start a new exchange with a message read from the client
+pub fn record_request(raw: &[u8], frame: &MessageFrame) {
+    RECORDER.with(|r| {
+        let mut recorder = r.borrow_mut();
+        let Some(recorder) = recorder.as_mut() else {
+            return;
+        };
+
+        let mut message = to_json(raw, frame);
+        redact(&recorder.redactor, &mut message);
+
+        let path = recorder.dir.join(exchange_file_name());
+        append(&path, "request", message);
+        recorder.exchange = Some(path);
+    });
+}
+
+/// Whether messages sent now are recorded
+pub fn is_recording() -> bool {
+    RECORDER.with(|r| r.borrow().as_ref().is_some_and(|r| r.exchange.is_some()))
+}
+
+/// Add a message sent to the client to the current exchange
+pub fn record_response(framed: &[u8], frame: &MessageFrame) {
+    RECORDER.with(|r| {
+        let recorder = r.borrow();
+        let Some(path) = recorder.as_ref().and_then(|r| r.exchange.as_ref()) else {
+            return;
+        };
+        append(path, "response", to_json(framed, frame));
+    });
+}
+
+/// Unframed JSON, or the raw text if it isn't valid JSON (parse errors are
+/// worth reproducing too)
+fn to_json(raw: &[u8], frame: &MessageFrame) -> Value {
+    let bytes = framing::strip_framing(raw, frame).unwrap_or_else(|_| raw.to_vec());
+    let text = String::from_utf8_lossy(&bytes);
+    serde_json::from_str(&text).unwrap_or_else(|_| Value::String(text.into_owned()))
+}
+
+/// Apply the redaction rules to tools/call and prompts/get params
+fn redact(redactor: &Redactor, message: &mut Value) {
+    if !matches!(
+        message.get("method").and_then(Value::as_str),
+        Some("tools/call" | "prompts/get")
+    ) {
+        return;
+    }
+    let Some(params) = message.get_mut("params") else {
+        return;
+    };
+    let Some(name) = params.get("name").and_then(Value::as_str).map(String::from) else {
+        return;
+    };
+    redactor.redact(&name, params);
+}
+
+/// Time-ordered and unique across concurrent instances
+fn exchange_file_name() -> String {
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis());
+    let nonce = RandomState::new().hash_one(millis) as u32;
+    format!("{:013}-{:08x}.jsonl", millis, nonce)
+}
+
+fn append(path: &PathBuf, direction: &str, message: Value) {
+    let line = json!({ "direction": direction, "message": message }).to_string();
+    let result = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| writeln!(file, "{}", line));
+    if let Err(e) = result {
+        eprintln!("[SERVER-IO] Failed to record to {}: {}", path.display(), e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redacts_tool_arguments_only() {
+        let redactor = Redactor::parse(r#"{"login": ["/arguments/password"]}"#).unwrap();
+
+        let mut call = json!({
+            "jsonrpc": "2.0", "id": 1, "method": "tools/call",
+            "params": {"name": "login", "arguments": {"user": "a", "password": "hunter2"}}
+        });
+        redact(&redactor, &mut call);
+        assert_eq!(call["params"]["arguments"]["password"], "[REDACTED]");
+        assert_eq!(call["params"]["arguments"]["user"], "a");
+
+        let mut other = json!({"jsonrpc": "2.0", "id": 2, "method": "resources/read",
+            "params": {"name": "login", "arguments": {"password": "x"}}});
+        let before = other.clone();
+        redact(&redactor, &mut other);
+        assert_eq!(other, before);
+    }
+
+    #[test]
+    fn test_to_json_strips_framing() {
+        let frame = MessageFrame {
+            prefix: b"data: ".to_vec(),
+            suffix: b"\n\n".to_vec(),
+        };
+        assert_eq!(to_json(b"data: {\"id\":1}\n\n", &frame), json!({"id": 1}));
+        assert_eq!(to_json(b"data: {oops\n\n", &frame), json!("{oops"));
+    }
+}
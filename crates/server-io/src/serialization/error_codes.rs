@@ -0,0 +1,80 @@
+//! Error codes for the negotiated protocol version
+//!
+//! wasmcp's error taxonomy (see `wasmcp-errors`) uses codes in the JSON-RPC
+//! server range that the MCP spec doesn't define, and newer spec versions
+//! define codes older ones don't know. Clients on an older version may
+//! reject or mishandle a code they don't expect, so errors to them carry the
+//! closest code their version defines. The original code goes into
+//! `error.data.originalCode`, so nothing is lost for clients that look.
+//!
+//! The transport sets the version with `set-protocol-version`; until it
+//! does, codes are sent unchanged.
+
+use crate::bindings::wasmcp::mcp_v20251125::mcp::ProtocolVersion;
+use serde_json::{Map, Value};
+use std::cell::Cell;
+
+/// Field of `error.data` holding a translated error's own code
+pub const ORIGINAL_CODE_FIELD: &str = "originalCode";
+
+/// Codes not every version defines: (code, first version sent it unchanged,
+/// closest code defined by every version)
+const TRANSLATIONS: &[(i64, ProtocolVersion, i64)] = &[
+    // Unauthorized, Forbidden: the request can't be served as sent
+    (-32001, ProtocolVersion::V20251125, -32600),
+    (-32003, ProtocolVersion::V20251125, -32600),
+    // RateLimited, Timeout, Unavailable: the server couldn't complete it
+    (-32004, ProtocolVersion::V20251125, -32603),
+    (-32005, ProtocolVersion::V20251125, -32603),
+    (-32006, ProtocolVersion::V20251125, -32603),
+    // URL elicitation required
+    (-32042, ProtocolVersion::V20251125, -32603),
+];
+
+thread_local! {
+    static NEGOTIATED: Cell<Option<ProtocolVersion>> = const { Cell::new(None) };
+}
+
+/// Set the protocol version errors are serialized for
+pub fn set_protocol_version(version: ProtocolVersion) {
+    NEGOTIATED.with(|v| v.set(Some(version)));
+}
+
+/// Code and data of an error as sent on the negotiated version
+///
+/// `data` is the error's JSON data, if any. A translated error's object data
+/// gains `originalCode`; other data moves under `data`.
+pub fn for_negotiated_version(code: i64, data: Option<Value>) -> (i64, Option<Value>) {
+    match NEGOTIATED.with(Cell::get) {
+        Some(version) => translate(code, data, version),
+        None => (code, data),
+    }
+}
+
+/// Code and data of an error as sent on `version`
+pub fn translate(code: i64, data: Option<Value>, version: ProtocolVersion) -> (i64, Option<Value>) {
+    let Some(&(_, since, fallback)) = TRANSLATIONS
+        .iter()
+        .find(|(c, since, _)| *c == code && release(version) < release(*since))
+    else {
+        return (code, data);
+    };
+
+    let mut object = match data {
+        Some(Value::Object(object)) => object,
+        Some(other) => Map::from_iter([("data".to_string(), other)]),
+        None => Map::new(),
+    };
+    object.insert(ORIGINAL_CODE_FIELD.to_string(), Value::from(code));
+    (fallback, Some(Value::Object(object)))
+}
+
+/// Release date of a version; versions order by it
+fn release(version: ProtocolVersion) -> &'static str {
+    match version {
+        ProtocolVersion::V20251125 => "2025-11-25",
+        ProtocolVersion::V20250618 => "2025-06-18",
+        ProtocolVersion::V20250326 => "2025-03-26",
+        ProtocolVersion::V20241105 => "2024-11-05",
+    }
+}
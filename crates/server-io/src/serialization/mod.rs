@@ -3,6 +3,7 @@
 //! Handles all MCP message serialization to JSON-RPC format.
 //! Organized into submodules by message type.
 
+pub mod error_codes;
 pub mod notifications;
 pub mod requests;
 pub mod server_messages;
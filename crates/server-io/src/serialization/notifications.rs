@@ -6,6 +6,7 @@ use crate::bindings::wasmcp::mcp_v20251125::mcp::{
     LogLevel, NotificationOptions, ProgressToken, ServerNotification,
 };
 use crate::serialization::server_messages::serialize_request_id;
+use crate::serializer::convert_content_block;
 
 /// Serialize server notification to method name and params
 pub fn serialize_server_notification(
@@ -85,6 +86,34 @@ pub fn serialize_server_notification(
                 "elicitationId": elicitation_complete.elicitation_id,
             }),
         ),
+        ServerNotification::ToolPartialResult(partial) => {
+            // Text streams are drained here, so a failed block is dropped rather
+            // than failing the whole notification
+            let content: Vec<serde_json::Value> = partial
+                .content
+                .iter()
+                .filter_map(|block| convert_content_block(block).ok())
+                .filter_map(|block| serde_json::to_value(block).ok())
+                .collect();
+
+            let mut params = serde_json::Map::new();
+            params.insert(
+                "requestId".to_string(),
+                serialize_request_id(&partial.request_id),
+            );
+            params.insert("content".to_string(), serde_json::Value::Array(content));
+
+            if let Some(ref meta) = partial.meta {
+                if let Ok(meta_value) = serde_json::from_str::<serde_json::Value>(meta) {
+                    params.insert("_meta".to_string(), meta_value);
+                }
+            }
+
+            (
+                "notifications/tools/partial_result",
+                serde_json::Value::Object(params),
+            )
+        }
     }
 }
 
@@ -3,9 +3,10 @@
 //! Handles serialization of all MCP server request types to JSON-RPC format.
 
 use crate::bindings::wasmcp::mcp_v20251125::mcp::{
-    BlobData, ElicitRequest, EnumSchema, IncludeContext, ModelPreferences, NumberSchemaType,
-    PrimitiveSchemaDefinition, ProgressToken, RequestedSchema, Role, SamplingContentBlock,
-    SamplingMessage, ServerRequest, StringSchemaFormat, TextData, ToolResultContentBlock,
+    Blob, BlobData, ContentOptions, ElicitRequest, EnumSchema, IncludeContext, ModelPreferences,
+    NumberSchemaType, PrimitiveSchemaDefinition, ProgressToken, RequestedSchema, Role,
+    SamplingContentBlock, SamplingMessage, ServerRequest, StringSchemaFormat, TextContent,
+    TextData, ToolResultContentBlock,
 };
 use crate::serializer;
 
@@ -302,41 +303,63 @@ fn serialize_sampling_message(msg: &SamplingMessage) -> serde_json::Value {
 /// Serialize sampling content block (text, image, audio, tool-use, tool-result)
 fn serialize_sampling_content_block(content: &SamplingContentBlock) -> serde_json::Value {
     match content {
-        SamplingContentBlock::Text(t) => {
-            let text_str = match &t.text {
-                TextData::Text(s) => s.clone(),
-                TextData::TextStream(_) => "[stream]".to_string(),
-            };
-            serde_json::json!({"type": "text", "text": text_str})
-        }
-        SamplingContentBlock::Image(img) => {
-            let data_b64 = blob_data_to_base64(&img.data);
-            serde_json::json!({"type": "image", "data": data_b64, "mimeType": img.mime_type})
-        }
-        SamplingContentBlock::Audio(audio) => {
-            let data_b64 = blob_data_to_base64(&audio.data);
-            serde_json::json!({"type": "audio", "data": data_b64, "mimeType": audio.mime_type})
-        }
+        SamplingContentBlock::Text(t) => serialize_text_content(t),
+        SamplingContentBlock::Image(img) => serialize_blob_content("image", img),
+        SamplingContentBlock::Audio(audio) => serialize_blob_content("audio", audio),
         SamplingContentBlock::ToolUse(tu) => {
             let input_val = serde_json::from_str::<serde_json::Value>(&tu.input).ok();
             serde_json::json!({"type": "tool_use", "id": tu.id, "name": tu.name, "input": input_val})
         }
         SamplingContentBlock::ToolResult(tr) => {
-            let content_arr: Vec<serde_json::Value> = tr.content.iter().map(|block| {
-                match block {
-                    ToolResultContentBlock::Text(t) => {
-                        let s = match &t.text { TextData::Text(s) => s.clone(), TextData::TextStream(_) => "[stream]".to_string() };
-                        serde_json::json!({"type": "text", "text": s})
-                    }
-                    ToolResultContentBlock::Image(img) => serde_json::json!({"type": "image", "data": blob_data_to_base64(&img.data), "mimeType": img.mime_type}),
-                    ToolResultContentBlock::Audio(audio) => serde_json::json!({"type": "audio", "data": blob_data_to_base64(&audio.data), "mimeType": audio.mime_type}),
-                }
-            }).collect();
+            let content_arr: Vec<serde_json::Value> = tr
+                .content
+                .iter()
+                .map(|block| match block {
+                    ToolResultContentBlock::Text(t) => serialize_text_content(t),
+                    ToolResultContentBlock::Image(img) => serialize_blob_content("image", img),
+                    ToolResultContentBlock::Audio(audio) => serialize_blob_content("audio", audio),
+                })
+                .collect();
             serde_json::json!({"type": "tool_result", "toolUseId": tr.tool_use_id, "content": content_arr})
         }
     }
 }
 
+fn serialize_text_content(t: &TextContent) -> serde_json::Value {
+    let text_str = match &t.text {
+        TextData::Text(s) => s.clone(),
+        TextData::TextStream(_) => "[stream]".to_string(),
+    };
+    with_annotations(
+        serde_json::json!({"type": "text", "text": text_str}),
+        t.options.as_ref(),
+    )
+}
+
+/// Image or audio content (`kind` is the content type)
+fn serialize_blob_content(kind: &str, blob: &Blob) -> serde_json::Value {
+    with_annotations(
+        serde_json::json!({"type": kind, "data": blob_data_to_base64(&blob.data), "mimeType": blob.mime_type}),
+        blob.options.as_ref(),
+    )
+}
+
+/// Add a content block's annotations, so audience and priority reach the client
+fn with_annotations(
+    mut block: serde_json::Value,
+    options: Option<&ContentOptions>,
+) -> serde_json::Value {
+    if let Some(annotations) = options.and_then(|o| o.annotations.as_ref())
+        && let Some(obj) = block.as_object_mut()
+    {
+        obj.insert(
+            "annotations".to_string(),
+            serializer::annotations_to_value(annotations),
+        );
+    }
+    block
+}
+
 /// Extract base64-encoded string from BlobData (inline only; streams return empty string)
 fn blob_data_to_base64(data: &BlobData) -> String {
     use base64::Engine as _;
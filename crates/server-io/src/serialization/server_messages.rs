@@ -31,17 +31,22 @@ pub fn serialize_server_message(message: &ServerMessage) -> Result<serde_json::V
                 | InternalError(e) | Server(e) | JsonRpc(e) | Mcp(e) => e,
             };
 
+            // Older protocol versions get the closest code they define
+            let data = error
+                .data
+                .as_deref()
+                .and_then(|data| serde_json::from_str::<serde_json::Value>(data).ok());
+            let (code, data) = super::error_codes::for_negotiated_version(error.code, data);
+
             // Generate JSON-RPC error response
             let mut error_obj = serde_json::Map::new();
-            error_obj.insert("code".to_string(), serde_json::json!(error.code));
+            error_obj.insert("code".to_string(), serde_json::json!(code));
             error_obj.insert(
                 "message".to_string(),
                 serde_json::Value::String(error.message.clone()),
             );
-            if let Some(ref data) = error.data {
-                if let Ok(data_value) = serde_json::from_str::<serde_json::Value>(data) {
-                    error_obj.insert("data".to_string(), data_value);
-                }
+            if let Some(data_value) = data {
+                error_obj.insert("data".to_string(), data_value);
             }
 
             Ok(serde_json::json!({
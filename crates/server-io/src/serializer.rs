@@ -4,13 +4,16 @@
 //! with support for both HTTP (SSE) and stdio (newline-delimited) transports.
 
 use crate::bindings::wasmcp::mcp_v20251125::mcp::{
-    Annotations, BlobData, CallToolResult, CompleteResult, ContentBlock, ErrorCode,
-    GetPromptResult, Implementation, InitializeResult, ListPromptsResult,
+    Annotations, BlobData, BlobResourceContents, CallToolResult, CompleteResult, ContentBlock,
+    ErrorCode, GetPromptResult, Implementation, InitializeResult, ListPromptsResult,
     ListResourceTemplatesResult, ListResourcesResult, ListToolsResult, McpResource, Prompt,
     PromptMessage, ProtocolVersion, ReadResourceResult, RequestId, ResourceContents,
     ResourceTemplate, Role, ServerCapabilities, ServerResult, TextData, Tool,
 };
-use crate::stream_reader::{StreamConfig, read_blob_stream, read_text_stream};
+use crate::blob_limits;
+use crate::stream_reader::{
+    BlobError, StreamConfig, read_blob_stream, read_text_stream, write_blob_stream,
+};
 use serde::{Deserialize, Serialize};
 use serde_json::{Value, json};
 
@@ -51,7 +54,7 @@ struct JsonServerCapabilities {
     #[serde(skip_serializing_if = "Option::is_none")]
     completions: Option<Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    experimental: Option<Vec<(String, Value)>>,
+    experimental: Option<serde_json::Map<String, Value>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     logging: Option<Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -134,6 +137,8 @@ struct JsonResourceContent {
     blob: Option<String>, // base64-encoded
     #[serde(skip_serializing_if = "Option::is_none")]
     mime_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    annotations: Option<JsonAnnotations>,
 }
 
 #[derive(Serialize)]
@@ -187,6 +192,9 @@ struct JsonListToolsResult {
 #[serde(rename_all = "camelCase")]
 struct JsonCallToolResult {
     content: Vec<JsonContentBlock>,
+    /// Omitted for tools that return no structured output
+    #[serde(skip_serializing_if = "Option::is_none")]
+    structured_content: Option<Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
     is_error: Option<bool>,
 }
@@ -337,6 +345,12 @@ fn protocol_version_to_string(version: &ProtocolVersion) -> String {
 fn convert_server_capabilities(caps: &ServerCapabilities) -> JsonServerCapabilities {
     use crate::bindings::wasmcp::mcp_v20251125::mcp::{ServerLists, ServerSubscriptions};
 
+    let list_changed = caps.list_changed.unwrap_or(ServerLists::empty());
+    // Without an explicit `lists`, a list-changed flag implies the feature is offered
+    let offered = caps.lists.unwrap_or(list_changed);
+    let offers = |list: ServerLists| offered.contains(list) || list_changed.contains(list);
+    let changes = |list: ServerLists| list_changed.contains(list).then_some(true);
+
     JsonServerCapabilities {
         completions: caps
             .completions
@@ -351,40 +365,27 @@ fn convert_server_capabilities(caps: &ServerCapabilities) -> JsonServerCapabilit
             .logging
             .as_ref()
             .and_then(|s| serde_json::from_str(s).ok()),
-        prompts: caps
-            .list_changed
-            .as_ref()
-            .filter(|lc| lc.contains(ServerLists::PROMPTS))
-            .map(|_| JsonPromptCapability {
-                list_changed: Some(true),
-            }),
+        prompts: offers(ServerLists::PROMPTS).then(|| JsonPromptCapability {
+            list_changed: changes(ServerLists::PROMPTS),
+        }),
         resources: {
-            let list_changed = caps
-                .list_changed
-                .as_ref()
-                .map(|lc| lc.contains(ServerLists::RESOURCES))
-                .unwrap_or(false);
             let subscribe = caps
                 .subscriptions
                 .as_ref()
                 .map(|s| s.contains(ServerSubscriptions::RESOURCES))
                 .unwrap_or(false);
-            if list_changed || subscribe {
+            if offers(ServerLists::RESOURCES) || subscribe {
                 Some(JsonResourceCapability {
-                    list_changed: if list_changed { Some(true) } else { None },
+                    list_changed: changes(ServerLists::RESOURCES),
                     subscribe: if subscribe { Some(true) } else { None },
                 })
             } else {
                 None
             }
         },
-        tools: caps
-            .list_changed
-            .as_ref()
-            .filter(|lc| lc.contains(ServerLists::TOOLS))
-            .map(|_| JsonToolCapability {
-                list_changed: Some(true),
-            }),
+        tools: offers(ServerLists::TOOLS).then(|| JsonToolCapability {
+            list_changed: changes(ServerLists::TOOLS),
+        }),
     }
 }
 
@@ -423,6 +424,11 @@ fn convert_annotations(annotations: &Annotations) -> JsonAnnotations {
     }
 }
 
+/// Annotations as JSON, for serializers that build `Value`s directly
+pub(crate) fn annotations_to_value(annotations: &Annotations) -> Value {
+    serde_json::to_value(convert_annotations(annotations)).unwrap_or_default()
+}
+
 /// Convert TextData (string or stream) to String
 ///
 /// For text-stream, reads the stream in chunks with bounded memory.
@@ -438,19 +444,45 @@ fn convert_text_data(data: &TextData) -> Result<String, String> {
 
 /// Convert BlobData (bytes or stream) to base64 String
 ///
-/// For blob-stream, reads the stream in chunks with bounded memory.
-fn convert_blob_data(data: &BlobData) -> Result<String, String> {
+/// For blob-stream, reads and encodes the stream in chunks with bounded memory.
+/// Blobs over the limit for their MIME type (see `blob_limits`) fail with
+/// `TooLarge` before being encoded.
+fn convert_blob_data(data: &BlobData, mime_type: Option<&str>) -> Result<String, BlobError> {
     use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
 
+    let max_size = blob_limits::max_bytes(mime_type);
     match data {
+        BlobData::Blob(bytes) if bytes.len() as u64 > max_size => {
+            Err(BlobError::TooLarge(max_size))
+        }
         BlobData::Blob(bytes) => Ok(BASE64.encode(bytes)),
         BlobData::BlobStream(stream) => {
-            let config = StreamConfig::default();
+            let config = StreamConfig {
+                max_size,
+                ..StreamConfig::default()
+            };
             read_blob_stream(stream, &config)
         }
     }
 }
 
+/// Text sent in place of a blob that exceeds its size limit
+fn oversized_blob_message(mime_type: Option<&str>, limit: u64) -> String {
+    format!(
+        "Content omitted: {} data exceeds the {}-byte size limit",
+        mime_type.unwrap_or("blob"),
+        limit
+    )
+}
+
+/// Text content block replacing an oversized blob
+fn oversized_blob_block(mime_type: Option<&str>, limit: u64) -> JsonContentBlock {
+    JsonContentBlock::Text(JsonTextContent {
+        text: oversized_blob_message(mime_type, limit),
+        annotations: None,
+    })
+}
+
 fn convert_tool(tool: &Tool) -> Result<JsonTool, String> {
     let input_schema: Value = serde_json::from_str(&tool.input_schema)
         .map_err(|e| format!("Invalid tool input schema JSON: {}", e))?;
@@ -485,6 +517,108 @@ fn convert_list_tools_result(result: &ListToolsResult) -> Result<JsonListToolsRe
     })
 }
 
+/// Whether every tool's input schema is valid JSON
+///
+/// `write_list_tools_response` can't report an invalid schema once it has
+/// started writing, so callers check first.
+pub fn tool_schemas_valid(result: &ListToolsResult) -> bool {
+    result
+        .tools
+        .iter()
+        .all(|tool| serde_json::from_str::<serde::de::IgnoredAny>(&tool.input_schema).is_ok())
+}
+
+/// Write a tools/list response one tool at a time
+///
+/// Produces the same JSON-RPC response as `serialize_jsonrpc_response`, but
+/// only one converted tool is in memory at a time instead of the whole list
+/// as a value and then as a string.
+pub fn write_list_tools_response<W: std::io::Write>(
+    id: &RequestId,
+    result: &ListToolsResult,
+    writer: &mut W,
+) -> std::io::Result<()> {
+    writer.write_all(br#"{"jsonrpc":"2.0","id":"#)?;
+    serde_json::to_writer(&mut *writer, &JsonRequestId::from(id))?;
+    writer.write_all(br#","result":{"tools":["#)?;
+    for (i, tool) in result.tools.iter().enumerate() {
+        if i > 0 {
+            writer.write_all(b",")?;
+        }
+        let tool = convert_tool(tool).map_err(std::io::Error::other)?;
+        serde_json::to_writer(&mut *writer, &tool)?;
+    }
+    writer.write_all(b"]")?;
+    if let Some(cursor) = &result.next_cursor {
+        writer.write_all(br#","nextCursor":"#)?;
+        serde_json::to_writer(&mut *writer, cursor)?;
+    }
+    writer.write_all(b"}}")
+}
+
+/// Whether any contents of a resources/read result are blob streams
+pub fn has_blob_streams(result: &ReadResourceResult) -> bool {
+    result.contents.iter().any(|contents| {
+        matches!(
+            contents,
+            ResourceContents::Blob(BlobResourceContents {
+                blob: BlobData::BlobStream(_),
+                ..
+            })
+        )
+    })
+}
+
+/// Write a resources/read response, encoding blob streams into the writer
+///
+/// Produces the same JSON-RPC response as `serialize_jsonrpc_response`, but
+/// blob streams are base64-encoded chunk by chunk straight into `writer`
+/// instead of into a string. A stream over its size limit can't be replaced
+/// by a text block once its encoding has started, so it fails the write.
+pub fn write_read_resource_response<W: std::io::Write>(
+    id: &RequestId,
+    result: &ReadResourceResult,
+    writer: &mut W,
+) -> std::io::Result<()> {
+    writer.write_all(br#"{"jsonrpc":"2.0","id":"#)?;
+    serde_json::to_writer(&mut *writer, &JsonRequestId::from(id))?;
+    writer.write_all(br#","result":{"contents":["#)?;
+    for (i, contents) in result.contents.iter().enumerate() {
+        if i > 0 {
+            writer.write_all(b",")?;
+        }
+        let ResourceContents::Blob(BlobResourceContents {
+            uri,
+            blob: BlobData::BlobStream(stream),
+            options,
+        }) = contents
+        else {
+            let contents = convert_resource_contents(contents).map_err(std::io::Error::other)?;
+            serde_json::to_writer(&mut *writer, &contents)?;
+            continue;
+        };
+
+        let mime_type = options.as_ref().and_then(|o| o.mime_type.as_deref());
+        writer.write_all(br#"{"uri":"#)?;
+        serde_json::to_writer(&mut *writer, uri)?;
+        writer.write_all(br#","mimeType":"#)?;
+        serde_json::to_writer(&mut *writer, &mime_type)?;
+        writer.write_all(br#","blob":""#)?;
+        let config = StreamConfig {
+            max_size: blob_limits::max_bytes(mime_type),
+            ..StreamConfig::default()
+        };
+        write_blob_stream(stream, &config, writer).map_err(|e| match e {
+            BlobError::TooLarge(limit) => {
+                std::io::Error::other(oversized_blob_message(mime_type, limit))
+            }
+            BlobError::Stream(e) => std::io::Error::other(e),
+        })?;
+        writer.write_all(br#""}"#)?;
+    }
+    writer.write_all(b"]}}")
+}
+
 fn convert_call_tool_result(result: &CallToolResult) -> Result<JsonCallToolResult, String> {
     let content = result
         .content
@@ -492,8 +626,16 @@ fn convert_call_tool_result(result: &CallToolResult) -> Result<JsonCallToolResul
         .map(convert_content_block)
         .collect::<Result<Vec<_>, _>>()?;
 
+    let structured_content = result
+        .structured_content
+        .as_deref()
+        .map(serde_json::from_str::<Value>)
+        .transpose()
+        .map_err(|e| format!("Invalid structured content JSON: {}", e))?;
+
     Ok(JsonCallToolResult {
         content,
+        structured_content,
         is_error: result.is_error,
     })
 }
@@ -529,10 +671,21 @@ fn convert_resource_contents(contents: &ResourceContents) -> Result<JsonResource
             }))
         }
         ResourceContents::Blob(blob_res) => {
-            let blob = convert_blob_data(&blob_res.blob)?;
+            let mime_type = blob_res.options.as_ref().and_then(|o| o.mime_type.clone());
+            let blob = match convert_blob_data(&blob_res.blob, mime_type.as_deref()) {
+                Ok(blob) => blob,
+                Err(BlobError::TooLarge(limit)) => {
+                    return Ok(JsonResourceContents::Text(JsonTextResourceContents {
+                        uri: blob_res.uri.clone(),
+                        mime_type: Some("text/plain".to_string()),
+                        text: oversized_blob_message(mime_type.as_deref(), limit),
+                    }));
+                }
+                Err(BlobError::Stream(e)) => return Err(e),
+            };
             Ok(JsonResourceContents::Blob(JsonBlobResourceContents {
                 uri: blob_res.uri.clone(),
-                mime_type: blob_res.options.as_ref().and_then(|o| o.mime_type.clone()),
+                mime_type,
                 blob,
             }))
         }
@@ -647,7 +800,14 @@ pub fn convert_content_block(block: &ContentBlock) -> Result<JsonContentBlock, S
             }))
         }
         ContentBlock::Image(image_content) => {
-            let data = convert_blob_data(&image_content.data)?;
+            let mime_type = Some(image_content.mime_type.as_str());
+            let data = match convert_blob_data(&image_content.data, mime_type) {
+                Ok(data) => data,
+                Err(BlobError::TooLarge(limit)) => {
+                    return Ok(oversized_blob_block(mime_type, limit));
+                }
+                Err(BlobError::Stream(e)) => return Err(e),
+            };
             Ok(JsonContentBlock::Image(JsonImageContent {
                 data,
                 mime_type: image_content.mime_type.clone(),
@@ -659,7 +819,14 @@ pub fn convert_content_block(block: &ContentBlock) -> Result<JsonContentBlock, S
             }))
         }
         ContentBlock::Audio(audio_content) => {
-            let data = convert_blob_data(&audio_content.data)?;
+            let mime_type = Some(audio_content.mime_type.as_str());
+            let data = match convert_blob_data(&audio_content.data, mime_type) {
+                Ok(data) => data,
+                Err(BlobError::TooLarge(limit)) => {
+                    return Ok(oversized_blob_block(mime_type, limit));
+                }
+                Err(BlobError::Stream(e)) => return Err(e),
+            };
             Ok(JsonContentBlock::Audio(JsonImageContent {
                 data,
                 mime_type: audio_content.mime_type.clone(),
@@ -675,9 +842,21 @@ pub fn convert_content_block(block: &ContentBlock) -> Result<JsonContentBlock, S
             text: None,
             blob: None,
             mime_type: link.options.as_ref().and_then(|o| o.mime_type.clone()),
+            annotations: link
+                .options
+                .as_ref()
+                .and_then(|o| o.annotations.as_ref())
+                .map(convert_annotations),
         })),
         ContentBlock::EmbeddedResource(embedded) => {
             use crate::bindings::wasmcp::mcp_v20251125::mcp::ResourceContents;
+            let annotations = || {
+                embedded
+                    .options
+                    .as_ref()
+                    .and_then(|o| o.annotations.as_ref())
+                    .map(convert_annotations)
+            };
             match &embedded.resource {
                 ResourceContents::Text(text_res) => {
                     let text = convert_text_data(&text_res.text)?;
@@ -686,15 +865,24 @@ pub fn convert_content_block(block: &ContentBlock) -> Result<JsonContentBlock, S
                         text: Some(text),
                         blob: None,
                         mime_type: text_res.options.as_ref().and_then(|o| o.mime_type.clone()),
+                        annotations: annotations(),
                     }))
                 }
                 ResourceContents::Blob(blob_res) => {
-                    let blob = convert_blob_data(&blob_res.blob)?;
+                    let mime_type = blob_res.options.as_ref().and_then(|o| o.mime_type.clone());
+                    let blob = match convert_blob_data(&blob_res.blob, mime_type.as_deref()) {
+                        Ok(blob) => blob,
+                        Err(BlobError::TooLarge(limit)) => {
+                            return Ok(oversized_blob_block(mime_type.as_deref(), limit));
+                        }
+                        Err(BlobError::Stream(e)) => return Err(e),
+                    };
                     Ok(JsonContentBlock::Resource(JsonResourceContent {
                         uri: blob_res.uri.clone(),
                         text: None,
                         blob: Some(blob),
-                        mime_type: blob_res.options.as_ref().and_then(|o| o.mime_type.clone()),
+                        mime_type,
+                        annotations: annotations(),
                     }))
                 }
             }
@@ -722,13 +910,19 @@ pub fn serialize_jsonrpc_response(
         }
         Err(error) => {
             let (code, message) = serialize_error_code(error);
+            let (code, data) =
+                crate::serialization::error_codes::for_negotiated_version(code, None);
+            let mut error = json!({
+                "code": code,
+                "message": message
+            });
+            if let Some(data) = data {
+                error["data"] = data;
+            }
             json!({
                 "jsonrpc": "2.0",
                 "id": JsonRequestId::from(id),
-                "error": {
-                    "code": code,
-                    "message": message
-                }
+                "error": error
             })
         }
     }
@@ -825,6 +1019,11 @@ pub fn serialize_server_response(response: &ServerResult) -> Value {
                 })
             })
         }
+        ServerResult::Custom(result) => serde_json::from_str(result).unwrap_or_else(|e| {
+            json!({
+                "error": format!("Failed to parse custom method result: {}", e)
+            })
+        }),
     }
 }
 
@@ -5,6 +5,7 @@
 
 use crate::bindings::wasi::io::streams::{InputStream, StreamError};
 use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
+use std::io::Write;
 
 /// Configuration for stream reading behavior
 pub struct StreamConfig {
@@ -34,13 +35,69 @@ pub fn read_text_stream(stream: &InputStream, config: &StreamConfig) -> Result<S
     String::from_utf8(bytes).map_err(|e| format!("Invalid UTF-8 in text stream: {}", e))
 }
 
+/// Why a blob could not be encoded
+#[derive(Debug)]
+pub enum BlobError {
+    /// The blob is larger than the configured limit (in bytes)
+    TooLarge(u64),
+    /// Reading the blob or writing its encoding failed
+    Stream(String),
+}
+
 /// Read an input stream to base64-encoded string (for blob-stream variant)
 ///
-/// Reads stream in chunks then encodes to base64.
-/// Returns error if stream exceeds max_size.
-pub fn read_blob_stream(stream: &InputStream, config: &StreamConfig) -> Result<String, String> {
-    let bytes = read_bytes_chunked(stream, config)?;
-    Ok(BASE64.encode(&bytes))
+/// Returns `TooLarge` if the stream exceeds max_size.
+pub fn read_blob_stream(stream: &InputStream, config: &StreamConfig) -> Result<String, BlobError> {
+    let mut encoded = Vec::new();
+    write_blob_stream(stream, config, &mut encoded)?;
+    // Base64 output is ASCII
+    String::from_utf8(encoded).map_err(|e| BlobError::Stream(e.to_string()))
+}
+
+/// Base64-encode an input stream into a writer
+///
+/// Each chunk is encoded and written as soon as it is read, so neither the
+/// raw bytes nor the full encoding are ever held. Whole 3-byte groups encode
+/// without padding, so up to two bytes are carried over to the next chunk.
+/// Returns `TooLarge` once the stream exceeds max_size; whatever was encoded
+/// before that has already been written.
+pub fn write_blob_stream<W: Write>(
+    stream: &InputStream,
+    config: &StreamConfig,
+    writer: &mut W,
+) -> Result<(), BlobError> {
+    let chunk_size = (config.chunk_size / 3).max(1) * 3;
+    let mut encoded = String::new();
+    let mut pending: Vec<u8> = Vec::new();
+    let mut total_read = 0u64;
+    let mut write = |encoded: &mut String| {
+        let written = writer.write_all(encoded.as_bytes());
+        encoded.clear();
+        written.map_err(|e| BlobError::Stream(format!("Write error: {}", e)))
+    };
+
+    loop {
+        let chunk = match stream.blocking_read(chunk_size) {
+            Ok(chunk) if chunk.is_empty() => break,
+            Ok(chunk) => chunk,
+            Err(StreamError::Closed) => break,
+            Err(e) => return Err(BlobError::Stream(format!("Stream read error: {:?}", e))),
+        };
+
+        total_read += chunk.len() as u64;
+        if total_read > config.max_size {
+            return Err(BlobError::TooLarge(config.max_size));
+        }
+
+        pending.extend_from_slice(&chunk);
+        let whole = pending.len() / 3 * 3;
+        BASE64.encode_string(&pending[..whole], &mut encoded);
+        pending.drain(..whole);
+        write(&mut encoded)?;
+    }
+
+    BASE64.encode_string(&pending, &mut encoded);
+    write(&mut encoded)
 }
 
 /// Read an input stream in chunks with size limit
@@ -0,0 +1,132 @@
+//! Incremental writing of large messages
+//!
+//! A tools/list result for thousands of tools serializes to megabytes, and
+//! the regular path holds it several times over: converted tools, a JSON
+//! value, the string, the framed bytes. On streaming frames (SSE, stdio),
+//! tools/list responses are instead serialized one tool at a time into
+//! 64KB chunks, each written to the output stream with backpressure (see
+//! `writing::write_all`), so peak memory no longer grows with the list.
+//!
+//! resources/read responses with blob streams are written the same way:
+//! each chunk read from a blob stream is base64-encoded and written before
+//! the next is read, instead of the encoding being collected into a JSON
+//! value. The size limit (see `blob_limits`) is only reached mid-write, so
+//! a stream over its limit fails the message rather than being replaced by
+//! a text block as on the regular path.
+//!
+//! Messages that must be kept whole still take the regular path: buffered
+//! (plain JSON) and held messages, exchanges being recorded, and lists with
+//! an invalid input schema (reported as an error result).
+
+use crate::bindings::exports::wasmcp::mcp_v20251125::server_io::{IoError, MessageFrame};
+use crate::bindings::wasi::io::streams::OutputStream;
+use crate::bindings::wasmcp::mcp_v20251125::mcp::{RequestId, ServerMessage, ServerResult};
+use crate::{recording, serializer, writing};
+use std::io::{self, Write};
+
+/// Bytes collected before a write to the stream
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Writes to an output stream in chunks
+struct StreamWriter<'a> {
+    stream: &'a OutputStream,
+    chunk: Vec<u8>,
+    /// The stream error behind a failed write
+    error: Option<IoError>,
+}
+
+impl StreamWriter<'_> {
+    fn write_chunk(&mut self) -> io::Result<()> {
+        if self.chunk.is_empty() {
+            return Ok(());
+        }
+        writing::write_all(self.stream, &self.chunk).map_err(|e| {
+            self.error = Some(e);
+            io::Error::other("stream write failed")
+        })?;
+        self.chunk.clear();
+        Ok(())
+    }
+}
+
+impl Write for StreamWriter<'_> {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        self.chunk.extend_from_slice(data);
+        if self.chunk.len() >= CHUNK_SIZE {
+            self.write_chunk()?;
+        }
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.write_chunk()
+    }
+}
+
+/// Write a message incrementally, or None when it takes the regular path
+pub fn try_send(
+    output: &OutputStream,
+    message: &ServerMessage,
+    frame: &MessageFrame,
+) -> Option<Result<(), IoError>> {
+    let ServerMessage::Result((id, result)) = message else {
+        return None;
+    };
+    let incremental = match result {
+        ServerResult::ToolsList(result) => serializer::tool_schemas_valid(result),
+        ServerResult::ResourcesRead(result) => serializer::has_blob_streams(result),
+        _ => false,
+    };
+    if !incremental
+        || writing::should_buffer(frame)
+        || writing::is_holding()
+        || recording::is_recording()
+    {
+        return None;
+    }
+
+    let mut writer = StreamWriter {
+        stream: output,
+        chunk: Vec::with_capacity(CHUNK_SIZE),
+        error: None,
+    };
+    let written = writer
+        .write_all(&frame.prefix)
+        .and_then(|()| write_response(id, result, &mut writer))
+        .and_then(|()| writer.write_all(&frame.suffix))
+        .and_then(|()| writer.flush());
+
+    Some(written.map_err(|e| {
+        writer
+            .error
+            .take()
+            .unwrap_or_else(|| IoError::Serialization(e.to_string()))
+    }))
+}
+
+/// Whether a message can be serialized again after a failed `try_send`
+///
+/// Blob streams have been read by then, so only lists can be kept for the
+/// client to receive on reconnect.
+pub fn can_retry(message: &ServerMessage) -> bool {
+    matches!(
+        message,
+        ServerMessage::Result((_, ServerResult::ToolsList(_)))
+    )
+}
+
+fn write_response(
+    id: &RequestId,
+    result: &ServerResult,
+    writer: &mut StreamWriter,
+) -> io::Result<()> {
+    match result {
+        ServerResult::ToolsList(result) => {
+            serializer::write_list_tools_response(id, result, writer)
+        }
+        ServerResult::ResourcesRead(result) => {
+            serializer::write_read_resource_response(id, result, writer)
+        }
+        _ => Err(io::Error::other("result is not written incrementally")),
+    }
+}
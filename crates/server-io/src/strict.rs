@@ -0,0 +1,132 @@
+//! Strict JSON-RPC validation (opt-in)
+//!
+//! With `WASMCP_STRICT_JSONRPC=true`, incoming messages are checked before
+//! parsing and rejected when they:
+//! - carry a `jsonrpc` member other than exactly `"2.0"`
+//! - have top-level members outside the JSON-RPC 2.0 envelope
+//! - name a method that is not recognized, with the closest known method
+//!   suggested in the error message when one is near
+//!
+//! The default mode stays lenient for compatibility with existing clients.
+//! Strict mode is meant for developing new clients against the server.
+
+use crate::bindings::exports::wasmcp::mcp_v20251125::server_io::IoError;
+use crate::bindings::wasi::cli::environment::get_environment;
+use serde_json::Value;
+
+const STRICT_ENV: &str = "WASMCP_STRICT_JSONRPC";
+
+/// Members allowed at the top level of a JSON-RPC 2.0 message
+const ENVELOPE_FIELDS: &[&str] = &["jsonrpc", "id", "method", "params", "result", "error"];
+
+/// Methods a client may send, as recognized by the parser
+const KNOWN_METHODS: &[&str] = &[
+    "initialize",
+    "ping",
+    "tools/list",
+    "tools/call",
+    "resources/list",
+    "resources/read",
+    "resources/templates/list",
+    "resources/subscribe",
+    "resources/unsubscribe",
+    "prompts/list",
+    "prompts/get",
+    "completion/complete",
+    "logging/setLevel",
+    "notifications/initialized",
+    "notifications/roots/list_changed",
+    "notifications/cancelled",
+    "notifications/progress",
+];
+
+/// Largest edit distance still reported as a suggestion
+const MAX_SUGGESTION_DISTANCE: usize = 3;
+
+thread_local! {
+    static ENABLED: bool = get_environment()
+        .into_iter()
+        .any(|(key, value)| key == STRICT_ENV && value.eq_ignore_ascii_case("true"));
+}
+
+/// Whether strict validation is turned on
+pub fn enabled() -> bool {
+    ENABLED.with(|e| *e)
+}
+
+/// Check a message against the strict rules
+pub fn validate(json: &Value) -> Result<(), IoError> {
+    let obj = json
+        .as_object()
+        .ok_or_else(|| IoError::InvalidJsonrpc("Message must be a JSON object".to_string()))?;
+
+    match obj.get("jsonrpc") {
+        Some(Value::String(version)) if version == "2.0" => {}
+        Some(other) => {
+            return Err(IoError::InvalidJsonrpc(format!(
+                "Invalid jsonrpc version {}, expected \"2.0\"",
+                other
+            )));
+        }
+        None => {
+            return Err(IoError::InvalidJsonrpc(
+                "Missing jsonrpc member, expected \"2.0\"".to_string(),
+            ));
+        }
+    }
+
+    let unknown: Vec<&str> = obj
+        .keys()
+        .map(String::as_str)
+        .filter(|key| !ENVELOPE_FIELDS.contains(key))
+        .collect();
+    if !unknown.is_empty() {
+        return Err(IoError::InvalidJsonrpc(format!(
+            "Unknown top-level member(s): {}",
+            unknown.join(", ")
+        )));
+    }
+
+    if let Some(method) = obj.get("method").and_then(Value::as_str)
+        && !KNOWN_METHODS.contains(&method)
+        && !method.starts_with("x-")
+    {
+        let message = match suggest_method(method) {
+            Some(suggestion) => format!(
+                "Unknown method '{}'. Did you mean '{}'?",
+                method, suggestion
+            ),
+            None => format!("Unknown method '{}'", method),
+        };
+        return Err(IoError::InvalidMcp(message));
+    }
+
+    Ok(())
+}
+
+/// Closest known method, if it is within a few edits
+pub(crate) fn suggest_method(method: &str) -> Option<&'static str> {
+    KNOWN_METHODS
+        .iter()
+        .map(|known| (*known, edit_distance(method, known)))
+        .filter(|(_, distance)| *distance <= MAX_SUGGESTION_DISTANCE)
+        .min_by_key(|(_, distance)| *distance)
+        .map(|(known, _)| known)
+}
+
+/// Levenshtein distance, case-insensitive
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.to_lowercase().chars().collect();
+    let b: Vec<char> = b.to_lowercase().chars().collect();
+
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
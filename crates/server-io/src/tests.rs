@@ -153,6 +153,632 @@ mod tests {
         assert_eq!(json2["id"], 1);
     }
 
+    #[test]
+    fn test_custom_method_parsing() {
+        use crate::bindings::wasmcp::mcp_v20251125::mcp::ClientRequest;
+        use crate::parser;
+
+        // x-* methods are passed through as custom requests
+        let json = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "x-acme/status",
+            "params": {"verbose": true}
+        });
+        match parser::parse_client_request(&json).unwrap() {
+            ClientRequest::Custom(req) => {
+                assert_eq!(req.method, "x-acme/status");
+                assert_eq!(req.params.as_deref(), Some("{\"verbose\":true}"));
+            }
+            other => panic!("Expected custom request, got {:?}", other),
+        }
+
+        // Other unknown methods are carried as unknown, for MethodNotFound downstream
+        let json = serde_json::json!({"jsonrpc": "2.0", "id": 2, "method": "acme/status"});
+        match parser::parse_client_request(&json).unwrap() {
+            ClientRequest::Unknown(method) => assert_eq!(method, "acme/status"),
+            other => panic!("Expected unknown request, got {:?}", other),
+        }
+
+        // Experimental methods reach their middleware as custom requests too
+        let json = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 3,
+            "method": "resources/search",
+            "params": {"query": "readme"}
+        });
+        match parser::parse_client_request(&json).unwrap() {
+            ClientRequest::Custom(req) => {
+                assert_eq!(req.method, "resources/search");
+                assert_eq!(req.params.as_deref(), Some("{\"query\":\"readme\"}"));
+            }
+            other => panic!("Expected custom request, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_completion_reference_parsing() {
+        use crate::bindings::wasmcp::mcp_v20251125::mcp::{ClientRequest, CompletionReference};
+        use crate::parser;
+
+        let complete = |reference: serde_json::Value| {
+            let json = serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "completion/complete",
+                "params": {"ref": reference, "argument": {"name": "lang", "value": "ru"}}
+            });
+            match parser::parse_client_request(&json) {
+                Ok(ClientRequest::CompletionComplete(req)) => Ok(req.ref_),
+                Ok(other) => panic!("Expected completion request, got {:?}", other),
+                Err(e) => Err(e),
+            }
+        };
+
+        match complete(serde_json::json!({"type": "ref/prompt", "name": "review"})).unwrap() {
+            CompletionReference::Prompt(prompt) => assert_eq!(prompt.name, "review"),
+            other => panic!("Expected prompt reference, got {:?}", other),
+        }
+        match complete(serde_json::json!({"type": "ref/resource", "uri": "file:///{path}"}))
+            .unwrap()
+        {
+            CompletionReference::ResourceTemplate(uri) => assert_eq!(uri, "file:///{path}"),
+            other => panic!("Expected resource reference, got {:?}", other),
+        }
+        match complete(serde_json::json!({"type": "ref/tool", "name": "search"})).unwrap() {
+            CompletionReference::ResourceTemplate(uri) => {
+                assert_eq!(uri, format!("{}search", parser::TOOL_REF_URI_PREFIX))
+            }
+            other => panic!("Expected tool reference, got {:?}", other),
+        }
+        assert!(complete(serde_json::json!({"type": "ref/other", "name": "x"})).is_err());
+        assert!(complete(serde_json::json!({"type": "ref/tool"})).is_err());
+    }
+
+    #[test]
+    fn test_partial_result_notification_serialization() {
+        use crate::bindings::wasmcp::mcp_v20251125::mcp::{
+            ContentBlock, PartialResultNotification, RequestId, ServerNotification, TextContent,
+            TextData,
+        };
+        use crate::serialization::notifications::serialize_server_notification;
+
+        let notification = ServerNotification::ToolPartialResult(PartialResultNotification {
+            request_id: RequestId::Number(7),
+            content: vec![ContentBlock::Text(TextContent {
+                text: TextData::Text("partial".to_string()),
+                options: None,
+            })],
+            meta: None,
+        });
+
+        let (method, params) = serialize_server_notification(&notification);
+        assert_eq!(method, "notifications/tools/partial_result");
+        assert_eq!(params["requestId"], 7);
+        assert_eq!(params["content"][0]["type"], "text");
+        assert_eq!(params["content"][0]["text"], "partial");
+        assert!(params.get("_meta").is_none());
+    }
+
+    #[test]
+    fn test_server_capabilities_lists_serialization() {
+        use crate::bindings::wasmcp::mcp_v20251125::mcp::{
+            Implementation, InitializeResult, ProtocolVersion, ServerCapabilities, ServerLists,
+            ServerResult, ServerSubscriptions,
+        };
+
+        let result = |capabilities| {
+            ServerResult::Initialize(InitializeResult {
+                meta: None,
+                server_info: Implementation {
+                    name: "test".to_string(),
+                    title: None,
+                    version: "1.0.0".to_string(),
+                    description: None,
+                    icons: None,
+                },
+                capabilities,
+                protocol_version: ProtocolVersion::V20251125,
+                options: None,
+            })
+        };
+
+        // Offered lists only get listChanged when flagged
+        let json = serializer::serialize_server_response(&result(ServerCapabilities {
+            completions: None,
+            experimental: None,
+            logging: None,
+            list_changed: Some(ServerLists::PROMPTS),
+            subscriptions: Some(ServerSubscriptions::RESOURCES),
+            lists: Some(ServerLists::TOOLS | ServerLists::RESOURCES | ServerLists::PROMPTS),
+        }));
+        let caps = &json["capabilities"];
+        assert_eq!(caps["tools"], serde_json::json!({}));
+        assert_eq!(caps["resources"], serde_json::json!({"subscribe": true}));
+        assert_eq!(caps["prompts"], serde_json::json!({"listChanged": true}));
+
+        // Without lists, list-changed implies the feature is offered
+        let json = serializer::serialize_server_response(&result(ServerCapabilities {
+            completions: None,
+            experimental: None,
+            logging: None,
+            list_changed: Some(ServerLists::TOOLS),
+            subscriptions: None,
+            lists: None,
+        }));
+        let caps = &json["capabilities"];
+        assert_eq!(caps["tools"], serde_json::json!({"listChanged": true}));
+        assert!(caps.get("resources").is_none());
+        assert!(caps.get("prompts").is_none());
+    }
+
+    #[test]
+    fn test_sampling_content_round_trip() {
+        use crate::bindings::wasmcp::mcp_v20251125::mcp::{
+            Blob, BlobData, ClientResult, ContentBlock, IncludeContext, Role, SamplingContentBlock,
+            SamplingCreateMessageRequest, SamplingMessage, ServerRequest, TextContent, TextData,
+        };
+        use crate::serialization::requests::serialize_server_request;
+
+        let blob = |data: &[u8], mime_type: &str| Blob {
+            data: BlobData::Blob(data.to_vec()),
+            mime_type: mime_type.to_string(),
+            options: None,
+        };
+        let messages = vec![
+            SamplingMessage {
+                content: SamplingContentBlock::Text(TextContent {
+                    text: TextData::Text("Describe this".to_string()),
+                    options: None,
+                }),
+                role: Role::User,
+            },
+            SamplingMessage {
+                content: SamplingContentBlock::Image(blob(&[0x89, 0x50, 0x4e], "image/png")),
+                role: Role::User,
+            },
+            SamplingMessage {
+                content: SamplingContentBlock::Audio(blob(&[1, 2, 3, 4], "audio/wav")),
+                role: Role::Assistant,
+            },
+        ];
+        let request = ServerRequest::SamplingCreateMessage(SamplingCreateMessageRequest {
+            include_context: IncludeContext::None,
+            max_tokens: 100,
+            messages,
+            metadata: None,
+            model_preferences: None,
+            stop_sequences: None,
+            system_prompt: None,
+            temperature: None,
+            tools: None,
+            tool_choice: None,
+        });
+
+        let (method, params) = serialize_server_request(&request);
+        assert_eq!(method, "sampling/createMessage");
+        let contents: Vec<_> = params["messages"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|m| m["content"].clone())
+            .collect();
+        assert_eq!(
+            contents[0],
+            serde_json::json!({"type": "text", "text": "Describe this"})
+        );
+        assert_eq!(
+            contents[1],
+            serde_json::json!({"type": "image", "data": "iVBO", "mimeType": "image/png"})
+        );
+        assert_eq!(
+            contents[2],
+            serde_json::json!({"type": "audio", "data": "AQIDBA==", "mimeType": "audio/wav"})
+        );
+
+        // The same content coming back in a sampling result parses to the same values
+        let parse = |content: &serde_json::Value| {
+            let json = serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": {"model": "test-model", "role": "assistant", "content": content}
+            });
+            match crate::parser::parse_client_result(&json) {
+                Ok(ClientResult::SamplingCreateMessage(result)) => result.content,
+                other => panic!("Expected sampling result, got {:?}", other),
+            }
+        };
+        match parse(&contents[0]) {
+            ContentBlock::Text(TextContent {
+                text: TextData::Text(text),
+                ..
+            }) => assert_eq!(text, "Describe this"),
+            other => panic!("Expected text content, got {:?}", other),
+        }
+        match parse(&contents[1]) {
+            ContentBlock::Image(Blob {
+                data: BlobData::Blob(data),
+                mime_type,
+                ..
+            }) => {
+                assert_eq!(data, [0x89, 0x50, 0x4e]);
+                assert_eq!(mime_type, "image/png");
+            }
+            other => panic!("Expected image content, got {:?}", other),
+        }
+        match parse(&contents[2]) {
+            ContentBlock::Audio(Blob {
+                data: BlobData::Blob(data),
+                mime_type,
+                ..
+            }) => {
+                assert_eq!(data, [1, 2, 3, 4]);
+                assert_eq!(mime_type, "audio/wav");
+            }
+            other => panic!("Expected audio content, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_content_annotations_round_trip() {
+        use crate::bindings::wasmcp::mcp_v20251125::mcp::{
+            Annotations, ClientResult, ContentBlock, ContentOptions, IncludeContext,
+            ResourceLinkContent, ResourceLinkOptions, Role, SamplingContentBlock,
+            SamplingCreateMessageRequest, SamplingMessage, ServerRequest, TextContent, TextData,
+        };
+        use crate::serialization::requests::serialize_server_request;
+
+        let annotations = || Annotations {
+            audience: Some(vec![Role::User]),
+            last_modified: Some("2025-01-12T15:00:58Z".to_string()),
+            priority: Some(0.5),
+        };
+        let expected = serde_json::json!({
+            "audience": ["user"],
+            "lastModified": "2025-01-12T15:00:58Z",
+            "priority": 0.5
+        });
+
+        // Tool results
+        let link = ContentBlock::ResourceLink(ResourceLinkContent {
+            uri: "file:///report.pdf".to_string(),
+            name: "report".to_string(),
+            options: Some(ResourceLinkOptions {
+                title: None,
+                description: None,
+                size: None,
+                mime_type: None,
+                annotations: Some(annotations()),
+                meta: None,
+                icons: None,
+            }),
+        });
+        let json = serde_json::to_value(serializer::convert_content_block(&link).unwrap()).unwrap();
+        assert_eq!(json["annotations"], expected);
+
+        // Sampling requests, and the same block parsed back from a sampling result
+        let request = ServerRequest::SamplingCreateMessage(SamplingCreateMessageRequest {
+            include_context: IncludeContext::None,
+            max_tokens: 100,
+            messages: vec![SamplingMessage {
+                content: SamplingContentBlock::Text(TextContent {
+                    text: TextData::Text("For the user".to_string()),
+                    options: Some(ContentOptions {
+                        annotations: Some(annotations()),
+                        meta: None,
+                    }),
+                }),
+                role: Role::Assistant,
+            }],
+            metadata: None,
+            model_preferences: None,
+            stop_sequences: None,
+            system_prompt: None,
+            temperature: None,
+            tools: None,
+            tool_choice: None,
+        });
+        let (_, params) = serialize_server_request(&request);
+        let content = &params["messages"][0]["content"];
+        assert_eq!(content["annotations"], expected);
+
+        let json = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": {"model": "test-model", "role": "assistant", "content": content}
+        });
+        match crate::parser::parse_client_result(&json) {
+            Ok(ClientResult::SamplingCreateMessage(result)) => match result.content {
+                ContentBlock::Text(TextContent {
+                    options: Some(options),
+                    ..
+                }) => {
+                    let parsed = options.annotations.unwrap();
+                    assert!(matches!(parsed.audience.as_deref(), Some([Role::User])));
+                    assert_eq!(parsed.last_modified, annotations().last_modified);
+                    assert_eq!(parsed.priority, Some(0.5));
+                }
+                other => panic!("Expected annotated text, got {:?}", other),
+            },
+            other => panic!("Expected sampling result, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_blob_limits_matching() {
+        use crate::blob_limits::BlobLimits;
+
+        let limits = BlobLimits::parse("image/*=100, image/png=200, *=300, bogus, audio/mpeg=x");
+        assert_eq!(limits.max_bytes(Some("image/png")), 200);
+        assert_eq!(limits.max_bytes(Some("IMAGE/PNG; q=1")), 200);
+        assert_eq!(limits.max_bytes(Some("image/jpeg")), 100);
+        assert_eq!(limits.max_bytes(Some("audio/mpeg")), 300);
+        assert_eq!(limits.max_bytes(None), 300);
+
+        // Without a catch-all rule the stream reader default applies
+        let limits = BlobLimits::parse("");
+        assert_eq!(limits.max_bytes(Some("image/png")), 50 * 1024 * 1024);
+    }
+
+    #[test]
+    fn test_read_resource_multiple_contents_serialization() {
+        use crate::bindings::wasmcp::mcp_v20251125::mcp::{
+            BlobData, BlobResourceContents, ReadResourceResult, RequestId, ResourceContents,
+            ServerResult, TextData, TextResourceContents,
+        };
+
+        let read = ReadResourceResult {
+            contents: vec![
+                ResourceContents::Text(TextResourceContents {
+                    uri: "docs://guides/a.md".to_string(),
+                    text: TextData::Text("# A".to_string()),
+                    options: None,
+                }),
+                ResourceContents::Blob(BlobResourceContents {
+                    uri: "docs://guides/b.png".to_string(),
+                    blob: BlobData::Blob(vec![1, 2, 3]),
+                    options: None,
+                }),
+            ],
+            meta: None,
+        };
+
+        // The incremental writer produces the same response
+        let id = RequestId::String("read-1".to_string());
+        let mut written = Vec::new();
+        serializer::write_read_resource_response(&id, &read, &mut written).unwrap();
+        assert!(!serializer::has_blob_streams(&read));
+
+        let result = ServerResult::ResourcesRead(read);
+        let written: serde_json::Value = serde_json::from_slice(&written).unwrap();
+        assert_eq!(
+            written,
+            serializer::serialize_jsonrpc_response(&id, Ok(&result))
+        );
+
+        let json = serializer::serialize_server_response(&result);
+        let contents = json["contents"].as_array().unwrap();
+        assert_eq!(contents.len(), 2);
+        assert_eq!(contents[0]["uri"], "docs://guides/a.md");
+        assert_eq!(contents[0]["text"], "# A");
+        assert_eq!(contents[1]["uri"], "docs://guides/b.png");
+        assert_eq!(contents[1]["blob"], "AQID");
+    }
+
+    #[test]
+    fn test_call_tool_result_without_output() {
+        use crate::bindings::wasmcp::mcp_v20251125::mcp::{CallToolResult, ServerResult};
+
+        let result = ServerResult::ToolsCall(CallToolResult {
+            meta: None,
+            content: vec![],
+            is_error: None,
+            structured_content: None,
+        });
+        let json = serializer::serialize_server_response(&result);
+        assert_eq!(json, serde_json::json!({ "content": [] }));
+
+        let result = ServerResult::ToolsCall(CallToolResult {
+            meta: None,
+            content: vec![],
+            is_error: Some(false),
+            structured_content: Some(r#"{"deleted": 3}"#.to_string()),
+        });
+        let json = serializer::serialize_server_response(&result);
+        assert_eq!(
+            json,
+            serde_json::json!({ "content": [], "structuredContent": { "deleted": 3 }, "isError": false })
+        );
+    }
+
+    #[test]
+    fn test_incremental_tools_list_matches_value() {
+        use crate::bindings::wasmcp::mcp_v20251125::mcp::{
+            ListToolsResult, RequestId, ServerResult, Tool, ToolOptions,
+        };
+
+        let tool = |name: &str, description: Option<&str>| Tool {
+            name: name.to_string(),
+            input_schema: r#"{"type": "object", "properties": {"q": {"type": "string"}}}"#
+                .to_string(),
+            options: description.map(|d| ToolOptions {
+                meta: None,
+                annotations: None,
+                description: Some(d.to_string()),
+                output_schema: None,
+                title: None,
+                icons: None,
+            }),
+        };
+        let id = RequestId::String("list-1".to_string());
+
+        for (tools, next_cursor) in [
+            (vec![], None),
+            (
+                vec![tool("search", Some("Find \"things\"")), tool("fetch", None)],
+                Some("page-2".to_string()),
+            ),
+        ] {
+            let result = ListToolsResult {
+                meta: None,
+                next_cursor,
+                tools,
+            };
+            let mut written = Vec::new();
+            serializer::write_list_tools_response(&id, &result, &mut written).unwrap();
+
+            let expected =
+                serializer::serialize_jsonrpc_response(&id, Ok(&ServerResult::ToolsList(result)));
+            let written: serde_json::Value = serde_json::from_slice(&written).unwrap();
+            assert_eq!(written, expected);
+        }
+    }
+
+    #[test]
+    fn test_tool_schemas_valid() {
+        use crate::bindings::wasmcp::mcp_v20251125::mcp::{ListToolsResult, Tool};
+
+        let result = |schema: &str| ListToolsResult {
+            meta: None,
+            next_cursor: None,
+            tools: vec![Tool {
+                name: "t".to_string(),
+                input_schema: schema.to_string(),
+                options: None,
+            }],
+        };
+        assert!(serializer::tool_schemas_valid(&result(
+            r#"{"type": "object"}"#
+        )));
+        assert!(!serializer::tool_schemas_valid(&result("{not json")));
+    }
+
+    #[test]
+    fn test_strict_validation() {
+        use crate::bindings::exports::wasmcp::mcp_v20251125::server_io::IoError;
+        use crate::strict;
+
+        let ok = serde_json::json!({"jsonrpc": "2.0", "id": 1, "method": "tools/list"});
+        assert!(strict::validate(&ok).is_ok());
+
+        let custom = serde_json::json!({"jsonrpc": "2.0", "id": 1, "method": "x-acme/status"});
+        assert!(strict::validate(&custom).is_ok());
+
+        let version = serde_json::json!({"jsonrpc": "1.0", "id": 1, "method": "ping"});
+        assert!(matches!(
+            strict::validate(&version),
+            Err(IoError::InvalidJsonrpc(_))
+        ));
+
+        let extra = serde_json::json!({"jsonrpc": "2.0", "id": 1, "method": "ping", "foo": 1});
+        match strict::validate(&extra) {
+            Err(IoError::InvalidJsonrpc(msg)) => assert!(msg.contains("foo")),
+            other => panic!("Expected unknown member error, got {:?}", other),
+        }
+
+        let typo = serde_json::json!({"jsonrpc": "2.0", "id": 1, "method": "tools/lsit"});
+        match strict::validate(&typo) {
+            Err(IoError::InvalidMcp(msg)) => assert!(msg.contains("Did you mean 'tools/list'?")),
+            other => panic!("Expected unknown method error, got {:?}", other),
+        }
+
+        assert_eq!(
+            strict::suggest_method("resources/reed"),
+            Some("resources/read")
+        );
+        assert_eq!(strict::suggest_method("completely/different"), None);
+    }
+
+    #[test]
+    fn test_error_codes_for_older_versions() {
+        use crate::bindings::wasmcp::mcp_v20251125::mcp::{
+            Error, ErrorCode, ProtocolVersion, RequestId, ServerMessage,
+        };
+        use crate::serialization::error_codes;
+        use crate::serialization::serialize_server_message;
+
+        let rate_limited = || {
+            ServerMessage::Error((
+                Some(RequestId::Number(1)),
+                ErrorCode::Server(Error {
+                    code: -32004,
+                    message: "Too many requests".to_string(),
+                    data: Some(r#"{"retryAfter":5}"#.to_string()),
+                }),
+            ))
+        };
+
+        // Unset and latest versions send the code unchanged
+        let json = serialize_server_message(&rate_limited()).unwrap();
+        assert_eq!(json["error"]["code"], -32004);
+        error_codes::set_protocol_version(ProtocolVersion::V20251125);
+        let json = serialize_server_message(&rate_limited()).unwrap();
+        assert_eq!(json["error"]["code"], -32004);
+        assert!(json["error"]["data"].get("originalCode").is_none());
+
+        error_codes::set_protocol_version(ProtocolVersion::V20250618);
+        let json = serialize_server_message(&rate_limited()).unwrap();
+        assert_eq!(json["error"]["code"], -32603);
+        assert_eq!(json["error"]["data"]["originalCode"], -32004);
+        assert_eq!(json["error"]["data"]["retryAfter"], 5);
+
+        // Spec codes every version defines are never translated
+        assert_eq!(
+            error_codes::translate(-32002, None, ProtocolVersion::V20241105),
+            (-32002, None)
+        );
+        assert_eq!(
+            error_codes::translate(
+                -32001,
+                Some(serde_json::json!("token expired")),
+                ProtocolVersion::V20241105
+            ),
+            (
+                -32600,
+                Some(serde_json::json!({"originalCode": -32001, "data": "token expired"}))
+            )
+        );
+    }
+
+    mod strategies {
+        wasmcp_fixtures::mcp_strategies!(crate::bindings::wasmcp::mcp_v20251125::mcp);
+    }
+
+    wasmcp_fixtures::proptest::proptest! {
+        #[test]
+        fn test_server_messages_serialize_to_jsonrpc(message in strategies::server_message()) {
+            use crate::bindings::wasmcp::mcp_v20251125::mcp::{ErrorCode, ServerMessage};
+            use crate::serialization::serialize_server_message;
+            use wasmcp_fixtures::proptest::prop_assert_eq;
+
+            let json = serialize_server_message(&message).unwrap();
+            prop_assert_eq!(&json["jsonrpc"], "2.0");
+            match &message {
+                ServerMessage::Result(_) => {
+                    prop_assert_eq!(json.get("id").is_some(), true);
+                    prop_assert_eq!(json.get("result").is_some(), true);
+                }
+                ServerMessage::Error((_, code)) => {
+                    let (ErrorCode::ParseError(e)
+                    | ErrorCode::InvalidRequest(e)
+                    | ErrorCode::MethodNotFound(e)
+                    | ErrorCode::InvalidParams(e)
+                    | ErrorCode::InternalError(e)
+                    | ErrorCode::Server(e)
+                    | ErrorCode::JsonRpc(e)
+                    | ErrorCode::Mcp(e)) = code;
+                    prop_assert_eq!(&json["error"]["code"], e.code);
+                    prop_assert_eq!(&json["error"]["message"], e.message.as_str());
+                }
+                ServerMessage::Notification(_) => {
+                    prop_assert_eq!(json.get("id"), None);
+                    prop_assert_eq!(json["method"].is_string(), true);
+                }
+                ServerMessage::Request(_) => {}
+            }
+        }
+    }
+
     // Note: More comprehensive tests requiring mock InputStream/OutputStream
     // would need WASI resource mocking, which is complex for unit tests.
     // Integration tests should verify full request/response handling.
@@ -3,6 +3,8 @@
 //! Provides buffered and streaming write modes:
 //! - Buffered mode (plain JSON): Accumulate all writes in memory, flush at end
 //! - Streaming mode (SSE/stdio): Write immediately with async yielding
+//! - Holding (after `hold-messages`): Keep every message, whatever its frame,
+//!   until the transport has chosen between a plain and a streamed response
 
 use crate::bindings::exports::wasmcp::mcp_v20251125::server_io::{IoError, MessageFrame};
 use crate::bindings::wasi::io::streams::OutputStream;
@@ -11,13 +13,67 @@ use std::cell::RefCell;
 /// Thread-local buffer for accumulating writes in buffered mode (plain JSON)
 thread_local! {
     pub(crate) static BUFFER: RefCell<Vec<u8>> = RefCell::new(Vec::new());
+
+    /// Messages held since `hold-messages` (None when not holding)
+    static HELD: RefCell<Option<Vec<Held>>> = const { RefCell::new(None) };
+}
+
+/// A framed message held for the transport
+struct Held {
+    framed: Vec<u8>,
+    prefix_len: usize,
+    suffix_len: usize,
+}
+
+/// Start holding messages until the next flush
+pub fn start_holding() {
+    HELD.with(|held| *held.borrow_mut() = Some(Vec::new()));
+}
+
+/// Whether messages are being held
+pub fn is_holding() -> bool {
+    HELD.with(|held| held.borrow().is_some())
+}
+
+/// Number of messages held (0 when not holding)
+pub fn held_count() -> u32 {
+    HELD.with(|held| held.borrow().as_ref().map_or(0, |h| h.len() as u32))
+}
+
+/// Keep a framed message if holding; returns whether it was kept
+pub fn hold(framed: &[u8], frame: &MessageFrame) -> bool {
+    HELD.with(|held| match held.borrow_mut().as_mut() {
+        Some(held) => {
+            held.push(Held {
+                framed: framed.to_vec(),
+                prefix_len: frame.prefix.len(),
+                suffix_len: frame.suffix.len(),
+            });
+            true
+        }
+        None => false,
+    })
+}
+
+/// Stop holding and return the bytes to write, or None when not holding
+pub fn take_held() -> Option<Vec<u8>> {
+    HELD.with(|held| held.borrow_mut().take())
+        .map(|held| held_body(&held))
+}
+
+/// A single message without its frame, several with their frames
+fn held_body(held: &[Held]) -> Vec<u8> {
+    match held {
+        [only] => only.framed[only.prefix_len..only.framed.len() - only.suffix_len].to_vec(),
+        _ => held.iter().flat_map(|h| h.framed.iter().copied()).collect(),
+    }
 }
 
 /// Determine if frame indicates buffering mode
 ///
 /// Plain JSON (empty prefix/suffix) requires buffering for atomic HTTP response.
 /// SSE and stdio (with framing) use immediate writes.
-fn should_buffer(frame: &MessageFrame) -> bool {
+pub(crate) fn should_buffer(frame: &MessageFrame) -> bool {
     frame.prefix.is_empty() && frame.suffix.is_empty()
 }
 
@@ -30,11 +86,6 @@ pub fn should_suppress_notifications(frame: &MessageFrame) -> bool {
 
 /// Write bytes to output stream with async yielding pattern
 ///
-/// Mimics Spin SDK's streaming pattern to avoid budget exhaustion:
-/// 1. Write data incrementally based on check_write() capacity
-/// 2. Flush after writing complete chunk
-/// 3. Subscribe to pollable to yield to async executor
-///
 /// Frame determines buffering: plain JSON buffers, SSE/stdio stream immediately.
 pub fn write_bytes(
     stream: &OutputStream,
@@ -49,8 +100,20 @@ pub fn write_bytes(
         return Ok(());
     }
 
-    // Streaming mode with async yielding pattern
+    write_all(stream, data)
+}
 
+/// Write all bytes and wait until they are flushed
+///
+/// Mimics Spin SDK's streaming pattern to avoid budget exhaustion:
+/// 1. Write data incrementally based on check_write() capacity
+/// 2. Subscribe to pollable to yield to async executor while the stream is full
+/// 3. Flush after writing the complete message and wait for the flush
+///
+/// Waiting for the flush means a message is fully handed to the host before
+/// the next one starts or the body is finished, and a failed flush is
+/// reported instead of silently cutting off the event.
+pub fn write_all(stream: &OutputStream, data: &[u8]) -> Result<(), IoError> {
     let mut offset = 0;
 
     // Write loop: incrementally write based on available capacity
@@ -82,11 +145,35 @@ pub fn write_bytes(
         }
     }
 
-    // Flush after writing complete message (like Spin SDK does)
-    stream.flush().map_err(|e| {
+    // Flush after writing complete message (like Spin SDK does), blocking
+    // until the host has taken the bytes
+    stream.blocking_flush().map_err(|e| {
         eprintln!("[SERVER-IO] ⚠️  Flush failed: {:?}", e);
         IoError::Stream(e)
     })?;
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sse(json: &str) -> Held {
+        Held {
+            framed: format!("data: {}\n\n", json).into_bytes(),
+            prefix_len: 6,
+            suffix_len: 2,
+        }
+    }
+
+    #[test]
+    fn test_held_body() {
+        assert_eq!(held_body(&[sse(r#"{"id":1}"#)]), br#"{"id":1}"#);
+        assert_eq!(
+            held_body(&[sse(r#"{"method":"x"}"#), sse(r#"{"id":1}"#)]),
+            b"data: {\"method\":\"x\"}\n\ndata: {\"id\":1}\n\n"
+        );
+        assert!(held_body(&[]).is_empty());
+    }
+}
@@ -0,0 +1,121 @@
+//! Session ID strategies
+//!
+//! Environment variables:
+//! - `WASMCP_SESSION_ID_STRATEGY`: "uuid" (default) or "prefixed"
+//! - `WASMCP_SESSION_ID_PREFIX`: Prefix for "prefixed" IDs, 1-32 of
+//!   `A-Z a-z 0-9 - _` (e.g. the instance's region or node name)
+//!
+//! "uuid" IDs are a bare UUID v4. "prefixed" IDs are `<prefix>.<uuid>`, so a
+//! load balancer can route a session back to the instance group that created
+//! it without reading the store. Every instance accepts both forms, whatever
+//! it generates itself: instances sharing a store can use different
+//! prefixes, and sessions created before a strategy change stay valid.
+
+use crate::bindings::wasi::cli::environment::get_environment;
+
+/// Separates a prefix from the UUID (not in UUIDs or prefixes)
+pub const PREFIX_SEPARATOR: char = '.';
+
+const MAX_PREFIX_LEN: usize = 32;
+
+/// How new session IDs are formed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IdStrategy {
+    Uuid,
+    Prefixed(String),
+}
+
+impl IdStrategy {
+    /// Strategy from environment variables
+    pub fn from_env() -> Result<Self, String> {
+        let env = get_environment();
+        let var = |key: &str| {
+            env.iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, v)| v.as_str())
+                .filter(|v| !v.is_empty())
+        };
+        Self::from_vars(
+            var("WASMCP_SESSION_ID_STRATEGY"),
+            var("WASMCP_SESSION_ID_PREFIX"),
+        )
+    }
+
+    pub fn from_vars(strategy: Option<&str>, prefix: Option<&str>) -> Result<Self, String> {
+        match strategy.map(str::to_lowercase).as_deref() {
+            None | Some("uuid") => Ok(IdStrategy::Uuid),
+            Some("prefixed") => {
+                let prefix = prefix.ok_or(
+                    "WASMCP_SESSION_ID_STRATEGY=prefixed requires WASMCP_SESSION_ID_PREFIX",
+                )?;
+                validate_prefix(prefix)?;
+                Ok(IdStrategy::Prefixed(prefix.to_string()))
+            }
+            Some(other) => Err(format!(
+                "Unknown WASMCP_SESSION_ID_STRATEGY '{}' (expected uuid or prefixed)",
+                other
+            )),
+        }
+    }
+
+    /// Session ID for a freshly generated UUID
+    pub fn session_id(&self, uuid: String) -> String {
+        match self {
+            IdStrategy::Uuid => uuid,
+            IdStrategy::Prefixed(prefix) => format!("{}{}{}", prefix, PREFIX_SEPARATOR, uuid),
+        }
+    }
+}
+
+/// Check a session ID prefix
+///
+/// The character set keeps prefixed IDs valid in headers and free of the
+/// `:` that separates session IDs from keys in the store.
+pub fn validate_prefix(prefix: &str) -> Result<(), String> {
+    if prefix.is_empty() || prefix.len() > MAX_PREFIX_LEN {
+        return Err(format!(
+            "Session ID prefix must be 1-{} characters",
+            MAX_PREFIX_LEN
+        ));
+    }
+    if !prefix
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err("Session ID prefix may only contain letters, digits, '-' and '_'".to_string());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UUID: &str = "550e8400-e29b-41d4-a716-446655440000";
+
+    #[test]
+    fn test_from_vars() {
+        assert_eq!(IdStrategy::from_vars(None, None), Ok(IdStrategy::Uuid));
+        assert_eq!(
+            IdStrategy::from_vars(Some("UUID"), Some("ignored")),
+            Ok(IdStrategy::Uuid)
+        );
+        assert_eq!(
+            IdStrategy::from_vars(Some("prefixed"), Some("eu-west_1")),
+            Ok(IdStrategy::Prefixed("eu-west_1".to_string()))
+        );
+        assert!(IdStrategy::from_vars(Some("prefixed"), None).is_err());
+        assert!(IdStrategy::from_vars(Some("prefixed"), Some("node:1")).is_err());
+        assert!(IdStrategy::from_vars(Some("prefixed"), Some(&"a".repeat(33))).is_err());
+        assert!(IdStrategy::from_vars(Some("sequential"), None).is_err());
+    }
+
+    #[test]
+    fn test_session_id() {
+        assert_eq!(IdStrategy::Uuid.session_id(UUID.to_string()), UUID);
+        assert_eq!(
+            IdStrategy::Prefixed("node-a".to_string()).session_id(UUID.to_string()),
+            format!("node-a.{}", UUID)
+        );
+    }
+}
@@ -13,7 +13,7 @@
 //!   - session-manager: Transports import for lifecycle (initialize, validate, terminate, delete)
 //! - Stores session data in WASI KV with session ID as the top-level key
 //! - Internal storage format: { "__meta__": {...}, "data": {...} }
-//! - Generates UUIDs using wasi:random for session IDs
+//! - Generates UUIDs using wasi:random for session IDs, optionally prefixed (see `id`)
 
 mod bindings {
     wit_bindgen::generate!({
@@ -22,6 +22,7 @@ mod bindings {
     });
 }
 
+mod id;
 mod session;
 
 use bindings::exports::wasmcp::mcp_v20251125::session_manager::Guest as SessionManagerGuest;
@@ -12,7 +12,11 @@ use crate::bindings::wasi::io::streams::OutputStream;
 use crate::bindings::wasmcp::keyvalue::store::{
     self as kv_store, Bucket, Error as KvError, TypedValue,
 };
+use crate::id::{self, IdStrategy};
 use serde::{Deserialize, Serialize};
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use wasmcp_capabilities::{Capability, ClientSupports};
 
 /// Convert KV store error to SessionError
 fn kv_to_session_error(e: KvError) -> SessionError {
@@ -80,7 +84,8 @@ impl SessionManager {
     /// and returns the session ID for inclusion in response headers.
     pub fn initialize(store_id: String) -> Result<String, SessionError> {
         // Generate new session ID
-        let session_id = generate_uuid_v4();
+        let strategy = IdStrategy::from_env().map_err(SessionError::Unexpected)?;
+        let session_id = strategy.session_id(generate_uuid_v4());
 
         // Open bucket
         let bucket = kv_store::open(&store_id).map_err(kv_to_session_error)?;
@@ -255,10 +260,23 @@ impl SessionManager {
 /// Storage model: session_id:key pattern
 /// - Metadata: session_id:__meta__
 /// - User keys: session_id:user_key
+///
+/// A resource lives for one request, so it caches what it reads: repeated
+/// gets cost one KV read. Sets are written through, unless the handler
+/// opened a batch with `begin_batch()`: then they are staged and written
+/// with a single `set-many` by `flush()`, or discarded by `rollback()`.
+/// Other session resources (other components, later requests) see staged
+/// writes once they are flushed.
 pub struct SessionImpl {
     bucket: Bucket,
     session_id: String,
     store_id: String, // Needed for terminate() to call session-manager
+    /// Values read or set through this resource, by user key
+    cache: RefCell<HashMap<String, Option<TypedValue>>>,
+    /// User keys set but not yet written to KV, in first-set order
+    dirty: RefCell<Vec<String>>,
+    /// Whether sets are staged (between `begin_batch` and `flush`/`rollback`)
+    batching: Cell<bool>,
 }
 
 /// Magic string for metadata field in session storage
@@ -283,11 +301,22 @@ const MAX_KEY_SIZE: usize = 1024;
 /// Maximum size for a single value (1MB)
 const MAX_VALUE_SIZE: usize = 1024 * 1024;
 
-/// Validate that a session ID is a properly formatted UUID v4
+/// Validate that a session ID is a properly formatted UUID v4, optionally prefixed
 ///
 /// UUID v4 format: 8-4-4-4-12 hex digits with hyphens (36 characters total)
-/// Example: "550e8400-e29b-41d4-a716-446655440000"
+/// Example: "550e8400-e29b-41d4-a716-446655440000", or "eu-west.550e8400-..."
 fn validate_session_id(session_id: &str) -> Result<(), SessionError> {
+    // IDs from the "prefixed" strategy carry a prefix before the UUID
+    let session_id = match session_id.split_once(id::PREFIX_SEPARATOR) {
+        Some((prefix, uuid)) => {
+            id::validate_prefix(prefix).map_err(|e| {
+                SessionError::Unexpected(format!("Invalid session ID format: {}", e))
+            })?;
+            uuid
+        }
+        None => session_id,
+    };
+
     // Check length (UUID v4 is always 36 characters with hyphens)
     if session_id.len() != 36 {
         return Err(SessionError::Unexpected(format!(
@@ -329,7 +358,7 @@ fn validate_session_id(session_id: &str) -> Result<(), SessionError> {
 
 impl GuestSession for SessionImpl {
     fn open(session_id: String, store_id: String) -> Result<Session, SessionError> {
-        // Validate session ID format (UUID v4)
+        // Validate session ID format ([prefix.]UUID v4)
         validate_session_id(&session_id)?;
 
         let bucket = kv_store::open(&store_id).map_err(kv_to_session_error)?;
@@ -341,6 +370,9 @@ impl GuestSession for SessionImpl {
             bucket,
             session_id,
             store_id, // Store for later use in terminate()
+            cache: RefCell::new(HashMap::new()),
+            dirty: RefCell::new(Vec::new()),
+            batching: Cell::new(false),
         }))
     }
 
@@ -352,10 +384,15 @@ impl GuestSession for SessionImpl {
         // Validate key before accessing storage
         validate_user_key(&key)?;
 
-        // Read typed value directly from KV using session_id:key pattern
+        if let Some(value) = self.cache.borrow().get(&key) {
+            return Ok(value.clone());
+        }
+
+        // Read typed value from KV using session_id:key pattern
         let kv_key = user_key(&self.session_id, &key);
         let value = self.bucket.get(&kv_key).map_err(kv_to_session_error)?;
 
+        self.cache.borrow_mut().insert(key, value.clone());
         Ok(value)
     }
 
@@ -366,15 +403,61 @@ impl GuestSession for SessionImpl {
         // Validate value size based on type
         validate_typed_value_size(&value)?;
 
-        // Write typed value directly to KV using session_id:key pattern
-        let kv_key = user_key(&self.session_id, &key);
-        self.bucket
-            .set(&kv_key, &value)
-            .map_err(kv_to_session_error)?;
+        if !self.batching.get() {
+            let kv_key = user_key(&self.session_id, &key);
+            self.bucket
+                .set(&kv_key, &value)
+                .map_err(kv_to_session_error)?;
+            self.cache.borrow_mut().insert(key, Some(value));
+            return Ok(());
+        }
+
+        // Stage the write; flush() persists it
+        let mut dirty = self.dirty.borrow_mut();
+        if !dirty.contains(&key) {
+            dirty.push(key.clone());
+        }
+        self.cache.borrow_mut().insert(key, Some(value));
 
         Ok(())
     }
 
+    fn begin_batch(&self) {
+        self.batching.set(true);
+    }
+
+    fn flush(&self) -> Result<(), SessionError> {
+        let keys = self.dirty.borrow().clone();
+        if keys.is_empty() {
+            self.batching.set(false);
+            return Ok(());
+        }
+
+        let cache = self.cache.borrow();
+        let pairs: Vec<(String, TypedValue)> = keys
+            .iter()
+            .filter_map(|key| {
+                let value = cache.get(key).cloned().flatten()?;
+                Some((user_key(&self.session_id, key), value))
+            })
+            .collect();
+        self.bucket.set_many(&pairs).map_err(kv_to_session_error)?;
+
+        // Only forget the writes once they landed, so a failed flush can be retried
+        self.dirty.borrow_mut().clear();
+        self.batching.set(false);
+        Ok(())
+    }
+
+    fn rollback(&self) {
+        // Staged values leave the cache too, so the next get reads KV again
+        let mut cache = self.cache.borrow_mut();
+        for key in self.dirty.borrow_mut().drain(..) {
+            cache.remove(&key);
+        }
+        self.batching.set(false);
+    }
+
     fn elicit(
         &self,
         _client: &OutputStream,
@@ -383,6 +466,13 @@ impl GuestSession for SessionImpl {
         crate::bindings::exports::wasmcp::mcp_v20251125::sessions::FutureElicitResult,
         SessionError,
     > {
+        // Never send an elicitation the client did not advertise support for
+        if let Some(capabilities) = client_capabilities(&self.bucket, &self.session_id)? {
+            capabilities
+                .require_client_support(Capability::Elicitation)
+                .map_err(|e| SessionError::UnsupportedCapability(e.to_string()))?;
+        }
+
         // MVP: Not implemented yet
         Err(SessionError::Unexpected(
             "elicit not implemented in MVP".to_string(),
@@ -396,6 +486,21 @@ impl GuestSession for SessionImpl {
     }
 }
 
+impl Drop for SessionImpl {
+    fn drop(&mut self) {
+        // A batch never flushed is discarded, as by rollback: persisting
+        // part of what the handler staged is what batches exist to avoid
+        let dirty = self.dirty.borrow();
+        if !dirty.is_empty() {
+            eprintln!(
+                "[session-store] Discarding {} unflushed write(s) to session {}",
+                dirty.len(),
+                self.session_id
+            );
+        }
+    }
+}
+
 // ============================================================================
 // Helper Functions
 // ============================================================================
@@ -433,6 +538,29 @@ fn validate_user_key(key: &str) -> Result<(), SessionError> {
     Ok(())
 }
 
+/// Session key where the transport stores the client's negotiated capabilities
+///
+/// Must match `session_keys::CLIENT_CAPABILITIES` in the transport component.
+const CLIENT_CAPABILITIES_KEY: &str = "client:capabilities";
+
+/// Capabilities the client advertised during initialize (MCP JSON shape)
+///
+/// None if nothing was stored (older transport, or sessions created before
+/// tracking): callers then skip the check so existing deployments keep working.
+fn client_capabilities(
+    bucket: &Bucket,
+    session_id: &str,
+) -> Result<Option<serde_json::Value>, SessionError> {
+    let kv_key = user_key(session_id, CLIENT_CAPABILITIES_KEY);
+    let Some(TypedValue::AsJson(json)) = bucket.get(&kv_key).map_err(kv_to_session_error)? else {
+        return Ok(None);
+    };
+
+    serde_json::from_str(&json).map(Some).map_err(|e| {
+        SessionError::Unexpected(format!("Failed to parse stored client capabilities: {}", e))
+    })
+}
+
 /// Validate typed value size
 fn validate_typed_value_size(value: &TypedValue) -> Result<(), SessionError> {
     let size = match value {
@@ -0,0 +1,173 @@
+//! Configuration for the SQL tool provider
+//!
+//! Environment variables:
+//! - `WASMCP_SQL_QUERIES`: JSON array of allow-listed queries (required), e.g.
+//!   `[{"name": "find_user", "sql": "SELECT * FROM users WHERE id = ?",
+//!     "parameters": [{"name": "id", "type": "integer"}]}]`
+//! - `WASMCP_SQL_DATABASE`: Spin SQLite database label (default: "default")
+//! - `WASMCP_SQL_MAX_ROWS`: Rows returned per call (default: "100")
+//! - `WASMCP_SQL_ALLOW_WRITES`: "true"/"false" (default: "false") - Expose
+//!   queries declared with `"readOnly": false`
+
+use crate::bindings::wasi::cli::environment::get_environment;
+use crate::query;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+const DEFAULT_DATABASE: &str = "default";
+const DEFAULT_MAX_ROWS: usize = 100;
+
+/// A SQL statement exposed as a tool
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryDef {
+    /// Tool name
+    pub name: String,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    /// Statement with positional `?` placeholders, bound in parameter order
+    pub sql: String,
+    #[serde(default)]
+    pub parameters: Vec<ParamDef>,
+    /// Read-only queries are checked to be a single SELECT/WITH/VALUES statement
+    #[serde(default = "default_true")]
+    pub read_only: bool,
+}
+
+/// A declared query parameter
+#[derive(Debug, Clone, Deserialize)]
+pub struct ParamDef {
+    pub name: String,
+    #[serde(rename = "type", default)]
+    pub kind: ParamType,
+    pub description: Option<String>,
+    #[serde(default = "default_true")]
+    pub required: bool,
+}
+
+/// JSON Schema type of a parameter, mapped onto a SQLite value when bound
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ParamType {
+    #[default]
+    String,
+    Integer,
+    Number,
+    Boolean,
+}
+
+impl ParamType {
+    pub fn schema_type(self) -> &'static str {
+        match self {
+            ParamType::String => "string",
+            ParamType::Integer => "integer",
+            ParamType::Number => "number",
+            ParamType::Boolean => "boolean",
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// SQL provider configuration from environment variables
+#[derive(Debug, Clone)]
+pub struct SqlConfig {
+    pub queries: Vec<QueryDef>,
+    /// Query name to position in `queries`
+    index: HashMap<String, usize>,
+    pub database: String,
+    pub max_rows: usize,
+}
+
+impl SqlConfig {
+    /// Load configuration from environment variables
+    ///
+    /// Queries that fail validation are dropped with a warning rather than
+    /// exposed in a weaker form.
+    pub fn from_env() -> Self {
+        let env_map: HashMap<String, String> = get_environment().into_iter().collect();
+
+        let allow_writes = env_map
+            .get("WASMCP_SQL_ALLOW_WRITES")
+            .map(|v| v.to_lowercase() == "true")
+            .unwrap_or(false);
+
+        let declared: Vec<QueryDef> = env_map
+            .get("WASMCP_SQL_QUERIES")
+            .map(|v| {
+                serde_json::from_str(v).unwrap_or_else(|e| {
+                    eprintln!(
+                        "[sql-tools] WARNING: Invalid WASMCP_SQL_QUERIES JSON: {}. No queries exposed.",
+                        e
+                    );
+                    Vec::new()
+                })
+            })
+            .unwrap_or_default();
+
+        let queries: Vec<QueryDef> = declared
+            .into_iter()
+            .filter(|q| match validate(q, allow_writes) {
+                Ok(()) => true,
+                Err(e) => {
+                    eprintln!("[sql-tools] WARNING: Skipping query '{}': {}", q.name, e);
+                    false
+                }
+            })
+            .collect();
+
+        let database = env_map
+            .get("WASMCP_SQL_DATABASE")
+            .cloned()
+            .unwrap_or_else(|| DEFAULT_DATABASE.to_string());
+
+        let max_rows = env_map
+            .get("WASMCP_SQL_MAX_ROWS")
+            .and_then(|v| v.parse::<usize>().ok())
+            .filter(|n| *n > 0)
+            .unwrap_or(DEFAULT_MAX_ROWS);
+
+        let mut index = HashMap::with_capacity(queries.len());
+        for (i, query) in queries.iter().enumerate() {
+            index.entry(query.name.clone()).or_insert(i);
+        }
+
+        SqlConfig {
+            queries,
+            index,
+            database,
+            max_rows,
+        }
+    }
+
+    pub fn query(&self, name: &str) -> Option<&QueryDef> {
+        self.index.get(name).map(|&i| &self.queries[i])
+    }
+}
+
+fn validate(query: &QueryDef, allow_writes: bool) -> Result<(), String> {
+    if query.name.is_empty() {
+        return Err("query name is empty".to_string());
+    }
+    if !query.read_only && !allow_writes {
+        return Err("write queries require WASMCP_SQL_ALLOW_WRITES=true".to_string());
+    }
+
+    let placeholders = query::count_placeholders(&query.sql)?;
+    if placeholders != query.parameters.len() {
+        return Err(format!(
+            "statement has {} placeholders but {} parameters are declared",
+            placeholders,
+            query.parameters.len()
+        ));
+    }
+
+    if query.read_only {
+        query::check_read_only(&query.sql)?;
+    } else {
+        query::check_single_statement(&query.sql)?;
+    }
+    Ok(())
+}
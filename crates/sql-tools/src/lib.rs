@@ -0,0 +1,235 @@
+//! SQL Tools Middleware Component
+//!
+//! A reusable middleware that exposes allow-listed SQL queries as MCP tools
+//! with no custom handler code. This component:
+//! - Lists one tool per query declared in `WASMCP_SQL_QUERIES`, with an input
+//!   schema generated from the declared parameters
+//! - Binds tool arguments as positional parameters (never string-interpolated)
+//! - Runs queries against a Spin SQLite database
+//! - Enforces read-only statements and a row limit by default
+//! - Delegates all other requests downstream
+//!
+//! Clients can only run the statements the operator declared; arguments are
+//! always bound as values.
+
+#![allow(warnings)]
+
+mod bindings {
+    wit_bindgen::generate!({
+        world: "sql-tools",
+        generate_all,
+    });
+}
+
+mod config;
+mod query;
+
+use bindings::exports::wasmcp::mcp_v20251125::server_handler::{Guest, MessageContext};
+use bindings::fermyon::spin::sqlite::Connection;
+use bindings::wasmcp::mcp_v20251125::mcp::*;
+use bindings::wasmcp::mcp_v20251125::server_handler as downstream;
+use config::{QueryDef, SqlConfig};
+use serde_json::json;
+use std::sync::OnceLock;
+
+struct SqlTools;
+
+/// Configuration, read once per instance
+static CONFIG: OnceLock<SqlConfig> = OnceLock::new();
+
+// Convert exported MessageContext to imported MessageContext
+fn to_downstream_ctx<'a>(ctx: &'a MessageContext<'a>) -> downstream::MessageContext<'a> {
+    downstream::MessageContext {
+        client_stream: ctx.client_stream,
+        protocol_version: ctx.protocol_version.clone(),
+        session: ctx.session.as_ref().map(|s| downstream::Session {
+            session_id: s.session_id.clone(),
+            store_id: s.store_id.clone(),
+        }),
+        identity: ctx.identity.as_ref().map(|i| downstream::Identity {
+            jwt: i.jwt.clone(),
+            claims: i.claims.clone(),
+        }),
+        frame: ctx.frame.clone(),
+        http_context: ctx.http_context.clone(),
+        client_capabilities: ctx.client_capabilities.clone(),
+        request_id: ctx.request_id.clone(),
+        deadline: ctx.deadline,
+        log_level: ctx.log_level,
+        client_info: ctx.client_info.clone(),
+        locale: ctx.locale.clone(),
+        trace_context: ctx.trace_context.clone(),
+    }
+}
+
+impl Guest for SqlTools {
+    fn handle(
+        ctx: MessageContext,
+        message: ClientMessage,
+    ) -> Option<Result<ServerResult, ErrorCode>> {
+        let config = CONFIG.get_or_init(SqlConfig::from_env);
+
+        match message {
+            ClientMessage::Request((request_id, request)) => {
+                let result = match &request {
+                    ClientRequest::ToolsList(list_req) if !config.queries.is_empty() => {
+                        handle_tools_list(request_id.clone(), list_req.clone(), &ctx, config)
+                    }
+                    ClientRequest::ToolsCall(call_req) => match config.query(&call_req.name) {
+                        Some(query) => Ok(handle_tools_call(call_req, query, config)),
+                        None => return delegate(&ctx, request_id, request),
+                    },
+                    _ => return delegate(&ctx, request_id, request),
+                };
+                Some(result)
+            }
+            _ => {
+                // Forward notifications, results, errors to downstream
+                downstream::handle(&to_downstream_ctx(&ctx), message)
+            }
+        }
+    }
+}
+
+fn delegate(
+    ctx: &MessageContext,
+    request_id: RequestId,
+    request: ClientRequest,
+) -> Option<Result<ServerResult, ErrorCode>> {
+    let downstream_msg = ClientMessage::Request((request_id, request));
+    downstream::handle(&to_downstream_ctx(ctx), downstream_msg)
+}
+
+fn handle_tools_list(
+    request_id: RequestId,
+    req: ListToolsRequest,
+    ctx: &MessageContext,
+    config: &SqlConfig,
+) -> Result<ServerResult, ErrorCode> {
+    let mut tools: Vec<Tool> = config.queries.iter().map(to_tool).collect();
+
+    let downstream_msg = ClientMessage::Request((request_id, ClientRequest::ToolsList(req)));
+    let next_cursor = match downstream::handle(&to_downstream_ctx(ctx), downstream_msg) {
+        Some(Ok(ServerResult::ToolsList(downstream_result))) => {
+            tools.extend(downstream_result.tools);
+            downstream_result.next_cursor
+        }
+        Some(Err(ErrorCode::MethodNotFound(_))) | None | Some(Ok(_)) => None,
+        Some(Err(e)) => return Err(e),
+    };
+
+    Ok(ServerResult::ToolsList(ListToolsResult {
+        tools,
+        next_cursor,
+        meta: None,
+    }))
+}
+
+fn to_tool(query: &QueryDef) -> Tool {
+    let properties: serde_json::Map<String, serde_json::Value> = query
+        .parameters
+        .iter()
+        .map(|param| {
+            let mut schema = json!({ "type": param.kind.schema_type() });
+            if let Some(description) = &param.description {
+                schema["description"] = json!(description);
+            }
+            (param.name.clone(), schema)
+        })
+        .collect();
+    let required: Vec<&str> = query
+        .parameters
+        .iter()
+        .filter(|p| p.required)
+        .map(|p| p.name.as_str())
+        .collect();
+
+    let input_schema = json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false,
+    });
+
+    Tool {
+        name: query.name.clone(),
+        input_schema: input_schema.to_string(),
+        options: Some(ToolOptions {
+            meta: None,
+            annotations: Some(ToolAnnotations {
+                destructive_hint: Some(!query.read_only),
+                idempotent_hint: Some(query.read_only),
+                open_world_hint: Some(false),
+                read_only_hint: Some(query.read_only),
+                title: None,
+            }),
+            description: query.description.clone(),
+            output_schema: None,
+            title: query.title.clone(),
+            icons: None,
+        }),
+    }
+}
+
+fn handle_tools_call(req: &CallToolRequest, query: &QueryDef, config: &SqlConfig) -> ServerResult {
+    match call(req, query, config) {
+        Ok((text, structured)) => tool_result(text, Some(structured), false),
+        Err(e) => tool_result(e, None, true),
+    }
+}
+
+fn call(
+    req: &CallToolRequest,
+    query: &QueryDef,
+    config: &SqlConfig,
+) -> Result<(String, serde_json::Value), String> {
+    let args = match req.arguments.as_deref().map(serde_json::from_str) {
+        Some(Ok(serde_json::Value::Object(args))) => args,
+        Some(Ok(serde_json::Value::Null)) | None => serde_json::Map::new(),
+        Some(Ok(_)) => return Err("Arguments must be a JSON object".to_string()),
+        Some(Err(e)) => return Err(format!("Invalid arguments: {}", e)),
+    };
+    let params = query::bind(query, &args)?;
+
+    let conn = Connection::open(&config.database)
+        .map_err(|e| format!("Failed to open database '{}': {:?}", config.database, e))?;
+
+    if !query.read_only {
+        let changes = query::run_write(&conn, query, &params)?;
+        let structured = json!({ "rowsAffected": changes });
+        return Ok((structured.to_string(), structured));
+    }
+
+    let output = query::run_read(&conn, query, &params, config.max_rows)?;
+    let structured = json!({
+        "columns": output.columns,
+        "rows": output.rows,
+        "truncated": output.truncated,
+    });
+    let mut text = serde_json::to_string_pretty(&output.rows).unwrap_or_default();
+    if output.truncated {
+        text.push_str(&format!(
+            "\n\n(truncated to {} rows; refine the query arguments to see more)",
+            config.max_rows
+        ));
+    }
+    Ok((text, structured))
+}
+
+fn tool_result(
+    text: String,
+    structured_content: Option<serde_json::Value>,
+    is_error: bool,
+) -> ServerResult {
+    ServerResult::ToolsCall(CallToolResult {
+        content: vec![ContentBlock::Text(TextContent {
+            text: TextData::Text(text),
+            options: None,
+        })],
+        is_error: is_error.then_some(true),
+        meta: None,
+        structured_content: structured_content.map(|v| v.to_string()),
+    })
+}
+
+bindings::export!(SqlTools with_types_in bindings);
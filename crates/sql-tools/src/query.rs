@@ -0,0 +1,358 @@
+//! Statement validation and execution over Spin SQLite
+//!
+//! Validation works on a "masked" copy of the statement in which string
+//! literals, quoted identifiers and comments are blanked out byte-for-byte, so
+//! keyword and placeholder checks never trip over text inside literals and
+//! byte offsets still line up with the original statement.
+
+use crate::bindings::fermyon::spin::sqlite::{Connection, Value};
+use crate::config::{ParamType, QueryDef};
+use base64::Engine;
+use serde_json::{Map, Value as Json};
+
+/// Leading keywords accepted for read-only queries
+const READ_ONLY_LEADING: &[&str] = &["SELECT", "WITH", "VALUES"];
+
+/// Keywords that can modify the database or connection state
+///
+/// REPLACE is absent so the replace() function stays usable; a `WITH ...
+/// REPLACE INTO` statement still fails because reads run inside a subquery.
+const WRITE_KEYWORDS: &[&str] = &[
+    "INSERT",
+    "UPDATE",
+    "DELETE",
+    "UPSERT",
+    "CREATE",
+    "DROP",
+    "ALTER",
+    "ATTACH",
+    "DETACH",
+    "PRAGMA",
+    "VACUUM",
+    "REINDEX",
+    "ANALYZE",
+    "BEGIN",
+    "COMMIT",
+    "ROLLBACK",
+    "SAVEPOINT",
+    "RELEASE",
+];
+
+/// Rows returned by a query
+pub struct QueryOutput {
+    pub columns: Vec<String>,
+    pub rows: Vec<Map<String, Json>>,
+    /// More rows matched than the row limit allowed
+    pub truncated: bool,
+}
+
+/// Blank out literals, quoted identifiers and comments, preserving byte offsets
+fn mask(sql: &str) -> Result<String, String> {
+    let bytes = sql.as_bytes();
+    let mut masked = String::with_capacity(sql.len());
+    let mut i = 0;
+
+    let blank = |masked: &mut String, n: usize| masked.extend(std::iter::repeat_n(' ', n));
+
+    while i < bytes.len() {
+        let rest = &sql[i..];
+        let end = if rest.starts_with("--") {
+            rest.find('\n').map(|n| i + n).unwrap_or(sql.len())
+        } else if rest.starts_with("/*") {
+            rest[2..]
+                .find("*/")
+                .map(|n| i + 2 + n + 2)
+                .ok_or("unterminated block comment")?
+        } else if let Some(close) = match bytes[i] {
+            b'\'' => Some('\''),
+            b'"' => Some('"'),
+            b'`' => Some('`'),
+            b'[' => Some(']'),
+            _ => None,
+        } {
+            // Doubled quotes ('' or "") escape themselves and fall out naturally
+            rest[1..]
+                .find(close)
+                .map(|n| i + 1 + n + 1)
+                .ok_or("unterminated quoted string or identifier")?
+        } else {
+            let ch = rest.chars().next().unwrap_or(' ');
+            if ch.is_ascii() {
+                masked.push(ch);
+            } else {
+                blank(&mut masked, ch.len_utf8());
+            }
+            i += ch.len_utf8();
+            continue;
+        };
+
+        blank(&mut masked, end - i);
+        i = end;
+    }
+
+    Ok(masked)
+}
+
+/// Byte length of the statement without its optional trailing semicolon
+///
+/// Fails if the text contains more than one statement.
+fn statement_len(sql: &str, masked: &str) -> Result<usize, String> {
+    let trimmed = masked.trim_end();
+    let (body, has_terminator) = match trimmed.strip_suffix(';') {
+        Some(body) => (body, true),
+        None => (trimmed, false),
+    };
+    if body.contains(';') {
+        return Err("only a single statement is allowed".to_string());
+    }
+    if body.trim().is_empty() {
+        return Err("statement is empty".to_string());
+    }
+    Ok(if has_terminator {
+        body.len()
+    } else {
+        sql.len()
+    })
+}
+
+fn words(masked: &str) -> impl Iterator<Item = &str> {
+    masked
+        .split(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .filter(|w| !w.is_empty())
+}
+
+/// Reject statements containing more than one statement
+pub fn check_single_statement(sql: &str) -> Result<(), String> {
+    let masked = mask(sql)?;
+    statement_len(sql, &masked).map(|_| ())
+}
+
+/// Reject anything but a single SELECT/WITH/VALUES statement without write keywords
+pub fn check_read_only(sql: &str) -> Result<(), String> {
+    let masked = mask(sql)?;
+    statement_len(sql, &masked)?;
+
+    let leading = words(&masked)
+        .next()
+        .unwrap_or_default()
+        .to_ascii_uppercase();
+    if !READ_ONLY_LEADING.contains(&leading.as_str()) {
+        return Err(format!(
+            "read-only queries must start with SELECT, WITH or VALUES (found '{}')",
+            leading
+        ));
+    }
+
+    if let Some(keyword) = words(&masked)
+        .map(|w| w.to_ascii_uppercase())
+        .find(|w| WRITE_KEYWORDS.contains(&w.as_str()))
+    {
+        return Err(format!("read-only query contains '{}'", keyword));
+    }
+    Ok(())
+}
+
+/// Count anonymous `?` placeholders; numbered and named placeholders are rejected
+pub fn count_placeholders(sql: &str) -> Result<usize, String> {
+    let masked = mask(sql)?;
+    let bytes = masked.as_bytes();
+    let mut count = 0;
+
+    for (i, &b) in bytes.iter().enumerate() {
+        let next = bytes.get(i + 1).copied().unwrap_or(b' ');
+        match b {
+            b'?' if next.is_ascii_digit() => {
+                return Err("numbered placeholders (?NNN) are not supported; use '?'".to_string());
+            }
+            b'?' => count += 1,
+            b':' | b'@' | b'$' if next.is_ascii_alphabetic() || next == b'_' => {
+                return Err("named placeholders are not supported; use '?'".to_string());
+            }
+            _ => {}
+        }
+    }
+    Ok(count)
+}
+
+/// Convert tool arguments into positional SQLite values in declaration order
+pub fn bind(query: &QueryDef, args: &Map<String, Json>) -> Result<Vec<Value>, String> {
+    query
+        .parameters
+        .iter()
+        .map(|param| {
+            let value = match args.get(&param.name) {
+                None | Some(Json::Null) if param.required => {
+                    return Err(format!("Missing required argument '{}'", param.name));
+                }
+                None | Some(Json::Null) => return Ok(Value::Null),
+                Some(value) => value,
+            };
+
+            let bound = match param.kind {
+                ParamType::String => value.as_str().map(|s| Value::Text(s.to_string())),
+                ParamType::Integer => value.as_i64().map(Value::Integer),
+                ParamType::Number => value.as_f64().map(Value::Real),
+                ParamType::Boolean => value.as_bool().map(|b| Value::Integer(b as i64)),
+            };
+            bound.ok_or_else(|| {
+                format!(
+                    "Argument '{}' must be of type {}",
+                    param.name,
+                    param.kind.schema_type()
+                )
+            })
+        })
+        .collect()
+}
+
+/// Run a read-only query, returning at most `max_rows` rows
+pub fn run_read(
+    conn: &Connection,
+    query: &QueryDef,
+    params: &[Value],
+    max_rows: usize,
+) -> Result<QueryOutput, String> {
+    // Defense in depth on top of the static checks; not every host allows it
+    if let Err(e) = conn.execute("PRAGMA query_only = ON", &[]) {
+        eprintln!("[sql-tools] Could not enable query_only mode: {:?}", e);
+    }
+
+    let masked = mask(&query.sql)?;
+    let body = &query.sql[..statement_len(&query.sql, &masked)?];
+
+    // Wrap so the row limit is enforced by SQLite rather than after the fact;
+    // newlines keep a trailing line comment from swallowing the parenthesis
+    let statement = format!("SELECT * FROM (\n{}\n) LIMIT {}", body, max_rows + 1);
+    let result = conn
+        .execute(&statement, params)
+        .map_err(|e| format!("Query failed: {:?}", e))?;
+
+    let truncated = result.rows.len() > max_rows;
+    let rows = result
+        .rows
+        .into_iter()
+        .take(max_rows)
+        .map(|row| {
+            result
+                .columns
+                .iter()
+                .cloned()
+                .zip(row.values.into_iter().map(to_json))
+                .collect()
+        })
+        .collect();
+
+    Ok(QueryOutput {
+        columns: result.columns,
+        rows,
+        truncated,
+    })
+}
+
+/// Run a write statement, returning the number of rows changed
+pub fn run_write(conn: &Connection, query: &QueryDef, params: &[Value]) -> Result<i64, String> {
+    conn.execute(&query.sql, params)
+        .map_err(|e| format!("Statement failed: {:?}", e))?;
+
+    let changes = conn
+        .execute("SELECT changes()", &[])
+        .map_err(|e| format!("Failed to read change count: {:?}", e))?;
+    Ok(changes
+        .rows
+        .first()
+        .and_then(|row| row.values.first())
+        .and_then(|value| match value {
+            Value::Integer(n) => Some(*n),
+            _ => None,
+        })
+        .unwrap_or(0))
+}
+
+fn to_json(value: Value) -> Json {
+    match value {
+        Value::Integer(n) => Json::from(n),
+        Value::Real(f) => serde_json::Number::from_f64(f)
+            .map(Json::Number)
+            .unwrap_or(Json::Null),
+        Value::Text(s) => Json::String(s),
+        // Blobs are base64-encoded so results stay valid JSON
+        Value::Blob(bytes) => Json::String(base64::engine::general_purpose::STANDARD.encode(bytes)),
+        Value::Null => Json::Null,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mask_preserves_offsets() {
+        let sql = "SELECT 'a;b', \"x\" -- DROP\nFROM t /* ; */";
+        let masked = mask(sql).unwrap();
+        assert_eq!(masked.len(), sql.len());
+        assert!(masked.starts_with("SELECT "));
+        assert!(!masked.contains(';') && !masked.contains("DROP"));
+        assert_eq!(masked.find("FROM t"), sql.find("FROM t"));
+        assert_eq!(mask("SELECT 'it''s'").unwrap(), "SELECT        ");
+        assert_eq!(mask("SELECT 'é'").unwrap().len(), "SELECT 'é'".len());
+        assert!(mask("SELECT 'open").is_err());
+        assert!(mask("SELECT 1 /* open").is_err());
+    }
+
+    #[test]
+    fn test_read_only_accepts_queries() {
+        assert!(check_read_only("SELECT * FROM users").is_ok());
+        assert!(check_read_only("select id from users;").is_ok());
+        assert!(check_read_only("WITH t AS (SELECT 1) SELECT * FROM t").is_ok());
+        assert!(check_read_only("VALUES (1), (2)").is_ok());
+        assert!(check_read_only("SELECT replace(name, 'a', 'b') FROM t").is_ok());
+    }
+
+    #[test]
+    fn test_read_only_ignores_hidden_keywords() {
+        assert!(check_read_only("SELECT 'DROP TABLE users' FROM t").is_ok());
+        assert!(check_read_only("SELECT \"delete\" FROM t").is_ok());
+        assert!(check_read_only("SELECT [update] FROM t").is_ok());
+        assert!(check_read_only("SELECT 1 -- DELETE FROM users").is_ok());
+        assert!(check_read_only("SELECT /* INSERT; */ 1").is_ok());
+        assert!(check_read_only("SELECT 'a;b' FROM t").is_ok());
+    }
+
+    #[test]
+    fn test_read_only_rejects_writes() {
+        assert!(check_read_only("SELECT 1; DROP TABLE users").is_err());
+        assert!(check_read_only("SELECT 1; -- trailing\nDROP TABLE users").is_err());
+        assert!(check_read_only("WITH t AS (SELECT 1) DELETE FROM users").is_err());
+        assert!(check_read_only("DELETE FROM users").is_err());
+        assert!(check_read_only("PRAGMA query_only = OFF").is_err());
+        assert!(check_read_only("ATTACH DATABASE 'x.db' AS x").is_err());
+        assert!(check_read_only("SELECT 1 FROM t WHERE 1 = 1 /* */ ; VACUUM").is_err());
+        assert!(check_read_only("-- only a comment").is_err());
+        assert!(check_read_only("").is_err());
+    }
+
+    #[test]
+    fn test_single_statement() {
+        assert!(check_single_statement("INSERT INTO t VALUES (1);").is_ok());
+        assert!(check_single_statement("INSERT INTO t VALUES (';')").is_ok());
+        assert!(check_single_statement("INSERT INTO t VALUES (1); DELETE FROM t").is_err());
+    }
+
+    #[test]
+    fn test_count_placeholders() {
+        assert_eq!(count_placeholders("SELECT * FROM t").unwrap(), 0);
+        assert_eq!(
+            count_placeholders("SELECT * FROM t WHERE a = ? AND b = ?").unwrap(),
+            2
+        );
+        assert_eq!(
+            count_placeholders("SELECT '?', \"?\" FROM t WHERE a = ? -- ?").unwrap(),
+            1
+        );
+        assert_eq!(count_placeholders("SELECT ':name' FROM t").unwrap(), 0);
+        assert!(count_placeholders("SELECT * FROM t WHERE a = ?1").is_err());
+        assert!(count_placeholders("SELECT * FROM t WHERE a = :name").is_err());
+        assert!(count_placeholders("SELECT * FROM t WHERE a = @name").is_err());
+        assert!(count_placeholders("SELECT * FROM t WHERE a = $name").is_err());
+    }
+}
@@ -0,0 +1,34 @@
+//! Stdout Guard Component
+//!
+//! Implements `wasi:cli/stdout` with the host's stderr stream. With the stdio
+//! transport, stdout is the MCP message stream: a handler that prints to it
+//! (`println!`, a dependency's debug output, a panic hook) corrupts the
+//! JSON-RPC framing and the client drops the connection. Plugged into a
+//! handler component, this guard sends everything the handler writes to
+//! stdout to stderr instead, where it shows up as diagnostics.
+//!
+//! Rust's `print!`/`println!` write through the component's `wasi:cli/stdout`
+//! import, so they are redirected with no change to the handler's code;
+//! `eprint!` already goes to stderr. Only the transport keeps the real stdout.
+
+#![allow(warnings)]
+
+mod bindings {
+    wit_bindgen::generate!({
+        world: "stdout-guard",
+        generate_all,
+    });
+}
+
+use bindings::exports::wasi::cli::stdout::{Guest, OutputStream};
+use bindings::wasi::cli::stderr;
+
+struct StdoutGuard;
+
+impl Guest for StdoutGuard {
+    fn get_stdout() -> OutputStream {
+        stderr::get_stderr()
+    }
+}
+
+bindings::export!(StdoutGuard with_types_in bindings);
@@ -0,0 +1,55 @@
+//! Configuration for the summarization pipeline
+//!
+//! Environment variables:
+//! - `WASMCP_SUMMARIZE_CHUNK_SIZE`: Largest chunk sent in one sampling request, in bytes (default: "12000")
+//! - `WASMCP_SUMMARIZE_MAX_CHUNKS`: Longest input accepted, in chunks (default: "16")
+//! - `WASMCP_SUMMARIZE_MAX_TOKENS`: Default token budget for the final summary (default: "1024")
+//! - `WASMCP_SUMMARIZE_MODEL_HINTS`: Comma-separated model name hints (default: none)
+
+use crate::bindings::wasi::cli::environment::get_environment;
+use std::collections::HashMap;
+
+const DEFAULT_CHUNK_SIZE: usize = 12_000;
+const DEFAULT_MAX_CHUNKS: usize = 16;
+const DEFAULT_MAX_TOKENS: u64 = 1024;
+
+/// Summarization configuration from environment variables
+#[derive(Debug, Clone)]
+pub struct SummarizeConfig {
+    pub chunk_size: usize,
+    pub max_chunks: usize,
+    pub max_tokens: u64,
+    pub model_hints: Vec<String>,
+}
+
+impl SummarizeConfig {
+    /// Load configuration from environment variables
+    pub fn from_env() -> Self {
+        let env_map: HashMap<String, String> = get_environment().into_iter().collect();
+
+        let number = |key: &str, default: usize| {
+            env_map
+                .get(key)
+                .and_then(|v| v.parse::<usize>().ok())
+                .filter(|n| *n > 0)
+                .unwrap_or(default)
+        };
+
+        let model_hints = env_map
+            .get("WASMCP_SUMMARIZE_MODEL_HINTS")
+            .map(|v| {
+                v.split(',')
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        SummarizeConfig {
+            chunk_size: number("WASMCP_SUMMARIZE_CHUNK_SIZE", DEFAULT_CHUNK_SIZE),
+            max_chunks: number("WASMCP_SUMMARIZE_MAX_CHUNKS", DEFAULT_MAX_CHUNKS),
+            max_tokens: number("WASMCP_SUMMARIZE_MAX_TOKENS", DEFAULT_MAX_TOKENS as usize) as u64,
+            model_hints,
+        }
+    }
+}
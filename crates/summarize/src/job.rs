@@ -0,0 +1,261 @@
+//! Summarization job state
+//!
+//! A job spans several messages: the tools/call that starts it, one client
+//! result per chunk, and possibly a merge result. State is persisted in the
+//! session (HTTP creates a fresh instance per request) and cached in-process
+//! for stdio, where there is no session bucket.
+//!
+//! Chunk results for one job can be handled by concurrent instances, so each
+//! chunk summary is stored under its own key (`summarize:job:<id>:part:<n>`)
+//! rather than in the job record, where one instance's write would drop
+//! another's. Every instance that finds all chunks done may try the merge;
+//! `claim_merge` lets only the first send it. The record itself still holds
+//! status fields written by read-modify-write, but each is written by a
+//! single step of the job.
+
+use crate::bindings::exports::wasmcp::mcp_v20251125::server_handler::MessageContext;
+use crate::bindings::wasmcp::keyvalue::store::{self as kv, TypedValue};
+use crate::bindings::wasmcp::mcp_v20251125::sessions;
+use crate::pipeline::Options;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+/// Session key prefix for job state
+const JOB_KEY_PREFIX: &str = "summarize:job:";
+
+/// Request ID prefix for sampling requests issued by this component
+const REQUEST_ID_PREFIX: &str = "summarize:";
+
+/// Request ID suffix for the merge step
+const MERGE_STEP: &str = "merge";
+
+thread_local! {
+    /// Jobs for connections without a session (stdio)
+    static CACHED_JOBS: RefCell<HashMap<String, Job>> = RefCell::new(HashMap::new());
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Job {
+    pub options: Options,
+    pub chunks: usize,
+    /// Chunk summaries, in chunk order (stored under their own keys)
+    #[serde(skip)]
+    pub partials: Vec<Option<String>>,
+    pub merge_requested: bool,
+    pub summary: Option<String>,
+    /// Model that produced the last result
+    pub model: Option<String>,
+    pub error: Option<String>,
+}
+
+impl Job {
+    pub fn new(options: Options, chunks: usize) -> Self {
+        Job {
+            options,
+            chunks,
+            partials: vec![None; chunks],
+            merge_requested: false,
+            summary: None,
+            model: None,
+            error: None,
+        }
+    }
+
+    /// All chunk summaries, once every chunk has been summarized
+    pub fn completed_partials(&self) -> Option<Vec<String>> {
+        self.partials.iter().cloned().collect()
+    }
+
+    pub fn status(&self) -> &'static str {
+        if self.error.is_some() {
+            "failed"
+        } else if self.summary.is_some() {
+            "complete"
+        } else if self.merge_requested {
+            "merging"
+        } else {
+            "summarizing"
+        }
+    }
+}
+
+/// Which step of a job a sampling request belongs to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Step {
+    Chunk(usize),
+    Merge,
+}
+
+/// Request ID for a job step: `summarize:<job>:<index|merge>`
+pub fn request_id(job_id: &str, step: Step) -> String {
+    match step {
+        Step::Chunk(index) => format!("{}{}:{}", REQUEST_ID_PREFIX, job_id, index),
+        Step::Merge => format!("{}{}:{}", REQUEST_ID_PREFIX, job_id, MERGE_STEP),
+    }
+}
+
+/// Parse a request ID issued by `request_id`
+pub fn parse_request_id(id: &str) -> Option<(String, Step)> {
+    let (job_id, step) = id.strip_prefix(REQUEST_ID_PREFIX)?.rsplit_once(':')?;
+    let step = match step {
+        MERGE_STEP => Step::Merge,
+        index => Step::Chunk(index.parse().ok()?),
+    };
+    Some((job_id.to_string(), step))
+}
+
+pub fn load(ctx: &MessageContext, job_id: &str) -> Option<Job> {
+    if let Some(session) = open_session(ctx) {
+        let mut job: Job = match session.get(&job_key(job_id)) {
+            Ok(Some(TypedValue::AsJson(json))) => serde_json::from_str(&json).ok()?,
+            _ => return None,
+        };
+        job.partials = (0..job.chunks)
+            .map(|index| match session.get(&partial_key(job_id, index)) {
+                Ok(Some(TypedValue::AsString(text))) => Some(text),
+                _ => None,
+            })
+            .collect();
+        return Some(job);
+    }
+    CACHED_JOBS.with(|c| c.borrow().get(job_id).cloned())
+}
+
+/// Store the job record; chunk summaries are stored by `save_partial`
+pub fn save(ctx: &MessageContext, job_id: &str, job: &Job) {
+    if let Some(session) = open_session(ctx) {
+        let result = serde_json::to_string(job)
+            .map_err(|e| e.to_string())
+            .and_then(|json| {
+                session
+                    .set(&job_key(job_id), &TypedValue::AsJson(json))
+                    .map_err(|e| format!("{:?}", e))
+            });
+        if let Err(e) = result {
+            eprintln!("[summarize] Failed to store job {}: {}", job_id, e);
+        }
+        return;
+    }
+    CACHED_JOBS.with(|c| c.borrow_mut().insert(job_id.to_string(), job.clone()));
+}
+
+/// Store one chunk summary
+pub fn save_partial(ctx: &MessageContext, job_id: &str, index: usize, text: &str) {
+    if let Some(session) = open_session(ctx) {
+        let value = TypedValue::AsString(text.to_string());
+        if let Err(e) = session.set(&partial_key(job_id, index), &value) {
+            eprintln!(
+                "[summarize] Failed to store chunk {} of job {}: {:?}",
+                index, job_id, e
+            );
+        }
+        return;
+    }
+    CACHED_JOBS.with(|c| {
+        if let Some(slot) = c
+            .borrow_mut()
+            .get_mut(job_id)
+            .and_then(|job| job.partials.get_mut(index))
+        {
+            *slot = Some(text.to_string());
+        }
+    });
+}
+
+/// Claim the right to send a job's merge request
+///
+/// Atomic across instances: the claim is an increment on the session's
+/// bucket, under the session's key prefix so it goes when the session does.
+/// Stdio jobs live in one instance, where `merge_requested` already guards.
+pub fn claim_merge(ctx: &MessageContext, job_id: &str) -> bool {
+    let Some(session) = ctx.session.as_ref() else {
+        return true;
+    };
+    let key = format!("{}:{}:merge", session.session_id, job_key(job_id));
+    let claimed = kv::open(&session.store_id).and_then(|bucket| bucket.increment(&key, 1));
+    match claimed {
+        Ok(count) => count == 1,
+        Err(e) => {
+            eprintln!("[summarize] Failed to claim merge of job {}: {}", job_id, e);
+            false
+        }
+    }
+}
+
+fn job_key(job_id: &str) -> String {
+    format!("{}{}", JOB_KEY_PREFIX, job_id)
+}
+
+fn partial_key(job_id: &str, index: usize) -> String {
+    format!("{}:part:{}", job_key(job_id), index)
+}
+
+fn open_session(ctx: &MessageContext) -> Option<sessions::Session> {
+    let session = ctx.session.as_ref()?;
+    sessions::Session::open(&session.session_id, &session.store_id).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options() -> Options {
+        Options {
+            max_tokens: 1024,
+            instructions: None,
+            model_hints: Vec::new(),
+            cost_priority: None,
+            speed_priority: None,
+            intelligence_priority: None,
+        }
+    }
+
+    #[test]
+    fn test_request_id_round_trip() {
+        for step in [Step::Chunk(0), Step::Chunk(12), Step::Merge] {
+            let id = request_id("job:7", step);
+            assert_eq!(parse_request_id(&id), Some(("job:7".to_string(), step)));
+        }
+    }
+
+    #[test]
+    fn test_parse_request_id_rejects_foreign_ids() {
+        assert_eq!(parse_request_id("other:1:0"), None);
+        assert_eq!(parse_request_id("summarize:1:next"), None);
+        assert_eq!(parse_request_id("summarize:1"), None);
+    }
+
+    #[test]
+    fn test_completed_partials() {
+        let mut job = Job::new(options(), 2);
+        job.partials[0] = Some("a".to_string());
+        assert_eq!(job.completed_partials(), None);
+        job.partials[1] = Some("b".to_string());
+        assert_eq!(
+            job.completed_partials(),
+            Some(vec!["a".to_string(), "b".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_status() {
+        let mut job = Job::new(options(), 2);
+        assert_eq!(job.status(), "summarizing");
+        job.merge_requested = true;
+        assert_eq!(job.status(), "merging");
+        job.summary = Some("done".to_string());
+        assert_eq!(job.status(), "complete");
+        job.error = Some("failed".to_string());
+        assert_eq!(job.status(), "failed");
+    }
+
+    #[test]
+    fn test_partials_are_not_in_the_record() {
+        let mut job = Job::new(options(), 2);
+        job.partials[0] = Some("a".to_string());
+        let record: Job = serde_json::from_str(&serde_json::to_string(&job).unwrap()).unwrap();
+        assert_eq!(record.chunks, 2);
+        assert!(record.partials.is_empty());
+    }
+}
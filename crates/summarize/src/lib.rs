@@ -0,0 +1,522 @@
+//! Summarization Middleware Component
+//!
+//! A reusable middleware that summarizes long text through the client's LLM
+//! using sampling/createMessage. This component:
+//! - Adds a `summarize` tool that chunks the input on paragraph boundaries
+//! - Sends one sampling request per chunk, with a per-chunk token budget and
+//!   the caller's model preferences
+//! - Merges the chunk summaries with a final sampling request
+//! - Serves the result as a `summarize://jobs/{id}` resource and sends
+//!   notifications/resources/updated when it is ready
+//! - Delegates all other requests downstream
+//!
+//! Sampling results arrive as separate client messages after the tool call has
+//! returned, so a summary is a job: the tool call starts it and the resource
+//! reports progress and the final text.
+
+#![allow(warnings)]
+
+mod bindings {
+    wit_bindgen::generate!({
+        world: "summarize",
+        generate_all,
+    });
+}
+
+mod config;
+mod job;
+mod pipeline;
+
+use bindings::exports::wasmcp::mcp_v20251125::server_handler::{Guest, MessageContext};
+use bindings::wasmcp::mcp_v20251125::mcp::*;
+use bindings::wasmcp::mcp_v20251125::server_handler as downstream;
+use bindings::wasmcp::mcp_v20251125::server_io;
+use config::SummarizeConfig;
+use job::{Job, Step};
+use pipeline::Options;
+use wasmcp_capabilities::{Capability, ClientSupports};
+
+wasmcp_capabilities::impl_client_supports!(MessageContext<'_>, ClientLists);
+
+const TOOL_NAME: &str = "summarize";
+
+/// URI prefix for job resources
+const JOB_URI_PREFIX: &str = "summarize://jobs/";
+
+struct Summarize;
+
+// Convert exported MessageContext to imported MessageContext
+fn to_downstream_ctx<'a>(ctx: &'a MessageContext<'a>) -> downstream::MessageContext<'a> {
+    downstream::MessageContext {
+        client_stream: ctx.client_stream,
+        protocol_version: ctx.protocol_version.clone(),
+        session: ctx.session.as_ref().map(|s| downstream::Session {
+            session_id: s.session_id.clone(),
+            store_id: s.store_id.clone(),
+        }),
+        identity: ctx.identity.as_ref().map(|i| downstream::Identity {
+            jwt: i.jwt.clone(),
+            claims: i.claims.clone(),
+        }),
+        frame: ctx.frame.clone(),
+        http_context: ctx.http_context.clone(),
+        client_capabilities: ctx.client_capabilities.clone(),
+        request_id: ctx.request_id.clone(),
+        deadline: ctx.deadline,
+        log_level: ctx.log_level,
+        client_info: ctx.client_info.clone(),
+        locale: ctx.locale.clone(),
+        trace_context: ctx.trace_context.clone(),
+    }
+}
+
+impl Guest for Summarize {
+    fn handle(
+        ctx: MessageContext,
+        message: ClientMessage,
+    ) -> Option<Result<ServerResult, ErrorCode>> {
+        match message {
+            ClientMessage::Request((request_id, request)) => {
+                let result = match &request {
+                    ClientRequest::ToolsList(list_req)
+                        if ctx.client_supports(Capability::Sampling) =>
+                    {
+                        handle_tools_list(request_id.clone(), list_req.clone(), &ctx)
+                    }
+                    ClientRequest::ToolsCall(call_req) if call_req.name == TOOL_NAME => {
+                        Ok(handle_summarize(&request_id, call_req, &ctx))
+                    }
+                    ClientRequest::ResourcesRead(read_req)
+                        if read_req.uri.starts_with(JOB_URI_PREFIX) =>
+                    {
+                        handle_resources_read(&read_req.uri, &ctx)
+                    }
+                    _ => return delegate(&ctx, request_id, request),
+                };
+                Some(result)
+            }
+            ClientMessage::Result((
+                RequestId::String(id),
+                ClientResult::SamplingCreateMessage(result),
+            )) => match job::parse_request_id(&id) {
+                Some((job_id, step)) => {
+                    record_result(&ctx, &job_id, step, result);
+                    None
+                }
+                None => {
+                    let message = ClientMessage::Result((
+                        RequestId::String(id),
+                        ClientResult::SamplingCreateMessage(result),
+                    ));
+                    downstream::handle(&to_downstream_ctx(&ctx), message)
+                }
+            },
+            ClientMessage::Error((Some(RequestId::String(id)), error)) => {
+                match job::parse_request_id(&id) {
+                    Some((job_id, _)) => {
+                        record_error(&ctx, &job_id, error_message(&error));
+                        None
+                    }
+                    None => {
+                        let message = ClientMessage::Error((Some(RequestId::String(id)), error));
+                        downstream::handle(&to_downstream_ctx(&ctx), message)
+                    }
+                }
+            }
+            _ => {
+                // Forward notifications, results, errors to downstream
+                downstream::handle(&to_downstream_ctx(&ctx), message)
+            }
+        }
+    }
+}
+
+fn delegate(
+    ctx: &MessageContext,
+    request_id: RequestId,
+    request: ClientRequest,
+) -> Option<Result<ServerResult, ErrorCode>> {
+    let downstream_msg = ClientMessage::Request((request_id, request));
+    downstream::handle(&to_downstream_ctx(ctx), downstream_msg)
+}
+
+/// Server requests need a stream that can carry more than the one response
+fn can_send(ctx: &MessageContext) -> bool {
+    ctx.client_stream.is_some() && (!ctx.frame.prefix.is_empty() || !ctx.frame.suffix.is_empty())
+}
+
+fn send(ctx: &MessageContext, message: ServerMessage) -> Result<(), String> {
+    let stream = ctx.client_stream.ok_or("No client stream")?;
+    server_io::send_message(stream, message, &ctx.frame).map_err(|e| format!("{:?}", e))
+}
+
+fn handle_tools_list(
+    request_id: RequestId,
+    req: ListToolsRequest,
+    ctx: &MessageContext,
+) -> Result<ServerResult, ErrorCode> {
+    let downstream_msg = ClientMessage::Request((request_id, ClientRequest::ToolsList(req)));
+    match downstream::handle(&to_downstream_ctx(ctx), downstream_msg) {
+        Some(Ok(ServerResult::ToolsList(mut downstream_result))) => {
+            downstream_result.tools.insert(0, summarize_tool());
+            Ok(ServerResult::ToolsList(downstream_result))
+        }
+        Some(Err(ErrorCode::MethodNotFound(_))) | None | Some(Ok(_)) => {
+            Ok(ServerResult::ToolsList(ListToolsResult {
+                tools: vec![summarize_tool()],
+                next_cursor: None,
+                meta: None,
+            }))
+        }
+        Some(Err(e)) => Err(e),
+    }
+}
+
+fn summarize_tool() -> Tool {
+    Tool {
+        name: TOOL_NAME.to_string(),
+        input_schema: r#"{
+            "type": "object",
+            "properties": {
+                "text": {"type": "string", "description": "Text to summarize"},
+                "max_tokens": {"type": "integer", "minimum": 1, "maximum": 9007199254740991, "description": "Token budget for the final summary"},
+                "instructions": {"type": "string", "description": "Extra guidance, e.g. audience, format or focus"},
+                "model_hints": {"type": "array", "items": {"type": "string"}, "description": "Preferred model names, most preferred first"},
+                "cost_priority": {"type": "number", "minimum": 0, "maximum": 1},
+                "speed_priority": {"type": "number", "minimum": 0, "maximum": 1},
+                "intelligence_priority": {"type": "number", "minimum": 0, "maximum": 1}
+            },
+            "required": ["text"]
+        }"#
+        .to_string(),
+        options: Some(ToolOptions {
+            meta: None,
+            annotations: Some(ToolAnnotations {
+                destructive_hint: Some(false),
+                idempotent_hint: Some(false),
+                open_world_hint: Some(true),
+                read_only_hint: Some(true),
+                title: None,
+            }),
+            description: Some(
+                "Summarize long text with the client's model. Returns a resource link that holds the summary once it is ready."
+                    .to_string(),
+            ),
+            output_schema: None,
+            title: Some("Summarize".to_string()),
+            icons: None,
+        }),
+    }
+}
+
+fn handle_summarize(
+    request_id: &RequestId,
+    req: &CallToolRequest,
+    ctx: &MessageContext,
+) -> ServerResult {
+    let job_id = match request_id {
+        RequestId::Number(n) => n.to_string(),
+        RequestId::String(s) => s.clone(),
+    };
+    match start_job(&job_id, req, ctx) {
+        Ok(chunks) => {
+            let uri = format!("{}{}", JOB_URI_PREFIX, job_id);
+            let text = format!(
+                "Summarizing {} chunk(s). The summary will be available at {} and a resource update notification is sent when it is ready.",
+                chunks, uri
+            );
+            let structured = serde_json::json!({ "job": job_id, "uri": uri, "chunks": chunks });
+            ServerResult::ToolsCall(CallToolResult {
+                content: vec![
+                    ContentBlock::Text(TextContent {
+                        text: TextData::Text(text),
+                        options: None,
+                    }),
+                    ContentBlock::ResourceLink(ResourceLinkContent {
+                        uri,
+                        name: "summary".to_string(),
+                        options: Some(ResourceLinkOptions {
+                            title: None,
+                            description: None,
+                            size: None,
+                            mime_type: Some("text/plain".to_string()),
+                            annotations: None,
+                            meta: None,
+                            icons: None,
+                        }),
+                    }),
+                ],
+                is_error: None,
+                meta: None,
+                structured_content: Some(structured.to_string()),
+            })
+        }
+        Err(e) => ServerResult::ToolsCall(CallToolResult {
+            content: vec![ContentBlock::Text(TextContent {
+                text: TextData::Text(e),
+                options: None,
+            })],
+            is_error: Some(true),
+            meta: None,
+            structured_content: None,
+        }),
+    }
+}
+
+/// Start a job and send its chunk requests, returning the chunk count
+fn start_job(job_id: &str, req: &CallToolRequest, ctx: &MessageContext) -> Result<usize, String> {
+    ctx.require_client_support(Capability::Sampling)
+        .map_err(|e| e.to_string())?;
+    if !can_send(ctx) {
+        return Err(
+            "Summarization needs a streaming connection (SSE or stdio) to send sampling requests"
+                .to_string(),
+        );
+    }
+
+    let config = SummarizeConfig::from_env();
+    let args: serde_json::Value = req
+        .arguments
+        .as_deref()
+        .map(serde_json::from_str)
+        .transpose()
+        .map_err(|e| format!("Invalid arguments: {}", e))?
+        .unwrap_or_default();
+
+    let text = args
+        .get("text")
+        .and_then(|v| v.as_str())
+        .ok_or("Missing 'text' argument")?;
+    let priority = |name: &str| args.get(name).and_then(|v| v.as_f64());
+    let model_hints = match args.get("model_hints").and_then(|v| v.as_array()) {
+        Some(hints) => hints
+            .iter()
+            .filter_map(|h| h.as_str().map(str::to_string))
+            .collect(),
+        None => config.model_hints.clone(),
+    };
+
+    let options = Options {
+        max_tokens: args
+            .get("max_tokens")
+            .and_then(|v| v.as_u64())
+            .filter(|n| *n > 0)
+            .unwrap_or(config.max_tokens)
+            .min(pipeline::MAX_TOKENS),
+        instructions: args
+            .get("instructions")
+            .and_then(|v| v.as_str())
+            .map(str::to_string),
+        model_hints,
+        cost_priority: priority("cost_priority"),
+        speed_priority: priority("speed_priority"),
+        intelligence_priority: priority("intelligence_priority"),
+    };
+
+    let requests = pipeline::plan(text, &options, config.chunk_size);
+    if requests.is_empty() {
+        return Err("Nothing to summarize: 'text' is empty".to_string());
+    }
+    if requests.len() > config.max_chunks {
+        return Err(format!(
+            "Text splits into {} chunks, more than the limit of {}",
+            requests.len(),
+            config.max_chunks
+        ));
+    }
+
+    // Save before sending so results that arrive quickly find the job
+    let chunks = requests.len();
+    let mut job = Job::new(options, chunks);
+    job::save(ctx, job_id, &job);
+
+    for (index, request) in requests.into_iter().enumerate() {
+        let message = ServerMessage::Request((
+            RequestId::String(job::request_id(job_id, Step::Chunk(index))),
+            ServerRequest::SamplingCreateMessage(request),
+        ));
+        if let Err(e) = send(ctx, message) {
+            job.error = Some(format!("Failed to send sampling request: {}", e));
+            job::save(ctx, job_id, &job);
+            return Err(job.error.unwrap_or_default());
+        }
+    }
+
+    Ok(chunks)
+}
+
+fn record_result(
+    ctx: &MessageContext,
+    job_id: &str,
+    step: Step,
+    result: SamplingCreateMessageResult,
+) {
+    let Some(mut job) = job::load(ctx, job_id) else {
+        eprintln!("[summarize] Result for unknown job {}", job_id);
+        return;
+    };
+
+    let text = match result.content {
+        ContentBlock::Text(TextContent {
+            text: TextData::Text(text),
+            ..
+        }) => text,
+        _ => {
+            record_error(
+                ctx,
+                job_id,
+                "Sampling returned non-text content".to_string(),
+            );
+            return;
+        }
+    };
+
+    let before = job.status();
+    match step {
+        Step::Chunk(index) => match job.partials.get_mut(index) {
+            Some(slot) => {
+                job::save_partial(ctx, job_id, index, &text);
+                *slot = Some(text);
+            }
+            None => return,
+        },
+        Step::Merge => job.summary = Some(text),
+    }
+    job.model = Some(result.model);
+
+    advance(ctx, job_id, &mut job);
+    // Chunk summaries have their own keys; the record only changes when the
+    // job moves on, which keeps concurrent chunk results from overwriting it
+    if step == Step::Merge || job.status() != before {
+        job::save(ctx, job_id, &job);
+    }
+
+    if job.summary.is_some() {
+        notify_updated(ctx, job_id);
+    }
+}
+
+fn record_error(ctx: &MessageContext, job_id: &str, message: String) {
+    if let Some(mut job) = job::load(ctx, job_id) {
+        job.error = Some(message);
+        job::save(ctx, job_id, &job);
+        notify_updated(ctx, job_id);
+    }
+}
+
+/// Move a job forward once every chunk is summarized
+///
+/// Single-chunk jobs are complete as soon as their chunk is. Otherwise the
+/// merge request is sent if a stream is available; HTTP delivers client
+/// results without one, so the merge may wait until the job resource is read.
+/// Instances handling the last chunks concurrently may all get here; only
+/// the one that claims the merge sends it.
+fn advance(ctx: &MessageContext, job_id: &str, job: &mut Job) {
+    if job.summary.is_some() || job.error.is_some() || job.merge_requested {
+        return;
+    }
+    let Some(partials) = job.completed_partials() else {
+        return;
+    };
+
+    if partials.len() == 1 {
+        job.summary = partials.into_iter().next();
+        return;
+    }
+    if !can_send(ctx) || !job::claim_merge(ctx, job_id) {
+        return;
+    }
+
+    let message = ServerMessage::Request((
+        RequestId::String(job::request_id(job_id, Step::Merge)),
+        ServerRequest::SamplingCreateMessage(pipeline::merge_request(&partials, &job.options)),
+    ));
+    match send(ctx, message) {
+        Ok(()) => job.merge_requested = true,
+        Err(e) => eprintln!("[summarize] Failed to send merge request: {}", e),
+    }
+}
+
+fn notify_updated(ctx: &MessageContext, job_id: &str) {
+    if ctx.client_stream.is_none() {
+        return;
+    }
+    let notification = ServerMessage::Notification(ServerNotification::ResourcesUpdated(
+        ResourceUpdatedNotification {
+            meta: None,
+            uri: format!("{}{}", JOB_URI_PREFIX, job_id),
+        },
+    ));
+    if let Err(e) = send(ctx, notification) {
+        eprintln!("[summarize] Failed to send update notification: {}", e);
+    }
+}
+
+fn handle_resources_read(uri: &str, ctx: &MessageContext) -> Result<ServerResult, ErrorCode> {
+    let job_id = &uri[JOB_URI_PREFIX.len()..];
+    let mut job = job::load(ctx, job_id).ok_or_else(|| {
+        ErrorCode::InvalidParams(Error {
+            code: -32602,
+            message: format!("Unknown summarization job: {}", job_id),
+            data: None,
+        })
+    })?;
+
+    let before = job.status();
+    advance(ctx, job_id, &mut job);
+    if job.summary.is_none() && job.error.is_none() && !job.merge_requested {
+        // No stream to send the merge request on; fall back to the chunk summaries
+        if let Some(partials) = job.completed_partials() {
+            job.summary = Some(partials.join("\n\n"));
+        }
+    }
+    if job.status() != before {
+        job::save(ctx, job_id, &job);
+    }
+
+    let done = job.partials.iter().filter(|p| p.is_some()).count();
+    let text = match (&job.summary, &job.error) {
+        (_, Some(error)) => format!("Summarization failed: {}", error),
+        (Some(summary), None) => summary.clone(),
+        (None, None) => format!(
+            "Summarization in progress ({}): {} of {} chunk(s) summarized",
+            job.status(),
+            done,
+            job.partials.len()
+        ),
+    };
+
+    let meta = serde_json::json!({
+        "status": job.status(),
+        "chunks": job.partials.len(),
+        "chunksDone": done,
+        "model": job.model,
+    });
+
+    Ok(ServerResult::ResourcesRead(ReadResourceResult {
+        contents: vec![ResourceContents::Text(TextResourceContents {
+            uri: uri.to_string(),
+            text: TextData::Text(text),
+            options: Some(EmbeddedResourceOptions {
+                mime_type: Some("text/plain".to_string()),
+                meta: Some(meta.to_string()),
+            }),
+        })],
+        meta: None,
+    }))
+}
+
+fn error_message(error: &ErrorCode) -> String {
+    match error {
+        ErrorCode::ParseError(e)
+        | ErrorCode::InvalidRequest(e)
+        | ErrorCode::MethodNotFound(e)
+        | ErrorCode::InvalidParams(e)
+        | ErrorCode::InternalError(e)
+        | ErrorCode::Server(e)
+        | ErrorCode::JsonRpc(e)
+        | ErrorCode::Mcp(e) => e.message.clone(),
+    }
+}
+
+bindings::export!(Summarize with_types_in bindings);
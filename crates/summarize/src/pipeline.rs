@@ -0,0 +1,233 @@
+//! Chunking, token budgeting and sampling request construction
+//!
+//! `plan(text, opts)` splits the text into chunks and builds one
+//! sampling/createMessage request per chunk. Once every chunk summary is back,
+//! `merge_request` combines them into the final summary. Single-chunk inputs
+//! skip the merge step.
+
+use crate::bindings::wasmcp::mcp_v20251125::mcp::{
+    IncludeContext, ModelHint, ModelPreferences, Role, SamplingContentBlock,
+    SamplingCreateMessageRequest, SamplingMessage, TextContent, TextData,
+};
+use serde::{Deserialize, Serialize};
+
+/// Smallest token budget given to a single chunk summary
+const MIN_CHUNK_TOKENS: u64 = 256;
+
+/// Largest token budget sent in a sampling request
+///
+/// `maxTokens` is a JSON number; beyond 2^53 - 1 clients can't read it exactly.
+pub const MAX_TOKENS: u64 = (1 << 53) - 1;
+
+const CHUNK_PROMPT: &str =
+    "Summarize the following text. Keep names, numbers and decisions; drop repetition.";
+const MERGE_PROMPT: &str = "The following are summaries of consecutive parts of one document. Merge them into a single coherent summary without repeating points.";
+
+/// Options for a summarization run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Options {
+    /// Token budget for the final summary
+    pub max_tokens: u64,
+    /// Extra guidance appended to the system prompt (audience, format, focus)
+    pub instructions: Option<String>,
+    /// Preferred model names, most preferred first
+    pub model_hints: Vec<String>,
+    pub cost_priority: Option<f64>,
+    pub speed_priority: Option<f64>,
+    pub intelligence_priority: Option<f64>,
+}
+
+/// Split text and build the per-chunk sampling requests
+pub fn plan(text: &str, opts: &Options, chunk_size: usize) -> Vec<SamplingCreateMessageRequest> {
+    let chunks = chunk(text, chunk_size);
+    let tokens = chunk_budget(opts.max_tokens, chunks.len());
+    chunks
+        .iter()
+        .enumerate()
+        .map(|(i, chunk)| {
+            let body = if chunks.len() == 1 {
+                chunk.clone()
+            } else {
+                format!("[Part {} of {}]\n\n{}", i + 1, chunks.len(), chunk)
+            };
+            request(CHUNK_PROMPT, body, tokens, opts)
+        })
+        .collect()
+}
+
+/// Build the request that merges chunk summaries into the final summary
+pub fn merge_request(summaries: &[String], opts: &Options) -> SamplingCreateMessageRequest {
+    let body = summaries
+        .iter()
+        .enumerate()
+        .map(|(i, s)| format!("[Part {}]\n{}", i + 1, s))
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    request(MERGE_PROMPT, body, opts.max_tokens, opts)
+}
+
+/// Per-chunk token budget
+///
+/// Chunk summaries together get about twice the final budget, so the merge
+/// step has material to condense, but no chunk gets more than the whole budget.
+fn chunk_budget(max_tokens: u64, chunks: usize) -> u64 {
+    if chunks <= 1 {
+        return max_tokens;
+    }
+    (max_tokens.saturating_mul(2) / chunks as u64)
+        .max(MIN_CHUNK_TOKENS)
+        .min(max_tokens)
+}
+
+/// Split on paragraph boundaries into chunks of at most `chunk_size` bytes
+fn chunk(text: &str, chunk_size: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+
+    let paragraphs = text.split("\n\n").map(str::trim).filter(|p| !p.is_empty());
+    for piece in paragraphs.flat_map(|p| split_long(p, chunk_size)) {
+        if !current.is_empty() && current.len() + 2 + piece.len() > chunk_size {
+            chunks.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push_str("\n\n");
+        }
+        current.push_str(piece);
+    }
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+/// Split an oversized paragraph, preferring whitespace near the limit
+fn split_long(mut text: &str, chunk_size: usize) -> Vec<&str> {
+    let mut pieces = Vec::new();
+    while text.len() > chunk_size {
+        let mut cut = chunk_size;
+        while !text.is_char_boundary(cut) {
+            cut -= 1;
+        }
+        if let Some(space) = text[..cut].rfind(char::is_whitespace) {
+            if space > chunk_size / 2 {
+                cut = space;
+            }
+        }
+        pieces.push(text[..cut].trim_end());
+        text = text[cut..].trim_start();
+    }
+    if !text.is_empty() {
+        pieces.push(text);
+    }
+    pieces
+}
+
+fn request(
+    prompt: &str,
+    body: String,
+    max_tokens: u64,
+    opts: &Options,
+) -> SamplingCreateMessageRequest {
+    let system_prompt = match &opts.instructions {
+        Some(instructions) => format!("{}\n\n{}", prompt, instructions),
+        None => prompt.to_string(),
+    };
+
+    let has_preferences = !opts.model_hints.is_empty()
+        || opts.cost_priority.is_some()
+        || opts.speed_priority.is_some()
+        || opts.intelligence_priority.is_some();
+    let model_preferences = has_preferences.then(|| ModelPreferences {
+        cost_priority: opts.cost_priority,
+        hints: (!opts.model_hints.is_empty()).then(|| {
+            opts.model_hints
+                .iter()
+                .map(|name| ModelHint {
+                    name: Some(name.clone()),
+                    extra: None,
+                })
+                .collect()
+        }),
+        intelligence_priority: opts.intelligence_priority,
+        speed_priority: opts.speed_priority,
+    });
+
+    SamplingCreateMessageRequest {
+        include_context: IncludeContext::None,
+        max_tokens,
+        messages: vec![SamplingMessage {
+            content: SamplingContentBlock::Text(TextContent {
+                text: TextData::Text(body),
+                options: None,
+            }),
+            role: Role::User,
+        }],
+        metadata: None,
+        model_preferences,
+        stop_sequences: None,
+        system_prompt: Some(system_prompt),
+        temperature: None,
+        tools: None,
+        tool_choice: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunk_budget_single_chunk_gets_whole_budget() {
+        assert_eq!(chunk_budget(1000, 0), 1000);
+        assert_eq!(chunk_budget(1000, 1), 1000);
+    }
+
+    #[test]
+    fn test_chunk_budget_splits_twice_the_budget() {
+        assert_eq!(chunk_budget(1000, 4), 500);
+        // Never more than the whole budget, never less than the minimum
+        assert_eq!(chunk_budget(1000, 2), 1000);
+        assert_eq!(chunk_budget(1000, 16), MIN_CHUNK_TOKENS);
+    }
+
+    #[test]
+    fn test_chunk_budget_does_not_overflow() {
+        assert_eq!(chunk_budget(u64::MAX, 2), u64::MAX / 2);
+        assert_eq!(chunk_budget(MAX_TOKENS, 2), MAX_TOKENS);
+    }
+
+    #[test]
+    fn test_chunk_packs_paragraphs() {
+        let chunks = chunk("one\n\ntwo\n\n\n\nthree", 10);
+        assert_eq!(chunks, vec!["one\n\ntwo", "three"]);
+    }
+
+    #[test]
+    fn test_chunk_empty_text() {
+        assert!(chunk("", 10).is_empty());
+        assert!(chunk(" \n\n \n\n", 10).is_empty());
+    }
+
+    #[test]
+    fn test_chunk_respects_size() {
+        let text = "word ".repeat(500);
+        for chunk in chunk(&text, 64) {
+            assert!(chunk.len() <= 64, "chunk of {} bytes", chunk.len());
+        }
+    }
+
+    #[test]
+    fn test_split_long_prefers_whitespace() {
+        assert_eq!(
+            split_long("alpha beta gamma", 12),
+            vec!["alpha beta", "gamma"]
+        );
+    }
+
+    #[test]
+    fn test_split_long_cuts_on_char_boundary() {
+        let pieces = split_long("ééééé", 3);
+        assert_eq!(pieces.concat(), "ééééé");
+        assert!(pieces.iter().all(|p| p.len() <= 3));
+    }
+}
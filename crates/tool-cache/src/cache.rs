@@ -0,0 +1,329 @@
+//! Cache keys and entries
+//!
+//! A cached result is stored under
+//! `wasmcp:tool-cache:{generation}:{tool}:{digest}`, where the digest is a
+//! SHA-256 of the caller's scope and the canonical arguments. Object keys
+//! are sorted when arguments are re-serialized, so payloads that differ only
+//! in key order or whitespace share an entry. Bumping the generation makes
+//! every earlier entry unreachable at once.
+//!
+//! Entries are JSON with `stored_at` and `expires_at` in Unix seconds, the
+//! shape the maintenance component's `kv-ttl` job sweeps.
+
+use crate::bindings::wasmcp::mcp_v20251125::mcp::{
+    CallToolResult, ContentBlock, ContentOptions, TextContent, TextData, Tool,
+};
+use serde_json::{Map, Value, json};
+use sha2::{Digest, Sha256};
+
+/// Prefix of every key this component writes
+pub const KEY_PREFIX: &str = "wasmcp:tool-cache:";
+
+/// Counter bumped whenever the downstream tool list changes
+pub const GENERATION_KEY: &str = "wasmcp:tool-cache:generation";
+
+/// Fingerprint of the last tool list seen
+pub const FINGERPRINT_KEY: &str = "wasmcp:tool-cache:fingerprint";
+
+/// `_meta` field carrying the bypass flag and the hit annotation
+pub const META_FIELD: &str = "wasmcp/cache";
+
+/// Arguments of a call, ready for lookup and forwarding
+#[derive(Debug, PartialEq)]
+pub struct Prepared {
+    /// Canonical arguments the entry is keyed by
+    pub canonical: String,
+    /// Arguments to forward, without the bypass flag
+    pub forwarded: Option<String>,
+    /// The caller asked for a fresh result
+    pub bypass: bool,
+}
+
+/// Canonicalize call arguments and take out the bypass flag
+///
+/// The flag is `"_meta": {"wasmcp/cache": "bypass"}` inside the arguments;
+/// it is removed before the call is forwarded so handlers never see it.
+/// Returns None for arguments that aren't valid JSON, which are forwarded
+/// as they are and never cached.
+pub fn prepare(arguments: Option<&str>) -> Option<Prepared> {
+    let Some(arguments) = arguments else {
+        return Some(Prepared {
+            canonical: "{}".to_string(),
+            forwarded: None,
+            bypass: false,
+        });
+    };
+    let mut value: Value = serde_json::from_str(arguments).ok()?;
+
+    let mut flag = None;
+    if let Some(object) = value.as_object_mut()
+        && let Some(Value::Object(meta)) = object.get_mut("_meta")
+    {
+        flag = meta.remove(META_FIELD);
+        if meta.is_empty() {
+            object.remove("_meta");
+        }
+    }
+    let bypass = flag.as_ref().is_some_and(|v| v == "bypass");
+
+    let canonical = value.to_string();
+    let forwarded = if flag.is_some() {
+        Some(canonical.clone())
+    } else {
+        Some(arguments.to_string())
+    };
+    Some(Prepared {
+        canonical,
+        forwarded,
+        bypass,
+    })
+}
+
+/// Whether the HTTP request asked caches to revalidate
+///
+/// `Cache-Control: no-cache` (or `no-store`) bypasses the cache, for
+/// clients that can't add fields to the arguments.
+pub fn header_bypass(headers: &[(String, String)]) -> bool {
+    headers.iter().any(|(name, value)| {
+        name.eq_ignore_ascii_case("cache-control")
+            && value.split(',').any(|directive| {
+                let directive = directive.trim();
+                directive.eq_ignore_ascii_case("no-cache")
+                    || directive.eq_ignore_ascii_case("no-store")
+            })
+    })
+}
+
+/// Key of a tool's entry for a caller scope and canonical arguments
+pub fn entry_key(generation: i64, tool: &str, scope: &str, canonical: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(scope.as_bytes());
+    hasher.update([0]);
+    hasher.update(canonical.as_bytes());
+    format!(
+        "{}{}:{}:{}",
+        KEY_PREFIX,
+        generation,
+        tool,
+        hex::encode(hasher.finalize())
+    )
+}
+
+/// Fingerprint of a tool list
+///
+/// Covers what a `notifications/tools/list_changed` would announce: names,
+/// descriptions and schemas.
+pub fn fingerprint(tools: &[Tool]) -> String {
+    let mut hasher = Sha256::new();
+    for tool in tools {
+        let options = tool.options.as_ref();
+        for part in [
+            Some(tool.name.as_str()),
+            Some(tool.input_schema.as_str()),
+            options.and_then(|o| o.description.as_deref()),
+            options.and_then(|o| o.output_schema.as_deref()),
+        ] {
+            hasher.update(part.unwrap_or_default().as_bytes());
+            hasher.update([0]);
+        }
+    }
+    hex::encode(hasher.finalize())
+}
+
+/// Cache entry for a result, if the result can be cached
+///
+/// Only successful results made of inline text blocks (and structured
+/// content) are cached; errors, streams, binary content and resources are
+/// always passed through fresh.
+pub fn encode(result: &CallToolResult, now: u64, ttl: u64) -> Option<Value> {
+    if result.is_error == Some(true) {
+        return None;
+    }
+    let mut content = Vec::with_capacity(result.content.len());
+    for block in &result.content {
+        let ContentBlock::Text(TextContent {
+            text: TextData::Text(text),
+            options,
+        }) = block
+        else {
+            return None;
+        };
+        if options.as_ref().is_some_and(|o| o.annotations.is_some()) {
+            return None;
+        }
+        let meta = options.as_ref().and_then(|o| o.meta.clone());
+        content.push(json!({ "text": text, "meta": meta }));
+    }
+
+    Some(json!({
+        "stored_at": now,
+        "expires_at": now.saturating_add(ttl),
+        "content": content,
+        "structuredContent": result.structured_content,
+        "meta": result.meta,
+    }))
+}
+
+/// The result in a cache entry, unless expired
+pub fn decode(entry: &Value, now: u64) -> Option<CallToolResult> {
+    let expires_at = entry["expires_at"].as_u64()?;
+    if now >= expires_at {
+        return None;
+    }
+    let age = now.saturating_sub(entry["stored_at"].as_u64()?);
+
+    let content = entry["content"]
+        .as_array()?
+        .iter()
+        .map(|block| {
+            let text = block["text"].as_str()?.to_string();
+            let options = block["meta"].as_str().map(|meta| ContentOptions {
+                annotations: None,
+                meta: Some(meta.to_string()),
+            });
+            Some(ContentBlock::Text(TextContent {
+                text: TextData::Text(text),
+                options,
+            }))
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    Some(CallToolResult {
+        meta: Some(hit_meta(entry["meta"].as_str(), age)),
+        content,
+        is_error: None,
+        structured_content: entry["structuredContent"].as_str().map(str::to_string),
+    })
+}
+
+/// Result `_meta` with the cache hit recorded
+///
+/// Keeps the fields of the cached result's own `_meta`.
+pub fn hit_meta(meta: Option<&str>, age: u64) -> String {
+    let mut object = meta
+        .and_then(|m| serde_json::from_str::<Map<String, Value>>(m).ok())
+        .unwrap_or_default();
+    object.insert(
+        META_FIELD.to_string(),
+        json!({ "status": "hit", "ageSeconds": age }),
+    );
+    Value::Object(object).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text_result(texts: &[&str]) -> CallToolResult {
+        CallToolResult {
+            meta: None,
+            content: texts
+                .iter()
+                .map(|t| {
+                    ContentBlock::Text(TextContent {
+                        text: TextData::Text(t.to_string()),
+                        options: None,
+                    })
+                })
+                .collect(),
+            is_error: None,
+            structured_content: Some(r#"{"n":1}"#.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_prepare_canonicalizes() {
+        let a = prepare(Some(r#"{ "b": 1, "a": [1, 2] }"#)).unwrap();
+        let b = prepare(Some(r#"{"a":[1,2],"b":1}"#)).unwrap();
+        assert_eq!(a.canonical, b.canonical);
+        assert!(!a.bypass);
+        // Unchanged arguments are forwarded as sent
+        assert_eq!(a.forwarded.as_deref(), Some(r#"{ "b": 1, "a": [1, 2] }"#));
+
+        assert_eq!(prepare(None).unwrap().canonical, "{}");
+        assert_eq!(prepare(Some("not json")), None);
+    }
+
+    #[test]
+    fn test_prepare_bypass() {
+        let prepared = prepare(Some(r#"{"q":"rust","_meta":{"wasmcp/cache":"bypass"}}"#)).unwrap();
+        assert!(prepared.bypass);
+        assert_eq!(prepared.forwarded.as_deref(), Some(r#"{"q":"rust"}"#));
+        // A bypassed call refreshes the entry of the same arguments
+        assert_eq!(
+            prepared.canonical,
+            prepare(Some(r#"{"q":"rust"}"#)).unwrap().canonical
+        );
+
+        let prepared = prepare(Some(
+            r#"{"q":"rust","_meta":{"wasmcp/cache":"bypass","trace":"x"}}"#,
+        ))
+        .unwrap();
+        assert!(prepared.bypass);
+        assert_eq!(
+            prepared.forwarded.as_deref(),
+            Some(r#"{"_meta":{"trace":"x"},"q":"rust"}"#)
+        );
+
+        let prepared = prepare(Some(r#"{"_meta":{"wasmcp/cache":"use"}}"#)).unwrap();
+        assert!(!prepared.bypass);
+        assert_eq!(prepared.forwarded.as_deref(), Some("{}"));
+    }
+
+    #[test]
+    fn test_header_bypass() {
+        let header = |v: &str| vec![("Cache-Control".to_string(), v.to_string())];
+        assert!(header_bypass(&header("no-cache")));
+        assert!(header_bypass(&header("max-age=0, No-Store")));
+        assert!(!header_bypass(&header("max-age=60")));
+        assert!(!header_bypass(&[]));
+    }
+
+    #[test]
+    fn test_entry_key() {
+        let key = entry_key(3, "search", "alice", "{}");
+        assert!(key.starts_with("wasmcp:tool-cache:3:search:"));
+        assert_ne!(key, entry_key(3, "search", "bob", "{}"));
+        assert_ne!(key, entry_key(4, "search", "alice", "{}"));
+        assert_eq!(key, entry_key(3, "search", "alice", "{}"));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let entry = encode(&text_result(&["a", "b"]), 1_000, 60).unwrap();
+        assert_eq!(entry["expires_at"], 1_060);
+
+        let result = decode(&entry, 1_015).unwrap();
+        assert_eq!(result.content.len(), 2);
+        assert_eq!(result.structured_content.as_deref(), Some(r#"{"n":1}"#));
+        let meta: Value = serde_json::from_str(result.meta.as_deref().unwrap()).unwrap();
+        assert_eq!(meta[META_FIELD]["status"], "hit");
+        assert_eq!(meta[META_FIELD]["ageSeconds"], 15);
+
+        assert!(decode(&entry, 1_060).is_none());
+    }
+
+    #[test]
+    fn test_uncacheable() {
+        let mut error = text_result(&["failed"]);
+        error.is_error = Some(true);
+        assert!(encode(&error, 0, 60).is_none());
+
+        let mut blob = text_result(&[]);
+        blob.content.push(ContentBlock::ResourceLink(
+            crate::bindings::wasmcp::mcp_v20251125::mcp::ResourceLinkContent {
+                uri: "file:///a".to_string(),
+                name: "a".to_string(),
+                options: None,
+            },
+        ));
+        assert!(encode(&blob, 0, 60).is_none());
+    }
+
+    #[test]
+    fn test_hit_meta_keeps_fields() {
+        let meta: Value = serde_json::from_str(&hit_meta(Some(r#"{"trace":"x"}"#), 0)).unwrap();
+        assert_eq!(meta["trace"], "x");
+        assert_eq!(meta[META_FIELD]["status"], "hit");
+    }
+}
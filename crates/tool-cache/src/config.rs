@@ -0,0 +1,153 @@
+//! Configuration for the tool cache middleware
+//!
+//! Environment variables:
+//! - `WASMCP_TOOL_CACHE_TOOLS`: Tools to cache and how long, e.g.
+//!   `search=60,weather=5m` (seconds, or with an `s`, `m`, `h` or `d` suffix).
+//!   Tools not listed are never cached.
+//! - `WASMCP_TOOL_CACHE_BUCKET`: KV bucket for cached results (default: "")
+//! - `WASMCP_TOOL_CACHE_SHARED`: "true"/"false" (default: "false") - Share
+//!   cached results between callers. By default results are cached per
+//!   authenticated subject.
+//! - `WASMCP_TOOL_CACHE_MAX_BYTES`: Largest result stored (default: 65536)
+
+use crate::bindings::wasi::cli::environment::get_environment;
+use std::collections::HashMap;
+
+const DEFAULT_MAX_BYTES: usize = 64 * 1024;
+
+/// Tool cache configuration from environment variables
+#[derive(Debug, Clone)]
+pub struct ToolCacheConfig {
+    /// Time to live in seconds per cached tool
+    pub ttls: HashMap<String, u64>,
+    pub bucket: String,
+    /// Serve one caller's cached results to every caller
+    pub shared: bool,
+    /// Largest serialized entry stored
+    pub max_bytes: usize,
+}
+
+impl ToolCacheConfig {
+    /// Load configuration from environment variables
+    pub fn from_env() -> Self {
+        let env_map: HashMap<String, String> = get_environment().into_iter().collect();
+
+        let ttls = match env_map.get("WASMCP_TOOL_CACHE_TOOLS") {
+            Some(v) => {
+                let (ttls, warnings) = parse_ttls(v);
+                for warning in warnings {
+                    eprintln!(
+                        "[tool-cache] WARNING: {} in WASMCP_TOOL_CACHE_TOOLS",
+                        warning
+                    );
+                }
+                ttls
+            }
+            None => HashMap::new(),
+        };
+
+        let bucket = env_map
+            .get("WASMCP_TOOL_CACHE_BUCKET")
+            .cloned()
+            .unwrap_or_default();
+
+        let shared = env_map
+            .get("WASMCP_TOOL_CACHE_SHARED")
+            .map(|v| v.to_lowercase() == "true")
+            .unwrap_or(false);
+
+        let max_bytes = match env_map.get("WASMCP_TOOL_CACHE_MAX_BYTES") {
+            None => DEFAULT_MAX_BYTES,
+            Some(v) => v.trim().parse::<usize>().unwrap_or_else(|_| {
+                eprintln!(
+                    "[tool-cache] WARNING: Invalid WASMCP_TOOL_CACHE_MAX_BYTES='{}', defaulting to {}",
+                    v, DEFAULT_MAX_BYTES
+                );
+                DEFAULT_MAX_BYTES
+            }),
+        };
+
+        ToolCacheConfig {
+            ttls,
+            bucket,
+            shared,
+            max_bytes,
+        }
+    }
+
+    /// Time to live of a tool's results, if the tool is cached
+    pub fn ttl(&self, tool: &str) -> Option<u64> {
+        self.ttls.get(tool).copied()
+    }
+}
+
+/// Parse `tool=ttl` pairs into TTLs in seconds
+///
+/// Invalid entries are reported and skipped, so one typo doesn't disable
+/// caching for the other tools.
+pub fn parse_ttls(value: &str) -> (HashMap<String, u64>, Vec<String>) {
+    let mut ttls = HashMap::new();
+    let mut warnings = Vec::new();
+
+    for entry in value.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+        let Some((tool, ttl)) = entry.split_once('=') else {
+            warnings.push(format!("Missing TTL in '{}'", entry));
+            continue;
+        };
+        match parse_duration(ttl.trim()) {
+            Some(secs) => {
+                ttls.insert(tool.trim().to_string(), secs);
+            }
+            None => warnings.push(format!("Invalid TTL '{}' for {}", ttl.trim(), tool.trim())),
+        }
+    }
+
+    (ttls, warnings)
+}
+
+/// Parse a duration: seconds, or a number with an `s`, `m`, `h` or `d` suffix
+fn parse_duration(value: &str) -> Option<u64> {
+    let (digits, unit) = match value.char_indices().last()? {
+        (i, c) if c.is_ascii_alphabetic() => (&value[..i], c),
+        _ => (value, 's'),
+    };
+    let scale = match unit {
+        's' => 1,
+        'm' => 60,
+        'h' => 60 * 60,
+        'd' => 24 * 60 * 60,
+        _ => return None,
+    };
+    digits
+        .parse::<u64>()
+        .ok()
+        .filter(|&n| n > 0)
+        .and_then(|n| n.checked_mul(scale))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ttls() {
+        let (ttls, warnings) = parse_ttls("search=60, weather = 5m,lookup,geo=0,docs=1d");
+        assert_eq!(ttls.len(), 3);
+        assert_eq!(ttls["search"], 60);
+        assert_eq!(ttls["weather"], 300);
+        assert_eq!(ttls["docs"], 86400);
+        assert_eq!(
+            warnings,
+            ["Missing TTL in 'lookup'", "Invalid TTL '0' for geo"]
+        );
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("90"), Some(90));
+        assert_eq!(parse_duration("45s"), Some(45));
+        assert_eq!(parse_duration("2h"), Some(7200));
+        assert_eq!(parse_duration("5w"), None);
+        assert_eq!(parse_duration(""), None);
+    }
+}
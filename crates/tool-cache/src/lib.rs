@@ -0,0 +1,244 @@
+//! Tool Cache Middleware Component
+//!
+//! Serves repeated calls to expensive read-only tools from wasmcp:keyvalue
+//! instead of calling the handler again. For every tool listed in
+//! `WASMCP_TOOL_CACHE_TOOLS`, this component:
+//! - Looks up a cached result for the tool, the caller and the canonical
+//!   arguments, and returns it while it is younger than the tool's TTL,
+//!   marked with `_meta["wasmcp/cache"]`
+//! - Otherwise forwards the call and caches a successful text result
+//! - Skips the lookup when the arguments carry
+//!   `"_meta": {"wasmcp/cache": "bypass"}` or the HTTP request sends
+//!   `Cache-Control: no-cache`, and stores the fresh result
+//! - Invalidates every cached result when the downstream tool list changes,
+//!   as seen on the first page of tools/list
+//! - Delegates everything else downstream unchanged
+
+#![allow(warnings)]
+
+mod bindings {
+    wit_bindgen::generate!({
+        world: "tool-cache",
+        generate_all,
+    });
+}
+
+mod cache;
+mod config;
+
+use bindings::exports::wasmcp::mcp_v20251125::server_handler::{Guest, MessageContext};
+use bindings::wasmcp::keyvalue::store::{self as kv, Bucket, TypedValue};
+use bindings::wasmcp::mcp_v20251125::mcp::*;
+use bindings::wasmcp::mcp_v20251125::server_handler as downstream;
+use config::ToolCacheConfig;
+use serde_json::Value;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Scope of callers without an identity, who can't be told apart
+const ANONYMOUS_SCOPE: &str = "anonymous";
+
+/// Scope of entries shared by every caller
+const SHARED_SCOPE: &str = "shared";
+
+struct ToolCache;
+
+// Convert exported MessageContext to imported MessageContext
+fn to_downstream_ctx<'a>(ctx: &'a MessageContext<'a>) -> downstream::MessageContext<'a> {
+    downstream::MessageContext {
+        client_stream: ctx.client_stream,
+        protocol_version: ctx.protocol_version.clone(),
+        session: ctx.session.as_ref().map(|s| downstream::Session {
+            session_id: s.session_id.clone(),
+            store_id: s.store_id.clone(),
+        }),
+        identity: ctx.identity.as_ref().map(|i| downstream::Identity {
+            jwt: i.jwt.clone(),
+            claims: i.claims.clone(),
+        }),
+        frame: ctx.frame.clone(),
+        http_context: ctx.http_context.clone(),
+        client_capabilities: ctx.client_capabilities.clone(),
+        request_id: ctx.request_id.clone(),
+        deadline: ctx.deadline,
+        log_level: ctx.log_level,
+        client_info: ctx.client_info.clone(),
+        locale: ctx.locale.clone(),
+        trace_context: ctx.trace_context.clone(),
+    }
+}
+
+impl Guest for ToolCache {
+    fn handle(
+        ctx: MessageContext,
+        message: ClientMessage,
+    ) -> Option<Result<ServerResult, ErrorCode>> {
+        let ClientMessage::Request((request_id, request)) = message else {
+            // Forward notifications, results, errors to downstream
+            return downstream::handle(&to_downstream_ctx(&ctx), message);
+        };
+
+        let config = ToolCacheConfig::from_env();
+        if config.ttls.is_empty() {
+            return delegate(&ctx, request_id, request);
+        }
+
+        match request {
+            ClientRequest::ToolsCall(call) => match config.ttl(&call.name) {
+                Some(ttl) => handle_tools_call(&ctx, &config, request_id, call, ttl),
+                None => delegate(&ctx, request_id, ClientRequest::ToolsCall(call)),
+            },
+            ClientRequest::ToolsList(list_req) if list_req.cursor.is_none() => {
+                let response = delegate(&ctx, request_id, ClientRequest::ToolsList(list_req));
+                if let Some(Ok(ServerResult::ToolsList(result))) = &response {
+                    check_tool_list(&config, &result.tools);
+                }
+                response
+            }
+            _ => delegate(&ctx, request_id, request),
+        }
+    }
+}
+
+fn delegate(
+    ctx: &MessageContext,
+    request_id: RequestId,
+    request: ClientRequest,
+) -> Option<Result<ServerResult, ErrorCode>> {
+    let downstream_msg = ClientMessage::Request((request_id, request));
+    downstream::handle(&to_downstream_ctx(ctx), downstream_msg)
+}
+
+/// Serve a call to a cached tool from the cache, or forward it and cache
+/// the result
+///
+/// Storage failures never fail the call: it is forwarded as if uncached.
+fn handle_tools_call(
+    ctx: &MessageContext,
+    config: &ToolCacheConfig,
+    request_id: RequestId,
+    call: CallToolRequest,
+    ttl: u64,
+) -> Option<Result<ServerResult, ErrorCode>> {
+    let Some(prepared) = cache::prepare(call.arguments.as_deref()) else {
+        return delegate(ctx, request_id, ClientRequest::ToolsCall(call));
+    };
+    let forwarded = ClientRequest::ToolsCall(CallToolRequest {
+        name: call.name.clone(),
+        arguments: prepared.forwarded.clone(),
+    });
+
+    let bucket = match kv::open(&config.bucket) {
+        Ok(bucket) => bucket,
+        Err(e) => {
+            eprintln!("[tool-cache] WARNING: Failed to open bucket: {}", e);
+            return delegate(ctx, request_id, forwarded);
+        }
+    };
+    let generation = bucket.increment(cache::GENERATION_KEY, 0).unwrap_or(0);
+    let key = cache::entry_key(
+        generation,
+        &call.name,
+        &scope(ctx, config),
+        &prepared.canonical,
+    );
+
+    let now = now_secs();
+    let bypass = prepared.bypass
+        || ctx
+            .http_context
+            .as_ref()
+            .is_some_and(|http| cache::header_bypass(&http.headers));
+    if !bypass {
+        let cached = bucket
+            .get_json(&key)
+            .ok()
+            .flatten()
+            .and_then(|json| serde_json::from_str::<Value>(&json).ok())
+            .and_then(|entry| cache::decode(&entry, now));
+        if let Some(result) = cached {
+            return Some(Ok(ServerResult::ToolsCall(result)));
+        }
+    }
+
+    let response = delegate(ctx, request_id, forwarded);
+    if let Some(Ok(ServerResult::ToolsCall(result))) = &response {
+        store(&bucket, config, &key, result, now, ttl);
+    }
+    response
+}
+
+/// Cache a result, unless it can't be cached or is too large
+fn store(
+    bucket: &Bucket,
+    config: &ToolCacheConfig,
+    key: &str,
+    result: &CallToolResult,
+    now: u64,
+    ttl: u64,
+) {
+    let Some(entry) = cache::encode(result, now, ttl) else {
+        return;
+    };
+    let json = entry.to_string();
+    if json.len() > config.max_bytes {
+        return;
+    }
+    if let Err(e) = bucket.set(key, &TypedValue::AsJson(json)) {
+        eprintln!("[tool-cache] WARNING: Failed to cache result: {}", e);
+    }
+}
+
+/// Invalidate every cached result if the tool list changed since last seen
+///
+/// Servers announce such changes with `notifications/tools/list_changed`,
+/// which clients answer by listing the tools again; that list is compared
+/// with the previous one.
+fn check_tool_list(config: &ToolCacheConfig, tools: &[Tool]) {
+    let bucket = match kv::open(&config.bucket) {
+        Ok(bucket) => bucket,
+        Err(e) => {
+            eprintln!("[tool-cache] WARNING: Failed to open bucket: {}", e);
+            return;
+        }
+    };
+
+    let fingerprint = cache::fingerprint(tools);
+    let previous = bucket.get_string(cache::FINGERPRINT_KEY).ok().flatten();
+    if previous.as_deref() == Some(fingerprint.as_str()) {
+        return;
+    }
+    if let Err(e) = bucket.increment(cache::GENERATION_KEY, 1) {
+        eprintln!("[tool-cache] WARNING: Failed to invalidate cache: {}", e);
+        return;
+    }
+    if let Err(e) = bucket.set_string(cache::FINGERPRINT_KEY, &fingerprint) {
+        eprintln!("[tool-cache] WARNING: Failed to record tool list: {}", e);
+    }
+}
+
+/// Caller scope cached results are kept under
+///
+/// Results are per authenticated subject unless sharing is configured, so
+/// one user's results are never served to another.
+fn scope(ctx: &MessageContext, config: &ToolCacheConfig) -> String {
+    if config.shared {
+        return SHARED_SCOPE.to_string();
+    }
+    match &ctx.identity {
+        Some(identity) => format!(
+            "{}|{}",
+            identity.claims.issuer.as_deref().unwrap_or_default(),
+            identity.claims.subject
+        ),
+        None => ANONYMOUS_SCOPE.to_string(),
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+bindings::export!(ToolCache with_types_in bindings);
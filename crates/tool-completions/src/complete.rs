@@ -0,0 +1,102 @@
+//! Completion value sources and matching
+//!
+//! Values come from the configuration, from downstream handlers, or from the
+//! tool's input schema. Whatever the source, they are filtered by the partial
+//! value the client typed and capped at the spec's 100 values per response.
+
+use serde_json::Value;
+
+/// The spec caps `values` at 100 entries per response
+pub const MAX_VALUES: usize = 100;
+
+/// Values that start with `partial` (case-insensitive), in order, capped
+///
+/// Returns the kept values and the number of matches before the cap.
+pub fn matching(candidates: &[String], partial: &str) -> (Vec<String>, usize) {
+    let partial = partial.to_lowercase();
+    let matches: Vec<&String> = candidates
+        .iter()
+        .filter(|c| c.to_lowercase().starts_with(&partial))
+        .collect();
+    let total = matches.len();
+    let values = matches.into_iter().take(MAX_VALUES).cloned().collect();
+    (values, total)
+}
+
+/// Candidate values for `argument` declared by a tool's input schema
+///
+/// Uses the property's `enum` (or `items.enum` for arrays), falling back to
+/// `examples`. Non-string values are rendered as JSON.
+pub fn schema_values(input_schema: &str, argument: &str) -> Vec<String> {
+    let Ok(schema) = serde_json::from_str::<Value>(input_schema) else {
+        return Vec::new();
+    };
+    let Some(property) = schema.get("properties").and_then(|p| p.get(argument)) else {
+        return Vec::new();
+    };
+
+    let declared = property
+        .get("enum")
+        .or_else(|| property.get("items").and_then(|items| items.get("enum")))
+        .or_else(|| property.get("examples"));
+    match declared {
+        Some(Value::Array(values)) => values.iter().map(render).collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn render(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_matching() {
+        let candidates = strings(&["Celsius", "fahrenheit", "kelvin", "centigrade"]);
+        assert_eq!(
+            matching(&candidates, "ce"),
+            (strings(&["Celsius", "centigrade"]), 2)
+        );
+        assert_eq!(matching(&candidates, "").1, 4);
+        assert_eq!(matching(&candidates, "x"), (Vec::new(), 0));
+
+        let many: Vec<String> = (0..150).map(|i| format!("v{}", i)).collect();
+        let (values, total) = matching(&many, "v");
+        assert_eq!(values.len(), MAX_VALUES);
+        assert_eq!(total, 150);
+    }
+
+    #[test]
+    fn test_schema_values() {
+        let schema = r#"{
+            "type": "object",
+            "properties": {
+                "unit": {"type": "string", "enum": ["celsius", "fahrenheit"]},
+                "tags": {"type": "array", "items": {"enum": ["a", "b"]}},
+                "city": {"type": "string", "examples": ["Berlin", "Paris"]},
+                "count": {"type": "integer", "enum": [1, 2]},
+                "note": {"type": "string"}
+            }
+        }"#;
+        assert_eq!(
+            schema_values(schema, "unit"),
+            strings(&["celsius", "fahrenheit"])
+        );
+        assert_eq!(schema_values(schema, "tags"), strings(&["a", "b"]));
+        assert_eq!(schema_values(schema, "city"), strings(&["Berlin", "Paris"]));
+        assert_eq!(schema_values(schema, "count"), strings(&["1", "2"]));
+        assert!(schema_values(schema, "note").is_empty());
+        assert!(schema_values(schema, "missing").is_empty());
+        assert!(schema_values("not json", "unit").is_empty());
+    }
+}
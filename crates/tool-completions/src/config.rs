@@ -0,0 +1,42 @@
+//! Configuration for the tool completions middleware
+//!
+//! Environment variables:
+//! - `WASMCP_TOOL_COMPLETIONS`: Static completion values per tool argument,
+//!   as a JSON object: `{"convert": {"unit": ["celsius", "fahrenheit"]}}`
+
+use crate::bindings::wasi::cli::environment::get_environment;
+use std::collections::HashMap;
+
+/// Tool name -> argument name -> values
+pub type StaticValues = HashMap<String, HashMap<String, Vec<String>>>;
+
+/// Tool completions configuration from environment variables
+#[derive(Debug, Clone, Default)]
+pub struct ToolCompletionsConfig {
+    pub values: StaticValues,
+}
+
+impl ToolCompletionsConfig {
+    /// Load configuration from environment variables
+    pub fn from_env() -> Self {
+        let env_map: HashMap<String, String> = get_environment().into_iter().collect();
+
+        let values = match env_map.get("WASMCP_TOOL_COMPLETIONS") {
+            Some(raw) if !raw.trim().is_empty() => serde_json::from_str(raw).unwrap_or_else(|e| {
+                eprintln!("[tool-completions] Invalid WASMCP_TOOL_COMPLETIONS: {}", e);
+                StaticValues::new()
+            }),
+            _ => StaticValues::new(),
+        };
+
+        ToolCompletionsConfig { values }
+    }
+
+    /// Configured values for one tool argument
+    pub fn values_for(&self, tool: &str, argument: &str) -> Option<&[String]> {
+        self.values
+            .get(tool)
+            .and_then(|arguments| arguments.get(argument))
+            .map(Vec::as_slice)
+    }
+}
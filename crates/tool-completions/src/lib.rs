@@ -0,0 +1,226 @@
+//! Tool Completions Middleware Component
+//!
+//! Answers `completion/complete` for tool arguments, which MCP itself only
+//! defines for prompts and resource templates. Clients send the wasmcp
+//! `ref/tool` reference, which server-io carries as the resource template
+//! `wasmcp://tool/{name}`. This component:
+//! - Completes from the values configured in `WASMCP_TOOL_COMPLETIONS`
+//! - Otherwise lets downstream handlers complete the argument, so tools with
+//!   dynamic values (database lookups, file names) provide their own
+//! - Otherwise falls back to the `enum` or `examples` declared for the
+//!   argument in the tool's input schema
+//! - Advertises the `completions` capability on initialize
+//! - Delegates all other requests downstream
+
+#![allow(warnings)]
+
+mod bindings {
+    wit_bindgen::generate!({
+        world: "tool-completions",
+        generate_all,
+    });
+}
+
+mod complete;
+mod config;
+
+use bindings::exports::wasmcp::mcp_v20251125::server_handler::{Guest, MessageContext};
+use bindings::wasmcp::mcp_v20251125::mcp;
+use bindings::wasmcp::mcp_v20251125::mcp::*;
+use bindings::wasmcp::mcp_v20251125::server_handler as downstream;
+use config::ToolCompletionsConfig;
+use wasmcp_errors::McpError;
+
+wasmcp_errors::impl_error_code!(mcp::ErrorCode, mcp::Error);
+
+/// Completion references to tool arguments (`ref/tool`, see server-io)
+const TOOL_REF_URI_PREFIX: &str = "wasmcp://tool/";
+
+/// Upper bound on tools/list pages fetched to find a tool's schema
+const MAX_PAGES: usize = 100;
+
+struct ToolCompletions;
+
+// Convert exported MessageContext to imported MessageContext
+fn to_downstream_ctx<'a>(ctx: &'a MessageContext<'a>) -> downstream::MessageContext<'a> {
+    downstream::MessageContext {
+        client_stream: ctx.client_stream,
+        protocol_version: ctx.protocol_version.clone(),
+        session: ctx.session.as_ref().map(|s| downstream::Session {
+            session_id: s.session_id.clone(),
+            store_id: s.store_id.clone(),
+        }),
+        identity: ctx.identity.as_ref().map(|i| downstream::Identity {
+            jwt: i.jwt.clone(),
+            claims: i.claims.clone(),
+        }),
+        frame: ctx.frame.clone(),
+        http_context: ctx.http_context.clone(),
+        client_capabilities: ctx.client_capabilities.clone(),
+        request_id: ctx.request_id.clone(),
+        deadline: ctx.deadline,
+        log_level: ctx.log_level,
+        client_info: ctx.client_info.clone(),
+        locale: ctx.locale.clone(),
+        trace_context: ctx.trace_context.clone(),
+    }
+}
+
+impl Guest for ToolCompletions {
+    fn handle(
+        ctx: MessageContext,
+        message: ClientMessage,
+    ) -> Option<Result<ServerResult, ErrorCode>> {
+        match message {
+            ClientMessage::Request((request_id, request)) => match request {
+                ClientRequest::Initialize(req) => handle_initialize(request_id, req, &ctx),
+                ClientRequest::CompletionComplete(req) => match tool_reference(&req) {
+                    Some(tool) => {
+                        let tool = tool.to_string();
+                        handle_tool_completion(request_id, &tool, req, &ctx)
+                    }
+                    None => delegate(&ctx, request_id, ClientRequest::CompletionComplete(req)),
+                },
+                _ => delegate(&ctx, request_id, request),
+            },
+            _ => {
+                // Forward notifications, results, errors to downstream
+                downstream::handle(&to_downstream_ctx(&ctx), message)
+            }
+        }
+    }
+}
+
+fn delegate(
+    ctx: &MessageContext,
+    request_id: RequestId,
+    request: ClientRequest,
+) -> Option<Result<ServerResult, ErrorCode>> {
+    let downstream_msg = ClientMessage::Request((request_id, request));
+    downstream::handle(&to_downstream_ctx(ctx), downstream_msg)
+}
+
+/// The tool named by a `ref/tool` completion reference
+fn tool_reference(req: &CompleteRequest) -> Option<&str> {
+    match &req.ref_ {
+        CompletionReference::ResourceTemplate(uri) => uri.strip_prefix(TOOL_REF_URI_PREFIX),
+        CompletionReference::Prompt(_) => None,
+    }
+}
+
+/// Advertise `completions`, on top of whatever downstream answers
+fn handle_initialize(
+    request_id: RequestId,
+    req: InitializeRequest,
+    ctx: &MessageContext,
+) -> Option<Result<ServerResult, ErrorCode>> {
+    let downstream_req = ClientRequest::Initialize(req.clone());
+    let downstream_msg = ClientMessage::Request((request_id, downstream_req));
+
+    match downstream::handle(&to_downstream_ctx(ctx), downstream_msg) {
+        Some(Ok(ServerResult::Initialize(mut result))) => {
+            // Downstream supplied an initialize result - add our capability to it
+            result
+                .capabilities
+                .completions
+                .get_or_insert_with(|| "{}".to_string());
+            Some(Ok(ServerResult::Initialize(result)))
+        }
+        _ => {
+            // Nobody else answered - contribute capabilities only
+            Some(Ok(ServerResult::Initialize(InitializeResult {
+                meta: None,
+                server_info: Implementation {
+                    name: String::new(),
+                    title: None,
+                    version: String::new(),
+                    description: None,
+                    icons: None,
+                },
+                capabilities: ServerCapabilities {
+                    completions: Some("{}".to_string()),
+                    experimental: None,
+                    logging: None,
+                    list_changed: None,
+                    subscriptions: None,
+                    lists: None,
+                },
+                protocol_version: req.protocol_version,
+                options: None,
+            })))
+        }
+    }
+}
+
+/// Complete a tool argument from configuration, downstream, or the schema
+fn handle_tool_completion(
+    request_id: RequestId,
+    tool: &str,
+    req: CompleteRequest,
+    ctx: &MessageContext,
+) -> Option<Result<ServerResult, ErrorCode>> {
+    let config = ToolCompletionsConfig::from_env();
+    let argument = req.argument.clone();
+
+    if let Some(values) = config.values_for(tool, &argument.name) {
+        return Some(Ok(completion_result(values, &argument.value)));
+    }
+
+    // Custom completion functions live in the handlers composed behind us
+    let downstream_req = ClientRequest::CompletionComplete(req);
+    match delegate(ctx, request_id.clone(), downstream_req) {
+        Some(Ok(ServerResult::CompletionComplete(result))) => {
+            return Some(Ok(ServerResult::CompletionComplete(result)));
+        }
+        Some(Err(ErrorCode::MethodNotFound(_))) | None | Some(Ok(_)) => {}
+        Some(Err(e)) => return Some(Err(e)),
+    }
+
+    let input_schema = match find_input_schema(ctx, &request_id, tool) {
+        Ok(Some(schema)) => schema,
+        Ok(None) => return Some(Err(McpError::tool_not_found(tool).into())),
+        Err(e) => return Some(Err(e)),
+    };
+    let values = complete::schema_values(&input_schema, &argument.name);
+    Some(Ok(completion_result(&values, &argument.value)))
+}
+
+/// The input schema of a downstream tool, searching every tools/list page
+fn find_input_schema(
+    ctx: &MessageContext,
+    request_id: &RequestId,
+    tool: &str,
+) -> Result<Option<String>, ErrorCode> {
+    let mut cursor = None;
+    for _ in 0..MAX_PAGES {
+        let list_req = ClientRequest::ToolsList(ListToolsRequest {
+            cursor: cursor.take(),
+            meta: None,
+        });
+        let list = match delegate(ctx, request_id.clone(), list_req) {
+            Some(Ok(ServerResult::ToolsList(list))) => list,
+            Some(Err(ErrorCode::MethodNotFound(_))) | None | Some(Ok(_)) => return Ok(None),
+            Some(Err(e)) => return Err(e),
+        };
+        if let Some(found) = list.tools.into_iter().find(|t| t.name == tool) {
+            return Ok(Some(found.input_schema));
+        }
+        match list.next_cursor {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+    Ok(None)
+}
+
+fn completion_result(candidates: &[String], partial: &str) -> ServerResult {
+    let (values, total) = complete::matching(candidates, partial);
+    ServerResult::CompletionComplete(CompleteResult {
+        meta: None,
+        has_more: Some(total > values.len()),
+        total: Some(total as u64),
+        values,
+    })
+}
+
+bindings::export!(ToolCompletions with_types_in bindings);
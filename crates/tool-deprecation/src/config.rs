@@ -0,0 +1,131 @@
+//! Configuration for the tool deprecation middleware
+//!
+//! Environment variables:
+//! - `WASMCP_DEPRECATED_TOOLS`: Tools to mark as deprecated (required)
+//!   - JSON object keyed by tool name: `{"add": {"since": "1.2.0", "replacement": "add_v2"}}`
+//!   - Or a comma-separated list of tool names
+//! - `WASMCP_DEPRECATED_TOOLS_WARN`: "true"/"false" (default: "false") - Send a
+//!   warning log notification on every call to a deprecated tool
+
+use crate::bindings::wasi::cli::environment::get_environment;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Why and since when a tool is deprecated
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct Deprecation {
+    /// Version the tool was deprecated in
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub since: Option<String>,
+    /// Tool to call instead
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replacement: Option<String>,
+    /// Free-form note for clients
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl Deprecation {
+    /// One-line notice, e.g. "Deprecated since 1.2.0: use add_v2 instead"
+    pub fn notice(&self) -> String {
+        let mut notice = "Deprecated".to_string();
+        if let Some(since) = &self.since {
+            notice.push_str(&format!(" since {}", since));
+        }
+
+        let details: Vec<String> = self
+            .replacement
+            .iter()
+            .map(|r| format!("use {} instead", r))
+            .chain(self.message.iter().cloned())
+            .collect();
+        if !details.is_empty() {
+            notice.push_str(": ");
+            notice.push_str(&details.join("; "));
+        }
+        notice
+    }
+}
+
+/// Tool deprecation configuration from environment variables
+#[derive(Debug, Clone)]
+pub struct DeprecationConfig {
+    pub tools: HashMap<String, Deprecation>,
+    /// Warn on every call to a deprecated tool
+    pub warn: bool,
+}
+
+impl DeprecationConfig {
+    /// Load configuration from environment variables
+    pub fn from_env() -> Self {
+        let env_map: HashMap<String, String> = get_environment().into_iter().collect();
+
+        let tools = env_map
+            .get("WASMCP_DEPRECATED_TOOLS")
+            .map(|v| parse_tools(v))
+            .unwrap_or_default();
+
+        let warn = env_map
+            .get("WASMCP_DEPRECATED_TOOLS_WARN")
+            .map(|v| v.to_lowercase() == "true")
+            .unwrap_or(false);
+
+        DeprecationConfig { tools, warn }
+    }
+
+    /// The deprecation of a tool, if it is deprecated
+    pub fn deprecation(&self, tool: &str) -> Option<&Deprecation> {
+        self.tools.get(tool)
+    }
+}
+
+fn parse_tools(value: &str) -> HashMap<String, Deprecation> {
+    let trimmed = value.trim();
+    if trimmed.starts_with('{') {
+        return serde_json::from_str(trimmed).unwrap_or_else(|e| {
+            eprintln!(
+                "[tool-deprecation] WARNING: Invalid WASMCP_DEPRECATED_TOOLS JSON: {}. No tools deprecated.",
+                e
+            );
+            HashMap::new()
+        });
+    }
+
+    trimmed
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| (name.to_string(), Deprecation::default()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notice() {
+        let full = Deprecation {
+            since: Some("1.2.0".to_string()),
+            replacement: Some("add_v2".to_string()),
+            message: Some("Removed in 2.0".to_string()),
+        };
+        assert_eq!(
+            full.notice(),
+            "Deprecated since 1.2.0: use add_v2 instead; Removed in 2.0"
+        );
+        assert_eq!(Deprecation::default().notice(), "Deprecated");
+    }
+
+    #[test]
+    fn test_parse_tools() {
+        let tools = parse_tools(r#"{"add": {"since": "1.2.0", "replacement": "add_v2"}}"#);
+        assert_eq!(tools["add"].replacement.as_deref(), Some("add_v2"));
+
+        let tools = parse_tools("add, subtract,");
+        assert_eq!(tools.len(), 2);
+        assert!(tools["subtract"].since.is_none());
+
+        assert!(parse_tools("{not json").is_empty());
+    }
+}
@@ -0,0 +1,171 @@
+//! Tool Deprecation Middleware Component
+//!
+//! Lets server authors retire tools gradually instead of removing them. For
+//! every tool listed in `WASMCP_DEPRECATED_TOOLS`, this component:
+//! - Adds a `deprecated` entry (since, replacement, message) to the tool's
+//!   `_meta` in tools/list results
+//! - Prefixes the description with a deprecation notice and marks the title,
+//!   so clients and models see it without reading `_meta`
+//! - Optionally sends a warning `notifications/message` on every call, while
+//!   still passing the call downstream
+//! - Delegates everything else downstream unchanged
+
+#![allow(warnings)]
+
+mod bindings {
+    wit_bindgen::generate!({
+        world: "tool-deprecation",
+        generate_all,
+    });
+}
+
+mod config;
+
+use bindings::exports::wasmcp::mcp_v20251125::server_handler::{Guest, MessageContext};
+use bindings::wasmcp::mcp_v20251125::mcp::*;
+use bindings::wasmcp::mcp_v20251125::server_handler as downstream;
+use bindings::wasmcp::mcp_v20251125::server_io;
+use config::{Deprecation, DeprecationConfig};
+use serde_json::{Map, Value};
+
+/// Logger name on deprecation warnings
+const LOGGER: &str = "tool-deprecation";
+
+struct ToolDeprecation;
+
+// Convert exported MessageContext to imported MessageContext
+fn to_downstream_ctx<'a>(ctx: &'a MessageContext<'a>) -> downstream::MessageContext<'a> {
+    downstream::MessageContext {
+        client_stream: ctx.client_stream,
+        protocol_version: ctx.protocol_version.clone(),
+        session: ctx.session.as_ref().map(|s| downstream::Session {
+            session_id: s.session_id.clone(),
+            store_id: s.store_id.clone(),
+        }),
+        identity: ctx.identity.as_ref().map(|i| downstream::Identity {
+            jwt: i.jwt.clone(),
+            claims: i.claims.clone(),
+        }),
+        frame: ctx.frame.clone(),
+        http_context: ctx.http_context.clone(),
+        client_capabilities: ctx.client_capabilities.clone(),
+        request_id: ctx.request_id.clone(),
+        deadline: ctx.deadline,
+        log_level: ctx.log_level,
+        client_info: ctx.client_info.clone(),
+        locale: ctx.locale.clone(),
+        trace_context: ctx.trace_context.clone(),
+    }
+}
+
+impl Guest for ToolDeprecation {
+    fn handle(
+        ctx: MessageContext,
+        message: ClientMessage,
+    ) -> Option<Result<ServerResult, ErrorCode>> {
+        let ClientMessage::Request((request_id, request)) = message else {
+            // Forward notifications, results, errors to downstream
+            return downstream::handle(&to_downstream_ctx(&ctx), message);
+        };
+
+        let config = DeprecationConfig::from_env();
+        if let ClientRequest::ToolsCall(call) = &request {
+            if let Some(deprecation) = config.deprecation(&call.name) {
+                if config.warn {
+                    warn(&ctx, &call.name, deprecation);
+                }
+            }
+        }
+
+        let is_list = matches!(request, ClientRequest::ToolsList(_));
+        let downstream_msg = ClientMessage::Request((request_id, request));
+        let response = downstream::handle(&to_downstream_ctx(&ctx), downstream_msg);
+
+        match response {
+            Some(Ok(ServerResult::ToolsList(mut list))) if is_list => {
+                for tool in &mut list.tools {
+                    if let Some(deprecation) = config.deprecation(&tool.name) {
+                        mark_deprecated(tool, deprecation);
+                    }
+                }
+                Some(Ok(ServerResult::ToolsList(list)))
+            }
+            other => other,
+        }
+    }
+}
+
+/// Surface a deprecation in a listed tool's metadata, description and title
+fn mark_deprecated(tool: &mut Tool, deprecation: &Deprecation) {
+    let notice = deprecation.notice();
+    let options = tool.options.get_or_insert_with(|| ToolOptions {
+        meta: None,
+        annotations: None,
+        description: None,
+        output_schema: None,
+        title: None,
+        icons: None,
+    });
+
+    options.meta = Some(with_deprecation(options.meta.as_deref(), deprecation));
+    options.description = Some(match options.description.take() {
+        Some(description) if !description.is_empty() => format!("{}. {}", notice, description),
+        _ => notice,
+    });
+
+    let titles = options
+        .annotations
+        .as_mut()
+        .and_then(|a| a.title.as_mut())
+        .into_iter()
+        .chain(options.title.as_mut());
+    for title in titles {
+        title.push_str(" (deprecated)");
+    }
+}
+
+/// Add a `deprecated` entry to a tool's `_meta`, keeping the existing entries
+fn with_deprecation(meta: Option<&str>, deprecation: &Deprecation) -> String {
+    let mut object = meta
+        .and_then(|m| serde_json::from_str::<Map<String, Value>>(m).ok())
+        .unwrap_or_default();
+    object.insert(
+        "deprecated".to_string(),
+        serde_json::to_value(deprecation).unwrap_or(Value::Bool(true)),
+    );
+    Value::Object(object).to_string()
+}
+
+/// Send a warning log notification for a call to a deprecated tool
+///
+/// Skipped when the client asked for a less verbose level with
+/// logging/setLevel. A failed send never fails the call.
+fn warn(ctx: &MessageContext, tool: &str, deprecation: &Deprecation) {
+    if matches!(
+        ctx.log_level,
+        Some(LogLevel::Error | LogLevel::Critical | LogLevel::Alert | LogLevel::Emergency)
+    ) {
+        return;
+    }
+    let Some(stream) = ctx.client_stream else {
+        return;
+    };
+
+    let notification = ServerNotification::Log(LoggingMessageNotification {
+        data: format!("{}: {}", tool, deprecation.notice()),
+        level: LogLevel::Warning,
+        logger: Some(LOGGER.to_string()),
+    });
+    if let Err(e) = server_io::send_message(
+        stream,
+        ServerMessage::Notification(notification),
+        &ctx.frame,
+    ) {
+        eprintln!(
+            "[tool-deprecation] Failed to send deprecation warning: {:?}",
+            e
+        );
+    }
+}
+
+bindings::export!(ToolDeprecation with_types_in bindings);
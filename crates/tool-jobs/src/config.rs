@@ -0,0 +1,76 @@
+//! Configuration for the tool jobs middleware
+//!
+//! Environment variables:
+//! - `WASMCP_QUEUED_TOOLS`: Comma-separated tools whose calls are queued as
+//!   jobs. With none listed, the component passes everything through.
+//! - `WASMCP_TOOL_JOBS_BUCKET`: KV bucket for jobs (default: "")
+//! - `WASMCP_TOOL_JOBS_TTL`: Seconds a job and its result are kept after
+//!   it is queued (default: 3600)
+
+use crate::bindings::wasi::cli::environment::get_environment;
+use std::collections::{HashMap, HashSet};
+
+const DEFAULT_TTL_SECS: u64 = 60 * 60;
+
+/// Tool jobs configuration from environment variables
+#[derive(Debug, Clone)]
+pub struct ToolJobsConfig {
+    pub queued: HashSet<String>,
+    pub bucket: String,
+    /// Lifetime of a job in seconds
+    pub ttl: u64,
+}
+
+impl ToolJobsConfig {
+    /// Load configuration from environment variables
+    pub fn from_env() -> Self {
+        let env_map: HashMap<String, String> = get_environment().into_iter().collect();
+
+        let queued = env_map
+            .get("WASMCP_QUEUED_TOOLS")
+            .map(|v| parse_tools(v))
+            .unwrap_or_default();
+
+        let bucket = env_map
+            .get("WASMCP_TOOL_JOBS_BUCKET")
+            .cloned()
+            .unwrap_or_default();
+
+        let ttl = match env_map.get("WASMCP_TOOL_JOBS_TTL") {
+            None => DEFAULT_TTL_SECS,
+            Some(v) => v
+                .trim()
+                .parse::<u64>()
+                .ok()
+                .filter(|&n| n > 0)
+                .unwrap_or_else(|| {
+                    eprintln!(
+                        "[tool-jobs] WARNING: Invalid WASMCP_TOOL_JOBS_TTL='{}', defaulting to {}",
+                        v, DEFAULT_TTL_SECS
+                    );
+                    DEFAULT_TTL_SECS
+                }),
+        };
+
+        ToolJobsConfig {
+            queued,
+            bucket,
+            ttl,
+        }
+    }
+
+    /// Whether calls to a tool are queued
+    pub fn is_queued(&self, tool: &str) -> bool {
+        self.queued.contains(tool)
+    }
+}
+
+/// Parse a comma-separated tool list
+pub fn parse_tools(value: &str) -> HashSet<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .map(str::to_string)
+        .collect()
+}
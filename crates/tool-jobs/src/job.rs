@@ -0,0 +1,359 @@
+//! Job records and the results handed to clients
+//!
+//! A job is stored under `wasmcp:job:{id}` as JSON with `stored_at` and
+//! `expires_at` in Unix seconds, the shape the maintenance component's
+//! `kv-ttl` job sweeps. It holds the call to run and, once it ran, the
+//! result. Results keep their text content, structured content and
+//! `_meta`; a result with other content (images, resources, streams) fails
+//! the job instead, since it can't be stored.
+
+use crate::bindings::wasmcp::mcp_v20251125::mcp::{
+    CallToolResult, ContentBlock, ContentOptions, TextContent, TextData, Tool, ToolOptions,
+};
+use serde_json::{Map, Value, json};
+
+/// Prefix of every key this component writes
+pub const KEY_PREFIX: &str = "wasmcp:job:";
+
+/// The built-in tool clients poll jobs with
+pub const STATUS_TOOL: &str = "job_status";
+
+/// `_meta` field marking queued tools and carrying job state in results
+pub const META_FIELD: &str = "wasmcp/job";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Queued,
+    Running,
+    Completed,
+    Failed,
+}
+
+impl Status {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Status::Queued => "queued",
+            Status::Running => "running",
+            Status::Completed => "completed",
+            Status::Failed => "failed",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "queued" => Some(Status::Queued),
+            "running" => Some(Status::Running),
+            "completed" => Some(Status::Completed),
+            "failed" => Some(Status::Failed),
+            _ => None,
+        }
+    }
+}
+
+/// A queued tool call
+#[derive(Debug, Clone, PartialEq)]
+pub struct Job {
+    pub id: String,
+    pub tool: String,
+    pub arguments: Option<String>,
+    /// Caller scope allowed to poll the job
+    pub owner: String,
+    /// Session that queued the job, notified when it finishes
+    pub session_id: Option<String>,
+    pub status: Status,
+    pub stored_at: u64,
+    pub expires_at: u64,
+    /// The stored result once completed, the error message once failed
+    pub result: Option<Value>,
+    pub error: Option<String>,
+}
+
+impl Job {
+    pub fn key(&self) -> String {
+        key(&self.id)
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "id": self.id,
+            "tool": self.tool,
+            "arguments": self.arguments,
+            "owner": self.owner,
+            "session": self.session_id,
+            "status": self.status.as_str(),
+            "stored_at": self.stored_at,
+            "expires_at": self.expires_at,
+            "result": self.result,
+            "error": self.error,
+        })
+    }
+
+    /// The job in a stored entry, unless expired
+    pub fn from_json(entry: &Value, now: u64) -> Option<Self> {
+        let expires_at = entry["expires_at"].as_u64()?;
+        if now >= expires_at {
+            return None;
+        }
+        let string = |field: &str| entry[field].as_str().map(str::to_string);
+        Some(Job {
+            id: string("id")?,
+            tool: string("tool")?,
+            arguments: string("arguments"),
+            owner: string("owner")?,
+            session_id: string("session"),
+            status: Status::parse(entry["status"].as_str()?)?,
+            stored_at: entry["stored_at"].as_u64()?,
+            expires_at,
+            result: entry.get("result").filter(|r| !r.is_null()).cloned(),
+            error: string("error"),
+        })
+    }
+}
+
+/// Key of a job's entry
+pub fn key(id: &str) -> String {
+    format!("{}{}", KEY_PREFIX, id)
+}
+
+/// Key of the counter a runner increments to claim a job
+pub fn claim_key(id: &str) -> String {
+    format!("{}{}:claim", KEY_PREFIX, id)
+}
+
+/// The `jobId` argument of a `job_status` call
+pub fn job_id_argument(arguments: Option<&str>) -> Option<String> {
+    let value: Value = serde_json::from_str(arguments?).ok()?;
+    value["jobId"].as_str().map(str::to_string)
+}
+
+/// Definition of the `job_status` tool
+pub fn status_tool() -> Tool {
+    Tool {
+        name: STATUS_TOOL.to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "jobId": { "type": "string", "description": "ID returned when the job was queued" }
+            },
+            "required": ["jobId"]
+        })
+        .to_string(),
+        options: Some(ToolOptions {
+            meta: None,
+            annotations: None,
+            description: Some(
+                "Get the status of a queued tool call, running it if it hasn't started. \
+                 Returns the tool's result once the job has completed."
+                    .to_string(),
+            ),
+            output_schema: None,
+            title: Some("Job status".to_string()),
+            icons: None,
+        }),
+    }
+}
+
+/// Mark a queued tool in a tool list, so clients expect a job handle
+pub fn mark_queued(tool: &mut Tool) {
+    let options = tool.options.get_or_insert_with(|| ToolOptions {
+        meta: None,
+        annotations: None,
+        description: None,
+        output_schema: None,
+        title: None,
+        icons: None,
+    });
+    options.meta = Some(with_meta_field(options.meta.as_deref(), json!(true)));
+    let note = format!(
+        "Runs as a job: returns a jobId to poll with {}.",
+        STATUS_TOOL
+    );
+    options.description = Some(match options.description.take() {
+        Some(description) => format!("{} {}", description, note),
+        None => note,
+    });
+}
+
+/// Result of a call to a queued tool, or a poll of an unfinished job
+pub fn status_result(job: &Job) -> CallToolResult {
+    let text = match job.status {
+        Status::Queued => format!(
+            "Job {} queued. Call {} with {{\"jobId\": \"{}\"}} for its result.",
+            job.id, STATUS_TOOL, job.id
+        ),
+        _ => format!("Job {} is {}.", job.id, job.status.as_str()),
+    };
+    CallToolResult {
+        meta: Some(with_meta_field(None, job_meta(job))),
+        content: vec![text_block(text, None)],
+        is_error: None,
+        structured_content: Some(job_meta(job).to_string()),
+    }
+}
+
+/// Stored form of a tool's result, or why it can't be stored
+pub fn encode_result(result: &CallToolResult) -> Result<Value, String> {
+    let mut content = Vec::with_capacity(result.content.len());
+    for block in &result.content {
+        let ContentBlock::Text(TextContent {
+            text: TextData::Text(text),
+            options,
+        }) = block
+        else {
+            return Err("Result has content other than inline text".to_string());
+        };
+        let meta = options.as_ref().and_then(|o| o.meta.clone());
+        content.push(json!({ "text": text, "meta": meta }));
+    }
+    Ok(json!({
+        "content": content,
+        "isError": result.is_error,
+        "structuredContent": result.structured_content,
+        "meta": result.meta,
+    }))
+}
+
+/// Result of a poll of a finished job
+///
+/// A completed job returns the tool's own result, a failed one an error
+/// result; both carry the job in `_meta`.
+pub fn finished_result(job: &Job) -> CallToolResult {
+    let stored = job
+        .result
+        .as_ref()
+        .filter(|_| job.status == Status::Completed);
+    let Some(stored) = stored else {
+        let message = job.error.as_deref().unwrap_or("Job failed");
+        return CallToolResult {
+            meta: Some(with_meta_field(None, job_meta(job))),
+            content: vec![text_block(
+                format!("Job {} failed: {}", job.id, message),
+                None,
+            )],
+            is_error: Some(true),
+            structured_content: None,
+        };
+    };
+
+    let content = stored["content"]
+        .as_array()
+        .map(|blocks| {
+            blocks
+                .iter()
+                .filter_map(|block| {
+                    let text = block["text"].as_str()?.to_string();
+                    Some(text_block(text, block["meta"].as_str().map(str::to_string)))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    CallToolResult {
+        meta: Some(with_meta_field(stored["meta"].as_str(), job_meta(job))),
+        content,
+        is_error: stored["isError"].as_bool(),
+        structured_content: stored["structuredContent"].as_str().map(str::to_string),
+    }
+}
+
+/// The job as clients see it: ID, tool and status
+pub fn job_meta(job: &Job) -> Value {
+    json!({ "jobId": job.id, "tool": job.tool, "status": job.status.as_str() })
+}
+
+fn text_block(text: String, meta: Option<String>) -> ContentBlock {
+    ContentBlock::Text(TextContent {
+        text: TextData::Text(text),
+        options: meta.map(|meta| ContentOptions {
+            annotations: None,
+            meta: Some(meta),
+        }),
+    })
+}
+
+/// `_meta` with this component's field set, keeping the other fields
+fn with_meta_field(meta: Option<&str>, value: Value) -> String {
+    let mut object = meta
+        .and_then(|m| serde_json::from_str::<Map<String, Value>>(m).ok())
+        .unwrap_or_default();
+    object.insert(META_FIELD.to_string(), value);
+    Value::Object(object).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(status: Status) -> Job {
+        Job {
+            id: "0a1b".to_string(),
+            tool: "export".to_string(),
+            arguments: Some(r#"{"table":"orders"}"#.to_string()),
+            owner: "anonymous".to_string(),
+            session_id: Some("s1".to_string()),
+            status,
+            stored_at: 1_000,
+            expires_at: 4_600,
+            result: None,
+            error: None,
+        }
+    }
+
+    fn text_result(text: &str) -> CallToolResult {
+        CallToolResult {
+            meta: Some(r#"{"trace":"x"}"#.to_string()),
+            content: vec![text_block(text.to_string(), None)],
+            is_error: None,
+            structured_content: Some(r#"{"rows":3}"#.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut job = job(Status::Completed);
+        job.result = Some(encode_result(&text_result("3 rows")).unwrap());
+
+        let restored = Job::from_json(&job.to_json(), 1_500).unwrap();
+        assert_eq!(restored, job);
+        assert!(Job::from_json(&job.to_json(), 4_600).is_none());
+
+        let result = finished_result(&restored);
+        assert_eq!(result.structured_content.as_deref(), Some(r#"{"rows":3}"#));
+        let meta: Value = serde_json::from_str(result.meta.as_deref().unwrap()).unwrap();
+        assert_eq!(meta["trace"], "x");
+        assert_eq!(meta[META_FIELD]["status"], "completed");
+    }
+
+    #[test]
+    fn test_results_for_clients() {
+        let queued = status_result(&job(Status::Queued));
+        let structured: Value =
+            serde_json::from_str(queued.structured_content.as_deref().unwrap()).unwrap();
+        assert_eq!(structured["jobId"], "0a1b");
+        assert_eq!(structured["status"], "queued");
+
+        let mut failed = job(Status::Failed);
+        failed.error = Some("table locked".to_string());
+        let result = finished_result(&failed);
+        assert_eq!(result.is_error, Some(true));
+
+        let mut blob = text_result("a");
+        blob.content.push(ContentBlock::ResourceLink(
+            crate::bindings::wasmcp::mcp_v20251125::mcp::ResourceLinkContent {
+                uri: "file:///a".to_string(),
+                name: "a".to_string(),
+                options: None,
+            },
+        ));
+        assert!(encode_result(&blob).is_err());
+    }
+
+    #[test]
+    fn test_job_id_argument() {
+        assert_eq!(
+            job_id_argument(Some(r#"{"jobId":"0a1b"}"#)).as_deref(),
+            Some("0a1b")
+        );
+        assert_eq!(job_id_argument(Some(r#"{"jobId":7}"#)), None);
+        assert_eq!(job_id_argument(None), None);
+    }
+}
@@ -0,0 +1,283 @@
+//! Tool Jobs Middleware Component
+//!
+//! Queues calls to long-running tools instead of holding the request open.
+//! For every tool listed in `WASMCP_QUEUED_TOOLS`, this component:
+//! - Answers a call at once with a job handle: `{"jobId", "tool", "status"}`
+//!   as structured content, with the call stored in wasmcp:keyvalue
+//! - Runs the job on a follow-up invocation: the first `job_status` call for
+//!   it claims the job, forwards the stored call downstream and stores the
+//!   result. Later polls, from any instance, return the stored result.
+//! - Notifies the session that queued the job when it finishes, through its
+//!   outbox, so a client polling from elsewhere (a worker, another
+//!   connection) lets the session's listening stream know
+//! - Adds `job_status` to the tool list and marks queued tools with
+//!   `_meta["wasmcp/job"]`
+//! - Delegates everything else downstream unchanged
+//!
+//! Jobs belong to the caller that queued them: the authenticated subject,
+//! else the session. Other callers get "Unknown job".
+
+#![allow(warnings)]
+
+mod bindings {
+    wit_bindgen::generate!({
+        world: "tool-jobs",
+        generate_all,
+    });
+}
+
+mod config;
+mod job;
+
+use bindings::exports::wasmcp::mcp_v20251125::server_handler::{Guest, MessageContext};
+use bindings::wasi::random::random::get_random_bytes;
+use bindings::wasmcp::keyvalue::store::{self as kv, Bucket};
+use bindings::wasmcp::mcp_v20251125::mcp::*;
+use bindings::wasmcp::mcp_v20251125::server_handler as downstream;
+use bindings::wasmcp::mcp_v20251125::server_io;
+use config::ToolJobsConfig;
+use job::{Job, Status};
+use serde_json::Value;
+use std::time::{SystemTime, UNIX_EPOCH};
+use wasmcp_errors::McpError;
+
+wasmcp_errors::impl_error_code!(ErrorCode, Error);
+
+/// Scope of callers with neither identity nor session
+const ANONYMOUS_SCOPE: &str = "anonymous";
+
+/// Logger name of completion notifications
+const LOGGER: &str = "wasmcp/jobs";
+
+struct ToolJobs;
+
+// Convert exported MessageContext to imported MessageContext
+fn to_downstream_ctx<'a>(ctx: &'a MessageContext<'a>) -> downstream::MessageContext<'a> {
+    downstream::MessageContext {
+        client_stream: ctx.client_stream,
+        protocol_version: ctx.protocol_version.clone(),
+        session: ctx.session.as_ref().map(|s| downstream::Session {
+            session_id: s.session_id.clone(),
+            store_id: s.store_id.clone(),
+        }),
+        identity: ctx.identity.as_ref().map(|i| downstream::Identity {
+            jwt: i.jwt.clone(),
+            claims: i.claims.clone(),
+        }),
+        frame: ctx.frame.clone(),
+        http_context: ctx.http_context.clone(),
+        client_capabilities: ctx.client_capabilities.clone(),
+        request_id: ctx.request_id.clone(),
+        deadline: ctx.deadline,
+        log_level: ctx.log_level,
+        client_info: ctx.client_info.clone(),
+        locale: ctx.locale.clone(),
+        trace_context: ctx.trace_context.clone(),
+    }
+}
+
+impl Guest for ToolJobs {
+    fn handle(
+        ctx: MessageContext,
+        message: ClientMessage,
+    ) -> Option<Result<ServerResult, ErrorCode>> {
+        let ClientMessage::Request((request_id, request)) = message else {
+            // Forward notifications, results, errors to downstream
+            return downstream::handle(&to_downstream_ctx(&ctx), message);
+        };
+
+        let config = ToolJobsConfig::from_env();
+        if config.queued.is_empty() {
+            return delegate(&ctx, request_id, request);
+        }
+
+        match request {
+            ClientRequest::ToolsCall(call) if call.name == job::STATUS_TOOL => {
+                Some(handle_status(&ctx, &config, request_id, call))
+            }
+            ClientRequest::ToolsCall(call) if config.is_queued(&call.name) => {
+                Some(enqueue(&ctx, &config, call))
+            }
+            ClientRequest::ToolsList(list_req) => {
+                let first_page = list_req.cursor.is_none();
+                let mut response = delegate(&ctx, request_id, ClientRequest::ToolsList(list_req));
+                if let Some(Ok(ServerResult::ToolsList(result))) = &mut response {
+                    for tool in result.tools.iter_mut() {
+                        if config.is_queued(&tool.name) {
+                            job::mark_queued(tool);
+                        }
+                    }
+                    if first_page {
+                        result.tools.push(job::status_tool());
+                    }
+                }
+                response
+            }
+            _ => delegate(&ctx, request_id, request),
+        }
+    }
+}
+
+fn delegate(
+    ctx: &MessageContext,
+    request_id: RequestId,
+    request: ClientRequest,
+) -> Option<Result<ServerResult, ErrorCode>> {
+    let downstream_msg = ClientMessage::Request((request_id, request));
+    downstream::handle(&to_downstream_ctx(ctx), downstream_msg)
+}
+
+/// Store a call to a queued tool and answer with its job handle
+fn enqueue(
+    ctx: &MessageContext,
+    config: &ToolJobsConfig,
+    call: CallToolRequest,
+) -> Result<ServerResult, ErrorCode> {
+    let bucket = open(config)?;
+    let now = now_secs();
+    let job = Job {
+        id: hex::encode(get_random_bytes(16)),
+        tool: call.name,
+        arguments: call.arguments,
+        owner: scope(ctx),
+        session_id: ctx.session.as_ref().map(|s| s.session_id.clone()),
+        status: Status::Queued,
+        stored_at: now,
+        expires_at: now.saturating_add(config.ttl),
+        result: None,
+        error: None,
+    };
+    save(&bucket, &job)?;
+    Ok(ServerResult::ToolsCall(job::status_result(&job)))
+}
+
+/// Report a job, running it first if nobody has yet
+fn handle_status(
+    ctx: &MessageContext,
+    config: &ToolJobsConfig,
+    request_id: RequestId,
+    call: CallToolRequest,
+) -> Result<ServerResult, ErrorCode> {
+    let id = job::job_id_argument(call.arguments.as_deref())
+        .ok_or_else(|| ErrorCode::from(McpError::invalid_params("Missing jobId")))?;
+    let bucket = open(config)?;
+    let unknown = || ErrorCode::from(McpError::invalid_params(format!("Unknown job: {}", id)));
+
+    let mut job = bucket
+        .get_json(&job::key(&id))
+        .map_err(|e| McpError::internal(format!("Failed to read job: {}", e)))?
+        .and_then(|json| serde_json::from_str::<Value>(&json).ok())
+        .and_then(|entry| Job::from_json(&entry, now_secs()))
+        .ok_or_else(unknown)?;
+    if job.owner != scope(ctx) {
+        return Err(unknown());
+    }
+
+    // The first poller to claim a queued job runs it; others see it running
+    if job.status == Status::Queued {
+        let claimed = bucket
+            .increment(&job::claim_key(&job.id), 1)
+            .map_err(|e| McpError::internal(format!("Failed to claim job: {}", e)))?;
+        if claimed != 1 {
+            job.status = Status::Running;
+            return Ok(ServerResult::ToolsCall(job::status_result(&job)));
+        }
+        run(ctx, &bucket, request_id, &mut job)?;
+    }
+
+    Ok(ServerResult::ToolsCall(match job.status {
+        Status::Completed | Status::Failed => job::finished_result(&job),
+        _ => job::status_result(&job),
+    }))
+}
+
+/// Run a claimed job downstream and store its outcome
+fn run(
+    ctx: &MessageContext,
+    bucket: &Bucket,
+    request_id: RequestId,
+    job: &mut Job,
+) -> Result<(), ErrorCode> {
+    job.status = Status::Running;
+    save(bucket, job)?;
+
+    let call = ClientRequest::ToolsCall(CallToolRequest {
+        name: job.tool.clone(),
+        arguments: job.arguments.clone(),
+    });
+    let outcome = match delegate(ctx, request_id, call) {
+        Some(Ok(ServerResult::ToolsCall(result))) => job::encode_result(&result),
+        Some(Ok(_)) => Err("Unexpected result type".to_string()),
+        Some(Err(e)) => Err(McpError::from(&e).message),
+        None => Err("Handler returned None for request".to_string()),
+    };
+    match outcome {
+        Ok(result) => {
+            job.status = Status::Completed;
+            job.result = Some(result);
+        }
+        Err(e) => {
+            job.status = Status::Failed;
+            job.error = Some(e);
+        }
+    }
+    save(bucket, job)?;
+    let _ = bucket.delete(&job::claim_key(&job.id));
+    notify(job);
+    Ok(())
+}
+
+/// Tell the session that queued a job that it finished
+///
+/// Delivery is best effort: the session may be gone, or have no listening
+/// stream until it next polls anyway.
+fn notify(job: &Job) {
+    let Some(session_id) = &job.session_id else {
+        return;
+    };
+    let notification = ServerNotification::Log(LoggingMessageNotification {
+        data: job::job_meta(job).to_string(),
+        level: LogLevel::Info,
+        logger: Some(LOGGER.to_string()),
+    });
+    if let Err(e) = server_io::queue_message(session_id, ServerMessage::Notification(notification))
+    {
+        eprintln!("[tool-jobs] WARNING: Failed to notify session: {:?}", e);
+    }
+}
+
+fn save(bucket: &Bucket, job: &Job) -> Result<(), ErrorCode> {
+    bucket
+        .set_json(&job.key(), &job.to_json().to_string())
+        .map_err(|e| McpError::internal(format!("Failed to store job: {}", e)).into())
+}
+
+fn open(config: &ToolJobsConfig) -> Result<Bucket, ErrorCode> {
+    kv::open(&config.bucket)
+        .map_err(|e| McpError::internal(format!("Failed to open bucket: {}", e)).into())
+}
+
+/// Caller scope a job belongs to
+///
+/// Authenticated callers own their jobs across sessions, so a worker with
+/// the same identity can run them; others only within their session.
+fn scope(ctx: &MessageContext) -> String {
+    match (&ctx.identity, &ctx.session) {
+        (Some(identity), _) => format!(
+            "{}|{}",
+            identity.claims.issuer.as_deref().unwrap_or_default(),
+            identity.claims.subject
+        ),
+        (None, Some(session)) => format!("session:{}", session.session_id),
+        (None, None) => ANONYMOUS_SCOPE.to_string(),
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+bindings::export!(ToolJobs with_types_in bindings);
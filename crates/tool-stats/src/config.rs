@@ -0,0 +1,48 @@
+//! Configuration for the tool statistics middleware
+//!
+//! Environment variables:
+//! - `WASMCP_TOOL_STATS_BUCKET`: KV bucket for the counters (default: "")
+//! - `WASMCP_TOOL_SLOW_MS`: Calls taking longer than this send a warning log
+//!   notification (default: 1000, 0 disables warnings)
+
+use crate::bindings::wasi::cli::environment::get_environment;
+use std::collections::HashMap;
+
+const DEFAULT_SLOW_MS: u64 = 1000;
+
+/// Tool statistics configuration from environment variables
+#[derive(Debug, Clone)]
+pub struct ToolStatsConfig {
+    pub bucket: String,
+    /// Slow-call threshold in milliseconds (None disables warnings)
+    pub slow_ms: Option<u64>,
+}
+
+impl ToolStatsConfig {
+    /// Load configuration from environment variables
+    pub fn from_env() -> Self {
+        let env_map: HashMap<String, String> = get_environment().into_iter().collect();
+
+        let bucket = env_map
+            .get("WASMCP_TOOL_STATS_BUCKET")
+            .cloned()
+            .unwrap_or_default();
+
+        let slow_ms = match env_map.get("WASMCP_TOOL_SLOW_MS") {
+            None => Some(DEFAULT_SLOW_MS),
+            Some(v) => match v.trim().parse::<u64>() {
+                Ok(0) => None,
+                Ok(ms) => Some(ms),
+                Err(_) => {
+                    eprintln!(
+                        "[tool-stats] WARNING: Invalid WASMCP_TOOL_SLOW_MS='{}', defaulting to {}",
+                        v, DEFAULT_SLOW_MS
+                    );
+                    Some(DEFAULT_SLOW_MS)
+                }
+            },
+        };
+
+        ToolStatsConfig { bucket, slow_ms }
+    }
+}
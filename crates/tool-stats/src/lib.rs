@@ -0,0 +1,240 @@
+//! Tool Statistics Middleware Component
+//!
+//! Measures the tool calls of the handlers composed behind it. This component:
+//! - Times every tools/call and counts calls, failures (protocol errors and
+//!   is-error results) and slow calls per tool in wasmcp:keyvalue
+//! - Serves the aggregates as JSON from the `wasmcp://stats` resource
+//! - Sends a warning `notifications/message` when a call takes longer than
+//!   `WASMCP_TOOL_SLOW_MS`
+//! - Delegates all other requests downstream
+
+#![allow(warnings)]
+
+mod bindings {
+    wit_bindgen::generate!({
+        world: "tool-stats",
+        generate_all,
+    });
+}
+
+mod config;
+mod stats;
+
+use bindings::exports::wasmcp::mcp_v20251125::server_handler::{Guest, MessageContext};
+use bindings::wasi::clocks::monotonic_clock;
+use bindings::wasmcp::keyvalue::store as kv;
+use bindings::wasmcp::mcp_v20251125::mcp::*;
+use bindings::wasmcp::mcp_v20251125::server_handler as downstream;
+use bindings::wasmcp::mcp_v20251125::server_io;
+use config::ToolStatsConfig;
+use wasmcp_errors::McpError;
+
+wasmcp_errors::impl_error_code!(ErrorCode, Error);
+
+const STATS_URI: &str = "wasmcp://stats";
+
+/// Logger name on slow-call warnings
+const LOGGER: &str = "tool-stats";
+
+struct ToolStatsMiddleware;
+
+// Convert exported MessageContext to imported MessageContext
+fn to_downstream_ctx<'a>(ctx: &'a MessageContext<'a>) -> downstream::MessageContext<'a> {
+    downstream::MessageContext {
+        client_stream: ctx.client_stream,
+        protocol_version: ctx.protocol_version.clone(),
+        session: ctx.session.as_ref().map(|s| downstream::Session {
+            session_id: s.session_id.clone(),
+            store_id: s.store_id.clone(),
+        }),
+        identity: ctx.identity.as_ref().map(|i| downstream::Identity {
+            jwt: i.jwt.clone(),
+            claims: i.claims.clone(),
+        }),
+        frame: ctx.frame.clone(),
+        http_context: ctx.http_context.clone(),
+        client_capabilities: ctx.client_capabilities.clone(),
+        request_id: ctx.request_id.clone(),
+        deadline: ctx.deadline,
+        log_level: ctx.log_level,
+        client_info: ctx.client_info.clone(),
+        locale: ctx.locale.clone(),
+        trace_context: ctx.trace_context.clone(),
+    }
+}
+
+impl Guest for ToolStatsMiddleware {
+    fn handle(
+        ctx: MessageContext,
+        message: ClientMessage,
+    ) -> Option<Result<ServerResult, ErrorCode>> {
+        let config = ToolStatsConfig::from_env();
+
+        match message {
+            ClientMessage::Request((request_id, request)) => {
+                let result = match &request {
+                    ClientRequest::ToolsCall(call) => {
+                        let tool = call.name.clone();
+                        return handle_tools_call(&ctx, &config, &tool, request_id, request);
+                    }
+                    // Our resource is on the first page
+                    ClientRequest::ResourcesList(list_req) if list_req.cursor.is_none() => {
+                        handle_resources_list(request_id.clone(), &ctx)
+                    }
+                    ClientRequest::ResourcesRead(read_req) if read_req.uri == STATS_URI => {
+                        handle_stats_read(&config)
+                    }
+                    _ => return delegate(&ctx, request_id, request),
+                };
+                Some(result)
+            }
+            _ => {
+                // Forward notifications, results, errors to downstream
+                downstream::handle(&to_downstream_ctx(&ctx), message)
+            }
+        }
+    }
+}
+
+fn delegate(
+    ctx: &MessageContext,
+    request_id: RequestId,
+    request: ClientRequest,
+) -> Option<Result<ServerResult, ErrorCode>> {
+    let downstream_msg = ClientMessage::Request((request_id, request));
+    downstream::handle(&to_downstream_ctx(ctx), downstream_msg)
+}
+
+/// Forward a tool call and record its duration and outcome
+fn handle_tools_call(
+    ctx: &MessageContext,
+    config: &ToolStatsConfig,
+    tool: &str,
+    request_id: RequestId,
+    request: ClientRequest,
+) -> Option<Result<ServerResult, ErrorCode>> {
+    let started = monotonic_clock::now();
+    let response = delegate(ctx, request_id, request);
+    let elapsed_us = monotonic_clock::now().saturating_sub(started) / 1_000;
+
+    let failed = match &response {
+        Some(Ok(ServerResult::ToolsCall(result))) => result.is_error == Some(true),
+        Some(Ok(_)) => false,
+        Some(Err(_)) | None => true,
+    };
+    let slow = config
+        .slow_ms
+        .is_some_and(|ms| elapsed_us > ms.saturating_mul(1_000));
+
+    match kv::open(&config.bucket) {
+        Ok(bucket) => stats::record(&bucket, tool, elapsed_us, failed, slow),
+        Err(e) => eprintln!("[tool-stats] Failed to open bucket: {:?}", e),
+    }
+    if slow {
+        warn_slow(
+            ctx,
+            tool,
+            elapsed_us / 1_000,
+            config.slow_ms.unwrap_or_default(),
+        );
+    }
+
+    response
+}
+
+fn handle_resources_list(
+    request_id: RequestId,
+    ctx: &MessageContext,
+) -> Result<ServerResult, ErrorCode> {
+    let mut resources = vec![stats_resource()];
+
+    let downstream_req = ClientRequest::ResourcesList(ListResourcesRequest { cursor: None });
+    let downstream_msg = ClientMessage::Request((request_id, downstream_req));
+    let next_cursor = match downstream::handle(&to_downstream_ctx(ctx), downstream_msg) {
+        Some(Ok(ServerResult::ResourcesList(downstream_result))) => {
+            resources.extend(downstream_result.resources);
+            downstream_result.next_cursor
+        }
+        Some(Err(ErrorCode::MethodNotFound(_))) | None => None,
+        Some(Err(e)) => return Err(e),
+        Some(Ok(_)) => None,
+    };
+
+    Ok(ServerResult::ResourcesList(ListResourcesResult {
+        resources,
+        next_cursor,
+        meta: None,
+    }))
+}
+
+fn handle_stats_read(config: &ToolStatsConfig) -> Result<ServerResult, ErrorCode> {
+    let bucket = kv::open(&config.bucket)
+        .map_err(|e| McpError::internal(format!("Failed to open bucket: {:?}", e)))?;
+    let stats = stats::load(&bucket).map_err(McpError::internal)?;
+    let text = serde_json::to_string_pretty(&stats::to_json(&stats, config.slow_ms))
+        .map_err(|e| McpError::internal(e.to_string()))?;
+
+    Ok(ServerResult::ResourcesRead(ReadResourceResult {
+        contents: vec![ResourceContents::Text(TextResourceContents {
+            uri: STATS_URI.to_string(),
+            text: TextData::Text(text),
+            options: Some(EmbeddedResourceOptions {
+                mime_type: Some("application/json".to_string()),
+                meta: None,
+            }),
+        })],
+        meta: None,
+    }))
+}
+
+fn stats_resource() -> McpResource {
+    McpResource {
+        uri: STATS_URI.to_string(),
+        name: "stats".to_string(),
+        options: Some(ResourceOptions {
+            size: None,
+            title: Some("Tool statistics".to_string()),
+            description: Some(
+                "Call counts, failures, slow calls and durations per tool".to_string(),
+            ),
+            mime_type: Some("application/json".to_string()),
+            annotations: None,
+            meta: None,
+            icons: None,
+        }),
+    }
+}
+
+/// Send a warning log notification for a slow call
+///
+/// Skipped when the client asked for a less verbose level with
+/// logging/setLevel. A failed send never fails the call.
+fn warn_slow(ctx: &MessageContext, tool: &str, elapsed_ms: u64, threshold_ms: u64) {
+    if matches!(
+        ctx.log_level,
+        Some(LogLevel::Error | LogLevel::Critical | LogLevel::Alert | LogLevel::Emergency)
+    ) {
+        return;
+    }
+    let Some(stream) = ctx.client_stream else {
+        return;
+    };
+
+    let notification = ServerNotification::Log(LoggingMessageNotification {
+        data: format!(
+            "Tool {} took {}ms (slow threshold {}ms)",
+            tool, elapsed_ms, threshold_ms
+        ),
+        level: LogLevel::Warning,
+        logger: Some(LOGGER.to_string()),
+    });
+    if let Err(e) = server_io::send_message(
+        stream,
+        ServerMessage::Notification(notification),
+        &ctx.frame,
+    ) {
+        eprintln!("[tool-stats] Failed to send slow-call warning: {:?}", e);
+    }
+}
+
+bindings::export!(ToolStatsMiddleware with_types_in bindings);
@@ -0,0 +1,137 @@
+//! Per-tool counters in the key-value store
+//!
+//! Each HTTP request runs in its own component instance, so the counters
+//! can't live in memory: like the transport's metrics, every value is a
+//! counter updated with atomic `increment`, under
+//! `wasmcp:tool-stats:{tool}:{field}`.
+
+use crate::bindings::wasmcp::keyvalue::store::Bucket;
+use serde_json::{Map, Value, json};
+use std::collections::BTreeMap;
+
+const KEY_PREFIX: &str = "wasmcp:tool-stats:";
+
+/// Aggregates for one tool since the counters were created
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ToolStats {
+    pub calls: i64,
+    pub errors: i64,
+    pub slow: i64,
+    pub duration_us: i64,
+}
+
+/// Count a finished call
+pub fn record(bucket: &Bucket, tool: &str, elapsed_us: u64, failed: bool, slow: bool) {
+    increment(bucket, tool, "calls", 1);
+    increment(bucket, tool, "duration_us", elapsed_us as i64);
+    if failed {
+        increment(bucket, tool, "errors", 1);
+    }
+    if slow {
+        increment(bucket, tool, "slow", 1);
+    }
+}
+
+/// Read the counters of every tool called so far
+pub fn load(bucket: &Bucket) -> Result<BTreeMap<String, ToolStats>, String> {
+    let mut stats: BTreeMap<String, ToolStats> = BTreeMap::new();
+    let mut cursor = None;
+    loop {
+        let page = bucket
+            .list_keys(cursor.as_deref())
+            .map_err(|e| format!("Failed to list keys: {:?}", e))?;
+        for key in &page.keys {
+            let Some((tool, field)) = parse_key(key) else {
+                continue;
+            };
+            // Incrementing by zero reads the counter
+            let value = bucket.increment(key, 0).unwrap_or(0);
+            let entry = stats.entry(tool.to_string()).or_default();
+            match field {
+                "calls" => entry.calls = value,
+                "errors" => entry.errors = value,
+                "slow" => entry.slow = value,
+                "duration_us" => entry.duration_us = value,
+                _ => {}
+            }
+        }
+        match page.cursor {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+    Ok(stats)
+}
+
+/// The `wasmcp://stats` document
+pub fn to_json(stats: &BTreeMap<String, ToolStats>, slow_ms: Option<u64>) -> Value {
+    let tools: Map<String, Value> = stats
+        .iter()
+        .map(|(name, s)| {
+            let total_ms = s.duration_us as f64 / 1000.0;
+            let mean_ms = if s.calls > 0 {
+                total_ms / s.calls as f64
+            } else {
+                0.0
+            };
+            let entry = json!({
+                "calls": s.calls,
+                "errors": s.errors,
+                "slowCalls": s.slow,
+                "totalMs": total_ms,
+                "meanMs": mean_ms,
+            });
+            (name.clone(), entry)
+        })
+        .collect();
+
+    json!({
+        "slowThresholdMs": slow_ms,
+        "tools": tools,
+    })
+}
+
+/// Split a counter key into tool name and field (tool names may contain ':')
+fn parse_key(key: &str) -> Option<(&str, &str)> {
+    key.strip_prefix(KEY_PREFIX)?.rsplit_once(':')
+}
+
+fn increment(bucket: &Bucket, tool: &str, field: &str, delta: i64) {
+    let key = format!("{}{}:{}", KEY_PREFIX, tool, field);
+    if let Err(e) = bucket.increment(&key, delta) {
+        eprintln!("[tool-stats] Failed to update {}: {:?}", key, e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_key() {
+        assert_eq!(
+            parse_key("wasmcp:tool-stats:math:add:calls"),
+            Some(("math:add", "calls"))
+        );
+        assert_eq!(parse_key("wasmcp:metrics:requests:POST:2xx"), None);
+    }
+
+    #[test]
+    fn test_to_json() {
+        let mut stats = BTreeMap::new();
+        stats.insert(
+            "add".to_string(),
+            ToolStats {
+                calls: 4,
+                errors: 1,
+                slow: 0,
+                duration_us: 10_000,
+            },
+        );
+        let doc = to_json(&stats, Some(1000));
+        assert_eq!(doc["slowThresholdMs"], 1000);
+        assert_eq!(doc["tools"]["add"]["errors"], 1);
+        assert_eq!(doc["tools"]["add"]["totalMs"], 10.0);
+        assert_eq!(doc["tools"]["add"]["meanMs"], 2.5);
+    }
+}
@@ -0,0 +1,156 @@
+//! Tag filters for tools/list
+//!
+//! Tools declare tags in their `_meta`, as filter-middleware reads them:
+//! `{"tags": {"category": "productivity", "tool-level": "foundational"}}`.
+//! A client can narrow a large aggregated list by sending
+//! `"_meta": {"filter": {"category": "productivity", "tags": {"tool-level": "foundational"}}}`
+//! in the tools/list params. `category` is shorthand for the `category` tag.
+//!
+//! A tool is listed when every requested tag matches (AND). A requested
+//! value may be a list, which matches any of its values; a tool tag may be
+//! a list too, which matches if any of its values is requested. Tools
+//! without a requested tag are left out.
+
+use crate::bindings::wasmcp::mcp_v20251125::mcp::Tool;
+use serde_json::{Map, Value};
+
+/// Tag values a tools/list request asked for
+#[derive(Debug, PartialEq)]
+pub struct TagFilter {
+    required: Vec<(String, Vec<String>)>,
+}
+
+impl TagFilter {
+    /// Filter from a tools/list request's `_meta`, if it has one
+    pub fn from_meta(meta: Option<&str>) -> Result<Option<Self>, String> {
+        let Some(filter) = meta
+            .and_then(|m| serde_json::from_str::<Value>(m).ok())
+            .and_then(|m| m.get("filter").cloned())
+        else {
+            return Ok(None);
+        };
+        let Value::Object(filter) = filter else {
+            return Err("_meta.filter must be an object".to_string());
+        };
+
+        let mut required = Vec::new();
+        for (key, value) in &filter {
+            match key.as_str() {
+                "category" => required.push(("category".to_string(), values(key, value)?)),
+                "tags" => {
+                    let Value::Object(tags) = value else {
+                        return Err("_meta.filter.tags must be an object".to_string());
+                    };
+                    for (tag, value) in tags {
+                        required.push((tag.clone(), values(tag, value)?));
+                    }
+                }
+                other => return Err(format!("Unknown _meta.filter field '{}'", other)),
+            }
+        }
+        Ok((!required.is_empty()).then_some(TagFilter { required }))
+    }
+
+    /// Whether a tool carries every requested tag
+    pub fn matches(&self, tool: &Tool) -> bool {
+        let tags = tool
+            .options
+            .as_ref()
+            .and_then(|o| o.meta.as_ref())
+            .and_then(|m| serde_json::from_str::<Value>(m).ok())
+            .and_then(|m| match m.get("tags") {
+                Some(Value::Object(tags)) => Some(tags.clone()),
+                _ => None,
+            })
+            .unwrap_or_default();
+
+        self.required
+            .iter()
+            .all(|(tag, wanted)| tag_values(&tags, tag).any(|v| wanted.iter().any(|w| w == v)))
+    }
+}
+
+/// Requested values: a string or a list of strings
+fn values(tag: &str, value: &Value) -> Result<Vec<String>, String> {
+    let values = match value {
+        Value::String(s) => vec![s.clone()],
+        Value::Array(items) => items
+            .iter()
+            .map(|v| v.as_str().map(String::from))
+            .collect::<Option<Vec<_>>>()
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    };
+    if values.is_empty() {
+        return Err(format!(
+            "_meta.filter value for '{}' must be a string or a list of strings",
+            tag
+        ));
+    }
+    Ok(values)
+}
+
+/// A tool's values for a tag
+fn tag_values<'a>(tags: &'a Map<String, Value>, tag: &str) -> impl Iterator<Item = &'a str> {
+    let values: Vec<&str> = match tags.get(tag) {
+        Some(Value::String(s)) => vec![s.as_str()],
+        Some(Value::Array(items)) => items.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    };
+    values.into_iter()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bindings::wasmcp::mcp_v20251125::mcp::ToolOptions;
+
+    fn tool(meta: Option<&str>) -> Tool {
+        Tool {
+            name: "t".to_string(),
+            input_schema: r#"{"type":"object"}"#.to_string(),
+            options: meta.map(|meta| ToolOptions {
+                meta: Some(meta.to_string()),
+                annotations: None,
+                description: None,
+                output_schema: None,
+                title: None,
+                icons: None,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_from_meta() {
+        assert_eq!(TagFilter::from_meta(None), Ok(None));
+        assert_eq!(
+            TagFilter::from_meta(Some(r#"{"progressToken":1}"#)),
+            Ok(None)
+        );
+        assert_eq!(TagFilter::from_meta(Some(r#"{"filter":{}}"#)), Ok(None));
+        assert!(TagFilter::from_meta(Some(r#"{"filter":"math"}"#)).is_err());
+        assert!(TagFilter::from_meta(Some(r#"{"filter":{"owner":"me"}}"#)).is_err());
+        assert!(TagFilter::from_meta(Some(r#"{"filter":{"tags":{"level":3}}}"#)).is_err());
+    }
+
+    #[test]
+    fn test_matches() {
+        let filter = TagFilter::from_meta(Some(
+            r#"{"filter":{"category":"productivity","tags":{"tool-level":["basic","foundational"]}}}"#,
+        ))
+        .unwrap()
+        .unwrap();
+
+        assert!(filter.matches(&tool(Some(
+            r#"{"tags":{"category":"productivity","tool-level":"foundational"}}"#
+        ))));
+        assert!(filter.matches(&tool(Some(
+            r#"{"tags":{"category":["math","productivity"],"tool-level":"basic"}}"#
+        ))));
+        assert!(!filter.matches(&tool(Some(
+            r#"{"tags":{"category":"productivity","tool-level":"advanced"}}"#
+        ))));
+        assert!(!filter.matches(&tool(Some(r#"{"tags":{"category":"productivity"}}"#))));
+        assert!(!filter.matches(&tool(None)));
+    }
+}
@@ -6,6 +6,15 @@
 //! - Calls the imported tools interface functions
 //! - Merges results with downstream handlers
 //! - Delegates all other requests downstream
+//!
+//! Calls go to the imported tools first, so a downstream tool with the same
+//! name as an imported one could never be called. Such collisions are logged
+//! and the downstream tool is left out of tools/list. With
+//! `WASMCP_TOOLS_NAMESPACE=math`, imported tools are exposed as `math/add`
+//! etc., so several tool components can be composed without clashing.
+//!
+//! A tools/list request with `_meta.filter` only lists tools whose tags
+//! match (see `filter`).
 
 #![allow(warnings)]
 
@@ -16,10 +25,14 @@ mod bindings {
     });
 }
 
+mod filter;
+
 use bindings::exports::wasmcp::mcp_v20251125::server_handler::{Guest, MessageContext};
+use bindings::wasi::cli::environment::get_environment;
 use bindings::wasmcp::mcp_v20251125::mcp::*;
 use bindings::wasmcp::mcp_v20251125::server_handler as downstream;
 use bindings::wasmcp::mcp_v20251125::tools;
+use std::collections::HashSet;
 
 struct ToolsMiddleware;
 
@@ -38,6 +51,13 @@ fn to_downstream_ctx<'a>(ctx: &'a MessageContext<'a>) -> downstream::MessageCont
         }),
         frame: ctx.frame.clone(),
         http_context: ctx.http_context.clone(),
+        client_capabilities: ctx.client_capabilities.clone(),
+        request_id: ctx.request_id.clone(),
+        deadline: ctx.deadline,
+        log_level: ctx.log_level,
+        client_info: ctx.client_info.clone(),
+        locale: ctx.locale.clone(),
+        trace_context: ctx.trace_context.clone(),
     }
 }
 
@@ -76,10 +96,43 @@ fn handle_tools_list(
     request_id: RequestId,
     req: ListToolsRequest,
     ctx: &MessageContext,
+) -> Result<ServerResult, ErrorCode> {
+    let tag_filter = filter::TagFilter::from_meta(req.meta.as_deref()).map_err(|message| {
+        ErrorCode::InvalidParams(Error {
+            code: -32602,
+            message,
+            data: None,
+        })
+    })?;
+
+    // Downstream tools may come from handlers that don't filter, so filter the merged list
+    let result = list_all_tools(request_id, req, ctx)?;
+    match (tag_filter, result) {
+        (Some(tag_filter), ServerResult::ToolsList(mut list)) => {
+            list.tools.retain(|tool| tag_filter.matches(tool));
+            Ok(ServerResult::ToolsList(list))
+        }
+        (_, result) => Ok(result),
+    }
+}
+
+/// Our tools merged with downstream tools
+fn list_all_tools(
+    request_id: RequestId,
+    req: ListToolsRequest,
+    ctx: &MessageContext,
 ) -> Result<ServerResult, ErrorCode> {
     // Try to get tools from imported tools interface
     let our_result = match tools::list_tools(&to_downstream_ctx(ctx), &req) {
-        Ok(result) => Some(result),
+        Ok(mut result) => {
+            if let Some(namespace) = namespace() {
+                for tool in &mut result.tools {
+                    tool.name = format!("{}/{}", namespace, tool.name);
+                }
+            }
+            warn_duplicates(&result.tools);
+            Some(result)
+        }
         Err(ErrorCode::MethodNotFound(_)) => {
             // Component doesn't implement tools interface - skip it
             None
@@ -100,7 +153,8 @@ fn handle_tools_list(
             match our_result {
                 Some(our) => {
                     let mut all_tools = our.tools;
-                    all_tools.extend(downstream_result.tools);
+                    let downstream_tools = drop_shadowed(&all_tools, downstream_result.tools);
+                    all_tools.extend(downstream_tools);
 
                     Ok(ServerResult::ToolsList(ListToolsResult {
                         tools: all_tools,
@@ -174,8 +228,27 @@ fn handle_tools_call(
     req: CallToolRequest,
     ctx: &MessageContext,
 ) -> Result<ServerResult, ErrorCode> {
-    // Try calling imported tools interface first
-    match tools::call_tool(&to_downstream_ctx(ctx), &req) {
+    // Try calling imported tools interface first, under its own name when
+    // namespaced (names outside the namespace are never ours)
+    let local_name = match namespace() {
+        Some(namespace) => req
+            .name
+            .strip_prefix(namespace.as_str())
+            .and_then(|name| name.strip_prefix('/')),
+        None => Some(req.name.as_str()),
+    };
+    let our_result = match local_name {
+        Some(name) => {
+            let local_req = CallToolRequest {
+                name: name.to_string(),
+                ..req.clone()
+            };
+            tools::call_tool(&to_downstream_ctx(ctx), &local_req)
+        }
+        None => Ok(None),
+    };
+
+    match our_result {
         Ok(Some(result)) => {
             // Imported interface handled it - return the result
             Ok(ServerResult::ToolsCall(result))
@@ -206,4 +279,48 @@ fn handle_tools_call(
     }
 }
 
+/// Prefix for imported tool names, from `WASMCP_TOOLS_NAMESPACE`
+fn namespace() -> Option<String> {
+    get_environment()
+        .into_iter()
+        .find(|(key, _)| key == "WASMCP_TOOLS_NAMESPACE")
+        .map(|(_, value)| value.trim().trim_end_matches('/').to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// Log tools the imported interface lists more than once
+fn warn_duplicates(tools: &[Tool]) {
+    let mut seen = HashSet::new();
+    for tool in tools {
+        if !seen.insert(tool.name.as_str()) {
+            eprintln!(
+                "[tools-middleware] WARNING: Tool '{}' is listed more than once by the tools component; clients can't tell the copies apart",
+                tool.name
+            );
+        }
+    }
+}
+
+/// Drop downstream tools shadowed by an imported tool with the same name
+///
+/// Calls reach the imported tools interface first, so the downstream tool
+/// can never be called; listing it would show clients two tools with one name.
+fn drop_shadowed(ours: &[Tool], downstream: Vec<Tool>) -> Vec<Tool> {
+    let names: HashSet<&str> = ours.iter().map(|t| t.name.as_str()).collect();
+    downstream
+        .into_iter()
+        .filter(|tool| {
+            let shadowed = names.contains(tool.name.as_str());
+            if shadowed {
+                eprintln!(
+                    "[tools-middleware] WARNING: Tool '{}' is provided both here and downstream; \
+                     the downstream tool is hidden. Set WASMCP_TOOLS_NAMESPACE to expose both.",
+                    tool.name
+                );
+            }
+            !shadowed
+        })
+        .collect()
+}
+
 bindings::export!(ToolsMiddleware with_types_in bindings);
@@ -0,0 +1,105 @@
+//! Stdio session checkpoints (opt-in)
+//!
+//! A stdio connection's state lives in the process: the negotiated protocol
+//! version, the client's capabilities, clientInfo and locale from initialize,
+//! and the level from logging/setLevel. Desktop clients that restart a crashed
+//! or updated server process and carry on without initializing again would
+//! otherwise talk to a server that has forgotten all of it.
+//!
+//! With `WASMCP_STDIO_RESUME_TOKEN` set, that state is written to the session
+//! bucket under the token after initialize and logging/setLevel, and restored
+//! when a process starts with the same token. The client picks the token
+//! (e.g. one per workspace) and passes it in the server's environment; any
+//! process started with it can read the checkpoint, so it should not be
+//! guessable when the bucket is shared. A new initialize replaces the
+//! checkpoint.
+//!
+//! Checkpoint failures are logged and never fail a request.
+
+use crate::bindings::wasmcp::keyvalue::store as kv_store;
+use crate::bindings::wasmcp::mcp_v20251125::mcp::ProtocolVersion;
+use crate::common::{self, capability, client_info, locale, log_level};
+use crate::config::TransportConfig;
+use serde_json::{Map, Value};
+
+/// KV key prefix of checkpoints; the resume token follows
+const CHECKPOINT_KEY_PREFIX: &str = "wasmcp:stdio-resume:";
+
+fn checkpoint_key(token: &str) -> String {
+    format!("{}{}", CHECKPOINT_KEY_PREFIX, token)
+}
+
+/// Restore the state checkpointed under the configured token
+///
+/// Returns the protocol version of the resumed connection, or None when
+/// resumption is off or there is no usable checkpoint.
+pub fn restore(config: &TransportConfig) -> Option<ProtocolVersion> {
+    let token = config.stdio_resume_token.as_deref()?;
+    let json = kv_store::open(config.get_session_bucket())
+        .and_then(|bucket| bucket.get_json(&checkpoint_key(token)))
+        .map_err(|e| eprintln!("[transport:checkpoint] Failed to read checkpoint: {:?}", e))
+        .ok()??;
+
+    let checkpoint: Map<String, Value> = match serde_json::from_str(&json) {
+        Ok(checkpoint) => checkpoint,
+        Err(e) => {
+            eprintln!("[transport:checkpoint] Ignoring invalid checkpoint: {}", e);
+            return None;
+        }
+    };
+    let field = |key: &str| checkpoint.get(key).and_then(Value::as_str);
+
+    let protocol_version = common::parse_protocol_version(field("protocolVersion")?).ok()?;
+    capability::set_negotiated_client_capabilities(
+        field("capabilities").and_then(capability::client_capabilities_from_json),
+    );
+    client_info::set_client_info(field("clientInfo").and_then(client_info::client_info_from_json));
+    locale::set_client_locale(field("locale").map(str::to_string));
+    log_level::set_client_log_level(field("logLevel").and_then(log_level::parse_log_level));
+
+    Some(common::clamp_protocol_version(
+        protocol_version,
+        config.max_protocol_version,
+    ))
+}
+
+/// Checkpoint the current connection state under the configured token
+pub fn save(config: &TransportConfig, protocol_version: ProtocolVersion) {
+    let Some(token) = config.stdio_resume_token.as_deref() else {
+        return;
+    };
+
+    let mut checkpoint = Map::new();
+    checkpoint.insert(
+        "protocolVersion".to_string(),
+        common::protocol::protocol_version_to_string(protocol_version).into(),
+    );
+    if let Some(capabilities) = capability::negotiated_client_capabilities() {
+        checkpoint.insert(
+            "capabilities".to_string(),
+            capability::client_capabilities_to_json(&capabilities).into(),
+        );
+    }
+    if let Some(info) = client_info::client_info() {
+        checkpoint.insert(
+            "clientInfo".to_string(),
+            client_info::client_info_to_json(&info).into(),
+        );
+    }
+    if let Some(locale) = locale::client_locale() {
+        checkpoint.insert("locale".to_string(), locale.into());
+    }
+    if let Some(level) = log_level::client_log_level() {
+        checkpoint.insert(
+            "logLevel".to_string(),
+            common::log_level_to_string(level).into(),
+        );
+    }
+
+    let json = Value::Object(checkpoint).to_string();
+    if let Err(e) = kv_store::open(config.get_session_bucket())
+        .and_then(|bucket| bucket.set_json(&checkpoint_key(token), &json))
+    {
+        eprintln!("[transport:checkpoint] Failed to write checkpoint: {:?}", e);
+    }
+}
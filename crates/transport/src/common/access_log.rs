@@ -0,0 +1,176 @@
+//! Structured access log
+//!
+//! With `WASMCP_ACCESS_LOG=true`, both transports write one JSON line per
+//! handled message to stderr, so operators get standard request logs without
+//! a logging middleware:
+//!
+//! ```text
+//! {"timestamp":"2025-11-25T12:00:00.123Z","transport":"http","http_method":"POST","method":"tools/call","tool":"search","session":"4f1c...","sub":"user-1","status":200,"duration_ms":12,"bytes_in":87}
+//! ```
+//!
+//! `WASMCP_ACCESS_LOG_FIELDS` limits the line to a comma-separated subset of
+//! the fields. Fields that don't apply (e.g. `status` on stdio, `sub` in
+//! public mode) are left out. Response sizes aren't known here: server-io
+//! writes the response body directly to the output stream.
+
+use crate::bindings::wasi::clocks::{monotonic_clock, wall_clock};
+use crate::bindings::wasmcp::mcp_v20251125::mcp::{ClientNotification, ClientRequest, ErrorCode};
+use crate::common::McpMessage;
+use crate::config::TransportConfig;
+use serde_json::{Map, Value};
+use std::cell::RefCell;
+use wasmcp_errors::McpError;
+
+/// Every field an access log line can carry, in output order
+pub const FIELDS: &[&str] = &[
+    "timestamp",
+    "transport",
+    "http_method",
+    "method",
+    "tool",
+    "session",
+    "sub",
+    "status",
+    "error",
+    "duration_ms",
+    "bytes_in",
+];
+
+/// The message currently being handled
+struct Entry {
+    fields: Vec<String>,
+    started: u64,
+    values: Map<String, Value>,
+}
+
+thread_local! {
+    /// None when access logging is disabled
+    static ENTRY: RefCell<Option<Entry>> = const { RefCell::new(None) };
+}
+
+/// Start an entry for a new message (no-op unless access logging is on)
+pub fn begin(config: &TransportConfig, transport: &str) {
+    let entry = config.access_log.as_ref().map(|fields| {
+        let mut values = Map::new();
+        values.insert("timestamp".into(), Value::String(timestamp()));
+        values.insert("transport".into(), Value::String(transport.to_string()));
+        Entry {
+            fields: fields.clone(),
+            started: monotonic_clock::now(),
+            values,
+        }
+    });
+    ENTRY.with(|e| *e.borrow_mut() = entry);
+}
+
+/// Set a field on the current entry
+pub fn record(field: &str, value: impl Into<Value>) {
+    ENTRY.with(|e| {
+        if let Some(entry) = e.borrow_mut().as_mut() {
+            entry.values.insert(field.to_string(), value.into());
+        }
+    });
+}
+
+/// Record the MCP method (and tool name for tools/call) of a parsed message
+pub fn record_message(message: &McpMessage) {
+    let method = match message {
+        McpMessage::Request(_, request) => {
+            if let ClientRequest::ToolsCall(call) = request {
+                record("tool", call.name.clone());
+            }
+            request_method(request)
+        }
+        McpMessage::Notification(notification) => notification_method(notification),
+        McpMessage::Result(..) => "result",
+        McpMessage::Error(..) => "error",
+    };
+    record("method", method);
+}
+
+/// Record the JSON-RPC error code a request failed with
+pub fn record_error(error: &ErrorCode) {
+    let (code, _, _) = McpError::from(error).into_parts();
+    record("error", code);
+}
+
+/// Write the current entry, if any
+pub fn finish() {
+    let Some(entry) = ENTRY.with(|e| e.borrow_mut().take()) else {
+        return;
+    };
+
+    let elapsed_ms = monotonic_clock::now().saturating_sub(entry.started) / 1_000_000;
+    let mut values = entry.values;
+    values.insert("duration_ms".into(), Value::from(elapsed_ms));
+
+    // Written by hand to keep FIELDS order (serde_json maps sort their keys)
+    let line: Vec<String> = FIELDS
+        .iter()
+        .filter(|field| entry.fields.iter().any(|f| f == *field))
+        .filter_map(|field| Some(format!("\"{}\":{}", field, values.remove(*field)?)))
+        .collect();
+    eprintln!("{{{}}}", line.join(","));
+}
+
+fn request_method(request: &ClientRequest) -> &str {
+    match request {
+        ClientRequest::Initialize(_) => "initialize",
+        ClientRequest::ToolsList(_) => "tools/list",
+        ClientRequest::ToolsCall(_) => "tools/call",
+        ClientRequest::ResourcesList(_) => "resources/list",
+        ClientRequest::ResourcesRead(_) => "resources/read",
+        ClientRequest::ResourcesTemplatesList(_) => "resources/templates/list",
+        ClientRequest::PromptsList(_) => "prompts/list",
+        ClientRequest::PromptsGet(_) => "prompts/get",
+        ClientRequest::CompletionComplete(_) => "completion/complete",
+        ClientRequest::LoggingSetLevel(_) => "logging/setLevel",
+        ClientRequest::Ping(_) => "ping",
+        ClientRequest::ResourcesSubscribe(_) => "resources/subscribe",
+        ClientRequest::ResourcesUnsubscribe(_) => "resources/unsubscribe",
+        ClientRequest::Custom(custom) => &custom.method,
+        ClientRequest::Unknown(method) => method,
+    }
+}
+
+fn notification_method(notification: &ClientNotification) -> &'static str {
+    match notification {
+        ClientNotification::Initialized(_) => "notifications/initialized",
+        ClientNotification::RootsListChanged(_) => "notifications/roots/list_changed",
+        ClientNotification::Cancelled(_) => "notifications/cancelled",
+        ClientNotification::Progress(_) => "notifications/progress",
+    }
+}
+
+/// Current wall-clock time as RFC 3339 UTC with milliseconds
+fn timestamp() -> String {
+    let now = wall_clock::now();
+    format_timestamp(now.seconds, now.nanoseconds)
+}
+
+fn format_timestamp(seconds: u64, nanoseconds: u32) -> String {
+    let days = (seconds / 86_400) as i64;
+    let secs_of_day = seconds % 86_400;
+
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        secs_of_day / 3_600,
+        secs_of_day % 3_600 / 60,
+        secs_of_day % 60,
+        nanoseconds / 1_000_000
+    )
+}
@@ -1,41 +1,279 @@
 //! Capability discovery for MCP servers
+//!
+//! Discovery probes the handler chain with list calls, which can be expensive
+//! for handlers that build their lists dynamically. Results are kept for the
+//! lifetime of the instance (a stdio process, or one HTTP request), and with
+//! `WASMCP_CAPABILITY_CACHE_TTL` also in the session bucket, so that other
+//! instances skip probing until the entry expires. Handlers that declare
+//! their lists in an initialize result are not probed at all (see
+//! `server_info.rs`).
+//!
+//! Resource subscriptions are detected with a `resources/unsubscribe` for a
+//! URI nobody subscribes to: handlers that support subscriptions accept it,
+//! others pass it on to the terminal method-not-found handler.
+//!
+//! Also tracks the capabilities the connected client negotiated during initialize,
+//! so they can be surfaced to handlers on every MessageContext.
 
+use crate::bindings::wasmcp::keyvalue::store as kv_store;
 use crate::bindings::wasmcp::mcp_v20251125::mcp::{
-    ClientMessage, ClientRequest, CompleteRequest, CompletionArgument, CompletionPromptReference,
-    CompletionReference, ErrorCode, ListPromptsRequest, ListResourcesRequest, ListToolsRequest,
-    ProtocolVersion, RequestId, ServerCapabilities, ServerLists, ServerResult,
+    ClientCapabilities, ClientLists, ClientMessage, ClientRequest, CompleteRequest,
+    CompletionArgument, CompletionPromptReference, CompletionReference, ErrorCode,
+    ListPromptsRequest, ListResourcesRequest, ListToolsRequest, ProtocolVersion, RequestId,
+    SamplingCapabilities, ServerCapabilities, ServerLists, ServerResult, ServerSubscriptions,
 };
 use crate::bindings::wasmcp::mcp_v20251125::server_handler::handle;
 use crate::bindings::wasmcp::mcp_v20251125::server_io::MessageFrame;
-use crate::common::protocol::create_message_context;
+use crate::common::protocol::{create_message_context, protocol_version_to_string};
+use crate::config::TransportConfig;
+use serde_json::json;
+use std::cell::{Cell, RefCell};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 /// Request ID for internal capability discovery probes
 /// Uses -1 to avoid conflicts with real client request IDs (which are typically positive)
 const CAPABILITY_PROBE_REQUEST_ID: i64 = -1;
 
+/// KV key prefix of cached discovery results; the protocol version follows
+const DISCOVERY_CACHE_KEY_PREFIX: &str = "wasmcp:capabilities:";
+
+/// URI for the subscription probe's unsubscribe; nobody subscribes to it
+const SUBSCRIPTION_PROBE_URI: &str = "wasmcp://capability-probe";
+
+/// What probing the handler chain found
+#[derive(Debug, Clone, Copy)]
+struct Discovered {
+    lists: ServerLists,
+    completions: bool,
+    subscriptions: bool,
+}
+
+thread_local! {
+    /// Discovery result of this instance and the protocol version it was probed with
+    static DISCOVERED: Cell<Option<(ProtocolVersion, Discovered)>> = const { Cell::new(None) };
+
+
+    /// Client capabilities negotiated for the current connection
+    ///
+    /// stdio: set once from initialize and kept for the process lifetime.
+    /// HTTP: set per request (from initialize, or loaded from session storage).
+    static NEGOTIATED_CLIENT_CAPABILITIES: RefCell<Option<ClientCapabilities>> =
+        const { RefCell::new(None) };
+}
+
+/// Record the client capabilities for the current connection
+pub fn set_negotiated_client_capabilities(capabilities: Option<ClientCapabilities>) {
+    NEGOTIATED_CLIENT_CAPABILITIES.with(|c| *c.borrow_mut() = capabilities);
+}
+
+/// Get the client capabilities for the current connection, if known
+pub fn negotiated_client_capabilities() -> Option<ClientCapabilities> {
+    NEGOTIATED_CLIENT_CAPABILITIES.with(|c| c.borrow().clone())
+}
+
+/// Serialize client capabilities to MCP JSON shape for session storage
+///
+/// Only advertised capabilities are emitted, so consumers (e.g. session-store)
+/// can gate on key presence: `{"elicitation": {...}, "sampling": {...}, "roots": {...}}`
+pub fn client_capabilities_to_json(capabilities: &ClientCapabilities) -> String {
+    let mut obj = serde_json::Map::new();
+
+    if let Some(ref elicitation) = capabilities.elicitation {
+        obj.insert("elicitation".to_string(), parse_json_or_empty(elicitation));
+    }
+
+    if let Some(ref sampling) = capabilities.sampling {
+        let mut sampling_obj = serde_json::Map::new();
+        if let Some(tools) = sampling.tools {
+            sampling_obj.insert("tools".to_string(), serde_json::Value::Bool(tools));
+        }
+        if let Some(context) = sampling.context {
+            sampling_obj.insert("context".to_string(), serde_json::Value::Bool(context));
+        }
+        obj.insert(
+            "sampling".to_string(),
+            serde_json::Value::Object(sampling_obj),
+        );
+    }
+
+    let offers = |lists: Option<ClientLists>| lists.is_some_and(|l| l.contains(ClientLists::ROOTS));
+    if offers(capabilities.lists) || offers(capabilities.list_changed) {
+        let roots = if offers(capabilities.list_changed) {
+            serde_json::json!({ "listChanged": true })
+        } else {
+            serde_json::json!({})
+        };
+        obj.insert("roots".to_string(), roots);
+    }
+
+    if let Some(ref experimental) = capabilities.experimental {
+        let experimental_obj: serde_json::Map<String, serde_json::Value> = experimental
+            .iter()
+            .map(|(k, v)| (k.clone(), parse_json_or_empty(v)))
+            .collect();
+        obj.insert(
+            "experimental".to_string(),
+            serde_json::Value::Object(experimental_obj),
+        );
+    }
+
+    serde_json::Value::Object(obj).to_string()
+}
+
+/// Parse client capabilities previously stored by `client_capabilities_to_json`
+pub fn client_capabilities_from_json(json: &str) -> Option<ClientCapabilities> {
+    let value: serde_json::Value = serde_json::from_str(json).ok()?;
+    let obj = value.as_object()?;
+
+    let sampling = obj.get("sampling").map(|s| SamplingCapabilities {
+        tools: s.get("tools").and_then(|v| v.as_bool()),
+        context: s.get("context").and_then(|v| v.as_bool()),
+    });
+
+    let list_changed = obj
+        .get("roots")
+        .and_then(|r| r.get("listChanged"))
+        .and_then(|v| v.as_bool())
+        .filter(|enabled| *enabled)
+        .map(|_| ClientLists::ROOTS);
+
+    let experimental = obj
+        .get("experimental")
+        .and_then(|e| e.as_object())
+        .map(|e| e.iter().map(|(k, v)| (k.clone(), v.to_string())).collect());
+
+    Some(ClientCapabilities {
+        elicitation: obj.get("elicitation").map(|v| v.to_string()),
+        experimental,
+        list_changed,
+        sampling,
+        lists: obj.get("roots").map(|_| ClientLists::ROOTS),
+    })
+}
+
+/// Parse a JSON-encoded capability value, falling back to an empty object
+fn parse_json_or_empty(json: &str) -> serde_json::Value {
+    serde_json::from_str(json).unwrap_or_else(|_| serde_json::json!({}))
+}
+
 /// Discover capabilities for initialize response
 ///
-/// This is called during initialize to probe the downstream handler
+/// Probes the downstream handler, unless this instance or (with
+/// `WASMCP_CAPABILITY_CACHE_TTL`) another one already did for this protocol
+/// version.
 pub fn discover_capabilities_for_init(
     protocol_version: ProtocolVersion,
     frame: &MessageFrame,
+    config: &TransportConfig,
 ) -> ServerCapabilities {
-    discover_capabilities(protocol_version, frame)
+    let discovered = match cached_discovery(protocol_version, config) {
+        Some(discovered) => discovered,
+        None => {
+            let discovered = discover_capabilities(protocol_version, frame);
+            cache_discovery(protocol_version, discovered, config);
+            discovered
+        }
+    };
+    build_capabilities(
+        discovered.lists,
+        discovered.completions,
+        discovered.subscriptions,
+    )
+}
+
+/// Capabilities for a handler that declared its lists, without probing
+///
+/// Completions and subscriptions are left for the handler's declaration to set.
+pub fn declared_capabilities(lists: ServerLists) -> ServerCapabilities {
+    build_capabilities(lists, false, false)
+}
+
+/// The discovery result of this instance, else a live KV cache entry
+fn cached_discovery(
+    protocol_version: ProtocolVersion,
+    config: &TransportConfig,
+) -> Option<Discovered> {
+    if let Some((version, discovered)) = DISCOVERED.with(Cell::get)
+        && version == protocol_version
+    {
+        return Some(discovered);
+    }
+    if config.capability_cache_ttl == 0 {
+        return None;
+    }
+
+    let json = kv_store::open(config.get_session_bucket())
+        .and_then(|bucket| bucket.get_json(&discovery_cache_key(protocol_version)))
+        .map_err(|e| eprintln!("[transport:capability] Failed to read cache: {:?}", e))
+        .ok()??;
+    let entry: serde_json::Value = serde_json::from_str(&json).ok()?;
+    if entry.get("expiresAt")?.as_u64()? <= now_secs() {
+        return None;
+    }
+    let discovered = Discovered {
+        lists: ServerLists::from_bits_truncate(entry.get("lists")?.as_u64()?.try_into().ok()?),
+        completions: entry.get("completions")?.as_bool()?,
+        subscriptions: entry
+            .get("subscriptions")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false),
+    };
+    DISCOVERED.with(|d| d.set(Some((protocol_version, discovered))));
+    Some(discovered)
+}
+
+/// Remember a discovery result for this instance, and in KV if enabled
+fn cache_discovery(
+    protocol_version: ProtocolVersion,
+    discovered: Discovered,
+    config: &TransportConfig,
+) {
+    DISCOVERED.with(|d| d.set(Some((protocol_version, discovered))));
+    if config.capability_cache_ttl == 0 {
+        return;
+    }
+
+    let entry = json!({
+        "lists": discovered.lists.bits(),
+        "completions": discovered.completions,
+        "subscriptions": discovered.subscriptions,
+        "expiresAt": now_secs().saturating_add(config.capability_cache_ttl),
+    });
+    if let Err(e) = kv_store::open(config.get_session_bucket()).and_then(|bucket| {
+        bucket.set_json(&discovery_cache_key(protocol_version), &entry.to_string())
+    }) {
+        eprintln!("[transport:capability] Failed to write cache: {:?}", e);
+    }
+}
+
+fn discovery_cache_key(protocol_version: ProtocolVersion) -> String {
+    format!(
+        "{}{}",
+        DISCOVERY_CACHE_KEY_PREFIX,
+        protocol_version_to_string(protocol_version)
+    )
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
 }
 
 /// Discover server capabilities by probing downstream handler
 ///
 /// This sends test requests to see what the middleware stack supports
-fn discover_capabilities(
-    protocol_version: ProtocolVersion,
-    frame: &MessageFrame,
-) -> ServerCapabilities {
+fn discover_capabilities(protocol_version: ProtocolVersion, frame: &MessageFrame) -> Discovered {
     let mut list_changed_flags = ServerLists::empty();
     let mut has_completions = false;
 
     // Probe for tools support
     let tools_ctx = create_message_context(None, protocol_version, None, None, "", frame, None);
-    let tools_request = ClientRequest::ToolsList(ListToolsRequest { cursor: None });
+    let tools_request = ClientRequest::ToolsList(ListToolsRequest {
+        cursor: None,
+        meta: None,
+    });
     let tools_message = ClientMessage::Request((
         RequestId::Number(CAPABILITY_PROBE_REQUEST_ID),
         tools_request,
@@ -51,8 +289,18 @@ fn discover_capabilities(
         RequestId::Number(CAPABILITY_PROBE_REQUEST_ID),
         resources_request,
     ));
+    let mut has_subscriptions = false;
     if let Some(Ok(_)) = handle(&resources_ctx, resources_message) {
         list_changed_flags |= ServerLists::RESOURCES;
+
+        // Probe for subscription support: unsubscribing from a URI nobody
+        // subscribed to has no effect on a handler that supports it
+        let subscribe_ctx =
+            create_message_context(None, protocol_version, None, None, "", frame, None);
+        let req = ClientRequest::ResourcesUnsubscribe(SUBSCRIPTION_PROBE_URI.to_string());
+        let subscribe_message =
+            ClientMessage::Request((RequestId::Number(CAPABILITY_PROBE_REQUEST_ID), req));
+        has_subscriptions = matches!(handle(&subscribe_ctx, subscribe_message), Some(Ok(_)));
     }
 
     // Probe for prompts support and use result to test completions
@@ -111,20 +359,33 @@ fn discover_capabilities(
         }
     }
 
-    // Build capabilities based on what succeeded
+    Discovered {
+        lists: list_changed_flags,
+        completions: has_completions,
+        subscriptions: has_subscriptions,
+    }
+}
+
+/// Server capabilities for the supported lists, completions and subscriptions
+fn build_capabilities(
+    lists: ServerLists,
+    completions: bool,
+    subscriptions: bool,
+) -> ServerCapabilities {
     ServerCapabilities {
-        completions: if has_completions {
+        completions: if completions {
             Some("{}".to_string())
         } else {
             None
         },
         experimental: None,
         logging: Some("{}".to_string()), // We support logging/setLevel
-        list_changed: if list_changed_flags.is_empty() {
-            None
+        list_changed: if lists.is_empty() { None } else { Some(lists) },
+        subscriptions: if subscriptions {
+            Some(ServerSubscriptions::RESOURCES)
         } else {
-            Some(list_changed_flags)
+            None
         },
-        subscriptions: None, // TODO: Probe for subscription support
+        lists: if lists.is_empty() { None } else { Some(lists) },
     }
 }
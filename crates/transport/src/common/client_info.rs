@@ -0,0 +1,55 @@
+//! Client implementation tracking (clientInfo from initialize)
+//!
+//! The client's name and version are surfaced on every MessageContext so
+//! handlers can adapt to specific clients (e.g. content types they render).
+
+use crate::bindings::wasmcp::mcp_v20251125::mcp::Implementation;
+use std::cell::RefCell;
+
+thread_local! {
+    /// clientInfo sent with initialize
+    ///
+    /// stdio: set once from initialize and kept for the process lifetime.
+    /// HTTP: set per request (from initialize, or loaded from session storage).
+    static CLIENT_INFO: RefCell<Option<Implementation>> = const { RefCell::new(None) };
+}
+
+/// Record the client implementation for the current connection
+pub fn set_client_info(info: Option<Implementation>) {
+    CLIENT_INFO.with(|c| *c.borrow_mut() = info);
+}
+
+/// Get the client implementation for the current connection, if known
+pub fn client_info() -> Option<Implementation> {
+    CLIENT_INFO.with(|c| c.borrow().clone())
+}
+
+/// Serialize client info to MCP JSON shape for session storage
+///
+/// Icons are not stored; handlers only need the identifying fields.
+pub fn client_info_to_json(info: &Implementation) -> String {
+    let mut obj = serde_json::Map::new();
+    obj.insert("name".to_string(), info.name.clone().into());
+    obj.insert("version".to_string(), info.version.clone().into());
+    if let Some(ref title) = info.title {
+        obj.insert("title".to_string(), title.clone().into());
+    }
+    if let Some(ref description) = info.description {
+        obj.insert("description".to_string(), description.clone().into());
+    }
+    serde_json::Value::Object(obj).to_string()
+}
+
+/// Parse client info previously stored by `client_info_to_json`
+pub fn client_info_from_json(json: &str) -> Option<Implementation> {
+    let value: serde_json::Value = serde_json::from_str(json).ok()?;
+    let field = |key: &str| value.get(key).and_then(|v| v.as_str()).map(str::to_string);
+
+    Some(Implementation {
+        name: field("name")?,
+        title: field("title"),
+        version: field("version").unwrap_or_default(),
+        description: field("description"),
+        icons: None,
+    })
+}
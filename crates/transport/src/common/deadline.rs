@@ -0,0 +1,43 @@
+//! Per-request deadline tracking
+//!
+//! The transport starts the clock when a message arrives; every MessageContext
+//! built while handling it carries the resulting monotonic-clock instant, so
+//! handlers can bound outbound calls by the remaining budget.
+
+use crate::bindings::wasi::clocks::monotonic_clock;
+use std::cell::Cell;
+
+thread_local! {
+    /// Deadline for the message currently being handled
+    static REQUEST_DEADLINE: Cell<Option<u64>> = const { Cell::new(None) };
+}
+
+/// Start the clock for a new message (None clears the deadline)
+pub fn start_request(timeout_ms: Option<u64>) {
+    let deadline =
+        timeout_ms.map(|ms| monotonic_clock::now().saturating_add(ms.saturating_mul(1_000_000)));
+    REQUEST_DEADLINE.with(|d| d.set(deadline));
+}
+
+/// Deadline for the message currently being handled, if any
+pub fn current_deadline() -> Option<u64> {
+    REQUEST_DEADLINE.with(|d| d.get())
+}
+
+/// Log a warning if the current message ran past its deadline
+///
+/// Handlers run to completion (components can't be preempted), so the overrun
+/// is surfaced here rather than silently exceeding the budget.
+pub fn warn_if_exceeded(what: &str) {
+    let Some(deadline) = current_deadline() else {
+        return;
+    };
+    let now = monotonic_clock::now();
+    if now > deadline {
+        eprintln!(
+            "[transport] WARNING: {} finished {}ms past the request deadline",
+            what,
+            (now - deadline) / 1_000_000
+        );
+    }
+}
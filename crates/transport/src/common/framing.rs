@@ -3,7 +3,7 @@
 use crate::bindings::wasmcp::mcp_v20251125::server_io::{MessageFrame, ReadLimit};
 
 /// Maximum size for HTTP request bodies (10MB)
-const HTTP_MAX_REQUEST_SIZE: u64 = 10 * 1024 * 1024;
+pub const HTTP_MAX_REQUEST_SIZE: u64 = 10 * 1024 * 1024;
 
 /// Plain JSON framing configuration (no prefix/suffix)
 ///
@@ -29,6 +29,19 @@ pub fn http_sse_frame() -> MessageFrame {
     }
 }
 
+/// HTTP SSE framing with an event id
+///
+/// Like `http_sse_frame`, with an `id:` line so the client can resume the
+/// stream with `Last-Event-ID`. Used for requests with a session; the id
+/// given is the session ID, which server-io numbers per event
+/// (`<session>:<n>`) and uses to queue undelivered events.
+pub fn http_sse_event_frame(event_id: &str) -> MessageFrame {
+    MessageFrame {
+        prefix: format!("id: {}\ndata: ", event_id).into_bytes(),
+        suffix: b"\n\n".to_vec(),
+    }
+}
+
 /// HTTP read limit configuration
 ///
 /// For HTTP, we read the entire request body up to a maximum size
@@ -0,0 +1,63 @@
+//! Client locale tracking
+//!
+//! The locale is surfaced on every MessageContext so handlers can localize
+//! titles, descriptions and content. Clients choose it with `_meta.locale`
+//! on initialize; HTTP clients that don't are served per the Accept-Language
+//! header of each request.
+
+use std::cell::RefCell;
+
+thread_local! {
+    /// Preferred locale of the client (BCP 47 tag)
+    ///
+    /// stdio: set once from initialize and kept for the process lifetime.
+    /// HTTP: set per request (from initialize, session storage or Accept-Language).
+    static CLIENT_LOCALE: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Record the locale for the current connection
+pub fn set_client_locale(locale: Option<String>) {
+    CLIENT_LOCALE.with(|l| *l.borrow_mut() = locale);
+}
+
+/// Get the locale for the current connection, if known
+pub fn client_locale() -> Option<String> {
+    CLIENT_LOCALE.with(|l| l.borrow().clone())
+}
+
+/// The `locale` from initialize `_meta`, if it is a non-empty string
+pub fn locale_from_meta(meta: Option<&str>) -> Option<String> {
+    let value: serde_json::Value = serde_json::from_str(meta?).ok()?;
+    value
+        .get("locale")
+        .and_then(|l| l.as_str())
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(str::to_string)
+}
+
+/// The most preferred language of an Accept-Language header
+///
+/// Picks the tag with the highest quality value (the first on ties),
+/// skipping the `*` wildcard and tags with `q=0`.
+pub fn locale_from_accept_language(header: &str) -> Option<String> {
+    let mut best: Option<(&str, f32)> = None;
+    for entry in header.split(',') {
+        let mut parts = entry.split(';');
+        let tag = parts.next().unwrap_or("").trim();
+        if tag.is_empty() || tag == "*" {
+            continue;
+        }
+        let quality = parts
+            .filter_map(|p| p.trim().strip_prefix("q="))
+            .find_map(|q| q.trim().parse::<f32>().ok())
+            .unwrap_or(1.0);
+        if quality <= 0.0 {
+            continue;
+        }
+        if best.is_none_or(|(_, q)| quality > q) {
+            best = Some((tag, quality));
+        }
+    }
+    best.map(|(tag, _)| tag.to_string())
+}
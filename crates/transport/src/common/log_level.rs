@@ -0,0 +1,40 @@
+//! Client log level tracking (logging/setLevel)
+//!
+//! The level the client asked for is surfaced on every MessageContext so
+//! handlers can skip notifications/message below it.
+
+use crate::bindings::wasmcp::mcp_v20251125::mcp::LogLevel;
+use std::cell::Cell;
+
+thread_local! {
+    /// Minimum log level requested by the client
+    ///
+    /// stdio: set by logging/setLevel and kept for the process lifetime.
+    /// HTTP: set per request (from logging/setLevel, or loaded from session storage).
+    static CLIENT_LOG_LEVEL: Cell<Option<LogLevel>> = const { Cell::new(None) };
+}
+
+/// Record the log level for the current connection
+pub fn set_client_log_level(level: Option<LogLevel>) {
+    CLIENT_LOG_LEVEL.with(|l| l.set(level));
+}
+
+/// Get the log level for the current connection, if the client set one
+pub fn client_log_level() -> Option<LogLevel> {
+    CLIENT_LOG_LEVEL.with(|l| l.get())
+}
+
+/// Parse an MCP log level name (as stored in session)
+pub fn parse_log_level(name: &str) -> Option<LogLevel> {
+    match name {
+        "debug" => Some(LogLevel::Debug),
+        "info" => Some(LogLevel::Info),
+        "notice" => Some(LogLevel::Notice),
+        "warning" => Some(LogLevel::Warning),
+        "error" => Some(LogLevel::Error),
+        "critical" => Some(LogLevel::Critical),
+        "alert" => Some(LogLevel::Alert),
+        "emergency" => Some(LogLevel::Emergency),
+        _ => None,
+    }
+}
@@ -1,23 +1,35 @@
 //! Common transport logic shared between HTTP and stdio implementations
 
+pub mod access_log;
 pub mod capability;
+pub mod client_info;
+pub mod deadline;
 pub mod framing;
+pub mod locale;
+pub mod log_level;
 pub mod protocol;
+pub mod response_budget;
+pub mod server_info;
+pub mod trace_context;
 
 use crate::bindings::wasi::io::streams::{InputStream, OutputStream};
 use crate::bindings::wasmcp::mcp_v20251125::mcp::{
-    ClientMessage, ClientNotification, ClientRequest, ErrorCode, ProtocolVersion, RequestId,
-    ServerMessage, ServerResult,
+    ClientMessage, ClientNotification, ClientRequest, ErrorCode, LogLevel, ProtocolVersion,
+    RequestId, ServerMessage, ServerResult,
 };
 use crate::bindings::wasmcp::mcp_v20251125::server_handler::handle;
 use crate::bindings::wasmcp::mcp_v20251125::server_io::{self, IoError, ReadLimit};
+use wasmcp_errors::McpError;
 
 // Re-export commonly used items
-pub use capability::discover_capabilities_for_init;
 pub use framing::{
-    http_read_limit, http_sse_frame, plain_json_frame, stdio_frame, stdio_read_limit,
+    http_read_limit, http_sse_event_frame, http_sse_frame, plain_json_frame, stdio_frame,
+    stdio_read_limit,
 };
-pub use protocol::{create_message_context, log_level_to_string, parse_protocol_version};
+pub use protocol::{
+    clamp_protocol_version, create_message_context, log_level_to_string, parse_protocol_version,
+};
+pub use server_info::build_initialize_result;
 
 // Re-export MessageFrame so it's public
 pub use crate::bindings::wasmcp::mcp_v20251125::server_io::MessageFrame;
@@ -55,18 +67,28 @@ pub fn parse_mcp_message(
 ) -> Result<McpMessage, String> {
     let client_message = server_io::parse_message(input, &limit, frame)
         .map_err(|e| format!("Failed to parse message: {:?}", e))?;
+    Ok(to_mcp_message(client_message))
+}
 
+/// Parse an MCP message from a body already read in full
+pub fn decode_mcp_message(bytes: &[u8]) -> Result<McpMessage, String> {
+    let client_message = server_io::decode_message(bytes)
+        .map_err(|e| format!("Failed to parse message: {:?}", e))?;
+    Ok(to_mcp_message(client_message))
+}
+
+fn to_mcp_message(client_message: ClientMessage) -> McpMessage {
     match client_message {
         ClientMessage::Request((request_id, client_request)) => {
-            Ok(McpMessage::Request(request_id, client_request))
+            McpMessage::Request(request_id, client_request)
         }
         ClientMessage::Notification(client_notification) => {
-            Ok(McpMessage::Notification(client_notification))
+            McpMessage::Notification(client_notification)
         }
         ClientMessage::Result((result_id, client_result)) => {
-            Ok(McpMessage::Result(result_id, client_result))
+            McpMessage::Result(result_id, client_result)
         }
-        ClientMessage::Error((error_id, error_code)) => Ok(McpMessage::Error(error_id, error_code)),
+        ClientMessage::Error((error_id, error_code)) => McpMessage::Error(error_id, error_code),
     }
 }
 
@@ -96,13 +118,17 @@ pub fn handle_ping() -> Result<(), ErrorCode> {
 
 /// Handle transport-level MCP method: logging/setLevel
 ///
-/// Transport-level logging configuration (returns empty success)
-pub fn handle_set_log_level(_level: String) -> Result<(), ErrorCode> {
-    // No-op for now - could be implemented with env_logger or similar
+/// Records the level for the current connection so later MessageContexts carry
+/// it. HTTP callers also persist it in the session (see `http::session`).
+pub fn handle_set_log_level(level: LogLevel) -> Result<(), ErrorCode> {
+    log_level::set_client_log_level(Some(level));
     Ok(())
 }
 
 /// Delegate non-transport methods to middleware via server-handler
+///
+/// `output_stream` is None when the caller can't receive notifications
+/// (the REST facade), so handlers see no client stream.
 #[allow(clippy::too_many_arguments)]
 pub fn delegate_to_middleware(
     request_id: RequestId,
@@ -111,13 +137,14 @@ pub fn delegate_to_middleware(
     session_id: Option<&str>,
     identity: Option<&crate::bindings::wasmcp::mcp_v20251125::mcp::Identity>,
     bucket_name: String,
-    output_stream: &OutputStream,
+    output_stream: Option<&OutputStream>,
     frame: &MessageFrame,
     http_context: Option<crate::bindings::wasmcp::mcp_v20251125::server_auth::HttpContext>,
 ) -> Result<ServerResult, ErrorCode> {
-    // Create message context
-    let ctx = create_message_context(
-        Some(output_stream),
+    // Create message context, tagged with the request ID so handlers can
+    // address notifications (e.g. partial tool results) to this request
+    let mut ctx = create_message_context(
+        output_stream,
         protocol_version,
         session_id,
         identity,
@@ -125,21 +152,22 @@ pub fn delegate_to_middleware(
         frame,
         http_context,
     );
+    ctx.request_id = Some(request_id.clone());
+    let label = match &request_id {
+        RequestId::Number(n) => format!("Request {}", n),
+        RequestId::String(s) => format!("Request \"{}\"", s),
+    };
 
     // Create client message
     let message = ClientMessage::Request((request_id, client_request));
 
     // Delegate to imported server-handler
-    match handle(&ctx, message) {
-        Some(Ok(result)) => Ok(result),
+    let result = handle(&ctx, message);
+    deadline::warn_if_exceeded(&label);
+    match result {
+        Some(Ok(result)) => response_budget::enforce(result),
         Some(Err(e)) => Err(e),
-        None => Err(ErrorCode::InternalError(
-            crate::bindings::wasmcp::mcp_v20251125::mcp::Error {
-                code: -32603,
-                message: "Handler returned None for request".to_string(),
-                data: None,
-            },
-        )),
+        None => Err(McpError::internal("Handler returned None for request").into()),
     }
 }
 
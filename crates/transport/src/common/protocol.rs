@@ -26,6 +26,19 @@ pub fn protocol_version_to_string(version: ProtocolVersion) -> String {
     }
 }
 
+/// Clamp a requested protocol version to the configured maximum
+///
+/// Version strings are release dates, so they order lexically.
+pub fn clamp_protocol_version(
+    requested: ProtocolVersion,
+    max: Option<ProtocolVersion>,
+) -> ProtocolVersion {
+    match max {
+        Some(max) if protocol_version_to_string(requested) > protocol_version_to_string(max) => max,
+        _ => requested,
+    }
+}
+
 /// Convert LogLevel enum to string
 pub fn log_level_to_string(level: crate::bindings::wasmcp::mcp_v20251125::mcp::LogLevel) -> String {
     use crate::bindings::wasmcp::mcp_v20251125::mcp::LogLevel;
@@ -56,6 +69,8 @@ pub fn create_session(
 /// Create MessageContext with common parameters
 ///
 /// This eliminates duplication of MessageContext construction across the codebase.
+/// Client capabilities and log level are taken from the connection state, and
+/// the deadline from the message currently being handled.
 pub fn create_message_context<'a>(
     client_stream: Option<&'a OutputStream>,
     protocol_version: ProtocolVersion,
@@ -72,5 +87,12 @@ pub fn create_message_context<'a>(
         identity: identity.cloned(),
         frame: frame.clone(),
         http_context,
+        client_capabilities: crate::common::capability::negotiated_client_capabilities(),
+        request_id: None,
+        deadline: crate::common::deadline::current_deadline(),
+        log_level: crate::common::log_level::client_log_level(),
+        client_info: crate::common::client_info::client_info(),
+        locale: crate::common::locale::client_locale(),
+        trace_context: crate::common::trace_context::trace_context(),
     }
 }
@@ -0,0 +1,268 @@
+//! Response size budget for tool results and resource reads
+//!
+//! Clients reject (or choke on) oversized frames, so results over
+//! `WASMCP_MAX_RESPONSE_BYTES` are reshaped before they are written,
+//! according to `WASMCP_RESPONSE_OVERFLOW`:
+//! - `truncate`: keep content in order until the budget runs out, cut the
+//!   text block that crosses it and append a marker; later blocks are dropped
+//! - `link`: replace embedded resources that don't fit with resource links
+//!   to their URI, then truncate whatever still doesn't fit
+//! - `error`: fail the request
+//!
+//! Sizes count the payload as sent (text bytes, base64-encoded blob bytes,
+//! structured content), not the JSON-RPC envelope. Streamed text and blobs
+//! can't be measured up front; server-io bounds those as it reads them.
+
+use crate::bindings::wasmcp::mcp_v20251125::mcp::{
+    BlobData, BlobResourceContents, CallToolResult, ContentBlock, EmbeddedResourceContent,
+    EmbeddedResourceOptions, ErrorCode, ReadResourceResult, ResourceContents, ResourceLinkContent,
+    ResourceLinkOptions, ServerResult, TextContent, TextData, TextResourceContents,
+};
+use crate::config::ResponseOverflow;
+use std::cell::Cell;
+use wasmcp_errors::McpError;
+
+thread_local! {
+    /// Budget for the message currently being handled (None = unlimited)
+    static BUDGET: Cell<Option<(u64, ResponseOverflow)>> = const { Cell::new(None) };
+}
+
+/// Set the budget for a new message
+pub fn configure(max_bytes: Option<u64>, overflow: ResponseOverflow) {
+    BUDGET.with(|b| b.set(max_bytes.map(|max| (max, overflow))));
+}
+
+/// Apply the budget to a handler's result
+pub fn enforce(result: ServerResult) -> Result<ServerResult, ErrorCode> {
+    let Some((max_bytes, overflow)) = BUDGET.with(Cell::get) else {
+        return Ok(result);
+    };
+
+    let size = match &result {
+        ServerResult::ToolsCall(r) => tool_result_size(r),
+        ServerResult::ResourcesRead(r) => r.contents.iter().map(contents_size).sum(),
+        _ => return Ok(result),
+    };
+    if size <= max_bytes {
+        return Ok(result);
+    }
+
+    eprintln!(
+        "[transport] WARNING: Response of {} bytes exceeds WASMCP_MAX_RESPONSE_BYTES={} ({:?})",
+        size, max_bytes, overflow
+    );
+
+    match (result, overflow) {
+        (_, ResponseOverflow::Error) => Err(McpError::internal(format!(
+            "Response of {} bytes exceeds the {} byte limit",
+            size, max_bytes
+        ))
+        .with_data(format!(r#"{{"size":{},"maxBytes":{}}}"#, size, max_bytes))
+        .into()),
+        (ServerResult::ToolsCall(r), overflow) => Ok(ServerResult::ToolsCall(fit_tool_result(
+            r,
+            max_bytes,
+            overflow == ResponseOverflow::Link,
+        ))),
+        (ServerResult::ResourcesRead(r), _) => {
+            Ok(ServerResult::ResourcesRead(fit_read_result(r, max_bytes)))
+        }
+        (other, _) => Ok(other),
+    }
+}
+
+fn fit_tool_result(mut result: CallToolResult, max_bytes: u64, link: bool) -> CallToolResult {
+    let mut remaining = max_bytes;
+
+    // Structured content can't be cut meaningfully; drop it if it alone is too big
+    let structured = result
+        .structured_content
+        .as_ref()
+        .map_or(0, |s| s.len() as u64);
+    if structured > remaining {
+        result.structured_content = None;
+    } else {
+        remaining -= structured;
+    }
+
+    let mut content = Vec::with_capacity(result.content.len());
+    for block in std::mem::take(&mut result.content) {
+        let size = block_size(&block);
+        if size <= remaining {
+            remaining -= size;
+            content.push(block);
+            continue;
+        }
+
+        if link && let ContentBlock::EmbeddedResource(embedded) = &block {
+            let resource_link = to_resource_link(embedded);
+            let link_size = block_size(&resource_link);
+            if link_size <= remaining {
+                remaining -= link_size;
+                content.push(resource_link);
+                continue;
+            }
+        }
+
+        content.push(match block {
+            ContentBlock::Text(TextContent {
+                text: TextData::Text(text),
+                options,
+            }) => ContentBlock::Text(TextContent {
+                text: TextData::Text(truncate_text(&text, remaining, max_bytes)),
+                options,
+            }),
+            _ => ContentBlock::Text(TextContent {
+                text: TextData::Text(marker(max_bytes).trim_start().to_string()),
+                options: None,
+            }),
+        });
+        break;
+    }
+
+    result.content = content;
+    result
+}
+
+fn fit_read_result(mut result: ReadResourceResult, max_bytes: u64) -> ReadResourceResult {
+    let mut remaining = max_bytes;
+
+    let mut contents = Vec::with_capacity(result.contents.len());
+    for entry in std::mem::take(&mut result.contents) {
+        let size = contents_size(&entry);
+        if size <= remaining {
+            remaining -= size;
+            contents.push(entry);
+            continue;
+        }
+
+        contents.push(match entry {
+            ResourceContents::Text(TextResourceContents {
+                uri,
+                text: TextData::Text(text),
+                options,
+            }) => ResourceContents::Text(TextResourceContents {
+                uri,
+                text: TextData::Text(truncate_text(&text, remaining, max_bytes)),
+                options,
+            }),
+            ResourceContents::Text(TextResourceContents { uri, .. })
+            | ResourceContents::Blob(BlobResourceContents { uri, .. }) => {
+                ResourceContents::Text(TextResourceContents {
+                    uri,
+                    text: TextData::Text(marker(max_bytes).trim_start().to_string()),
+                    options: Some(EmbeddedResourceOptions {
+                        mime_type: Some("text/plain".to_string()),
+                        meta: None,
+                    }),
+                })
+            }
+        });
+        break;
+    }
+
+    result.contents = contents;
+    result
+}
+
+fn to_resource_link(embedded: &EmbeddedResourceContent) -> ContentBlock {
+    // Links report the resource's own size, not its encoded size
+    let (uri, options, size) = match &embedded.resource {
+        ResourceContents::Text(t) => (
+            &t.uri,
+            &t.options,
+            match &t.text {
+                TextData::Text(s) => Some(s.len() as u64),
+                TextData::TextStream(_) => None,
+            },
+        ),
+        ResourceContents::Blob(b) => (
+            &b.uri,
+            &b.options,
+            match &b.blob {
+                BlobData::Blob(bytes) => Some(bytes.len() as u64),
+                BlobData::BlobStream(_) => None,
+            },
+        ),
+    };
+    let name = uri
+        .trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .filter(|n| !n.is_empty())
+        .unwrap_or(uri);
+
+    ContentBlock::ResourceLink(ResourceLinkContent {
+        uri: uri.clone(),
+        name: name.to_string(),
+        options: Some(ResourceLinkOptions {
+            title: None,
+            description: None,
+            size,
+            mime_type: options.as_ref().and_then(|o| o.mime_type.clone()),
+            annotations: None,
+            meta: None,
+            icons: None,
+        }),
+    })
+}
+
+/// Cut text to fit `remaining` bytes including the marker
+fn truncate_text(text: &str, remaining: u64, max_bytes: u64) -> String {
+    let marker = marker(max_bytes);
+    let keep = (remaining as usize)
+        .saturating_sub(marker.len())
+        .min(text.len());
+    // Back off to a character boundary
+    let keep = (0..=keep)
+        .rev()
+        .find(|&i| text.is_char_boundary(i))
+        .unwrap_or(0);
+    format!("{}{}", &text[..keep], marker)
+}
+
+fn marker(max_bytes: u64) -> String {
+    format!(
+        "\n\n[Truncated: response exceeded the {} byte limit]",
+        max_bytes
+    )
+}
+
+fn tool_result_size(result: &CallToolResult) -> u64 {
+    let structured = result
+        .structured_content
+        .as_ref()
+        .map_or(0, |s| s.len() as u64);
+    structured + result.content.iter().map(block_size).sum::<u64>()
+}
+
+fn block_size(block: &ContentBlock) -> u64 {
+    match block {
+        ContentBlock::Text(t) => text_size(&t.text),
+        ContentBlock::Image(b) | ContentBlock::Audio(b) => blob_size(&b.data),
+        ContentBlock::ResourceLink(l) => (l.uri.len() + l.name.len()) as u64,
+        ContentBlock::EmbeddedResource(e) => contents_size(&e.resource),
+    }
+}
+
+fn contents_size(contents: &ResourceContents) -> u64 {
+    match contents {
+        ResourceContents::Text(t) => text_size(&t.text),
+        ResourceContents::Blob(b) => blob_size(&b.blob),
+    }
+}
+
+fn text_size(text: &TextData) -> u64 {
+    match text {
+        TextData::Text(s) => s.len() as u64,
+        TextData::TextStream(_) => 0,
+    }
+}
+
+/// Base64-encoded size
+fn blob_size(blob: &BlobData) -> u64 {
+    match blob {
+        BlobData::Blob(bytes) => (bytes.len() as u64).div_ceil(3) * 4,
+        BlobData::BlobStream(_) => 0,
+    }
+}
@@ -0,0 +1,155 @@
+//! Server metadata advertised in the initialize response
+//!
+//! The `serverInfo` and `instructions` fields are resolved in three layers,
+//! later layers taking precedence:
+//!
+//! 1. Built-in defaults (`wasmcp-server`, transport crate version, no instructions)
+//! 2. Environment variables (`WASMCP_SERVER_NAME`, `WASMCP_SERVER_TITLE`,
+//!    `WASMCP_SERVER_VERSION`, `WASMCP_SERVER_INSTRUCTIONS`)
+//! 3. Runtime override: the initialize request is forwarded down the handler
+//!    chain, and if any handler answers with an initialize result, its
+//!    `server-info` (when the name is non-empty) and `options` replace the
+//!    configured values.
+//!
+//! Capabilities are discovered by probing the handler chain (see
+//! `capability.rs`), unless the handler's initialize result sets `lists`: that
+//! declares which lists the server supports, and the list, completion and
+//! subscription probes are skipped. A handler's initialize result can override them field by
+//! field: every capability it sets (`completions`, `logging`, `list-changed`,
+//! `subscriptions`, `lists`) replaces the discovered value, and fields left as
+//! none keep it. This lets declaring servers advertise `subscribe` or turn off
+//! `listChanged` for lists that never change. Experimental capabilities are
+//! merged rather than replaced, so extensions can be advertised without
+//! touching server metadata (see the custom-middleware component).
+//!
+//! Handlers that don't care about initialize simply pass it through; the
+//! terminal method-not-found handler rejects it and the configured values are used.
+
+use crate::bindings::wasi::cli::environment::get_environment;
+use crate::bindings::wasmcp::mcp_v20251125::mcp::{
+    ClientMessage, ClientRequest, Implementation, InitializeRequest, InitializeResult,
+    InitializeResultOptions, ProtocolVersion, RequestId, ServerCapabilities, ServerResult,
+};
+use crate::bindings::wasmcp::mcp_v20251125::server_handler::handle;
+use crate::bindings::wasmcp::mcp_v20251125::server_io::MessageFrame;
+use crate::common::capability;
+use crate::common::protocol::create_message_context;
+use crate::config::TransportConfig;
+use std::collections::HashMap;
+
+/// Request ID for the initialize override probe (matches capability probes)
+const INITIALIZE_PROBE_REQUEST_ID: i64 = -1;
+
+/// Default server name when `WASMCP_SERVER_NAME` is not set
+const DEFAULT_SERVER_NAME: &str = "wasmcp-server";
+
+/// Default server title when `WASMCP_SERVER_TITLE` is not set
+const DEFAULT_SERVER_TITLE: &str = "wasmcp Universal Transport Server";
+
+/// Build the initialize result for a client's initialize request
+///
+/// `init_req` is forwarded downstream for the runtime override; pass `None`
+/// to skip the probe and use configured metadata only.
+pub fn build_initialize_result(
+    init_req: Option<&InitializeRequest>,
+    protocol_version: ProtocolVersion,
+    frame: &MessageFrame,
+    config: &TransportConfig,
+) -> InitializeResult {
+    let (mut server_info, mut options) = configured_metadata();
+    let handler_result =
+        init_req.and_then(|req| probe_initialize_override(req, protocol_version, frame));
+
+    // A handler that declares its lists knows its features; don't probe
+    let mut capabilities = match handler_result.as_ref().and_then(|r| r.capabilities.lists) {
+        Some(lists) => capability::declared_capabilities(lists),
+        None => capability::discover_capabilities_for_init(protocol_version, frame, config),
+    };
+
+    if let Some(result) = handler_result {
+        // An empty name means the handler only contributes capabilities
+        if !result.server_info.name.is_empty() {
+            server_info = result.server_info;
+        }
+        if result.options.is_some() {
+            options = result.options;
+        }
+        merge_capabilities(&mut capabilities, result.capabilities);
+    }
+
+    InitializeResult {
+        meta: None,
+        server_info,
+        capabilities,
+        protocol_version,
+        options,
+    }
+}
+
+/// Apply a handler's capabilities on top of the discovered ones
+fn merge_capabilities(capabilities: &mut ServerCapabilities, handler: ServerCapabilities) {
+    if handler.completions.is_some() {
+        capabilities.completions = handler.completions;
+    }
+    if handler.logging.is_some() {
+        capabilities.logging = handler.logging;
+    }
+    if handler.list_changed.is_some() {
+        capabilities.list_changed = handler.list_changed;
+    }
+    if handler.subscriptions.is_some() {
+        capabilities.subscriptions = handler.subscriptions;
+    }
+    if handler.lists.is_some() {
+        capabilities.lists = handler.lists;
+    }
+    if let Some(experimental) = handler.experimental {
+        capabilities
+            .experimental
+            .get_or_insert_with(Vec::new)
+            .extend(experimental);
+    }
+}
+
+/// Server info and instructions from environment variables, with defaults
+fn configured_metadata() -> (Implementation, Option<InitializeResultOptions>) {
+    let env_map: HashMap<String, String> = get_environment().into_iter().collect();
+    let get = |key: &str| env_map.get(key).filter(|v| !v.is_empty()).cloned();
+
+    let server_info = Implementation {
+        name: get("WASMCP_SERVER_NAME").unwrap_or_else(|| DEFAULT_SERVER_NAME.to_string()),
+        title: Some(get("WASMCP_SERVER_TITLE").unwrap_or_else(|| DEFAULT_SERVER_TITLE.to_string())),
+        version: get("WASMCP_SERVER_VERSION")
+            .unwrap_or_else(|| env!("CARGO_PKG_VERSION").to_string()),
+        description: None,
+        icons: None,
+    };
+
+    let options = get("WASMCP_SERVER_INSTRUCTIONS").map(|instructions| InitializeResultOptions {
+        instructions: Some(instructions),
+        meta: None,
+    });
+
+    (server_info, options)
+}
+
+/// Ask the handler chain whether it wants to supply its own initialize result
+///
+/// Only a successful `initialize` result counts as an override. Errors (including
+/// method-not-found from the end of the chain) fall back to configured metadata.
+fn probe_initialize_override(
+    init_req: &InitializeRequest,
+    protocol_version: ProtocolVersion,
+    frame: &MessageFrame,
+) -> Option<InitializeResult> {
+    let ctx = create_message_context(None, protocol_version, None, None, "", frame, None);
+    let message = ClientMessage::Request((
+        RequestId::Number(INITIALIZE_PROBE_REQUEST_ID),
+        ClientRequest::Initialize(init_req.clone()),
+    ));
+
+    match handle(&ctx, message) {
+        Some(Ok(ServerResult::Initialize(result))) => Some(result),
+        _ => None,
+    }
+}
@@ -0,0 +1,58 @@
+//! W3C trace context tracking (`traceparent`)
+//!
+//! The caller's trace context is surfaced on every MessageContext so handlers
+//! and middleware read it from one typed field instead of agreeing on a
+//! header or `_meta` key. Only well-formed values are passed on; a malformed
+//! header is dropped, as the W3C spec requires, so handlers start a new trace.
+
+use std::cell::RefCell;
+
+thread_local! {
+    /// `traceparent` of the message being handled
+    ///
+    /// HTTP: set per request from the `traceparent` header.
+    /// stdio: never set, there are no headers.
+    static TRACE_CONTEXT: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Record the trace context of the current request
+pub fn set_trace_context(trace_context: Option<String>) {
+    TRACE_CONTEXT.with(|t| *t.borrow_mut() = trace_context);
+}
+
+/// Get the trace context of the current request, if the client sent one
+pub fn trace_context() -> Option<String> {
+    TRACE_CONTEXT.with(|t| t.borrow().clone())
+}
+
+/// A `traceparent` header value, if it is well-formed
+///
+/// Accepts `version-traceid-parentid-flags` in lowercase hex. Version `ff`
+/// and all-zero IDs are invalid; later versions may append fields, which
+/// are kept.
+pub fn parse_traceparent(header: &str) -> Option<String> {
+    let header = header.trim();
+    let mut parts = header.split('-');
+    let version = parts.next()?;
+    let trace_id = parts.next()?;
+    let parent_id = parts.next()?;
+    let flags = parts.next()?;
+
+    let is_hex = |s: &str, len: usize| {
+        s.len() == len
+            && s.bytes()
+                .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b))
+    };
+    let is_zero = |s: &str| s.bytes().all(|b| b == b'0');
+
+    let valid = is_hex(version, 2)
+        && version != "ff"
+        && is_hex(trace_id, 32)
+        && !is_zero(trace_id)
+        && is_hex(parent_id, 16)
+        && !is_zero(parent_id)
+        && is_hex(flags, 2)
+        // Version 00 has exactly four fields
+        && (version != "00" || parts.next().is_none());
+    valid.then(|| header.to_string())
+}
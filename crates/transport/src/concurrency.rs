@@ -0,0 +1,253 @@
+//! Tool invocation concurrency limiting
+//!
+//! Limits how many `tools/call` requests may execute at the same time, both
+//! per session and across the whole server. Each HTTP request runs in its own
+//! component instance, so in-memory semaphores cannot see each other - the
+//! slots live in the session key-value bucket and are claimed with the
+//! store's atomic `increment`.
+//!
+//! Tools can also share a serialization group (`WASMCP_TOOL_GROUPS`): at
+//! most one call per group runs at a time, per session (or server-wide
+//! without sessions), so tools updating the same session state don't race
+//! while tools in other groups, or in none, still run alongside them.
+//!
+//! When a limit is saturated the transport either waits briefly for a slot
+//! (`WASMCP_TOOL_QUEUE_TIMEOUT_MS`) or rejects the call with a rate-limited error
+//! carrying a Retry-After hint.
+//!
+//! Slots are leases rather than bare counters, so a call that never releases
+//! its slot (the instance trapped, or the host killed it at its timeout)
+//! only holds it until the lease expires. A limit of N is N slot keys, each
+//! holding the expiry of the lease on it (wall-clock milliseconds, 0 or
+//! missing when free). Acquiring first reads every slot of a limit and only
+//! tries the free and expired ones. A claim is one `increment` by the
+//! difference between the new expiry and the value read: the result equals
+//! the new expiry only if the slot still held that value, so of two calls
+//! taking over the same free or expired slot exactly one gets it, and the
+//! other undoes its increment.
+//!
+//! Leases last `WASMCP_TOOL_LEASE_SECS`, which must exceed the longest tool
+//! call, or a slow call loses its slot to the next one. Slots holding
+//! something other than a lease can't be claimed that way; they are deleted
+//! first, and only for them two calls can both get the slot.
+
+use crate::bindings::wasi::clocks::{monotonic_clock, wall_clock};
+use crate::bindings::wasmcp::keyvalue::store::{self as kv_store, Bucket, TypedValue};
+use crate::config::TransportConfig;
+use crate::error::TransportError;
+
+/// KV key prefix of the slots limiting tool calls across all sessions
+const GLOBAL_SLOTS_KEY: &str = "wasmcp:concurrency:tools";
+
+/// Interval between slot acquisition attempts while queueing (10ms)
+const QUEUE_POLL_INTERVAL_NS: u64 = 10_000_000;
+
+/// KV key prefix of the slots limiting tool calls for a session
+fn session_slots_key(session_id: &str) -> String {
+    format!("{}:concurrency:tools", session_id)
+}
+
+/// KV key prefix of a serialization group's slot, per session if any
+fn group_slots_key(session_id: Option<&str>, group: &str) -> String {
+    match session_id {
+        Some(id) => format!("{}:concurrency:group:{}", id, group),
+        None => format!("wasmcp:concurrency:group:{}", group),
+    }
+}
+
+/// KV key of one slot of a limit
+fn slot_key(slots_key: &str, slot: u32) -> String {
+    format!("{}:{}", slots_key, slot)
+}
+
+/// A held tool execution slot
+///
+/// Releases every lease it holds when dropped, so the permit should be kept
+/// alive for the full duration of the tool call. Leases a dropped permit
+/// never released expire on their own.
+pub struct ToolPermit {
+    bucket: Bucket,
+    slots: Vec<String>,
+}
+
+impl Drop for ToolPermit {
+    fn drop(&mut self) {
+        if let Err(e) = self.bucket.delete_many(&self.slots) {
+            eprintln!(
+                "[transport:concurrency] Failed to release slots {:?}: {:?}",
+                self.slots, e
+            );
+        }
+    }
+}
+
+/// Acquire a tool execution slot for a call to `tool` in the given session
+///
+/// Returns:
+/// - Ok(None) if no concurrency limits are configured
+/// - Ok(Some(permit)) once a slot is held under every configured limit
+/// - Err(TransportError::RateLimited) if no slot frees up before the queue timeout
+pub fn acquire_tool_permit(
+    config: &TransportConfig,
+    session_id: Option<&str>,
+    tool: &str,
+) -> Result<Option<ToolPermit>, TransportError> {
+    // Per-session limits only apply when there is a session to key them on
+    let session_limit = config
+        .max_concurrent_tools_per_session
+        .zip(session_id)
+        .map(|(limit, id)| (session_slots_key(id), limit));
+    let global_limit = config
+        .max_concurrent_tools
+        .map(|limit| (GLOBAL_SLOTS_KEY.to_string(), limit));
+
+    let group_limit = config
+        .tool_groups
+        .get(tool)
+        .map(|group| (group_slots_key(session_id, group), 1));
+
+    let limits: Vec<(String, u32)> = group_limit
+        .into_iter()
+        .chain(session_limit)
+        .chain(global_limit)
+        .collect();
+    if limits.is_empty() {
+        return Ok(None);
+    }
+
+    let bucket = kv_store::open(config.get_session_bucket()).map_err(|e| {
+        TransportError::internal(format!(
+            "Failed to open bucket for concurrency limits: {:?}",
+            e
+        ))
+    })?;
+
+    let deadline = monotonic_clock::now()
+        .saturating_add(config.tool_queue_timeout_ms.saturating_mul(1_000_000));
+
+    loop {
+        match try_acquire(&bucket, &limits, config.tool_lease_secs)? {
+            Some(slots) => return Ok(Some(ToolPermit { bucket, slots })),
+            None if monotonic_clock::now() < deadline => {
+                monotonic_clock::subscribe_duration(QUEUE_POLL_INTERVAL_NS).block();
+            }
+            None => {
+                return Err(TransportError::rate_limited(
+                    "Too many concurrent tool calls, retry later",
+                    retry_after_secs(config),
+                ));
+            }
+        }
+    }
+}
+
+/// Suggested Retry-After for a rejected call
+///
+/// No slot freed up within the queue timeout, so retrying sooner than that is
+/// unlikely to help; without queueing, a second is enough to smooth bursts.
+fn retry_after_secs(config: &TransportConfig) -> u64 {
+    config.tool_queue_timeout_ms.div_ceil(1000).max(1)
+}
+
+/// Attempt to take one slot under every limit
+///
+/// Claims a slot per limit in turn; if any limit has no free slot, the
+/// slots claimed so far are released and `None` is returned.
+fn try_acquire(
+    bucket: &Bucket,
+    limits: &[(String, u32)],
+    lease_secs: u64,
+) -> Result<Option<Vec<String>>, TransportError> {
+    let now = now_ms();
+    let lease_ms = i64::try_from(lease_secs.saturating_mul(1000)).unwrap_or(i64::MAX);
+    let expires_at = now.saturating_add(lease_ms);
+    let mut acquired: Vec<String> = Vec::with_capacity(limits.len());
+
+    for (slots_key, limit) in limits {
+        match claim_any(bucket, slots_key, *limit, now, expires_at) {
+            Ok(Some(slot)) => acquired.push(slot),
+            Ok(None) => {
+                release(bucket, &acquired);
+                return Ok(None);
+            }
+            Err(e) => {
+                release(bucket, &acquired);
+                return Err(e);
+            }
+        }
+    }
+
+    Ok(Some(acquired))
+}
+
+/// Claim a free or expired slot of one limit, returning its key
+fn claim_any(
+    bucket: &Bucket,
+    slots_key: &str,
+    limit: u32,
+    now: i64,
+    expires_at: i64,
+) -> Result<Option<String>, TransportError> {
+    let keys: Vec<String> = (0..limit).map(|slot| slot_key(slots_key, slot)).collect();
+    let leases = bucket.get_many(&keys).map_err(storage_error)?;
+
+    for (key, lease) in keys.iter().zip(leases) {
+        let held_until = match lease {
+            None => 0,
+            Some((_, TypedValue::AsS64(held_until))) if held_until >= 0 => held_until,
+            // Not a lease; sweep it so the slot can be claimed from empty
+            Some(_) => {
+                bucket.delete(key).map_err(storage_error)?;
+                0
+            }
+        };
+        if held_until > now {
+            continue;
+        }
+        if claim(bucket, key, held_until, expires_at)? {
+            return Ok(Some(key.clone()));
+        }
+    }
+    Ok(None)
+}
+
+/// Take over a slot last seen holding `seen`, storing the lease's expiry
+///
+/// Fails (false) if the slot changed since it was read; the increment is
+/// then undone.
+fn claim(bucket: &Bucket, key: &str, seen: i64, expires_at: i64) -> Result<bool, TransportError> {
+    let delta = expires_at - seen;
+    let value = bucket.increment(key, delta).map_err(storage_error)?;
+    if value == expires_at {
+        return Ok(true);
+    }
+    if let Err(e) = bucket.increment(key, -delta) {
+        // The slot now looks held far into the future, until its holder
+        // releases it
+        eprintln!(
+            "[transport:concurrency] Failed to undo claim on {}: {:?}",
+            key, e
+        );
+    }
+    Ok(false)
+}
+
+/// Release slots claimed during a failed acquisition attempt
+fn release(bucket: &Bucket, slots: &[String]) {
+    if !slots.is_empty() {
+        let _ = bucket.delete_many(slots);
+    }
+}
+
+/// Wall-clock time in milliseconds since the Unix epoch
+fn now_ms() -> i64 {
+    let now = wall_clock::now();
+    i64::try_from(now.seconds)
+        .unwrap_or(i64::MAX)
+        .saturating_mul(1000)
+        .saturating_add(i64::from(now.nanoseconds / 1_000_000))
+}
+
+fn storage_error(e: impl std::fmt::Debug) -> TransportError {
+    TransportError::internal(format!("Failed to update concurrency slots: {:?}", e))
+}
@@ -3,14 +3,45 @@
 //! Environment variables:
 //! - `WASMCP_SESSION_ENABLED`: "true"/"false" (default: "false") - Enable session support
 //! - `WASMCP_SESSION_BUCKET`: Bucket name (default: "") - KV bucket for sessions
+//! - `WASMCP_SESSION_BINDING`: "true"/"false" (default: "true") - Tie sessions to the token identity
 //! - `WASMCP_DISABLE_SSE`: "true"/"false" (default: "false") - Use plain JSON instead of SSE for HTTP
+//! - `WASMCP_SSE_ON_DEMAND`: "true"/"false" (default: "false") - Answer with SSE only when handlers send interim messages
 //! - `WASMCP_AUTH_MODE`: "public"/"oauth" (default: "public") - Authentication mode
 //! - `JWT_PUBLIC_KEY`: PEM-encoded public key (optional, alternative to JWT_JWKS_URI)
 //! - `JWT_JWKS_URI`: JWKS endpoint URL (optional, alternative to JWT_PUBLIC_KEY)
+//! - `WASMCP_DPOP`: "off"/"optional"/"required" (default: "off") - DPoP-bound tokens (RFC 9449)
+//! - `WASMCP_DPOP_MAX_AGE_SECS`: How far a DPoP proof's iat may be from now (default: "300")
+//! - `WASMCP_DEV_JWKS`: JWKS JSON served at /.well-known/jwks.json (optional, development only)
+//! - `WASMCP_MAX_CONCURRENT_TOOLS`: Max in-flight tools/call across all sessions (optional)
+//! - `WASMCP_MAX_CONCURRENT_TOOLS_PER_SESSION`: Max in-flight tools/call per session (optional)
+//! - `WASMCP_TOOL_QUEUE_TIMEOUT_MS`: How long to wait for a free slot (default: "0")
+//! - `WASMCP_TOOL_GROUPS`: Serialization groups, e.g. "add_todo=todos,remove_todo=todos" (optional)
+//! - `WASMCP_REQUEST_TIMEOUT_MS`: Response budget exposed to handlers as a deadline (optional)
+//! - `WASMCP_MAX_PROTOCOL_VERSION`: Newest protocol version to negotiate (optional)
+//! - `WASMCP_METRICS_ENABLED`: "true"/"false" (default: "false") - Serve Prometheus metrics at /metrics
+//! - `WASMCP_REST_ENABLED`: "true"/"false" (default: "false") - Serve tools at POST /tools/{name}
+//! - `WASMCP_ACCESS_LOG`: "true"/"false" (default: "false") - JSON access log lines on stderr
+//! - `WASMCP_ACCESS_LOG_FIELDS`: Comma-separated access log fields (default: all)
+//! - `WASMCP_STDIO_RESUME_TOKEN`: Checkpoint stdio connection state under this token (optional)
+//! - `WASMCP_CAPABILITY_CACHE_TTL`: Seconds other instances reuse discovered capabilities (default: "0")
 
 use crate::bindings::wasi::cli::environment::get_environment;
+use crate::bindings::wasmcp::mcp_v20251125::mcp::ProtocolVersion;
+use crate::common::{access_log, parse_protocol_version};
 use std::collections::HashMap;
 
+/// Headers never forwarded to handlers, even when listed in `WASMCP_FORWARD_HEADERS`
+///
+/// Handlers see the verified identity, not the raw credentials, so that a
+/// logging or third-party middleware can't leak them.
+const CREDENTIAL_HEADERS: &[&str] = &["authorization", "proxy-authorization", "cookie", "dpop"];
+
+/// Default distance allowed between a DPoP proof's iat and now (5 minutes)
+const DEFAULT_DPOP_MAX_AGE_SECS: u64 = 300;
+
+/// Default lifetime of a tool concurrency slot (10 minutes)
+const DEFAULT_TOOL_LEASE_SECS: u64 = 600;
+
 /// Authentication mode for MCP server
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum AuthMode {
@@ -21,19 +52,102 @@ pub enum AuthMode {
     OAuth,
 }
 
+/// DPoP (RFC 9449) sender-constrained token support
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DpopMode {
+    /// Tokens are only accepted with the Bearer scheme (default)
+    #[default]
+    Off,
+    /// Tokens bound to a key (`cnf.jkt`) need a DPoP proof; unbound bearer tokens still work
+    Optional,
+    /// Every token must be bound to a key and come with a DPoP proof
+    Required,
+}
+
+impl DpopMode {
+    /// Parse a `WASMCP_DPOP` value (case-insensitive)
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "off" => Some(Self::Off),
+            "optional" => Some(Self::Optional),
+            "required" => Some(Self::Required),
+            _ => None,
+        }
+    }
+}
+
+/// What to do with a tool result or resource read over the response budget
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ResponseOverflow {
+    /// Cut content to fit and append a truncation marker (default)
+    #[default]
+    Truncate,
+    /// Replace oversized embedded resources with resource links, then truncate
+    Link,
+    /// Fail the request with an error
+    Error,
+}
+
 /// Transport configuration from environment variables
 #[derive(Debug, Clone)]
 pub struct TransportConfig {
     // Session configuration
     pub session_enabled: bool,
     pub session_bucket_name: String,
+    pub session_binding: bool,
 
     // HTTP mode (SSE vs plain JSON)
     pub disable_sse: bool,
+    // Choose SSE or plain JSON per request, after the handler returns
+    pub sse_on_demand: bool,
+    // Lifetime of GET listening streams in seconds (0 = not served)
+    pub sse_listen_secs: u64,
 
     // Authentication configuration
     pub auth_mode: AuthMode,
     pub jwt_configured: bool,
+    pub dpop: DpopMode,
+    // Largest distance between a DPoP proof's iat and now, in seconds
+    pub dpop_max_age_secs: u64,
+    // Self-issued key set served at GET /.well-known/jwks.json (None = not served)
+    pub dev_jwks: Option<String>,
+
+    // Tool concurrency limits (None = unlimited)
+    pub max_concurrent_tools: Option<u32>,
+    pub max_concurrent_tools_per_session: Option<u32>,
+    pub tool_queue_timeout_ms: u64,
+    // Lifetime of a held slot; an unreleased slot frees up after it
+    pub tool_lease_secs: u64,
+    // Serialization group per tool; one call per group runs at a time
+    pub tool_groups: HashMap<String, String>,
+
+    // Per-request response budget (None = no deadline)
+    pub request_timeout_ms: Option<u64>,
+
+    // Extra request headers exposed to handlers via http-context (lowercase)
+    pub forward_headers: Vec<String>,
+
+    // Largest tool result / resource read payload (None = unlimited)
+    pub max_response_bytes: Option<u64>,
+    pub response_overflow: ResponseOverflow,
+
+    // Newest protocol version offered at initialize (None = latest supported)
+    pub max_protocol_version: Option<ProtocolVersion>,
+
+    // Prometheus metrics at GET /metrics
+    pub metrics_enabled: bool,
+
+    // Plain JSON tool calls at POST /tools/{name}
+    pub rest_enabled: bool,
+
+    // Fields written to the access log (None = access log disabled)
+    pub access_log: Option<Vec<String>>,
+
+    // Token stdio connection state is checkpointed under (None = no checkpoints)
+    pub stdio_resume_token: Option<String>,
+
+    // Seconds discovered capabilities are shared across instances (0 = not shared)
+    pub capability_cache_ttl: u64,
 }
 
 impl TransportConfig {
@@ -42,10 +156,32 @@ impl TransportConfig {
     /// Reads all configuration in a single pass:
     /// - `WASMCP_SESSION_ENABLED`: "true"/"false" (case-insensitive, default: false)
     /// - `WASMCP_SESSION_BUCKET`: Bucket name (default: empty string)
+    /// - `WASMCP_SESSION_BINDING`: "true"/"false" (case-insensitive, default: true)
     /// - `WASMCP_DISABLE_SSE`: "true"/"false" (case-insensitive, default: false)
+    /// - `WASMCP_SSE_ON_DEMAND`: "true"/"false" (case-insensitive, default: false)
+    /// - `WASMCP_SSE_LISTEN_SECS`: Seconds (default: 0, no listening streams)
     /// - `WASMCP_AUTH_MODE`: "public"/"oauth" (case-insensitive, default: public)
     /// - `JWT_PUBLIC_KEY`: PEM public key (optional)
     /// - `JWT_JWKS_URI`: JWKS endpoint URL (optional)
+    /// - `WASMCP_DPOP`: "off"/"optional"/"required" (case-insensitive, default: off)
+    /// - `WASMCP_DPOP_MAX_AGE_SECS`: Positive integer seconds (default: 300)
+    /// - `WASMCP_DEV_JWKS`: JWKS JSON with a `keys` array (optional, default: not served)
+    /// - `WASMCP_MAX_CONCURRENT_TOOLS`: Positive integer (optional, default: unlimited)
+    /// - `WASMCP_MAX_CONCURRENT_TOOLS_PER_SESSION`: Positive integer (optional, default: unlimited)
+    /// - `WASMCP_TOOL_QUEUE_TIMEOUT_MS`: Milliseconds (default: 0, reject immediately)
+    /// - `WASMCP_TOOL_GROUPS`: Comma-separated `tool=group` pairs (optional, default: no groups)
+    /// - `WASMCP_REQUEST_TIMEOUT_MS`: Positive integer milliseconds (optional, default: no deadline)
+    /// - `WASMCP_FORWARD_HEADERS`: Comma-separated header names added to http-context (optional,
+    ///   credential headers are dropped)
+    /// - `WASMCP_MAX_RESPONSE_BYTES`: Positive integer (optional, default: unlimited)
+    /// - `WASMCP_RESPONSE_OVERFLOW`: "truncate"/"link"/"error" (case-insensitive, default: truncate)
+    /// - `WASMCP_MAX_PROTOCOL_VERSION`: Protocol version date, e.g. "2025-03-26" (optional)
+    /// - `WASMCP_METRICS_ENABLED`: "true"/"false" (case-insensitive, default: false)
+    /// - `WASMCP_REST_ENABLED`: "true"/"false" (case-insensitive, default: false)
+    /// - `WASMCP_ACCESS_LOG`: "true"/"false" (case-insensitive, default: false)
+    /// - `WASMCP_ACCESS_LOG_FIELDS`: Comma-separated field names (optional, default: all fields)
+    /// - `WASMCP_STDIO_RESUME_TOKEN`: Non-empty token (optional, default: no checkpoints)
+    /// - `WASMCP_CAPABILITY_CACHE_TTL`: Seconds (default: 0, every instance probes)
     pub fn from_env() -> Self {
         let env_vars = get_environment();
         let env_map: HashMap<String, String> = env_vars.into_iter().collect();
@@ -61,11 +197,25 @@ impl TransportConfig {
             .cloned()
             .unwrap_or_default();
 
+        // Only the identity that created a session may use it (OAuth mode)
+        let session_binding = env_map
+            .get("WASMCP_SESSION_BINDING")
+            .map(|v| v.to_lowercase() != "false")
+            .unwrap_or(true);
+
         // HTTP mode (SSE vs plain JSON)
         let disable_sse = env_map
             .get("WASMCP_DISABLE_SSE")
             .map(|v| v.to_lowercase() == "true")
             .unwrap_or(false);
+        let sse_on_demand = env_map
+            .get("WASMCP_SSE_ON_DEMAND")
+            .map(|v| v.to_lowercase() == "true")
+            .unwrap_or(false);
+        let sse_listen_secs = env_map
+            .get("WASMCP_SSE_LISTEN_SECS")
+            .and_then(|v| v.parse::<u64>().ok())
+            .unwrap_or(0);
 
         // Authentication mode
         let auth_mode_str = env_map
@@ -94,14 +244,209 @@ impl TransportConfig {
             || env_map
                 .get("JWT_JWKS_URI")
                 .filter(|v| !v.is_empty())
+                .is_some()
+            || env_map
+                .get("JWT_TRUSTED_ISSUERS")
+                .filter(|v| !v.is_empty())
                 .is_some();
 
+        // DPoP-bound tokens
+        let dpop = env_map
+            .get("WASMCP_DPOP")
+            .map(|v| {
+                DpopMode::parse(v).unwrap_or_else(|| {
+                    eprintln!(
+                        "[transport] WARNING: Invalid WASMCP_DPOP='{}', defaulting to 'off'. \
+                         Valid values: 'off', 'optional', 'required'",
+                        v
+                    );
+                    DpopMode::Off
+                })
+            })
+            .unwrap_or_default();
+        let dpop_max_age_secs = env_map
+            .get("WASMCP_DPOP_MAX_AGE_SECS")
+            .and_then(|v| v.parse::<u64>().ok())
+            .filter(|n| *n > 0)
+            .unwrap_or(DEFAULT_DPOP_MAX_AGE_SECS);
+
+        // Self-issued key set for local testing
+        let dev_jwks = env_map
+            .get("WASMCP_DEV_JWKS")
+            .filter(|v| !v.trim().is_empty())
+            .and_then(|v| {
+                let valid = serde_json::from_str::<serde_json::Value>(v)
+                    .is_ok_and(|jwks| jwks.get("keys").is_some_and(|keys| keys.is_array()));
+                if !valid {
+                    eprintln!(
+                        "[transport] WARNING: WASMCP_DEV_JWKS is not a JWKS with a keys array. Ignoring."
+                    );
+                    return None;
+                }
+                Some(v.clone())
+            });
+
+        // Tool concurrency limits
+        let max_concurrent_tools = parse_limit(&env_map, "WASMCP_MAX_CONCURRENT_TOOLS");
+        let max_concurrent_tools_per_session =
+            parse_limit(&env_map, "WASMCP_MAX_CONCURRENT_TOOLS_PER_SESSION");
+
+        let tool_queue_timeout_ms = env_map
+            .get("WASMCP_TOOL_QUEUE_TIMEOUT_MS")
+            .and_then(|v| v.parse::<u64>().ok())
+            .unwrap_or(0);
+
+        let tool_lease_secs = env_map
+            .get("WASMCP_TOOL_LEASE_SECS")
+            .and_then(|v| v.parse::<u64>().ok())
+            .filter(|secs| *secs > 0)
+            .unwrap_or(DEFAULT_TOOL_LEASE_SECS);
+
+        let tool_groups = env_map
+            .get("WASMCP_TOOL_GROUPS")
+            .map(|v| parse_tool_groups(v))
+            .unwrap_or_default();
+
+        // Request deadline
+        let request_timeout_ms = env_map
+            .get("WASMCP_REQUEST_TIMEOUT_MS")
+            .and_then(|v| v.parse::<u64>().ok())
+            .filter(|ms| *ms > 0);
+
+        // Extra headers for handlers (e.g. request signatures checked by middleware)
+        let forward_headers = env_map
+            .get("WASMCP_FORWARD_HEADERS")
+            .map(|v| {
+                v.split(',')
+                    .map(|name| name.trim().to_ascii_lowercase())
+                    .filter(|name| !name.is_empty())
+                    .filter(|name| {
+                        let credential = CREDENTIAL_HEADERS.contains(&name.as_str());
+                        if credential {
+                            eprintln!(
+                                "[transport] WARNING: WASMCP_FORWARD_HEADERS lists '{}', which carries \
+                                 credentials. Not forwarding it.",
+                                name
+                            );
+                        }
+                        !credential
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        // Response size budget
+        let max_response_bytes = env_map
+            .get("WASMCP_MAX_RESPONSE_BYTES")
+            .and_then(|v| v.parse::<u64>().ok())
+            .filter(|n| *n > 0);
+
+        let response_overflow = match env_map
+            .get("WASMCP_RESPONSE_OVERFLOW")
+            .map(|v| v.to_lowercase())
+            .as_deref()
+        {
+            None | Some("truncate") => ResponseOverflow::Truncate,
+            Some("link") => ResponseOverflow::Link,
+            Some("error") => ResponseOverflow::Error,
+            Some(other) => {
+                eprintln!(
+                    "[transport] WARNING: Invalid WASMCP_RESPONSE_OVERFLOW='{}', defaulting to 'truncate'. \
+                     Valid values: 'truncate', 'link', 'error'",
+                    other
+                );
+                ResponseOverflow::Truncate
+            }
+        };
+
+        // Protocol version pin
+        let max_protocol_version = env_map
+            .get("WASMCP_MAX_PROTOCOL_VERSION")
+            .filter(|v| !v.is_empty())
+            .and_then(|v| match parse_protocol_version(v.trim()) {
+                Ok(version) => Some(version),
+                Err(e) => {
+                    eprintln!(
+                        "[transport] WARNING: Invalid WASMCP_MAX_PROTOCOL_VERSION: {}. Ignoring.",
+                        e
+                    );
+                    None
+                }
+            });
+
+        // Prometheus metrics
+        let metrics_enabled = env_map
+            .get("WASMCP_METRICS_ENABLED")
+            .map(|v| v.to_lowercase() == "true")
+            .unwrap_or(false);
+
+        // REST facade
+        let rest_enabled = env_map
+            .get("WASMCP_REST_ENABLED")
+            .map(|v| v.to_lowercase() == "true")
+            .unwrap_or(false);
+
+        // Access log, optionally limited to some fields
+        let access_log = env_map
+            .get("WASMCP_ACCESS_LOG")
+            .filter(|v| v.to_lowercase() == "true")
+            .map(|_| match env_map.get("WASMCP_ACCESS_LOG_FIELDS") {
+                Some(v) => v
+                    .split(',')
+                    .map(|f| f.trim().to_lowercase())
+                    .filter(|f| {
+                        let known = access_log::FIELDS.contains(&f.as_str());
+                        if !known && !f.is_empty() {
+                            eprintln!(
+                                "[transport] WARNING: Unknown WASMCP_ACCESS_LOG_FIELDS field: {}. Ignoring.",
+                                f
+                            );
+                        }
+                        known
+                    })
+                    .collect(),
+                None => access_log::FIELDS.iter().map(|f| f.to_string()).collect(),
+            });
+
+        // Stdio checkpoints
+        let stdio_resume_token = env_map
+            .get("WASMCP_STDIO_RESUME_TOKEN")
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty());
+
+        // Capability discovery cache
+        let capability_cache_ttl = env_map
+            .get("WASMCP_CAPABILITY_CACHE_TTL")
+            .and_then(|v| v.parse::<u64>().ok())
+            .unwrap_or(0);
+
         TransportConfig {
             session_enabled,
             session_bucket_name,
+            session_binding,
             disable_sse,
+            sse_on_demand,
+            sse_listen_secs,
             auth_mode,
             jwt_configured,
+            dpop,
+            dpop_max_age_secs,
+            dev_jwks,
+            max_concurrent_tools,
+            max_concurrent_tools_per_session,
+            tool_queue_timeout_ms,
+            tool_lease_secs,
+            tool_groups,
+            request_timeout_ms,
+            forward_headers,
+            max_response_bytes,
+            response_overflow,
+            max_protocol_version,
+            metrics_enabled,
+            rest_enabled,
+            access_log,
+            stdio_resume_token,
+            capability_cache_ttl,
         }
     }
 
@@ -116,3 +461,40 @@ impl TransportConfig {
         }
     }
 }
+
+/// Parse an optional positive limit from the environment
+///
+/// Missing, zero, or unparseable values mean "no limit".
+fn parse_limit(env_map: &HashMap<String, String>, key: &str) -> Option<u32> {
+    let value = env_map.get(key)?;
+    match value.parse::<u32>() {
+        Ok(0) => None,
+        Ok(n) => Some(n),
+        Err(_) => {
+            eprintln!(
+                "[transport] WARNING: Invalid {}='{}', expected a positive integer. Ignoring.",
+                key, value
+            );
+            None
+        }
+    }
+}
+
+/// Parse `tool=group` pairs into each tool's serialization group
+///
+/// Entries without a group are reported and skipped.
+fn parse_tool_groups(value: &str) -> HashMap<String, String> {
+    let mut groups = HashMap::new();
+    for entry in value.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+        match entry.split_once('=') {
+            Some((tool, group)) if !tool.trim().is_empty() && !group.trim().is_empty() => {
+                groups.insert(tool.trim().to_string(), group.trim().to_string());
+            }
+            _ => eprintln!(
+                "[transport] WARNING: Invalid WASMCP_TOOL_GROUPS entry '{}', expected tool=group. Ignoring.",
+                entry
+            ),
+        }
+    }
+    groups
+}
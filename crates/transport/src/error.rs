@@ -3,8 +3,11 @@
 //! Provides a unified error type for all transport-layer operations,
 //! eliminating the need for repeated error handling boilerplate.
 
-use crate::bindings::wasmcp::mcp_v20251125::mcp::ErrorCode;
+use crate::bindings::wasmcp::mcp_v20251125::mcp::{Error, ErrorCode};
 use crate::bindings::wasmcp::mcp_v20251125::server_io::IoError;
+use wasmcp_errors::McpError;
+
+wasmcp_errors::impl_error_code!(ErrorCode, Error);
 
 /// Session-specific error types
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -49,16 +52,53 @@ impl SessionError {
     }
 }
 
+/// Bearer token error codes (RFC 6750 §3.1, RFC 9449 §7.1)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BearerError {
+    /// Malformed request (e.g. unparseable Authorization header)
+    InvalidRequest,
+    /// Token expired, revoked, malformed or otherwise invalid
+    InvalidToken,
+    /// Token is valid but lacks the scopes the resource requires
+    InsufficientScope,
+    /// DPoP proof missing, malformed, replayed or not matching the request or token
+    InvalidDpopProof,
+}
+
+impl BearerError {
+    /// Error code for the `error` challenge parameter
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::InvalidRequest => "invalid_request",
+            Self::InvalidToken => "invalid_token",
+            Self::InsufficientScope => "insufficient_scope",
+            Self::InvalidDpopProof => "invalid_dpop_proof",
+        }
+    }
+
+    /// Status RFC 6750 assigns to this error code
+    pub fn http_status_code(&self) -> u16 {
+        match self {
+            Self::InvalidRequest => 400,
+            Self::InvalidToken => 401,
+            Self::InsufficientScope => 403,
+            Self::InvalidDpopProof => 401,
+        }
+    }
+}
+
 /// Unified transport error type
 #[derive(Debug)]
 pub enum TransportError {
     /// Validation error (origin, headers, protocol version, etc.)
     Validation(String),
 
-    /// OAuth authentication error (invalid/missing token)
-    /// Includes WWW-Authenticate header value
+    /// OAuth bearer token rejected (missing, malformed, invalid or under-scoped)
+    /// Includes WWW-Authenticate header value; the status follows the error
+    /// code, 401 without one
     Unauthorized {
         message: String,
+        error: Option<BearerError>,
         www_authenticate: Option<String>,
     },
 
@@ -82,6 +122,13 @@ pub enum TransportError {
     /// Session management error
     Session(SessionError),
 
+    /// Concurrency limit saturated (e.g. too many in-flight tool calls)
+    /// Includes the suggested Retry-After delay in seconds
+    RateLimited {
+        message: String,
+        retry_after_secs: u64,
+    },
+
     /// Internal error (should not happen in normal operation)
     Internal(String),
 }
@@ -92,21 +139,29 @@ impl TransportError {
         Self::Validation(msg.into())
     }
 
-    /// Create an unauthorized error (401) with optional WWW-Authenticate header
-    /// TODO: Currently unused - use unauthorized_with_challenge instead
+    /// Create an unauthorized error (401) without a WWW-Authenticate header
+    /// TODO: Currently unused - use bearer instead
     /// Kept for completeness when authorization is implemented
     #[allow(dead_code)]
     pub fn unauthorized(msg: impl Into<String>) -> Self {
         Self::Unauthorized {
             message: msg.into(),
+            error: None,
             www_authenticate: None,
         }
     }
 
-    /// Create an unauthorized error with WWW-Authenticate header
-    pub fn unauthorized_with_challenge(msg: impl Into<String>, www_authenticate: String) -> Self {
+    /// Create a bearer token rejection with its WWW-Authenticate challenge
+    ///
+    /// See `http::challenge` for building the challenge.
+    pub fn bearer(
+        error: Option<BearerError>,
+        msg: impl Into<String>,
+        www_authenticate: String,
+    ) -> Self {
         Self::Unauthorized {
             message: msg.into(),
+            error,
             www_authenticate: Some(www_authenticate),
         }
     }
@@ -145,6 +200,14 @@ impl TransportError {
         Self::Session(SessionError::Required)
     }
 
+    /// Create a rate-limited error (429) suggesting when to retry
+    pub fn rate_limited(msg: impl Into<String>, retry_after_secs: u64) -> Self {
+        Self::RateLimited {
+            message: msg.into(),
+            retry_after_secs,
+        }
+    }
+
     /// Create an internal error
     pub fn internal(msg: impl Into<String>) -> Self {
         Self::Internal(msg.into())
@@ -154,10 +217,11 @@ impl TransportError {
     pub fn http_status_code(&self) -> u16 {
         match self {
             Self::Validation(_) => 400,
-            Self::Unauthorized { .. } => 401,
+            Self::Unauthorized { error, .. } => error.map_or(401, |error| error.http_status_code()),
             Self::Forbidden(_) => 403,
             Self::Protocol(_) => 400,
             Self::Session(session_error) => session_error.http_status_code(),
+            Self::RateLimited { .. } => 429,
             Self::Io(_) => 500,
             Self::Internal(_) => 500,
         }
@@ -174,6 +238,16 @@ impl TransportError {
         }
     }
 
+    /// Get Retry-After delay in seconds if the request is worth retrying
+    pub fn retry_after_secs(&self) -> Option<u64> {
+        match self {
+            Self::RateLimited {
+                retry_after_secs, ..
+            } => Some(*retry_after_secs),
+            _ => None,
+        }
+    }
+
     /// Get error message
     pub fn message(&self) -> String {
         match self {
@@ -182,6 +256,7 @@ impl TransportError {
             Self::Forbidden(msg) => msg.clone(),
             Self::Protocol(msg) => msg.clone(),
             Self::Session(session_error) => session_error.message(),
+            Self::RateLimited { message, .. } => message.clone(),
             Self::Io(e) => format!("I/O error: {:?}", e),
             Self::Internal(msg) => msg.clone(),
         }
@@ -210,6 +285,29 @@ impl From<ErrorCode> for TransportError {
     }
 }
 
+/// JSON-RPC error for failures reported on an already-open response stream
+///
+/// Only kinds clients can act on are distinguished; everything else stays an
+/// internal error.
+impl From<&TransportError> for McpError {
+    fn from(e: &TransportError) -> Self {
+        match e {
+            TransportError::Unauthorized {
+                message,
+                error: Some(BearerError::InsufficientScope),
+                ..
+            } => McpError::forbidden(message.clone()),
+            TransportError::Unauthorized { message, .. } => McpError::unauthorized(message.clone()),
+            TransportError::Forbidden(msg) => McpError::forbidden(msg.clone()),
+            TransportError::RateLimited {
+                message,
+                retry_after_secs,
+            } => McpError::rate_limited(message.clone()).with_retry_after(*retry_after_secs),
+            _ => McpError::internal(e.message()),
+        }
+    }
+}
+
 impl From<String> for TransportError {
     fn from(s: String) -> Self {
         Self::Internal(s)
@@ -0,0 +1,110 @@
+//! Bearer token authentication
+//!
+//! Verifies the Authorization header according to `WASMCP_AUTH_MODE`. Shared
+//! by every method that acts on a session (POST, DELETE), so a session can't
+//! be used or terminated without the token that's bound to it.
+//!
+//! With `WASMCP_DPOP` enabled, tokens bound to a key must also come with a
+//! DPoP proof of that key (see `dpop`).
+
+use crate::bindings::wasi::http::types::IncomingRequest;
+use crate::bindings::wasmcp::mcp_v20251125::mcp::Identity;
+use crate::bindings::wasmcp::mcp_v20251125::server_auth;
+use crate::config::{AuthMode, DpopMode, TransportConfig};
+use crate::error::{BearerError, TransportError};
+use crate::http::validation::TokenScheme;
+use crate::http::{challenge, dpop, validation};
+
+/// Authenticate the request
+///
+/// Returns:
+/// - Ok(None) in public mode
+/// - Ok(Some(identity)) for a valid token carrying the required scopes
+/// - Err(TransportError) with a bearer challenge otherwise
+pub fn authenticate(
+    request: &IncomingRequest,
+    session_config: &TransportConfig,
+) -> Result<Option<Identity>, TransportError> {
+    match session_config.auth_mode {
+        AuthMode::Public => {
+            // Public mode - no authentication required
+            Ok(None)
+        }
+        AuthMode::OAuth => {
+            // OAuth mode - JWT required
+            // Validate that JWT is configured properly
+            if !session_config.jwt_configured {
+                return Err(TransportError::internal(
+                    "WASMCP_AUTH_MODE=oauth requires JWT_PUBLIC_KEY or JWT_JWKS_URI to be configured",
+                ));
+            }
+
+            match validation::extract_authorization_header(request) {
+                Ok(Some((TokenScheme::Dpop, _))) if session_config.dpop == DpopMode::Off => {
+                    Err(challenge::reject(
+                        request,
+                        Some(BearerError::InvalidRequest),
+                        "Malformed Authorization header",
+                        &challenge::required_scopes(None),
+                        "Invalid Authorization header: Authorization header must use Bearer scheme",
+                    ))
+                }
+                Ok(Some((scheme, jwt))) => match server_auth::decode(&jwt) {
+                    Ok(claims) => {
+                        // Key-bound token: the request must prove possession of the key
+                        if session_config.dpop != DpopMode::Off {
+                            dpop::authenticate(request, scheme, &jwt, &claims, session_config)?;
+                        }
+
+                        // Valid token without the required scopes: 403 with a scope hint
+                        let required = challenge::required_scopes(claims.issuer.as_deref());
+                        if let Some(missing) =
+                            required.iter().find(|scope| !claims.scopes.contains(scope))
+                        {
+                            return Err(challenge::reject(
+                                request,
+                                Some(BearerError::InsufficientScope),
+                                "The access token lacks the scopes required by this resource",
+                                &required,
+                                format!("Token missing required scope: {}", missing),
+                            ));
+                        }
+
+                        Ok(Some(Identity { jwt, claims }))
+                    }
+                    Err(e) => {
+                        // Strict validation: return 401 with WWW-Authenticate header
+                        Err(challenge::reject(
+                            request,
+                            Some(BearerError::InvalidToken),
+                            "The access token provided is invalid, expired, or malformed",
+                            &challenge::required_scopes(None),
+                            format!("JWT validation failed: {:?}", e),
+                        ))
+                    }
+                },
+                Ok(None) => {
+                    // OAuth mode requires token; RFC 6750 §3.1: no error code
+                    // when the request carries no credentials
+                    Err(challenge::reject(
+                        request,
+                        None,
+                        "",
+                        &challenge::required_scopes(None),
+                        "Missing required Authorization header",
+                    ))
+                }
+                Err(e) => {
+                    // Malformed header - always error (400 per RFC 6750)
+                    Err(challenge::reject(
+                        request,
+                        Some(BearerError::InvalidRequest),
+                        "Malformed Authorization header",
+                        &challenge::required_scopes(None),
+                        format!("Invalid Authorization header: {}", e),
+                    ))
+                }
+            }
+        }
+    }
+}
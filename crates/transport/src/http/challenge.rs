@@ -0,0 +1,111 @@
+//! Bearer token challenges (RFC 6750 §3, RFC 9728 §5.1)
+//!
+//! Every rejected OAuth request gets a `WWW-Authenticate` challenge that
+//! points at the protected resource metadata and lists the scopes the server
+//! expects, so clients can discover where and how to (re)authorize:
+//! - No token: bare challenge without an error code (401)
+//! - Malformed Authorization header: `invalid_request` (400)
+//! - Token rejected by server-auth: `invalid_token` (401)
+//! - Token lacks required scopes: `insufficient_scope` (403)
+//! - DPoP proof rejected: `invalid_dpop_proof` (401)
+//!
+//! Challenges use the `DPoP` scheme (RFC 9449 §7.1), listing the accepted
+//! proof algorithms, for DPoP failures and whenever `WASMCP_DPOP=required`.
+
+use crate::bindings::wasi::cli::environment::get_environment;
+use crate::bindings::wasi::http::types::IncomingRequest;
+use crate::bindings::wasmcp::mcp_v20251125::server_auth;
+use crate::config::DpopMode;
+use crate::error::{BearerError, TransportError};
+use crate::http::dpop;
+use crate::http::helpers::{get_env, get_server_uri};
+
+/// Reject a request with a bearer challenge
+///
+/// `scopes` is the scope hint for the client; the error code, if any, picks
+/// the status (see [`BearerError::http_status_code`]).
+pub fn reject(
+    request: &IncomingRequest,
+    error: Option<BearerError>,
+    description: &str,
+    scopes: &[String],
+    message: impl Into<String>,
+) -> TransportError {
+    let env_vars = get_environment();
+    let dpop_required = get_env(&env_vars, "WASMCP_DPOP")
+        .and_then(|v| DpopMode::parse(&v))
+        .is_some_and(|mode| mode == DpopMode::Required);
+    let server_uri = get_server_uri(&env_vars, request);
+    let challenge = format_challenge(
+        dpop_required,
+        &server_uri,
+        scopes,
+        error.map(|e| (e, description)),
+    );
+    TransportError::bearer(error, message, challenge)
+}
+
+/// Reject a request with a DPoP challenge
+pub fn reject_dpop(
+    request: &IncomingRequest,
+    error: BearerError,
+    description: &str,
+    scopes: &[String],
+    message: impl Into<String>,
+) -> TransportError {
+    let env_vars = get_environment();
+    let server_uri = get_server_uri(&env_vars, request);
+    let challenge = format_challenge(true, &server_uri, scopes, Some((error, description)));
+    TransportError::bearer(Some(error), message, challenge)
+}
+
+/// Scopes a token from `issuer` must carry
+///
+/// Asked of server-auth, which matches issuers as it does when verifying
+/// tokens; with no issuer, the scopes of its default provider.
+pub fn required_scopes(issuer: Option<&str>) -> Vec<String> {
+    server_auth::required_scopes(issuer)
+}
+
+/// Build the `WWW-Authenticate` value
+///
+/// RFC 6750 §3.1: a request without credentials gets no error code, so
+/// `error` is optional.
+fn format_challenge(
+    dpop: bool,
+    server_uri: &str,
+    scopes: &[String],
+    error: Option<(BearerError, &str)>,
+) -> String {
+    let mut params = vec![
+        format!("realm={}", quote(server_uri)),
+        format!(
+            "resource_metadata={}",
+            quote(&format!(
+                "{}/.well-known/oauth-protected-resource",
+                server_uri
+            ))
+        ),
+    ];
+
+    if !scopes.is_empty() {
+        params.push(format!("scope={}", quote(&scopes.join(" "))));
+    }
+
+    if dpop {
+        params.push(format!("algs={}", quote(&dpop::ALGORITHMS.join(" "))));
+    }
+
+    if let Some((error, description)) = error {
+        params.push(format!("error={}", quote(error.as_str())));
+        params.push(format!("error_description={}", quote(description)));
+    }
+
+    let scheme = if dpop { "DPoP" } else { "Bearer" };
+    format!("{} {}", scheme, params.join(", "))
+}
+
+/// RFC 9110 quoted-string
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
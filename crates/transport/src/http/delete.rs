@@ -2,12 +2,13 @@
 //!
 //! Performs soft delete (mark-terminated) on sessions when requested by client.
 //! Session data remains in storage for background cleanup processes.
+//! In OAuth mode the request must carry a token for the session's identity.
 //! Returns 405 Method Not Allowed when sessions are disabled.
 
 use crate::bindings::wasi::http::types::{IncomingRequest, ResponseOutparam};
 use crate::config::TransportConfig;
 use crate::error::TransportError;
-use crate::http::{response, session, validation};
+use crate::http::{auth, response, session, validation};
 use crate::send_error;
 
 pub fn handle_delete(
@@ -39,6 +40,21 @@ pub fn handle_delete(
         Err(e) => send_error!(response_out, e),
     };
 
+    crate::common::access_log::record("session", session_id.as_str());
+
+    // Only the identity bound to the session may terminate it
+    let identity = match auth::authenticate(&request, session_config) {
+        Ok(identity) => identity,
+        Err(e) => send_error!(response_out, e),
+    };
+    if let Err(e) = session::enforce_session_binding(
+        Some(session_id.as_str()),
+        identity.as_ref(),
+        session_config,
+    ) {
+        send_error!(response_out, e);
+    }
+
     // Terminate session (soft delete) using session helper
     match session::delete_session_by_id(&session_id, session_config) {
         Ok(_) => {
@@ -0,0 +1,55 @@
+//! Content-Digest verification (RFC 9530)
+//!
+//! Handlers receive the request already parsed, so a handler that trusts
+//! a signed `Content-Digest` header (hmac-auth) can't check it against the
+//! body itself. The transport does: a POST carrying the header has its body
+//! read whole and hashed before it is parsed, and a mismatch rejects the
+//! request. Requests without the header are parsed as before.
+//!
+//! Supported algorithms are `sha-256` and `sha-512`; every supported entry
+//! must match, and a header with none of them is rejected, since it can't
+//! be checked.
+
+use crate::bindings::wasi::http::types::IncomingRequest;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use sha2::{Digest, Sha256, Sha512};
+
+/// The request's Content-Digest header, if sent
+pub fn content_digest(request: &IncomingRequest) -> Option<String> {
+    let values = request.headers().get("content-digest");
+    String::from_utf8(values.first()?.clone()).ok()
+}
+
+/// Check a Content-Digest header value against the body
+pub fn verify(header: &str, body: &[u8]) -> Result<(), String> {
+    let mut checked = false;
+    for member in header.split(',') {
+        let Some((algorithm, value)) = member.trim().split_once('=') else {
+            return Err("Malformed Content-Digest header".to_string());
+        };
+        // Parameters (`;key=value`) don't change the digest
+        let value = value.split(';').next().unwrap_or_default().trim();
+        let Some(encoded) = value.strip_prefix(':').and_then(|v| v.strip_suffix(':')) else {
+            return Err("Malformed Content-Digest header".to_string());
+        };
+        let actual = match algorithm.trim().to_ascii_lowercase().as_str() {
+            "sha-256" => Sha256::digest(body).to_vec(),
+            "sha-512" => Sha512::digest(body).to_vec(),
+            _ => continue,
+        };
+        let expected = STANDARD
+            .decode(encoded)
+            .map_err(|_| "Malformed Content-Digest header".to_string())?;
+        if expected != actual {
+            return Err("Content-Digest does not match the request body".to_string());
+        }
+        checked = true;
+    }
+
+    if checked {
+        Ok(())
+    } else {
+        Err("Content-Digest has no supported algorithm (sha-256, sha-512)".to_string())
+    }
+}
@@ -6,6 +6,8 @@
 
 use crate::bindings::wasi::cli::environment::get_environment;
 use crate::bindings::wasi::http::types::{IncomingRequest, ResponseOutparam};
+use crate::config::DpopMode;
+use crate::http::dpop;
 use crate::http::helpers::{get_env, get_server_uri};
 use crate::http::response::ResponseBuilder;
 use serde_json::json;
@@ -81,11 +83,24 @@ fn build_protected_resource_metadata(request: &IncomingRequest) -> serde_json::V
     let resource = get_server_uri(&env_vars, request);
 
     // Get authorization server(s) from config
-    let auth_servers: Vec<String> = get_env(&env_vars, "WASMCP_AUTH_SERVER_URL")
+    let mut auth_servers: Vec<String> = get_env(&env_vars, "WASMCP_AUTH_SERVER_URL")
         .or_else(|| get_env(&env_vars, "JWT_ISSUER"))
         .map(|v| vec![v])
         .unwrap_or_default();
 
+    // Plus every additional trusted issuer
+    for issuer in get_env(&env_vars, "JWT_TRUSTED_ISSUERS")
+        .and_then(|v| serde_json::from_str::<Vec<serde_json::Value>>(&v).ok())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|entry| entry.get("issuer")?.as_str())
+        .map(|issuer| issuer.trim_end_matches('/').to_string())
+    {
+        if !auth_servers.contains(&issuer) {
+            auth_servers.push(issuer);
+        }
+    }
+
     // Get JWKS URI if configured
     let jwks_uri = get_env(&env_vars, "JWT_JWKS_URI");
 
@@ -94,12 +109,23 @@ fn build_protected_resource_metadata(request: &IncomingRequest) -> serde_json::V
         .map(|v| parse_comma_separated(&v))
         .unwrap_or_default();
 
-    json!({
+    let mut metadata = json!({
         "resource": resource,
         "authorization_servers": auth_servers,
         "jwks_uri": jwks_uri,
         "scopes_supported": scopes_supported,
         "bearer_methods_supported": ["header"],
         "resource_documentation": format!("{}/.well-known/oauth-protected-resource", resource),
-    })
+    });
+
+    // DPoP support (RFC 9449 §5.1)
+    let dpop = get_env(&env_vars, "WASMCP_DPOP")
+        .and_then(|v| DpopMode::parse(&v))
+        .unwrap_or_default();
+    if dpop != DpopMode::Off {
+        metadata["dpop_signing_alg_values_supported"] = json!(dpop::ALGORITHMS);
+        metadata["dpop_bound_access_tokens_required"] = json!(dpop == DpopMode::Required);
+    }
+
+    metadata
 }
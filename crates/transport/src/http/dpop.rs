@@ -0,0 +1,253 @@
+//! DPoP proof validation (RFC 9449)
+//!
+//! With `WASMCP_DPOP` set, an access token bound to a key (`cnf.jkt`) is
+//! only accepted with the `DPoP` scheme and a `DPoP` header carrying a proof
+//! signed by that key, so a leaked token is useless without the key. A
+//! proof is accepted when:
+//! - It is a JWT with `typ: dpop+jwt`, an asymmetric `alg` and a public `jwk`
+//! - It is signed by that `jwk`, whose RFC 7638 thumbprint is the token's `cnf.jkt`
+//! - `htm` and `htu` are this request's method and URI (query and fragment ignored)
+//! - `ath` is the hash of the access token
+//! - `iat` is within `WASMCP_DPOP_MAX_AGE_SECS` of the current time
+//! - Its `jti` was never seen before for that key
+//!
+//! Seen `jti`s are recorded in the session bucket with an atomic increment
+//! under `dpop-jti:{jkt}:{jti}`; they only need to be kept for twice the
+//! maximum age. Server-provided nonces (`DPoP-Nonce`) are not issued.
+
+use crate::bindings::wasi::cli::environment::get_environment;
+use crate::bindings::wasi::clocks::wall_clock;
+use crate::bindings::wasi::http::types::IncomingRequest;
+use crate::bindings::wasmcp::auth::types::JwtClaims;
+use crate::bindings::wasmcp::keyvalue::store as kv_store;
+use crate::config::{DpopMode, TransportConfig};
+use crate::error::{BearerError, TransportError};
+use crate::http::validation::TokenScheme;
+use crate::http::{challenge, helpers};
+use base64::Engine as _;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use jsonwebtoken::jwk::ThumbprintHash;
+use jsonwebtoken::{Algorithm, DecodingKey, Validation, decode, decode_header};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+/// Proof signing algorithms accepted (asymmetric only)
+pub const ALGORITHMS: &[&str] = &[
+    "ES256", "ES384", "RS256", "RS384", "RS512", "PS256", "PS384", "PS512", "EdDSA",
+];
+
+const JTI_PREFIX: &str = "dpop-jti:";
+
+/// Claims of a DPoP proof
+#[derive(Deserialize)]
+struct ProofClaims {
+    jti: String,
+    htm: String,
+    htu: String,
+    iat: u64,
+    #[serde(default)]
+    ath: Option<String>,
+}
+
+/// What a proof must match
+struct Expected<'a> {
+    method: &'a str,
+    uri: &'a str,
+    access_token: &'a [u8],
+    jkt: &'a str,
+    now: u64,
+    max_age: u64,
+}
+
+/// Check the token's key binding against the scheme it was sent with
+///
+/// Only called when DPoP is enabled. Bound tokens need the DPoP scheme and
+/// a valid proof; unbound tokens need the Bearer scheme, and are rejected
+/// altogether when DPoP is required.
+pub fn authenticate(
+    request: &IncomingRequest,
+    scheme: TokenScheme,
+    access_token: &[u8],
+    claims: &JwtClaims,
+    config: &TransportConfig,
+) -> Result<(), TransportError> {
+    let jkt = claims
+        .confirmation
+        .as_ref()
+        .and_then(|cnf| cnf.jkt.as_deref());
+    let scopes = challenge::required_scopes(claims.issuer.as_deref());
+    let reject_token = |description: &str| {
+        challenge::reject_dpop(
+            request,
+            BearerError::InvalidToken,
+            description,
+            &scopes,
+            format!("DPoP binding check failed: {}", description),
+        )
+    };
+
+    match (scheme, jkt) {
+        (TokenScheme::Dpop, Some(jkt)) => {
+            verify_proof(request, access_token, jkt, config).map_err(|description| {
+                challenge::reject_dpop(
+                    request,
+                    BearerError::InvalidDpopProof,
+                    &description,
+                    &scopes,
+                    format!("DPoP proof rejected: {}", description),
+                )
+            })
+        }
+        (TokenScheme::Dpop, None) => Err(reject_token("The access token is not DPoP-bound")),
+        (TokenScheme::Bearer, Some(_)) => Err(reject_token(
+            "DPoP-bound access tokens must be sent with the DPoP scheme",
+        )),
+        (TokenScheme::Bearer, None) if config.dpop == DpopMode::Required => Err(reject_token(
+            "This resource requires DPoP-bound access tokens",
+        )),
+        (TokenScheme::Bearer, None) => Ok(()),
+    }
+}
+
+/// Verify the request's proof for a token bound to `jkt`
+///
+/// Returns the reason for rejection, used as the challenge description.
+fn verify_proof(
+    request: &IncomingRequest,
+    access_token: &[u8],
+    jkt: &str,
+    config: &TransportConfig,
+) -> Result<(), String> {
+    let proofs = request.headers().get("dpop");
+    let proof = match proofs.as_slice() {
+        [proof] => std::str::from_utf8(proof)
+            .map_err(|_| "Invalid DPoP header encoding".to_string())?
+            .to_string(),
+        [] => return Err("Missing DPoP proof".to_string()),
+        _ => return Err("Multiple DPoP proofs".to_string()),
+    };
+
+    let env_vars = get_environment();
+    let uri = format!(
+        "{}{}",
+        helpers::get_server_uri(&env_vars, request),
+        request.path_with_query().unwrap_or_default()
+    );
+    let method = request.method();
+
+    let jti = check_proof(
+        &proof,
+        &Expected {
+            method: helpers::method_name(&method),
+            uri: &uri,
+            access_token,
+            jkt,
+            now: wall_clock::now().seconds,
+            max_age: config.dpop_max_age_secs,
+        },
+    )?;
+    record_jti(config, jkt, &jti)
+}
+
+/// Validate a proof, returning its `jti`
+fn check_proof(proof: &str, expected: &Expected) -> Result<String, String> {
+    let header = decode_header(proof).map_err(|_| "Malformed DPoP proof".to_string())?;
+    if header.typ.as_deref() != Some("dpop+jwt") {
+        return Err("DPoP proof typ must be dpop+jwt".to_string());
+    }
+    if matches!(
+        header.alg,
+        Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512
+    ) {
+        return Err("DPoP proof must use an asymmetric algorithm".to_string());
+    }
+    let jwk = header
+        .jwk
+        .ok_or_else(|| "DPoP proof has no jwk header".to_string())?;
+    if has_private_key(proof) {
+        return Err("DPoP proof jwk must not contain a private key".to_string());
+    }
+    if jwk.thumbprint(ThumbprintHash::SHA256) != expected.jkt {
+        return Err("DPoP proof key does not match the access token".to_string());
+    }
+
+    let key = DecodingKey::from_jwk(&jwk).map_err(|_| "Unsupported DPoP proof key".to_string())?;
+    let mut validation = Validation::new(header.alg);
+    validation.required_spec_claims.clear();
+    validation.validate_exp = false;
+    validation.validate_aud = false;
+    let claims = decode::<ProofClaims>(proof, &key, &validation)
+        .map_err(|_| "Invalid DPoP proof signature or claims".to_string())?
+        .claims;
+
+    if claims.htm != expected.method {
+        return Err("DPoP proof htm does not match the request method".to_string());
+    }
+    if normalize_uri(&claims.htu) != normalize_uri(expected.uri) {
+        return Err("DPoP proof htu does not match the request URI".to_string());
+    }
+    if claims.ath.as_deref() != Some(access_token_hash(expected.access_token).as_str()) {
+        return Err("DPoP proof ath does not match the access token".to_string());
+    }
+    if claims.iat.abs_diff(expected.now) > expected.max_age {
+        return Err("DPoP proof iat is too far from the current time".to_string());
+    }
+    if claims.jti.is_empty() {
+        return Err("DPoP proof has no jti".to_string());
+    }
+
+    Ok(claims.jti)
+}
+
+/// Whether the proof's `jwk` header carries a private key (`d`)
+fn has_private_key(proof: &str) -> bool {
+    proof
+        .split('.')
+        .next()
+        .and_then(|header| URL_SAFE_NO_PAD.decode(header).ok())
+        .and_then(|bytes| serde_json::from_slice::<serde_json::Value>(&bytes).ok())
+        .is_some_and(|header| header["jwk"].get("d").is_some())
+}
+
+/// `ath`: base64url SHA-256 of the access token
+fn access_token_hash(access_token: &[u8]) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(access_token))
+}
+
+/// URI without query and fragment, with case and default ports normalized
+fn normalize_uri(uri: &str) -> String {
+    let uri = uri.split(['?', '#']).next().unwrap_or_default();
+    let Some((scheme, rest)) = uri.split_once("://") else {
+        return uri.to_string();
+    };
+    let scheme = scheme.to_lowercase();
+    let (authority, path) = rest.find('/').map_or((rest, "/"), |i| rest.split_at(i));
+    let authority = authority.to_lowercase();
+    let authority = match scheme.as_str() {
+        "https" => authority.strip_suffix(":443").unwrap_or(&authority),
+        "http" => authority.strip_suffix(":80").unwrap_or(&authority),
+        _ => &authority,
+    };
+    format!("{}://{}{}", scheme, authority, path)
+}
+
+/// Record a proof's `jti`, failing if it was seen before
+///
+/// Storage errors reject the proof: without the store a replay cannot be
+/// told apart from a fresh proof.
+fn record_jti(config: &TransportConfig, jkt: &str, jti: &str) -> Result<(), String> {
+    let unavailable = "DPoP replay protection unavailable".to_string();
+    let bucket = kv_store::open(config.get_session_bucket()).map_err(|e| {
+        eprintln!("[transport:dpop] Failed to open bucket: {:?}", e);
+        unavailable.clone()
+    })?;
+
+    match bucket.increment(&format!("{}{}:{}", JTI_PREFIX, jkt, jti), 1) {
+        Ok(1) => Ok(()),
+        Ok(_) => Err("DPoP proof has already been used".to_string()),
+        Err(e) => {
+            eprintln!("[transport:dpop] Failed to record jti: {:?}", e);
+            Err(unavailable)
+        }
+    }
+}
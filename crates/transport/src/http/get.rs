@@ -2,13 +2,18 @@
 //!
 //! GET requests are used for the OAuth 2.0 discovery endpoint:
 //! - /.well-known/oauth-protected-resource (RFC 9728)
+//! - /.well-known/jwks.json (development key set, when `WASMCP_DEV_JWKS` is set)
+//! - /metrics (Prometheus, when `WASMCP_METRICS_ENABLED=true`)
+//! - Stream resumption, for requests with `Last-Event-ID` (see `resume.rs`)
+//! - Listening streams, for other session requests accepting SSE, when
+//!   `WASMCP_SSE_LISTEN_SECS` is set (see `listen.rs`)
 //!
 //! All other GET requests return 405 Method Not Allowed.
 
 use crate::bindings::wasi::http::types::{IncomingRequest, ResponseOutparam};
 use crate::config::TransportConfig;
 use crate::error::TransportError;
-use crate::http::{discovery, response};
+use crate::http::{discovery, jwks, listen, metrics, response, resume};
 use crate::send_error;
 
 pub fn handle_get(
@@ -17,6 +22,13 @@ pub fn handle_get(
     response_out: ResponseOutparam,
     session_config: &TransportConfig,
 ) {
+    if resume::is_resume(&request) {
+        return resume::handle_resume(request, response_out, session_config);
+    }
+    if listen::is_listen(&request, session_config) {
+        return listen::handle_listen(request, response_out, session_config);
+    }
+
     // Get request path
     let path = match request.path_with_query() {
         Some(p) => p,
@@ -35,6 +47,15 @@ pub fn handle_get(
         "/.well-known/oauth-protected-resource" => {
             discovery::handle_protected_resource_metadata(&request, response_out);
         }
+        "/.well-known/jwks.json" if session_config.dev_jwks.is_some() => {
+            jwks::handle_jwks(
+                session_config.dev_jwks.as_deref().unwrap_or_default(),
+                response_out,
+            );
+        }
+        "/metrics" if session_config.metrics_enabled => {
+            metrics::handle_metrics(response_out, session_config);
+        }
         _ => {
             send_method_not_allowed(response_out, session_config);
         }
@@ -2,7 +2,8 @@
 //!
 //! Shared helper functions for HTTP request handling
 
-use crate::bindings::wasi::http::types::IncomingRequest;
+use crate::bindings::wasi::http::types::{IncomingRequest, Method};
+use crate::bindings::wasi::io::streams::{InputStream, StreamError};
 
 /// Get environment variable value by key
 ///
@@ -50,3 +51,44 @@ pub fn get_server_uri(env_vars: &[(String, String)], request: &IncomingRequest)
     // Last resort fallback
     "https://localhost:3000".to_string()
 }
+
+/// HTTP method name as sent on the wire
+pub fn method_name(method: &Method) -> &str {
+    match method {
+        Method::Get => "GET",
+        Method::Head => "HEAD",
+        Method::Post => "POST",
+        Method::Put => "PUT",
+        Method::Delete => "DELETE",
+        Method::Connect => "CONNECT",
+        Method::Options => "OPTIONS",
+        Method::Trace => "TRACE",
+        Method::Patch => "PATCH",
+        Method::Other(other) => other,
+    }
+}
+
+/// Request body size from the Content-Length header, if sent
+pub fn content_length(request: &IncomingRequest) -> Option<u64> {
+    let values = request.headers().get("content-length");
+    std::str::from_utf8(values.first()?)
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+}
+
+/// Read a stream to its end, failing past `max_bytes`
+pub fn read_all(stream: &InputStream, max_bytes: u64) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::new();
+    loop {
+        match stream.blocking_read(4096) {
+            Ok(chunk) => bytes.extend_from_slice(&chunk),
+            Err(StreamError::Closed) => return Ok(bytes),
+            Err(e) => return Err(format!("Failed to read body: {:?}", e)),
+        }
+        if bytes.len() as u64 > max_bytes {
+            return Err(format!("Body exceeds {} bytes", max_bytes));
+        }
+    }
+}
@@ -0,0 +1,42 @@
+//! Self-issued JWKS endpoint (`WASMCP_DEV_JWKS`)
+//!
+//! Serves a development key set at /.well-known/jwks.json so the whole
+//! JWKS path can be exercised offline: `wasmcp jwt jwks` prints the key set
+//! for the local test keypair, tokens from `wasmcp jwt mint` are signed with
+//! it, and the authorization component fetches it from this server through
+//! `JWT_JWKS_URI`.
+//!
+//! The key set is public by nature; serving it only matters because the
+//! matching private key lives on a developer machine. Never set it in
+//! production.
+
+use crate::bindings::wasi::http::types::{OutgoingBody, ResponseOutparam};
+use crate::http::response::ResponseBuilder;
+
+/// Handle GET /.well-known/jwks.json
+pub fn handle_jwks(jwks: &str, response_out: ResponseOutparam) {
+    // Keys are regenerated freely during development, so don't cache
+    let response = match ResponseBuilder::new()
+        .status(200)
+        .header("content-type", b"application/json")
+        .header("cache-control", b"no-store")
+        .build()
+    {
+        Ok(r) => r,
+        Err(e) => {
+            let error_response = crate::http::response::transport_error_to_response(&e);
+            ResponseOutparam::set(response_out, Ok(error_response));
+            return;
+        }
+    };
+
+    if let Ok(body) = response.body() {
+        if let Ok(stream) = body.write() {
+            let _ = stream.blocking_write_and_flush(jwks.as_bytes());
+            drop(stream);
+        }
+        let _ = OutgoingBody::finish(body, None);
+    }
+
+    ResponseOutparam::set(response_out, Ok(response));
+}
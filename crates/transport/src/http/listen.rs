@@ -0,0 +1,114 @@
+//! Listening streams (GET without Last-Event-ID)
+//!
+//! With `WASMCP_SSE_LISTEN_SECS` set, a client may open a standalone SSE
+//! stream with `GET`, `Mcp-Session-Id` and `Accept: text/event-stream` to
+//! receive messages not tied to one of its requests.
+//!
+//! Instances share nothing but the session bucket, so the instance holding
+//! the stream can't be told directly when another instance has a message
+//! for the session. Messages go through the session outbox instead: server-io
+//! appends to it (`queue-message`, or a failed write on a POST stream) from
+//! whichever instance handled the triggering request, and this stream polls
+//! it, writes what it finds and removes what it wrote.
+//!
+//! The stream ends after `WASMCP_SSE_LISTEN_SECS`, so it never outlives the
+//! host's request timeout; clients reopen it as the spec allows. Messages
+//! arriving in between wait in the outbox. Authorization is as for resume.
+
+use crate::bindings::wasi::clocks::monotonic_clock;
+use crate::bindings::wasi::http::types::{IncomingRequest, OutgoingBody, ResponseOutparam};
+use crate::config::TransportConfig;
+use crate::http::{response, resume};
+use crate::send_error;
+
+/// How often the outbox is checked (250ms)
+const POLL_INTERVAL_NS: u64 = 250_000_000;
+
+/// Idle time after which a comment is sent to keep proxies from closing the stream
+const KEEP_ALIVE_NS: u64 = 15_000_000_000;
+
+/// Whether the request opens a listening stream
+pub fn is_listen(request: &IncomingRequest, session_config: &TransportConfig) -> bool {
+    let headers = request.headers();
+    session_config.sse_listen_secs > 0
+        && !headers.get("mcp-session-id").is_empty()
+        && headers
+            .get("accept")
+            .iter()
+            .any(|v| String::from_utf8_lossy(v).contains("text/event-stream"))
+}
+
+pub fn handle_listen(
+    request: IncomingRequest,
+    response_out: ResponseOutparam,
+    session_config: &TransportConfig,
+) {
+    let session_id = match resume::authorize(&request, session_config) {
+        Ok(id) => id,
+        Err(e) => send_error!(response_out, e),
+    };
+
+    let response = match response::ResponseBuilder::new()
+        .status(200)
+        .header("content-type", b"text/event-stream")
+        .header("cache-control", b"no-cache")
+        .build()
+    {
+        Ok(r) => r,
+        Err(e) => send_error!(response_out, e),
+    };
+    let Ok(output_body) = response.body() else {
+        return;
+    };
+
+    // Send headers now; events follow as they arrive
+    ResponseOutparam::set(response_out, Ok(response));
+
+    if let Ok(stream) = output_body.write() {
+        let end = monotonic_clock::now()
+            .saturating_add(session_config.sse_listen_secs.saturating_mul(1_000_000_000));
+        let mut last_write = monotonic_clock::now();
+
+        while monotonic_clock::now() < end {
+            let events = match resume::take_outbox(&session_id, session_config) {
+                Ok(events) => events,
+                Err(e) => {
+                    eprintln!(
+                        "[transport:listen] Failed to read outbox for session {}: {}",
+                        session_id, e
+                    );
+                    break;
+                }
+            };
+
+            let bytes = if !events.is_empty() {
+                resume::concat(&events)
+            } else if monotonic_clock::now().saturating_sub(last_write) >= KEEP_ALIVE_NS {
+                ": keep-alive\n\n".to_string()
+            } else {
+                monotonic_clock::subscribe_duration(POLL_INTERVAL_NS).block();
+                continue;
+            };
+
+            // blocking-write-and-flush takes at most 4096 bytes per call
+            if bytes
+                .as_bytes()
+                .chunks(4096)
+                .any(|chunk| stream.blocking_write_and_flush(chunk).is_err())
+            {
+                // The client went away; keep the events for its next stream.
+                // Part of them may have arrived, and is sent again.
+                if let Err(e) = resume::restore_outbox(&session_id, events, session_config) {
+                    eprintln!(
+                        "[transport:listen] Failed to requeue events for session {}: {}",
+                        session_id, e
+                    );
+                }
+                break;
+            }
+            last_write = monotonic_clock::now();
+        }
+        drop(stream);
+    }
+    let _ = OutgoingBody::finish(output_body, None);
+}
@@ -0,0 +1,283 @@
+//! Prometheus metrics for the HTTP transport
+//!
+//! With `WASMCP_METRICS_ENABLED=true`, `GET /metrics` serves request counts,
+//! latencies, active sessions, auth failures and unmatched client responses in the Prometheus text format,
+//! for deployments without an OTLP collector.
+//!
+//! Each HTTP request runs in its own component instance, so the registry
+//! can't live in memory: like the concurrency counters, every series is a
+//! counter in the session key-value bucket updated with atomic `increment`.
+//! The set of series is fixed, so a scrape reads known keys instead of
+//! listing the bucket.
+//!
+//! NOTE: `wasmcp_active_sessions` counts sessions created minus sessions
+//! terminated by DELETE; sessions that only expire are not subtracted.
+
+use crate::bindings::wasi::clocks::monotonic_clock;
+use crate::bindings::wasi::http::types::{Method, OutgoingBody, ResponseOutparam};
+use crate::bindings::wasmcp::keyvalue::store::{self as kv_store, Bucket};
+use crate::config::TransportConfig;
+use crate::error::{BearerError, SessionError, TransportError};
+use crate::http::response;
+use crate::send_error;
+use std::cell::RefCell;
+use std::fmt::Write as _;
+
+/// KV key prefix shared by every series
+const KEY_PREFIX: &str = "wasmcp:metrics";
+
+/// Methods with their own request series; anything else counts as OTHER
+const METHODS: &[&str] = &["POST", "GET", "DELETE", "OTHER"];
+
+/// Status classes with their own request series
+const STATUS_CLASSES: &[&str] = &["2xx", "3xx", "4xx", "5xx"];
+
+/// Upper bounds of the latency histogram buckets, in seconds
+const LATENCY_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Reasons requests are rejected by authentication or session binding
+const AUTH_FAILURE_REASONS: &[&str] = &[
+    "missing_token",
+    "invalid_request",
+    "invalid_token",
+    "insufficient_scope",
+    "invalid_dpop_proof",
+    "session_mismatch",
+];
+
+/// Measurements for the request currently being handled
+struct Recording {
+    bucket: String,
+    started: u64,
+    status: Option<u16>,
+}
+
+thread_local! {
+    /// None when metrics are disabled
+    static RECORDING: RefCell<Option<Recording>> = const { RefCell::new(None) };
+}
+
+/// Start measuring a new request
+pub fn begin(config: &TransportConfig) {
+    let recording = config.metrics_enabled.then(|| Recording {
+        bucket: config.get_session_bucket().to_string(),
+        started: monotonic_clock::now(),
+        status: None,
+    });
+    RECORDING.with(|r| *r.borrow_mut() = recording);
+}
+
+/// Note the status of the response being sent
+pub fn record_status(status: u16) {
+    RECORDING.with(|r| {
+        if let Some(recording) = r.borrow_mut().as_mut() {
+            recording.status = Some(status);
+        }
+    });
+}
+
+/// Count a request rejected by authentication or session binding
+pub fn record_rejection(error: &TransportError) {
+    let reason = match error {
+        TransportError::Unauthorized { error: None, .. } => "missing_token",
+        TransportError::Unauthorized {
+            error: Some(error), ..
+        } => match error {
+            BearerError::InvalidRequest => "invalid_request",
+            BearerError::InvalidToken => "invalid_token",
+            BearerError::InsufficientScope => "insufficient_scope",
+            BearerError::InvalidDpopProof => "invalid_dpop_proof",
+        },
+        TransportError::Session(SessionError::IdentityMismatch(_)) => "session_mismatch",
+        _ => return,
+    };
+    add(&format!("auth_failures:{}", reason), 1);
+}
+
+/// Count a session created at initialize
+pub fn session_opened() {
+    add("sessions:active", 1);
+}
+
+/// Count a session terminated by the client
+pub fn session_closed() {
+    add("sessions:active", -1);
+}
+
+/// Count a client response dropped for matching no server request
+pub fn unmatched_response() {
+    add("responses:unmatched", 1);
+}
+
+/// Record the finished request's count and latency
+pub fn finish(method: &Method) {
+    let Some(recording) = RECORDING.with(|r| r.borrow_mut().take()) else {
+        return;
+    };
+    let Some(bucket) = open(&recording.bucket) else {
+        return;
+    };
+
+    let method = match method {
+        Method::Post => "POST",
+        Method::Get => "GET",
+        Method::Delete => "DELETE",
+        _ => "OTHER",
+    };
+    let class = match recording.status.unwrap_or(500) {
+        200..=299 => "2xx",
+        300..=399 => "3xx",
+        400..=499 => "4xx",
+        _ => "5xx",
+    };
+    increment(&bucket, &format!("requests:{}:{}", method, class), 1);
+
+    let elapsed_us = monotonic_clock::now().saturating_sub(recording.started) / 1_000;
+    let seconds = elapsed_us as f64 / 1_000_000.0;
+    let bucket_index = LATENCY_BUCKETS
+        .iter()
+        .position(|&le| seconds <= le)
+        .unwrap_or(LATENCY_BUCKETS.len());
+    increment(&bucket, &format!("duration:{}", bucket_index), 1);
+    increment(&bucket, "duration:sum_us", elapsed_us as i64);
+    increment(&bucket, "duration:count", 1);
+}
+
+/// Handle GET /metrics
+pub fn handle_metrics(response_out: ResponseOutparam, config: &TransportConfig) {
+    let Some(bucket) = open(config.get_session_bucket()) else {
+        send_error!(
+            response_out,
+            TransportError::internal("Failed to open metrics storage")
+        );
+    };
+    let text = render(|key| read(&bucket, key));
+
+    let response = match response::ResponseBuilder::new()
+        .status(200)
+        .header("content-type", b"text/plain; version=0.0.4; charset=utf-8")
+        .build()
+    {
+        Ok(r) => r,
+        Err(e) => send_error!(response_out, e),
+    };
+
+    if let Ok(body) = response.body() {
+        if let Ok(stream) = body.write() {
+            let _ = stream.blocking_write_and_flush(text.as_bytes());
+            drop(stream);
+        }
+        let _ = OutgoingBody::finish(body, None);
+    }
+
+    ResponseOutparam::set(response_out, Ok(response));
+}
+
+/// Render every series in the Prometheus text format
+///
+/// `read` returns the stored value of a counter key (without the prefix).
+fn render(read: impl Fn(&str) -> i64) -> String {
+    let mut out = String::new();
+
+    out.push_str("# HELP wasmcp_http_requests_total HTTP requests handled.\n");
+    out.push_str("# TYPE wasmcp_http_requests_total counter\n");
+    for method in METHODS {
+        for class in STATUS_CLASSES {
+            let _ = writeln!(
+                out,
+                "wasmcp_http_requests_total{{method=\"{}\",status=\"{}\"}} {}",
+                method,
+                class,
+                read(&format!("requests:{}:{}", method, class))
+            );
+        }
+    }
+
+    out.push_str("# HELP wasmcp_http_request_duration_seconds HTTP request latency.\n");
+    out.push_str("# TYPE wasmcp_http_request_duration_seconds histogram\n");
+    let mut cumulative = 0;
+    for (i, le) in LATENCY_BUCKETS.iter().enumerate() {
+        cumulative += read(&format!("duration:{}", i));
+        let _ = writeln!(
+            out,
+            "wasmcp_http_request_duration_seconds_bucket{{le=\"{}\"}} {}",
+            le, cumulative
+        );
+    }
+    cumulative += read(&format!("duration:{}", LATENCY_BUCKETS.len()));
+    let _ = writeln!(
+        out,
+        "wasmcp_http_request_duration_seconds_bucket{{le=\"+Inf\"}} {}",
+        cumulative
+    );
+    let _ = writeln!(
+        out,
+        "wasmcp_http_request_duration_seconds_sum {}",
+        read("duration:sum_us") as f64 / 1_000_000.0
+    );
+    let _ = writeln!(
+        out,
+        "wasmcp_http_request_duration_seconds_count {}",
+        read("duration:count")
+    );
+
+    out.push_str("# HELP wasmcp_active_sessions Sessions created and not yet terminated.\n");
+    out.push_str("# TYPE wasmcp_active_sessions gauge\n");
+    let _ = writeln!(
+        out,
+        "wasmcp_active_sessions {}",
+        read("sessions:active").max(0)
+    );
+
+    out.push_str("# HELP wasmcp_auth_failures_total Requests rejected by authentication.\n");
+    out.push_str("# TYPE wasmcp_auth_failures_total counter\n");
+    for reason in AUTH_FAILURE_REASONS {
+        let _ = writeln!(
+            out,
+            "wasmcp_auth_failures_total{{reason=\"{}\"}} {}",
+            reason,
+            read(&format!("auth_failures:{}", reason))
+        );
+    }
+
+    out.push_str(
+        "# HELP wasmcp_unmatched_responses_total Client responses matching no server request.\n",
+    );
+    out.push_str("# TYPE wasmcp_unmatched_responses_total counter\n");
+    let _ = writeln!(
+        out,
+        "wasmcp_unmatched_responses_total {}",
+        read("responses:unmatched")
+    );
+
+    out
+}
+
+/// Add to a counter in the current request's bucket
+fn add(key: &str, delta: i64) {
+    let bucket = RECORDING.with(|r| r.borrow().as_ref().map(|rec| rec.bucket.clone()));
+    if let Some(bucket) = bucket.as_deref().and_then(open) {
+        increment(&bucket, key, delta);
+    }
+}
+
+fn open(name: &str) -> Option<Bucket> {
+    kv_store::open(name)
+        .map_err(|e| eprintln!("[transport:metrics] Failed to open bucket: {:?}", e))
+        .ok()
+}
+
+fn increment(bucket: &Bucket, key: &str, delta: i64) {
+    if let Err(e) = bucket.increment(&format!("{}:{}", KEY_PREFIX, key), delta) {
+        eprintln!("[transport:metrics] Failed to update {}: {:?}", key, e);
+    }
+}
+
+/// Current value of a counter (incrementing by zero creates missing keys at 0)
+fn read(bucket: &Bucket, key: &str) -> i64 {
+    bucket
+        .increment(&format!("{}:{}", KEY_PREFIX, key), 0)
+        .unwrap_or(0)
+}
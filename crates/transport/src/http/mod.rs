@@ -3,22 +3,38 @@
 //! Handles HTTP-specific protocol concerns:
 //! - Origin validation (DNS rebinding protection)
 //! - Header validation (Accept, MCP-Protocol-Version)
+//! - Checking POST bodies against their Content-Digest header
 //! - HTTP method routing (POST, GET, DELETE)
+//! - Dropping client responses to requests the server never sent
+//! - Resuming SSE streams from the session outbox (GET with Last-Event-ID)
+//! - Listening streams polling the session outbox (GET, `WASMCP_SSE_LISTEN_SECS`)
+//! - The REST facade for tools (`POST /tools/{name}`, when enabled)
 //! - Request/response lifecycle
 //!
 //! Delegates I/O to http-server-io via server-io interface
 
+mod auth;
+mod challenge;
 mod delete;
+mod digest;
 pub mod discovery;
+pub(crate) mod dpop;
 mod get;
 pub(crate) mod helpers;
+mod jwks;
+mod listen;
+pub(crate) mod metrics;
+mod pending;
 pub mod post;
 pub(crate) mod response;
+mod rest;
+mod resume;
 mod session;
 mod validation;
 
 use crate::bindings::exports::wasi::http::incoming_handler::Guest;
 use crate::bindings::wasi::http::types::{IncomingRequest, Method, ResponseOutparam};
+use crate::common;
 use crate::config::TransportConfig;
 use crate::error::TransportError;
 use crate::send_error;
@@ -39,6 +55,26 @@ async fn handle_http_request_async(request: IncomingRequest, response_out: Respo
     // 1. Load session configuration once for the entire request
     let session_config = TransportConfig::from_env();
 
+    // Count, time and log every request, including rejected ones
+    metrics::begin(&session_config);
+    common::access_log::begin(&session_config, "http");
+    let method = request.method();
+    common::access_log::record("http_method", helpers::method_name(&method));
+    if let Some(bytes_in) = helpers::content_length(&request) {
+        common::access_log::record("bytes_in", bytes_in);
+    }
+
+    route_request(request, response_out, &session_config).await;
+
+    metrics::finish(&method);
+    common::access_log::finish();
+}
+
+async fn route_request(
+    request: IncomingRequest,
+    response_out: ResponseOutparam,
+    session_config: &TransportConfig,
+) {
     // 2. Validate Origin header (DNS rebinding protection)
     if let Err(e) = validation::validate_origin(&request) {
         send_error!(response_out, e);
@@ -53,13 +89,27 @@ async fn handle_http_request_async(request: IncomingRequest, response_out: Respo
     // 4. Parse method and handle accordingly
     let method = request.method();
 
-    match method {
-        Method::Post => {
-            post::handle_post(request, protocol_version, response_out, &session_config).await
+    // Plain JSON tool calls skip the MCP Accept and session checks
+    let rest_path = request
+        .path_with_query()
+        .filter(|path| session_config.rest_enabled && path.starts_with(rest::TOOLS_PREFIX));
+
+    match (method, rest_path) {
+        (Method::Post, Some(path)) => rest::handle_tool_call(
+            request,
+            &path,
+            protocol_version,
+            response_out,
+            session_config,
+        ),
+        (Method::Post, None) => {
+            post::handle_post(request, protocol_version, response_out, session_config).await
+        }
+        (Method::Get, _) => {
+            get::handle_get(request, protocol_version, response_out, session_config)
         }
-        Method::Get => get::handle_get(request, protocol_version, response_out, &session_config),
-        Method::Delete => delete::handle_delete(request, response_out, &session_config),
-        _ => match response::create_method_not_allowed_response(&session_config) {
+        (Method::Delete, _) => delete::handle_delete(request, response_out, session_config),
+        _ => match response::create_method_not_allowed_response(session_config) {
             Ok(response) => ResponseOutparam::set(response_out, Ok(response)),
             Err(e) => send_error!(response_out, TransportError::internal(e)),
         },
@@ -0,0 +1,73 @@
+//! Matching client responses to server-initiated requests
+//!
+//! server-io records every request sent on a session's stream under the
+//! session's `pending-request:<id>` key (see its `pending.rs`). A result or
+//! error the client POSTs for that session is forwarded to the handler chain
+//! only when it takes such an entry; anything else (a stale retry, a
+//! duplicate, an ID the server never used) is dropped with a log line and
+//! counted in `wasmcp_unmatched_responses_total`, so handlers never see
+//! responses to requests they did not make.
+//!
+//! Responses without a session are forwarded as before: requests are only
+//! recorded for sessions. If the bucket can't be read the response is
+//! forwarded too, rather than dropping one that may be awaited.
+
+use crate::bindings::wasmcp::keyvalue::store as kv_store;
+use crate::bindings::wasmcp::mcp_v20251125::mcp::RequestId;
+use crate::config::TransportConfig;
+use crate::http::metrics;
+use crate::session_keys;
+
+/// Whether a client response should reach the handler chain
+///
+/// Takes the matching entry, so a second response to the same request is
+/// dropped.
+pub fn take(session_id: Option<&str>, id: Option<&RequestId>, config: &TransportConfig) -> bool {
+    let (Some(session_id), Some(id)) = (session_id, id) else {
+        return true;
+    };
+
+    let key = format!(
+        "{}:{}:{}",
+        session_id,
+        session_keys::PENDING_REQUEST,
+        request_key(id)
+    );
+    let pending = kv_store::open(config.get_session_bucket()).and_then(|bucket| {
+        if bucket.exists(&key)? {
+            bucket.delete(&key)?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    });
+
+    match pending {
+        Ok(true) => true,
+        Ok(false) => {
+            eprintln!(
+                "[transport:pending] Dropping response to unknown request {} (session {})",
+                request_key(id),
+                session_id
+            );
+            metrics::unmatched_response();
+            false
+        }
+        Err(e) => {
+            eprintln!(
+                "[transport:pending] Failed to look up pending request {}: {:?}",
+                request_key(id),
+                e
+            );
+            true
+        }
+    }
+}
+
+/// Session key suffix identifying a request ID (as server-io writes it)
+fn request_key(id: &RequestId) -> String {
+    match id {
+        RequestId::Number(n) => format!("n:{}", n),
+        RequestId::String(s) => format!("s:{}", s),
+    }
+}
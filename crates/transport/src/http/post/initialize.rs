@@ -8,7 +8,7 @@
 
 use crate::bindings::wasi::http::types::{OutgoingBody, ResponseOutparam};
 use crate::bindings::wasmcp::mcp_v20251125::mcp::{
-    ClientRequest, Implementation, InitializeResult, RequestId, ServerMessage, ServerResult,
+    ClientRequest, RequestId, ServerMessage, ServerResult,
 };
 use crate::common;
 use crate::config::TransportConfig;
@@ -18,7 +18,7 @@ use crate::send_error;
 
 pub fn handle_initialize_request(
     request_id: RequestId,
-    _client_request: ClientRequest,
+    client_request: ClientRequest,
     protocol_version: String,
     identity: Option<&crate::bindings::wasmcp::mcp_v20251125::mcp::Identity>,
     response_out: ResponseOutparam,
@@ -33,9 +33,21 @@ pub fn handle_initialize_request(
         }
     };
 
-    // Get capabilities from downstream handler
-    let capabilities =
-        common::discover_capabilities_for_init(proto_ver, &common::plain_json_frame());
+    // Record client capabilities so probes and handlers see the negotiated state
+    let init_req = match client_request {
+        ClientRequest::Initialize(init_req) => Some(init_req),
+        _ => None,
+    };
+    let client_capabilities = init_req.as_ref().map(|req| req.capabilities.clone());
+    common::capability::set_negotiated_client_capabilities(client_capabilities.clone());
+    let client_info = init_req.as_ref().map(|req| req.client_info.clone());
+    common::client_info::set_client_info(client_info.clone());
+    let meta_locale = init_req
+        .as_ref()
+        .and_then(|req| common::locale::locale_from_meta(req.meta.as_deref()));
+    if meta_locale.is_some() {
+        common::locale::set_client_locale(meta_locale.clone());
+    }
 
     // Create session if enabled
     let new_session_id = session::initialize_session(session_config);
@@ -59,6 +71,33 @@ pub fn handle_initialize_request(
         send_error!(response_out, error);
     }
 
+    // Persist client capabilities for subsequent requests (best effort)
+    if let (Some(session_id), Some(client_capabilities)) = (&new_session_id, &client_capabilities)
+        && let Err(e) =
+            session::store_client_capabilities(session_id, client_capabilities, session_config)
+    {
+        eprintln!(
+            "[transport:initialize] WARNING: Failed to store client capabilities: {}",
+            e
+        );
+    }
+
+    // Persist client info, locale and the negotiated protocol version (best effort)
+    if let Some(session_id) = &new_session_id
+        && let Err(e) = session::store_client_info(
+            session_id,
+            client_info.as_ref(),
+            meta_locale.as_deref(),
+            &protocol_version,
+            session_config,
+        )
+    {
+        eprintln!(
+            "[transport:initialize] WARNING: Failed to store client info: {}",
+            e
+        );
+    }
+
     // Create plain JSON response with optional session header
     let mut builder = response::ResponseBuilder::new()
         .status(200)
@@ -89,20 +128,14 @@ pub fn handle_initialize_request(
         }
     };
 
-    // Build InitializeResult using MCP types
-    let init_result = InitializeResult {
-        meta: None,
-        server_info: Implementation {
-            name: "wasmcp-server".to_string(),
-            title: Some("wasmcp Universal Transport Server".to_string()),
-            version: env!("CARGO_PKG_VERSION").to_string(),
-            description: None,
-            icons: None,
-        },
-        capabilities,
-        protocol_version: proto_ver,
-        options: None,
-    };
+    // Build InitializeResult (configured server metadata, or a handler override)
+    // with capabilities discovered from downstream
+    let init_result = common::build_initialize_result(
+        init_req.as_ref(),
+        proto_ver,
+        &common::plain_json_frame(),
+        session_config,
+    );
 
     // Construct ServerMessage
     let server_message = ServerMessage::Result((request_id, ServerResult::Initialize(init_result)));
@@ -7,11 +7,12 @@
 //! - Single flush operation
 
 use crate::bindings::wasi::http::types::{OutgoingBody, ResponseOutparam};
-use crate::bindings::wasmcp::mcp_v20251125::mcp::{Error, ErrorCode, RequestId, ServerMessage};
+use crate::bindings::wasmcp::mcp_v20251125::mcp::{ErrorCode, RequestId, ServerMessage};
 use crate::common;
 use crate::config::TransportConfig;
 use crate::http::{post::message_handlers, response};
 use crate::send_error;
+use wasmcp_errors::McpError;
 
 #[allow(clippy::too_many_arguments)]
 pub fn handle_json_mode(
@@ -67,11 +68,7 @@ pub fn handle_json_mode(
     ) {
         eprintln!("[TRANSPORT] ERROR during request processing: {:?}", e);
         // Write error response to stream
-        let error_code = ErrorCode::InternalError(Error {
-            code: -32603,
-            message: e.message(),
-            data: None,
-        });
+        let error_code = ErrorCode::from(McpError::from(&e));
         let error_message = ServerMessage::Error((Some(request_id), error_code));
         let _ = crate::bindings::wasmcp::mcp_v20251125::server_io::send_message(
             &output_stream,
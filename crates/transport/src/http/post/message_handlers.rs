@@ -15,7 +15,7 @@ use crate::bindings::wasmcp::mcp_v20251125::server_handler::handle;
 use crate::common;
 use crate::config::TransportConfig;
 use crate::error::TransportError;
-use crate::http::response;
+use crate::http::{pending, response, session};
 use crate::send_error;
 
 /// Handle MCP request message
@@ -57,9 +57,12 @@ pub fn handle_mcp_request(
             Ok(())
         }
         ClientRequest::LoggingSetLevel(level) => {
-            let level_str = common::log_level_to_string(level);
-            common::handle_set_log_level(level_str)
+            common::handle_set_log_level(level)
                 .map_err(|e| TransportError::protocol(format!("SetLevel failed: {:?}", e)))?;
+            // Later requests run in fresh instances, so keep the level in the session
+            if let Some(sess_id) = session_id {
+                session::store_log_level(sess_id, level, config)?;
+            }
             common::write_mcp_result(
                 output_stream,
                 request_id,
@@ -79,7 +82,7 @@ pub fn handle_mcp_request(
                 session_id,
                 identity,
                 bucket,
-                output_stream,
+                Some(output_stream),
                 frame,
                 http_context,
             )
@@ -128,6 +131,7 @@ pub fn handle_mcp_notification(
 /// Handle result response from client (bidirectional MCP)
 ///
 /// In bidirectional MCP, clients can send responses to server-initiated requests.
+/// Responses matching no outstanding request are dropped (see `pending.rs`).
 pub fn handle_mcp_result(
     result_id: RequestId,
     client_result: ClientResult,
@@ -139,6 +143,10 @@ pub fn handle_mcp_result(
     let proto_ver =
         common::parse_protocol_version(&protocol_version).map_err(TransportError::protocol)?;
 
+    if !pending::take(session_id, Some(&result_id), session_config) {
+        return Ok(());
+    }
+
     // Create message context (no client-stream for results - client sending to server)
     let ctx = common::create_message_context(
         None,
@@ -164,6 +172,7 @@ pub fn handle_mcp_result(
 /// Handle error response from client (bidirectional MCP)
 ///
 /// In bidirectional MCP, clients can send error responses to server-initiated requests.
+/// Errors for no outstanding request are dropped (see `pending.rs`).
 pub fn handle_mcp_error(
     error_id: Option<RequestId>,
     error_code: ErrorCode,
@@ -175,6 +184,10 @@ pub fn handle_mcp_error(
     let proto_ver =
         common::parse_protocol_version(&protocol_version).map_err(TransportError::protocol)?;
 
+    if !pending::take(session_id, error_id.as_ref(), session_config) {
+        return Ok(());
+    }
+
     // Create message context (no client-stream for errors - client sending to server)
     let ctx = common::create_message_context(
         None,
@@ -7,19 +7,22 @@
 //! - Request body stream acquisition
 //! - MCP message parsing
 //! - Message type routing (Request, Notification, Result, Error)
-//! - Delegation to mode-specific handlers (JSON vs SSE)
+//! - Delegation to mode-specific handlers (JSON, SSE, or chosen per request)
 
 pub mod initialize;
 pub mod json_mode;
 pub mod message_handlers;
+pub mod on_demand_mode;
 pub mod sse_mode;
 
 use crate::bindings::wasi::http::types::{IncomingRequest, ResponseOutparam};
 use crate::bindings::wasmcp::mcp_v20251125::mcp::ClientRequest;
+use crate::bindings::wasmcp::mcp_v20251125::server_io;
 use crate::common;
-use crate::config::{AuthMode, TransportConfig};
+use crate::concurrency;
+use crate::config::TransportConfig;
 use crate::error::TransportError;
-use crate::http::{session, validation};
+use crate::http::{auth, digest, helpers, session, validation};
 use crate::send_error;
 
 pub async fn handle_post(
@@ -28,6 +31,13 @@ pub async fn handle_post(
     response_out: ResponseOutparam,
     session_config: &TransportConfig,
 ) {
+    // Start the response budget before any validation or queueing
+    common::deadline::start_request(session_config.request_timeout_ms);
+    common::response_budget::configure(
+        session_config.max_response_bytes,
+        session_config.response_overflow,
+    );
+
     // Validate Accept header per spec
     if let Err(e) = validation::validate_accept_header(&request) {
         send_error!(response_out, e);
@@ -39,82 +49,64 @@ pub async fn handle_post(
         Err(e) => send_error!(response_out, e),
     };
 
-    // Validate JWT based on auth mode from config
-    let identity = match session_config.auth_mode {
-        AuthMode::Public => {
-            // Public mode - no authentication required
-            None
-        }
-        AuthMode::OAuth => {
-            // OAuth mode - JWT required
-            // Validate that JWT is configured properly
-            if !session_config.jwt_configured {
-                let error = TransportError::internal(
-                    "WASMCP_AUTH_MODE=oauth requires JWT_PUBLIC_KEY or JWT_JWKS_URI to be configured",
-                );
-                send_error!(response_out, error);
-            }
-
-            match validation::extract_authorization_header(&request) {
-                Ok(Some(jwt)) => {
-                    // Import server-auth for JWT validation
-                    use crate::bindings::wasmcp::mcp_v20251125::server_auth;
-
-                    match server_auth::decode(&jwt) {
-                        Ok(claims) => Some(crate::bindings::wasmcp::mcp_v20251125::mcp::Identity {
-                            jwt,
-                            claims,
-                        }),
-                        Err(e) => {
-                            // Strict validation: return 401 with WWW-Authenticate header
-                            let www_authenticate = create_www_authenticate_challenge(
-                                &request,
-                                "invalid_token",
-                                "The access token provided is invalid, expired, or malformed",
-                            );
-
-                            let error = TransportError::unauthorized_with_challenge(
-                                format!("JWT validation failed: {:?}", e),
-                                www_authenticate,
-                            );
+    if let Some(sess_id) = &session_id {
+        common::access_log::record("session", sess_id.as_str());
+    }
 
-                            send_error!(response_out, error);
-                        }
-                    }
-                }
-                Ok(None) => {
-                    // OAuth mode requires token
-                    let www_authenticate = create_www_authenticate_challenge(
-                        &request,
-                        "invalid_request",
-                        "Authorization header with Bearer token is required",
-                    );
+    // Restore the client capabilities and info negotiated at initialize and
+    // the logging/setLevel choice for this session
+    let mut protocol_version = protocol_version;
+    if let Some(sess_id) = &session_id {
+        common::capability::set_negotiated_client_capabilities(session::load_client_capabilities(
+            sess_id,
+            session_config,
+        ));
+        common::log_level::set_client_log_level(session::load_log_level(sess_id, session_config));
+        common::client_info::set_client_info(session::load_client_info(sess_id, session_config));
+
+        // Without an MCP-Protocol-Version header, use the version negotiated
+        // at initialize rather than the backwards-compatible default
+        if !validation::has_protocol_version_header(&request)
+            && let Some(negotiated) = session::load_protocol_version(sess_id, session_config)
+        {
+            protocol_version = negotiated;
+        }
+    }
 
-                    let error = TransportError::unauthorized_with_challenge(
-                        "Missing required Authorization header",
-                        www_authenticate,
-                    );
+    // The locale chosen at initialize, else this request's Accept-Language
+    let locale = session_id
+        .as_ref()
+        .and_then(|sess_id| session::load_locale(sess_id, session_config))
+        .or_else(|| validation::accept_language(&request));
+    common::locale::set_client_locale(locale);
+    common::trace_context::set_trace_context(validation::traceparent(&request));
+
+    // Never go above the configured maximum, including at initialize; errors
+    // use the codes that version defines
+    if let Ok(requested) = common::parse_protocol_version(&protocol_version) {
+        let clamped =
+            common::clamp_protocol_version(requested, session_config.max_protocol_version);
+        server_io::set_protocol_version(clamped);
+        protocol_version = common::protocol::protocol_version_to_string(clamped);
+    }
 
-                    send_error!(response_out, error);
-                }
-                Err(e) => {
-                    // Malformed header - always error
-                    let www_authenticate = create_www_authenticate_challenge(
-                        &request,
-                        "invalid_request",
-                        "Malformed Authorization header",
-                    );
+    // Validate JWT based on auth mode from config
+    let identity = match auth::authenticate(&request, session_config) {
+        Ok(identity) => identity,
+        Err(e) => send_error!(response_out, e),
+    };
 
-                    let error = TransportError::unauthorized_with_challenge(
-                        format!("Invalid Authorization header: {}", e),
-                        www_authenticate,
-                    );
+    if let Some(identity) = &identity {
+        common::access_log::record("sub", identity.claims.subject.as_str());
+    }
 
-                    send_error!(response_out, error);
-                }
-            }
-        }
-    };
+    // Only the identity that created the session may use it
+    // This prevents session hijacking where User B's JWT could access User A's session
+    if let Err(e) =
+        session::enforce_session_binding(session_id.as_deref(), identity.as_ref(), session_config)
+    {
+        send_error!(response_out, e);
+    }
 
     // Get request body stream
     let body_stream = match request.consume() {
@@ -132,21 +124,36 @@ pub async fn handle_post(
         }
     };
 
-    // Parse MCP message
-    let message = match common::parse_mcp_message(
-        &input_stream,
-        common::http_read_limit(),
-        &common::plain_json_frame(),
-    ) {
+    // Parse MCP message; a body with a Content-Digest must match it first
+    let parsed = match digest::content_digest(&request) {
+        Some(content_digest) => {
+            match helpers::read_all(&input_stream, common::framing::HTTP_MAX_REQUEST_SIZE) {
+                Ok(body) => digest::verify(&content_digest, &body)
+                    .map_err(TransportError::validation)
+                    .and_then(|()| {
+                        common::decode_mcp_message(&body).map_err(TransportError::protocol)
+                    }),
+                Err(e) => Err(TransportError::validation(e)),
+            }
+        }
+        None => common::parse_mcp_message(
+            &input_stream,
+            common::http_read_limit(),
+            &common::plain_json_frame(),
+        )
+        .map_err(TransportError::protocol),
+    };
+    let message = match parsed {
         Ok(m) => m,
-        Err(e) => {
-            let error = TransportError::protocol(e);
+        Err(error) => {
             send_error!(response_out, error);
         }
     };
 
+    common::access_log::record_message(&message);
+
     // Build HTTP context to pass to downstream components
-    let http_context = build_http_context(&request);
+    let http_context = build_http_context(&request, &session_config.forward_headers);
 
     match message {
         common::McpMessage::Request(request_id, client_request) => {
@@ -165,16 +172,6 @@ pub async fn handle_post(
                 return;
             }
 
-            // Not initialize - validate session identity binding if both session and identity present
-            // This prevents session hijacking where User B's JWT could access User A's session
-            if let (Some(sess_id), Some(ident)) = (&session_id, &identity)
-                && let Err(e) = session::validate_session_identity(sess_id, ident, session_config)
-            {
-                drop(input_stream);
-                drop(body_stream);
-                send_error!(response_out, e);
-            }
-
             // Not initialize - check if session is required
             if !session::check_session_required(session_config, session_id.as_deref()) {
                 drop(input_stream);
@@ -183,6 +180,25 @@ pub async fn handle_post(
                 send_error!(response_out, error);
             }
 
+            // Not initialize - enforce tool concurrency limits before dispatching
+            // The permit is held until the mode-specific handler returns
+            let _tool_permit = if let ClientRequest::ToolsCall(call) = &client_request {
+                match concurrency::acquire_tool_permit(
+                    session_config,
+                    session_id.as_deref(),
+                    &call.name,
+                ) {
+                    Ok(permit) => permit,
+                    Err(e) => {
+                        drop(input_stream);
+                        drop(body_stream);
+                        send_error!(response_out, e);
+                    }
+                }
+            } else {
+                None
+            };
+
             // Not initialize - delegate to mode-specific handler
             if session_config.disable_sse {
                 json_mode::handle_json_mode(
@@ -197,6 +213,19 @@ pub async fn handle_post(
                     session_config,
                     Some(http_context.clone()),
                 )
+            } else if session_config.sse_on_demand {
+                on_demand_mode::handle_on_demand_mode(
+                    request_id,
+                    client_request,
+                    protocol_version,
+                    session_id.as_deref(),
+                    identity.as_ref(),
+                    input_stream,
+                    body_stream,
+                    response_out,
+                    session_config,
+                    Some(http_context.clone()),
+                )
             } else {
                 sse_mode::handle_sse_streaming_mode(
                     request_id,
@@ -253,8 +282,9 @@ pub async fn handle_post(
 }
 
 /// Build HTTP context for authorization
-fn build_http_context(
+pub(crate) fn build_http_context(
     request: &IncomingRequest,
+    forward_headers: &[String],
 ) -> crate::bindings::wasmcp::mcp_v20251125::server_auth::HttpContext {
     // Extract HTTP method
     let method = match request.method() {
@@ -288,7 +318,13 @@ fn build_http_context(
         "x-real-ip",
     ];
 
-    for name in &header_names {
+    // Plus any configured via WASMCP_FORWARD_HEADERS
+    let extra_names = forward_headers
+        .iter()
+        .map(String::as_str)
+        .filter(|name| !header_names.contains(name));
+
+    for name in header_names.into_iter().chain(extra_names) {
         let values = headers_obj.get(name);
         if !values.is_empty()
             && let Ok(value) = String::from_utf8(values[0].clone())
@@ -303,33 +339,3 @@ fn build_http_context(
         headers,
     }
 }
-
-/// Create WWW-Authenticate challenge header per RFC 6750
-/// Includes error code, description, and resource metadata URL
-fn create_www_authenticate_challenge(
-    request: &IncomingRequest,
-    error: &str,
-    error_description: &str,
-) -> String {
-    use crate::bindings::wasi::cli::environment::get_environment;
-    use crate::http::helpers::get_server_uri;
-
-    // Get server URI for resource metadata
-    let env_vars = get_environment();
-    let server_uri = get_server_uri(&env_vars, request);
-
-    // Build WWW-Authenticate header
-    let mut parts = vec!["Bearer".to_string()];
-    parts.push(format!("realm=\"{}\"", server_uri));
-
-    // Add resource_metadata URL per RFC 9728
-    parts.push(format!(
-        "resource_metadata=\"{}/.well-known/oauth-protected-resource\"",
-        server_uri
-    ));
-
-    parts.push(format!("error=\"{}\"", error));
-    parts.push(format!("error_description=\"{}\"", error_description));
-
-    parts.join(", ")
-}
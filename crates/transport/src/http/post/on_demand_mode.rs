@@ -0,0 +1,141 @@
+//! On-demand SSE POST handler (`WASMCP_SSE_ON_DEMAND`)
+//!
+//! Streamable HTTP lets the server answer a POST with either a single JSON
+//! response or an SSE stream carrying notifications and server requests
+//! before the response. This mode picks per request:
+//! - Handlers get an SSE frame, so they may send interim messages
+//! - server-io holds every message until the handler returns
+//! - One message (just the response) goes out as `application/json`
+//! - More go out as `text/event-stream`, in the order they were sent
+//!
+//! The response is set after all writes complete, as in JSON mode.
+
+use crate::bindings::wasi::http::types::{OutgoingBody, OutgoingResponse, ResponseOutparam};
+use crate::bindings::wasmcp::mcp_v20251125::mcp::{ErrorCode, RequestId, ServerMessage};
+use crate::bindings::wasmcp::mcp_v20251125::server_io;
+use crate::common;
+use crate::config::TransportConfig;
+use crate::error::TransportError;
+use crate::http::{post::message_handlers, response};
+use crate::send_error;
+use wasmcp_errors::McpError;
+
+#[allow(clippy::too_many_arguments)]
+pub fn handle_on_demand_mode(
+    request_id: RequestId,
+    client_request: crate::bindings::wasmcp::mcp_v20251125::mcp::ClientRequest,
+    protocol_version: String,
+    session_id: Option<&str>,
+    identity: Option<&crate::bindings::wasmcp::mcp_v20251125::mcp::Identity>,
+    input_stream: crate::bindings::wasi::io::streams::InputStream,
+    body_stream: crate::bindings::wasi::http::types::IncomingBody,
+    response_out: ResponseOutparam,
+    config: &TransportConfig,
+    http_context: Option<crate::bindings::wasmcp::mcp_v20251125::server_auth::HttpContext>,
+) {
+    // The JSON response is the likely answer; handlers only need its stream
+    // to pass to server-io, which holds their messages
+    let json_response = match build_response(false) {
+        Ok(r) => r,
+        Err(e) => send_error!(response_out, e),
+    };
+    let json_body = match json_response.body() {
+        Ok(b) => b,
+        Err(_) => {
+            let error = TransportError::internal("Failed to get response body");
+            send_error!(response_out, error);
+        }
+    };
+    let json_stream = match json_body.write() {
+        Ok(s) => s,
+        Err(_) => {
+            let error = TransportError::internal("Failed to get output stream");
+            send_error!(response_out, error);
+        }
+    };
+
+    // Session streams tag events so the client can resume them
+    let frame = session_id.map_or_else(common::http_sse_frame, common::http_sse_event_frame);
+
+    server_io::hold_messages();
+    if let Err(e) = message_handlers::handle_mcp_request(
+        request_id.clone(),
+        client_request,
+        protocol_version,
+        session_id,
+        identity,
+        &json_stream,
+        &frame,
+        config,
+        http_context,
+    ) {
+        eprintln!("[TRANSPORT] ERROR during request processing: {:?}", e);
+        let error_code = ErrorCode::from(McpError::from(&e));
+        let error_message = ServerMessage::Error((Some(request_id), error_code));
+        let _ = server_io::send_message(&json_stream, error_message, &frame);
+    }
+    drop(input_stream);
+    drop(body_stream);
+
+    if server_io::held_messages() <= 1 {
+        finish(json_response, json_body, json_stream, response_out);
+        return;
+    }
+
+    // Interim messages were sent: answer with an event stream instead
+    drop(json_stream);
+    drop(json_body);
+    drop(json_response);
+    let sse_response = match build_response(true) {
+        Ok(r) => r,
+        Err(e) => send_error!(response_out, e),
+    };
+    let sse_body = match sse_response.body() {
+        Ok(b) => b,
+        Err(_) => {
+            let error = TransportError::internal("Failed to get response body");
+            send_error!(response_out, error);
+        }
+    };
+    let sse_stream = match sse_body.write() {
+        Ok(s) => s,
+        Err(_) => {
+            let error = TransportError::internal("Failed to get output stream");
+            send_error!(response_out, error);
+        }
+    };
+    finish(sse_response, sse_body, sse_stream, response_out);
+}
+
+/// A 200 response with a JSON body, or an event stream body
+fn build_response(event_stream: bool) -> Result<OutgoingResponse, TransportError> {
+    let builder = response::ResponseBuilder::new().status(200);
+    if event_stream {
+        builder
+            .header("content-type", b"text/event-stream")
+            .header("cache-control", b"no-cache")
+            .build()
+    } else {
+        builder.header("content-type", b"application/json").build()
+    }
+}
+
+/// Write the held messages, then set the response
+fn finish(
+    response: OutgoingResponse,
+    output_body: OutgoingBody,
+    output_stream: crate::bindings::wasi::io::streams::OutputStream,
+    response_out: ResponseOutparam,
+) {
+    if let Err(e) = server_io::flush_buffer(&output_stream) {
+        eprintln!("[TRANSPORT] ERROR flushing buffer: {:?}", e);
+    }
+
+    // Drop the stream BEFORE finishing the body (it is a child of the body)
+    drop(output_stream);
+    if let Err(e) = OutgoingBody::finish(output_body, None) {
+        eprintln!("[TRANSPORT] ERROR finishing body: {:?}", e);
+    }
+
+    crate::bindings::wasi::http::types::ResponseOutparam::set(response_out, Ok(response));
+}
@@ -5,16 +5,18 @@
 //! - Streams output incrementally
 //! - Respects backpressure
 //! - Async writes with yielding
+//! - Tags events with the session ID, for resumption (see `resume.rs`)
 //!
 //! CRITICAL: Response is set before getting output stream.
 //! After that point, errors cannot use send_error! (response_out consumed).
 
 use crate::bindings::wasi::http::types::{OutgoingBody, ResponseOutparam};
-use crate::bindings::wasmcp::mcp_v20251125::mcp::{Error, ErrorCode, RequestId, ServerMessage};
+use crate::bindings::wasmcp::mcp_v20251125::mcp::{ErrorCode, RequestId, ServerMessage};
 use crate::common;
 use crate::config::TransportConfig;
 use crate::http::{post::message_handlers, response};
 use crate::send_error;
+use wasmcp_errors::McpError;
 
 /// Handle POST request in SSE streaming mode (async writes with yielding)
 ///
@@ -64,6 +66,9 @@ pub async fn handle_sse_streaming_mode(
         }
     };
 
+    // Session streams tag events so the client can resume them
+    let frame = session_id.map_or_else(common::http_sse_frame, common::http_sse_event_frame);
+
     // Process request with SSE framing
     if let Err(e) = message_handlers::handle_mcp_request(
         request_id.clone(),
@@ -72,22 +77,18 @@ pub async fn handle_sse_streaming_mode(
         session_id,
         identity,
         &output_stream,
-        &common::http_sse_frame(),
+        &frame,
         config,
         http_context,
     ) {
         eprintln!("[TRANSPORT] ERROR during request processing: {:?}", e);
         // Write error response to SSE stream
-        let error_code = ErrorCode::InternalError(Error {
-            code: -32603,
-            message: e.message(),
-            data: None,
-        });
+        let error_code = ErrorCode::from(McpError::from(&e));
         let error_message = ServerMessage::Error((Some(request_id), error_code));
         let _ = crate::bindings::wasmcp::mcp_v20251125::server_io::send_message(
             &output_stream,
             error_message,
-            &common::http_sse_frame(),
+            &frame,
         );
     }
 
@@ -8,8 +8,11 @@
 use crate::bindings::wasi::http::types::{
     Fields, OutgoingBody, OutgoingResponse, ResponseOutparam,
 };
+use crate::common::access_log;
 use crate::config::TransportConfig;
 use crate::error::TransportError;
+use crate::http::metrics;
+use wasmcp_errors::McpError;
 
 /// Macro to send an error response and return early
 ///
@@ -77,6 +80,7 @@ impl ResponseBuilder {
             .set_status_code(self.status)
             .map_err(|_| TransportError::internal("Failed to set status code"))?;
 
+        note_status(self.status);
         Ok(response)
     }
 
@@ -106,6 +110,8 @@ impl Default for ResponseBuilder {
 pub fn transport_error_to_response(error: &TransportError) -> OutgoingResponse {
     let status_code = error.http_status_code();
     let error_message = error.message();
+    note_status(status_code);
+    metrics::record_rejection(error);
 
     let response = OutgoingResponse::new(Fields::new());
     let _ = response.set_status_code(status_code);
@@ -113,7 +119,7 @@ pub fn transport_error_to_response(error: &TransportError) -> OutgoingResponse {
     let headers = response.headers();
     let _ = headers.set("content-type", &[b"application/json".to_vec()]);
 
-    // Add WWW-Authenticate header for 401 Unauthorized responses
+    // Add WWW-Authenticate challenge for rejected bearer tokens
     if let Some(www_authenticate) = error.www_authenticate_header() {
         eprintln!(
             "[transport:response] Adding WWW-Authenticate header: {}",
@@ -122,9 +128,14 @@ pub fn transport_error_to_response(error: &TransportError) -> OutgoingResponse {
         let _ = headers.set("www-authenticate", &[www_authenticate.as_bytes().to_vec()]);
     }
 
+    // Tell clients how long to back off for retryable failures
+    if let Some(retry_after) = error.retry_after_secs() {
+        let _ = headers.set("retry-after", &[retry_after.to_string().into_bytes()]);
+    }
+
     if let Ok(body) = response.body() {
         if let Ok(stream) = body.write() {
-            let error_json = serde_json::json!({
+            let mut error_json = serde_json::json!({
                 "jsonrpc": "2.0",
                 "id": null,
                 "error": {
@@ -132,6 +143,13 @@ pub fn transport_error_to_response(error: &TransportError) -> OutgoingResponse {
                     "message": error_message
                 }
             });
+            // Mirror the retry hints JSON-RPC errors carry in their data
+            let (_, _, data) = McpError::from(error).into_parts();
+            if let Some(data) =
+                data.and_then(|d| serde_json::from_str::<serde_json::Value>(&d).ok())
+            {
+                error_json["error"]["data"] = data;
+            }
             let _ = stream.blocking_write_and_flush(error_json.to_string().as_bytes());
             drop(stream);
         }
@@ -163,6 +181,7 @@ pub fn create_method_not_allowed_response(
     response
         .set_status_code(405)
         .map_err(|_| "Failed to set status")?;
+    note_status(405);
 
     Ok(response)
 }
@@ -173,5 +192,12 @@ pub fn create_accepted_response() -> Result<OutgoingResponse, String> {
     response
         .set_status_code(202)
         .map_err(|_| "Failed to set status")?;
+    note_status(202);
     Ok(response)
 }
+
+/// Record the status of the response being sent for metrics and the access log
+fn note_status(status: u16) {
+    metrics::record_status(status);
+    access_log::record("status", status);
+}
@@ -0,0 +1,247 @@
+//! REST facade for tools
+//!
+//! With `WASMCP_REST_ENABLED=true`, `POST /tools/{name}` calls a tool without
+//! speaking MCP, for cron jobs, webhooks and other plain HTTP clients:
+//! - The request body is the tool's arguments as a JSON object (or empty)
+//! - The call goes through the same handler chain as `tools/call`
+//! - The response is plain JSON: the structured content if the tool returned
+//!   any, else `{"text": ...}` with its text content joined by newlines
+//! - A tool that returns neither content nor structured content answers
+//!   204 No Content
+//! - Tool errors (`isError`) are 422 with `{"error": {"message", "data"}}`
+//! - JSON-RPC errors use their kind's HTTP status (e.g. 400 for an unknown
+//!   tool) with `{"error": {"code", "message", "data"}}`
+//! - Transport errors (authentication, rate limits) are sent as for MCP
+//!   requests
+//!
+//! REST calls are sessionless: there is no initialize, so handlers see no
+//! session, client capabilities or client info, and no client stream for
+//! notifications. Authentication, concurrency limits, the response budget
+//! and the access log apply as they do to MCP requests.
+
+use crate::bindings::wasi::http::types::{IncomingRequest, OutgoingBody, ResponseOutparam};
+use crate::bindings::wasmcp::mcp_v20251125::mcp::{
+    CallToolRequest, CallToolResult, ClientRequest, ContentBlock, ErrorCode, RequestId,
+    ServerResult, TextData,
+};
+use crate::common;
+use crate::concurrency;
+use crate::config::TransportConfig;
+use crate::error::TransportError;
+use crate::http::helpers::read_all;
+use crate::http::{auth, post, response, validation};
+use crate::send_error;
+use serde_json::{Value, json};
+use wasmcp_errors::McpError;
+
+/// Path prefix of tool endpoints
+pub const TOOLS_PREFIX: &str = "/tools/";
+
+/// Status for a tool that ran and reported an error (`isError: true`)
+const TOOL_ERROR_STATUS: u16 = 422;
+
+/// Request ID handed to handlers, which never reaches the REST caller
+const REST_REQUEST_ID: &str = "rest";
+
+/// Handle POST /tools/{name}
+pub fn handle_tool_call(
+    request: IncomingRequest,
+    path: &str,
+    protocol_version: String,
+    response_out: ResponseOutparam,
+    config: &TransportConfig,
+) {
+    common::deadline::start_request(config.request_timeout_ms);
+    common::response_budget::configure(config.max_response_bytes, config.response_overflow);
+    common::locale::set_client_locale(validation::accept_language(&request));
+    common::trace_context::set_trace_context(validation::traceparent(&request));
+
+    // Tool names never contain '/', and a query string has no meaning here
+    let name = path
+        .strip_prefix(TOOLS_PREFIX)
+        .unwrap_or_default()
+        .split('?')
+        .next()
+        .unwrap_or_default();
+    if name.is_empty() || name.contains('/') {
+        send_error!(
+            response_out,
+            TransportError::validation("Expected POST /tools/{name}")
+        );
+    }
+
+    let protocol_version = match common::parse_protocol_version(&protocol_version) {
+        Ok(requested) => common::clamp_protocol_version(requested, config.max_protocol_version),
+        Err(e) => send_error!(response_out, TransportError::protocol(e)),
+    };
+
+    let identity = match auth::authenticate(&request, config) {
+        Ok(identity) => identity,
+        Err(e) => send_error!(response_out, e),
+    };
+    if let Some(identity) = &identity {
+        common::access_log::record("sub", identity.claims.subject.as_str());
+    }
+
+    let http_context = post::build_http_context(&request, &config.forward_headers);
+
+    let arguments = match read_arguments(&request) {
+        Ok(arguments) => arguments,
+        Err(e) => send_error!(response_out, e),
+    };
+
+    let client_request = ClientRequest::ToolsCall(CallToolRequest {
+        name: name.to_string(),
+        arguments,
+    });
+    common::access_log::record("method", "tools/call");
+    common::access_log::record("tool", name);
+
+    // Held until the tool returns
+    let _tool_permit = match concurrency::acquire_tool_permit(config, None, name) {
+        Ok(permit) => permit,
+        Err(e) => send_error!(response_out, e),
+    };
+
+    let result = common::delegate_to_middleware(
+        RequestId::String(REST_REQUEST_ID.to_string()),
+        client_request,
+        protocol_version,
+        None,
+        identity.as_ref(),
+        config.get_session_bucket().to_string(),
+        None,
+        &common::plain_json_frame(),
+        Some(http_context),
+    );
+
+    let (status, body) = match result {
+        Ok(ServerResult::ToolsCall(result)) if is_empty(&result) => {
+            let _ = response::ResponseBuilder::new()
+                .status(204)
+                .build_and_send(response_out);
+            return;
+        }
+        Ok(ServerResult::ToolsCall(result)) => tool_result_to_json(result),
+        Ok(_) => {
+            let error = McpError::internal("Handler returned a non-tools/call result");
+            error_to_json(&ErrorCode::from(error))
+        }
+        Err(e) => {
+            common::access_log::record_error(&e);
+            error_to_json(&e)
+        }
+    };
+    send_json(response_out, status, &body);
+}
+
+/// Whether a tool succeeded without producing any output
+fn is_empty(result: &CallToolResult) -> bool {
+    !result.is_error.unwrap_or(false)
+        && result.content.is_empty()
+        && result.structured_content.is_none()
+}
+
+/// Read the request body as tool arguments
+///
+/// An empty body calls the tool without arguments.
+fn read_arguments(request: &IncomingRequest) -> Result<Option<String>, TransportError> {
+    let body = request
+        .consume()
+        .map_err(|_| TransportError::internal("Failed to consume request"))?;
+    let stream = body
+        .stream()
+        .map_err(|_| TransportError::internal("Failed to get input stream"))?;
+    let bytes = read_all(&stream, common::framing::HTTP_MAX_REQUEST_SIZE)
+        .map_err(TransportError::validation)?;
+    drop(stream);
+    drop(body);
+
+    if bytes.iter().all(u8::is_ascii_whitespace) {
+        return Ok(None);
+    }
+    match serde_json::from_slice::<Value>(&bytes) {
+        Ok(arguments @ Value::Object(_)) => Ok(Some(arguments.to_string())),
+        Ok(_) => Err(TransportError::validation(
+            "Tool arguments must be a JSON object",
+        )),
+        Err(e) => Err(TransportError::validation(format!(
+            "Invalid JSON body: {}",
+            e
+        ))),
+    }
+}
+
+/// Plain JSON for a tool result, with its HTTP status
+fn tool_result_to_json(result: CallToolResult) -> (u16, Value) {
+    let text = result
+        .content
+        .iter()
+        .filter_map(|block| match block {
+            ContentBlock::Text(t) => match &t.text {
+                TextData::Text(s) => Some(s.clone()),
+                TextData::TextStream(stream) => {
+                    read_all(stream, common::framing::HTTP_MAX_REQUEST_SIZE)
+                        .ok()
+                        .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+                }
+            },
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let structured = result
+        .structured_content
+        .and_then(|s| serde_json::from_str::<Value>(&s).ok());
+
+    if result.is_error.unwrap_or(false) {
+        let mut error = json!({ "message": text });
+        if let Some(structured) = structured {
+            error["data"] = structured;
+        }
+        return (TOOL_ERROR_STATUS, json!({ "error": error }));
+    }
+    match structured {
+        Some(structured) => (200, structured),
+        None => (200, json!({ "text": text })),
+    }
+}
+
+/// Plain JSON for a JSON-RPC error, with its kind's HTTP status
+fn error_to_json(error: &ErrorCode) -> (u16, Value) {
+    let error = McpError::from(error);
+    let status = error.kind.http_status();
+    let (code, message, data) = error.into_parts();
+    let mut body = json!({ "code": code, "message": message });
+    if let Some(data) = data.and_then(|d| serde_json::from_str::<Value>(&d).ok()) {
+        body["data"] = data;
+    }
+    (status, json!({ "error": body }))
+}
+
+/// Send a JSON response
+fn send_json(response_out: ResponseOutparam, status: u16, body: &Value) {
+    let response = match response::ResponseBuilder::new()
+        .status(status)
+        .header("content-type", b"application/json")
+        .build()
+    {
+        Ok(r) => r,
+        Err(e) => send_error!(response_out, e),
+    };
+
+    if let Ok(output_body) = response.body() {
+        if let Ok(stream) = output_body.write() {
+            // blocking-write-and-flush takes at most 4096 bytes per call
+            for chunk in body.to_string().as_bytes().chunks(4096) {
+                if stream.blocking_write_and_flush(chunk).is_err() {
+                    break;
+                }
+            }
+            drop(stream);
+        }
+        let _ = OutgoingBody::finish(output_body, None);
+    }
+
+    ResponseOutparam::set(response_out, Ok(response));
+}
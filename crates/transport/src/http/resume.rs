@@ -0,0 +1,279 @@
+//! Stream resumption (GET with Last-Event-ID)
+//!
+//! SSE events sent on a session's POST streams carry an event id of the form
+//! `<session>:<n>`, numbered per session by server-io. Messages that could
+//! not be written because the client went away are queued by server-io in
+//! the session outbox (see its `outbox.rs`). A client reconnecting with
+//! `GET`, `Mcp-Session-Id` and `Last-Event-ID` gets the queued events
+//! numbered after the one it names as an SSE stream, and they are removed
+//! from the outbox. A `Last-Event-ID` without a number replays every queued
+//! event. In OAuth mode the request must carry a token for the session's
+//! identity, as for DELETE.
+
+use crate::bindings::wasi::http::types::{IncomingRequest, OutgoingBody, ResponseOutparam};
+use crate::bindings::wasmcp::keyvalue::store as kv_store;
+use crate::config::TransportConfig;
+use crate::error::TransportError;
+use crate::http::{auth, response, session};
+use crate::send_error;
+use crate::session_keys;
+
+/// Whether the request asks to resume a stream
+pub fn is_resume(request: &IncomingRequest) -> bool {
+    !request.headers().get("last-event-id").is_empty()
+}
+
+pub fn handle_resume(
+    request: IncomingRequest,
+    response_out: ResponseOutparam,
+    session_config: &TransportConfig,
+) {
+    let session_id = match authorize(&request, session_config) {
+        Ok(id) => id,
+        Err(e) => send_error!(response_out, e),
+    };
+
+    let last_seen = last_event_number(&request);
+    let events: Vec<QueuedEvent> = match take_outbox(&session_id, session_config) {
+        Ok(events) => events
+            .into_iter()
+            .filter(|e| follows(&e.event, last_seen))
+            .collect(),
+        Err(e) => send_error!(response_out, e),
+    };
+
+    let response = match response::ResponseBuilder::new()
+        .status(200)
+        .header("content-type", b"text/event-stream")
+        .header("cache-control", b"no-cache")
+        .build()
+    {
+        Ok(r) => r,
+        Err(e) => send_error!(response_out, e),
+    };
+
+    if let Ok(output_body) = response.body() {
+        if let Ok(stream) = output_body.write() {
+            // blocking-write-and-flush takes at most 4096 bytes per call
+            let bytes = concat(&events);
+            if bytes
+                .as_bytes()
+                .chunks(4096)
+                .any(|chunk| stream.blocking_write_and_flush(chunk).is_err())
+            {
+                // The client went away again; keep the events for its next
+                // attempt. Part of them may have arrived, and is sent again.
+                if let Err(e) = restore_outbox(&session_id, events, session_config) {
+                    eprintln!(
+                        "[transport:resume] Failed to requeue events for session {}: {}",
+                        session_id, e
+                    );
+                }
+            }
+            drop(stream);
+        }
+        let _ = OutgoingBody::finish(output_body, None);
+    }
+
+    ResponseOutparam::set(response_out, Ok(response));
+}
+
+/// Number of the last event the client saw, from `Last-Event-ID`
+fn last_event_number(request: &IncomingRequest) -> Option<u64> {
+    let values = request.headers().get("last-event-id");
+    let value = String::from_utf8(values.first()?.clone()).ok()?;
+    event_number(value.trim())
+}
+
+/// Number of a `<session>:<n>` event id
+fn event_number(id: &str) -> Option<u64> {
+    id.rsplit_once(':')?.1.parse().ok()
+}
+
+/// Whether a queued SSE event comes after event number `last_seen`
+///
+/// Events whose id has no number are always sent.
+fn follows(event: &str, last_seen: Option<u64>) -> bool {
+    let number = event
+        .strip_prefix("id: ")
+        .and_then(|rest| rest.split_once('\n'))
+        .and_then(|(id, _)| event_number(id));
+    match (number, last_seen) {
+        (Some(number), Some(last_seen)) => number > last_seen,
+        _ => true,
+    }
+}
+
+/// The session whose messages a GET request may read
+///
+/// Validates the session (also rejecting terminated sessions) and, in OAuth
+/// mode, that the request carries a token for the session's identity.
+pub fn authorize(
+    request: &IncomingRequest,
+    session_config: &TransportConfig,
+) -> Result<String, TransportError> {
+    let session_id = match session::validate_session_from_request(request, session_config)? {
+        Some(id) => id,
+        None => {
+            return Err(TransportError::session(
+                crate::error::SessionError::Required,
+            ));
+        }
+    };
+
+    crate::common::access_log::record("session", session_id.as_str());
+
+    // Only the identity bound to the session may read its messages
+    let identity = auth::authenticate(request, session_config)?;
+    session::enforce_session_binding(Some(session_id.as_str()), identity.as_ref(), session_config)?;
+    Ok(session_id)
+}
+
+/// A queued SSE event and the outbox slot it was taken from
+pub struct QueuedEvent {
+    pub slot: u64,
+    pub event: String,
+}
+
+/// Concatenated events, ready to write to a stream
+pub fn concat(events: &[QueuedEvent]) -> String {
+    events.iter().map(|e| e.event.as_str()).collect()
+}
+
+/// Remove and return the queued SSE events of a session, oldest first
+///
+/// Reads the slots from the queue's head to the last one handed out, and
+/// deletes exactly the slots it read. A slot handed out but not yet written
+/// (an append in progress on another instance) stops the head from moving
+/// past it, so its event is found by the next read.
+pub fn take_outbox(
+    session_id: &str,
+    session_config: &TransportConfig,
+) -> Result<Vec<QueuedEvent>, TransportError> {
+    let storage_error = |e: String| {
+        TransportError::session(crate::error::SessionError::StorageFailed(format!(
+            "Failed to read outbox: {}",
+            e
+        )))
+    };
+
+    let bucket = kv_store::open(session_config.get_session_bucket())
+        .map_err(|e| storage_error(format!("{:?}", e)))?;
+    let seq = bucket
+        .get_s64(&outbox_key(session_id, session_keys::OUTBOX_SEQ))
+        .map_err(|e| storage_error(format!("{:?}", e)))?
+        .unwrap_or(0)
+        .max(0) as u64;
+    let head_key = outbox_key(session_id, session_keys::OUTBOX_HEAD);
+    let head = bucket
+        .get_u64(&head_key)
+        .map_err(|e| storage_error(format!("{:?}", e)))?
+        .unwrap_or(1);
+    if head > seq {
+        return Ok(Vec::new());
+    }
+
+    let keys: Vec<String> = (head..=seq)
+        .map(|slot| outbox_key(session_id, &slot.to_string()))
+        .collect();
+    let values = bucket
+        .get_many(&keys)
+        .map_err(|e| storage_error(format!("{:?}", e)))?;
+
+    let mut events = Vec::new();
+    let mut taken = Vec::new();
+    let mut next_head = None;
+    for (slot, value) in (head..=seq).zip(values) {
+        match value {
+            Some((key, kv_store::TypedValue::AsString(event))) => {
+                taken.push(key);
+                events.push(QueuedEvent { slot, event });
+            }
+            Some((key, _)) => {
+                eprintln!(
+                    "[transport:resume] Dropping unreadable outbox entry {}",
+                    key
+                );
+                taken.push(key);
+            }
+            None => {
+                next_head.get_or_insert(slot);
+            }
+        }
+    }
+    if taken.is_empty() {
+        return Ok(events);
+    }
+
+    bucket
+        .delete_many(&taken)
+        .map_err(|e| storage_error(format!("{:?}", e)))?;
+    let bytes: usize = events.iter().map(|e| e.event.len()).sum();
+    // The byte count only bounds the queue; a stale one is corrected as
+    // events are evicted
+    let _ = bucket.increment(
+        &outbox_key(session_id, session_keys::OUTBOX_BYTES),
+        -(bytes as i64),
+    );
+    let next_head = next_head.unwrap_or(seq + 1);
+    if next_head > head {
+        bucket
+            .set_u64(&head_key, next_head)
+            .map_err(|e| storage_error(format!("{:?}", e)))?;
+    }
+    Ok(events)
+}
+
+/// Put taken events back into their slots, ahead of any queued since
+pub fn restore_outbox(
+    session_id: &str,
+    events: Vec<QueuedEvent>,
+    session_config: &TransportConfig,
+) -> Result<(), TransportError> {
+    let Some(first) = events.first().map(|e| e.slot) else {
+        return Ok(());
+    };
+    let storage_error = |e: String| {
+        TransportError::session(crate::error::SessionError::StorageFailed(format!(
+            "Failed to restore outbox: {}",
+            e
+        )))
+    };
+
+    let bucket = kv_store::open(session_config.get_session_bucket())
+        .map_err(|e| storage_error(format!("{:?}", e)))?;
+    let bytes: usize = events.iter().map(|e| e.event.len()).sum();
+    let pairs: Vec<(String, kv_store::TypedValue)> = events
+        .into_iter()
+        .map(|e| {
+            (
+                outbox_key(session_id, &e.slot.to_string()),
+                kv_store::TypedValue::AsString(e.event),
+            )
+        })
+        .collect();
+    bucket
+        .set_many(&pairs)
+        .map_err(|e| storage_error(format!("{:?}", e)))?;
+    let _ = bucket.increment(
+        &outbox_key(session_id, session_keys::OUTBOX_BYTES),
+        bytes as i64,
+    );
+
+    let head_key = outbox_key(session_id, session_keys::OUTBOX_HEAD);
+    let head = bucket
+        .get_u64(&head_key)
+        .map_err(|e| storage_error(format!("{:?}", e)))?
+        .unwrap_or(1);
+    if first < head {
+        bucket
+            .set_u64(&head_key, first)
+            .map_err(|e| storage_error(format!("{:?}", e)))?;
+    }
+    Ok(())
+}
+
+/// Storage key of one part of a session's outbox
+fn outbox_key(session_id: &str, part: &str) -> String {
+    format!("{}:{}:{}", session_id, session_keys::OUTBOX, part)
+}
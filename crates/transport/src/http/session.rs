@@ -13,7 +13,7 @@ use crate::bindings::wasmcp::mcp_v20251125::session_manager::{
 };
 use crate::config::TransportConfig;
 use crate::error::TransportError;
-use crate::http::validation;
+use crate::http::{metrics, validation};
 use crate::session_keys;
 
 /// Validate and retrieve session ID from request
@@ -67,7 +67,9 @@ pub fn check_session_required(session_config: &TransportConfig, session_id: Opti
 pub fn initialize_session(session_config: &TransportConfig) -> Option<String> {
     if session_config.session_enabled {
         let bucket = session_config.get_session_bucket();
-        manager_initialize(bucket).ok()
+        let session_id = manager_initialize(bucket).ok()?;
+        metrics::session_opened();
+        Some(session_id)
     } else {
         None
     }
@@ -88,7 +90,10 @@ pub fn delete_session_by_id(
     let bucket = session_config.get_session_bucket();
 
     match manager_mark_terminated(session_id, bucket, Some("Client requested deletion")) {
-        Ok(_) => Ok(()),
+        Ok(_) => {
+            metrics::session_closed();
+            Ok(())
+        }
         Err(SessionError::NoSuchSession) => Err(TransportError::session_not_found()),
         Err(e) => Err(TransportError::session(
             crate::error::SessionError::StorageFailed(format!(
@@ -158,6 +163,21 @@ pub fn bind_identity_to_session(
             })?;
     }
 
+    // Store authorized party (client) if the token names one
+    if let Some(azp) = helpers::get_claim(&identity.claims, "azp") {
+        session
+            .set(
+                session_keys::JWT_AUTHORIZED_PARTY,
+                &TypedValue::AsBytes(azp.as_bytes().to_vec()),
+            )
+            .map_err(|e| {
+                eprintln!("[transport:session] Failed to store JWT azp: {:?}", e);
+                TransportError::session(crate::error::SessionError::StorageFailed(
+                    "Failed to store JWT azp".to_string(),
+                ))
+            })?;
+    }
+
     // Store scopes as comma-separated list
     let scopes = helpers::get_scopes(&identity.claims);
     let scopes_str = scopes.join(",");
@@ -238,6 +258,201 @@ pub fn bind_identity_to_session(
     Ok(())
 }
 
+/// Persist the client's negotiated capabilities in session storage
+///
+/// Stored as JSON under `client:capabilities` so later requests (each in a fresh
+/// instance) can surface them on MessageContext, and so session-store can gate
+/// server-initiated requests the client never advertised.
+pub fn store_client_capabilities(
+    session_id: &str,
+    capabilities: &crate::bindings::wasmcp::mcp_v20251125::mcp::ClientCapabilities,
+    session_config: &TransportConfig,
+) -> Result<(), TransportError> {
+    use crate::bindings::wasmcp::keyvalue::store::TypedValue;
+    use crate::bindings::wasmcp::mcp_v20251125::sessions::Session;
+
+    let bucket = session_config.get_session_bucket();
+
+    let session = Session::open(session_id, bucket).map_err(|e| {
+        TransportError::session(crate::error::SessionError::StorageFailed(format!(
+            "Failed to open session for capability storage: {:?}",
+            e
+        )))
+    })?;
+
+    let json = crate::common::capability::client_capabilities_to_json(capabilities);
+    session
+        .set(session_keys::CLIENT_CAPABILITIES, &TypedValue::AsJson(json))
+        .map_err(|e| {
+            TransportError::session(crate::error::SessionError::StorageFailed(format!(
+                "Failed to store client capabilities: {:?}",
+                e
+            )))
+        })
+}
+
+/// Load the client's negotiated capabilities from session storage
+///
+/// Returns None if the session cannot be opened or no capabilities were stored
+/// (e.g. sessions created before capability tracking existed).
+pub fn load_client_capabilities(
+    session_id: &str,
+    session_config: &TransportConfig,
+) -> Option<crate::bindings::wasmcp::mcp_v20251125::mcp::ClientCapabilities> {
+    use crate::bindings::wasmcp::keyvalue::store::TypedValue;
+    use crate::bindings::wasmcp::mcp_v20251125::sessions::Session;
+
+    let session = Session::open(session_id, session_config.get_session_bucket()).ok()?;
+
+    match session.get(session_keys::CLIENT_CAPABILITIES) {
+        Ok(Some(TypedValue::AsJson(json))) => {
+            crate::common::capability::client_capabilities_from_json(&json)
+        }
+        _ => None,
+    }
+}
+
+/// Persist the level requested via logging/setLevel in session storage
+pub fn store_log_level(
+    session_id: &str,
+    level: crate::bindings::wasmcp::mcp_v20251125::mcp::LogLevel,
+    session_config: &TransportConfig,
+) -> Result<(), TransportError> {
+    use crate::bindings::wasmcp::keyvalue::store::TypedValue;
+    use crate::bindings::wasmcp::mcp_v20251125::sessions::Session;
+
+    let session = Session::open(session_id, session_config.get_session_bucket()).map_err(|e| {
+        TransportError::session(crate::error::SessionError::StorageFailed(format!(
+            "Failed to open session for log level storage: {:?}",
+            e
+        )))
+    })?;
+
+    let name = crate::common::log_level_to_string(level);
+    session
+        .set(session_keys::CLIENT_LOG_LEVEL, &TypedValue::AsString(name))
+        .map_err(|e| {
+            TransportError::session(crate::error::SessionError::StorageFailed(format!(
+                "Failed to store log level: {:?}",
+                e
+            )))
+        })
+}
+
+/// Load the level requested via logging/setLevel from session storage
+///
+/// Returns None if the client never set a level.
+pub fn load_log_level(
+    session_id: &str,
+    session_config: &TransportConfig,
+) -> Option<crate::bindings::wasmcp::mcp_v20251125::mcp::LogLevel> {
+    use crate::bindings::wasmcp::keyvalue::store::TypedValue;
+    use crate::bindings::wasmcp::mcp_v20251125::sessions::Session;
+
+    let session = Session::open(session_id, session_config.get_session_bucket()).ok()?;
+
+    match session.get(session_keys::CLIENT_LOG_LEVEL) {
+        Ok(Some(TypedValue::AsString(name))) => crate::common::log_level::parse_log_level(&name),
+        _ => None,
+    }
+}
+
+/// Persist the client's clientInfo, locale and the negotiated protocol version
+///
+/// Stored under `client:info` (JSON), `client:locale` and
+/// `client:protocol-version` so later requests can surface the client on
+/// MessageContext and fall back to the negotiated version when the
+/// MCP-Protocol-Version header is missing.
+pub fn store_client_info(
+    session_id: &str,
+    client_info: Option<&crate::bindings::wasmcp::mcp_v20251125::mcp::Implementation>,
+    locale: Option<&str>,
+    protocol_version: &str,
+    session_config: &TransportConfig,
+) -> Result<(), TransportError> {
+    use crate::bindings::wasmcp::keyvalue::store::TypedValue;
+    use crate::bindings::wasmcp::mcp_v20251125::sessions::Session;
+
+    let session = Session::open(session_id, session_config.get_session_bucket()).map_err(|e| {
+        TransportError::session(crate::error::SessionError::StorageFailed(format!(
+            "Failed to open session for client info storage: {:?}",
+            e
+        )))
+    })?;
+
+    let store_failed = |e| {
+        TransportError::session(crate::error::SessionError::StorageFailed(format!(
+            "Failed to store client info: {:?}",
+            e
+        )))
+    };
+
+    if let Some(info) = client_info {
+        let json = crate::common::client_info::client_info_to_json(info);
+        session
+            .set(session_keys::CLIENT_INFO, &TypedValue::AsJson(json))
+            .map_err(store_failed)?;
+    }
+    if let Some(locale) = locale {
+        session
+            .set(
+                session_keys::CLIENT_LOCALE,
+                &TypedValue::AsString(locale.to_string()),
+            )
+            .map_err(store_failed)?;
+    }
+    session
+        .set(
+            session_keys::CLIENT_PROTOCOL_VERSION,
+            &TypedValue::AsString(protocol_version.to_string()),
+        )
+        .map_err(store_failed)
+}
+
+/// Load the client's clientInfo from session storage
+pub fn load_client_info(
+    session_id: &str,
+    session_config: &TransportConfig,
+) -> Option<crate::bindings::wasmcp::mcp_v20251125::mcp::Implementation> {
+    use crate::bindings::wasmcp::keyvalue::store::TypedValue;
+    use crate::bindings::wasmcp::mcp_v20251125::sessions::Session;
+
+    let session = Session::open(session_id, session_config.get_session_bucket()).ok()?;
+
+    match session.get(session_keys::CLIENT_INFO) {
+        Ok(Some(TypedValue::AsJson(json))) => {
+            crate::common::client_info::client_info_from_json(&json)
+        }
+        _ => None,
+    }
+}
+
+/// Load the locale chosen at initialize from session storage
+pub fn load_locale(session_id: &str, session_config: &TransportConfig) -> Option<String> {
+    use crate::bindings::wasmcp::keyvalue::store::TypedValue;
+    use crate::bindings::wasmcp::mcp_v20251125::sessions::Session;
+
+    let session = Session::open(session_id, session_config.get_session_bucket()).ok()?;
+
+    match session.get(session_keys::CLIENT_LOCALE) {
+        Ok(Some(TypedValue::AsString(locale))) => Some(locale),
+        _ => None,
+    }
+}
+
+/// Load the protocol version negotiated at initialize from session storage
+pub fn load_protocol_version(session_id: &str, session_config: &TransportConfig) -> Option<String> {
+    use crate::bindings::wasmcp::keyvalue::store::TypedValue;
+    use crate::bindings::wasmcp::mcp_v20251125::sessions::Session;
+
+    let session = Session::open(session_id, session_config.get_session_bucket()).ok()?;
+
+    match session.get(session_keys::CLIENT_PROTOCOL_VERSION) {
+        Ok(Some(TypedValue::AsString(version))) => Some(version),
+        _ => None,
+    }
+}
+
 /// Extract stored string value from session
 ///
 /// Helper to reduce nested Option handling when retrieving string values from session storage.
@@ -300,6 +515,8 @@ fn get_stored_string(
 /// Compares:
 /// - JWT subject (sub claim) with stored jwt:sub
 /// - JWT issuer (iss claim) with stored jwt:iss
+/// - JWT authorized party (azp claim) with stored jwt:azp, so a token issued
+///   to another client can't use the session either
 ///
 /// Returns:
 /// - Ok(()) if identity matches session
@@ -360,5 +577,36 @@ pub fn validate_session_identity(
         }
     }
 
+    // Retrieve and compare authorized party (if stored)
+    if let Ok(Some(crate::bindings::wasmcp::keyvalue::store::TypedValue::AsBytes(bytes))) =
+        session.get(session_keys::JWT_AUTHORIZED_PARTY)
+        && let Ok(stored_azp) = String::from_utf8(bytes)
+        && helpers::get_claim(&identity.claims, "azp").as_deref() != Some(stored_azp.as_str())
+    {
+        eprintln!("[transport:session] Session identity mismatch detected (azp)");
+        return Err(TransportError::session(
+            crate::error::SessionError::IdentityMismatch(
+                "JWT identity does not match session".to_string(),
+            ),
+        ));
+    }
+
     Ok(())
 }
+
+/// Enforce `WASMCP_SESSION_BINDING` for a request on an existing session
+///
+/// No-op without a session, without an authenticated identity (public mode)
+/// or with binding disabled.
+pub fn enforce_session_binding(
+    session_id: Option<&str>,
+    identity: Option<&crate::bindings::wasmcp::mcp_v20251125::mcp::Identity>,
+    session_config: &TransportConfig,
+) -> Result<(), TransportError> {
+    match (session_id, identity) {
+        (Some(session_id), Some(identity)) if session_config.session_binding => {
+            validate_session_identity(session_id, identity, session_config)
+        }
+        _ => Ok(()),
+    }
+}
@@ -3,7 +3,7 @@
 //! This module contains all header extraction and validation logic for the HTTP transport.
 //! Functions here validate:
 //! - Session IDs (Mcp-Session-Id header)
-//! - JWT tokens (Authorization header)
+//! - JWT tokens (Authorization header, Bearer or DPoP scheme)
 //! - Accept headers (application/json and text/event-stream)
 //! - Protocol versions (MCP-Protocol-Version header)
 //! - Origins (Origin header for DNS rebinding protection)
@@ -38,10 +38,19 @@ pub fn extract_session_id_header(
         .map_err(|_| TransportError::validation("Invalid Mcp-Session-Id header encoding"))
 }
 
-/// Extract JWT bearer token from Authorization header
+/// Scheme an access token was presented with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenScheme {
+    /// `Authorization: Bearer <token>` (RFC 6750)
+    Bearer,
+    /// `Authorization: DPoP <token>`, with a `DPoP` proof header (RFC 9449)
+    Dpop,
+}
+
+/// Extract the access token and its scheme from the Authorization header
 pub fn extract_authorization_header(
     request: &IncomingRequest,
-) -> Result<Option<Vec<u8>>, TransportError> {
+) -> Result<Option<(TokenScheme, Vec<u8>)>, TransportError> {
     let headers = request.headers();
     let auth_values = headers.get("authorization");
 
@@ -53,10 +62,12 @@ pub fn extract_authorization_header(
         .map_err(|_| TransportError::validation("Invalid Authorization header encoding"))?;
 
     if let Some(token) = auth_str.strip_prefix("Bearer ") {
-        Ok(Some(token.as_bytes().to_vec()))
+        Ok(Some((TokenScheme::Bearer, token.as_bytes().to_vec())))
+    } else if let Some(token) = auth_str.strip_prefix("DPoP ") {
+        Ok(Some((TokenScheme::Dpop, token.as_bytes().to_vec())))
     } else {
         Err(TransportError::validation(
-            "Authorization header must use Bearer scheme",
+            "Authorization header must use Bearer or DPoP scheme",
         ))
     }
 }
@@ -85,6 +96,25 @@ pub fn validate_accept_header(request: &IncomingRequest) -> Result<(), Transport
     Ok(())
 }
 
+/// Whether the request carries an MCP-Protocol-Version header
+pub fn has_protocol_version_header(request: &IncomingRequest) -> bool {
+    !request.headers().get("mcp-protocol-version").is_empty()
+}
+
+/// The client's preferred language from the Accept-Language header
+pub fn accept_language(request: &IncomingRequest) -> Option<String> {
+    let values = request.headers().get("accept-language");
+    let header = String::from_utf8(values.first()?.clone()).ok()?;
+    crate::common::locale::locale_from_accept_language(&header)
+}
+
+/// The caller's W3C trace context from the traceparent header, if valid
+pub fn traceparent(request: &IncomingRequest) -> Option<String> {
+    let values = request.headers().get("traceparent");
+    let header = String::from_utf8(values.first()?.clone()).ok()?;
+    crate::common::trace_context::parse_traceparent(&header)
+}
+
 /// Validate MCP-Protocol-Version header
 pub fn validate_protocol_version(request: &IncomingRequest) -> Result<String, TransportError> {
     let headers = request.headers();
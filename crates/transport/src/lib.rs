@@ -17,6 +17,22 @@
 //!   - Set to `true` to use plain JSON mode (single response, notifications suppressed)
 //!   - Only affects HTTP transport; stdio transport is unaffected
 //!
+//! - **`WASMCP_SSE_ON_DEMAND`** - Choose the POST response format per request
+//!   - Default: `false` (every request is answered with an SSE stream)
+//!   - Set to `true` to answer with a single JSON response unless the handler
+//!     sent notifications or server requests, which then arrive as an SSE
+//!     stream followed by the response
+//!   - Messages are held until the handler returns, so progress is not live;
+//!     keep the default for long requests that report progress
+//!   - Ignored when `WASMCP_DISABLE_SSE=true`
+//!
+//! - **`WASMCP_SSE_LISTEN_SECS`** - Serve listening streams on GET
+//!   - Default: `0` (GET with a session id and no `Last-Event-ID` is not served)
+//!   - Set to N to keep such a stream open for N seconds, delivering messages
+//!     queued for the session (`queue-message`) by any instance as they arrive
+//!   - Keep N below the host's request timeout; clients reopen the stream
+//!   - Requires `WASMCP_SESSION_ENABLED=true`
+//!
 //! ## Session Management
 //!
 //! - **`WASMCP_SESSION_ENABLED`** - Enable HTTP session support
@@ -27,6 +43,12 @@
 //!   - Default: `""` (default bucket)
 //!   - Used when `WASMCP_SESSION_ENABLED=true`
 //!
+//! - **`WASMCP_SESSION_BINDING`** - Bind sessions to the identity that created them
+//!   - Default: `true`; only applies with `WASMCP_AUTH_MODE=oauth`
+//!   - The token's `sub`, `iss` and `azp` are stored at initialize; later POST and
+//!     DELETE requests on the session with another identity get 403 Forbidden
+//!   - Set to `false` to let any valid token use a known session ID
+//!
 //! ## Authentication & Authorization
 //!
 //! - **`WASMCP_AUTH_MODE`** - Authentication mode: `public` (default) or `oauth`
@@ -47,8 +69,55 @@
 //!   - Optional: Use instead of JWT_JWKS_URI for static key validation
 //!
 //! - **`JWT_REQUIRED_SCOPES`** - Comma-separated list of required OAuth scopes
-//!   - Optional: Currently not enforced in dynamic registration flows
+//!   - Optional: Valid tokens without them get 403 `insufficient_scope`
 //!   - Used in discovery endpoint metadata (/.well-known/oauth-protected-resource)
+//!     and as the `scope` hint in `WWW-Authenticate` challenges
+//!
+//! - **`JWT_TRUSTED_ISSUERS`** - JSON array of additional identity providers
+//!   - Optional: Alternative or addition to `JWT_ISSUER` + `JWT_JWKS_URI`/`JWT_PUBLIC_KEY`
+//!   - Each entry: `issuer`, one of `jwks_uri` / `public_key`, and optionally `audience`
+//!     (list), `algorithm` and `required_scopes` (defaulting to `JWT_ALGORITHM` /
+//!     `JWT_REQUIRED_SCOPES`)
+//!   - Tokens are verified by the provider matching their `iss` claim
+//!   - Example: `[{"issuer":"https://staging.example.com","jwks_uri":"https://staging.example.com/jwks","audience":["mcp-staging"]}]`
+//!   - Listed as authorization servers in discovery metadata
+//!
+//! - **`JWT_CLAIM_MAPPINGS`** - JSON array copying claims into custom claims under stable names
+//!   - Optional: Entries are `{"from": "org.id", "to": "tenant", "type": "number"}`
+//!   - `from` is a claim name or a dotted path; `type` is `string` (default), `number`,
+//!     `bool` or `list` (comma-separated); missing or mismatched claims are skipped
+//!   - `JWT_TRUSTED_ISSUERS` entries can set their own `claim_mappings`
+//!
+//! - **`JWT_CLOCK_SKEW_SECS`** - Clock skew tolerated when checking `exp`, `nbf` and `iat`
+//!   - Optional: Defaults to 60 seconds
+//!
+//! - **`JWT_VALIDATE_NBF`** / **`JWT_VALIDATE_IAT`** - Reject tokens before their `nbf`,
+//!   or issued in the future
+//!   - Optional: `nbf` is checked by default, `iat` is not; `exp` is always checked
+//!
+//! - **`JWT_MAX_TOKEN_AGE_SECS`** - Reject tokens issued longer ago than this, whatever their `exp`
+//!   - Optional: No limit by default
+//!
+//! - **`WASMCP_DPOP`** - DPoP sender-constrained tokens (RFC 9449): "off", "optional" or "required"
+//!   - Optional: Defaults to "off" (only the Bearer scheme is accepted)
+//!   - "optional": tokens with a `cnf.jkt` claim must be sent as `Authorization: DPoP`
+//!     with a `DPoP` proof signed by that key; unbound tokens still work as Bearer
+//!   - "required": every token must be key-bound; challenges use the DPoP scheme
+//!   - Proofs are checked against the request method and URI, the access token (`ath`)
+//!     and replays (`jti`, recorded in the session bucket under `dpop-jti:`)
+//!
+//! - **`WASMCP_DPOP_MAX_AGE_SECS`** - How far a DPoP proof's `iat` may be from now
+//!   - Optional: Defaults to 300 seconds
+//!
+//! - **`WASMCP_DEV_JWKS`** - JWKS JSON served at `GET /.well-known/jwks.json`
+//!   - Optional, development only: `wasmcp jwt jwks` prints the key set for the
+//!     local test keypair, and `JWT_JWKS_URI` pointed at this server lets the full
+//!     JWKS path be tested offline with tokens from `wasmcp jwt mint`
+//!
+//! Rejected requests carry an RFC 6750 / RFC 9728 `WWW-Authenticate` challenge
+//! with `resource_metadata` and `scope`: 401 without an error code when no token
+//! is sent, 400 `invalid_request` for a malformed header, 401 `invalid_token`
+//! when verification fails, 403 `insufficient_scope` when scopes are missing.
 //!
 //! ## Security & CORS
 //!
@@ -64,6 +133,101 @@
 //!   - NOTE: Most MCP clients (desktop apps) don't send Origin headers
 //!   - Only enable if all your clients are browser-based
 //!
+//! ## Handler HTTP Context
+//!
+//! HTTP requests reach handlers with `MessageContext.http-context`: the method, the path
+//! including its query string, and an allow-list of headers (`host`, `user-agent`, `origin`,
+//! `referer`, `x-forwarded-for`, `x-real-ip`). stdio requests have none.
+//!
+//! A valid W3C `traceparent` header is passed on as `MessageContext.trace-context`,
+//! so tracing middleware and handlers join the caller's trace without each
+//! reading the header.
+//!
+//! - **`WASMCP_FORWARD_HEADERS`** - Extra header names added to the allow-list
+//!   - Optional: Comma-separated, case-insensitive
+//!   - `authorization`, `proxy-authorization`, `cookie` and `dpop` are never forwarded
+//!   - wasi:http doesn't expose the peer address or TLS details; behind a proxy, use
+//!     `x-forwarded-for` / `x-real-ip`
+//!
+//! ## Tool Concurrency Limits
+//!
+//! - **`WASMCP_MAX_CONCURRENT_TOOLS`** - Maximum in-flight `tools/call` requests server-wide
+//!   - Optional: Unset or `0` means unlimited
+//!   - Slots are stored in the session bucket (`WASMCP_SESSION_BUCKET`)
+//!
+//! - **`WASMCP_MAX_CONCURRENT_TOOLS_PER_SESSION`** - Maximum in-flight `tools/call` per session
+//!   - Optional: Unset or `0` means unlimited
+//!   - Only enforced when sessions are enabled
+//!
+//! - **`WASMCP_TOOL_QUEUE_TIMEOUT_MS`** - How long a saturated call waits for a free slot
+//!   - Default: `0` (reject immediately with HTTP 429)
+//!   - Rejections carry `Retry-After` (the timeout rounded up to seconds, at
+//!     least 1) and the same hint as `{"retryable":true,"retryAfter":N}` error data
+//!
+//! - **`WASMCP_TOOL_LEASE_SECS`** - How long a call holds its slot at most
+//!   - Default: `600`
+//!   - A call that never releases its slot (trapped or killed instance) frees
+//!     it after this long; set it above the longest tool call
+//!
+//! - **`WASMCP_TOOL_GROUPS`** - Serialize calls to tools that share state
+//!   - Format: `tool=group` pairs, e.g. `add_todo=todos,remove_todo=todos`
+//!   - At most one call per group runs at a time, per session (server-wide
+//!     without sessions); tools in other groups or none are not held up
+//!   - A call finding its group busy waits like a saturated limit, so set
+//!     `WASMCP_TOOL_QUEUE_TIMEOUT_MS` to queue calls rather than reject them
+//!   - Slots are stored in the session bucket (`WASMCP_SESSION_BUCKET`)
+//!
+//! ## Request Deadline
+//!
+//! - **`WASMCP_REQUEST_TIMEOUT_MS`** - Response budget for each request
+//!   - Optional: Unset or `0` means no deadline
+//!   - Exposed to handlers as `MessageContext.deadline` (a monotonic-clock instant)
+//!   - Advisory: handlers are not interrupted, but overruns are logged to stderr
+//!
+//! ## Response Size Budget
+//!
+//! - **`WASMCP_MAX_RESPONSE_BYTES`** - Largest `tools/call` or `resources/read` payload sent
+//!   - Optional: Unset or `0` means unlimited
+//!   - Counts text, base64-encoded blobs and structured content; streamed content is not counted
+//!
+//! - **`WASMCP_RESPONSE_OVERFLOW`** - What to do with a result over the budget
+//!   - `truncate` (default): cut content to fit and append a truncation marker
+//!   - `link`: replace oversized embedded resources with resource links, then truncate
+//!   - `error`: fail the request with an internal error carrying `size` and `maxBytes`
+//!
+//! ## Protocol Version
+//!
+//! - **`WASMCP_MAX_PROTOCOL_VERSION`** - Newest protocol version the server negotiates
+//!   - Optional: Unset means the latest supported version (`2025-11-25`)
+//!   - Clients asking for a newer version get this one, e.g. `2025-03-26` for
+//!     clients with known `2025-06-18` bugs
+//!   - Applies to initialize and every later request on both transports
+//!
+//! ## Server Metadata
+//!
+//! - **`WASMCP_SERVER_NAME`** - `serverInfo.name` in the initialize response
+//!   - Default: `wasmcp-server`
+//!
+//! - **`WASMCP_SERVER_TITLE`** - `serverInfo.title` in the initialize response
+//!   - Default: `wasmcp Universal Transport Server`
+//!
+//! - **`WASMCP_SERVER_VERSION`** - `serverInfo.version` in the initialize response
+//!   - Default: transport component version
+//!
+//! - **`WASMCP_SERVER_INSTRUCTIONS`** - Usage instructions advertised to clients
+//!   - Optional: Omitted from the initialize response if not set
+//!
+//! Handlers can override these at runtime by answering the `initialize` request
+//! the transport forwards down the chain; see `common::server_info`.
+//!
+//! ## Capability Discovery
+//!
+//! - **`WASMCP_CAPABILITY_CACHE_TTL`** - Seconds discovered capabilities are shared
+//!   - Default: `0` (each instance probes the handler chain at initialize)
+//!   - Results are stored in the session bucket (`WASMCP_SESSION_BUCKET`) per
+//!     protocol version, so later HTTP initializes skip the list probes
+//!   - Handlers whose initialize result sets `lists` are never probed
+//!
 //! ## Discovery & Metadata
 //!
 //! - **`WASMCP_SERVER_URI`** - Server's canonical URI (resource identifier)
@@ -78,6 +242,44 @@
 //! - **`WASMCP_DISCOVERY_CACHE_TTL`** - Cache TTL for discovery endpoints in seconds
 //!   - Default: `3600` (1 hour)
 //!   - Controls Cache-Control headers on /.well-known/* endpoints
+//!
+//! ## Metrics
+//!
+//! - **`WASMCP_METRICS_ENABLED`** - Serve Prometheus metrics at `GET /metrics`
+//!   - Default: `false`
+//!   - Request counts by method and status class, request latency histogram,
+//!     active sessions, auth failures by reason and client responses dropped
+//!     for matching no server request
+//!   - Counters are stored in the session bucket (`WASMCP_SESSION_BUCKET`)
+//!   - The endpoint is unauthenticated; restrict it at the proxy if needed
+//!
+//! ## REST Facade
+//!
+//! - **`WASMCP_REST_ENABLED`** - Call tools with `POST /tools/{name}` and plain JSON
+//!   - Default: `false`
+//!   - The body is the tool's arguments; the response is its structured content,
+//!     else `{"text": ...}`, or 204 when the tool returned no output
+//!   - Calls are sessionless but authenticated like MCP requests
+//!
+//! ## Access Log
+//!
+//! - **`WASMCP_ACCESS_LOG`** - Write one JSON line per handled message to stderr
+//!   - Default: `false`; applies to both HTTP and stdio
+//!
+//! - **`WASMCP_ACCESS_LOG_FIELDS`** - Comma-separated fields to include
+//!   - Default: all of `timestamp`, `transport`, `http_method`, `method`, `tool`,
+//!     `session`, `sub`, `status`, `error`, `duration_ms`, `bytes_in`
+//!   - Fields that don't apply to a message are left out
+//!
+//! ## Stdio Resumption
+//!
+//! - **`WASMCP_STDIO_RESUME_TOKEN`** - Checkpoint stdio connection state under this token
+//!   - Optional: Unset means state lives and dies with the process
+//!   - The protocol version, client capabilities, clientInfo, locale and log level
+//!     are stored in the session bucket (`WASMCP_SESSION_BUCKET`) after initialize
+//!     and logging/setLevel, and restored when a process starts with the same token
+//!   - Lets clients restart a server process without initializing again; see
+//!     `checkpoint.rs`
 
 mod bindings {
     wit_bindgen::generate!({
@@ -86,7 +288,9 @@ mod bindings {
     });
 }
 
+mod checkpoint;
 mod common;
+mod concurrency;
 mod config;
 mod error;
 mod http;
@@ -9,6 +9,9 @@ pub const JWT_SUBJECT: &str = "jwt:sub";
 /// JWT issuer claim stored in session
 pub const JWT_ISSUER: &str = "jwt:iss";
 
+/// JWT authorized party (azp claim: the client the token was issued to)
+pub const JWT_AUTHORIZED_PARTY: &str = "jwt:azp";
+
 /// JWT scopes stored as comma-separated list
 pub const JWT_SCOPES: &str = "jwt:scopes";
 
@@ -20,3 +23,40 @@ pub const JWT_EXPIRATION: &str = "jwt:exp";
 
 /// JWT issued-at timestamp (Unix epoch seconds)
 pub const JWT_ISSUED_AT: &str = "jwt:iat";
+
+/// Client capabilities negotiated during initialize (JSON, MCP shape)
+///
+/// Also read by session-store to gate server-initiated requests (e.g. elicitation).
+pub const CLIENT_CAPABILITIES: &str = "client:capabilities";
+
+/// Log level requested via logging/setLevel (MCP level name)
+pub const CLIENT_LOG_LEVEL: &str = "client:log-level";
+
+/// Client implementation (clientInfo) sent with initialize (JSON, MCP shape)
+pub const CLIENT_INFO: &str = "client:info";
+
+/// Locale the client chose with initialize `_meta.locale` (BCP 47 tag)
+pub const CLIENT_LOCALE: &str = "client:locale";
+
+/// Protocol version negotiated during initialize (e.g. "2025-11-25")
+pub const CLIENT_PROTOCOL_VERSION: &str = "client:protocol-version";
+
+/// Undelivered SSE events, one framed event per slot (suffixed with `:<n>`)
+///
+/// Written by server-io when a session's stream closes or a handler queues a
+/// message; drained on resume or by a listening stream.
+pub const OUTBOX: &str = "outbox";
+
+/// Last outbox slot handed out (counter, suffix of `OUTBOX`)
+pub const OUTBOX_SEQ: &str = "seq";
+
+/// First outbox slot that may still hold an event (suffix of `OUTBOX`)
+pub const OUTBOX_HEAD: &str = "head";
+
+/// Bytes held by queued outbox events (counter, suffix of `OUTBOX`)
+pub const OUTBOX_BYTES: &str = "bytes";
+
+/// Server requests awaiting a client response (suffixed with `:<request-id>`)
+///
+/// Written by server-io when a request is sent, taken when the response arrives.
+pub const PENDING_REQUEST: &str = "pending-request";
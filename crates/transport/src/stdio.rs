@@ -3,6 +3,7 @@
 //! Handles stdio-specific protocol concerns:
 //! - Line-delimited JSON-RPC over stdin/stdout
 //! - Process lifecycle via wasi:cli/run
+//! - Optional connection checkpoints across restarts (see `checkpoint.rs`)
 //!
 //! Delegates I/O to server-io via common wrappers
 
@@ -12,7 +13,10 @@ use crate::bindings::wasi::cli::stdout::get_stdout;
 use crate::bindings::wasmcp::mcp_v20251125::mcp::{
     ClientRequest, ErrorCode, ProtocolVersion, ServerResult,
 };
+use crate::bindings::wasmcp::mcp_v20251125::server_io;
+use crate::checkpoint;
 use crate::common;
+use crate::config::TransportConfig;
 
 pub struct StdioTransportGuest;
 
@@ -22,11 +26,22 @@ impl Guest for StdioTransportGuest {
         let stdin = get_stdin();
         let stdout = get_stdout();
 
-        // Track protocol version from initialize (default to latest)
-        let mut protocol_version = ProtocolVersion::V20251125;
+        let config = TransportConfig::from_env();
+        let request_timeout_ms = config.request_timeout_ms;
+        common::response_budget::configure(config.max_response_bytes, config.response_overflow);
+
+        // Track protocol version from initialize (default to latest allowed),
+        // or from the checkpoint of a previous process
+        let mut protocol_version = checkpoint::restore(&config).unwrap_or_else(|| {
+            common::clamp_protocol_version(ProtocolVersion::V20251125, config.max_protocol_version)
+        });
+        server_io::set_protocol_version(protocol_version);
 
         // Event loop: read messages from stdin, process, write to stdout
         loop {
+            // The previous message is done once we're back waiting for input
+            common::access_log::finish();
+
             // Parse incoming message (blocks waiting for input)
             let message = match common::parse_mcp_message(
                 &stdin,
@@ -44,12 +59,19 @@ impl Guest for StdioTransportGuest {
                 }
             };
 
+            // Each message gets its own response budget and access log entry
+            common::deadline::start_request(request_timeout_ms);
+            common::access_log::begin(&config, "stdio");
+            common::access_log::record_message(&message);
+
             // Handle message based on type
             match message {
                 common::McpMessage::Request(request_id, client_request) => {
                     // Handle initialize specially (capabilities discovery)
                     if let ClientRequest::Initialize(init_req) = &client_request {
-                        protocol_version = handle_initialize(&stdout, request_id, init_req)?;
+                        protocol_version =
+                            handle_initialize(&stdout, request_id, init_req, &config)?;
+                        checkpoint::save(&config, protocol_version);
                         continue;
                     }
 
@@ -72,11 +94,11 @@ impl Guest for StdioTransportGuest {
 
                     // Handle logging/setLevel directly
                     if let ClientRequest::LoggingSetLevel(level) = &client_request {
-                        let level_str = common::protocol::log_level_to_string(*level);
-                        if let Err(e) = common::handle_set_log_level(level_str) {
+                        if let Err(e) = common::handle_set_log_level(*level) {
                             write_error(&stdout, Some(request_id.clone()), e);
                             continue;
                         }
+                        checkpoint::save(&config, protocol_version);
                         if let Err(e) = common::write_mcp_result(
                             &stdout,
                             request_id,
@@ -96,7 +118,7 @@ impl Guest for StdioTransportGuest {
                         Some("0"),     // Session ID "0" indicates stdio mode
                         None,          // No identity in stdio mode
                         String::new(), // No session bucket in stdio
-                        &stdout,
+                        Some(&stdout),
                         &common::stdio_frame(),
                         None, // No HTTP context in stdio mode
                     ) {
@@ -134,8 +156,10 @@ impl Guest for StdioTransportGuest {
                     use crate::bindings::wasmcp::mcp_v20251125::mcp::ClientMessage;
                     use crate::bindings::wasmcp::mcp_v20251125::server_handler::handle;
 
+                    // stdout stays open, so middleware can follow up with
+                    // further server requests (e.g. the next sampling step)
                     let ctx = common::create_message_context(
-                        None,
+                        Some(&stdout),
                         protocol_version,
                         Some("0"), // Session ID "0" indicates stdio mode
                         None,
@@ -153,8 +177,10 @@ impl Guest for StdioTransportGuest {
                     use crate::bindings::wasmcp::mcp_v20251125::mcp::ClientMessage;
                     use crate::bindings::wasmcp::mcp_v20251125::server_handler::handle;
 
+                    // stdout stays open, so middleware can follow up with
+                    // further server requests (e.g. the next sampling step)
                     let ctx = common::create_message_context(
-                        None,
+                        Some(&stdout),
                         protocol_version,
                         Some("0"), // Session ID "0" indicates stdio mode
                         None,
@@ -177,30 +203,26 @@ fn handle_initialize(
     stdout: &crate::bindings::wasi::io::streams::OutputStream,
     request_id: crate::bindings::wasmcp::mcp_v20251125::mcp::RequestId,
     init_req: &crate::bindings::wasmcp::mcp_v20251125::mcp::InitializeRequest,
+    config: &TransportConfig,
 ) -> Result<ProtocolVersion, ()> {
-    // Use client's requested protocol version (for now, we only support one version)
-    let protocol_version = init_req.protocol_version;
-
-    // Discover capabilities from downstream
-    let capabilities =
-        common::discover_capabilities_for_init(protocol_version, &common::stdio_frame());
-
-    // Create initialize result
-    let result = ServerResult::Initialize(
-        crate::bindings::wasmcp::mcp_v20251125::mcp::InitializeResult {
-            meta: None,
-            server_info: crate::bindings::wasmcp::mcp_v20251125::mcp::Implementation {
-                name: "wasmcp-server".to_string(),
-                title: None,
-                version: env!("CARGO_PKG_VERSION").to_string(),
-                description: None,
-                icons: None,
-            },
-            capabilities,
-            protocol_version,
-            options: None,
-        },
-    );
+    // Use client's requested protocol version, up to the configured maximum
+    let protocol_version =
+        common::clamp_protocol_version(init_req.protocol_version, config.max_protocol_version);
+    server_io::set_protocol_version(protocol_version);
+
+    // Remember client capabilities for the lifetime of the stdio connection
+    common::capability::set_negotiated_client_capabilities(Some(init_req.capabilities.clone()));
+    common::client_info::set_client_info(Some(init_req.client_info.clone()));
+    common::locale::set_client_locale(common::locale::locale_from_meta(init_req.meta.as_deref()));
+
+    // Create initialize result (configured server metadata, or a handler override)
+    // with capabilities discovered from downstream
+    let result = ServerResult::Initialize(common::build_initialize_result(
+        Some(init_req),
+        protocol_version,
+        &common::stdio_frame(),
+        config,
+    ));
 
     if let Err(e) = common::write_mcp_result(stdout, request_id, result, &common::stdio_frame()) {
         eprintln!("[ERROR] Failed to write initialize result: {:?}", e);
@@ -217,8 +239,8 @@ fn write_error(
     error: ErrorCode,
 ) {
     use crate::bindings::wasmcp::mcp_v20251125::mcp::ServerMessage;
-    use crate::bindings::wasmcp::mcp_v20251125::server_io;
 
+    common::access_log::record_error(&error);
     let message = ServerMessage::Error((id, error));
     if let Err(e) = server_io::send_message(stdout, message, &common::stdio_frame()) {
         eprintln!("[ERROR] Failed to write error: {:?}", e);
@@ -0,0 +1,99 @@
+//! Client capability checks
+//!
+//! Handlers that issue server requests (sampling, elicitation, roots) must
+//! only do so when the client advertised the matching capability during
+//! initialize. The capabilities reach handlers as the generated
+//! `client-capabilities` record on `MessageContext`, and session-backed
+//! components see the JSON the transport stored with the session. This
+//! crate reads both the same way:
+//! - `Capability`: the client capabilities a server request depends on
+//! - `ClientSupports`: `client_supports` and `require_client_support`
+//! - `impl_client_supports!`: implements `ClientSupports` for a crate's
+//!   generated `MessageContext`
+//!
+//! Capabilities that were never negotiated (older transports, sessions
+//! created before they were tracked) count as supported, so existing
+//! deployments keep working.
+//!
+//! ```ignore
+//! use bindings::wasmcp::mcp_v20251125::mcp::ClientLists;
+//! use bindings::wasmcp::mcp_v20251125::server_handler::MessageContext;
+//! use wasmcp_capabilities::{Capability, ClientSupports};
+//! wasmcp_capabilities::impl_client_supports!(MessageContext<'_>, ClientLists);
+//!
+//! if ctx.client_supports(Capability::Sampling) { ... }
+//! ```
+
+mod macros;
+
+use serde_json::Value;
+use std::fmt;
+
+/// A client capability server requests depend on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Capability {
+    /// `sampling/createMessage`
+    Sampling,
+    /// `elicitation/create`
+    Elicitation,
+    /// `roots/list`
+    Roots,
+}
+
+impl Capability {
+    /// Key of the capability in the client's `capabilities` object
+    pub fn name(self) -> &'static str {
+        match self {
+            Capability::Sampling => "sampling",
+            Capability::Elicitation => "elicitation",
+            Capability::Roots => "roots",
+        }
+    }
+}
+
+impl fmt::Display for Capability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The client did not advertise a capability an operation requires
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Unsupported(pub Capability);
+
+impl fmt::Display for Unsupported {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Client did not advertise the '{}' capability",
+            self.0.name()
+        )
+    }
+}
+
+impl std::error::Error for Unsupported {}
+
+/// Checks against the capabilities a client advertised
+pub trait ClientSupports {
+    /// Whether the client advertised `capability` (or it is unknown)
+    fn client_supports(&self, capability: Capability) -> bool;
+
+    /// Fail with `Unsupported` unless the client supports `capability`
+    fn require_client_support(&self, capability: Capability) -> Result<(), Unsupported> {
+        if self.client_supports(capability) {
+            Ok(())
+        } else {
+            Err(Unsupported(capability))
+        }
+    }
+}
+
+/// Capabilities in their MCP JSON shape: supported when the key is present
+impl ClientSupports for Value {
+    fn client_supports(&self, capability: Capability) -> bool {
+        self.get(capability.name()).is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests;
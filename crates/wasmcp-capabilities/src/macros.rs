@@ -0,0 +1,26 @@
+//! Implementations for generated bindings
+
+/// Implement `ClientSupports` for a crate's generated `MessageContext`
+///
+/// `$client_lists` is the generated `client-lists` flags type; roots support
+/// is advertised through its `ROOTS` flag, in `lists` or `list-changed`.
+#[macro_export]
+macro_rules! impl_client_supports {
+    ($message_context:ty, $client_lists:ty) => {
+        impl $crate::ClientSupports for $message_context {
+            fn client_supports(&self, capability: $crate::Capability) -> bool {
+                let Some(capabilities) = self.client_capabilities.as_ref() else {
+                    return true;
+                };
+                match capability {
+                    $crate::Capability::Sampling => capabilities.sampling.is_some(),
+                    $crate::Capability::Elicitation => capabilities.elicitation.is_some(),
+                    $crate::Capability::Roots => [capabilities.lists, capabilities.list_changed]
+                        .into_iter()
+                        .flatten()
+                        .any(|lists| lists.contains(<$client_lists>::ROOTS)),
+                }
+            }
+        }
+    };
+}
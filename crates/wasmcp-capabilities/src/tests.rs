@@ -0,0 +1,92 @@
+use super::*;
+use serde_json::json;
+
+// Shape of the wit-bindgen output for `client-lists`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ClientLists(u8);
+
+impl ClientLists {
+    const ROOTS: Self = ClientLists(1);
+
+    fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+// Shape of the wit-bindgen output for `client-capabilities`
+#[derive(Debug, Clone, Default)]
+struct ClientCapabilities {
+    elicitation: Option<String>,
+    list_changed: Option<ClientLists>,
+    sampling: Option<()>,
+    lists: Option<ClientLists>,
+}
+
+struct MessageContext<'a> {
+    client_capabilities: Option<ClientCapabilities>,
+    #[allow(dead_code)]
+    client_stream: Option<&'a ()>,
+}
+
+crate::impl_client_supports!(MessageContext<'_>, ClientLists);
+
+#[test]
+fn test_generated_capabilities() {
+    let ctx = MessageContext {
+        client_capabilities: Some(ClientCapabilities {
+            elicitation: Some("{}".to_string()),
+            list_changed: Some(ClientLists::ROOTS),
+            sampling: None,
+            lists: Some(ClientLists::ROOTS),
+        }),
+        client_stream: None,
+    };
+    assert!(ctx.client_supports(Capability::Elicitation));
+    assert!(ctx.client_supports(Capability::Roots));
+    assert!(!ctx.client_supports(Capability::Sampling));
+    assert_eq!(
+        ctx.require_client_support(Capability::Sampling),
+        Err(Unsupported(Capability::Sampling))
+    );
+
+    let empty = MessageContext {
+        client_capabilities: Some(ClientCapabilities::default()),
+        client_stream: None,
+    };
+    assert!(!empty.client_supports(Capability::Roots));
+}
+
+#[test]
+fn test_roots_without_list_changed() {
+    let ctx = MessageContext {
+        client_capabilities: Some(ClientCapabilities {
+            lists: Some(ClientLists::ROOTS),
+            ..Default::default()
+        }),
+        client_stream: None,
+    };
+    assert!(ctx.client_supports(Capability::Roots));
+    assert!(json!({ "roots": {} }).client_supports(Capability::Roots));
+}
+
+#[test]
+fn test_unknown_capabilities_are_supported() {
+    let ctx = MessageContext {
+        client_capabilities: None,
+        client_stream: None,
+    };
+    assert!(ctx.client_supports(Capability::Sampling));
+    assert!(ctx.require_client_support(Capability::Elicitation).is_ok());
+}
+
+#[test]
+fn test_json_capabilities() {
+    let capabilities = json!({ "sampling": {}, "roots": { "listChanged": true } });
+    assert!(capabilities.client_supports(Capability::Sampling));
+    assert!(capabilities.client_supports(Capability::Roots));
+    assert!(!capabilities.client_supports(Capability::Elicitation));
+    assert_eq!(
+        Unsupported(Capability::Elicitation).to_string(),
+        "Client did not advertise the 'elicitation' capability"
+    );
+}
@@ -0,0 +1,145 @@
+//! Typed access to JWT claims
+//!
+//! Handlers get the caller's claims as the generated `jwt-claims` record,
+//! where everything beyond the registered claims is a list of strings: plain
+//! strings as-is, anything else as JSON. Reading roles or groups meant
+//! splitting strings by hand, and every handler split differently. This
+//! crate defines the reading once:
+//! - `Claims`: the claims independent of any bindings, with typed getters
+//! - `parse_list`: the string-list rules used for roles and `list`
+//! - `impl_claims!`: generates the conversion from a crate's generated
+//!   `JwtClaims`
+//!
+//! ```ignore
+//! use bindings::wasmcp::auth::types::JwtClaims;
+//! wasmcp_claims::impl_claims!(JwtClaims);
+//!
+//! let claims = wasmcp_claims::Claims::from(&identity.claims);
+//! if claims.has_scope("mcp:write") && claims.has_role("editor") { ... }
+//! ```
+
+mod macros;
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+/// Claims holding the caller's roles, in order of precedence
+const ROLE_CLAIMS: &[&str] = &["roles", "role"];
+
+/// JWT claims independent of any generated bindings
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Claims {
+    pub subject: String,
+    pub issuer: Option<String>,
+    pub audience: Vec<String>,
+    /// Unix epoch seconds
+    pub expiration: Option<u64>,
+    /// Unix epoch seconds
+    pub issued_at: Option<u64>,
+    /// Unix epoch seconds
+    pub not_before: Option<u64>,
+    pub jwt_id: Option<String>,
+    pub scopes: Vec<String>,
+    /// Other claims: strings as-is, anything else as JSON
+    pub custom_claims: Vec<(String, String)>,
+}
+
+impl Claims {
+    /// OAuth scopes (`scope` or `scp`)
+    pub fn scopes(&self) -> &[String] {
+        &self.scopes
+    }
+
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scopes.iter().any(|s| s == scope)
+    }
+
+    /// Roles from the `roles` claim, else `role`
+    ///
+    /// Either may be a JSON array or a space or comma separated string.
+    pub fn roles(&self) -> Vec<String> {
+        ROLE_CLAIMS
+            .iter()
+            .find_map(|name| self.claim(name))
+            .map(parse_list)
+            .unwrap_or_default()
+    }
+
+    pub fn has_role(&self, role: &str) -> bool {
+        self.roles().iter().any(|r| r == role)
+    }
+
+    /// Audiences the token was issued for
+    pub fn audience(&self) -> &[String] {
+        &self.audience
+    }
+
+    pub fn has_audience(&self, audience: &str) -> bool {
+        self.audience.iter().any(|a| a == audience)
+    }
+
+    /// Expiration time (Unix epoch seconds)
+    pub fn expiry(&self) -> Option<u64> {
+        self.expiration
+    }
+
+    /// Whether the token has expired at `now` (Unix epoch seconds)
+    ///
+    /// Tokens without an expiration never expire.
+    pub fn is_expired_at(&self, now: u64) -> bool {
+        self.expiration.is_some_and(|exp| now >= exp)
+    }
+
+    /// The raw value of a custom claim
+    pub fn claim(&self, name: &str) -> Option<&str> {
+        self.custom_claims
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// A custom claim read as a list (see `parse_list`)
+    ///
+    /// Missing claims are an empty list.
+    pub fn list(&self, name: &str) -> Vec<String> {
+        self.claim(name).map(parse_list).unwrap_or_default()
+    }
+
+    /// A custom claim deserialized into `T`
+    ///
+    /// Values stored as JSON (numbers, booleans, objects, arrays) are parsed;
+    /// plain strings are deserialized as JSON strings. None when the claim is
+    /// missing or doesn't fit `T`.
+    pub fn custom<T: DeserializeOwned>(&self, name: &str) -> Option<T> {
+        let value = self.claim(name)?;
+        serde_json::from_str(value)
+            .or_else(|_| serde_json::from_value(Value::String(value.to_string())))
+            .ok()
+    }
+}
+
+/// Read a list-valued claim
+///
+/// A JSON array gives its items (strings as-is, anything else as JSON).
+/// Any other value is split on commas and whitespace, so `"a b"`, `"a,b"`
+/// and `"a, b"` are all `["a", "b"]`. Empty items are dropped.
+pub fn parse_list(value: &str) -> Vec<String> {
+    if let Ok(Value::Array(items)) = serde_json::from_str(value) {
+        return items
+            .into_iter()
+            .map(|item| match item {
+                Value::String(s) => s,
+                other => other.to_string(),
+            })
+            .filter(|item| !item.is_empty())
+            .collect();
+    }
+    value
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|item| !item.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests;
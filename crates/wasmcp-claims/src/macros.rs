@@ -0,0 +1,44 @@
+//! Conversions for generated bindings
+
+/// Implement `From<&JwtClaims>` and `From<JwtClaims>` for `Claims` from a
+/// crate's generated `jwt-claims` record
+#[macro_export]
+macro_rules! impl_claims {
+    ($jwt_claims:ty) => {
+        const _: () = {
+            type Generated = $jwt_claims;
+
+            impl ::core::convert::From<&Generated> for $crate::Claims {
+                fn from(claims: &Generated) -> Self {
+                    $crate::Claims {
+                        subject: claims.subject.clone(),
+                        issuer: claims.issuer.clone(),
+                        audience: claims.audience.clone(),
+                        expiration: claims.expiration,
+                        issued_at: claims.issued_at,
+                        not_before: claims.not_before,
+                        jwt_id: claims.jwt_id.clone(),
+                        scopes: claims.scopes.clone(),
+                        custom_claims: claims.custom_claims.clone(),
+                    }
+                }
+            }
+
+            impl ::core::convert::From<Generated> for $crate::Claims {
+                fn from(claims: Generated) -> Self {
+                    $crate::Claims {
+                        subject: claims.subject,
+                        issuer: claims.issuer,
+                        audience: claims.audience,
+                        expiration: claims.expiration,
+                        issued_at: claims.issued_at,
+                        not_before: claims.not_before,
+                        jwt_id: claims.jwt_id,
+                        scopes: claims.scopes,
+                        custom_claims: claims.custom_claims,
+                    }
+                }
+            }
+        };
+    };
+}
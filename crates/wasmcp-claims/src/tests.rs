@@ -0,0 +1,136 @@
+use super::*;
+
+// Shape of the wit-bindgen output for `jwt-claims`
+#[derive(Debug, Clone)]
+struct JwtClaims {
+    subject: String,
+    issuer: Option<String>,
+    audience: Vec<String>,
+    expiration: Option<u64>,
+    issued_at: Option<u64>,
+    not_before: Option<u64>,
+    jwt_id: Option<String>,
+    scopes: Vec<String>,
+    #[allow(dead_code)]
+    confirmation: Option<()>,
+    custom_claims: Vec<(String, String)>,
+}
+
+crate::impl_claims!(JwtClaims);
+
+fn claims(custom: &[(&str, &str)]) -> Claims {
+    Claims::from(JwtClaims {
+        subject: "user-1".to_string(),
+        issuer: Some("https://issuer.example.com".to_string()),
+        audience: vec!["https://mcp.example.com".to_string()],
+        expiration: Some(2_000),
+        issued_at: Some(1_000),
+        not_before: None,
+        jwt_id: None,
+        scopes: vec!["mcp:read".to_string(), "mcp:write".to_string()],
+        confirmation: None,
+        custom_claims: custom
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect(),
+    })
+}
+
+#[test]
+fn test_conversion() {
+    let generated = JwtClaims {
+        subject: "user-1".to_string(),
+        issuer: None,
+        audience: vec![],
+        expiration: None,
+        issued_at: None,
+        not_before: Some(5),
+        jwt_id: Some("jti-1".to_string()),
+        scopes: vec![],
+        confirmation: None,
+        custom_claims: vec![("tenant".to_string(), "acme".to_string())],
+    };
+    let claims = Claims::from(&generated);
+    assert_eq!(claims.subject, "user-1");
+    assert_eq!(claims.not_before, Some(5));
+    assert_eq!(claims.jwt_id.as_deref(), Some("jti-1"));
+    assert_eq!(claims, Claims::from(generated));
+}
+
+#[test]
+fn test_standard_getters() {
+    let claims = claims(&[]);
+    assert_eq!(claims.scopes(), ["mcp:read", "mcp:write"]);
+    assert!(claims.has_scope("mcp:write"));
+    assert!(!claims.has_scope("mcp"));
+    assert_eq!(claims.audience(), ["https://mcp.example.com"]);
+    assert!(claims.has_audience("https://mcp.example.com"));
+    assert_eq!(claims.expiry(), Some(2_000));
+    assert!(!claims.is_expired_at(1_999));
+    assert!(claims.is_expired_at(2_000));
+    assert!(!Claims::default().is_expired_at(u64::MAX));
+}
+
+#[test]
+fn test_parse_list() {
+    assert_eq!(parse_list("admin editor"), ["admin", "editor"]);
+    assert_eq!(parse_list("admin,editor"), ["admin", "editor"]);
+    assert_eq!(parse_list(" admin,  editor ,"), ["admin", "editor"]);
+    assert_eq!(
+        parse_list(r#"["admin", "team lead", 7]"#),
+        ["admin", "team lead", "7"]
+    );
+    assert_eq!(parse_list("admin"), ["admin"]);
+    assert!(parse_list("").is_empty());
+    assert!(parse_list("[]").is_empty());
+}
+
+#[test]
+fn test_roles() {
+    assert_eq!(
+        claims(&[("roles", r#"["admin","ops"]"#)]).roles(),
+        ["admin", "ops"]
+    );
+    assert_eq!(claims(&[("role", "admin")]).roles(), ["admin"]);
+    // `roles` wins over `role`
+    let both = claims(&[("role", "viewer"), ("roles", "admin ops")]);
+    assert_eq!(both.roles(), ["admin", "ops"]);
+    assert!(both.has_role("ops"));
+    assert!(!both.has_role("viewer"));
+    assert!(claims(&[]).roles().is_empty());
+}
+
+#[test]
+fn test_custom_claims() {
+    let claims = claims(&[
+        ("tenant", "acme"),
+        ("seats", "25"),
+        ("beta", "true"),
+        ("org", r#"{"id": 7, "name": "Acme"}"#),
+        ("allowed_tools", "add_item, list_items"),
+    ]);
+
+    assert_eq!(claims.claim("tenant"), Some("acme"));
+    assert_eq!(claims.claim("missing"), None);
+    assert_eq!(claims.custom::<String>("tenant").as_deref(), Some("acme"));
+    assert_eq!(claims.custom::<u32>("seats"), Some(25));
+    assert_eq!(claims.custom::<bool>("beta"), Some(true));
+    assert_eq!(claims.custom::<u32>("tenant"), None);
+    assert_eq!(claims.custom::<u32>("missing"), None);
+
+    #[derive(serde::Deserialize, Debug, PartialEq)]
+    struct Org {
+        id: u64,
+        name: String,
+    }
+    assert_eq!(
+        claims.custom::<Org>("org"),
+        Some(Org {
+            id: 7,
+            name: "Acme".to_string()
+        })
+    );
+
+    assert_eq!(claims.list("allowed_tools"), ["add_item", "list_items"]);
+    assert!(claims.list("missing").is_empty());
+}
@@ -0,0 +1,237 @@
+//! Streamable HTTP transport
+//!
+//! Each message is POSTed to the server's MCP endpoint. The reply is a JSON
+//! body or an SSE stream whose `data` events are queued for `receive`. The
+//! session id from the initialize response and the negotiated protocol
+//! version are sent on every later request, and `close` DELETEs the session.
+//!
+//! The HTTP exchange itself is pluggable (`HttpClient`), so the transport
+//! runs over wasi:http inside a component (a `wasmcp_http_client::HttpClient`,
+//! with its timeouts and egress policy) and over any native client in tests.
+
+use crate::ClientError;
+use crate::transport::Transport;
+use serde_json::Value;
+use std::collections::VecDeque;
+
+/// Longest response body quoted in an HTTP error
+const MAX_ERROR_TEXT: usize = 512;
+
+pub struct HttpRequest {
+    pub method: &'static str,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+pub struct HttpResponse {
+    pub status: u16,
+    /// Header names are lowercased
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    /// First value of a header (name must be lowercase)
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// Performs one blocking HTTP exchange
+pub trait HttpClient {
+    fn request(&mut self, request: HttpRequest) -> Result<HttpResponse, String>;
+}
+
+impl<F> HttpClient for F
+where
+    F: FnMut(HttpRequest) -> Result<HttpResponse, String>,
+{
+    fn request(&mut self, request: HttpRequest) -> Result<HttpResponse, String> {
+        self(request)
+    }
+}
+
+impl<T: wasmcp_http_client::Transport> HttpClient for wasmcp_http_client::HttpClient<T> {
+    fn request(&mut self, request: HttpRequest) -> Result<HttpResponse, String> {
+        let outgoing = request.headers.into_iter().fold(
+            wasmcp_http_client::Request::new(request.method, request.url).with_body(request.body),
+            |outgoing, (name, value)| outgoing.with_header(name, value),
+        );
+        let response = self.send(outgoing).map_err(|e| e.to_string())?;
+        Ok(HttpResponse {
+            status: response.status,
+            headers: response.headers,
+            body: response.body,
+        })
+    }
+}
+
+pub struct StreamableHttp<H> {
+    client: H,
+    url: String,
+    headers: Vec<(String, String)>,
+    session_id: Option<String>,
+    protocol_version: Option<String>,
+    pending: VecDeque<Value>,
+}
+
+impl<H: HttpClient> StreamableHttp<H> {
+    pub fn new(url: impl Into<String>, client: H) -> Self {
+        StreamableHttp {
+            client,
+            url: url.into(),
+            headers: Vec::new(),
+            session_id: None,
+            protocol_version: None,
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Add a header sent with every request (e.g. `Authorization`)
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// The session the server assigned at initialization, if any
+    pub fn session_id(&self) -> Option<&str> {
+        self.session_id.as_deref()
+    }
+
+    fn request_headers(&self) -> Vec<(String, String)> {
+        let mut headers = self.headers.clone();
+        if let Some(session_id) = &self.session_id {
+            headers.push(("mcp-session-id".to_string(), session_id.clone()));
+        }
+        if let Some(version) = &self.protocol_version {
+            headers.push(("mcp-protocol-version".to_string(), version.clone()));
+        }
+        headers
+    }
+}
+
+impl<H: HttpClient> Transport for StreamableHttp<H> {
+    fn send(&mut self, message: &Value) -> Result<(), ClientError> {
+        let mut headers = self.request_headers();
+        headers.push(("content-type".to_string(), "application/json".to_string()));
+        headers.push((
+            "accept".to_string(),
+            "application/json, text/event-stream".to_string(),
+        ));
+
+        let response = self
+            .client
+            .request(HttpRequest {
+                method: "POST",
+                url: self.url.clone(),
+                headers,
+                body: message.to_string().into_bytes(),
+            })
+            .map_err(ClientError::Transport)?;
+
+        if let Some(session_id) = response.header("mcp-session-id") {
+            self.session_id = Some(session_id.to_string());
+        }
+
+        let body = String::from_utf8_lossy(&response.body);
+        let is_sse = response
+            .header("content-type")
+            .is_some_and(|ct| ct.starts_with("text/event-stream"));
+        let messages = if is_sse {
+            parse_sse(&body)?
+        } else if body.trim().is_empty() {
+            Vec::new()
+        } else {
+            match serde_json::from_str::<Value>(&body) {
+                Ok(Value::Array(batch)) => batch,
+                Ok(message) => vec![message],
+                // Only successful responses must be JSON-RPC
+                Err(_) if !(200..300).contains(&response.status) => Vec::new(),
+                Err(e) => {
+                    return Err(ClientError::Protocol(format!(
+                        "Invalid JSON from server: {}",
+                        e
+                    )));
+                }
+            }
+        };
+
+        // Error statuses may still carry a JSON-RPC error for the request
+        if !(200..300).contains(&response.status) && messages.is_empty() {
+            if response.status == 404 && self.session_id.is_some() {
+                return Err(ClientError::Transport(
+                    "Session expired (HTTP 404); initialize a new client".to_string(),
+                ));
+            }
+            let excerpt: String = body.chars().take(MAX_ERROR_TEXT).collect();
+            return Err(ClientError::Transport(format!(
+                "HTTP {}: {}",
+                response.status, excerpt
+            )));
+        }
+
+        self.pending.extend(messages);
+        Ok(())
+    }
+
+    fn receive(&mut self) -> Result<Option<Value>, ClientError> {
+        Ok(self.pending.pop_front())
+    }
+
+    fn set_protocol_version(&mut self, version: &str) {
+        self.protocol_version = Some(version.to_string());
+    }
+
+    fn close(&mut self) -> Result<(), ClientError> {
+        if self.session_id.is_none() {
+            return Ok(());
+        }
+        let response = self
+            .client
+            .request(HttpRequest {
+                method: "DELETE",
+                url: self.url.clone(),
+                headers: self.request_headers(),
+                body: Vec::new(),
+            })
+            .map_err(ClientError::Transport)?;
+        self.session_id = None;
+
+        // 405: the server does not let clients end sessions
+        match response.status {
+            200..300 | 404 | 405 => Ok(()),
+            status => Err(ClientError::Transport(format!(
+                "HTTP {} ending the session",
+                status
+            ))),
+        }
+    }
+}
+
+/// JSON-RPC messages from the `data` of each event in an SSE body
+pub fn parse_sse(body: &str) -> Result<Vec<Value>, ClientError> {
+    let mut messages = Vec::new();
+    let mut data: Vec<&str> = Vec::new();
+    // A final event without a trailing blank line still counts
+    for line in body.lines().chain(std::iter::once("")) {
+        if line.is_empty() {
+            let joined = data.join("\n");
+            data.clear();
+            // Events without data (e.g. priming an event id) are skipped
+            if !joined.trim().is_empty() {
+                let message = serde_json::from_str(&joined).map_err(|e| {
+                    ClientError::Protocol(format!("Invalid JSON in SSE event: {}", e))
+                })?;
+                messages.push(message);
+            }
+        } else if let Some(value) = line.strip_prefix("data:") {
+            data.push(value.strip_prefix(' ').unwrap_or(value));
+        }
+        // `event:`, `id:`, `retry:` and `:` comments carry no message
+    }
+    Ok(messages)
+}
@@ -0,0 +1,427 @@
+//! MCP client for calling other MCP servers
+//!
+//! wasmcp components are servers; this crate lets a component (or a native
+//! integration test) be the client of another MCP server, e.g. to build a
+//! proxy or aggregator:
+//! - `Client`: initialize negotiation, typed request methods, notification
+//!   and server-request callbacks
+//! - `StreamableHttp`: the Streamable HTTP transport over any `HttpClient`,
+//!   including `wasmcp-http-client`'s client over wasi:http
+//! - `Stdio`: newline-delimited JSON over any pair of byte streams
+//!
+//! ```ignore
+//! wasmcp_http_client::impl_wasi_transport!(crate::bindings::wasi);
+//!
+//! let transport = StreamableHttp::new(
+//!     "https://search.example.com/mcp",
+//!     wasi_client("aggregator", ctx.deadline),
+//! );
+//! let mut client = Client::new(transport).with_client_info("aggregator", "0.1.0");
+//! client.initialize()?;
+//! let result = client.call_tool("search", json!({ "query": "wasm" }))?;
+//! println!("{}", result.text());
+//! ```
+//!
+//! JSON-RPC errors from the server surface as `wasmcp_errors::McpError`, so
+//! a proxy can pass them on with its own `impl_error_code!` conversions.
+
+mod http;
+mod transport;
+mod types;
+
+#[doc(hidden)]
+pub use http::{HttpClient, HttpRequest, HttpResponse, StreamableHttp, parse_sse};
+pub use transport::{Stdio, Transport};
+pub use types::*;
+pub use wasmcp_errors::McpError;
+
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+
+/// Version requested in `initialize`
+pub const LATEST_PROTOCOL_VERSION: &str = "2025-11-25";
+
+/// Versions the client accepts from the server
+pub const SUPPORTED_PROTOCOL_VERSIONS: &[&str] =
+    &["2025-11-25", "2025-06-18", "2025-03-26", "2024-11-05"];
+
+#[derive(Debug)]
+pub enum ClientError {
+    /// The server answered with a JSON-RPC error
+    Rpc(McpError),
+    /// A message could not be sent or received
+    Transport(String),
+    /// The server's reply did not follow the protocol
+    Protocol(String),
+}
+
+impl std::fmt::Display for ClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientError::Rpc(e) => write!(f, "Server error: {}", e),
+            ClientError::Transport(e) => write!(f, "Transport error: {}", e),
+            ClientError::Protocol(e) => write!(f, "Protocol error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+type NotificationHandler = Box<dyn FnMut(&str, &Value)>;
+type RequestHandler = Box<dyn FnMut(&str, &Value) -> Result<Value, McpError>>;
+
+/// An MCP client session over a transport
+pub struct Client<T: Transport> {
+    transport: T,
+    next_id: i64,
+    client_info: Implementation,
+    capabilities: Value,
+    server: Option<InitializeResult>,
+    on_notification: Option<NotificationHandler>,
+    on_request: Option<RequestHandler>,
+}
+
+impl<T: Transport> Client<T> {
+    pub fn new(transport: T) -> Self {
+        Client {
+            transport,
+            next_id: 1,
+            client_info: Implementation {
+                name: "wasmcp-client".to_string(),
+                version: env!("CARGO_PKG_VERSION").to_string(),
+                title: None,
+            },
+            capabilities: json!({}),
+            server: None,
+            on_notification: None,
+            on_request: None,
+        }
+    }
+
+    /// Name and version sent as `clientInfo`
+    pub fn with_client_info(mut self, name: impl Into<String>, version: impl Into<String>) -> Self {
+        self.client_info = Implementation {
+            name: name.into(),
+            version: version.into(),
+            title: None,
+        };
+        self
+    }
+
+    /// Capabilities declared in `initialize` (default: none)
+    ///
+    /// Declare only what `on_request` handles, e.g. `{"sampling": {}}`.
+    pub fn with_capabilities(mut self, capabilities: Value) -> Self {
+        self.capabilities = capabilities;
+        self
+    }
+
+    /// Called with the method and params of every server notification
+    /// (progress, log messages, list changes, ...)
+    pub fn on_notification(mut self, handler: impl FnMut(&str, &Value) + 'static) -> Self {
+        self.on_notification = Some(Box::new(handler));
+        self
+    }
+
+    /// Answers server requests (sampling, elicitation, roots)
+    ///
+    /// `ping` is always answered by the client; without a handler, other
+    /// requests get method-not-found.
+    pub fn on_request(
+        mut self,
+        handler: impl FnMut(&str, &Value) -> Result<Value, McpError> + 'static,
+    ) -> Self {
+        self.on_request = Some(Box::new(handler));
+        self
+    }
+
+    /// Negotiate the protocol version and capabilities, then send
+    /// `notifications/initialized`
+    pub fn initialize(&mut self) -> Result<&InitializeResult, ClientError> {
+        let params = json!({
+            "protocolVersion": LATEST_PROTOCOL_VERSION,
+            "capabilities": self.capabilities,
+            "clientInfo": self.client_info,
+        });
+        let result: InitializeResult = self.request_as("initialize", Some(params))?;
+        if !SUPPORTED_PROTOCOL_VERSIONS.contains(&result.protocol_version.as_str()) {
+            return Err(ClientError::Protocol(format!(
+                "Server chose unsupported protocol version {}",
+                result.protocol_version
+            )));
+        }
+
+        self.transport
+            .set_protocol_version(&result.protocol_version);
+        self.notify("notifications/initialized", None)?;
+        Ok(self.server.insert(result))
+    }
+
+    /// The server's initialize result, once initialized
+    pub fn server(&self) -> Option<&InitializeResult> {
+        self.server.as_ref()
+    }
+
+    pub fn transport(&self) -> &T {
+        &self.transport
+    }
+
+    pub fn ping(&mut self) -> Result<(), ClientError> {
+        self.request("ping", None).map(|_| ())
+    }
+
+    pub fn list_tools(&mut self, cursor: Option<&str>) -> Result<Page<Tool>, ClientError> {
+        self.list("tools/list", "tools", cursor)
+    }
+
+    /// Every tool, following cursors across pages
+    pub fn list_all_tools(&mut self) -> Result<Vec<Tool>, ClientError> {
+        self.list_all("tools/list", "tools")
+    }
+
+    pub fn call_tool(
+        &mut self,
+        name: &str,
+        arguments: Value,
+    ) -> Result<CallToolResult, ClientError> {
+        self.request_as(
+            "tools/call",
+            Some(json!({ "name": name, "arguments": arguments })),
+        )
+    }
+
+    pub fn list_resources(&mut self, cursor: Option<&str>) -> Result<Page<Resource>, ClientError> {
+        self.list("resources/list", "resources", cursor)
+    }
+
+    pub fn list_all_resources(&mut self) -> Result<Vec<Resource>, ClientError> {
+        self.list_all("resources/list", "resources")
+    }
+
+    pub fn list_resource_templates(
+        &mut self,
+        cursor: Option<&str>,
+    ) -> Result<Page<ResourceTemplate>, ClientError> {
+        self.list("resources/templates/list", "resourceTemplates", cursor)
+    }
+
+    pub fn read_resource(&mut self, uri: &str) -> Result<Vec<ResourceContents>, ClientError> {
+        let result = self.request("resources/read", Some(json!({ "uri": uri })))?;
+        field(result, "contents")
+    }
+
+    pub fn subscribe(&mut self, uri: &str) -> Result<(), ClientError> {
+        self.request("resources/subscribe", Some(json!({ "uri": uri })))
+            .map(|_| ())
+    }
+
+    pub fn unsubscribe(&mut self, uri: &str) -> Result<(), ClientError> {
+        self.request("resources/unsubscribe", Some(json!({ "uri": uri })))
+            .map(|_| ())
+    }
+
+    pub fn list_prompts(&mut self, cursor: Option<&str>) -> Result<Page<Prompt>, ClientError> {
+        self.list("prompts/list", "prompts", cursor)
+    }
+
+    pub fn list_all_prompts(&mut self) -> Result<Vec<Prompt>, ClientError> {
+        self.list_all("prompts/list", "prompts")
+    }
+
+    /// `arguments` is an object of string values
+    pub fn get_prompt(
+        &mut self,
+        name: &str,
+        arguments: Value,
+    ) -> Result<GetPromptResult, ClientError> {
+        self.request_as(
+            "prompts/get",
+            Some(json!({ "name": name, "arguments": arguments })),
+        )
+    }
+
+    /// Completion values for an argument of a prompt or resource template
+    ///
+    /// `reference` is e.g. `{"type": "ref/prompt", "name": "review"}`.
+    pub fn complete(
+        &mut self,
+        reference: Value,
+        argument: &str,
+        value: &str,
+    ) -> Result<Vec<String>, ClientError> {
+        let result = self.request(
+            "completion/complete",
+            Some(json!({
+                "ref": reference,
+                "argument": { "name": argument, "value": value },
+            })),
+        )?;
+        let completion = result.get("completion").cloned().unwrap_or(Value::Null);
+        field(completion, "values")
+    }
+
+    /// Minimum level of `notifications/message` the server sends
+    pub fn set_log_level(&mut self, level: &str) -> Result<(), ClientError> {
+        self.request("logging/setLevel", Some(json!({ "level": level })))
+            .map(|_| ())
+    }
+
+    /// Send a request and wait for its result
+    ///
+    /// Notifications and server requests that arrive first go to the
+    /// callbacks.
+    pub fn request(&mut self, method: &str, params: Option<Value>) -> Result<Value, ClientError> {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let mut message = json!({ "jsonrpc": "2.0", "id": id, "method": method });
+        if let Some(params) = params {
+            message["params"] = params;
+        }
+        self.transport.send(&message)?;
+
+        loop {
+            let Some(incoming) = self.transport.receive()? else {
+                return Err(ClientError::Transport(format!(
+                    "Connection closed before the response to '{}'",
+                    method
+                )));
+            };
+
+            let incoming_method = incoming.get("method").and_then(Value::as_str);
+            match (incoming_method, incoming.get("id")) {
+                (Some(server_method), Some(server_id)) => {
+                    let params = incoming.get("params").cloned().unwrap_or(Value::Null);
+                    let response = self.answer(server_method, &params, server_id.clone());
+                    self.transport.send(&response)?;
+                }
+                (Some(notification), None) => {
+                    if let Some(handler) = &mut self.on_notification {
+                        let params = incoming.get("params").cloned().unwrap_or(Value::Null);
+                        handler(notification, &params);
+                    }
+                }
+                // Responses to other requests (e.g. ones that timed out) are dropped
+                (None, Some(response_id)) if response_id.as_i64() == Some(id) => {
+                    return response_result(incoming);
+                }
+                (None, _) => {}
+            }
+        }
+    }
+
+    /// Send a notification
+    pub fn notify(&mut self, method: &str, params: Option<Value>) -> Result<(), ClientError> {
+        let mut message = json!({ "jsonrpc": "2.0", "method": method });
+        if let Some(params) = params {
+            message["params"] = params;
+        }
+        self.transport.send(&message)
+    }
+
+    /// End the session
+    pub fn close(mut self) -> Result<(), ClientError> {
+        self.transport.close()
+    }
+
+    fn request_as<R: DeserializeOwned>(
+        &mut self,
+        method: &str,
+        params: Option<Value>,
+    ) -> Result<R, ClientError> {
+        let result = self.request(method, params)?;
+        serde_json::from_value(result)
+            .map_err(|e| ClientError::Protocol(format!("Invalid {} result: {}", method, e)))
+    }
+
+    fn list<I: DeserializeOwned>(
+        &mut self,
+        method: &str,
+        key: &str,
+        cursor: Option<&str>,
+    ) -> Result<Page<I>, ClientError> {
+        let params = cursor.map(|c| json!({ "cursor": c }));
+        let result = self.request(method, params)?;
+        let next_cursor = result
+            .get("nextCursor")
+            .and_then(Value::as_str)
+            .map(str::to_string);
+        Ok(Page {
+            items: field(result, key)?,
+            next_cursor,
+        })
+    }
+
+    fn list_all<I: DeserializeOwned>(
+        &mut self,
+        method: &str,
+        key: &str,
+    ) -> Result<Vec<I>, ClientError> {
+        let mut items = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let page = self.list(method, key, cursor.as_deref())?;
+            items.extend(page.items);
+            match page.next_cursor {
+                // A server repeating its cursor would never finish
+                Some(next) if cursor.as_deref() == Some(next.as_str()) => {
+                    return Err(ClientError::Protocol(format!(
+                        "{} returned the same cursor twice",
+                        method
+                    )));
+                }
+                Some(next) => cursor = Some(next),
+                None => return Ok(items),
+            }
+        }
+    }
+
+    /// The response to a server request
+    fn answer(&mut self, method: &str, params: &Value, id: Value) -> Value {
+        let result = match (method, &mut self.on_request) {
+            ("ping", _) => Ok(json!({})),
+            (_, Some(handler)) => handler(method, params),
+            (_, None) => Err(McpError::method_not_found(method)),
+        };
+        match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err(e) => {
+                let (code, message, data) = e.into_parts();
+                let mut error = json!({ "code": code, "message": message });
+                if let Some(data) = data.and_then(|d| serde_json::from_str::<Value>(&d).ok()) {
+                    error["data"] = data;
+                }
+                json!({ "jsonrpc": "2.0", "id": id, "error": error })
+            }
+        }
+    }
+}
+
+/// The result of a response, or its error as `ClientError::Rpc`
+fn response_result(mut response: Value) -> Result<Value, ClientError> {
+    if let Some(error) = response.get("error") {
+        let code = error.get("code").and_then(Value::as_i64).unwrap_or(-32603);
+        let message = error
+            .get("message")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string();
+        let data = error.get("data").map(Value::to_string);
+        return Err(ClientError::Rpc(McpError::from_parts(code, message, data)));
+    }
+    match response.get_mut("result") {
+        Some(result) => Ok(result.take()),
+        None => Err(ClientError::Protocol(
+            "Response has neither result nor error".to_string(),
+        )),
+    }
+}
+
+fn field<I: DeserializeOwned>(mut value: Value, key: &str) -> Result<I, ClientError> {
+    let inner = value.get_mut(key).map(Value::take).unwrap_or(Value::Null);
+    serde_json::from_value(inner)
+        .map_err(|e| ClientError::Protocol(format!("Invalid '{}' in result: {}", key, e)))
+}
+
+#[cfg(test)]
+mod tests;
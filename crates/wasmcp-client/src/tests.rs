@@ -0,0 +1,345 @@
+use super::*;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+/// A server scripted as a function from each sent message to its replies
+struct Scripted<F> {
+    script: F,
+    inbox: VecDeque<Value>,
+    sent: Rc<RefCell<Vec<Value>>>,
+    protocol_version: Option<String>,
+}
+
+impl<F: FnMut(&Value) -> Vec<Value>> Transport for Scripted<F> {
+    fn send(&mut self, message: &Value) -> Result<(), ClientError> {
+        self.sent.borrow_mut().push(message.clone());
+        let replies = (self.script)(message);
+        self.inbox.extend(replies);
+        Ok(())
+    }
+
+    fn receive(&mut self) -> Result<Option<Value>, ClientError> {
+        Ok(self.inbox.pop_front())
+    }
+
+    fn set_protocol_version(&mut self, version: &str) {
+        self.protocol_version = Some(version.to_string());
+    }
+}
+
+fn scripted<F: FnMut(&Value) -> Vec<Value>>(
+    script: F,
+) -> (Client<Scripted<F>>, Rc<RefCell<Vec<Value>>>) {
+    let sent = Rc::new(RefCell::new(Vec::new()));
+    let transport = Scripted {
+        script,
+        inbox: VecDeque::new(),
+        sent: sent.clone(),
+        protocol_version: None,
+    };
+    (Client::new(transport), sent)
+}
+
+fn result(request: &Value, result: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": request["id"], "result": result })
+}
+
+fn initialize_result(version: &str) -> Value {
+    json!({
+        "protocolVersion": version,
+        "capabilities": { "tools": {} },
+        "serverInfo": { "name": "upstream", "version": "1.0.0" },
+    })
+}
+
+#[test]
+fn test_initialize_negotiates_version() {
+    let (mut client, sent) = scripted(|message| match message["method"].as_str() {
+        Some("initialize") => vec![result(message, initialize_result("2025-06-18"))],
+        _ => vec![],
+    });
+    let server = client.initialize().unwrap().clone();
+    assert_eq!(server.server_info.name, "upstream");
+    assert_eq!(server.protocol_version, "2025-06-18");
+    assert_eq!(
+        client.transport().protocol_version.as_deref(),
+        Some("2025-06-18")
+    );
+
+    let sent = sent.borrow();
+    assert_eq!(
+        sent[0]["params"]["protocolVersion"],
+        LATEST_PROTOCOL_VERSION
+    );
+    assert_eq!(sent[0]["params"]["clientInfo"]["name"], "wasmcp-client");
+    assert_eq!(
+        sent[1],
+        json!({ "jsonrpc": "2.0", "method": "notifications/initialized" })
+    );
+}
+
+#[test]
+fn test_initialize_rejects_unsupported_version() {
+    let (mut client, sent) =
+        scripted(|message| vec![result(message, initialize_result("2023-01-01"))]);
+    assert!(matches!(client.initialize(), Err(ClientError::Protocol(_))));
+    // No initialized notification after a failed negotiation
+    assert_eq!(sent.borrow().len(), 1);
+}
+
+#[test]
+fn test_server_messages_before_the_response() {
+    let notifications = Rc::new(RefCell::new(Vec::new()));
+    let seen = notifications.clone();
+    let (client, sent) = scripted(|message| match message["method"].as_str() {
+        Some("tools/call") => vec![
+            json!({ "jsonrpc": "2.0", "method": "notifications/progress", "params": { "progress": 1 } }),
+            json!({ "jsonrpc": "2.0", "id": "s1", "method": "ping" }),
+            json!({ "jsonrpc": "2.0", "id": "s2", "method": "roots/list" }),
+            result(
+                message,
+                json!({
+                    "content": [{ "type": "text", "text": "4" }, { "type": "image", "data": "", "mimeType": "image/png" }],
+                    "structuredContent": { "sum": 4 },
+                }),
+            ),
+        ],
+        _ => vec![],
+    });
+    let mut client = client.on_notification(move |method, params| {
+        seen.borrow_mut().push((method.to_string(), params.clone()));
+    });
+
+    let called = client.call_tool("add", json!({ "a": 2, "b": 2 })).unwrap();
+    assert_eq!(called.text(), "4");
+    assert_eq!(called.structured_content, Some(json!({ "sum": 4 })));
+    assert!(!called.is_error);
+
+    assert_eq!(
+        *notifications.borrow(),
+        [(
+            "notifications/progress".to_string(),
+            json!({ "progress": 1 })
+        )]
+    );
+    let sent = sent.borrow();
+    assert_eq!(
+        sent[1],
+        json!({ "jsonrpc": "2.0", "id": "s1", "result": {} })
+    );
+    assert_eq!(sent[2]["id"], "s2");
+    assert_eq!(sent[2]["error"]["code"], -32601);
+}
+
+#[test]
+fn test_request_handler() {
+    let (client, sent) = scripted(|message| match message["method"].as_str() {
+        Some("tools/call") => vec![
+            json!({ "jsonrpc": "2.0", "id": 7, "method": "sampling/createMessage", "params": { "maxTokens": 10 } }),
+            result(message, json!({ "content": [] })),
+        ],
+        _ => vec![],
+    });
+    let mut client = client.on_request(|method, params| {
+        assert_eq!(method, "sampling/createMessage");
+        Ok(json!({ "maxTokens": params["maxTokens"] }))
+    });
+    client.call_tool("summarize", json!({})).unwrap();
+    assert_eq!(sent.borrow()[1]["result"], json!({ "maxTokens": 10 }));
+}
+
+#[test]
+fn test_rpc_error() {
+    let (mut client, _) = scripted(|message| {
+        vec![json!({
+            "jsonrpc": "2.0",
+            "id": message["id"],
+            "error": { "code": -32602, "message": "Unknown tool: nope", "data": { "retryable": false } },
+        })]
+    });
+    match client.call_tool("nope", json!({})) {
+        Err(ClientError::Rpc(e)) => {
+            assert_eq!(e.kind, wasmcp_errors::ErrorKind::InvalidParams);
+            assert_eq!(e.message, "Unknown tool: nope");
+            assert_eq!(e.data.as_deref(), Some(r#"{"retryable":false}"#));
+        }
+        other => panic!("expected an RPC error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_no_response() {
+    let (mut client, _) = scripted(|_| vec![]);
+    assert!(matches!(client.ping(), Err(ClientError::Transport(_))));
+}
+
+#[test]
+fn test_list_all_follows_cursors() {
+    let (mut client, sent) = scripted(|message| {
+        let page = match message["params"]["cursor"].as_str() {
+            None => json!({ "tools": [{ "name": "a", "inputSchema": {} }], "nextCursor": "p2" }),
+            Some("p2") => json!({ "tools": [{ "name": "b", "inputSchema": {} }] }),
+            Some(other) => panic!("unexpected cursor {}", other),
+        };
+        vec![result(message, page)]
+    });
+    let names: Vec<String> = client
+        .list_all_tools()
+        .unwrap()
+        .into_iter()
+        .map(|t| t.name)
+        .collect();
+    assert_eq!(names, ["a", "b"]);
+    assert_eq!(sent.borrow().len(), 2);
+
+    let (mut client, _) = scripted(|message| {
+        vec![result(
+            message,
+            json!({ "tools": [], "nextCursor": "same" }),
+        )]
+    });
+    assert!(matches!(
+        client.list_all_tools(),
+        Err(ClientError::Protocol(_))
+    ));
+}
+
+#[test]
+fn test_streamable_http() {
+    let requests = Rc::new(RefCell::new(Vec::new()));
+    let log = requests.clone();
+    let http = move |request: HttpRequest| -> Result<HttpResponse, String> {
+        let message: Value = serde_json::from_slice(&request.body).unwrap_or(Value::Null);
+        log.borrow_mut()
+            .push((request.method, request.headers.clone()));
+        let response = |status, headers: &[(&str, &str)], body: String| HttpResponse {
+            status,
+            headers: headers
+                .iter()
+                .map(|(n, v)| (n.to_string(), v.to_string()))
+                .collect(),
+            body: body.into_bytes(),
+        };
+        Ok(match (request.method, message["method"].as_str()) {
+            ("POST", Some("initialize")) => response(
+                200,
+                &[
+                    ("content-type", "application/json"),
+                    ("mcp-session-id", "sess-1"),
+                ],
+                result(&message, initialize_result("2025-11-25")).to_string(),
+            ),
+            ("POST", Some("tools/list")) => response(
+                200,
+                &[("content-type", "text/event-stream")],
+                format!(
+                    "id: 1\ndata:\n\nevent: message\ndata: {}\n\ndata: {}",
+                    json!({ "jsonrpc": "2.0", "method": "notifications/message", "params": {} }),
+                    result(&message, json!({ "tools": [] })),
+                ),
+            ),
+            ("DELETE", _) => response(200, &[], String::new()),
+            _ => response(202, &[], String::new()),
+        })
+    };
+
+    let transport = StreamableHttp::new("https://upstream.example.com/mcp", http)
+        .with_header("authorization", "Bearer t");
+    let mut client = Client::new(transport);
+    client.initialize().unwrap();
+    assert_eq!(client.transport().session_id(), Some("sess-1"));
+    assert!(client.list_tools(None).unwrap().items.is_empty());
+    client.close().unwrap();
+
+    let requests = requests.borrow();
+    let header = |i: usize, name: &str| {
+        requests[i]
+            .1
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.clone())
+    };
+    assert_eq!(header(0, "mcp-session-id"), None);
+    assert_eq!(header(0, "authorization").as_deref(), Some("Bearer t"));
+    // notifications/initialized, tools/list and the DELETE carry the session
+    assert_eq!(header(1, "mcp-session-id").as_deref(), Some("sess-1"));
+    assert_eq!(
+        header(1, "mcp-protocol-version").as_deref(),
+        Some("2025-11-25")
+    );
+    assert_eq!(header(2, "mcp-session-id").as_deref(), Some("sess-1"));
+    assert_eq!(requests[3].0, "DELETE");
+}
+
+#[test]
+fn test_http_errors() {
+    let http = |_: HttpRequest| -> Result<HttpResponse, String> {
+        Ok(HttpResponse {
+            status: 401,
+            headers: vec![],
+            body: b"unauthorized".to_vec(),
+        })
+    };
+    let mut client = Client::new(StreamableHttp::new(
+        "https://upstream.example.com/mcp",
+        http,
+    ));
+    match client.ping() {
+        Err(ClientError::Transport(e)) => assert_eq!(e, "HTTP 401: unauthorized"),
+        other => panic!("expected a transport error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_stdio() {
+    let replies = format!(
+        "{}\n\n{}\n",
+        json!({ "jsonrpc": "2.0", "method": "notifications/tools/list_changed" }),
+        json!({ "jsonrpc": "2.0", "id": 1, "result": {} }),
+    );
+    let mut written = Vec::new();
+    let mut client = Client::new(Stdio::new(replies.as_bytes(), &mut written));
+    client.ping().unwrap();
+    assert!(matches!(client.ping(), Err(ClientError::Transport(_))));
+    drop(client);
+
+    let written = String::from_utf8(written).unwrap();
+    let first: Value = serde_json::from_str(written.lines().next().unwrap()).unwrap();
+    assert_eq!(
+        first,
+        json!({ "jsonrpc": "2.0", "id": 1, "method": "ping" })
+    );
+}
+
+#[test]
+fn test_http_client_transport() {
+    let sent = Rc::new(RefCell::new(Vec::new()));
+    let log = sent.clone();
+    let transport = move |request: &wasmcp_http_client::Request,
+                          _: &wasmcp_http_client::SendOptions| {
+        log.borrow_mut().push(request.clone());
+        Ok(wasmcp_http_client::Response {
+            status: 200,
+            headers: vec![("content-type".to_string(), "application/json".to_string())],
+            body: br#"{"jsonrpc":"2.0","id":1,"result":{}}"#.to_vec(),
+        })
+    };
+    let mut http = StreamableHttp::new(
+        "https://example.com/mcp",
+        wasmcp_http_client::HttpClient::new(transport),
+    );
+
+    http.send(&json!({ "jsonrpc": "2.0", "id": 1, "method": "ping" }))
+        .unwrap();
+    assert_eq!(
+        http.receive().unwrap(),
+        Some(json!({ "jsonrpc": "2.0", "id": 1, "result": {} }))
+    );
+
+    let sent = sent.borrow();
+    assert_eq!(sent[0].method, "POST");
+    assert_eq!(sent[0].url, "https://example.com/mcp");
+    assert!(sent[0].has_header("content-type"));
+    assert_eq!(sent[0].body, br#"{"id":1,"jsonrpc":"2.0","method":"ping"}"#);
+}
@@ -0,0 +1,72 @@
+//! Transports carrying JSON-RPC messages to a server
+//!
+//! A transport sends one message at a time and hands back what the server
+//! sent, one message at a time, so the client can answer a server request
+//! (e.g. `ping`) before the response to its own request arrives.
+
+use crate::ClientError;
+use serde_json::Value;
+use std::io::{BufRead, Write};
+
+pub trait Transport {
+    /// Send one JSON-RPC message
+    fn send(&mut self, message: &Value) -> Result<(), ClientError>;
+
+    /// The next message from the server, or None when nothing more will
+    /// arrive for the messages sent so far
+    fn receive(&mut self) -> Result<Option<Value>, ClientError>;
+
+    /// Called once initialization has negotiated a protocol version
+    fn set_protocol_version(&mut self, _version: &str) {}
+
+    /// End the session, if the transport has one
+    fn close(&mut self) -> Result<(), ClientError> {
+        Ok(())
+    }
+}
+
+/// Newline-delimited JSON over a pair of byte streams
+///
+/// In tests these are typically the pipes of a child process running the
+/// server (e.g. `wasmtime run server.wasm`); inside a component, any pair
+/// of streams the host provides.
+pub struct Stdio<R, W> {
+    reader: R,
+    writer: W,
+}
+
+impl<R: BufRead, W: Write> Stdio<R, W> {
+    pub fn new(reader: R, writer: W) -> Self {
+        Stdio { reader, writer }
+    }
+}
+
+impl<R: BufRead, W: Write> Transport for Stdio<R, W> {
+    fn send(&mut self, message: &Value) -> Result<(), ClientError> {
+        // serde_json escapes newlines inside strings, so one message is one line
+        let mut line = message.to_string();
+        line.push('\n');
+        self.writer
+            .write_all(line.as_bytes())
+            .and_then(|_| self.writer.flush())
+            .map_err(|e| ClientError::Transport(format!("Failed to write to server: {}", e)))
+    }
+
+    fn receive(&mut self) -> Result<Option<Value>, ClientError> {
+        loop {
+            let mut line = String::new();
+            let read = self.reader.read_line(&mut line).map_err(|e| {
+                ClientError::Transport(format!("Failed to read from server: {}", e))
+            })?;
+            if read == 0 {
+                return Ok(None);
+            }
+            if line.trim().is_empty() {
+                continue;
+            }
+            return serde_json::from_str(&line)
+                .map(Some)
+                .map_err(|e| ClientError::Protocol(format!("Invalid JSON from server: {}", e)));
+        }
+    }
+}
@@ -0,0 +1,161 @@
+//! Tamper-evident pagination cursors
+//!
+//! List handlers used to hand out cursors clients could read and edit
+//! (`fs:100`), so a client could skip ahead, or replay a cursor against
+//! filters it was never issued for. This crate encodes a handler's position
+//! once for all of them:
+//! - `Cursor`: the offset, plus the filters the page was listed with
+//! - `CursorCodec`: encodes cursors as `<scope>.<payload>.<mac>`, where the
+//!   payload is base64url JSON and the MAC is HMAC-SHA256 over scope and
+//!   payload under the handler's key
+//!
+//! The scope prefix lets a handler that chains to downstream lists recognize
+//! its own cursors; everything after it is opaque to clients. A cursor that
+//! was modified, or signed under another key, fails to decode, and converts
+//! to an InvalidParams `McpError`.
+//!
+//! ```ignore
+//! let codec = CursorCodec::new("fs", &key);
+//! let next = codec.encode(&Cursor::at(100));
+//! let cursor = codec.decode(&next)?;
+//! ```
+
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use hmac::{Hmac, Mac};
+use serde_json::{Map, Value, json};
+use sha2::Sha256;
+use wasmcp_errors::McpError;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Separator between scope, payload and MAC (not in the base64url alphabet)
+const SEPARATOR: char = '.';
+
+/// A position in a paginated list
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Cursor {
+    /// Items already returned
+    pub offset: u64,
+    /// Filters the listing was made with; carried so later pages match
+    pub filters: Option<Value>,
+}
+
+impl Cursor {
+    pub fn at(offset: u64) -> Self {
+        Cursor {
+            offset,
+            filters: None,
+        }
+    }
+
+    pub fn with_filters(mut self, filters: Value) -> Self {
+        self.filters = Some(filters);
+        self
+    }
+}
+
+/// Why a cursor was rejected
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CursorError {
+    /// Not a cursor this codec issued
+    Malformed,
+    /// The MAC doesn't match: modified, or signed under another key
+    Tampered,
+}
+
+impl std::fmt::Display for CursorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CursorError::Malformed => write!(f, "Invalid cursor"),
+            CursorError::Tampered => write!(f, "Invalid cursor: signature mismatch"),
+        }
+    }
+}
+
+impl std::error::Error for CursorError {}
+
+impl From<CursorError> for McpError {
+    fn from(e: CursorError) -> Self {
+        McpError::invalid_params(e.to_string())
+    }
+}
+
+/// Encodes and verifies one list handler's cursors
+#[derive(Clone)]
+pub struct CursorCodec {
+    scope: String,
+    key: Vec<u8>,
+}
+
+impl CursorCodec {
+    /// Codec for cursors prefixed with `scope` (no `.`), signed with `key`
+    ///
+    /// Cursors only decode under the key they were signed with. A key drawn
+    /// per instance invalidates them when the instance goes away, so servers
+    /// running an instance per request must configure a shared key.
+    pub fn new(scope: impl Into<String>, key: &[u8]) -> Self {
+        CursorCodec {
+            scope: scope.into(),
+            key: key.to_vec(),
+        }
+    }
+
+    /// Whether a cursor carries this codec's scope (it may still be invalid)
+    pub fn owns(&self, cursor: &str) -> bool {
+        cursor
+            .strip_prefix(self.scope.as_str())
+            .is_some_and(|rest| rest.starts_with(SEPARATOR))
+    }
+
+    pub fn encode(&self, cursor: &Cursor) -> String {
+        let mut payload = Map::new();
+        payload.insert("o".to_string(), json!(cursor.offset));
+        if let Some(filters) = &cursor.filters {
+            payload.insert("f".to_string(), filters.clone());
+        }
+        let payload = URL_SAFE_NO_PAD.encode(Value::Object(payload).to_string());
+        let mac = URL_SAFE_NO_PAD.encode(self.mac(&payload).finalize().into_bytes());
+        format!("{}{}{}{}{}", self.scope, SEPARATOR, payload, SEPARATOR, mac)
+    }
+
+    pub fn decode(&self, cursor: &str) -> Result<Cursor, CursorError> {
+        let (scope, rest) = cursor.split_once(SEPARATOR).ok_or(CursorError::Malformed)?;
+        let (payload, mac) = rest.split_once(SEPARATOR).ok_or(CursorError::Malformed)?;
+        if scope != self.scope {
+            return Err(CursorError::Malformed);
+        }
+
+        let mac = URL_SAFE_NO_PAD
+            .decode(mac)
+            .map_err(|_| CursorError::Malformed)?;
+        self.mac(payload)
+            .verify_slice(&mac)
+            .map_err(|_| CursorError::Tampered)?;
+
+        // Signed by us, so a payload that doesn't parse is a bug, not an attack
+        let payload = URL_SAFE_NO_PAD
+            .decode(payload)
+            .ok()
+            .and_then(|p| serde_json::from_slice::<Value>(&p).ok())
+            .ok_or(CursorError::Malformed)?;
+        Ok(Cursor {
+            offset: payload
+                .get("o")
+                .and_then(Value::as_u64)
+                .ok_or(CursorError::Malformed)?,
+            filters: payload.get("f").cloned(),
+        })
+    }
+
+    fn mac(&self, payload: &str) -> HmacSha256 {
+        let mut mac = HmacSha256::new_from_slice(&self.key).expect("HMAC accepts any key length");
+        mac.update(self.scope.as_bytes());
+        mac.update(&[SEPARATOR as u8]);
+        mac.update(payload.as_bytes());
+        mac
+    }
+}
+
+#[cfg(test)]
+mod tests;
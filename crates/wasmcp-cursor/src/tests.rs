@@ -0,0 +1,54 @@
+use super::*;
+
+fn codec() -> CursorCodec {
+    CursorCodec::new("fs", b"instance key")
+}
+
+#[test]
+fn test_round_trip() {
+    let codec = codec();
+    let cursor = Cursor::at(100).with_filters(json!({"mimeType": "text/markdown"}));
+    let encoded = codec.encode(&cursor);
+
+    assert!(encoded.starts_with("fs."));
+    assert!(!encoded.contains("100"));
+    assert!(codec.owns(&encoded));
+    assert_eq!(codec.decode(&encoded), Ok(cursor));
+    assert_eq!(
+        codec.decode(&codec.encode(&Cursor::at(0))),
+        Ok(Cursor::at(0))
+    );
+}
+
+#[test]
+fn test_rejects_modified_cursors() {
+    let codec = codec();
+    let encoded = codec.encode(&Cursor::at(100));
+    let (prefix, mac) = encoded.rsplit_once('.').unwrap();
+
+    // A payload the client wrote itself
+    let forged = format!("fs.{}.{}", URL_SAFE_NO_PAD.encode(r#"{"o":5000}"#), mac);
+    assert_eq!(codec.decode(&forged), Err(CursorError::Tampered));
+
+    // A flipped MAC character
+    let flipped = if mac.starts_with('A') { "B" } else { "A" };
+    let tampered = format!("{}.{}{}", prefix, flipped, &mac[1..]);
+    assert_eq!(codec.decode(&tampered), Err(CursorError::Tampered));
+
+    // Another key or another scope
+    let other_key = CursorCodec::new("fs", b"other key");
+    assert_eq!(other_key.decode(&encoded), Err(CursorError::Tampered));
+    let other_scope = CursorCodec::new("db", b"instance key");
+    assert!(!other_scope.owns(&encoded));
+    assert_eq!(other_scope.decode(&encoded), Err(CursorError::Malformed));
+
+    assert_eq!(codec.decode("fs:100"), Err(CursorError::Malformed));
+    assert_eq!(codec.decode("fs.abc"), Err(CursorError::Malformed));
+}
+
+#[test]
+fn test_errors_are_invalid_params() {
+    let err: McpError = CursorError::Tampered.into();
+    assert_eq!(err.code, -32602);
+    assert!(!codec().owns("fsx.abc.def"));
+}
@@ -0,0 +1,326 @@
+//! Outbound host allow-list and audit log
+//!
+//! Components that call upstreams over wasi:http (OpenAPI and GraphQL tools,
+//! HTTP resources, maintenance jobs) take their URLs from configuration or
+//! documents, and a host grants network access to the whole component. This
+//! crate gives them one policy, checked in their HTTP client before any
+//! request leaves the component:
+//!
+//! ```text
+//! WASMCP_EGRESS_ALLOW='api.example.com, *.internal.example.com:8443, [::1]:*'
+//! WASMCP_EGRESS_AUDIT=true
+//! ```
+//!
+//! - `host` allows the scheme's default port (443 for https, 80 for http)
+//! - `host:port` allows one port, `host:*` every port
+//! - `*.example.com` allows subdomains of example.com, not example.com itself
+//! - `*` allows every host
+//!
+//! Without `WASMCP_EGRESS_ALLOW` every host is allowed. A list that fails to
+//! parse blocks every request: a typo must not open the network.
+//!
+//! Blocked requests always write a JSON audit line to stderr; with
+//! `WASMCP_EGRESS_AUDIT=true` allowed requests do too:
+//!
+//! ```text
+//! {"event":"egress","component":"openapi-tools","method":"GET","scheme":"https","host":"evil.example","port":443,"decision":"deny","reason":"host not in WASMCP_EGRESS_ALLOW"}
+//! ```
+//!
+//! ```ignore
+//! let egress = wasmcp_egress::Egress::from_env("openapi-tools", &get_environment());
+//! egress
+//!     .authorize(method, parsed.scheme(), host, parsed.port_or_known_default().unwrap_or(0))
+//!     .map_err(|e| e.to_string())?;
+//! ```
+
+use serde_json::{Value, json};
+use std::fmt;
+
+/// Environment variable holding the allow-list
+pub const EGRESS_ALLOW_ENV: &str = "WASMCP_EGRESS_ALLOW";
+
+/// Environment variable enabling audit lines for allowed requests
+pub const EGRESS_AUDIT_ENV: &str = "WASMCP_EGRESS_AUDIT";
+
+/// Hosts and ports a rule matches
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum HostPattern {
+    /// `*`
+    Any,
+    /// `*.example.com`, stored as `.example.com`
+    Suffix(String),
+    /// `api.example.com`, `10.0.0.1` or `[::1]`
+    Exact(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PortPattern {
+    /// No port given: the scheme's default port
+    Default,
+    /// `:*`
+    Any,
+    Exact(u16),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Rule {
+    host: HostPattern,
+    port: PortPattern,
+}
+
+/// Parsed allow-list
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EgressPolicy {
+    /// None allows every host
+    rules: Option<Vec<Rule>>,
+}
+
+impl EgressPolicy {
+    /// Parse a comma-separated allow-list
+    ///
+    /// An empty or whitespace-only spec allows every host.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let entries: Vec<&str> = spec
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .collect();
+        if entries.is_empty() {
+            return Ok(Self::default());
+        }
+
+        let rules = entries
+            .into_iter()
+            .map(parse_rule)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(EgressPolicy { rules: Some(rules) })
+    }
+
+    /// Whether any host is allowed
+    pub fn is_unrestricted(&self) -> bool {
+        self.rules.is_none()
+    }
+
+    /// Whether a request to `host:port` over `scheme` is allowed
+    ///
+    /// `host` is the URL's host as written (lowercase, IPv6 in brackets).
+    pub fn allows(&self, scheme: &str, host: &str, port: u16) -> bool {
+        let Some(rules) = &self.rules else {
+            return true;
+        };
+        let host = host.trim_end_matches('.').to_ascii_lowercase();
+        rules.iter().any(|rule| {
+            let host_matches = match &rule.host {
+                HostPattern::Any => true,
+                HostPattern::Suffix(suffix) => host.ends_with(suffix.as_str()),
+                HostPattern::Exact(exact) => host == *exact,
+            };
+            let port_matches = match rule.port {
+                PortPattern::Default => default_port(scheme) == Some(port),
+                PortPattern::Any => true,
+                PortPattern::Exact(allowed) => port == allowed,
+            };
+            host_matches && port_matches
+        })
+    }
+}
+
+/// Parse one `host[:port]` entry
+fn parse_rule(entry: &str) -> Result<Rule, String> {
+    let invalid =
+        |reason: &str| format!("Invalid {} entry '{}': {}", EGRESS_ALLOW_ENV, entry, reason);
+
+    // IPv6 literals keep their brackets, like url hosts
+    let (host, port) = if entry.starts_with('[') {
+        let end = entry
+            .find(']')
+            .ok_or_else(|| invalid("unclosed IPv6 bracket"))?;
+        let (host, rest) = entry.split_at(end + 1);
+        match rest {
+            "" => (host, None),
+            _ => (
+                host,
+                Some(
+                    rest.strip_prefix(':')
+                        .ok_or_else(|| invalid("expected ':' after IPv6 address"))?,
+                ),
+            ),
+        }
+    } else {
+        match entry.rsplit_once(':') {
+            Some((host, port)) => (host, Some(port)),
+            None => (entry, None),
+        }
+    };
+
+    let port = match port {
+        None => PortPattern::Default,
+        Some("*") => PortPattern::Any,
+        Some(port) => PortPattern::Exact(
+            port.parse::<u16>()
+                .ok()
+                .filter(|port| *port > 0)
+                .ok_or_else(|| invalid("port must be 1-65535 or '*'"))?,
+        ),
+    };
+
+    let host = host.to_ascii_lowercase();
+    let host = if host == "*" {
+        HostPattern::Any
+    } else if let Some(domain) = host.strip_prefix("*.") {
+        let domain = domain.trim_end_matches('.');
+        if domain.is_empty() || domain.contains('*') {
+            return Err(invalid("wildcards are only allowed as a leading '*.'"));
+        }
+        HostPattern::Suffix(format!(".{}", domain))
+    } else if host.is_empty() {
+        return Err(invalid("missing host"));
+    } else if host.contains(['*', '/', '@', ' ']) {
+        return Err(invalid("expected a host name or IP address"));
+    } else {
+        HostPattern::Exact(host.trim_end_matches('.').to_string())
+    };
+
+    Ok(Rule { host, port })
+}
+
+fn default_port(scheme: &str) -> Option<u16> {
+    match scheme {
+        "https" => Some(443),
+        "http" => Some(80),
+        _ => None,
+    }
+}
+
+/// A request the allow-list blocked
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EgressViolation {
+    pub component: String,
+    pub method: String,
+    pub host: String,
+    pub port: u16,
+    pub reason: String,
+}
+
+impl EgressViolation {
+    /// Structured form, e.g. for JSON-RPC error data
+    pub fn to_json(&self) -> Value {
+        json!({
+            "component": self.component,
+            "method": self.method,
+            "host": self.host,
+            "port": self.port,
+            "reason": self.reason,
+        })
+    }
+}
+
+impl fmt::Display for EgressViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Outbound request to {}:{} blocked: {}",
+            self.host, self.port, self.reason
+        )
+    }
+}
+
+impl std::error::Error for EgressViolation {}
+
+/// Egress policy of one component
+#[derive(Debug, Clone)]
+pub struct Egress {
+    component: String,
+    /// Err holds the parse error; every request is then blocked
+    policy: Result<EgressPolicy, String>,
+    audit: bool,
+}
+
+impl Egress {
+    /// Load the policy from `WASMCP_EGRESS_ALLOW` and `WASMCP_EGRESS_AUDIT`
+    ///
+    /// `vars` is the component's environment, e.g. wasi:cli `get-environment`.
+    pub fn from_env(component: &str, vars: &[(String, String)]) -> Self {
+        let var = |name: &str| {
+            vars.iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.as_str())
+        };
+        Egress {
+            component: component.to_string(),
+            policy: EgressPolicy::parse(var(EGRESS_ALLOW_ENV).unwrap_or_default()),
+            audit: var(EGRESS_AUDIT_ENV).is_some_and(|v| v.eq_ignore_ascii_case("true")),
+        }
+    }
+
+    /// Egress policy from an already parsed allow-list
+    pub fn new(component: &str, policy: EgressPolicy, audit: bool) -> Self {
+        Egress {
+            component: component.to_string(),
+            policy: Ok(policy),
+            audit,
+        }
+    }
+
+    /// Check a request before sending it, writing the audit line
+    ///
+    /// `port` is the port the request goes to, explicit or the scheme's default.
+    pub fn authorize(
+        &self,
+        method: &str,
+        scheme: &str,
+        host: &str,
+        port: u16,
+    ) -> Result<(), EgressViolation> {
+        let denied = match &self.policy {
+            Ok(policy) if policy.allows(scheme, host, port) => None,
+            Ok(_) => Some(format!("host not in {}", EGRESS_ALLOW_ENV)),
+            Err(e) => Some(e.clone()),
+        };
+
+        let Some(reason) = denied else {
+            if self.audit {
+                let entry = audit_entry(&self.component, method, scheme, host, port, None);
+                eprintln!("{}", entry);
+            }
+            return Ok(());
+        };
+
+        let entry = audit_entry(&self.component, method, scheme, host, port, Some(&reason));
+        eprintln!("{}", entry);
+        Err(EgressViolation {
+            component: self.component.clone(),
+            method: method.to_string(),
+            host: host.to_string(),
+            port,
+            reason,
+        })
+    }
+}
+
+/// Audit line for a request; `reason` is set for blocked requests
+fn audit_entry(
+    component: &str,
+    method: &str,
+    scheme: &str,
+    host: &str,
+    port: u16,
+    reason: Option<&str>,
+) -> Value {
+    let mut entry = json!({
+        "event": "egress",
+        "component": component,
+        "method": method,
+        "scheme": scheme,
+        "host": host,
+        "port": port,
+        "decision": if reason.is_some() { "deny" } else { "allow" },
+    });
+    if let Some(reason) = reason {
+        entry["reason"] = json!(reason);
+    }
+    entry
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,79 @@
+use super::*;
+
+#[test]
+fn test_unset_allows_everything() {
+    let policy = EgressPolicy::parse(" , ").unwrap();
+    assert!(policy.is_unrestricted());
+    assert!(policy.allows("https", "anything.example", 8443));
+}
+
+#[test]
+fn test_host_and_port_patterns() {
+    let policy =
+        EgressPolicy::parse("api.example.com, *.internal.example.com:8443, [::1]:*, 10.0.0.1:9000")
+            .unwrap();
+
+    // Bare host: default port of the scheme only
+    assert!(policy.allows("https", "api.example.com", 443));
+    assert!(policy.allows("http", "API.example.com.", 80));
+    assert!(!policy.allows("https", "api.example.com", 8443));
+    assert!(!policy.allows("https", "evil-api.example.com", 443));
+
+    // Subdomain wildcard with a port
+    assert!(policy.allows("https", "db.internal.example.com", 8443));
+    assert!(!policy.allows("https", "internal.example.com", 8443));
+    assert!(!policy.allows("https", "db.internal.example.com", 443));
+    assert!(!policy.allows("https", "db.internal.example.com.evil", 8443));
+
+    assert!(policy.allows("http", "[::1]", 1234));
+    assert!(policy.allows("http", "10.0.0.1", 9000));
+    assert!(!policy.allows("http", "10.0.0.1", 80));
+}
+
+#[test]
+fn test_invalid_entries() {
+    for spec in [
+        "api.example.com:0",
+        "api.example.com:http",
+        "*.",
+        "api.*.example.com",
+        "https://api.example.com",
+        "[::1",
+        ":443",
+    ] {
+        assert!(EgressPolicy::parse(spec).is_err(), "{}", spec);
+    }
+}
+
+#[test]
+fn test_authorize() {
+    let vars = vec![(EGRESS_ALLOW_ENV.to_string(), "*.example.com".to_string())];
+    let egress = Egress::from_env("openapi-tools", &vars);
+    assert!(
+        egress
+            .authorize("GET", "https", "api.example.com", 443)
+            .is_ok()
+    );
+
+    let violation = egress
+        .authorize("POST", "https", "evil.example", 443)
+        .unwrap_err();
+    assert_eq!(
+        violation.to_string(),
+        "Outbound request to evil.example:443 blocked: host not in WASMCP_EGRESS_ALLOW"
+    );
+    assert_eq!(violation.to_json()["method"], "POST");
+    assert_eq!(violation.to_json()["component"], "openapi-tools");
+
+    // A list that doesn't parse blocks everything
+    let vars = vec![(
+        EGRESS_ALLOW_ENV.to_string(),
+        "api.example.com:x".to_string(),
+    )];
+    let egress = Egress::from_env("openapi-tools", &vars);
+    assert!(
+        egress
+            .authorize("GET", "https", "api.example.com", 443)
+            .is_err()
+    );
+}
@@ -0,0 +1,123 @@
+//! Error kinds and their JSON-RPC code assignments
+//!
+//! Standard JSON-RPC errors keep their spec codes. wasmcp-specific kinds use
+//! the implementation-defined server range (-32000 to -32099); -32002 for
+//! missing resources comes from the MCP spec.
+
+/// What went wrong
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    /// Invalid JSON was received (-32700)
+    ParseError,
+    /// The message is not a valid request (-32600)
+    InvalidRequest,
+    /// The method does not exist or is not offered (-32601)
+    MethodNotFound,
+    /// Invalid parameters, including unknown tool and prompt names (-32602)
+    InvalidParams,
+    /// Internal error, and any code this taxonomy doesn't know (-32603)
+    InternalError,
+    /// Missing or invalid credentials (-32001)
+    Unauthorized,
+    /// Resource URI not found (-32002)
+    ResourceNotFound,
+    /// Valid credentials without the required permission (-32003)
+    Forbidden,
+    /// Concurrency or rate limit hit (-32004)
+    RateLimited,
+    /// Request deadline exceeded (-32005)
+    Timeout,
+    /// An upstream dependency is unavailable (-32006)
+    Unavailable,
+}
+
+/// Which generated `error-code` variant carries a code
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Variant {
+    ParseError,
+    InvalidRequest,
+    MethodNotFound,
+    InvalidParams,
+    InternalError,
+    /// -32000 to -32099
+    Server,
+    /// Other codes in the reserved JSON-RPC range (-32768 to -32000)
+    JsonRpc,
+    /// Anything else
+    Mcp,
+}
+
+pub(crate) const ALL: [ErrorKind; 11] = [
+    ErrorKind::ParseError,
+    ErrorKind::InvalidRequest,
+    ErrorKind::MethodNotFound,
+    ErrorKind::InvalidParams,
+    ErrorKind::InternalError,
+    ErrorKind::Unauthorized,
+    ErrorKind::ResourceNotFound,
+    ErrorKind::Forbidden,
+    ErrorKind::RateLimited,
+    ErrorKind::Timeout,
+    ErrorKind::Unavailable,
+];
+
+impl ErrorKind {
+    /// JSON-RPC error code
+    pub const fn code(self) -> i64 {
+        match self {
+            Self::ParseError => -32700,
+            Self::InvalidRequest => -32600,
+            Self::MethodNotFound => -32601,
+            Self::InvalidParams => -32602,
+            Self::InternalError => -32603,
+            Self::Unauthorized => -32001,
+            Self::ResourceNotFound => -32002,
+            Self::Forbidden => -32003,
+            Self::RateLimited => -32004,
+            Self::Timeout => -32005,
+            Self::Unavailable => -32006,
+        }
+    }
+
+    /// Kind for a wire code; unknown codes are internal errors
+    pub fn from_code(code: i64) -> Self {
+        ALL.into_iter()
+            .find(|kind| kind.code() == code)
+            .unwrap_or(Self::InternalError)
+    }
+
+    /// Whether the same request may succeed if retried later
+    pub const fn retryable(self) -> bool {
+        matches!(self, Self::RateLimited | Self::Timeout | Self::Unavailable)
+    }
+
+    /// HTTP status for transports that fail a request before JSON-RPC
+    pub const fn http_status(self) -> u16 {
+        match self {
+            Self::ParseError | Self::InvalidRequest | Self::InvalidParams => 400,
+            Self::Unauthorized => 401,
+            Self::Forbidden => 403,
+            Self::MethodNotFound | Self::ResourceNotFound => 404,
+            Self::RateLimited => 429,
+            Self::InternalError => 500,
+            Self::Unavailable => 503,
+            Self::Timeout => 504,
+        }
+    }
+}
+
+impl Variant {
+    /// Variant for a wire code, following the ranges in the `error-code` WIT docs
+    pub const fn for_code(code: i64) -> Self {
+        match code {
+            -32700 => Self::ParseError,
+            -32600 => Self::InvalidRequest,
+            -32601 => Self::MethodNotFound,
+            -32602 => Self::InvalidParams,
+            -32603 => Self::InternalError,
+            -32099..=-32000 => Self::Server,
+            -32768..=-32100 => Self::JsonRpc,
+            _ => Self::Mcp,
+        }
+    }
+}
@@ -0,0 +1,190 @@
+//! Shared MCP error taxonomy
+//!
+//! Every component generates its own `ErrorCode` bindings, so the same error
+//! used to be spelled differently in each crate (bare codes, ad-hoc variants).
+//! This crate defines the taxonomy once:
+//! - `ErrorKind`: what went wrong, with its JSON-RPC code, HTTP status and
+//!   retryability
+//! - `McpError`: a kind plus message and data, independent of any bindings,
+//!   optionally overriding the kind's retryability and suggesting a delay
+//! - `impl_error_code!`: generates conversions to and from a crate's
+//!   generated `ErrorCode` / `Error` types
+//!
+//! ```ignore
+//! use bindings::wasmcp::mcp_v20251125::mcp::{Error, ErrorCode};
+//! wasmcp_errors::impl_error_code!(ErrorCode, Error);
+//!
+//! let err: ErrorCode = McpError::tool_not_found("add").into();
+//! ```
+
+mod kind;
+mod macros;
+
+pub use kind::{ErrorKind, Variant};
+
+/// An MCP error independent of any generated bindings
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct McpError {
+    pub kind: ErrorKind,
+    /// JSON-RPC code (normally `kind.code()`, but preserved from the wire)
+    pub code: i64,
+    pub message: String,
+    /// Additional error data (JSON)
+    pub data: Option<String>,
+    /// Overrides `kind.retryable()` when set
+    pub retryable: Option<bool>,
+    /// Suggested delay before retrying, in seconds
+    pub retry_after: Option<u64>,
+}
+
+impl McpError {
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        McpError {
+            kind,
+            code: kind.code(),
+            message: message.into(),
+            data: None,
+            retryable: None,
+            retry_after: None,
+        }
+    }
+
+    /// Rebuild an error from its wire representation
+    pub fn from_parts(code: i64, message: String, data: Option<String>) -> Self {
+        McpError {
+            kind: ErrorKind::from_code(code),
+            code,
+            message,
+            data,
+            retryable: None,
+            retry_after: None,
+        }
+    }
+
+    /// Attach error data (must be JSON)
+    pub fn with_data(mut self, data: impl Into<String>) -> Self {
+        self.data = Some(data.into());
+        self
+    }
+
+    /// Mark the failure as transient: retrying the same request may succeed
+    pub fn transient(mut self) -> Self {
+        self.retryable = Some(true);
+        self
+    }
+
+    /// Mark the failure as permanent: retrying the same request will fail
+    /// again, even if the kind is normally retryable (e.g. an exhausted quota)
+    pub fn permanent(mut self) -> Self {
+        self.retryable = Some(false);
+        self.retry_after = None;
+        self
+    }
+
+    /// Suggest how long clients should wait before retrying (implies transient)
+    pub fn with_retry_after(mut self, seconds: u64) -> Self {
+        self.retryable = Some(true);
+        self.retry_after = Some(seconds);
+        self
+    }
+
+    pub fn parse_error(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::ParseError, message)
+    }
+
+    pub fn invalid_request(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::InvalidRequest, message)
+    }
+
+    pub fn method_not_found(method: &str) -> Self {
+        Self::new(
+            ErrorKind::MethodNotFound,
+            format!("Method not found: {}", method),
+        )
+    }
+
+    pub fn invalid_params(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::InvalidParams, message)
+    }
+
+    /// Unknown tool name (the spec reports these as invalid params)
+    pub fn tool_not_found(name: &str) -> Self {
+        Self::new(ErrorKind::InvalidParams, format!("Unknown tool: {}", name))
+    }
+
+    pub fn resource_not_found(uri: &str) -> Self {
+        Self::new(
+            ErrorKind::ResourceNotFound,
+            format!("Resource not found: {}", uri),
+        )
+    }
+
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::InternalError, message)
+    }
+
+    pub fn unauthorized(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Unauthorized, message)
+    }
+
+    pub fn forbidden(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Forbidden, message)
+    }
+
+    pub fn rate_limited(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::RateLimited, message)
+    }
+
+    pub fn timeout(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Timeout, message)
+    }
+
+    pub fn unavailable(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Unavailable, message)
+    }
+
+    pub fn retryable(&self) -> bool {
+        self.retryable.unwrap_or_else(|| self.kind.retryable())
+    }
+
+    /// Retry hints as JSON, for errors clients should treat specially
+    ///
+    /// `{"retryable": true}` (plus `"retryAfter"` seconds when suggested) for
+    /// retryable errors, `{"retryable": false}` for errors of a retryable kind
+    /// marked permanent, `None` otherwise. Tools reporting failures as
+    /// `is-error` results can put this in the result's `meta`.
+    pub fn retry_metadata(&self) -> Option<String> {
+        match (self.retryable(), self.retry_after) {
+            (true, Some(seconds)) => {
+                Some(format!(r#"{{"retryable":true,"retryAfter":{}}}"#, seconds))
+            }
+            (true, None) => Some(r#"{"retryable":true}"#.to_string()),
+            (false, _) if self.kind.retryable() => Some(r#"{"retryable":false}"#.to_string()),
+            (false, _) => None,
+        }
+    }
+
+    /// Code, message and data as sent on the wire
+    ///
+    /// Errors without data get their `retry_metadata()` so clients can tell
+    /// transient failures apart without knowing wasmcp's code assignments.
+    /// Caller-supplied data is sent as is.
+    pub fn into_parts(self) -> (i64, String, Option<String>) {
+        let data = match self.data {
+            None => self.retry_metadata(),
+            data => data,
+        };
+        (self.code, self.message, data)
+    }
+}
+
+impl std::fmt::Display for McpError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.message, self.code)
+    }
+}
+
+impl std::error::Error for McpError {}
+
+#[cfg(test)]
+mod tests;
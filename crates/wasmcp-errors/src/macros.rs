@@ -0,0 +1,64 @@
+//! Conversions for generated bindings
+
+/// Implement conversions between `McpError` and a crate's generated
+/// `error-code` variant and `error` record
+///
+/// `From<McpError> for ErrorCode` picks the variant from the code range, and
+/// `From<ErrorCode>` / `From<&ErrorCode> for McpError` recover the kind from
+/// the code.
+#[macro_export]
+macro_rules! impl_error_code {
+    ($error_code:ty, $error:ty) => {
+        const _: () = {
+            type Code = $error_code;
+            type Payload = $error;
+
+            impl ::core::convert::From<$crate::McpError> for Code {
+                fn from(e: $crate::McpError) -> Self {
+                    let (code, message, data) = e.into_parts();
+                    let payload = Payload {
+                        code,
+                        message,
+                        data,
+                    };
+                    match $crate::Variant::for_code(code) {
+                        $crate::Variant::ParseError => Code::ParseError(payload),
+                        $crate::Variant::InvalidRequest => Code::InvalidRequest(payload),
+                        $crate::Variant::MethodNotFound => Code::MethodNotFound(payload),
+                        $crate::Variant::InvalidParams => Code::InvalidParams(payload),
+                        $crate::Variant::InternalError => Code::InternalError(payload),
+                        $crate::Variant::Server => Code::Server(payload),
+                        $crate::Variant::JsonRpc => Code::JsonRpc(payload),
+                        $crate::Variant::Mcp => Code::Mcp(payload),
+                    }
+                }
+            }
+
+            impl ::core::convert::From<&Code> for $crate::McpError {
+                fn from(error_code: &Code) -> Self {
+                    let payload = match error_code {
+                        Code::ParseError(p)
+                        | Code::InvalidRequest(p)
+                        | Code::MethodNotFound(p)
+                        | Code::InvalidParams(p)
+                        | Code::InternalError(p)
+                        | Code::Server(p)
+                        | Code::JsonRpc(p)
+                        | Code::Mcp(p) => p,
+                    };
+                    $crate::McpError::from_parts(
+                        payload.code,
+                        payload.message.clone(),
+                        payload.data.clone(),
+                    )
+                }
+            }
+
+            impl ::core::convert::From<Code> for $crate::McpError {
+                fn from(error_code: Code) -> Self {
+                    $crate::McpError::from(&error_code)
+                }
+            }
+        };
+    };
+}
@@ -0,0 +1,113 @@
+use super::*;
+
+// Shape of the wit-bindgen output for `error` / `error-code`
+#[derive(Debug, Clone, PartialEq)]
+struct Error {
+    code: i64,
+    message: String,
+    data: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum ErrorCode {
+    ParseError(Error),
+    InvalidRequest(Error),
+    MethodNotFound(Error),
+    InvalidParams(Error),
+    InternalError(Error),
+    Server(Error),
+    JsonRpc(Error),
+    Mcp(Error),
+}
+
+crate::impl_error_code!(ErrorCode, Error);
+
+#[test]
+fn test_standard_codes_map_to_their_variants() {
+    let code: ErrorCode = McpError::method_not_found("foo/bar").into();
+    assert_eq!(
+        code,
+        ErrorCode::MethodNotFound(Error {
+            code: -32601,
+            message: "Method not found: foo/bar".to_string(),
+            data: None,
+        })
+    );
+
+    let code: ErrorCode = McpError::tool_not_found("add").into();
+    assert!(matches!(code, ErrorCode::InvalidParams(ref e) if e.code == -32602));
+}
+
+#[test]
+fn test_wasmcp_kinds_use_server_range() {
+    let code: ErrorCode = McpError::resource_not_found("file:///x").into();
+    assert!(matches!(code, ErrorCode::Server(ref e) if e.code == -32002));
+}
+
+#[test]
+fn test_retryable_errors_carry_metadata() {
+    let code: ErrorCode = McpError::rate_limited("Too many calls").into();
+    let ErrorCode::Server(error) = code else {
+        panic!("Expected server error");
+    };
+    assert_eq!(error.data.as_deref(), Some(r#"{"retryable":true}"#));
+
+    // Caller-supplied data is left alone
+    let (_, _, data) = McpError::timeout("Slow").with_data("{}").into_parts();
+    assert_eq!(data.as_deref(), Some("{}"));
+
+    assert!(!McpError::internal("Boom").retryable());
+}
+
+#[test]
+fn test_retry_overrides() {
+    // Tools can mark any failure transient and suggest a delay
+    let (_, _, data) = McpError::internal("Upstream flaked")
+        .with_retry_after(30)
+        .into_parts();
+    assert_eq!(
+        data.as_deref(),
+        Some(r#"{"retryable":true,"retryAfter":30}"#)
+    );
+
+    let error = McpError::internal("Upstream flaked").transient();
+    assert!(error.retryable());
+    assert_eq!(
+        error.retry_metadata().as_deref(),
+        Some(r#"{"retryable":true}"#)
+    );
+
+    // ...or tell clients not to retry an otherwise retryable kind
+    let error = McpError::rate_limited("Daily quota exhausted")
+        .with_retry_after(5)
+        .permanent();
+    assert!(!error.retryable());
+    let (_, _, data) = error.into_parts();
+    assert_eq!(data.as_deref(), Some(r#"{"retryable":false}"#));
+
+    // Permanent failures of non-retryable kinds need no hint
+    assert_eq!(
+        McpError::invalid_params("Bad").permanent().retry_metadata(),
+        None
+    );
+}
+
+#[test]
+fn test_round_trip_preserves_unknown_codes() {
+    let code = ErrorCode::JsonRpc(Error {
+        code: -32500,
+        message: "Custom".to_string(),
+        data: None,
+    });
+    let error = McpError::from(&code);
+    assert_eq!(error.kind, ErrorKind::InternalError);
+    assert_eq!(error.code, -32500);
+    assert_eq!(ErrorCode::from(error), code);
+}
+
+#[test]
+fn test_kind_codes_are_unique_and_recoverable() {
+    for kind in kind::ALL {
+        assert_eq!(ErrorKind::from_code(kind.code()), kind);
+    }
+}
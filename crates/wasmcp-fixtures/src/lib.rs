@@ -0,0 +1,44 @@
+//! Property-test strategies for the WIT-generated MCP types
+//!
+//! Serializers, parsers and middlewares all consume the same
+//! `wasmcp:mcp-v20251125/mcp` types, but hand-written fixtures only ever
+//! cover the shapes their author thought of. This crate generates values
+//! across the whole type space with proptest:
+//! - Binding-independent strategies for the WIT type aliases (`json`, `uri`,
+//!   `cursor`, ...) as plain functions
+//! - `mcp_strategies!`: generates strategies for a crate's own generated
+//!   types (`CallToolRequest`, `ServerNotification`, `ErrorCode`, ...)
+//!
+//! Every component generates its own bindings, so the record and variant
+//! strategies can't be written against one set of types; the macro expands
+//! them against the path it is given, like `wasmcp_errors::impl_error_code!`.
+//!
+//! ```ignore
+//! #[cfg(test)]
+//! mod strategies {
+//!     wasmcp_fixtures::mcp_strategies!(crate::bindings::wasmcp::mcp_v20251125::mcp);
+//! }
+//!
+//! proptest! {
+//!     #[test]
+//!     fn notifications_serialize(n in strategies::server_notification()) {
+//!         let (method, _) = serialize_server_notification(&n);
+//!         prop_assert!(method.starts_with("notifications/"));
+//!     }
+//! }
+//! ```
+//!
+//! Stream variants (`text-stream`, `blob-stream`) hold host resources that
+//! can't be created outside a running component, so generated text and blob
+//! data is always inline.
+
+mod macros;
+mod scalars;
+
+pub use scalars::{cursor, json, json_object, json_value, mime_type, name, text, uri};
+
+/// Re-exported so expanded strategies don't need a direct dependency
+pub use proptest;
+
+#[cfg(test)]
+mod tests;
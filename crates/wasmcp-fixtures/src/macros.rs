@@ -0,0 +1,893 @@
+//! Strategies for generated bindings
+
+/// Generate proptest strategies for a crate's generated `mcp` types
+///
+/// Expands to one `pub fn` per type, each returning a `BoxedStrategy`, plus
+/// `use` items for the types and proptest; invoke it in a module of its own.
+///
+/// Coverage follows the messages a server exchanges:
+/// - `client_request`, `client_notification` and `client_message`
+///   (requests, errors and notifications)
+/// - `server_result`, `server_notification` and `server_message`
+///   (results, errors and notifications)
+/// - every record and variant they contain, e.g. `call_tool_request`,
+///   `content_block`, `error_code`
+///
+/// Server-to-client requests and their results (elicitation, sampling,
+/// roots) are not generated.
+#[macro_export]
+macro_rules! mcp_strategies {
+    ($mcp:path) => {
+        use $crate::proptest::prelude::{BoxedStrategy, Just, Strategy, any, prop};
+        use $crate::proptest::prop_oneof;
+        use $mcp as mcp;
+
+        // =====================================================================
+        // Protocol scalars
+        // =====================================================================
+
+        pub fn request_id() -> BoxedStrategy<mcp::RequestId> {
+            prop_oneof![
+                any::<i64>().prop_map(mcp::RequestId::Number),
+                $crate::text().prop_map(mcp::RequestId::String),
+            ]
+            .boxed()
+        }
+
+        pub fn progress_token() -> BoxedStrategy<mcp::ProgressToken> {
+            prop_oneof![
+                $crate::text().prop_map(mcp::ProgressToken::String),
+                any::<i64>().prop_map(mcp::ProgressToken::Integer),
+            ]
+            .boxed()
+        }
+
+        pub fn log_level() -> BoxedStrategy<mcp::LogLevel> {
+            prop_oneof![
+                Just(mcp::LogLevel::Debug),
+                Just(mcp::LogLevel::Info),
+                Just(mcp::LogLevel::Notice),
+                Just(mcp::LogLevel::Warning),
+                Just(mcp::LogLevel::Error),
+                Just(mcp::LogLevel::Critical),
+                Just(mcp::LogLevel::Alert),
+                Just(mcp::LogLevel::Emergency),
+            ]
+            .boxed()
+        }
+
+        pub fn protocol_version() -> BoxedStrategy<mcp::ProtocolVersion> {
+            prop_oneof![
+                Just(mcp::ProtocolVersion::V20251125),
+                Just(mcp::ProtocolVersion::V20250618),
+                Just(mcp::ProtocolVersion::V20250326),
+                Just(mcp::ProtocolVersion::V20241105),
+            ]
+            .boxed()
+        }
+
+        pub fn role() -> BoxedStrategy<mcp::Role> {
+            prop_oneof![Just(mcp::Role::User), Just(mcp::Role::Assistant)].boxed()
+        }
+
+        pub fn meta() -> BoxedStrategy<Option<String>> {
+            prop::option::of($crate::json_object()).boxed()
+        }
+
+        // =====================================================================
+        // Content
+        // =====================================================================
+
+        pub fn annotations() -> BoxedStrategy<mcp::Annotations> {
+            (
+                prop::option::of(prop::collection::vec(role(), 0..3)),
+                prop::option::of($crate::text()),
+                prop::option::of(0.0..=1.0f64),
+            )
+                .prop_map(|(audience, last_modified, priority)| mcp::Annotations {
+                    audience,
+                    last_modified,
+                    priority,
+                })
+                .boxed()
+        }
+
+        pub fn icon() -> BoxedStrategy<mcp::Icon> {
+            (
+                $crate::uri(),
+                prop::option::of($crate::mime_type()),
+                prop::option::of(prop::collection::vec(
+                    "any|[1-9][0-9]{1,2}x[1-9][0-9]{1,2}",
+                    0..3,
+                )),
+                prop::option::of(prop_oneof![
+                    Just(mcp::IconTheme::Light),
+                    Just(mcp::IconTheme::Dark),
+                ]),
+            )
+                .prop_map(|(src, mime_type, sizes, theme)| mcp::Icon {
+                    src,
+                    mime_type,
+                    sizes,
+                    theme,
+                })
+                .boxed()
+        }
+
+        pub fn icons() -> BoxedStrategy<Option<Vec<mcp::Icon>>> {
+            prop::option::of(prop::collection::vec(icon(), 0..3)).boxed()
+        }
+
+        pub fn content_options() -> BoxedStrategy<mcp::ContentOptions> {
+            (prop::option::of(annotations()), meta())
+                .prop_map(|(annotations, meta)| mcp::ContentOptions { annotations, meta })
+                .boxed()
+        }
+
+        pub fn embedded_resource_options() -> BoxedStrategy<mcp::EmbeddedResourceOptions> {
+            (prop::option::of($crate::mime_type()), meta())
+                .prop_map(|(mime_type, meta)| mcp::EmbeddedResourceOptions { mime_type, meta })
+                .boxed()
+        }
+
+        pub fn text_content() -> BoxedStrategy<mcp::TextContent> {
+            ($crate::text(), prop::option::of(content_options()))
+                .prop_map(|(text, options)| mcp::TextContent {
+                    text: mcp::TextData::Text(text),
+                    options,
+                })
+                .boxed()
+        }
+
+        /// Image and audio content
+        pub fn blob() -> BoxedStrategy<mcp::Blob> {
+            (
+                prop::collection::vec(any::<u8>(), 0..64),
+                $crate::mime_type(),
+                prop::option::of(content_options()),
+            )
+                .prop_map(|(data, mime_type, options)| mcp::Blob {
+                    data: mcp::BlobData::Blob(data),
+                    mime_type,
+                    options,
+                })
+                .boxed()
+        }
+
+        pub fn resource_link_content() -> BoxedStrategy<mcp::ResourceLinkContent> {
+            let options = (
+                prop::option::of($crate::text()),
+                prop::option::of($crate::text()),
+                prop::option::of(any::<u64>()),
+                prop::option::of($crate::mime_type()),
+                prop::option::of(annotations()),
+                meta(),
+                icons(),
+            )
+                .prop_map(
+                    |(title, description, size, mime_type, annotations, meta, icons)| {
+                        mcp::ResourceLinkOptions {
+                            title,
+                            description,
+                            size,
+                            mime_type,
+                            annotations,
+                            meta,
+                            icons,
+                        }
+                    },
+                );
+            ($crate::uri(), $crate::name(), prop::option::of(options))
+                .prop_map(|(uri, name, options)| mcp::ResourceLinkContent { uri, name, options })
+                .boxed()
+        }
+
+        pub fn resource_contents() -> BoxedStrategy<mcp::ResourceContents> {
+            prop_oneof![
+                (
+                    $crate::uri(),
+                    $crate::text(),
+                    prop::option::of(embedded_resource_options()),
+                )
+                    .prop_map(|(uri, text, options)| {
+                        mcp::ResourceContents::Text(mcp::TextResourceContents {
+                            uri,
+                            text: mcp::TextData::Text(text),
+                            options,
+                        })
+                    }),
+                (
+                    $crate::uri(),
+                    prop::collection::vec(any::<u8>(), 0..64),
+                    prop::option::of(embedded_resource_options()),
+                )
+                    .prop_map(|(uri, blob, options)| {
+                        mcp::ResourceContents::Blob(mcp::BlobResourceContents {
+                            uri,
+                            blob: mcp::BlobData::Blob(blob),
+                            options,
+                        })
+                    }),
+            ]
+            .boxed()
+        }
+
+        pub fn embedded_resource_content() -> BoxedStrategy<mcp::EmbeddedResourceContent> {
+            (resource_contents(), prop::option::of(content_options()))
+                .prop_map(|(resource, options)| mcp::EmbeddedResourceContent { resource, options })
+                .boxed()
+        }
+
+        pub fn content_block() -> BoxedStrategy<mcp::ContentBlock> {
+            prop_oneof![
+                text_content().prop_map(mcp::ContentBlock::Text),
+                blob().prop_map(mcp::ContentBlock::Image),
+                blob().prop_map(mcp::ContentBlock::Audio),
+                resource_link_content().prop_map(mcp::ContentBlock::ResourceLink),
+                embedded_resource_content().prop_map(mcp::ContentBlock::EmbeddedResource),
+            ]
+            .boxed()
+        }
+
+        // =====================================================================
+        // Client requests
+        // =====================================================================
+
+        pub fn implementation() -> BoxedStrategy<mcp::Implementation> {
+            (
+                $crate::name(),
+                prop::option::of($crate::text()),
+                "[0-9]{1,2}\\.[0-9]{1,2}\\.[0-9]{1,2}",
+                prop::option::of($crate::text()),
+                icons(),
+            )
+                .prop_map(
+                    |(name, title, version, description, icons)| mcp::Implementation {
+                        name,
+                        title,
+                        version,
+                        description,
+                        icons,
+                    },
+                )
+                .boxed()
+        }
+
+        pub fn client_capabilities() -> BoxedStrategy<mcp::ClientCapabilities> {
+            (
+                prop::option::of($crate::json_object()),
+                prop::option::of(prop::collection::vec(
+                    ($crate::name(), $crate::json_object()),
+                    0..3,
+                )),
+                // Roots, with or without listChanged
+                prop::option::of(any::<bool>()),
+                prop::option::of(
+                    (
+                        prop::option::of(any::<bool>()),
+                        prop::option::of(any::<bool>()),
+                    )
+                        .prop_map(|(tools, context)| mcp::SamplingCapabilities { tools, context }),
+                ),
+            )
+                .prop_map(
+                    |(elicitation, experimental, roots, sampling)| mcp::ClientCapabilities {
+                        elicitation,
+                        experimental,
+                        list_changed: roots
+                            .filter(|list_changed| *list_changed)
+                            .map(|_| mcp::ClientLists::ROOTS),
+                        sampling,
+                        lists: roots.map(|_| mcp::ClientLists::ROOTS),
+                    },
+                )
+                .boxed()
+        }
+
+        pub fn initialize_request() -> BoxedStrategy<mcp::InitializeRequest> {
+            (
+                client_capabilities(),
+                implementation(),
+                protocol_version(),
+                meta(),
+            )
+                .prop_map(|(capabilities, client_info, protocol_version, meta)| {
+                    mcp::InitializeRequest {
+                        capabilities,
+                        client_info,
+                        protocol_version,
+                        meta,
+                    }
+                })
+                .boxed()
+        }
+
+        pub fn cursor() -> BoxedStrategy<Option<String>> {
+            prop::option::of($crate::cursor()).boxed()
+        }
+
+        pub fn call_tool_request() -> BoxedStrategy<mcp::CallToolRequest> {
+            ($crate::name(), prop::option::of($crate::json_object()))
+                .prop_map(|(name, arguments)| mcp::CallToolRequest { name, arguments })
+                .boxed()
+        }
+
+        pub fn read_resource_request() -> BoxedStrategy<mcp::ReadResourceRequest> {
+            $crate::uri()
+                .prop_map(|uri| mcp::ReadResourceRequest { uri })
+                .boxed()
+        }
+
+        pub fn get_prompt_request() -> BoxedStrategy<mcp::GetPromptRequest> {
+            ($crate::name(), prop::option::of($crate::json_object()))
+                .prop_map(|(name, arguments)| mcp::GetPromptRequest { name, arguments })
+                .boxed()
+        }
+
+        pub fn complete_request() -> BoxedStrategy<mcp::CompleteRequest> {
+            let reference = prop_oneof![
+                ($crate::name(), prop::option::of($crate::text())).prop_map(|(name, title)| {
+                    mcp::CompletionReference::Prompt(mcp::CompletionPromptReference { name, title })
+                }),
+                $crate::uri().prop_map(mcp::CompletionReference::ResourceTemplate),
+            ];
+            (
+                ($crate::name(), $crate::text()),
+                reference,
+                prop::option::of(
+                    prop::option::of($crate::json_object())
+                        .prop_map(|arguments| mcp::CompletionContext { arguments }),
+                ),
+            )
+                .prop_map(|((name, value), ref_, context)| mcp::CompleteRequest {
+                    argument: mcp::CompletionArgument { name, value },
+                    ref_,
+                    context,
+                })
+                .boxed()
+        }
+
+        pub fn ping_request() -> BoxedStrategy<mcp::PingRequest> {
+            (
+                meta(),
+                prop::option::of(progress_token()),
+                prop::collection::vec(($crate::name(), $crate::json()), 0..3),
+            )
+                .prop_map(|(meta, progress_token, extras)| mcp::PingRequest {
+                    meta,
+                    progress_token,
+                    extras,
+                })
+                .boxed()
+        }
+
+        pub fn custom_request() -> BoxedStrategy<mcp::CustomRequest> {
+            (
+                "[a-z]{1,12}(/[a-z_]{1,12}){0,2}",
+                prop::option::of($crate::json_object()),
+            )
+                .prop_map(|(method, params)| mcp::CustomRequest { method, params })
+                .boxed()
+        }
+
+        pub fn client_request() -> BoxedStrategy<mcp::ClientRequest> {
+            prop_oneof![
+                initialize_request().prop_map(mcp::ClientRequest::Initialize),
+                (cursor(), meta()).prop_map(|(cursor, meta)| {
+                    mcp::ClientRequest::ToolsList(mcp::ListToolsRequest { cursor, meta })
+                }),
+                call_tool_request().prop_map(mcp::ClientRequest::ToolsCall),
+                cursor().prop_map(|cursor| {
+                    mcp::ClientRequest::ResourcesList(mcp::ListResourcesRequest { cursor })
+                }),
+                read_resource_request().prop_map(mcp::ClientRequest::ResourcesRead),
+                cursor().prop_map(|cursor| {
+                    mcp::ClientRequest::ResourcesTemplatesList(mcp::ListResourceTemplatesRequest {
+                        cursor,
+                    })
+                }),
+                cursor().prop_map(|cursor| {
+                    mcp::ClientRequest::PromptsList(mcp::ListPromptsRequest { cursor })
+                }),
+                get_prompt_request().prop_map(mcp::ClientRequest::PromptsGet),
+                complete_request().prop_map(mcp::ClientRequest::CompletionComplete),
+                log_level().prop_map(mcp::ClientRequest::LoggingSetLevel),
+                ping_request().prop_map(mcp::ClientRequest::Ping),
+                $crate::uri().prop_map(mcp::ClientRequest::ResourcesSubscribe),
+                $crate::uri().prop_map(mcp::ClientRequest::ResourcesUnsubscribe),
+                custom_request().prop_map(mcp::ClientRequest::Custom),
+                $crate::text().prop_map(mcp::ClientRequest::Unknown),
+            ]
+            .boxed()
+        }
+
+        // =====================================================================
+        // Server results
+        // =====================================================================
+
+        pub fn tool() -> BoxedStrategy<mcp::Tool> {
+            let annotations = (
+                prop::option::of($crate::text()),
+                prop::option::of(any::<bool>()),
+                prop::option::of(any::<bool>()),
+                prop::option::of(any::<bool>()),
+                prop::option::of(any::<bool>()),
+            )
+                .prop_map(
+                    |(
+                        title,
+                        read_only_hint,
+                        destructive_hint,
+                        idempotent_hint,
+                        open_world_hint,
+                    )| {
+                        mcp::ToolAnnotations {
+                            title,
+                            read_only_hint,
+                            destructive_hint,
+                            idempotent_hint,
+                            open_world_hint,
+                        }
+                    },
+                );
+            let options = (
+                meta(),
+                prop::option::of(annotations),
+                prop::option::of($crate::text()),
+                prop::option::of($crate::json_object()),
+                prop::option::of($crate::text()),
+                icons(),
+            )
+                .prop_map(
+                    |(meta, annotations, description, output_schema, title, icons)| {
+                        mcp::ToolOptions {
+                            meta,
+                            annotations,
+                            description,
+                            output_schema,
+                            title,
+                            icons,
+                        }
+                    },
+                );
+            (
+                $crate::name(),
+                $crate::json_object(),
+                prop::option::of(options),
+            )
+                .prop_map(|(name, input_schema, options)| mcp::Tool {
+                    name,
+                    input_schema,
+                    options,
+                })
+                .boxed()
+        }
+
+        pub fn mcp_resource() -> BoxedStrategy<mcp::McpResource> {
+            let options = (
+                prop::option::of(any::<u64>()),
+                prop::option::of($crate::text()),
+                prop::option::of($crate::text()),
+                prop::option::of($crate::mime_type()),
+                prop::option::of(annotations()),
+                meta(),
+                icons(),
+            )
+                .prop_map(
+                    |(size, title, description, mime_type, annotations, meta, icons)| {
+                        mcp::ResourceOptions {
+                            size,
+                            title,
+                            description,
+                            mime_type,
+                            annotations,
+                            meta,
+                            icons,
+                        }
+                    },
+                );
+            ($crate::uri(), $crate::name(), prop::option::of(options))
+                .prop_map(|(uri, name, options)| mcp::McpResource { uri, name, options })
+                .boxed()
+        }
+
+        pub fn resource_template() -> BoxedStrategy<mcp::ResourceTemplate> {
+            let options = (
+                prop::option::of($crate::text()),
+                prop::option::of($crate::text()),
+                prop::option::of($crate::mime_type()),
+                prop::option::of(annotations()),
+                meta(),
+                icons(),
+            )
+                .prop_map(
+                    |(description, title, mime_type, annotations, meta, icons)| {
+                        mcp::ResourceTemplateOptions {
+                            description,
+                            title,
+                            mime_type,
+                            annotations,
+                            meta,
+                            icons,
+                        }
+                    },
+                );
+            (
+                $crate::uri().prop_map(|uri| format!("{}/{{id}}", uri)),
+                $crate::name(),
+                prop::option::of(options),
+            )
+                .prop_map(|(uri_template, name, options)| mcp::ResourceTemplate {
+                    uri_template,
+                    name,
+                    options,
+                })
+                .boxed()
+        }
+
+        pub fn prompt() -> BoxedStrategy<mcp::Prompt> {
+            let argument = (
+                $crate::name(),
+                prop::option::of($crate::text()),
+                prop::option::of(any::<bool>()),
+                prop::option::of($crate::text()),
+            )
+                .prop_map(|(name, description, required, title)| mcp::PromptArgument {
+                    name,
+                    description,
+                    required,
+                    title,
+                });
+            let options = (
+                meta(),
+                prop::option::of(prop::collection::vec(argument, 0..3)),
+                prop::option::of($crate::text()),
+                prop::option::of($crate::text()),
+                icons(),
+            )
+                .prop_map(
+                    |(meta, arguments, description, title, icons)| mcp::PromptOptions {
+                        meta,
+                        arguments,
+                        description,
+                        title,
+                        icons,
+                    },
+                );
+            ($crate::name(), prop::option::of(options))
+                .prop_map(|(name, options)| mcp::Prompt { name, options })
+                .boxed()
+        }
+
+        pub fn server_capabilities() -> BoxedStrategy<mcp::ServerCapabilities> {
+            let lists = || {
+                prop::option::of((any::<bool>(), any::<bool>(), any::<bool>()).prop_map(
+                    |(tools, resources, prompts)| {
+                        let mut lists = mcp::ServerLists::empty();
+                        lists.set(mcp::ServerLists::TOOLS, tools);
+                        lists.set(mcp::ServerLists::RESOURCES, resources);
+                        lists.set(mcp::ServerLists::PROMPTS, prompts);
+                        lists
+                    },
+                ))
+            };
+            (
+                prop::option::of($crate::json_object()),
+                prop::option::of(prop::collection::vec(
+                    ($crate::name(), $crate::json_object()),
+                    0..3,
+                )),
+                prop::option::of($crate::json_object()),
+                lists(),
+                prop::option::of(any::<bool>().prop_map(|resources| {
+                    if resources {
+                        mcp::ServerSubscriptions::RESOURCES
+                    } else {
+                        mcp::ServerSubscriptions::empty()
+                    }
+                })),
+                lists(),
+            )
+                .prop_map(
+                    |(completions, experimental, logging, list_changed, subscriptions, lists)| {
+                        mcp::ServerCapabilities {
+                            completions,
+                            experimental,
+                            logging,
+                            list_changed,
+                            subscriptions,
+                            lists,
+                        }
+                    },
+                )
+                .boxed()
+        }
+
+        pub fn initialize_result() -> BoxedStrategy<mcp::InitializeResult> {
+            let options =
+                (prop::option::of($crate::text()), meta()).prop_map(|(instructions, meta)| {
+                    mcp::InitializeResultOptions { instructions, meta }
+                });
+            (
+                meta(),
+                implementation(),
+                server_capabilities(),
+                protocol_version(),
+                prop::option::of(options),
+            )
+                .prop_map(
+                    |(meta, server_info, capabilities, protocol_version, options)| {
+                        mcp::InitializeResult {
+                            meta,
+                            server_info,
+                            capabilities,
+                            protocol_version,
+                            options,
+                        }
+                    },
+                )
+                .boxed()
+        }
+
+        pub fn call_tool_result() -> BoxedStrategy<mcp::CallToolResult> {
+            (
+                meta(),
+                prop::collection::vec(content_block(), 0..4),
+                prop::option::of(any::<bool>()),
+                prop::option::of($crate::json_object()),
+            )
+                .prop_map(
+                    |(meta, content, is_error, structured_content)| mcp::CallToolResult {
+                        meta,
+                        content,
+                        is_error,
+                        structured_content,
+                    },
+                )
+                .boxed()
+        }
+
+        pub fn read_resource_result() -> BoxedStrategy<mcp::ReadResourceResult> {
+            (meta(), prop::collection::vec(resource_contents(), 0..4))
+                .prop_map(|(meta, contents)| mcp::ReadResourceResult { meta, contents })
+                .boxed()
+        }
+
+        pub fn get_prompt_result() -> BoxedStrategy<mcp::GetPromptResult> {
+            let message = (content_block(), role())
+                .prop_map(|(content, role)| mcp::PromptMessage { content, role });
+            (
+                meta(),
+                prop::option::of($crate::text()),
+                prop::collection::vec(message, 0..4),
+            )
+                .prop_map(|(meta, description, messages)| mcp::GetPromptResult {
+                    meta,
+                    description,
+                    messages,
+                })
+                .boxed()
+        }
+
+        pub fn complete_result() -> BoxedStrategy<mcp::CompleteResult> {
+            (
+                meta(),
+                prop::option::of(any::<bool>()),
+                prop::option::of(any::<u64>()),
+                prop::collection::vec($crate::text(), 0..4),
+            )
+                .prop_map(|(meta, has_more, total, values)| mcp::CompleteResult {
+                    meta,
+                    has_more,
+                    total,
+                    values,
+                })
+                .boxed()
+        }
+
+        pub fn server_result() -> BoxedStrategy<mcp::ServerResult> {
+            prop_oneof![
+                Just(()).prop_map(|()| mcp::ServerResult::Ping),
+                Just(()).prop_map(|()| mcp::ServerResult::LoggingSetLevel),
+                initialize_result().prop_map(mcp::ServerResult::Initialize),
+                (meta(), cursor(), prop::collection::vec(tool(), 0..4)).prop_map(
+                    |(meta, next_cursor, tools)| {
+                        mcp::ServerResult::ToolsList(mcp::ListToolsResult {
+                            meta,
+                            next_cursor,
+                            tools,
+                        })
+                    }
+                ),
+                call_tool_result().prop_map(mcp::ServerResult::ToolsCall),
+                (
+                    meta(),
+                    cursor(),
+                    prop::collection::vec(mcp_resource(), 0..4)
+                )
+                    .prop_map(|(meta, next_cursor, resources)| {
+                        mcp::ServerResult::ResourcesList(mcp::ListResourcesResult {
+                            meta,
+                            next_cursor,
+                            resources,
+                        })
+                    }),
+                read_resource_result().prop_map(mcp::ServerResult::ResourcesRead),
+                (
+                    meta(),
+                    cursor(),
+                    prop::collection::vec(resource_template(), 0..4)
+                )
+                    .prop_map(|(meta, next_cursor, resource_templates)| {
+                        mcp::ServerResult::ResourcesTemplatesList(
+                            mcp::ListResourceTemplatesResult {
+                                meta,
+                                next_cursor,
+                                resource_templates,
+                            },
+                        )
+                    }),
+                (meta(), cursor(), prop::collection::vec(prompt(), 0..4)).prop_map(
+                    |(meta, next_cursor, prompts)| {
+                        mcp::ServerResult::PromptsList(mcp::ListPromptsResult {
+                            meta,
+                            next_cursor,
+                            prompts,
+                        })
+                    }
+                ),
+                get_prompt_result().prop_map(mcp::ServerResult::PromptsGet),
+                complete_result().prop_map(mcp::ServerResult::CompletionComplete),
+                $crate::json().prop_map(mcp::ServerResult::Custom),
+            ]
+            .boxed()
+        }
+
+        // =====================================================================
+        // Notifications
+        // =====================================================================
+
+        pub fn notification_options() -> BoxedStrategy<mcp::NotificationOptions> {
+            (meta(), prop::option::of($crate::json_object()))
+                .prop_map(|(meta, extras)| mcp::NotificationOptions { meta, extras })
+                .boxed()
+        }
+
+        pub fn cancelled_notification() -> BoxedStrategy<mcp::CancelledNotification> {
+            (request_id(), prop::option::of($crate::text()))
+                .prop_map(|(request_id, reason)| mcp::CancelledNotification { request_id, reason })
+                .boxed()
+        }
+
+        pub fn progress_notification() -> BoxedStrategy<mcp::ProgressNotification> {
+            (
+                progress_token(),
+                0.0..1e6f64,
+                prop::option::of(0.0..1e6f64),
+                prop::option::of($crate::text()),
+            )
+                .prop_map(
+                    |(progress_token, progress, total, message)| mcp::ProgressNotification {
+                        progress_token,
+                        progress,
+                        total,
+                        message,
+                    },
+                )
+                .boxed()
+        }
+
+        pub fn server_notification() -> BoxedStrategy<mcp::ServerNotification> {
+            prop_oneof![
+                notification_options().prop_map(mcp::ServerNotification::ToolsListChanged),
+                notification_options().prop_map(mcp::ServerNotification::ResourcesListChanged),
+                (meta(), $crate::uri()).prop_map(|(meta, uri)| {
+                    mcp::ServerNotification::ResourcesUpdated(mcp::ResourceUpdatedNotification {
+                        meta,
+                        uri,
+                    })
+                }),
+                notification_options().prop_map(mcp::ServerNotification::PromptsListChanged),
+                (
+                    $crate::json(),
+                    log_level(),
+                    prop::option::of($crate::name())
+                )
+                    .prop_map(|(data, level, logger)| {
+                        mcp::ServerNotification::Log(mcp::LoggingMessageNotification {
+                            data,
+                            level,
+                            logger,
+                        })
+                    }),
+                cancelled_notification().prop_map(mcp::ServerNotification::Cancellation),
+                progress_notification().prop_map(mcp::ServerNotification::Progress),
+                $crate::text().prop_map(|elicitation_id| {
+                    mcp::ServerNotification::ElicitationComplete(
+                        mcp::ElicitationCompleteNotification { elicitation_id },
+                    )
+                }),
+                (
+                    request_id(),
+                    prop::collection::vec(content_block(), 0..4),
+                    meta(),
+                )
+                    .prop_map(|(request_id, content, meta)| {
+                        mcp::ServerNotification::ToolPartialResult(mcp::PartialResultNotification {
+                            request_id,
+                            content,
+                            meta,
+                        })
+                    }),
+            ]
+            .boxed()
+        }
+
+        pub fn client_notification() -> BoxedStrategy<mcp::ClientNotification> {
+            prop_oneof![
+                notification_options().prop_map(mcp::ClientNotification::Initialized),
+                notification_options().prop_map(mcp::ClientNotification::RootsListChanged),
+                cancelled_notification().prop_map(mcp::ClientNotification::Cancelled),
+                progress_notification().prop_map(mcp::ClientNotification::Progress),
+            ]
+            .boxed()
+        }
+
+        // =====================================================================
+        // Errors and messages
+        // =====================================================================
+
+        pub fn error() -> BoxedStrategy<mcp::Error> {
+            let code = prop_oneof![
+                prop::sample::select(vec![
+                    -32700i64, -32600, -32601, -32602, -32603, -32001, -32002, -32003, -32004,
+                    -32005, -32006,
+                ]),
+                any::<i64>(),
+            ];
+            (code, $crate::text(), prop::option::of($crate::json()))
+                .prop_map(|(code, message, data)| mcp::Error {
+                    code,
+                    message,
+                    data,
+                })
+                .boxed()
+        }
+
+        /// Any variant with any error; codes don't necessarily match variants
+        pub fn error_code() -> BoxedStrategy<mcp::ErrorCode> {
+            (0..8u8, error())
+                .prop_map(|(variant, e)| match variant {
+                    0 => mcp::ErrorCode::ParseError(e),
+                    1 => mcp::ErrorCode::InvalidRequest(e),
+                    2 => mcp::ErrorCode::MethodNotFound(e),
+                    3 => mcp::ErrorCode::InvalidParams(e),
+                    4 => mcp::ErrorCode::InternalError(e),
+                    5 => mcp::ErrorCode::Server(e),
+                    6 => mcp::ErrorCode::JsonRpc(e),
+                    _ => mcp::ErrorCode::Mcp(e),
+                })
+                .boxed()
+        }
+
+        /// Requests, errors and notifications (client results are not generated)
+        pub fn client_message() -> BoxedStrategy<mcp::ClientMessage> {
+            prop_oneof![
+                (request_id(), client_request()).prop_map(mcp::ClientMessage::Request),
+                (prop::option::of(request_id()), error_code()).prop_map(mcp::ClientMessage::Error),
+                client_notification().prop_map(mcp::ClientMessage::Notification),
+            ]
+            .boxed()
+        }
+
+        /// Results, errors and notifications (server requests are not generated)
+        pub fn server_message() -> BoxedStrategy<mcp::ServerMessage> {
+            prop_oneof![
+                (request_id(), server_result()).prop_map(mcp::ServerMessage::Result),
+                (prop::option::of(request_id()), error_code()).prop_map(mcp::ServerMessage::Error),
+                server_notification().prop_map(mcp::ServerMessage::Notification),
+            ]
+            .boxed()
+        }
+    };
+}
@@ -0,0 +1,64 @@
+//! Strategies for the WIT type aliases and plain strings
+//!
+//! `json`, `meta`, `uri` and `cursor` are all strings in the generated
+//! bindings, so these don't depend on any of them.
+
+use proptest::prelude::*;
+use serde_json::Value;
+
+/// Any JSON value, nested a few levels deep
+///
+/// Numbers stay finite, since NaN and infinities have no JSON encoding, and
+/// fractions have few enough digits to survive a parse round trip.
+pub fn json_value() -> impl Strategy<Value = Value> {
+    let leaf = prop_oneof![
+        Just(Value::Null),
+        any::<bool>().prop_map(Value::Bool),
+        any::<i64>().prop_map(Value::from),
+        (-100_000_000i64..100_000_000).prop_map(|n| Value::from(n as f64 / 100.0)),
+        text().prop_map(Value::String),
+    ];
+    leaf.prop_recursive(3, 32, 4, |inner| {
+        prop_oneof![
+            prop::collection::vec(inner.clone(), 0..4).prop_map(Value::Array),
+            prop::collection::btree_map(text(), inner, 0..4)
+                .prop_map(|members| Value::Object(members.into_iter().collect())),
+        ]
+    })
+}
+
+/// Any JSON document, encoded (`json` and `meta` fields)
+pub fn json() -> impl Strategy<Value = String> {
+    json_value().prop_map(|v| v.to_string())
+}
+
+/// A JSON object, encoded (tool and prompt arguments, `_meta`)
+pub fn json_object() -> impl Strategy<Value = String> {
+    prop::collection::btree_map(name(), json_value(), 0..4)
+        .prop_map(|members| Value::Object(members.into_iter().collect()).to_string())
+}
+
+/// Tool, prompt and argument names
+pub fn name() -> impl Strategy<Value = String> {
+    "[a-zA-Z_][a-zA-Z0-9_-]{0,23}"
+}
+
+/// Free text, including control characters, quotes and non-ASCII
+pub fn text() -> impl Strategy<Value = String> {
+    prop::collection::vec(any::<char>(), 0..32).prop_map(String::from_iter)
+}
+
+/// Resource URIs
+pub fn uri() -> impl Strategy<Value = String> {
+    "(file|https|mem)://[a-z0-9._-]{1,12}(/[a-zA-Z0-9._%-]{0,12}){0,3}"
+}
+
+/// Pagination cursors (opaque to clients)
+pub fn cursor() -> impl Strategy<Value = String> {
+    "[A-Za-z0-9+/=]{1,24}"
+}
+
+/// MIME types
+pub fn mime_type() -> impl Strategy<Value = String> {
+    "(text|image|audio|application)/[a-z0-9.+-]{1,16}"
+}
@@ -0,0 +1,71 @@
+use proptest::prelude::*;
+use serde_json::Value;
+
+mod bindings {
+    wit_bindgen::generate!({
+        world: "fixtures",
+        generate_all,
+        generate_unused_types: true,
+    });
+}
+
+mod strategies {
+    crate::mcp_strategies!(super::bindings::wasmcp::mcp_v20251125::mcp);
+}
+
+use bindings::wasmcp::mcp_v20251125::mcp::*;
+
+fn is_json_object(s: &str) -> bool {
+    matches!(serde_json::from_str::<Value>(s), Ok(Value::Object(_)))
+}
+
+proptest! {
+    #[test]
+    fn test_json_round_trips(value in crate::json_value()) {
+        let parsed: Value = serde_json::from_str(&value.to_string()).unwrap();
+        prop_assert_eq!(parsed, value);
+    }
+
+    #[test]
+    fn test_tool_arguments_are_objects(request in strategies::call_tool_request()) {
+        prop_assert!(!request.name.is_empty());
+        if let Some(arguments) = &request.arguments {
+            prop_assert!(is_json_object(arguments));
+        }
+    }
+
+    #[test]
+    fn test_content_is_inline(block in strategies::content_block()) {
+        let inline = match &block {
+            ContentBlock::Text(t) => matches!(t.text, TextData::Text(_)),
+            ContentBlock::Image(b) | ContentBlock::Audio(b) => matches!(b.data, BlobData::Blob(_)),
+            ContentBlock::ResourceLink(_) => true,
+            ContentBlock::EmbeddedResource(e) => match &e.resource {
+                ResourceContents::Text(t) => matches!(t.text, TextData::Text(_)),
+                ResourceContents::Blob(b) => matches!(b.blob, BlobData::Blob(_)),
+            },
+        };
+        prop_assert!(inline);
+    }
+
+    #[test]
+    fn test_meta_is_json(notification in strategies::server_notification()) {
+        let meta = match &notification {
+            ServerNotification::ToolsListChanged(o)
+            | ServerNotification::ResourcesListChanged(o)
+            | ServerNotification::PromptsListChanged(o) => o.meta.clone(),
+            ServerNotification::ResourcesUpdated(n) => n.meta.clone(),
+            ServerNotification::ToolPartialResult(n) => n.meta.clone(),
+            _ => None,
+        };
+        if let Some(meta) = meta {
+            prop_assert!(is_json_object(&meta));
+        }
+    }
+
+    #[test]
+    fn test_messages_generate(
+        _client in strategies::client_message(),
+        _server in strategies::server_message(),
+    ) {}
+}
@@ -0,0 +1,442 @@
+//! Outbound HTTP client for wasmcp components
+//!
+//! Components calling upstreams each hand-rolled the same wasi:http request
+//! code. This crate puts the policies around it in one place:
+//! - Per-attempt timeouts, capped by the caller's deadline
+//! - Retries with exponential backoff for failed exchanges and retryable
+//!   statuses (honoring `Retry-After`), for idempotent methods by default
+//! - Default headers sent with every request
+//! - gzip: `accept-encoding: gzip` and transparent decoding
+//! - Trace context: the caller's `traceparent` forwarded upstream
+//! - Egress: the host checked against `WASMCP_EGRESS_ALLOW` (see
+//!   `wasmcp-egress`) before anything is sent
+//!
+//! The exchange itself is pluggable (`Transport`): `impl_wasi_transport!`
+//! generates one over a crate's own wasi:http bindings, with `wasi_client`
+//! returning a client that enforces the component's egress policy, and
+//! closures work in tests.
+//!
+//! ```ignore
+//! wasmcp_http_client::impl_wasi_transport!(crate::bindings::wasi);
+//!
+//! let mut client = wasi_client("openapi-tools", ctx.deadline)
+//!     .with_timeout(Duration::from_secs(5))
+//!     .with_retry(RetryPolicy::default())
+//!     .with_header("authorization", format!("Bearer {}", token))
+//!     .with_gzip(true)
+//!     .with_traceparent(ctx.trace_context.clone());
+//! let response = client.get("https://api.example.com/items")?;
+//! ```
+
+mod macros;
+mod retry;
+
+pub use retry::RetryPolicy;
+pub use wasmcp_egress::{Egress, EgressViolation};
+
+use flate2::read::GzDecoder;
+use std::fmt;
+use std::io::Read;
+use std::time::Duration;
+
+/// Default cap on response bodies, after decoding (10MB)
+pub const DEFAULT_MAX_RESPONSE_BYTES: u64 = 10 * 1024 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl Request {
+    pub fn new(method: impl Into<String>, url: impl Into<String>) -> Self {
+        Request {
+            method: method.into().to_ascii_uppercase(),
+            url: url.into(),
+            headers: Vec::new(),
+            body: Vec::new(),
+        }
+    }
+
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    pub fn with_body(mut self, body: impl Into<Vec<u8>>) -> Self {
+        self.body = body.into();
+        self
+    }
+
+    /// Whether the request carries a header (case-insensitive)
+    pub fn has_header(&self, name: &str) -> bool {
+        self.headers
+            .iter()
+            .any(|(n, _)| n.eq_ignore_ascii_case(name))
+    }
+
+    /// Whether sending the request twice has the same effect as once
+    pub fn is_idempotent(&self) -> bool {
+        matches!(
+            self.method.as_str(),
+            "GET" | "HEAD" | "OPTIONS" | "TRACE" | "PUT" | "DELETE"
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    /// Header names are lowercased
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    /// First value of a header (name must be lowercase)
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
+
+/// Limits for one attempt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SendOptions {
+    /// Time allowed for the attempt; None leaves host defaults in place
+    pub timeout: Option<Duration>,
+    /// Largest body to read; larger bodies fail the attempt
+    pub max_bytes: u64,
+}
+
+/// Performs one blocking HTTP exchange
+pub trait Transport {
+    fn send(&mut self, request: &Request, options: &SendOptions) -> Result<Response, String>;
+
+    /// Wait before a retry
+    ///
+    /// Fails when waiting would leave no time for another attempt.
+    fn sleep(&mut self, _duration: Duration) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+impl<F> Transport for F
+where
+    F: FnMut(&Request, &SendOptions) -> Result<Response, String>,
+{
+    fn send(&mut self, request: &Request, options: &SendOptions) -> Result<Response, String> {
+        self(request, options)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HttpError {
+    /// No exchange succeeded; the message is from the last attempt
+    Transport { attempts: u32, message: String },
+    /// The response body could not be decoded
+    Decode(String),
+    /// The URL could not be checked against the egress policy
+    InvalidUrl(String),
+    /// The egress policy blocked the request; nothing was sent
+    Blocked(EgressViolation),
+}
+
+impl fmt::Display for HttpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HttpError::Transport {
+                attempts: 1,
+                message,
+            } => write!(f, "{}", message),
+            HttpError::Transport { attempts, message } => {
+                write!(f, "{} (after {} attempts)", message, attempts)
+            }
+            HttpError::Decode(message) | HttpError::InvalidUrl(message) => {
+                write!(f, "{}", message)
+            }
+            HttpError::Blocked(violation) => write!(f, "{}", violation),
+        }
+    }
+}
+
+impl std::error::Error for HttpError {}
+
+pub struct HttpClient<T> {
+    transport: T,
+    timeout: Option<Duration>,
+    retry: RetryPolicy,
+    headers: Vec<(String, String)>,
+    gzip: bool,
+    traceparent: Option<String>,
+    max_response_bytes: u64,
+    egress: Option<Egress>,
+}
+
+impl<T: Transport> HttpClient<T> {
+    /// Client without timeout, retries, gzip, trace context or egress policy
+    pub fn new(transport: T) -> Self {
+        HttpClient {
+            transport,
+            timeout: None,
+            retry: RetryPolicy::none(),
+            headers: Vec::new(),
+            gzip: false,
+            traceparent: None,
+            max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+            egress: None,
+        }
+    }
+
+    /// Time allowed for each attempt
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Add a header sent with every request, unless the request sets it
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Ask for gzip responses and decode them
+    pub fn with_gzip(mut self, gzip: bool) -> Self {
+        self.gzip = gzip;
+        self
+    }
+
+    /// Forward the caller's W3C `traceparent` (e.g. MessageContext's trace context)
+    pub fn with_traceparent(mut self, traceparent: Option<String>) -> Self {
+        self.traceparent = traceparent.filter(|t| !t.trim().is_empty());
+        self
+    }
+
+    /// Largest response body, after decoding
+    pub fn with_max_response_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_response_bytes = max_bytes;
+        self
+    }
+
+    /// Check every request's host against an egress policy before sending
+    pub fn with_egress(mut self, egress: Egress) -> Self {
+        self.egress = Some(egress);
+        self
+    }
+
+    pub fn get(&mut self, url: &str) -> Result<Response, HttpError> {
+        self.send(Request::new("GET", url))
+    }
+
+    pub fn post(
+        &mut self,
+        url: &str,
+        content_type: &str,
+        body: impl Into<Vec<u8>>,
+    ) -> Result<Response, HttpError> {
+        self.send(
+            Request::new("POST", url)
+                .with_header("content-type", content_type)
+                .with_body(body),
+        )
+    }
+
+    /// Send a request, retrying as the policy allows
+    ///
+    /// Non-2xx responses are returned, not errors: callers decide what a
+    /// status means. Only a failed exchange, undecodable body or blocked
+    /// host is an error.
+    pub fn send(&mut self, request: Request) -> Result<Response, HttpError> {
+        self.authorize(&request)?;
+        let request = self.prepare(request);
+        let options = SendOptions {
+            timeout: self.timeout,
+            max_bytes: self.max_response_bytes,
+        };
+        let retryable = request.is_idempotent() || self.retry.retry_non_idempotent;
+
+        let mut attempt = 1;
+        loop {
+            let result = self.transport.send(&request, &options);
+            let last = !retryable || attempt >= self.retry.max_attempts;
+
+            let wait = match &result {
+                _ if last => None,
+                Ok(response) if self.retry.retry_statuses.contains(&response.status) => {
+                    // A server asking for a longer pause than we allow gets its answer back
+                    match retry::retry_after(response) {
+                        Some(wait) if wait > self.retry.max_backoff => None,
+                        Some(wait) => Some(wait),
+                        None => Some(self.retry.backoff(attempt)),
+                    }
+                }
+                Ok(_) => None,
+                Err(_) => Some(self.retry.backoff(attempt)),
+            };
+
+            match wait {
+                Some(wait) if self.transport.sleep(wait).is_ok() => attempt += 1,
+                _ => {
+                    return match result {
+                        Ok(response) => self.decode(response),
+                        Err(message) => Err(HttpError::Transport {
+                            attempts: attempt,
+                            message,
+                        }),
+                    };
+                }
+            }
+        }
+    }
+
+    /// Check the request's host against the egress policy, if any
+    fn authorize(&self, request: &Request) -> Result<(), HttpError> {
+        let Some(egress) = &self.egress else {
+            return Ok(());
+        };
+        let (scheme, authority, _) = split_url(&request.url).map_err(HttpError::InvalidUrl)?;
+        let (host, port) = host_and_port(scheme, authority).map_err(HttpError::InvalidUrl)?;
+        egress
+            .authorize(&request.method, scheme, &host, port)
+            .map_err(HttpError::Blocked)
+    }
+
+    /// Add default headers, `accept-encoding` and `traceparent`
+    fn prepare(&self, mut request: Request) -> Request {
+        for (name, value) in &self.headers {
+            if !request.has_header(name) {
+                request.headers.push((name.clone(), value.clone()));
+            }
+        }
+        if self.gzip && !request.has_header("accept-encoding") {
+            request
+                .headers
+                .push(("accept-encoding".to_string(), "gzip".to_string()));
+        }
+        if let Some(traceparent) = &self.traceparent
+            && !request.has_header("traceparent")
+        {
+            request
+                .headers
+                .push(("traceparent".to_string(), traceparent.clone()));
+        }
+        request
+    }
+
+    /// Decode a gzip body, so callers always see the representation
+    fn decode(&self, mut response: Response) -> Result<Response, HttpError> {
+        let gzipped = response
+            .header("content-encoding")
+            .is_some_and(|encoding| encoding.trim().eq_ignore_ascii_case("gzip"));
+        if !gzipped || response.body.is_empty() {
+            return Ok(response);
+        }
+
+        // Read one byte past the limit to tell a full body from an oversized one
+        let mut decoded = Vec::new();
+        GzDecoder::new(response.body.as_slice())
+            .take(self.max_response_bytes.saturating_add(1))
+            .read_to_end(&mut decoded)
+            .map_err(|e| HttpError::Decode(format!("Invalid gzip response body: {}", e)))?;
+        if decoded.len() as u64 > self.max_response_bytes {
+            return Err(HttpError::Decode(format!(
+                "Decoded response exceeds the {} byte limit",
+                self.max_response_bytes
+            )));
+        }
+
+        response.body = decoded;
+        response
+            .headers
+            .retain(|(name, _)| name != "content-encoding" && name != "content-length");
+        Ok(response)
+    }
+}
+
+/// Split an absolute URL into scheme, authority and path with query
+#[doc(hidden)]
+pub fn split_url(url: &str) -> Result<(&str, &str, String), String> {
+    let (scheme, rest) = url
+        .split_once("://")
+        .ok_or_else(|| format!("Invalid URL '{}'", url))?;
+    let rest = rest.split('#').next().unwrap_or_default();
+    let (authority, path) = match rest.find(['/', '?']) {
+        Some(i) => (&rest[..i], &rest[i..]),
+        None => (rest, ""),
+    };
+    if authority.is_empty() {
+        return Err(format!("No host in URL: {}", url));
+    }
+    let path = if path.starts_with('/') {
+        path.to_string()
+    } else {
+        format!("/{}", path)
+    };
+    Ok((scheme, authority, path))
+}
+
+/// Host and port a URL's authority connects to
+///
+/// The host is lowercased, IPv6 literals keep their brackets, and the port
+/// defaults to the scheme's. Authorities with credentials are refused: the
+/// host they name is easy to misread.
+#[doc(hidden)]
+pub fn host_and_port(scheme: &str, authority: &str) -> Result<(String, u16), String> {
+    if authority.contains('@') {
+        return Err(format!(
+            "Credentials in URLs are not supported: {}",
+            authority
+        ));
+    }
+    let (host, port) = match authority.strip_prefix('[') {
+        Some(rest) => {
+            let end = rest
+                .find(']')
+                .ok_or_else(|| format!("Invalid IPv6 host: {}", authority))?;
+            let port = &rest[end + 1..];
+            let port = match port {
+                "" => None,
+                _ => Some(
+                    port.strip_prefix(':')
+                        .ok_or_else(|| format!("Invalid host: {}", authority))?,
+                ),
+            };
+            (&authority[..end + 2], port)
+        }
+        None => match authority.rsplit_once(':') {
+            Some((host, port)) => (host, Some(port)),
+            None => (authority, None),
+        },
+    };
+
+    let port = match port {
+        Some(port) => port
+            .parse::<u16>()
+            .map_err(|_| format!("Invalid port in {}", authority))?,
+        None => match scheme {
+            "https" => 443,
+            "http" => 80,
+            s => return Err(format!("Unsupported URL scheme: {}", s)),
+        },
+    };
+    if host.is_empty() {
+        return Err(format!("No host in {}", authority));
+    }
+    Ok((host.to_ascii_lowercase(), port))
+}
+
+#[cfg(test)]
+mod tests;
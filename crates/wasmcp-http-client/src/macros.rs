@@ -0,0 +1,216 @@
+//! wasi:http transport for generated bindings
+
+/// Define `WasiTransport`, a `Transport` over a crate's generated wasi:http
+/// bindings
+///
+/// Takes the path to the crate's `wasi` bindings module, whose world must
+/// import `wasi:http/outgoing-handler` and `wasi:cli/environment`. Each
+/// attempt's timeouts are capped at the time left before `deadline` (a
+/// monotonic-clock instant), and retries that would start after it are not
+/// attempted.
+///
+/// Also defines `wasi_client(component, deadline)`, an `HttpClient` over the
+/// transport that enforces the component's egress policy. Components create
+/// their clients with it, so no request skips the allow-list.
+#[macro_export]
+macro_rules! impl_wasi_transport {
+    ($($wasi:tt)+) => {
+        /// HTTP client over wasi:http, checking hosts against the egress
+        /// policy in the component's environment
+        ///
+        /// `component` names the component in audit lines.
+        pub fn wasi_client(
+            component: &str,
+            deadline: ::core::option::Option<u64>,
+        ) -> $crate::HttpClient<WasiTransport> {
+            let vars = $($wasi)+::cli::environment::get_environment();
+            $crate::HttpClient::new(WasiTransport { deadline })
+                .with_egress($crate::Egress::from_env(component, &vars))
+        }
+
+        /// wasi:http transport for `wasmcp_http_client::HttpClient`
+        pub struct WasiTransport {
+            /// Monotonic-clock instant requests must finish by
+            pub deadline: Option<u64>,
+        }
+
+        impl $crate::Transport for WasiTransport {
+            fn send(
+                &mut self,
+                request: &$crate::Request,
+                options: &$crate::SendOptions,
+            ) -> ::core::result::Result<$crate::Response, ::std::string::String> {
+                use $($wasi)+::clocks::monotonic_clock;
+                use $($wasi)+::http::outgoing_handler;
+                use $($wasi)+::http::types::{
+                    Fields, Method, OutgoingBody, OutgoingRequest, RequestOptions, Scheme,
+                };
+                use $($wasi)+::io::poll;
+                use $($wasi)+::io::streams::StreamError;
+
+                // This attempt ends at its timeout or the deadline, whichever is first
+                let now = monotonic_clock::now();
+                let timeout_at = options
+                    .timeout
+                    .map(|t| now.saturating_add(u64::try_from(t.as_nanos()).unwrap_or(u64::MAX)));
+                let attempt_deadline = match (timeout_at, self.deadline) {
+                    (Some(a), Some(b)) => Some(a.min(b)),
+                    (a, b) => a.or(b),
+                };
+
+                let (scheme, authority, path) = $crate::split_url(&request.url)?;
+                let scheme = match scheme {
+                    "https" => Scheme::Https,
+                    "http" => Scheme::Http,
+                    s => return Err(format!("Unsupported URL scheme: {}", s)),
+                };
+                let method = match request.method.as_str() {
+                    "GET" => Method::Get,
+                    "HEAD" => Method::Head,
+                    "POST" => Method::Post,
+                    "PUT" => Method::Put,
+                    "DELETE" => Method::Delete,
+                    "OPTIONS" => Method::Options,
+                    "TRACE" => Method::Trace,
+                    "PATCH" => Method::Patch,
+                    other => Method::Other(other.to_string()),
+                };
+
+                let fields = Fields::new();
+                for (name, value) in &request.headers {
+                    fields
+                        .append(name, value.as_bytes())
+                        .map_err(|_| format!("Failed to set {} header", name))?;
+                }
+
+                let outgoing = OutgoingRequest::new(fields);
+                outgoing
+                    .set_method(&method)
+                    .map_err(|_| format!("Failed to set {} method", request.method))?;
+                outgoing
+                    .set_scheme(Some(&scheme))
+                    .map_err(|_| "Failed to set scheme".to_string())?;
+                outgoing
+                    .set_authority(Some(authority))
+                    .map_err(|_| "Failed to set authority".to_string())?;
+                outgoing
+                    .set_path_with_query(Some(&path))
+                    .map_err(|_| "Failed to set path".to_string())?;
+
+                let outgoing_body = outgoing
+                    .body()
+                    .map_err(|_| "Failed to get request body".to_string())?;
+                let stream = outgoing_body
+                    .write()
+                    .map_err(|_| "Failed to get request body stream".to_string())?;
+                // blocking-write-and-flush takes at most 4096 bytes per call
+                for chunk in request.body.chunks(4096) {
+                    stream
+                        .blocking_write_and_flush(chunk)
+                        .map_err(|e| format!("Failed to write request body: {:?}", e))?;
+                }
+                drop(stream);
+                OutgoingBody::finish(outgoing_body, None)
+                    .map_err(|_| "Failed to finish request body".to_string())?;
+
+                let request_options = match attempt_deadline {
+                    Some(deadline) => {
+                        let remaining = deadline.saturating_sub(monotonic_clock::now());
+                        if remaining == 0 {
+                            return Err("Request deadline exceeded before the request".to_string());
+                        }
+                        // Hosts may not support every timeout; unsupported ones are left unset
+                        let request_options = RequestOptions::new();
+                        let _ = request_options.set_connect_timeout(Some(remaining));
+                        let _ = request_options.set_first_byte_timeout(Some(remaining));
+                        let _ = request_options.set_between_bytes_timeout(Some(remaining));
+                        Some(request_options)
+                    }
+                    None => None,
+                };
+                let future_response = outgoing_handler::handle(outgoing, request_options)
+                    .map_err(|e| format!("Request failed: {:?}", e))?;
+
+                // Also wake at the deadline, in case the host ignores the timeouts
+                let pollable = future_response.subscribe();
+                match attempt_deadline {
+                    Some(deadline) => {
+                        let timer = monotonic_clock::subscribe_instant(deadline);
+                        poll::poll(&[&pollable, &timer]);
+                    }
+                    None => {
+                        poll::poll(&[&pollable]);
+                    }
+                }
+                drop(pollable);
+
+                let response = future_response
+                    .get()
+                    .ok_or_else(|| format!("Request timed out waiting for {}", request.url))?
+                    .map_err(|e| format!("Future error: {:?}", e))?
+                    .map_err(|e| format!("HTTP error: {:?}", e))?;
+
+                let status = response.status();
+                let headers = response
+                    .headers()
+                    .entries()
+                    .into_iter()
+                    .map(|(name, value)| {
+                        (
+                            name.to_lowercase(),
+                            String::from_utf8_lossy(&value).to_string(),
+                        )
+                    })
+                    .collect();
+
+                let body = response
+                    .consume()
+                    .map_err(|_| "Failed to get response body".to_string())?;
+                let stream = body
+                    .stream()
+                    .map_err(|_| "Failed to get response stream".to_string())?;
+                let mut bytes = Vec::new();
+                loop {
+                    match stream.blocking_read(4096) {
+                        Ok(chunk) if chunk.is_empty() => break,
+                        Ok(chunk) => bytes.extend_from_slice(&chunk),
+                        Err(StreamError::Closed) => break,
+                        Err(e) => return Err(format!("Failed to read response body: {:?}", e)),
+                    }
+                    if attempt_deadline.is_some_and(|d| monotonic_clock::now() >= d) {
+                        return Err(format!("Request timed out reading {}", request.url));
+                    }
+                    if bytes.len() as u64 > options.max_bytes {
+                        return Err(format!(
+                            "Response from {} exceeds the {} byte limit",
+                            request.url, options.max_bytes
+                        ));
+                    }
+                }
+
+                Ok($crate::Response {
+                    status,
+                    headers,
+                    body: bytes,
+                })
+            }
+
+            fn sleep(
+                &mut self,
+                duration: ::std::time::Duration,
+            ) -> ::core::result::Result<(), ::std::string::String> {
+                use $($wasi)+::clocks::monotonic_clock;
+
+                let nanos = u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX);
+                if self
+                    .deadline
+                    .is_some_and(|d| monotonic_clock::now().saturating_add(nanos) >= d)
+                {
+                    return Err("No time left before the deadline to retry".to_string());
+                }
+                monotonic_clock::subscribe_duration(nanos).block();
+                Ok(())
+            }
+        }
+    };
+}
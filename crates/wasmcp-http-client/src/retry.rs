@@ -0,0 +1,59 @@
+//! Retry policy
+//!
+//! Backoff doubles from `initial_backoff` up to `max_backoff`, without
+//! jitter: components have no shared randomness source, and the number of
+//! attempts is small enough that synchronized retries stay cheap.
+
+use crate::Response;
+use std::time::Duration;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Attempts in total, including the first (1 = never retry)
+    pub max_attempts: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    /// Statuses worth another attempt; other responses are returned as-is
+    pub retry_statuses: Vec<u16>,
+    /// Also retry methods that are not idempotent (e.g. POST)
+    pub retry_non_idempotent: bool,
+}
+
+impl RetryPolicy {
+    /// Policy that sends each request once
+    pub fn none() -> Self {
+        RetryPolicy {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Wait after failed attempt number `attempt` (starting at 1)
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+impl Default for RetryPolicy {
+    /// 3 attempts, 100ms then 200ms apart, on 429, 502, 503 and 504
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(2),
+            retry_statuses: vec![429, 502, 503, 504],
+            retry_non_idempotent: false,
+        }
+    }
+}
+
+/// `Retry-After` in seconds; HTTP dates are ignored (no wall clock here)
+pub(crate) fn retry_after(response: &Response) -> Option<Duration> {
+    response
+        .header("retry-after")
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .map(Duration::from_secs)
+}
@@ -0,0 +1,201 @@
+use super::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+fn response(status: u16, headers: &[(&str, &str)], body: &[u8]) -> Response {
+    Response {
+        status,
+        headers: headers
+            .iter()
+            .map(|(n, v)| (n.to_string(), v.to_string()))
+            .collect(),
+        body: body.to_vec(),
+    }
+}
+
+/// Headers of each request a scripted transport received
+type SentHeaders = Rc<RefCell<Vec<Vec<(String, String)>>>>;
+
+/// A transport replying with `replies` in order, recording the headers sent
+fn scripted(
+    replies: Vec<Result<Response, String>>,
+) -> (
+    impl FnMut(&Request, &SendOptions) -> Result<Response, String>,
+    SentHeaders,
+) {
+    let sent = Rc::new(RefCell::new(Vec::new()));
+    let log = sent.clone();
+    let mut replies = replies.into_iter();
+    let transport = move |request: &Request, _: &SendOptions| {
+        log.borrow_mut().push(request.headers.clone());
+        replies.next().expect("unexpected request")
+    };
+    (transport, sent)
+}
+
+#[test]
+fn test_retries_idempotent_requests() {
+    let (transport, sent) = scripted(vec![
+        Err("connection reset".to_string()),
+        Ok(response(503, &[], b"")),
+        Ok(response(200, &[], b"ok")),
+    ]);
+    let mut client = HttpClient::new(transport).with_retry(RetryPolicy::default());
+    assert_eq!(
+        client.get("https://api.example.com/items").unwrap().status,
+        200
+    );
+    assert_eq!(sent.borrow().len(), 3);
+
+    // The last response is returned when attempts run out
+    let (transport, _) = scripted(vec![
+        Ok(response(503, &[], b"")),
+        Ok(response(503, &[], b"")),
+        Ok(response(503, &[], b"")),
+    ]);
+    let mut client = HttpClient::new(transport).with_retry(RetryPolicy::default());
+    assert_eq!(client.get("https://api.example.com/").unwrap().status, 503);
+}
+
+#[test]
+fn test_no_retry_for_post_or_long_retry_after() {
+    let (transport, sent) = scripted(vec![Err("connection reset".to_string())]);
+    let mut client = HttpClient::new(transport).with_retry(RetryPolicy::default());
+    let error = client
+        .post("https://api.example.com/items", "application/json", "{}")
+        .unwrap_err();
+    assert_eq!(error.to_string(), "connection reset");
+    assert_eq!(sent.borrow().len(), 1);
+
+    let (transport, sent) = scripted(vec![Ok(response(429, &[("retry-after", "60")], b""))]);
+    let mut client = HttpClient::new(transport).with_retry(RetryPolicy::default());
+    assert_eq!(client.get("https://api.example.com/").unwrap().status, 429);
+    assert_eq!(sent.borrow().len(), 1);
+}
+
+#[test]
+fn test_backoff() {
+    let policy = RetryPolicy::default();
+    assert_eq!(policy.backoff(1), Duration::from_millis(100));
+    assert_eq!(policy.backoff(2), Duration::from_millis(200));
+    assert_eq!(policy.backoff(10), Duration::from_secs(2));
+    assert_eq!(policy.backoff(u32::MAX), Duration::from_secs(2));
+}
+
+#[test]
+fn test_headers() {
+    let (transport, sent) = scripted(vec![Ok(response(200, &[], b""))]);
+    let traceparent = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+    let mut client = HttpClient::new(transport)
+        .with_header("authorization", "Bearer default")
+        .with_header("user-agent", "wasmcp")
+        .with_gzip(true)
+        .with_traceparent(Some(traceparent.to_string()));
+    client
+        .send(
+            Request::new("get", "https://api.example.com/")
+                .with_header("Authorization", "Bearer own"),
+        )
+        .unwrap();
+
+    let sent = sent.borrow();
+    let header = |name: &str| {
+        sent[0]
+            .iter()
+            .filter(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(header("authorization"), ["Bearer own"]);
+    assert_eq!(header("user-agent"), ["wasmcp"]);
+    assert_eq!(header("accept-encoding"), ["gzip"]);
+    assert_eq!(header("traceparent"), [traceparent]);
+}
+
+#[test]
+fn test_gzip_decoding() {
+    // gzip of {"ok":true}
+    let gzipped = [
+        31, 139, 8, 0, 0, 0, 0, 0, 2, 3, 171, 86, 202, 207, 86, 178, 42, 41, 42, 77, 173, 5, 0,
+        144, 95, 212, 167, 11, 0, 0, 0,
+    ];
+    let (transport, _) = scripted(vec![Ok(response(
+        200,
+        &[("content-encoding", "gzip"), ("content-length", "31")],
+        &gzipped,
+    ))]);
+    let mut client = HttpClient::new(transport).with_gzip(true);
+    let decoded = client.get("https://api.example.com/").unwrap();
+    assert_eq!(decoded.body, br#"{"ok":true}"#);
+    assert_eq!(decoded.header("content-encoding"), None);
+
+    let (transport, _) = scripted(vec![Ok(response(
+        200,
+        &[("content-encoding", "gzip")],
+        &gzipped,
+    ))]);
+    let mut client = HttpClient::new(transport).with_max_response_bytes(4);
+    assert!(matches!(
+        client.get("https://api.example.com/"),
+        Err(HttpError::Decode(_))
+    ));
+}
+
+#[test]
+fn test_split_url() {
+    assert_eq!(
+        split_url("https://api.example.com:8443/v1/items?q=1#frag").unwrap(),
+        ("https", "api.example.com:8443", "/v1/items?q=1".to_string())
+    );
+    assert_eq!(
+        split_url("http://localhost?x").unwrap(),
+        ("http", "localhost", "/?x".to_string())
+    );
+    assert!(split_url("https:///path").is_err());
+}
+
+#[test]
+fn test_host_and_port() {
+    assert_eq!(
+        host_and_port("https", "API.example.com").unwrap(),
+        ("api.example.com".to_string(), 443)
+    );
+    assert_eq!(
+        host_and_port("http", "localhost:8080").unwrap(),
+        ("localhost".to_string(), 8080)
+    );
+    assert_eq!(
+        host_and_port("http", "[::1]:3000").unwrap(),
+        ("[::1]".to_string(), 3000)
+    );
+    assert_eq!(
+        host_and_port("https", "[::1]").unwrap(),
+        ("[::1]".to_string(), 443)
+    );
+    assert!(host_and_port("https", "user@evil.example").is_err());
+    assert!(host_and_port("https", "api.example.com:http").is_err());
+    assert!(host_and_port("ftp", "files.example.com").is_err());
+}
+
+#[test]
+fn test_egress_blocks_before_sending() {
+    let policy = wasmcp_egress::EgressPolicy::parse("api.example.com").unwrap();
+    let egress = Egress::new("test", policy, false);
+
+    let (transport, sent) = scripted(vec![Ok(response(200, &[], b"ok"))]);
+    let mut client = HttpClient::new(transport)
+        .with_retry(RetryPolicy::default())
+        .with_egress(egress);
+    assert!(matches!(
+        client.get("https://evil.example/items"),
+        Err(HttpError::Blocked(_))
+    ));
+    assert!(matches!(
+        client.get("https://api.example.com:8443/"),
+        Err(HttpError::Blocked(_))
+    ));
+    assert!(sent.borrow().is_empty());
+
+    assert_eq!(client.get("https://api.example.com/").unwrap().status, 200);
+    assert_eq!(sent.borrow().len(), 1);
+}
@@ -0,0 +1,34 @@
+//! Partial tool results
+//!
+//! Long-running tools can send output as they produce it, ahead of the final
+//! `CallToolResult`, as `notifications/tools/partial_result` tagged with the
+//! tools/call request ID. The transport writes them on the call's stream,
+//! so they only go out when it can interleave messages (SSE or stdio) and
+//! the context belongs to a request. Clients that ignore them still get the
+//! full output in the final result, which must carry it too.
+//! - `PartialResults`: `stream_text` on the handler's context
+//! - `impl_partial_results!`: implements `PartialResults` for a crate's
+//!   generated `MessageContext`
+//!
+//! ```ignore
+//! use wasmcp_partial_results::PartialResults;
+//! wasmcp_partial_results::impl_partial_results!(MessageContext<'_>, bindings::wasmcp::mcp_v20251125);
+//!
+//! for i in 1..=n {
+//!     ctx.stream_text(format!("step {i} done\n"));
+//! }
+//! ```
+
+mod macros;
+
+/// Sends partial results for the tools/call being handled
+pub trait PartialResults {
+    /// Send a chunk of text output ahead of the final result
+    ///
+    /// False when it could not be sent: no framed stream, no request ID, or
+    /// the write failed.
+    fn stream_text(&self, chunk: impl Into<String>) -> bool;
+}
+
+#[cfg(test)]
+mod tests;
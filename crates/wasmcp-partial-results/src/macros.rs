@@ -0,0 +1,46 @@
+//! Implementations for generated bindings
+
+/// Implement `PartialResults` for a crate's generated `MessageContext`
+///
+/// `$package` is the generated module of the MCP package, the one holding
+/// `mcp` and `server_io` (e.g. `bindings::wasmcp::mcp_v20251125`).
+#[macro_export]
+macro_rules! impl_partial_results {
+    ($message_context:ty, $($package:ident)::+) => {
+        const _: () = {
+            use $($package)::+::mcp::{
+                ContentBlock, PartialResultNotification, ServerMessage, ServerNotification,
+                TextContent, TextData,
+            };
+            use $($package)::+::server_io;
+
+            impl $crate::PartialResults for $message_context {
+                fn stream_text(
+                    &self,
+                    chunk: impl ::core::convert::Into<::std::string::String>,
+                ) -> bool {
+                    let framed = !self.frame.prefix.is_empty() || !self.frame.suffix.is_empty();
+                    let (::core::option::Option::Some(stream), ::core::option::Option::Some(request_id)) =
+                        (self.client_stream.filter(|_| framed), &self.request_id)
+                    else {
+                        return false;
+                    };
+                    let notification = ServerNotification::ToolPartialResult(PartialResultNotification {
+                        request_id: request_id.clone(),
+                        content: ::std::vec![ContentBlock::Text(TextContent {
+                            text: TextData::Text(chunk.into()),
+                            options: ::core::option::Option::None,
+                        })],
+                        meta: ::core::option::Option::None,
+                    });
+                    server_io::send_message(
+                        stream,
+                        ServerMessage::Notification(notification),
+                        &self.frame,
+                    )
+                    .is_ok()
+                }
+            }
+        };
+    };
+}
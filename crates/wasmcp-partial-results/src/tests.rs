@@ -0,0 +1,150 @@
+use super::*;
+
+// Shape of the wit-bindgen output for the MCP package
+mod package {
+    pub mod mcp {
+        #[derive(Debug, Clone, PartialEq)]
+        pub enum RequestId {
+            Number(i64),
+            String(String),
+        }
+
+        #[derive(Debug, Clone, PartialEq)]
+        pub enum TextData {
+            Text(String),
+        }
+
+        #[derive(Debug, Clone, PartialEq)]
+        pub struct TextContent {
+            pub text: TextData,
+            pub options: Option<()>,
+        }
+
+        #[derive(Debug, Clone, PartialEq)]
+        pub enum ContentBlock {
+            Text(TextContent),
+        }
+
+        #[derive(Debug, Clone)]
+        pub struct PartialResultNotification {
+            pub request_id: RequestId,
+            pub content: Vec<ContentBlock>,
+            pub meta: Option<String>,
+        }
+
+        #[derive(Debug, Clone)]
+        pub enum ServerNotification {
+            ToolPartialResult(PartialResultNotification),
+        }
+
+        #[derive(Debug, Clone)]
+        pub enum ServerMessage {
+            Notification(ServerNotification),
+        }
+    }
+
+    pub mod server_io {
+        use super::mcp::ServerMessage;
+        use std::cell::RefCell;
+
+        pub struct OutputStream;
+
+        pub struct MessageFrame {
+            pub prefix: Vec<u8>,
+            pub suffix: Vec<u8>,
+        }
+
+        thread_local! {
+            pub static SENT: RefCell<Vec<ServerMessage>> = const { RefCell::new(Vec::new()) };
+        }
+
+        pub fn send_message(
+            _stream: &OutputStream,
+            message: ServerMessage,
+            _frame: &MessageFrame,
+        ) -> Result<(), ()> {
+            SENT.with(|s| s.borrow_mut().push(message));
+            Ok(())
+        }
+    }
+}
+
+use package::mcp::{ContentBlock, RequestId, ServerMessage, ServerNotification, TextData};
+use package::server_io::{MessageFrame, OutputStream, SENT};
+
+struct MessageContext<'a> {
+    client_stream: Option<&'a OutputStream>,
+    frame: MessageFrame,
+    request_id: Option<RequestId>,
+}
+
+crate::impl_partial_results!(MessageContext<'_>, package);
+
+fn sent() -> Vec<(RequestId, String)> {
+    SENT.with(|s| s.borrow_mut().drain(..).collect::<Vec<_>>())
+        .into_iter()
+        .map(|message| match message {
+            ServerMessage::Notification(ServerNotification::ToolPartialResult(partial)) => {
+                let [ContentBlock::Text(content)] = partial.content.as_slice() else {
+                    panic!("expected one text block");
+                };
+                let TextData::Text(text) = &content.text;
+                assert!(partial.meta.is_none());
+                (partial.request_id, text.clone())
+            }
+        })
+        .collect()
+}
+
+fn sse_frame() -> MessageFrame {
+    MessageFrame {
+        prefix: b"data: ".to_vec(),
+        suffix: b"\n\n".to_vec(),
+    }
+}
+
+#[test]
+fn test_stream_text_tags_request() {
+    let stream = OutputStream;
+    let ctx = MessageContext {
+        client_stream: Some(&stream),
+        frame: sse_frame(),
+        request_id: Some(RequestId::Number(7)),
+    };
+    assert!(ctx.stream_text("1! = 1\n"));
+    assert!(ctx.stream_text(String::from("2! = 2\n")));
+    assert_eq!(
+        sent(),
+        vec![
+            (RequestId::Number(7), "1! = 1\n".to_string()),
+            (RequestId::Number(7), "2! = 2\n".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_stream_text_needs_request() {
+    let stream = OutputStream;
+    let ctx = MessageContext {
+        client_stream: Some(&stream),
+        frame: sse_frame(),
+        request_id: None,
+    };
+    assert!(!ctx.stream_text("dropped"));
+    assert!(sent().is_empty());
+}
+
+#[test]
+fn test_stream_text_needs_framed_stream() {
+    let stream = OutputStream;
+    let ctx = MessageContext {
+        client_stream: Some(&stream),
+        frame: MessageFrame {
+            prefix: Vec::new(),
+            suffix: Vec::new(),
+        },
+        request_id: Some(RequestId::String("call-1".to_string())),
+    };
+    assert!(!ctx.stream_text("dropped"));
+    assert!(sent().is_empty());
+}
@@ -0,0 +1,175 @@
+//! Redaction of sensitive request fields
+//!
+//! Tool and prompt arguments often carry secrets (passwords, tokens), and
+//! every component that logs or traces a request would otherwise need its
+//! own scrubbing. This crate applies one declarative configuration everywhere
+//! arguments are emitted: log lines, audit records, trace attributes.
+//!
+//! Rules map a tool or prompt name (or `*` for all of them) to JSON pointers
+//! into the request params, and are usually read from `WASMCP_REDACT`:
+//!
+//! ```text
+//! WASMCP_REDACT='{"*": ["/arguments/password"], "login": ["/arguments/otp", "/arguments/users/*/token"]}'
+//! ```
+//!
+//! Pointers follow RFC 6901 (`~1` for `/`, `~0` for `~`), plus a `*` segment
+//! that matches every member of an object or element of an array. Matched
+//! values are replaced with `"[REDACTED]"`; pointers that match nothing are
+//! ignored.
+//!
+//! ```ignore
+//! let redactor = wasmcp_redact::Redactor::parse(&spec)?;
+//! let safe = redactor.redact_arguments(&request.name, request.arguments.as_deref());
+//! tracing::debug!(tool = %request.name, arguments = ?safe, "tools/call");
+//! ```
+//!
+//! Redaction only applies to emitted copies; handlers still receive the
+//! original arguments.
+
+use serde_json::{Map, Value};
+
+/// Environment variable components read their rules from
+pub const REDACT_ENV: &str = "WASMCP_REDACT";
+
+/// Replacement for redacted values
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Rule key that applies to every tool and prompt
+const ANY_TARGET: &str = "*";
+
+/// Parsed redaction rules
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Redactor {
+    /// (target name or `*`, pointer split into unescaped segments)
+    rules: Vec<(String, Vec<String>)>,
+}
+
+impl Redactor {
+    /// Parse rules from their JSON form
+    ///
+    /// An empty or whitespace-only spec yields a redactor that changes nothing.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        if spec.trim().is_empty() {
+            return Ok(Self::default());
+        }
+
+        let targets: Map<String, Value> = serde_json::from_str(spec)
+            .map_err(|e| format!("Invalid redaction rules, expected a JSON object: {}", e))?;
+
+        let mut rules = Vec::new();
+        for (target, pointers) in targets {
+            let pointers = pointers.as_array().ok_or_else(|| {
+                format!(
+                    "Invalid redaction rules for '{}', expected an array of JSON pointers",
+                    target
+                )
+            })?;
+            for pointer in pointers {
+                let pointer = pointer.as_str().ok_or_else(|| {
+                    format!("Invalid redaction pointer for '{}': {}", target, pointer)
+                })?;
+                rules.push((target.clone(), parse_pointer(pointer)?));
+            }
+        }
+
+        Ok(Redactor { rules })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Whether any rule applies to this tool or prompt
+    pub fn applies_to(&self, target: &str) -> bool {
+        self.rules_for(target).next().is_some()
+    }
+
+    /// Redact request params in place
+    ///
+    /// `target` is the tool or prompt name the params belong to.
+    pub fn redact(&self, target: &str, params: &mut Value) {
+        for segments in self.rules_for(target) {
+            redact_at(params, segments);
+        }
+    }
+
+    /// Redact the JSON-encoded arguments of a tools/call or prompts/get
+    ///
+    /// Pointers are relative to the params, so `/arguments/password` selects
+    /// the `password` argument. Arguments that are not valid JSON are
+    /// replaced entirely when any rule applies, since their contents cannot
+    /// be checked.
+    pub fn redact_arguments(&self, target: &str, arguments: Option<&str>) -> Option<String> {
+        let arguments = arguments?;
+        if !self.applies_to(target) {
+            return Some(arguments.to_string());
+        }
+
+        let Ok(parsed) = serde_json::from_str::<Value>(arguments) else {
+            return Some(Value::String(REDACTED.to_string()).to_string());
+        };
+
+        let mut params = Value::Object(Map::from_iter([("arguments".to_string(), parsed)]));
+        self.redact(target, &mut params);
+
+        match params {
+            Value::Object(mut params) => params.remove("arguments").map(|a| a.to_string()),
+            // A rule on the whole params ("") replaced the object
+            other => Some(other.to_string()),
+        }
+    }
+
+    fn rules_for<'a>(&'a self, target: &'a str) -> impl Iterator<Item = &'a [String]> + 'a {
+        self.rules
+            .iter()
+            .filter(move |(t, _)| t == ANY_TARGET || t == target)
+            .map(|(_, segments)| segments.as_slice())
+    }
+}
+
+/// Split a JSON pointer into unescaped segments
+fn parse_pointer(pointer: &str) -> Result<Vec<String>, String> {
+    if pointer.is_empty() {
+        return Ok(Vec::new());
+    }
+    let rest = pointer.strip_prefix('/').ok_or_else(|| {
+        format!(
+            "Invalid redaction pointer '{}', expected it to start with '/'",
+            pointer
+        )
+    })?;
+    Ok(rest
+        .split('/')
+        .map(|segment| segment.replace("~1", "/").replace("~0", "~"))
+        .collect())
+}
+
+fn redact_at(value: &mut Value, segments: &[String]) {
+    let Some((segment, rest)) = segments.split_first() else {
+        *value = Value::String(REDACTED.to_string());
+        return;
+    };
+
+    match value {
+        Value::Object(map) if segment == "*" => {
+            map.values_mut().for_each(|v| redact_at(v, rest));
+        }
+        Value::Object(map) => {
+            if let Some(v) = map.get_mut(segment) {
+                redact_at(v, rest);
+            }
+        }
+        Value::Array(items) if segment == "*" => {
+            items.iter_mut().for_each(|v| redact_at(v, rest));
+        }
+        Value::Array(items) => {
+            if let Some(v) = segment.parse::<usize>().ok().and_then(|i| items.get_mut(i)) {
+                redact_at(v, rest);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,94 @@
+use super::*;
+use serde_json::json;
+
+#[test]
+fn test_redacts_matching_arguments() {
+    let redactor =
+        Redactor::parse(r#"{"*": ["/arguments/password"], "login": ["/arguments/otp"]}"#).unwrap();
+
+    let redacted = redactor
+        .redact_arguments(
+            "login",
+            Some(r#"{"user":"ada","password":"hunter2","otp":"123456"}"#),
+        )
+        .unwrap();
+    let redacted: Value = serde_json::from_str(&redacted).unwrap();
+    assert_eq!(
+        redacted,
+        json!({"user": "ada", "password": REDACTED, "otp": REDACTED})
+    );
+
+    // Only the wildcard rule applies to other tools
+    let redacted = redactor
+        .redact_arguments("signup", Some(r#"{"password":"x","otp":"1"}"#))
+        .unwrap();
+    let redacted: Value = serde_json::from_str(&redacted).unwrap();
+    assert_eq!(redacted, json!({"password": REDACTED, "otp": "1"}));
+}
+
+#[test]
+fn test_wildcard_and_index_segments() {
+    let redactor = Redactor::parse(
+        r#"{"bulk": ["/arguments/users/*/token", "/arguments/keys/0", "/arguments/a~1b"]}"#,
+    )
+    .unwrap();
+
+    let mut params = json!({
+        "arguments": {
+            "users": [{"name": "a", "token": "t1"}, {"name": "b", "token": "t2"}],
+            "keys": ["k0", "k1"],
+            "a/b": "slash"
+        }
+    });
+    redactor.redact("bulk", &mut params);
+    assert_eq!(
+        params,
+        json!({
+            "arguments": {
+                "users": [{"name": "a", "token": REDACTED}, {"name": "b", "token": REDACTED}],
+                "keys": [REDACTED, "k1"],
+                "a/b": REDACTED
+            }
+        })
+    );
+}
+
+#[test]
+fn test_unmatched_and_unparseable_arguments() {
+    let redactor = Redactor::parse(r#"{"login": ["/arguments/password"]}"#).unwrap();
+
+    // Missing paths are ignored
+    assert_eq!(
+        redactor.redact_arguments("login", Some(r#"{"user":"ada"}"#)),
+        Some(r#"{"user":"ada"}"#.to_string())
+    );
+    // Unparseable arguments are dropped entirely when a rule applies
+    assert_eq!(
+        redactor.redact_arguments("login", Some("not json")),
+        Some(format!("\"{}\"", REDACTED))
+    );
+    // ...and passed through untouched when none does
+    assert_eq!(
+        redactor.redact_arguments("other", Some("not json")),
+        Some("not json".to_string())
+    );
+    assert_eq!(redactor.redact_arguments("login", None), None);
+}
+
+#[test]
+fn test_whole_arguments_rule() {
+    let redactor = Redactor::parse(r#"{"vault": ["/arguments"]}"#).unwrap();
+    assert_eq!(
+        redactor.redact_arguments("vault", Some(r#"{"secret":"x"}"#)),
+        Some(format!("\"{}\"", REDACTED))
+    );
+}
+
+#[test]
+fn test_parse_errors() {
+    assert!(Redactor::parse("").unwrap().is_empty());
+    assert!(Redactor::parse("[]").is_err());
+    assert!(Redactor::parse(r#"{"login": "/arguments/password"}"#).is_err());
+    assert!(Redactor::parse(r#"{"login": ["arguments/password"]}"#).is_err());
+    assert!(Redactor::parse(r#"{"login": [42]}"#).is_err());
+}
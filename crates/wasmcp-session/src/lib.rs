@@ -0,0 +1,100 @@
+//! Transactional session updates
+//!
+//! Handlers often keep related state in several session keys (a todo list
+//! and its next id). Writing them one by one could leave the session half
+//! updated when the handler failed between writes. Session resources stage
+//! their writes between `begin-batch` and `flush`, and `rollback` discards
+//! them; this crate builds transactions on the three:
+//! - `Staged`: a session that stages writes, independent of any bindings
+//! - `Transaction`: `session.transaction(|txn| ...)`, committing the
+//!   closure's writes with one batch on `Ok` and discarding them on `Err`
+//! - `impl_session!`: implements `Staged` for a crate's generated `Session`
+//!
+//! ```ignore
+//! use bindings::wasmcp::mcp_v20251125::sessions::{Session, SessionError};
+//! use wasmcp_session::Transaction;
+//! wasmcp_session::impl_session!(Session, SessionError);
+//!
+//! session.transaction(|txn| {
+//!     txn.set("todo:list", &TypedValue::AsJson(list))?;
+//!     txn.set("todo:next_id", &TypedValue::AsU64(next_id))
+//! })?;
+//! ```
+
+mod macros;
+
+/// A session whose writes can be staged until flushed
+pub trait Staged {
+    type Error;
+
+    /// Stage writes until `flush` or `rollback`
+    fn begin_batch(&self);
+
+    /// Persist staged writes in one batch, ending the batch
+    fn flush(&self) -> Result<(), Self::Error>;
+
+    /// Discard staged writes, ending the batch
+    fn rollback(&self);
+}
+
+/// Why a transaction didn't commit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransactionError<E, S> {
+    /// The closure failed; its writes were discarded
+    Aborted(E),
+    /// Writes staged before the transaction could not be flushed; the
+    /// closure did not run
+    Pending(S),
+    /// The commit failed; its writes were discarded
+    Commit(S),
+}
+
+impl<E: std::fmt::Display, S: std::fmt::Debug> std::fmt::Display for TransactionError<E, S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TransactionError::Aborted(e) => write!(f, "Transaction aborted: {}", e),
+            TransactionError::Pending(e) => {
+                write!(f, "Failed to flush writes before transaction: {:?}", e)
+            }
+            TransactionError::Commit(e) => write!(f, "Failed to commit transaction: {:?}", e),
+        }
+    }
+}
+
+/// Transactions over any `Staged` session
+pub trait Transaction: Staged + Sized {
+    /// Run `f` with this session, persisting its writes only if it succeeds
+    ///
+    /// Writes staged before the call (a batch the caller left open) are
+    /// flushed first, so they are neither part of the commit nor lost by a
+    /// rollback. `f`'s writes are staged in a batch; inside `f`, gets see the
+    /// transaction's own writes. The commit is a single `set-many`; it is as
+    /// atomic as the key-value backend makes batches.
+    fn transaction<T, E>(
+        &self,
+        f: impl FnOnce(&Self) -> Result<T, E>,
+    ) -> Result<T, TransactionError<E, Self::Error>> {
+        self.flush().map_err(TransactionError::Pending)?;
+        self.begin_batch();
+
+        let value = match f(self) {
+            Ok(value) => value,
+            Err(e) => {
+                self.rollback();
+                return Err(TransactionError::Aborted(e));
+            }
+        };
+
+        if let Err(e) = self.flush() {
+            // Don't leave half of a failed commit staged
+            self.rollback();
+            return Err(TransactionError::Commit(e));
+        }
+        Ok(value)
+    }
+}
+
+impl<S: Staged> Transaction for S {}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,25 @@
+//! Conversions for generated bindings
+
+/// Implement `Staged` for a crate's generated `session` resource
+#[macro_export]
+macro_rules! impl_session {
+    ($session:ty, $session_error:ty) => {
+        const _: () = {
+            impl $crate::Staged for $session {
+                type Error = $session_error;
+
+                fn begin_batch(&self) {
+                    <$session>::begin_batch(self)
+                }
+
+                fn flush(&self) -> ::core::result::Result<(), Self::Error> {
+                    <$session>::flush(self)
+                }
+
+                fn rollback(&self) {
+                    <$session>::rollback(self)
+                }
+            }
+        };
+    };
+}
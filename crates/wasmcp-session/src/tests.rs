@@ -0,0 +1,139 @@
+use super::*;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+
+// Shape of the session-store resource: a cache over KV, with opt-in staged
+// writes
+#[derive(Default)]
+struct Session {
+    stored: RefCell<HashMap<String, u64>>,
+    staged: RefCell<HashMap<String, u64>>,
+    batching: Cell<bool>,
+    fail_flush: Cell<bool>,
+}
+
+#[derive(Debug, PartialEq)]
+struct SessionError(String);
+
+impl Session {
+    fn get(&self, key: &str) -> Option<u64> {
+        let staged = self.staged.borrow().get(key).copied();
+        staged.or_else(|| self.stored.borrow().get(key).copied())
+    }
+
+    fn set(&self, key: &str, value: u64) -> Result<(), String> {
+        if self.batching.get() {
+            self.staged.borrow_mut().insert(key.to_string(), value);
+        } else {
+            self.stored.borrow_mut().insert(key.to_string(), value);
+        }
+        Ok(())
+    }
+
+    fn begin_batch(&self) {
+        self.batching.set(true);
+    }
+
+    fn flush(&self) -> Result<(), SessionError> {
+        if self.fail_flush.get() && !self.staged.borrow().is_empty() {
+            return Err(SessionError("store unavailable".to_string()));
+        }
+        let staged: Vec<_> = self.staged.borrow_mut().drain().collect();
+        self.stored.borrow_mut().extend(staged);
+        self.batching.set(false);
+        Ok(())
+    }
+
+    fn rollback(&self) {
+        self.staged.borrow_mut().clear();
+        self.batching.set(false);
+    }
+}
+
+crate::impl_session!(Session, SessionError);
+
+#[test]
+fn test_commit() {
+    let session = Session::default();
+    session.set("todo:next_id", 1).unwrap();
+
+    let id = session
+        .transaction(|txn| {
+            let id = txn.get("todo:next_id").unwrap_or(1);
+            txn.set("todo:list", id)?;
+            txn.set("todo:next_id", id + 1)?;
+            assert_eq!(txn.get("todo:next_id"), Some(2));
+            Ok::<_, String>(id)
+        })
+        .unwrap();
+
+    assert_eq!(id, 1);
+    assert!(session.staged.borrow().is_empty());
+    assert_eq!(session.stored.borrow().get("todo:list"), Some(&1));
+    assert_eq!(session.stored.borrow().get("todo:next_id"), Some(&2));
+}
+
+#[test]
+fn test_writes_outside_batch_are_stored() {
+    let session = Session::default();
+    session.set("count", 1).unwrap();
+    assert_eq!(session.stored.borrow().get("count"), Some(&1));
+
+    session.begin_batch();
+    session.set("count", 2).unwrap();
+    assert_eq!(session.stored.borrow().get("count"), Some(&1));
+    session.flush().unwrap();
+    assert_eq!(session.stored.borrow().get("count"), Some(&2));
+}
+
+#[test]
+fn test_abort_discards_writes() {
+    let session = Session::default();
+    session.set("count", 1).unwrap();
+
+    let result = session.transaction(|txn| {
+        txn.set("todo:list", 7)?;
+        Err::<(), _>("item title missing".to_string())
+    });
+
+    assert_eq!(
+        result,
+        Err(TransactionError::Aborted("item title missing".to_string()))
+    );
+    // Writes from before the transaction were kept
+    assert_eq!(session.get("count"), Some(1));
+    assert_eq!(session.get("todo:list"), None);
+}
+
+#[test]
+fn test_failed_commit_discards_writes() {
+    let session = Session::default();
+    let mut ran = false;
+    // A batch the caller left open can't be flushed
+    session.begin_batch();
+    session.set("count", 1).unwrap();
+    session.fail_flush.set(true);
+
+    let result = session.transaction(|_| {
+        ran = true;
+        Ok::<_, String>(())
+    });
+    assert_eq!(
+        result,
+        Err(TransactionError::Pending(SessionError(
+            "store unavailable".to_string()
+        )))
+    );
+    assert!(!ran);
+    session.rollback();
+
+    session.fail_flush.set(false);
+    let result = session.transaction(|txn| {
+        txn.set("todo:list", 1)?;
+        txn.fail_flush.set(true);
+        Ok::<_, String>(())
+    });
+    assert!(matches!(result, Err(TransactionError::Commit(_))));
+    assert!(session.staged.borrow().is_empty());
+    assert!(session.stored.borrow().is_empty());
+}
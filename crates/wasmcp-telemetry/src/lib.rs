@@ -0,0 +1,315 @@
+//! `tracing` integration for wasmcp handlers
+//!
+//! `init()` installs a global subscriber that records `tracing` events.
+//! Wrap handler bodies in `scope(&ctx, ..)`: events below the client's
+//! logging/setLevel choice are dropped, and the rest are sent as
+//! notifications/message on the call's stream before the result is returned.
+//! Without a framed stream (plain JSON HTTP), events go to stderr.
+//!
+//! wasm32-wasip2 has no unwinding, so a panic traps the component before
+//! `scope` can send anything. `init()` also installs a panic hook that runs
+//! before the trap: inside a scope it sends the events recorded so far and
+//! an error log with a hash of the panic's message and location. The
+//! details go to stderr under the same hash, so the client can quote it
+//! without seeing them.
+//! - `Telemetry`: the client's level and a way to send it a record
+//! - `impl_telemetry!`: implements `Telemetry` for a crate's generated
+//!   `MessageContext`
+//! - `log_call`: logs tool arguments with the `WASMCP_REDACT` rules applied,
+//!   so secrets never reach the client or stderr
+//!
+//! ```ignore
+//! wasmcp_telemetry::impl_telemetry!(MessageContext<'_>, bindings::wasmcp::mcp_v20251125);
+//!
+//! wasmcp_telemetry::init();
+//! let result = wasmcp_telemetry::scope(&ctx, || {
+//!     wasmcp_telemetry::log_call(&request.name, request.arguments.as_deref());
+//!     tracing::info!(target: "factorial", "Starting calculation");
+//!     ...
+//! });
+//! ```
+
+mod macros;
+
+use std::cell::{Cell, RefCell};
+use std::collections::hash_map::DefaultHasher;
+use std::fmt::Write;
+use std::hash::{Hash, Hasher};
+use std::panic::{self, PanicHookInfo};
+use std::sync::Once;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::prelude::*;
+use wasmcp_redact::{REDACT_ENV, REDACTED, Redactor};
+
+/// Level used when the client never sent logging/setLevel
+pub const DEFAULT_LEVEL: Level = Level::Info;
+
+static INIT: Once = Once::new();
+
+/// MCP log levels (RFC 5424), least severe first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Debug,
+    Info,
+    Notice,
+    Warning,
+    Error,
+    Critical,
+    Alert,
+    Emergency,
+}
+
+impl Level {
+    pub fn name(self) -> &'static str {
+        match self {
+            Level::Debug => "DEBUG",
+            Level::Info => "INFO",
+            Level::Notice => "NOTICE",
+            Level::Warning => "WARNING",
+            Level::Error => "ERROR",
+            Level::Critical => "CRITICAL",
+            Level::Alert => "ALERT",
+            Level::Emergency => "EMERGENCY",
+        }
+    }
+}
+
+/// MCP has no trace level, so trace events are sent as debug
+impl From<&tracing::Level> for Level {
+    fn from(level: &tracing::Level) -> Self {
+        match *level {
+            tracing::Level::TRACE | tracing::Level::DEBUG => Level::Debug,
+            tracing::Level::INFO => Level::Info,
+            tracing::Level::WARN => Level::Warning,
+            tracing::Level::ERROR => Level::Error,
+        }
+    }
+}
+
+/// One recorded event
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub level: Level,
+    pub target: String,
+    /// The event's message, with other fields appended as ` key=value`
+    pub message: String,
+}
+
+/// Where a scope's events go
+pub trait Telemetry {
+    /// The level the client chose with logging/setLevel, if any
+    fn client_log_level(&self) -> Option<Level>;
+
+    /// Send a record to the client as notifications/message
+    ///
+    /// False when there is no framed stream to send it on.
+    fn send_log(&self, record: &Record) -> bool;
+}
+
+thread_local! {
+    /// Events recorded inside the current scope
+    static PENDING: RefCell<Option<Vec<Record>>> = const { RefCell::new(None) };
+    /// Least severe level recorded in the current scope
+    static MIN_LEVEL: Cell<Level> = const { Cell::new(Level::Debug) };
+    /// Redaction rules from WASMCP_REDACT (None if they failed to parse)
+    static REDACTOR: Option<Redactor> = load_redactor();
+    /// Context of the enclosing scope, for the panic hook
+    static CONTEXT: Cell<Option<*const dyn Telemetry>> = const { Cell::new(None) };
+}
+
+/// Install the subscriber and panic hook (safe to call on every request)
+pub fn init() {
+    INIT.call_once(|| {
+        let _ = tracing_subscriber::registry().with(McpLayer).try_init();
+        panic::set_hook(Box::new(report_panic));
+    });
+}
+
+/// Run a handler body, forwarding its events to the client afterwards
+pub fn scope<T>(ctx: &impl Telemetry, f: impl FnOnce() -> T) -> T {
+    let min_level = ctx.client_log_level().unwrap_or(DEFAULT_LEVEL);
+    let _context = ContextGuard::enter(ctx);
+    let (result, records) = capture(min_level, f);
+    for record in records {
+        if !ctx.send_log(&record) {
+            write_stderr(&record);
+        }
+    }
+    result
+}
+
+/// Run `f`, returning the events at or above `min_level` it recorded
+pub fn capture<T>(min_level: Level, f: impl FnOnce() -> T) -> (T, Vec<Record>) {
+    MIN_LEVEL.with(|m| m.set(min_level));
+    PENDING.with(|p| *p.borrow_mut() = Some(Vec::new()));
+
+    let result = f();
+
+    let records = PENDING.with(|p| p.borrow_mut().take()).unwrap_or_default();
+    (result, records)
+}
+
+/// Log a tool call with its arguments redacted
+///
+/// When the `WASMCP_REDACT` rules don't parse, arguments are replaced
+/// entirely: rules that can't be applied leave nothing safe to log.
+pub fn log_call(tool: &str, arguments: Option<&str>) {
+    let arguments = REDACTOR.with(|redactor| match redactor {
+        Some(redactor) => redactor.redact_arguments(tool, arguments),
+        None => arguments.map(|_| REDACTED.to_string()),
+    });
+    tracing::debug!(
+        target: "tools",
+        tool = %tool,
+        arguments = arguments.as_deref().unwrap_or("{}"),
+        "tools/call"
+    );
+}
+
+/// Makes a scope's context available to the panic hook until dropped
+struct ContextGuard(Option<*const dyn Telemetry>);
+
+impl ContextGuard {
+    fn enter(ctx: &dyn Telemetry) -> Self {
+        let ctx: *const (dyn Telemetry + '_) = ctx;
+        // SAFETY: only the lifetime bound changes. The pointer is cleared
+        // when the guard drops, before `ctx` can go out of scope.
+        let ctx: *const dyn Telemetry = unsafe { std::mem::transmute(ctx) };
+        ContextGuard(CONTEXT.with(|c| c.replace(Some(ctx))))
+    }
+}
+
+impl Drop for ContextGuard {
+    fn drop(&mut self) {
+        CONTEXT.with(|c| c.set(self.0));
+    }
+}
+
+fn report_panic(info: &PanicHookInfo<'_>) {
+    let message = match info.payload().downcast_ref::<&str>() {
+        Some(s) => s.to_string(),
+        None => match info.payload().downcast_ref::<String>() {
+            Some(s) => s.clone(),
+            None => "Box<dyn Any>".to_string(),
+        },
+    };
+    let location = info
+        .location()
+        .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()))
+        .unwrap_or_default();
+
+    let mut hasher = DefaultHasher::new();
+    message.hash(&mut hasher);
+    location.hash(&mut hasher);
+    let hash = hasher.finish();
+    eprintln!(
+        "[wasmcp-telemetry] panic {:016x} at {}: {}",
+        hash, location, message
+    );
+
+    let Some(ctx) = CONTEXT.with(Cell::get) else {
+        return;
+    };
+    // SAFETY: set only while `scope` runs, and the panicking code runs inside it
+    let ctx = unsafe { &*ctx };
+
+    // The trap would lose events recorded before the panic
+    let mut records = PENDING
+        .with(|p| p.try_borrow_mut().ok().and_then(|mut p| p.take()))
+        .unwrap_or_default();
+    if Level::Error >= MIN_LEVEL.with(Cell::get) {
+        records.push(Record {
+            level: Level::Error,
+            target: "panic".to_string(),
+            message: format!("Tool call failed unexpectedly (panic {:016x})", hash),
+        });
+    }
+    for record in &records {
+        if !ctx.send_log(record) {
+            write_stderr(record);
+        }
+    }
+}
+
+fn load_redactor() -> Option<Redactor> {
+    let spec = std::env::var(REDACT_ENV).unwrap_or_default();
+    Redactor::parse(&spec)
+        .map_err(|e| {
+            eprintln!(
+                "[wasmcp-telemetry] {}: {}. Arguments will not be logged.",
+                REDACT_ENV, e
+            )
+        })
+        .ok()
+}
+
+fn write_stderr(record: &Record) {
+    eprintln!(
+        "[{}] {}: {}",
+        record.level.name(),
+        record.target,
+        record.message
+    );
+}
+
+/// Layer that records events for the enclosing `scope`
+struct McpLayer;
+
+impl<S: Subscriber> Layer<S> for McpLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let level = Level::from(event.metadata().level());
+        if level < MIN_LEVEL.with(Cell::get) {
+            return;
+        }
+
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        let record = Record {
+            level,
+            target: event.metadata().target().to_string(),
+            message: visitor.message + &visitor.fields,
+        };
+
+        // Outside a scope there is no stream to send on
+        let record = PENDING.with(|p| match p.borrow_mut().as_mut() {
+            Some(pending) => {
+                pending.push(record);
+                None
+            }
+            None => Some(record),
+        });
+        if let Some(record) = record {
+            write_stderr(&record);
+        }
+    }
+}
+
+/// Collects the `message` field, with other fields appended as `key=value`
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+    fields: String,
+}
+
+impl Visit for MessageVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            let _ = write!(self.fields, " {}={}", field.name(), value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{:?}", value);
+        } else {
+            let _ = write!(self.fields, " {}={:?}", field.name(), value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
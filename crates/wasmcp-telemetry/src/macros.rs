@@ -0,0 +1,62 @@
+//! Implementations for generated bindings
+
+/// Implement `Telemetry` for a crate's generated `MessageContext`
+///
+/// `$package` is the generated module of the MCP package, the one holding
+/// `mcp` and `server_io` (e.g. `bindings::wasmcp::mcp_v20251125`).
+#[macro_export]
+macro_rules! impl_telemetry {
+    ($message_context:ty, $($package:ident)::+) => {
+        const _: () = {
+            use $($package)::+::mcp::{
+                LogLevel, LoggingMessageNotification, ServerMessage, ServerNotification,
+            };
+            use $($package)::+::server_io;
+
+            impl $crate::Telemetry for $message_context {
+                fn client_log_level(&self) -> ::core::option::Option<$crate::Level> {
+                    self.log_level.map(|level| match level {
+                        LogLevel::Debug => $crate::Level::Debug,
+                        LogLevel::Info => $crate::Level::Info,
+                        LogLevel::Notice => $crate::Level::Notice,
+                        LogLevel::Warning => $crate::Level::Warning,
+                        LogLevel::Error => $crate::Level::Error,
+                        LogLevel::Critical => $crate::Level::Critical,
+                        LogLevel::Alert => $crate::Level::Alert,
+                        LogLevel::Emergency => $crate::Level::Emergency,
+                    })
+                }
+
+                fn send_log(&self, record: &$crate::Record) -> bool {
+                    let framed = !self.frame.prefix.is_empty() || !self.frame.suffix.is_empty();
+                    let ::core::option::Option::Some(stream) =
+                        self.client_stream.filter(|_| framed)
+                    else {
+                        return false;
+                    };
+                    let level = match record.level {
+                        $crate::Level::Debug => LogLevel::Debug,
+                        $crate::Level::Info => LogLevel::Info,
+                        $crate::Level::Notice => LogLevel::Notice,
+                        $crate::Level::Warning => LogLevel::Warning,
+                        $crate::Level::Error => LogLevel::Error,
+                        $crate::Level::Critical => LogLevel::Critical,
+                        $crate::Level::Alert => LogLevel::Alert,
+                        $crate::Level::Emergency => LogLevel::Emergency,
+                    };
+                    let notification = ServerNotification::Log(LoggingMessageNotification {
+                        data: record.message.clone(),
+                        level,
+                        logger: ::core::option::Option::Some(record.target.clone()),
+                    });
+                    let _ = server_io::send_message(
+                        stream,
+                        ServerMessage::Notification(notification),
+                        &self.frame,
+                    );
+                    true
+                }
+            }
+        };
+    };
+}
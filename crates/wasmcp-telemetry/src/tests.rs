@@ -0,0 +1,226 @@
+use super::*;
+
+// Shape of the wit-bindgen output for the MCP package
+mod package {
+    pub mod mcp {
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub enum LogLevel {
+            Debug,
+            Info,
+            Notice,
+            Warning,
+            Error,
+            Critical,
+            Alert,
+            Emergency,
+        }
+
+        #[derive(Debug, Clone)]
+        pub struct LoggingMessageNotification {
+            pub data: String,
+            pub level: LogLevel,
+            pub logger: Option<String>,
+        }
+
+        #[derive(Debug, Clone)]
+        pub enum ServerNotification {
+            Log(LoggingMessageNotification),
+        }
+
+        #[derive(Debug, Clone)]
+        pub enum ServerMessage {
+            Notification(ServerNotification),
+        }
+    }
+
+    pub mod server_io {
+        use super::mcp::ServerMessage;
+        use std::cell::RefCell;
+
+        pub struct OutputStream;
+
+        pub struct MessageFrame {
+            pub prefix: Vec<u8>,
+            pub suffix: Vec<u8>,
+        }
+
+        thread_local! {
+            pub static SENT: RefCell<Vec<ServerMessage>> = const { RefCell::new(Vec::new()) };
+        }
+
+        pub fn send_message(
+            _stream: &OutputStream,
+            message: ServerMessage,
+            _frame: &MessageFrame,
+        ) -> Result<(), ()> {
+            SENT.with(|s| s.borrow_mut().push(message));
+            Ok(())
+        }
+    }
+}
+
+use package::mcp::{LogLevel, ServerMessage, ServerNotification};
+use package::server_io::{MessageFrame, OutputStream, SENT};
+
+struct MessageContext<'a> {
+    client_stream: Option<&'a OutputStream>,
+    frame: MessageFrame,
+    log_level: Option<LogLevel>,
+}
+
+crate::impl_telemetry!(MessageContext<'_>, package);
+
+fn sent() -> Vec<(LogLevel, String, Option<String>)> {
+    SENT.with(|s| s.borrow_mut().drain(..).collect::<Vec<_>>())
+        .into_iter()
+        .map(|message| match message {
+            ServerMessage::Notification(ServerNotification::Log(log)) => {
+                (log.level, log.data, log.logger)
+            }
+        })
+        .collect()
+}
+
+fn sse_frame() -> MessageFrame {
+    MessageFrame {
+        prefix: b"data: ".to_vec(),
+        suffix: b"\n\n".to_vec(),
+    }
+}
+
+#[test]
+fn test_capture_filters_by_level() {
+    init();
+    let ((), records) = capture(Level::Warning, || {
+        tracing::info!("dropped");
+        tracing::warn!(target: "calc", "kept");
+        tracing::error!(target: "calc", "also kept");
+    });
+    let levels: Vec<Level> = records.iter().map(|r| r.level).collect();
+    assert_eq!(levels, vec![Level::Warning, Level::Error]);
+    assert_eq!(records[0].target, "calc");
+    assert_eq!(records[0].message, "kept");
+}
+
+#[test]
+fn test_capture_appends_fields() {
+    init();
+    let ((), records) = capture(Level::Debug, || {
+        tracing::info!(count = 3, name = "x", "hello");
+    });
+    assert_eq!(records[0].message, "hello count=3 name=x");
+}
+
+#[test]
+fn test_trace_is_debug() {
+    assert_eq!(Level::from(&tracing::Level::TRACE), Level::Debug);
+    assert_eq!(Level::from(&tracing::Level::WARN), Level::Warning);
+}
+
+#[test]
+fn test_scope_sends_at_client_level() {
+    init();
+    let stream = OutputStream;
+    let ctx = MessageContext {
+        client_stream: Some(&stream),
+        frame: sse_frame(),
+        log_level: Some(LogLevel::Notice),
+    };
+    let result = scope(&ctx, || {
+        tracing::info!("dropped");
+        tracing::warn!(target: "calc", "sent");
+        42
+    });
+    assert_eq!(result, 42);
+    assert_eq!(
+        sent(),
+        vec![(
+            LogLevel::Warning,
+            "sent".to_string(),
+            Some("calc".to_string())
+        )]
+    );
+}
+
+#[test]
+fn test_scope_defaults_to_info() {
+    init();
+    let stream = OutputStream;
+    let ctx = MessageContext {
+        client_stream: Some(&stream),
+        frame: sse_frame(),
+        log_level: None,
+    };
+    scope(&ctx, || {
+        tracing::debug!("dropped");
+        tracing::info!("sent");
+    });
+    let sent = sent();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].0, LogLevel::Info);
+}
+
+#[test]
+fn test_unframed_stream_is_not_sent() {
+    let stream = OutputStream;
+    let ctx = MessageContext {
+        client_stream: Some(&stream),
+        frame: MessageFrame {
+            prefix: Vec::new(),
+            suffix: Vec::new(),
+        },
+        log_level: None,
+    };
+    let record = Record {
+        level: Level::Info,
+        target: "calc".to_string(),
+        message: "to stderr".to_string(),
+    };
+    assert!(!ctx.send_log(&record));
+    assert!(sent().is_empty());
+}
+
+#[test]
+fn test_log_call_logs_arguments() {
+    init();
+    let ((), records) = capture(Level::Debug, || {
+        log_call("add", Some(r#"{"a":1}"#));
+        log_call("list", None);
+    });
+    assert_eq!(records[0].target, "tools");
+    assert_eq!(
+        records[0].message,
+        r#"tools/call tool=add arguments={"a":1}"#
+    );
+    assert_eq!(records[1].message, "tools/call tool=list arguments={}");
+}
+
+#[test]
+fn test_panic_in_scope_is_sent() {
+    init();
+    let stream = OutputStream;
+    let ctx = MessageContext {
+        client_stream: Some(&stream),
+        frame: sse_frame(),
+        log_level: None,
+    };
+    let result = scope(&ctx, || {
+        std::panic::catch_unwind(|| {
+            tracing::warn!(target: "calc", "before");
+            panic!("boom");
+        })
+    });
+    assert!(result.is_err());
+
+    let sent = sent();
+    assert_eq!(sent.len(), 2);
+    assert_eq!(sent[0].1, "before");
+    assert_eq!(sent[1].0, LogLevel::Error);
+    assert!(
+        sent[1]
+            .1
+            .starts_with("Tool call failed unexpectedly (panic ")
+    );
+    assert!(!sent[1].1.contains("boom"));
+    assert_eq!(sent[1].2.as_deref(), Some("panic"));
+}
@@ -1,6 +1,10 @@
 //! Calculator Tools Capability Provider
 //!
 //! A tools capability that provides basic calculator operations with notification support.
+//! Logging goes through `tracing`; `wasmcp-telemetry` sends events to the client
+//! and redacts call arguments. Panics trap the component; `wasmcp-telemetry`
+//! reports them to the client first. Factorial steps are streamed with
+//! `wasmcp-partial-results` ahead of the final result.
 
 mod bindings {
     wit_bindgen::generate!({
@@ -11,8 +15,11 @@ mod bindings {
 
 use bindings::exports::wasmcp::mcp_v20251125::tools::Guest;
 use bindings::wasmcp::mcp_v20251125::mcp::*;
-use bindings::wasmcp::mcp_v20251125::server_io;
 use bindings::wasmcp::mcp_v20251125::server_handler::MessageContext;
+use wasmcp_partial_results::PartialResults;
+
+wasmcp_telemetry::impl_telemetry!(MessageContext<'_>, bindings::wasmcp::mcp_v20251125);
+wasmcp_partial_results::impl_partial_results!(MessageContext<'_>, bindings::wasmcp::mcp_v20251125);
 
 struct Calculator;
 
@@ -75,7 +82,7 @@ impl Guest for Calculator {
                     options: Some(ToolOptions {
                         meta: None,
                         annotations: None,
-                        description: Some("Calculate factorial with progress updates".to_string()),
+                        description: Some("Calculate factorial with progress updates and streamed intermediate results".to_string()),
                         output_schema: None,
                         icons: None,
                         title: Some("Factorial".to_string()),
@@ -91,12 +98,16 @@ impl Guest for Calculator {
         ctx: MessageContext,
         request: CallToolRequest,
     ) -> Result<Option<CallToolResult>, ErrorCode> {
-        let result = match request.name.as_str() {
-            "add" => Some(execute_operation(&request.arguments, |a, b| a + b)),
-            "subtract" => Some(execute_operation(&request.arguments, |a, b| a - b)),
-            "factorial" => Some(execute_factorial(&ctx, &request)),
-            _ => None, // We don't handle this tool
-        };
+        wasmcp_telemetry::init();
+        let result = wasmcp_telemetry::scope(&ctx, || {
+            wasmcp_telemetry::log_call(&request.name, request.arguments.as_deref());
+            match request.name.as_str() {
+                "add" => Some(execute_operation(&request.arguments, |a, b| a + b)),
+                "subtract" => Some(execute_operation(&request.arguments, |a, b| a - b)),
+                "factorial" => Some(execute_factorial(&ctx, &request)),
+                _ => None, // We don't handle this tool
+            }
+        });
         Ok(result)
     }
 }
@@ -159,7 +170,7 @@ fn error_result(message: String) -> CallToolResult {
     }
 }
 
-// Calculates factorial with SSE notifications demonstrating progress updates.
+// Calculates factorial with log notifications and streamed partial results.
 // Note: When running with wasmtime serve, use `-S http-outgoing-body-buffer-chunks=10`
 // to ensure sufficient buffer for streaming multiple notifications plus the final response.
 fn execute_factorial(
@@ -174,20 +185,8 @@ fn execute_factorial(
         }
     };
 
-    let log = |msg| if let Some(stream) = ctx.client_stream {
-        let _ = server_io::send_message(
-            stream,
-            ServerMessage::Notification(ServerNotification::Log(LoggingMessageNotification {
-                data: msg,
-                level: LogLevel::Info,
-                logger: Some("factorial".to_string())
-            })),
-            &ctx.frame
-        );
-    };
-
-    // Send initial progress notification if stream is available
-    log(format!("Starting factorial calculation for {n}!"));
+    // Log notifications are sent by wasmcp_telemetry::scope, filtered by logging/setLevel
+    tracing::info!(target: "factorial", "Starting factorial calculation for {n}!");
 
     // Calculate factorial with progress updates
     let mut result: u64 = 1;
@@ -201,12 +200,14 @@ fn execute_factorial(
 
         // Send progress notification every few steps (to avoid overwhelming)
         if i % 3 == 0 || i == n {
-            log(format!("Computing: {i} * {} = {result}", result / i));
+            tracing::info!(target: "factorial", "Computing: {i} * {} = {result}", result / i);
         }
+
+        // Stream each intermediate factorial as partial content
+        ctx.stream_text(format!("{i}! = {result}\n"));
     }
 
-    // Send completion notification
-    log(format!("Factorial calculation complete: {n}! = {result}"));
+    tracing::info!(target: "factorial", "Factorial calculation complete: {n}! = {result}");
 
     success_result(result.to_string())
 }
@@ -0,0 +1,108 @@
+//! Typed builder for initialize results
+//!
+//! The transport discovers capabilities by probing the handler chain and
+//! fills in server metadata from its configuration. A handler that answers
+//! initialize can override any of it; whatever the builder leaves unset keeps
+//! the transport's value:
+//!
+//! - server info: only replaced when `server_info` is called
+//! - instructions: only replaced when `instructions` is called
+//! - capabilities: each field that is set replaces the discovered one, and
+//!   experimental entries are merged into the discovered ones
+
+use crate::bindings::wasmcp::mcp_v20251125::mcp::{
+    Implementation, InitializeResult, InitializeResultOptions, ProtocolVersion, ServerCapabilities,
+    ServerLists, ServerSubscriptions,
+};
+
+pub struct InitializeResultBuilder {
+    protocol_version: ProtocolVersion,
+    server_info: Option<Implementation>,
+    instructions: Option<String>,
+    capabilities: ServerCapabilities,
+}
+
+impl InitializeResultBuilder {
+    pub fn new(protocol_version: ProtocolVersion) -> Self {
+        InitializeResultBuilder {
+            protocol_version,
+            server_info: None,
+            instructions: None,
+            capabilities: ServerCapabilities {
+                completions: None,
+                experimental: None,
+                logging: None,
+                list_changed: None,
+                subscriptions: None,
+                lists: None,
+            },
+        }
+    }
+
+    pub fn server_info(mut self, name: &str, version: &str) -> Self {
+        self.server_info = Some(Implementation {
+            name: name.to_string(),
+            title: None,
+            version: version.to_string(),
+            description: None,
+            icons: None,
+        });
+        self
+    }
+
+    pub fn instructions(mut self, instructions: &str) -> Self {
+        self.instructions = Some(instructions.to_string());
+        self
+    }
+
+    /// Advertise these lists as offered, replacing the detected set
+    pub fn offer(mut self, lists: ServerLists) -> Self {
+        self.capabilities.lists = Some(lists);
+        self
+    }
+
+    /// Advertise `listChanged` for exactly these lists
+    ///
+    /// Pass `ServerLists::empty()` for servers whose lists never change.
+    pub fn list_changed(mut self, lists: ServerLists) -> Self {
+        self.capabilities.list_changed = Some(lists);
+        self
+    }
+
+    /// Advertise `resources.subscribe`
+    pub fn subscribe_resources(mut self) -> Self {
+        self.capabilities.subscriptions = Some(ServerSubscriptions::RESOURCES);
+        self
+    }
+
+    /// Add an experimental capability; `value` must be a JSON document
+    pub fn experimental(mut self, name: &str, value: &str) -> Self {
+        self.capabilities
+            .experimental
+            .get_or_insert_with(Vec::new)
+            .push((name.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn build(self) -> InitializeResult {
+        InitializeResult {
+            meta: None,
+            // An empty name tells the transport to keep its configured server info
+            server_info: self.server_info.unwrap_or(Implementation {
+                name: String::new(),
+                title: None,
+                version: String::new(),
+                description: None,
+                icons: None,
+            }),
+            capabilities: self.capabilities,
+            protocol_version: self.protocol_version,
+            options: self
+                .instructions
+                .map(|instructions| InitializeResultOptions {
+                    instructions: Some(instructions),
+                    meta: None,
+                }),
+        }
+    }
+}
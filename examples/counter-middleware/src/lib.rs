@@ -10,12 +10,15 @@ mod bindings {
     });
 }
 
+mod initialize;
+
 use bindings::exports::wasmcp::mcp_v20251125::server_handler::{Guest, MessageContext};
 use bindings::wasmcp::keyvalue::store::TypedValue;
 use bindings::wasmcp::mcp_v20251125::mcp::*;
 use bindings::wasmcp::mcp_v20251125::server_handler as downstream;
 use bindings::wasmcp::mcp_v20251125::server_io;
 use bindings::wasmcp::mcp_v20251125::sessions::Session;
+use initialize::InitializeResultBuilder;
 
 struct Counter;
 
@@ -34,6 +37,13 @@ impl Guest for Counter {
                 identity: ctx.identity,
                 frame: ctx.frame,
                 http_context: ctx.http_context,
+                client_capabilities: ctx.client_capabilities,
+                request_id: ctx.request_id,
+                deadline: ctx.deadline,
+                log_level: ctx.log_level,
+                client_info: ctx.client_info,
+                locale: ctx.locale,
+                trace_context: ctx.trace_context,
             };
             return downstream::handle(&downstream_ctx, message);
         };
@@ -45,6 +55,7 @@ impl Guest for Counter {
             ClientRequest::ToolsCall(req) => {
                 Some(handle_call_tool(&ctx, req.clone()).map(ServerResult::ToolsCall))
             }
+            ClientRequest::Initialize(req) => handle_initialize(&ctx, req.clone()),
             // All other requests - delegate to downstream handler
             _ => {
                 let downstream_ctx = downstream::MessageContext {
@@ -54,6 +65,13 @@ impl Guest for Counter {
                     identity: ctx.identity,
                     frame: ctx.frame,
                     http_context: ctx.http_context,
+                    client_capabilities: ctx.client_capabilities,
+                    request_id: ctx.request_id,
+                    deadline: ctx.deadline,
+                    log_level: ctx.log_level,
+                    client_info: ctx.client_info,
+                    locale: ctx.locale,
+                    trace_context: ctx.trace_context,
                 };
                 downstream::handle(&downstream_ctx, message)
             }
@@ -61,6 +79,46 @@ impl Guest for Counter {
     }
 }
 
+fn handle_initialize(
+    ctx: &MessageContext,
+    request: InitializeRequest,
+) -> Option<Result<ServerResult, ErrorCode>> {
+    let downstream_ctx = downstream::MessageContext {
+        client_stream: ctx.client_stream,
+        protocol_version: ctx.protocol_version.clone(),
+        session: ctx.session.clone(),
+        identity: ctx.identity.clone(),
+        frame: ctx.frame.clone(),
+        http_context: ctx.http_context.clone(),
+        client_capabilities: ctx.client_capabilities.clone(),
+        request_id: ctx.request_id.clone(),
+        deadline: ctx.deadline,
+        log_level: ctx.log_level,
+        client_info: ctx.client_info.clone(),
+        locale: ctx.locale.clone(),
+        trace_context: ctx.trace_context.clone(),
+    };
+
+    let protocol_version = request.protocol_version.clone();
+    let downstream_msg = ClientMessage::Request((
+        RequestId::Number(0),
+        ClientRequest::Initialize(request),
+    ));
+
+    match downstream::handle(&downstream_ctx, downstream_msg) {
+        // A downstream handler customized initialize - leave it alone
+        result @ Some(Ok(ServerResult::Initialize(_))) => result,
+        // Keep the transport's metadata and detected capabilities, and
+        // advertise the session-scoped counter
+        _ => {
+            let result = InitializeResultBuilder::new(protocol_version)
+                .experimental("counter", r#"{"tool":"get-count","scope":"session"}"#)
+                .build();
+            Some(Ok(ServerResult::Initialize(result)))
+        }
+    }
+}
+
 fn handle_list_tools(
     ctx: &MessageContext,
     _request: ListToolsRequest,
@@ -93,6 +151,13 @@ fn handle_list_tools(
         identity: ctx.identity.clone(),
         frame: ctx.frame.clone(),
         http_context: ctx.http_context.clone(),
+        client_capabilities: ctx.client_capabilities.clone(),
+        request_id: ctx.request_id.clone(),
+        deadline: ctx.deadline,
+        log_level: ctx.log_level,
+        client_info: ctx.client_info.clone(),
+        locale: ctx.locale.clone(),
+        trace_context: ctx.trace_context.clone(),
     };
 
     let downstream_msg = ClientMessage::Request((
@@ -140,6 +205,13 @@ fn handle_call_tool(
         identity: ctx.identity.clone(),
         frame: ctx.frame.clone(),
         http_context: ctx.http_context.clone(),
+        client_capabilities: ctx.client_capabilities.clone(),
+        request_id: ctx.request_id.clone(),
+        deadline: ctx.deadline,
+        log_level: ctx.log_level,
+        client_info: ctx.client_info.clone(),
+        locale: ctx.locale.clone(),
+        trace_context: ctx.trace_context.clone(),
     };
 
     let downstream_msg = ClientMessage::Request((
@@ -163,7 +235,14 @@ fn handle_call_tool(
 }
 
 /// Send a log notification to the client if a stream is available
+///
+/// Messages below the level the client chose with logging/setLevel are
+/// dropped. The transport keeps that level in the session, so it applies to
+/// every later request, not only the one that set it.
 fn log_notification(ctx: &MessageContext, message: String, level: LogLevel) {
+    if severity(level) < severity(ctx.log_level.unwrap_or(LogLevel::Info)) {
+        return;
+    }
     if let Some(stream) = &ctx.client_stream {
         let notification = ServerNotification::Log(LoggingMessageNotification {
             data: message,
@@ -175,6 +254,19 @@ fn log_notification(ctx: &MessageContext, message: String, level: LogLevel) {
     }
 }
 
+fn severity(level: LogLevel) -> u8 {
+    match level {
+        LogLevel::Debug => 0,
+        LogLevel::Info => 1,
+        LogLevel::Notice => 2,
+        LogLevel::Warning => 3,
+        LogLevel::Error => 4,
+        LogLevel::Critical => 5,
+        LogLevel::Alert => 6,
+        LogLevel::Emergency => 7,
+    }
+}
+
 fn increment_counter(ctx: &MessageContext) {
     let counter_key = "tool_call_count";
 
@@ -0,0 +1,22 @@
+//! Static Resources Capability Provider
+//!
+//! Embeds the `assets/` directory at compile time and serves every Markdown
+//! file in it as an MCP resource, so documentation ships inside the component.
+
+mod bindings {
+    wit_bindgen::generate!({
+        world: "static-resources",
+        generate_all,
+    });
+}
+
+mod static_resources;
+
+use static_resources::static_resources;
+
+static_resources! {
+    provider: DocsBundle,
+    dir: "$CARGO_MANIFEST_DIR/assets",
+    pattern: "**/*.md",
+    scheme: "docs",
+}
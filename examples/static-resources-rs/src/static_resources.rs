@@ -0,0 +1,329 @@
+//! Compile-time embedded resources
+//!
+//! `static_resources!` embeds a directory with `include_dir!` and implements the
+//! resources interface over the files matching a glob pattern:
+//!
+//! - URIs are `<scheme>://<relative path>` (e.g. `docs://guides/intro.md`)
+//! - Names come from the relative path, titles from the file stem
+//! - MIME types are detected from the file extension
+//! - Listing is paginated with signed cursors (`wasmcp-cursor`)
+//! - Reading a directory URI (ending in `/`) returns every file under it
+//!
+//! Files that don't match the pattern are still embedded in the binary but are
+//! never listed or readable.
+
+use crate::bindings::wasmcp::mcp_v20251125::mcp::*;
+use include_dir::{Dir, File};
+use wasmcp_cursor::{Cursor, CursorCodec};
+
+/// Number of resources returned per resources/list page
+const PAGE_SIZE: usize = 50;
+
+/// Key signing cursors when `WASMCP_CURSOR_KEY` is unset
+///
+/// Every instance lists the same embedded files, so a built-in key keeps
+/// cursors valid across instances. A cursor forged with it can only skip
+/// through files any client may list anyway.
+const DEFAULT_CURSOR_KEY: &str = "static-resources";
+
+/// Embed a directory and export it as the resources capability
+///
+/// ```ignore
+/// static_resources! {
+///     provider: DocsBundle,
+///     dir: "$CARGO_MANIFEST_DIR/assets",
+///     pattern: "**/*.md",
+///     scheme: "docs",
+/// }
+/// ```
+macro_rules! static_resources {
+    (
+        provider: $provider:ident,
+        dir: $dir:tt,
+        pattern: $pattern:literal,
+        scheme: $scheme:literal $(,)?
+    ) => {
+        static ASSETS: include_dir::Dir<'static> = include_dir::include_dir!($dir);
+
+        struct $provider;
+
+        impl crate::bindings::exports::wasmcp::mcp_v20251125::resources::Guest for $provider {
+            fn list_resources(
+                _ctx: crate::bindings::wasmcp::mcp_v20251125::server_handler::MessageContext,
+                request: crate::bindings::wasmcp::mcp_v20251125::mcp::ListResourcesRequest,
+            ) -> Result<
+                crate::bindings::wasmcp::mcp_v20251125::mcp::ListResourcesResult,
+                crate::bindings::wasmcp::mcp_v20251125::mcp::ErrorCode,
+            > {
+                crate::static_resources::list(&ASSETS, $pattern, $scheme, request.cursor)
+            }
+
+            fn read_resource(
+                _ctx: crate::bindings::wasmcp::mcp_v20251125::server_handler::MessageContext,
+                request: crate::bindings::wasmcp::mcp_v20251125::mcp::ReadResourceRequest,
+            ) -> Result<
+                Option<crate::bindings::wasmcp::mcp_v20251125::mcp::ReadResourceResult>,
+                crate::bindings::wasmcp::mcp_v20251125::mcp::ErrorCode,
+            > {
+                crate::static_resources::read(&ASSETS, $pattern, $scheme, &request.uri)
+            }
+
+            fn list_resource_templates(
+                _ctx: crate::bindings::wasmcp::mcp_v20251125::server_handler::MessageContext,
+                _request: crate::bindings::wasmcp::mcp_v20251125::mcp::ListResourceTemplatesRequest,
+            ) -> Result<
+                crate::bindings::wasmcp::mcp_v20251125::mcp::ListResourceTemplatesResult,
+                crate::bindings::wasmcp::mcp_v20251125::mcp::ErrorCode,
+            > {
+                // Embedded files are enumerable, no templates needed
+                Ok(crate::bindings::wasmcp::mcp_v20251125::mcp::ListResourceTemplatesResult {
+                    resource_templates: vec![],
+                    next_cursor: None,
+                    meta: None,
+                })
+            }
+        }
+
+        crate::bindings::export!($provider with_types_in crate::bindings);
+    };
+}
+
+pub(crate) use static_resources;
+
+/// List one page of embedded resources
+pub fn list(
+    dir: &'static Dir<'static>,
+    pattern: &str,
+    scheme: &str,
+    cursor: Option<String>,
+) -> Result<ListResourcesResult, ErrorCode> {
+    let files = matching_files(dir, pattern)?;
+
+    let codec = cursor_codec(scheme);
+    let offset = match cursor {
+        Some(c) => {
+            let cursor = codec.decode(&c).map_err(|e| {
+                ErrorCode::InvalidParams(Error {
+                    code: -32602,
+                    message: e.to_string(),
+                    data: None,
+                })
+            })?;
+            usize::try_from(cursor.offset).unwrap_or(usize::MAX)
+        }
+        None => 0,
+    };
+
+    let end = offset.saturating_add(PAGE_SIZE).min(files.len());
+    let resources = files
+        .get(offset..end)
+        .unwrap_or_default()
+        .iter()
+        .map(|file| to_resource(file, scheme))
+        .collect();
+
+    Ok(ListResourcesResult {
+        resources,
+        next_cursor: (end < files.len()).then(|| codec.encode(&Cursor::at(end as u64))),
+        meta: None,
+    })
+}
+
+/// Codec for a provider's cursors, scoped by its URI scheme
+fn cursor_codec(scheme: &str) -> CursorCodec {
+    let key = std::env::var("WASMCP_CURSOR_KEY")
+        .ok()
+        .filter(|key| !key.is_empty())
+        .unwrap_or_else(|| DEFAULT_CURSOR_KEY.to_string());
+    CursorCodec::new(scheme, key.as_bytes())
+}
+
+/// Read an embedded resource by URI
+///
+/// A URI ending in `/` (e.g. `docs://guides/`, or `docs://` for everything)
+/// reads the directory: every matching file under it is returned as its own
+/// contents entry, in path order.
+///
+/// Returns Ok(None) for URIs outside this provider's scheme or pattern, so
+/// other resource providers in the chain get a chance to handle them.
+pub fn read(
+    dir: &'static Dir<'static>,
+    pattern: &str,
+    scheme: &str,
+    uri: &str,
+) -> Result<Option<ReadResourceResult>, ErrorCode> {
+    let Some(path) = uri.strip_prefix(&format!("{}://", scheme)) else {
+        return Ok(None);
+    };
+
+    let is_dir = path.is_empty() || path.ends_with('/');
+    let builder = matching_files(dir, pattern)?
+        .into_iter()
+        .filter(|file| {
+            let file_path = relative_path(file);
+            if is_dir {
+                file_path.starts_with(path)
+            } else {
+                file_path == path
+            }
+        })
+        .fold(ResourceReadBuilder::new(), |builder, file| {
+            builder.file(&format!("{}://{}", scheme, relative_path(file)), file)
+        });
+
+    if builder.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(builder.build()))
+}
+
+/// Builds a resources/read result with one or more contents entries
+///
+/// A single read may return several entries, e.g. every file in a directory,
+/// or a text rendering next to the original blob. Each entry carries its own
+/// URI and MIME type.
+#[derive(Default)]
+pub struct ResourceReadBuilder {
+    contents: Vec<ResourceContents>,
+}
+
+impl ResourceReadBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn text(mut self, uri: &str, text: &str, mime_type: &str) -> Self {
+        self.contents
+            .push(ResourceContents::Text(TextResourceContents {
+                uri: uri.to_string(),
+                text: TextData::Text(text.to_string()),
+                options: contents_options(mime_type),
+            }));
+        self
+    }
+
+    pub fn blob(mut self, uri: &str, bytes: &[u8], mime_type: &str) -> Self {
+        self.contents
+            .push(ResourceContents::Blob(BlobResourceContents {
+                uri: uri.to_string(),
+                blob: BlobData::Blob(bytes.to_vec()),
+                options: contents_options(mime_type),
+            }));
+        self
+    }
+
+    /// Add an embedded file as text or blob, depending on its MIME type
+    pub fn file(self, uri: &str, file: &File) -> Self {
+        let mime_type = mime_type(&relative_path(file));
+        match file.contents_utf8().filter(|_| is_text(mime_type)) {
+            Some(text) => self.text(uri, text, mime_type),
+            None => self.blob(uri, file.contents(), mime_type),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.contents.is_empty()
+    }
+
+    pub fn build(self) -> ReadResourceResult {
+        ReadResourceResult {
+            contents: self.contents,
+            meta: None,
+        }
+    }
+}
+
+fn contents_options(mime_type: &str) -> Option<EmbeddedResourceOptions> {
+    Some(EmbeddedResourceOptions {
+        mime_type: Some(mime_type.to_string()),
+        meta: None,
+    })
+}
+
+/// Embedded files matching the glob pattern, sorted by path
+fn matching_files(
+    dir: &'static Dir<'static>,
+    pattern: &str,
+) -> Result<Vec<&'static File<'static>>, ErrorCode> {
+    let entries = dir.find(pattern).map_err(|e| {
+        ErrorCode::InternalError(Error {
+            code: -32603,
+            message: format!("Invalid static resource pattern '{}': {}", pattern, e),
+            data: None,
+        })
+    })?;
+
+    let mut files: Vec<&'static File<'static>> = entries.filter_map(|e| e.as_file()).collect();
+    files.sort_by_key(|file| file.path());
+    Ok(files)
+}
+
+fn to_resource(file: &File, scheme: &str) -> McpResource {
+    let path = relative_path(file);
+    let title = file
+        .path()
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string());
+
+    McpResource {
+        uri: format!("{}://{}", scheme, path),
+        name: path.clone(),
+        options: Some(ResourceOptions {
+            size: Some(file.contents().len() as u64),
+            title,
+            description: None,
+            mime_type: Some(mime_type(&path).to_string()),
+            annotations: None,
+            meta: None,
+            icons: None,
+        }),
+    }
+}
+
+/// Path relative to the embedded directory, always '/'-separated
+fn relative_path(file: &File) -> String {
+    file.path()
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Detect MIME type from file extension
+fn mime_type(path: &str) -> &'static str {
+    let extension = path
+        .rsplit_once('.')
+        .map(|(_, ext)| ext.to_ascii_lowercase());
+    match extension.as_deref() {
+        Some("md" | "markdown") => "text/markdown",
+        Some("txt") => "text/plain",
+        Some("html" | "htm") => "text/html",
+        Some("css") => "text/css",
+        Some("csv") => "text/csv",
+        Some("js") => "text/javascript",
+        Some("json") => "application/json",
+        Some("yaml" | "yml") => "application/yaml",
+        Some("toml") => "application/toml",
+        Some("xml") => "application/xml",
+        Some("svg") => "image/svg+xml",
+        Some("png") => "image/png",
+        Some("jpg" | "jpeg") => "image/jpeg",
+        Some("gif") => "image/gif",
+        Some("webp") => "image/webp",
+        Some("pdf") => "application/pdf",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Whether a MIME type should be served as text contents
+fn is_text(mime_type: &str) -> bool {
+    mime_type.starts_with("text/")
+        || matches!(
+            mime_type,
+            "application/json"
+                | "application/yaml"
+                | "application/toml"
+                | "application/xml"
+                | "image/svg+xml"
+        )
+}
@@ -62,6 +62,10 @@ class StringsTools(exports.Tools):
             return error_result("Missing tool arguments")
 
         def log(message):
+            # Respect the level the client chose with logging/setLevel
+            min_level = ctx.log_level if ctx.log_level is not None else mcp.LogLevel.INFO
+            if mcp.LogLevel.INFO.value < min_level.value:
+                return
             if ctx.client_stream is not None:
                 notification = mcp.ServerNotification_Log(value=mcp.LoggingMessageNotification(
                     data=message, level=mcp.LogLevel.INFO, logger="python-tools"
@@ -31,6 +31,9 @@ use bindings::wasmcp::mcp_v20251125::server_handler::MessageContext;
 use bindings::wasmcp::mcp_v20251125::sessions::Session;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use wasmcp_claims::Claims;
+
+wasmcp_claims::impl_claims!(JwtClaims);
 
 struct TodoListAuth;
 
@@ -216,16 +219,11 @@ fn check_scope(claims: Option<&JwtClaims>, required_scope: &str) -> bool {
     }
 }
 
-/// Check if JWT claims contain a specific role (custom claim)
+/// Check if JWT claims contain a specific role (custom `roles` or `role` claim)
 fn check_role(claims: Option<&JwtClaims>, required_role: &str) -> bool {
     match claims {
-        Some(c) => {
-            // Use get-claim helper to access custom 'role' claim
-            match bindings::wasmcp::auth::helpers::get_claim(c, "role") {
-                Some(role) => role == required_role,
-                None => false,
-            }
-        }
+        // Roles may be a JSON array or a space/comma separated string
+        Some(c) => Claims::from(c).has_role(required_role),
         None => false,
     }
 }
@@ -235,12 +233,9 @@ fn check_role(claims: Option<&JwtClaims>, required_role: &str) -> bool {
 fn check_tool_allowed(claims: Option<&JwtClaims>, tool_name: &str) -> bool {
     match claims {
         Some(c) => {
-            // Use get-claim helper to access custom 'allowed_tools' claim
-            match bindings::wasmcp::auth::helpers::get_claim(c, "allowed_tools") {
-                Some(allowed) => {
-                    // Parse comma-separated list
-                    allowed.split(',').any(|t| t.trim() == tool_name)
-                }
+            let claims = Claims::from(c);
+            match claims.claim("allowed_tools") {
+                Some(_) => claims.list("allowed_tools").iter().any(|t| t == tool_name),
                 None => true, // No allowed_tools claim means allow all
             }
         }
@@ -16,9 +16,9 @@ import type {
   ServerNotification,
   LoggingMessageNotification,
   LogLevel,
-} from 'wasmcp:mcp-v20251125/mcp@0.1.1';
-import type { MessageContext } from 'wasmcp:mcp-v20251125/server-handler@0.1.1';
-import { sendMessage } from 'wasmcp:mcp-v20251125/server-io@0.1.1';
+} from 'wasmcp:mcp-v20251125/mcp@0.2.0';
+import type { MessageContext } from 'wasmcp:mcp-v20251125/server-handler@0.2.0';
+import { sendMessage } from 'wasmcp:mcp-v20251125/server-io@0.2.0';
 
 // Tool input schemas
 const GetWeatherSchema = z.object({
@@ -69,11 +69,28 @@ function listTools(
   return { tools };
 }
 
+/** Log levels from least to most severe */
+const LOG_LEVELS: LogLevel[] = [
+  'debug',
+  'info',
+  'notice',
+  'warning',
+  'error',
+  'critical',
+  'alert',
+  'emergency',
+];
+
 async function callTool(
   ctx: MessageContext,
   request: CallToolRequest
 ): Promise<CallToolResult | undefined> {
   const log = (message: string) => {
+    // Respect the level the client chose with logging/setLevel
+    const minLevel = ctx.logLevel ?? 'info';
+    if (LOG_LEVELS.indexOf('info') < LOG_LEVELS.indexOf(minLevel)) {
+      return;
+    }
     if (ctx.clientStream) {
       const notification: ServerNotification = {
         tag: 'log',
@@ -6,6 +6,7 @@ mod build;
 mod compose;
 mod deps;
 mod release;
+mod replay;
 mod util;
 
 #[derive(Parser)]
@@ -60,6 +61,16 @@ enum Commands {
         #[arg(short, long, default_value = ".agent/composed.wasm")]
         wasm: PathBuf,
     },
+    /// Replay recorded exchanges (WASMCP_RECORD_DIR) through the composed component
+    Replay {
+        /// Recording files or directories
+        #[arg(required = true)]
+        recordings: Vec<PathBuf>,
+
+        /// Path to the composed wasm
+        #[arg(short, long, default_value = ".agent/composed.wasm")]
+        wasm: PathBuf,
+    },
     /// Check versions and trigger releases
     Release {
         #[command(subcommand)]
@@ -219,6 +230,9 @@ fn main() -> Result<()> {
         Commands::Run { runtime, wasm } => {
             compose::run_component(runtime, &wasm)?;
         }
+        Commands::Replay { recordings, wasm } => {
+            replay::replay(&recordings, &wasm)?;
+        }
         Commands::Release { action } => match action {
             ReleaseAction::Check { component } => {
                 release::check_versions(component)?;
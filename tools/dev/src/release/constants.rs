@@ -1,12 +1,35 @@
 pub const COMPONENTS: &[&str] = &[
+    "arg-preprocess",
     "authorization",
+    "custom-middleware",
+    "fake-auth",
     "filter-middleware",
+    "fs-resources",
+    "glob-filter",
+    "graphql-tools",
+    "hmac-auth",
+    "http-resources",
+    "introspection",
+    "kv-admin",
     "kv-store",
+    "localization",
+    "log-bridge",
+    "maintenance",
+    "messaging-transport",
     "method-not-found",
+    "openapi-tools",
     "prompts-middleware",
     "resources-middleware",
     "server-io",
     "session-store",
+    "sql-tools",
+    "stdout-guard",
+    "summarize",
+    "tool-cache",
+    "tool-completions",
+    "tool-deprecation",
+    "tool-jobs",
+    "tool-stats",
     "tools-middleware",
     "transport",
 ];
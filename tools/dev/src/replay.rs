@@ -0,0 +1,189 @@
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// A message recorded by server-io (`WASMCP_RECORD_DIR`)
+struct Recorded {
+    direction: String,
+    message: Value,
+}
+
+/// Replay recorded exchanges through a composed server over stdio
+///
+/// Requests are sent in recording order (file names are time-ordered) to
+/// `wasmtime run`, and each response is compared with the recorded one by
+/// JSON-RPC id. Redacted arguments are sent as the `[REDACTED]` placeholder.
+pub fn replay(recordings: &[PathBuf], wasm: &Path) -> Result<()> {
+    let files = collect_files(recordings)?;
+    if files.is_empty() {
+        anyhow::bail!("No recordings (*.jsonl) found");
+    }
+
+    let mut messages = Vec::new();
+    for file in &files {
+        let content = std::fs::read_to_string(file)
+            .with_context(|| format!("Failed to read {}", file.display()))?;
+        messages.extend(
+            parse_recording(&content).with_context(|| format!("Invalid {}", file.display()))?,
+        );
+    }
+
+    let requests: Vec<&Value> = messages
+        .iter()
+        .filter(|m| m.direction == "request")
+        .map(|m| &m.message)
+        .collect();
+    let recorded = responses_by_id(
+        messages
+            .iter()
+            .filter(|m| m.direction == "response")
+            .map(|m| &m.message),
+    );
+
+    println!(
+        "Replaying {} messages from {} recordings through {}...",
+        requests.len(),
+        files.len(),
+        wasm.display()
+    );
+
+    let output = run_stdio(wasm, &requests)?;
+    let replayed = responses_by_id(
+        output
+            .lines()
+            .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+            .collect::<Vec<_>>()
+            .iter(),
+    );
+
+    let mut differences = 0;
+    for request in &requests {
+        let Some(id) = request.get("id") else {
+            continue;
+        };
+        let method = request.get("method").and_then(Value::as_str).unwrap_or("?");
+        let key = id.to_string();
+
+        match (recorded.get(&key), replayed.get(&key)) {
+            (Some(before), Some(after)) if before == after => {
+                println!("  ✓ {} {}", key, method);
+            }
+            (before, after) => {
+                differences += 1;
+                println!("  ✗ {} {}", key, method);
+                println!("    recorded: {}", describe(before));
+                println!("    replayed: {}", describe(after));
+            }
+        }
+    }
+
+    if differences > 0 {
+        anyhow::bail!("{} responses differ from the recording", differences);
+    }
+    println!("All responses match the recording");
+    Ok(())
+}
+
+/// Recording files, with directories expanded to their `*.jsonl` files
+fn collect_files(paths: &[PathBuf]) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            for entry in std::fs::read_dir(path)
+                .with_context(|| format!("Failed to read {}", path.display()))?
+            {
+                let file = entry?.path();
+                if file.extension().is_some_and(|ext| ext == "jsonl") {
+                    files.push(file);
+                }
+            }
+        } else {
+            files.push(path.clone());
+        }
+    }
+    files.sort_by(|a, b| a.file_name().cmp(&b.file_name()));
+    Ok(files)
+}
+
+fn parse_recording(content: &str) -> Result<Vec<Recorded>> {
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let mut entry: Value = serde_json::from_str(line)?;
+            let direction = entry
+                .get("direction")
+                .and_then(Value::as_str)
+                .context("Missing direction")?
+                .to_string();
+            let message = entry.get_mut("message").context("Missing message")?.take();
+            Ok(Recorded { direction, message })
+        })
+        .collect()
+}
+
+/// Final responses (results and errors) keyed by their JSON-RPC id
+fn responses_by_id<'a>(messages: impl Iterator<Item = &'a Value>) -> HashMap<String, Value> {
+    messages
+        .filter(|m| m.get("result").is_some() || m.get("error").is_some())
+        .filter_map(|m| Some((m.get("id")?.to_string(), m.clone())))
+        .collect()
+}
+
+/// Send the requests to the server's stdio transport and collect its output
+fn run_stdio(wasm: &Path, requests: &[&Value]) -> Result<String> {
+    let mut child = Command::new("wasmtime")
+        .arg("run")
+        .arg(wasm)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .context("Failed to run wasmtime")?;
+
+    {
+        let mut stdin = child.stdin.take().context("Failed to open stdin")?;
+        for request in requests {
+            writeln!(stdin, "{}", serde_json::to_string(request)?)?;
+        }
+        // Dropping stdin closes it, so the transport exits after the last message
+    }
+
+    let output = child.wait_with_output().context("wasmtime failed")?;
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn describe(response: Option<&Value>) -> String {
+    response.map_or_else(|| "(no response)".to_string(), Value::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_recording() {
+        let content = r#"{"direction":"request","message":{"jsonrpc":"2.0","id":1,"method":"ping"}}
+{"direction":"response","message":{"jsonrpc":"2.0","id":1,"result":{}}}
+"#;
+        let messages = parse_recording(content).unwrap();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].direction, "request");
+        assert_eq!(messages[1].message["result"], serde_json::json!({}));
+    }
+
+    #[test]
+    fn test_responses_by_id_skips_notifications() {
+        let messages = [
+            serde_json::json!({"jsonrpc":"2.0","method":"notifications/progress","params":{}}),
+            serde_json::json!({"jsonrpc":"2.0","id":"a","error":{"code":-32601,"message":"x"}}),
+            serde_json::json!({"jsonrpc":"2.0","id":2,"result":{}}),
+        ];
+        let responses = responses_by_id(messages.iter());
+        assert_eq!(responses.len(), 2);
+        assert!(responses.contains_key("\"a\""));
+        assert!(responses.contains_key("2"));
+    }
+}